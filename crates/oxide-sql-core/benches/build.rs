@@ -0,0 +1,86 @@
+//! Builder rendering benchmarks, `build()` against `build_into()`.
+//!
+//! Run with `cargo bench -p oxide-sql-core --bench build`. The builders
+//! are constructed outside the timed section, so each case measures
+//! rendering only. The `build_into()` cases reuse one buffer across
+//! iterations, as a caller batching statements would.
+
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use oxide_sql_core::builder::{InsertDyn, SelectDyn, SqlValue, dyn_col};
+
+const ROWS: usize = 1000;
+
+fn bench_insert(c: &mut Criterion) {
+    // An INSERT with `ROWS` rows of three values each.
+    let large_insert = || {
+        InsertDyn::new()
+            .into_table("events")
+            .columns(&["id", "kind", "payload"])
+            .values_many(
+                (0..ROWS)
+                    .map(|i| {
+                        vec![
+                            SqlValue::Int(i64::try_from(i).unwrap()),
+                            SqlValue::from("click"),
+                            SqlValue::Null,
+                        ]
+                    })
+                    .collect(),
+            )
+    };
+
+    let mut group = c.benchmark_group("insert 1000 rows");
+    group.bench_function("build", |b| {
+        b.iter_batched(large_insert, |insert| insert.build(), BatchSize::SmallInput);
+    });
+    let mut sql = String::new();
+    group.bench_function("build_into", |b| {
+        b.iter_batched(
+            large_insert,
+            |insert| {
+                sql.clear();
+                black_box(insert.build_into(&mut sql))
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+fn bench_select(c: &mut Criterion) {
+    let select = || {
+        SelectDyn::new()
+            .columns(&["id", "name", "email", "created_at"])
+            .from("users AS u")
+            .where_clause(
+                dyn_col("u.active")
+                    .eq(true)
+                    .and(dyn_col("u.created_at").gt(1_700_000_000))
+                    .and(dyn_col("u.name").like("a%")),
+            )
+            .order_by_desc(&["created_at"])
+            .limit(50)
+    };
+
+    let mut group = c.benchmark_group("select");
+    group.bench_function("build", |b| {
+        b.iter_batched(select, |select| select.build(), BatchSize::SmallInput);
+    });
+    let mut sql = String::new();
+    group.bench_function("build_into", |b| {
+        b.iter_batched(
+            select,
+            |select| {
+                sql.clear();
+                black_box(select.build_into(&mut sql))
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_select);
+criterion_main!(benches);
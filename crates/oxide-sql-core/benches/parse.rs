@@ -0,0 +1,107 @@
+//! Parser benchmarks, with and without a `NodePool`.
+//!
+//! Run with `cargo bench -p oxide-sql-core --bench parse`. The pooled
+//! and unpooled parsers are checked to agree before timing.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use oxide_sql_core::Parser;
+use oxide_sql_core::ast::Statement;
+use oxide_sql_core::parser::NodePool;
+
+/// A SELECT whose WHERE clause joins `n` predicates with AND.
+fn wide_predicate(n: usize) -> String {
+    let predicates: Vec<String> = (0..n)
+        .map(|i| {
+            format!(
+                "(c{i} + {i}) * 2 BETWEEN {i} AND {} OR c{i} IN (1, 2, 3)",
+                i + 10
+            )
+        })
+        .collect();
+    format!("SELECT * FROM t WHERE {}", predicates.join(" AND "))
+}
+
+/// `n` statements of the kind found in a migration or query log.
+fn statement_log(n: usize) -> Vec<String> {
+    (0..n)
+        .map(|i| match i % 4 {
+            0 => format!(
+                "SELECT a, b + {i}, CASE WHEN c > {i} THEN 'x' ELSE 'y' END FROM t{i} \
+                 WHERE a = ? AND b BETWEEN 1 AND {i} ORDER BY a DESC LIMIT 10"
+            ),
+            1 => format!("INSERT INTO t{i} (a, b, c) VALUES ({i}, 'v', -{i}), (1, 2, 3)"),
+            2 => format!("UPDATE t{i} SET a = a + 1, b = NOT b WHERE id IN (1, 2, {i})"),
+            _ => format!("DELETE FROM t{i} WHERE created < {i} AND (kind = 1 OR kind = 2)"),
+        })
+        .collect()
+}
+
+fn parse(sql: &str) -> Statement {
+    Parser::new(sql).parse_statement().unwrap()
+}
+
+fn parse_pooled(sql: &str, pool: NodePool) -> (Statement, NodePool) {
+    let mut parser = Parser::new(sql).with_pool(pool);
+    let stmt = parser.parse_statement().unwrap();
+    (stmt, parser.into_pool())
+}
+
+/// Checks that pooled parsing gives the same statements as unpooled.
+fn check_pooled<'a>(sqls: impl IntoIterator<Item = &'a String>) {
+    let mut pool = NodePool::new();
+    for sql in sqls {
+        let (stmt, rest) = parse_pooled(sql, pool);
+        assert_eq!(stmt, parse(sql), "pooled parse differs for {sql}");
+        pool = rest;
+        pool.reclaim(stmt);
+    }
+}
+
+fn bench_wide_predicate(c: &mut Criterion) {
+    let wide = wide_predicate(500);
+    check_pooled([&wide]);
+
+    let mut group = c.benchmark_group("wide predicate");
+    group.bench_function("unpooled", |b| {
+        b.iter(|| parse(black_box(&wide)));
+    });
+    let mut pool = Some(NodePool::new());
+    group.bench_function("pooled", |b| {
+        b.iter(|| {
+            let (stmt, mut rest) = parse_pooled(black_box(&wide), pool.take().unwrap());
+            rest.reclaim(black_box(stmt));
+            pool = Some(rest);
+        });
+    });
+    group.finish();
+}
+
+fn bench_statement_log(c: &mut Criterion) {
+    let log = statement_log(1000);
+    check_pooled(&log);
+
+    let mut group = c.benchmark_group("statement log");
+    group.bench_function("unpooled", |b| {
+        b.iter(|| {
+            for sql in &log {
+                black_box(parse(black_box(sql)));
+            }
+        });
+    });
+    let mut pool = Some(NodePool::new());
+    group.bench_function("pooled", |b| {
+        b.iter(|| {
+            for sql in &log {
+                let (stmt, mut rest) = parse_pooled(black_box(sql), pool.take().unwrap());
+                rest.reclaim(black_box(stmt));
+                pool = Some(rest);
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_wide_predicate, bench_statement_log);
+criterion_main!(benches);
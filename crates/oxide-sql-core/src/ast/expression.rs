@@ -2,6 +2,8 @@
 
 use core::fmt;
 
+use super::ident::Ident;
+use super::statement::OrderBy;
 use crate::lexer::Span;
 
 /// A literal value.
@@ -11,6 +13,9 @@ pub enum Literal {
     Integer(i64),
     /// Float literal.
     Float(f64),
+    /// Numeric literal too large for `Integer` or `Float`, kept as its
+    /// decimal text.
+    BigNumber(String),
     /// String literal.
     String(String),
     /// Blob literal.
@@ -19,6 +24,49 @@ pub enum Literal {
     Boolean(bool),
     /// NULL literal.
     Null,
+    /// `DATE '2024-01-01'` literal, holding the unquoted text.
+    Date(String),
+    /// `TIME '10:00'` literal, holding the unquoted text.
+    Time(String),
+    /// `TIMESTAMP '2024-01-01 10:00:00'` literal, holding the unquoted text.
+    Timestamp(String),
+    /// `INTERVAL '1 day'` literal, holding the unquoted text.
+    Interval(String),
+}
+
+impl Literal {
+    /// Returns the type name and text of a `DATE`, `TIME`, `TIMESTAMP`, or
+    /// `INTERVAL` literal, or `None` for other literals.
+    #[must_use]
+    pub fn temporal(&self) -> Option<(&'static str, &str)> {
+        match self {
+            Self::Date(s) => Some(("DATE", s)),
+            Self::Time(s) => Some(("TIME", s)),
+            Self::Timestamp(s) => Some(("TIMESTAMP", s)),
+            Self::Interval(s) => Some(("INTERVAL", s)),
+            _ => None,
+        }
+    }
+}
+
+/// The quantifier of a [`Expr::Quantified`] comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    /// `ANY` (or its synonym `SOME`): true if any element matches.
+    Any,
+    /// `ALL`: true if every element matches.
+    All,
+}
+
+impl Quantifier {
+    /// Returns the SQL keyword.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "ANY",
+            Self::All => "ALL",
+        }
+    }
 }
 
 /// Binary operators.
@@ -52,6 +100,16 @@ pub enum BinaryOp {
     BitOr,
     LeftShift,
     RightShift,
+
+    // JSON
+    /// `->`: a JSON field or element as JSON.
+    JsonGet,
+    /// `->>`: a JSON field or element as text.
+    JsonGetText,
+    /// `#>`: the JSON value at a path, as JSON.
+    JsonPath,
+    /// `#>>`: the JSON value at a path, as text.
+    JsonPathText,
 }
 
 impl BinaryOp {
@@ -78,9 +136,22 @@ impl BinaryOp {
             Self::BitOr => "|",
             Self::LeftShift => "<<",
             Self::RightShift => ">>",
+            Self::JsonGet => "->",
+            Self::JsonGetText => "->>",
+            Self::JsonPath => "#>",
+            Self::JsonPathText => "#>>",
         }
     }
 
+    /// Returns `true` for `=`, `<>`, `<`, `<=`, `>`, and `>=`.
+    #[must_use]
+    pub const fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Self::Eq | Self::NotEq | Self::Lt | Self::LtEq | Self::Gt | Self::GtEq
+        )
+    }
+
     /// Returns the precedence of the operator (higher = binds tighter).
     #[must_use]
     pub const fn precedence(&self) -> u8 {
@@ -94,6 +165,7 @@ impl BinaryOp {
             Self::LeftShift | Self::RightShift => 7,
             Self::Add | Self::Sub | Self::Concat => 8,
             Self::Mul | Self::Div | Self::Mod => 9,
+            Self::JsonGet | Self::JsonGetText | Self::JsonPath | Self::JsonPathText => 10,
         }
     }
 }
@@ -102,11 +174,18 @@ impl fmt::Display for Literal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Integer(n) => write!(f, "{n}"),
-            Self::Float(v) => write!(f, "{v}"),
+            // `Debug` keeps a fractional part or exponent, so the value
+            // reads back as a float.
+            Self::Float(v) => write!(f, "{v:?}"),
+            Self::BigNumber(digits) => f.write_str(digits),
             Self::String(s) => {
                 let escaped = s.replace('\'', "''");
                 write!(f, "'{escaped}'")
             }
+            Self::Date(s) => write!(f, "DATE '{}'", s.replace('\'', "''")),
+            Self::Time(s) => write!(f, "TIME '{}'", s.replace('\'', "''")),
+            Self::Timestamp(s) => write!(f, "TIMESTAMP '{}'", s.replace('\'', "''")),
+            Self::Interval(s) => write!(f, "INTERVAL '{}'", s.replace('\'', "''")),
             Self::Blob(bytes) => {
                 write!(f, "X'")?;
                 for b in bytes {
@@ -165,6 +244,25 @@ pub struct FunctionCall {
     pub args: Vec<Expr>,
     /// Whether DISTINCT was specified.
     pub distinct: bool,
+    /// The ordering of an ordered-set aggregate
+    /// (`WITHIN GROUP (ORDER BY ...)`), empty if absent.
+    pub within_group: Vec<OrderBy>,
+    /// The aggregate filter (`FILTER (WHERE ...)`).
+    pub filter: Option<Box<Expr>>,
+}
+
+impl FunctionCall {
+    /// Creates a call to `name` with the given arguments and no modifiers.
+    #[must_use]
+    pub fn new(name: impl Into<String>, args: Vec<Expr>) -> Self {
+        Self {
+            name: name.into(),
+            args,
+            distinct: false,
+            within_group: vec![],
+            filter: None,
+        }
+    }
 }
 
 /// An SQL expression.
@@ -176,9 +274,9 @@ pub enum Expr {
     /// A column reference (optionally qualified with table name).
     Column {
         /// Table name or alias (optional).
-        table: Option<String>,
+        table: Option<Ident>,
         /// Column name.
-        name: String,
+        name: Ident,
         /// Source span.
         span: Span,
     },
@@ -207,6 +305,10 @@ pub enum Expr {
     /// A subquery.
     Subquery(Box<super::SelectStatement>),
 
+    /// An `EXISTS (subquery)` predicate; `NOT EXISTS` is a [`UnaryOp::Not`]
+    /// around it.
+    Exists(Box<super::SelectStatement>),
+
     /// IS NULL expression.
     IsNull {
         /// The expression to check.
@@ -258,6 +360,30 @@ pub enum Expr {
     /// Parenthesized expression.
     Paren(Box<Expr>),
 
+    /// COLLATE expression (`name COLLATE NOCASE`).
+    Collate {
+        /// The expression whose collation is set.
+        expr: Box<Expr>,
+        /// The collation name, quoted as written.
+        collation: Ident,
+    },
+
+    /// Array constructor (`ARRAY[1, 2, 3]`).
+    Array(Vec<Expr>),
+
+    /// Comparison against every element of an array or subquery
+    /// (`x = ANY(...)`, `x > ALL(...)`).
+    Quantified {
+        /// Left operand.
+        left: Box<Expr>,
+        /// Comparison operator.
+        op: BinaryOp,
+        /// `ANY` or `ALL`.
+        quantifier: Quantifier,
+        /// The array or subquery inside the parentheses.
+        right: Box<Expr>,
+    },
+
     /// A parameter placeholder (? or :name).
     Parameter {
         /// The parameter index or name.
@@ -269,21 +395,12 @@ pub enum Expr {
     /// Wildcard (*) in SELECT.
     Wildcard {
         /// Table qualifier (optional).
-        table: Option<String>,
+        table: Option<Ident>,
     },
 }
 
 impl fmt::Display for FunctionCall {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // EXISTS gets special handling: the subquery already
-        // contains its own parentheses in the rendered form,
-        // so we render `EXISTS(SELECT ...)` instead of
-        // `EXISTS((SELECT ...))`.
-        if self.name == "EXISTS" {
-            if let [Expr::Subquery(q)] = self.args.as_slice() {
-                return write!(f, "EXISTS({q})");
-            }
-        }
         write!(f, "{}(", self.name)?;
         if self.distinct {
             write!(f, "DISTINCT ")?;
@@ -294,7 +411,21 @@ impl fmt::Display for FunctionCall {
             }
             write!(f, "{arg}")?;
         }
-        write!(f, ")")
+        write!(f, ")")?;
+        if !self.within_group.is_empty() {
+            write!(f, " WITHIN GROUP (ORDER BY ")?;
+            for (i, order) in self.within_group.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{order}")?;
+            }
+            write!(f, ")")?;
+        }
+        if let Some(filter) = &self.filter {
+            write!(f, " FILTER (WHERE {filter})")?;
+        }
+        Ok(())
     }
 }
 
@@ -314,11 +445,14 @@ impl fmt::Display for Expr {
             }
             Self::Unary { op, operand } => match op {
                 UnaryOp::Not => write!(f, "NOT {operand}"),
+                // `--` would start a comment.
+                UnaryOp::Neg if operand.starts_with_minus() => write!(f, "- {operand}"),
                 UnaryOp::Neg => write!(f, "-{operand}"),
                 UnaryOp::BitNot => write!(f, "~{operand}"),
             },
             Self::Function(func) => write!(f, "{func}"),
             Self::Subquery(q) => write!(f, "({q})"),
+            Self::Exists(q) => write!(f, "EXISTS({q})"),
             Self::IsNull { expr, negated } => {
                 if *negated {
                     write!(f, "{expr} IS NOT NULL")
@@ -378,6 +512,31 @@ impl fmt::Display for Expr {
                 write!(f, "CAST({expr} AS {data_type})")
             }
             Self::Paren(inner) => write!(f, "({inner})"),
+            Self::Collate { expr, collation } => write!(f, "{expr} COLLATE {collation}"),
+            Self::Array(elements) => {
+                write!(f, "ARRAY[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Quantified {
+                left,
+                op,
+                quantifier,
+                right,
+            } => {
+                let quantifier = quantifier.as_str();
+                // A subquery renders its own parentheses.
+                if matches!(right.as_ref(), Self::Subquery(_)) {
+                    write!(f, "{left} {op} {quantifier}{right}")
+                } else {
+                    write!(f, "{left} {op} {quantifier}({right})")
+                }
+            }
             Self::Parameter { name, .. } => {
                 if let Some(n) = name {
                     write!(f, ":{n}")
@@ -397,9 +556,27 @@ impl fmt::Display for Expr {
 }
 
 impl Expr {
+    /// Returns `true` if the rendered expression begins with `-`.
+    pub(crate) fn starts_with_minus(&self) -> bool {
+        match self {
+            Self::Unary {
+                op: UnaryOp::Neg, ..
+            } => true,
+            Self::Literal(Literal::Integer(n)) => *n < 0,
+            Self::Literal(Literal::Float(v)) => v.is_sign_negative(),
+            Self::Binary { left: expr, .. }
+            | Self::Quantified { left: expr, .. }
+            | Self::Collate { expr, .. }
+            | Self::IsNull { expr, .. }
+            | Self::In { expr, .. }
+            | Self::Between { expr, .. } => expr.starts_with_minus(),
+            _ => false,
+        }
+    }
+
     /// Creates a new column reference.
     #[must_use]
-    pub fn column(name: impl Into<String>) -> Self {
+    pub fn column(name: impl Into<Ident>) -> Self {
         Self::Column {
             table: None,
             name: name.into(),
@@ -409,7 +586,7 @@ impl Expr {
 
     /// Creates a new qualified column reference.
     #[must_use]
-    pub fn qualified_column(table: impl Into<String>, name: impl Into<String>) -> Self {
+    pub fn qualified_column(table: impl Into<Ident>, name: impl Into<Ident>) -> Self {
         Self::Column {
             table: Some(table.into()),
             name: name.into(),
@@ -435,6 +612,30 @@ impl Expr {
         Self::Literal(Literal::String(value.into()))
     }
 
+    /// Creates a `DATE '...'` literal.
+    #[must_use]
+    pub fn date(value: impl Into<String>) -> Self {
+        Self::Literal(Literal::Date(value.into()))
+    }
+
+    /// Creates a `TIME '...'` literal.
+    #[must_use]
+    pub fn time(value: impl Into<String>) -> Self {
+        Self::Literal(Literal::Time(value.into()))
+    }
+
+    /// Creates a `TIMESTAMP '...'` literal.
+    #[must_use]
+    pub fn timestamp(value: impl Into<String>) -> Self {
+        Self::Literal(Literal::Timestamp(value.into()))
+    }
+
+    /// Creates an `INTERVAL '...'` literal.
+    #[must_use]
+    pub fn interval(value: impl Into<String>) -> Self {
+        Self::Literal(Literal::Interval(value.into()))
+    }
+
     /// Creates a new boolean literal.
     #[must_use]
     pub const fn boolean(value: bool) -> Self {
@@ -564,6 +765,15 @@ impl Expr {
             negated: true,
         }
     }
+
+    /// Creates a COLLATE expression.
+    #[must_use]
+    pub fn collate(self, collation: impl Into<Ident>) -> Self {
+        Self::Collate {
+            expr: Box::new(self),
+            collation: collation.into(),
+        }
+    }
 }
 
 #[cfg(test)]
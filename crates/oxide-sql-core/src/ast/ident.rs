@@ -0,0 +1,260 @@
+//! SQL identifiers.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::dialect::Dialect;
+
+/// How an identifier was quoted in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteStyle {
+    /// `"name"`, the SQL standard.
+    Double,
+    /// `` `name` ``, as in MySQL and SQLite.
+    Backtick,
+    /// `[name]`, as in SQL Server and SQLite.
+    Bracket,
+}
+
+impl QuoteStyle {
+    /// Returns the quote style that starts with `c`, if any.
+    #[must_use]
+    pub const fn from_open(c: char) -> Option<Self> {
+        match c {
+            '"' => Some(Self::Double),
+            '`' => Some(Self::Backtick),
+            '[' => Some(Self::Bracket),
+            _ => None,
+        }
+    }
+
+    /// Returns the opening quote character.
+    #[must_use]
+    pub const fn open(self) -> char {
+        match self {
+            Self::Double => '"',
+            Self::Backtick => '`',
+            Self::Bracket => '[',
+        }
+    }
+
+    /// Returns the closing quote character, which is doubled inside the
+    /// identifier.
+    #[must_use]
+    pub const fn close(self) -> char {
+        match self {
+            Self::Double => '"',
+            Self::Backtick => '`',
+            Self::Bracket => ']',
+        }
+    }
+
+    /// Returns the quote style `dialect` uses for identifiers.
+    #[must_use]
+    pub fn for_dialect<D: Dialect + ?Sized>(dialect: &D) -> Self {
+        Self::from_open(dialect.identifier_quote()).unwrap_or(Self::Double)
+    }
+
+    /// Writes `value` quoted in this style.
+    fn write(self, f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+        use fmt::Write;
+
+        let close = self.close();
+        f.write_char(self.open())?;
+        for c in value.chars() {
+            if c == close {
+                f.write_char(c)?;
+            }
+            f.write_char(c)?;
+        }
+        f.write_char(close)
+    }
+}
+
+/// An identifier such as a table, column, or alias name.
+///
+/// The value is stored unescaped; `quote_style` records how it was quoted
+/// in the source so `Display` can render it the same way. Identifiers
+/// dereference to their value and compare by value alone, with each other
+/// and with strings, so `"users"` and `users` are equal.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::ast::{Ident, QuoteStyle};
+///
+/// let ident = Ident::quoted("weird \"name\"", QuoteStyle::Double);
+/// assert_eq!(ident, "weird \"name\"");
+/// assert_eq!(ident.to_string(), "\"weird \"\"name\"\"\"");
+/// assert_eq!(Ident::new("users").to_string(), "users");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ident {
+    /// The unescaped name.
+    pub value: String,
+    /// How the name was quoted, or `None` for a bare identifier.
+    pub quote_style: Option<QuoteStyle>,
+}
+
+impl Ident {
+    /// Creates an unquoted identifier.
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            quote_style: None,
+        }
+    }
+
+    /// Creates a quoted identifier.
+    #[must_use]
+    pub fn quoted(value: impl Into<String>, style: QuoteStyle) -> Self {
+        Self {
+            value: value.into(),
+            quote_style: Some(style),
+        }
+    }
+
+    /// Returns the unescaped name.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Rewrites this identifier for `dialect`: quoted identifiers switch to
+    /// the dialect's quote character, and bare identifiers the dialect
+    /// reserves or cannot read unquoted become quoted.
+    pub fn requote<D: Dialect + ?Sized>(&mut self, dialect: &D) {
+        if self.quote_style.is_some() || dialect.requires_quoting(&self.value) {
+            self.quote_style = Some(QuoteStyle::for_dialect(dialect));
+        }
+    }
+
+    /// Renders this identifier for `dialect`, as [`requote`](Self::requote)
+    /// would.
+    #[must_use]
+    pub fn to_sql<D: Dialect + ?Sized>(&self, dialect: &D) -> String {
+        let mut ident = self.clone();
+        ident.requote(dialect);
+        ident.to_string()
+    }
+}
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.quote_style {
+            Some(style) => style.write(f, &self.value),
+            None => f.write_str(&self.value),
+        }
+    }
+}
+
+impl Deref for Ident {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl AsRef<str> for Ident {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl From<&str> for Ident {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Ident {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Ident> for String {
+    fn from(ident: Ident) -> Self {
+        ident.value
+    }
+}
+
+impl PartialEq for Ident {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Ident {}
+
+impl Hash for Ident {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl PartialEq<str> for Ident {
+    fn eq(&self, other: &str) -> bool {
+        self.value == other
+    }
+}
+
+impl PartialEq<&str> for Ident {
+    fn eq(&self, other: &&str) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<String> for Ident {
+    fn eq(&self, other: &String) -> bool {
+        self.value == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GenericDialect;
+
+    #[test]
+    fn test_display_escapes_closing_quote() {
+        assert_eq!(
+            Ident::quoted("a\"b", QuoteStyle::Double).to_string(),
+            "\"a\"\"b\""
+        );
+        assert_eq!(
+            Ident::quoted("a`b", QuoteStyle::Backtick).to_string(),
+            "`a``b`"
+        );
+        assert_eq!(
+            Ident::quoted("a]b[c", QuoteStyle::Bracket).to_string(),
+            "[a]]b[c]"
+        );
+        assert_eq!(Ident::new("naïve").to_string(), "naïve");
+    }
+
+    #[test]
+    fn test_to_sql_uses_dialect_quotes() {
+        let dialect = GenericDialect::new();
+        assert_eq!(
+            Ident::quoted("my col", QuoteStyle::Bracket).to_sql(&dialect),
+            "\"my col\""
+        );
+        assert_eq!(Ident::new("order").to_sql(&dialect), "\"order\"");
+        assert_eq!(Ident::new("orders").to_sql(&dialect), "orders");
+    }
+
+    #[test]
+    fn test_compares_with_strings_by_value() {
+        let ident = Ident::quoted("users", QuoteStyle::Backtick);
+        assert_eq!(ident, "users");
+        assert_eq!(ident, String::from("users"));
+        // Identifiers compare with each other the same way.
+        assert_eq!(ident, Ident::new("users"));
+        assert_ne!(ident, Ident::new("Users"));
+        assert_eq!(ident.len(), 5);
+    }
+}
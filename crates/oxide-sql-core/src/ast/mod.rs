@@ -1,12 +1,17 @@
 //! Abstract Syntax Tree (AST) types for SQL statements.
 
 mod expression;
+mod ident;
 mod statement;
 mod types;
+pub(crate) mod visit;
 
-pub use expression::{BinaryOp, Expr, FunctionCall, Literal, UnaryOp};
+pub use expression::{BinaryOp, Expr, FunctionCall, Literal, Quantifier, UnaryOp};
+pub use ident::{Ident, QuoteStyle};
 pub use statement::{
-    DeleteStatement, InsertSource, InsertStatement, JoinClause, JoinType, OrderBy, OrderDirection,
-    SelectColumn, SelectStatement, Statement, TableRef, UpdateAssignment, UpdateStatement,
+    ConflictAction, DeleteStatement, GroupByItem, InsertSource, InsertStatement, JoinClause,
+    JoinType, MergeAction, MergeClause, MergeStatement, NullOrdering, OnConflict, OrderBy,
+    OrderDirection, SelectColumn, SelectStatement, Statement, TableRef, UpdateAssignment,
+    UpdateStatement,
 };
 pub use types::{ColumnDef, DataType};
@@ -3,6 +3,9 @@
 use core::fmt;
 
 use super::expression::Expr;
+use super::ident::Ident;
+use super::visit::VisitIdents;
+use crate::dialect::Dialect;
 
 /// Order direction for ORDER BY.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -57,6 +60,29 @@ impl fmt::Display for NullOrdering {
     }
 }
 
+/// A GROUP BY clause entry.
+///
+/// ROLLUP, CUBE, and GROUPING SETS take a list of groups, each a list of
+/// expressions; a group of one expression is written without parentheses
+/// and an empty group (`()`) is the grand total.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupByItem {
+    /// A plain grouping expression.
+    Expr(Expr),
+    /// `ROLLUP(a, b)`: groups by each prefix of the list.
+    Rollup(Vec<Vec<Expr>>),
+    /// `CUBE(a, b)`: groups by every subset of the list.
+    Cube(Vec<Vec<Expr>>),
+    /// `GROUPING SETS ((a), (b))`: groups by each listed set.
+    GroupingSets(Vec<Vec<Expr>>),
+}
+
+impl From<Expr> for GroupByItem {
+    fn from(expr: Expr) -> Self {
+        Self::Expr(expr)
+    }
+}
+
 /// An ORDER BY clause entry.
 #[derive(Debug, Clone, PartialEq)]
 pub struct OrderBy {
@@ -113,7 +139,7 @@ pub struct JoinClause {
     /// The join condition (for non-CROSS joins).
     pub on: Option<Expr>,
     /// USING columns (alternative to ON).
-    pub using: Vec<String>,
+    pub using: Vec<Ident>,
 }
 
 /// A table reference in FROM clause.
@@ -122,18 +148,18 @@ pub enum TableRef {
     /// A simple table name.
     Table {
         /// Schema name (optional).
-        schema: Option<String>,
+        schema: Option<Ident>,
         /// Table name.
-        name: String,
+        name: Ident,
         /// Alias.
-        alias: Option<String>,
+        alias: Option<Ident>,
     },
     /// A subquery.
     Subquery {
         /// The subquery.
         query: Box<SelectStatement>,
         /// Alias (required for subqueries).
-        alias: String,
+        alias: Ident,
     },
     /// A joined table.
     Join {
@@ -147,7 +173,7 @@ pub enum TableRef {
 impl TableRef {
     /// Creates a simple table reference.
     #[must_use]
-    pub fn table(name: impl Into<String>) -> Self {
+    pub fn table(name: impl Into<Ident>) -> Self {
         Self::Table {
             schema: None,
             name: name.into(),
@@ -157,7 +183,7 @@ impl TableRef {
 
     /// Creates a table reference with schema.
     #[must_use]
-    pub fn with_schema(schema: impl Into<String>, name: impl Into<String>) -> Self {
+    pub fn with_schema(schema: impl Into<Ident>, name: impl Into<Ident>) -> Self {
         Self::Table {
             schema: Some(schema.into()),
             name: name.into(),
@@ -167,7 +193,7 @@ impl TableRef {
 
     /// Adds an alias to this table reference.
     #[must_use]
-    pub fn alias(self, alias: impl Into<String>) -> Self {
+    pub fn alias(self, alias: impl Into<Ident>) -> Self {
         match self {
             Self::Table { schema, name, .. } => Self::Table {
                 schema,
@@ -197,8 +223,8 @@ pub struct SelectStatement {
     pub from: Option<TableRef>,
     /// The WHERE clause.
     pub where_clause: Option<Expr>,
-    /// GROUP BY expressions.
-    pub group_by: Vec<Expr>,
+    /// GROUP BY entries.
+    pub group_by: Vec<GroupByItem>,
     /// HAVING clause.
     pub having: Option<Expr>,
     /// ORDER BY clauses.
@@ -215,7 +241,7 @@ pub struct SelectColumn {
     /// The expression.
     pub expr: Expr,
     /// Column alias.
-    pub alias: Option<String>,
+    pub alias: Option<Ident>,
 }
 
 impl SelectColumn {
@@ -227,7 +253,7 @@ impl SelectColumn {
 
     /// Creates a select column with an alias.
     #[must_use]
-    pub fn with_alias(expr: Expr, alias: impl Into<String>) -> Self {
+    pub fn with_alias(expr: Expr, alias: impl Into<Ident>) -> Self {
         Self {
             expr,
             alias: Some(alias.into()),
@@ -239,11 +265,11 @@ impl SelectColumn {
 #[derive(Debug, Clone, PartialEq)]
 pub struct InsertStatement {
     /// Schema name.
-    pub schema: Option<String>,
+    pub schema: Option<Ident>,
     /// Table name.
-    pub table: String,
+    pub table: Ident,
     /// Column names (optional).
-    pub columns: Vec<String>,
+    pub columns: Vec<Ident>,
     /// Values to insert.
     pub values: InsertSource,
     /// ON CONFLICT clause (for UPSERT).
@@ -265,7 +291,7 @@ pub enum InsertSource {
 #[derive(Debug, Clone, PartialEq)]
 pub struct OnConflict {
     /// Conflict target columns.
-    pub columns: Vec<String>,
+    pub columns: Vec<Ident>,
     /// Action to take on conflict.
     pub action: ConflictAction,
 }
@@ -283,11 +309,11 @@ pub enum ConflictAction {
 #[derive(Debug, Clone, PartialEq)]
 pub struct UpdateStatement {
     /// Schema name.
-    pub schema: Option<String>,
+    pub schema: Option<Ident>,
     /// Table name.
-    pub table: String,
+    pub table: Ident,
     /// Alias.
-    pub alias: Option<String>,
+    pub alias: Option<Ident>,
     /// SET assignments.
     pub assignments: Vec<UpdateAssignment>,
     /// FROM clause (for joins in UPDATE).
@@ -300,7 +326,7 @@ pub struct UpdateStatement {
 #[derive(Debug, Clone, PartialEq)]
 pub struct UpdateAssignment {
     /// Column name.
-    pub column: String,
+    pub column: Ident,
     /// Value expression.
     pub value: Expr,
 }
@@ -309,17 +335,65 @@ pub struct UpdateAssignment {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeleteStatement {
     /// Schema name.
-    pub schema: Option<String>,
+    pub schema: Option<Ident>,
     /// Table name.
-    pub table: String,
+    pub table: Ident,
     /// Alias.
-    pub alias: Option<String>,
+    pub alias: Option<Ident>,
     /// WHERE clause.
     pub where_clause: Option<Expr>,
 }
 
+/// A MERGE statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeStatement {
+    /// Schema name of the target table.
+    pub schema: Option<Ident>,
+    /// Target table name.
+    pub table: Ident,
+    /// Target alias.
+    pub alias: Option<Ident>,
+    /// The rows merged into the target (USING).
+    pub source: TableRef,
+    /// The condition matching source rows to target rows (ON).
+    pub on: Expr,
+    /// WHEN clauses, tried in order.
+    pub clauses: Vec<MergeClause>,
+}
+
+/// A `WHEN [NOT] MATCHED [AND condition] THEN action` clause of a MERGE.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeClause {
+    /// Whether the clause applies to matched (`true`) or unmatched source
+    /// rows.
+    pub matched: bool,
+    /// Additional condition (`AND ...`).
+    pub condition: Option<Expr>,
+    /// What to do with the row.
+    pub action: MergeAction,
+}
+
+/// The action of a MERGE clause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeAction {
+    /// UPDATE SET ... (matched rows only).
+    Update(Vec<UpdateAssignment>),
+    /// DELETE (matched rows only).
+    Delete,
+    /// INSERT (columns) VALUES (...) (unmatched rows only).
+    Insert {
+        /// Column names (optional).
+        columns: Vec<Ident>,
+        /// Values to insert.
+        values: Vec<Expr>,
+    },
+    /// DO NOTHING
+    DoNothing,
+}
+
 /// A SQL statement.
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)] // SELECT is the common case; keep it unboxed.
 pub enum Statement {
     /// SELECT statement.
     Select(SelectStatement),
@@ -329,12 +403,85 @@ pub enum Statement {
     Update(UpdateStatement),
     /// DELETE statement.
     Delete(DeleteStatement),
+    /// MERGE statement.
+    Merge(MergeStatement),
+}
+
+impl Statement {
+    /// Rewrites every identifier for `dialect` (see [`Ident::requote`]), so
+    /// that `Display` renders the statement with the dialect's quoting.
+    pub fn requote<D: Dialect + ?Sized>(&mut self, dialect: &D) {
+        self.visit_idents(&mut |ident| ident.requote(dialect));
+    }
+
+    /// Renders the statement with identifiers quoted for `dialect`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::Parser;
+    /// use oxide_sql_core::dialect::GenericDialect;
+    /// use oxide_sql_core::lexer::LexerOptions;
+    ///
+    /// let options = LexerOptions::new().bracket_identifiers(true);
+    /// let stmt = Parser::with_options("SELECT [first name] FROM `order`", options)
+    ///     .parse_statement()
+    ///     .unwrap();
+    /// assert_eq!(stmt.to_string(), "SELECT [first name] FROM `order`");
+    /// assert_eq!(
+    ///     stmt.to_sql(&GenericDialect::new()),
+    ///     "SELECT \"first name\" FROM \"order\""
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_sql<D: Dialect + ?Sized>(&self, dialect: &D) -> String {
+        let mut stmt = self.clone();
+        stmt.requote(dialect);
+        stmt.to_string()
+    }
 }
 
 // ===================================================================
 // Display implementations
 // ===================================================================
 
+/// Writes `(a, b)`, or just `a` for a single expression unless `parens`
+/// is set.
+fn write_group(f: &mut fmt::Formatter<'_>, group: &[Expr], parens: bool) -> fmt::Result {
+    if let [expr] = group
+        && !parens
+    {
+        return write!(f, "{expr}");
+    }
+    write!(f, "(")?;
+    for (i, expr) in group.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{expr}")?;
+    }
+    write!(f, ")")
+}
+
+impl fmt::Display for GroupByItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, groups, parens) = match self {
+            Self::Expr(expr) => return write!(f, "{expr}"),
+            Self::Rollup(groups) => ("ROLLUP(", groups, false),
+            Self::Cube(groups) => ("CUBE(", groups, false),
+            Self::GroupingSets(groups) => ("GROUPING SETS (", groups, true),
+        };
+        write!(f, "{name}")?;
+        for (i, group) in groups.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write_group(f, group, parens)?;
+        }
+        write!(f, ")")
+    }
+}
+
 impl fmt::Display for OrderBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {}", self.expr, self.direction)?;
@@ -583,6 +730,78 @@ impl fmt::Display for DeleteStatement {
     }
 }
 
+impl fmt::Display for MergeAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Update(assignments) => {
+                write!(f, "UPDATE SET")?;
+                for (i, a) in assignments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {a}")?;
+                }
+                Ok(())
+            }
+            Self::Delete => write!(f, "DELETE"),
+            Self::Insert { columns, values } => {
+                write!(f, "INSERT")?;
+                if !columns.is_empty() {
+                    write!(f, " (")?;
+                    for (i, col) in columns.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{col}")?;
+                    }
+                    write!(f, ")")?;
+                }
+                write!(f, " VALUES (")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, ")")
+            }
+            Self::DoNothing => write!(f, "DO NOTHING"),
+        }
+    }
+}
+
+impl fmt::Display for MergeClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WHEN ")?;
+        if !self.matched {
+            write!(f, "NOT ")?;
+        }
+        write!(f, "MATCHED")?;
+        if let Some(c) = &self.condition {
+            write!(f, " AND {c}")?;
+        }
+        write!(f, " THEN {}", self.action)
+    }
+}
+
+impl fmt::Display for MergeStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MERGE INTO ")?;
+        if let Some(s) = &self.schema {
+            write!(f, "{s}.")?;
+        }
+        write!(f, "{}", self.table)?;
+        if let Some(a) = &self.alias {
+            write!(f, " AS {a}")?;
+        }
+        write!(f, " USING {} ON {}", self.source, self.on)?;
+        for clause in &self.clauses {
+            write!(f, " {clause}")?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for Statement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -590,6 +809,7 @@ impl fmt::Display for Statement {
             Self::Insert(i) => write!(f, "{i}"),
             Self::Update(u) => write!(f, "{u}"),
             Self::Delete(d) => write!(f, "{d}"),
+            Self::Merge(m) => write!(f, "{m}"),
         }
     }
 }
@@ -614,7 +834,7 @@ mod tests {
     fn test_table_ref_builder() {
         let table = TableRef::table("users").alias("u");
         assert!(
-            matches!(table, TableRef::Table { name, alias, .. } if name == "users" && alias == Some(String::from("u")))
+            matches!(table, TableRef::Table { name, alias, .. } if name == "users" && alias.as_deref() == Some("u"))
         );
     }
 }
@@ -63,6 +63,9 @@ pub enum DataType {
     /// Boolean.
     Boolean,
 
+    /// Array of another type (`INTEGER[]`), as in PostgreSQL and DuckDB.
+    Array(Box<DataType>),
+
     // Custom type (for database-specific types).
     Custom(String),
 }
@@ -110,6 +113,7 @@ impl DataType {
             Self::Timestamp => String::from("TIMESTAMP"),
             Self::Datetime => String::from("DATETIME"),
             Self::Boolean => String::from("BOOLEAN"),
+            Self::Array(inner) => format!("{}[]", inner.to_sql()),
             Self::Custom(name) => name.clone(),
         }
     }
@@ -208,6 +212,10 @@ mod tests {
             .to_sql(),
             "DECIMAL(10, 2)"
         );
+        assert_eq!(
+            DataType::Array(Box::new(DataType::Integer)).to_sql(),
+            "INTEGER[]"
+        );
     }
 
     #[test]
@@ -0,0 +1,397 @@
+//! Traversals over the identifiers and expressions in an AST.
+
+use super::{
+    ConflictAction, DeleteStatement, Expr, GroupByItem, Ident, InsertSource, InsertStatement,
+    JoinClause, MergeAction, MergeClause, MergeStatement, OnConflict, SelectStatement, Statement,
+    TableRef, UpdateAssignment, UpdateStatement,
+};
+
+/// Calls a function on every identifier in a node, in source order.
+pub(crate) trait VisitIdents {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident));
+}
+
+impl<T: VisitIdents> VisitIdents for Option<T> {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        if let Some(node) = self {
+            node.visit_idents(f);
+        }
+    }
+}
+
+impl<T: VisitIdents> VisitIdents for Vec<T> {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        for node in self {
+            node.visit_idents(f);
+        }
+    }
+}
+
+impl<T: VisitIdents> VisitIdents for Box<T> {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        (**self).visit_idents(f);
+    }
+}
+
+impl VisitIdents for Ident {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        f(self);
+    }
+}
+
+impl VisitIdents for Expr {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        match self {
+            Self::Literal(_) | Self::Parameter { .. } => {}
+            Self::Column { table, name, .. } => {
+                table.visit_idents(f);
+                name.visit_idents(f);
+            }
+            Self::Wildcard { table } => table.visit_idents(f),
+            Self::Binary { left, right, .. } => {
+                left.visit_idents(f);
+                right.visit_idents(f);
+            }
+            Self::Unary { operand: expr, .. }
+            | Self::IsNull { expr, .. }
+            | Self::Cast { expr, .. }
+            | Self::Collate { expr, .. }
+            | Self::Paren(expr) => expr.visit_idents(f),
+            Self::Function(func) => {
+                func.args.visit_idents(f);
+                for order in &mut func.within_group {
+                    order.expr.visit_idents(f);
+                }
+                func.filter.visit_idents(f);
+            }
+            Self::Subquery(query) | Self::Exists(query) => query.visit_idents(f),
+            Self::Array(elements) => elements.visit_idents(f),
+            Self::Quantified { left, right, .. } => {
+                left.visit_idents(f);
+                right.visit_idents(f);
+            }
+            Self::In { expr, list, .. } => {
+                expr.visit_idents(f);
+                list.visit_idents(f);
+            }
+            Self::Between {
+                expr, low, high, ..
+            } => {
+                expr.visit_idents(f);
+                low.visit_idents(f);
+                high.visit_idents(f);
+            }
+            Self::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                operand.visit_idents(f);
+                for (when, then) in when_clauses {
+                    when.visit_idents(f);
+                    then.visit_idents(f);
+                }
+                else_clause.visit_idents(f);
+            }
+        }
+    }
+}
+
+impl VisitIdents for GroupByItem {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        match self {
+            Self::Expr(expr) => expr.visit_idents(f),
+            Self::Rollup(groups) | Self::Cube(groups) | Self::GroupingSets(groups) => {
+                groups.visit_idents(f);
+            }
+        }
+    }
+}
+
+impl VisitIdents for TableRef {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        match self {
+            Self::Table {
+                schema,
+                name,
+                alias,
+            } => {
+                schema.visit_idents(f);
+                name.visit_idents(f);
+                alias.visit_idents(f);
+            }
+            Self::Subquery { query, alias } => {
+                query.visit_idents(f);
+                alias.visit_idents(f);
+            }
+            Self::Join { left, join } => {
+                left.visit_idents(f);
+                join.visit_idents(f);
+            }
+        }
+    }
+}
+
+impl VisitIdents for JoinClause {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.table.visit_idents(f);
+        self.on.visit_idents(f);
+        self.using.visit_idents(f);
+    }
+}
+
+impl VisitIdents for SelectStatement {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        for column in &mut self.columns {
+            column.expr.visit_idents(f);
+            column.alias.visit_idents(f);
+        }
+        self.from.visit_idents(f);
+        self.where_clause.visit_idents(f);
+        self.group_by.visit_idents(f);
+        self.having.visit_idents(f);
+        for order in &mut self.order_by {
+            order.expr.visit_idents(f);
+        }
+        self.limit.visit_idents(f);
+        self.offset.visit_idents(f);
+    }
+}
+
+impl VisitIdents for UpdateAssignment {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.column.visit_idents(f);
+        self.value.visit_idents(f);
+    }
+}
+
+impl VisitIdents for OnConflict {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.columns.visit_idents(f);
+        if let ConflictAction::DoUpdate(assignments) = &mut self.action {
+            assignments.visit_idents(f);
+        }
+    }
+}
+
+impl VisitIdents for InsertStatement {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.schema.visit_idents(f);
+        self.table.visit_idents(f);
+        self.columns.visit_idents(f);
+        match &mut self.values {
+            InsertSource::Values(rows) => rows.visit_idents(f),
+            InsertSource::Query(query) => query.visit_idents(f),
+            InsertSource::DefaultValues => {}
+        }
+        self.on_conflict.visit_idents(f);
+    }
+}
+
+impl VisitIdents for UpdateStatement {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.schema.visit_idents(f);
+        self.table.visit_idents(f);
+        self.alias.visit_idents(f);
+        self.assignments.visit_idents(f);
+        self.from.visit_idents(f);
+        self.where_clause.visit_idents(f);
+    }
+}
+
+impl VisitIdents for DeleteStatement {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.schema.visit_idents(f);
+        self.table.visit_idents(f);
+        self.alias.visit_idents(f);
+        self.where_clause.visit_idents(f);
+    }
+}
+
+impl VisitIdents for MergeClause {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.condition.visit_idents(f);
+        match &mut self.action {
+            MergeAction::Update(assignments) => assignments.visit_idents(f),
+            MergeAction::Insert { columns, values } => {
+                columns.visit_idents(f);
+                values.visit_idents(f);
+            }
+            MergeAction::Delete | MergeAction::DoNothing => {}
+        }
+    }
+}
+
+impl VisitIdents for MergeStatement {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        self.schema.visit_idents(f);
+        self.table.visit_idents(f);
+        self.alias.visit_idents(f);
+        self.source.visit_idents(f);
+        self.on.visit_idents(f);
+        self.clauses.visit_idents(f);
+    }
+}
+
+impl VisitIdents for Statement {
+    fn visit_idents(&mut self, f: &mut dyn FnMut(&mut Ident)) {
+        match self {
+            Self::Select(select) => select.visit_idents(f),
+            Self::Insert(insert) => insert.visit_idents(f),
+            Self::Update(update) => update.visit_idents(f),
+            Self::Delete(delete) => delete.visit_idents(f),
+            Self::Merge(merge) => merge.visit_idents(f),
+        }
+    }
+}
+
+/// Calls a function on every top-level expression in a node, in source
+/// order. Subexpressions are not visited: the function is expected to
+/// descend into the expressions it is given.
+pub(crate) trait VisitExprs {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr));
+}
+
+impl<T: VisitExprs> VisitExprs for Option<T> {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        if let Some(node) = self {
+            node.visit_exprs(f);
+        }
+    }
+}
+
+impl<T: VisitExprs> VisitExprs for Vec<T> {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        for node in self {
+            node.visit_exprs(f);
+        }
+    }
+}
+
+impl<T: VisitExprs> VisitExprs for Box<T> {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        (**self).visit_exprs(f);
+    }
+}
+
+impl VisitExprs for Expr {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        f(self);
+    }
+}
+
+impl VisitExprs for GroupByItem {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        match self {
+            Self::Expr(expr) => f(expr),
+            Self::Rollup(groups) | Self::Cube(groups) | Self::GroupingSets(groups) => {
+                groups.visit_exprs(f);
+            }
+        }
+    }
+}
+
+impl VisitExprs for TableRef {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        match self {
+            Self::Table { .. } => {}
+            Self::Subquery { query, .. } => query.visit_exprs(f),
+            Self::Join { left, join } => {
+                left.visit_exprs(f);
+                join.visit_exprs(f);
+            }
+        }
+    }
+}
+
+impl VisitExprs for JoinClause {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        self.table.visit_exprs(f);
+        self.on.visit_exprs(f);
+    }
+}
+
+impl VisitExprs for SelectStatement {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        for column in &mut self.columns {
+            f(&mut column.expr);
+        }
+        self.from.visit_exprs(f);
+        self.where_clause.visit_exprs(f);
+        self.group_by.visit_exprs(f);
+        self.having.visit_exprs(f);
+        for order in &mut self.order_by {
+            f(&mut order.expr);
+        }
+        self.limit.visit_exprs(f);
+        self.offset.visit_exprs(f);
+    }
+}
+
+impl VisitExprs for UpdateAssignment {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        f(&mut self.value);
+    }
+}
+
+impl VisitExprs for InsertStatement {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        match &mut self.values {
+            InsertSource::Values(rows) => rows.visit_exprs(f),
+            InsertSource::Query(query) => query.visit_exprs(f),
+            InsertSource::DefaultValues => {}
+        }
+        if let Some(OnConflict {
+            action: ConflictAction::DoUpdate(assignments),
+            ..
+        }) = &mut self.on_conflict
+        {
+            assignments.visit_exprs(f);
+        }
+    }
+}
+
+impl VisitExprs for UpdateStatement {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        self.assignments.visit_exprs(f);
+        self.from.visit_exprs(f);
+        self.where_clause.visit_exprs(f);
+    }
+}
+
+impl VisitExprs for DeleteStatement {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        self.where_clause.visit_exprs(f);
+    }
+}
+
+impl VisitExprs for MergeClause {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        self.condition.visit_exprs(f);
+        match &mut self.action {
+            MergeAction::Update(assignments) => assignments.visit_exprs(f),
+            MergeAction::Insert { values, .. } => values.visit_exprs(f),
+            MergeAction::Delete | MergeAction::DoNothing => {}
+        }
+    }
+}
+
+impl VisitExprs for MergeStatement {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        self.source.visit_exprs(f);
+        f(&mut self.on);
+        self.clauses.visit_exprs(f);
+    }
+}
+
+impl VisitExprs for Statement {
+    fn visit_exprs(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        match self {
+            Self::Select(select) => select.visit_exprs(f),
+            Self::Insert(insert) => insert.visit_exprs(f),
+            Self::Update(update) => update.visit_exprs(f),
+            Self::Delete(delete) => delete.visit_exprs(f),
+            Self::Merge(merge) => merge.visit_exprs(f),
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Opt-in caching of generated SQL text.
+//!
+//! Hot paths often build the same statement over and over with different
+//! parameter values. A [`QueryCache`] keys the generated SQL by the
+//! structural shape of the builder (tables, columns, clauses, and the
+//! number of placeholders, but not parameter values), so the SQL string is
+//! rendered once and then shared as an `Arc<str>`.
+//!
+//! ```rust
+//! use oxide_sql_core::builder::{QueryCache, SelectDyn, SqlValue, dyn_col};
+//!
+//! let cache = QueryCache::new();
+//!
+//! let find_user = |id: i64| {
+//!     SelectDyn::new()
+//!         .columns(&["id", "name"])
+//!         .from("users")
+//!         .where_clause(dyn_col("id").eq(id))
+//! };
+//!
+//! let (first, params) = cache.build(find_user(1));
+//! let (second, _) = cache.build(find_user(2));
+//!
+//! assert_eq!(&*first, "SELECT id, name FROM users WHERE id = ?");
+//! assert_eq!(params, vec![SqlValue::Int(1)]);
+//! assert!(std::sync::Arc::ptr_eq(&first, &second));
+//! assert_eq!(cache.len(), 1);
+//! ```
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::expr::ExprBuilder;
+use super::value::SqlValue;
+
+/// A builder whose SQL text can be cached by [`QueryCache`].
+///
+/// Implemented for all statement builders once they are ready to build.
+pub trait CacheableQuery {
+    /// Feeds everything that determines the SQL text into `state`.
+    ///
+    /// Parameter values must not be hashed; only their number and position
+    /// (which is already part of the rendered SQL fragments) matter.
+    fn hash_shape<H: Hasher>(&self, state: &mut H);
+
+    /// Returns the bound parameters, in order, without rendering SQL.
+    fn into_params(self) -> Vec<SqlValue>;
+
+    /// Renders the SQL and parameters, quoting names as `dialect` does.
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>);
+
+    /// Returns the structural fingerprint of the builder.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash_shape(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns every byte [`hash_shape`](Self::hash_shape) feeds in, which
+    /// identifies the shape exactly where the fingerprint may collide.
+    fn shape(&self) -> Vec<u8> {
+        let mut recorder = ShapeRecorder::default();
+        self.hash_shape(&mut recorder);
+        recorder.bytes
+    }
+}
+
+/// A [`Hasher`] that keeps the bytes written to it instead of mixing them.
+#[derive(Default)]
+struct ShapeRecorder {
+    bytes: Vec<u8>,
+}
+
+impl Hasher for ShapeRecorder {
+    fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&self.bytes);
+        hasher.finish()
+    }
+}
+
+/// A [`Hasher`] that compares the bytes written to it against a recorded
+/// shape, so a lookup can confirm a match without allocating.
+struct ShapeMatcher<'a> {
+    expected: &'a [u8],
+    matches: bool,
+}
+
+impl ShapeMatcher<'_> {
+    /// Returns `true` if `query` feeds in exactly the bytes of `shape`.
+    fn check<Q: CacheableQuery>(query: &Q, shape: &[u8]) -> bool {
+        let mut matcher = ShapeMatcher {
+            expected: shape,
+            matches: true,
+        };
+        query.hash_shape(&mut matcher);
+        matcher.matches && matcher.expected.is_empty()
+    }
+}
+
+impl Hasher for ShapeMatcher<'_> {
+    fn write(&mut self, bytes: &[u8]) {
+        match self.expected.strip_prefix(bytes) {
+            Some(rest) if self.matches => self.expected = rest,
+            _ => self.matches = false,
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        0
+    }
+}
+
+/// One cached statement.
+#[derive(Debug)]
+struct Entry {
+    /// The [`Dialect::name`] the SQL was rendered for.
+    dialect: &'static str,
+    /// The full shape of the builder, compared on lookup.
+    shape: Box<[u8]>,
+    sql: Arc<str>,
+}
+
+/// A thread-safe cache of generated SQL keyed by builder shape.
+///
+/// Entries are keyed by the fingerprint of the builder (see
+/// [`CacheableQuery::fingerprint`]) and the dialect, so a lookup does not
+/// allocate. Each entry keeps the full shape of its builder (see
+/// [`CacheableQuery::shape`]) and a lookup compares against it, so
+/// differently shaped builders never share SQL, even if their fingerprints
+/// collide. Dialects are told apart by [`Dialect::name`].
+#[derive(Debug, Default)]
+pub struct QueryCache {
+    sql: RwLock<HashMap<u64, Vec<Entry>>>,
+}
+
+impl QueryCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds `query`, reusing the cached SQL text if a builder with the
+    /// same shape was built before.
+    ///
+    /// Names are quoted as [`GenericDialect`] does; use
+    /// [`build_for`](Self::build_for) to quote for another dialect.
+    pub fn build<Q: CacheableQuery>(&self, query: Q) -> (Arc<str>, Vec<SqlValue>) {
+        self.build_for(query, &GenericDialect)
+    }
+
+    /// Builds `query` for `dialect`, reusing the cached SQL text if a
+    /// builder with the same shape was built for the same dialect before.
+    pub fn build_for<Q: CacheableQuery, D: Dialect + ?Sized>(
+        &self,
+        query: Q,
+        dialect: &D,
+    ) -> (Arc<str>, Vec<SqlValue>) {
+        let mut hasher = DefaultHasher::new();
+        dialect.name().hash(&mut hasher);
+        query.fingerprint().hash(&mut hasher);
+        let key = hasher.finish();
+
+        let cached = self
+            .sql
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .and_then(|entries| {
+                entries
+                    .iter()
+                    .find(|e| e.dialect == dialect.name() && ShapeMatcher::check(&query, &e.shape))
+                    .map(|e| Arc::clone(&e.sql))
+            });
+        if let Some(sql) = cached {
+            return (sql, query.into_params());
+        }
+
+        let shape = query.shape().into_boxed_slice();
+        let (sql, params) = query.render(dialect);
+        let sql: Arc<str> = Arc::from(sql);
+        let mut cache = self.sql.write().unwrap_or_else(PoisonError::into_inner);
+        let entries = cache.entry(key).or_default();
+        if !entries
+            .iter()
+            .any(|e| e.dialect == dialect.name() && *e.shape == *shape)
+        {
+            entries.push(Entry {
+                dialect: dialect.name(),
+                shape,
+                sql: Arc::clone(&sql),
+            });
+        }
+        (sql, params)
+    }
+
+    /// Returns the number of cached statements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sql
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Returns `true` if nothing has been cached yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached statements.
+    pub fn clear(&self) {
+        self.sql
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+/// Hashes the SQL of an optional expression.
+pub(crate) fn hash_expr<H: Hasher>(expr: Option<&ExprBuilder>, state: &mut H) {
+    expr.map(ExprBuilder::fragment).hash(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::test_support::Backticks;
+    use crate::builder::{DeleteDyn, InsertDyn, SelectDyn, UpdateDyn, dyn_col, func};
+
+    #[test]
+    fn test_same_shape_shares_sql() {
+        let cache = QueryCache::new();
+        let (a, a_params) = cache.build(
+            UpdateDyn::new()
+                .table("users")
+                .set("name", "a")
+                .where_clause(dyn_col("id").eq(1)),
+        );
+        let (b, b_params) = cache.build(
+            UpdateDyn::new()
+                .table("users")
+                .set("name", "b")
+                .where_clause(dyn_col("id").eq(2)),
+        );
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&*a, "UPDATE users SET name = ? WHERE id = ?");
+        assert_eq!(a_params[0], SqlValue::Text(String::from("a")));
+        assert_eq!(b_params[1], SqlValue::Int(2));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_different_shapes_are_separate() {
+        let cache = QueryCache::new();
+        let (one, _) = cache.build(
+            InsertDyn::new()
+                .into_table("t")
+                .columns(&["a"])
+                .values(vec![1]),
+        );
+        let (two, params) = cache.build(
+            InsertDyn::new()
+                .into_table("t")
+                .columns(&["a"])
+                .values(vec![1])
+                .and_values(vec![2]),
+        );
+
+        assert_eq!(&*one, "INSERT INTO t (a) VALUES (?)");
+        assert_eq!(&*two, "INSERT INTO t (a) VALUES (?), (?)");
+        assert_eq!(params, vec![SqlValue::Int(1), SqlValue::Int(2)]);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_colliding_fingerprints_are_separate() {
+        struct Fixed(&'static str);
+        impl CacheableQuery for Fixed {
+            fn hash_shape<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+            fn into_params(self) -> Vec<SqlValue> {
+                vec![]
+            }
+            fn render<D: Dialect + ?Sized>(self, _dialect: &D) -> (String, Vec<SqlValue>) {
+                (String::from(self.0), vec![])
+            }
+            fn fingerprint(&self) -> u64 {
+                0
+            }
+        }
+
+        let cache = QueryCache::new();
+        let (one, _) = cache.build(Fixed("SELECT 1"));
+        let (two, _) = cache.build(Fixed("SELECT 2"));
+        assert_eq!(&*one, "SELECT 1");
+        assert_eq!(&*two, "SELECT 2");
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_params_match_uncached_build() {
+        let build = || {
+            SelectDyn::new()
+                .columns(&["status"])
+                .from("orders")
+                .where_clause(dyn_col("total").gt(10))
+                .group_by(&["status"])
+                .having(func::count_all().gt(2))
+                .limit(5)
+        };
+        let cache = QueryCache::new();
+        let _ = cache.build(build());
+        let (sql, params) = cache.build(build());
+        assert_eq!((sql.to_string(), params), build().build());
+    }
+
+    #[test]
+    fn test_dialects_are_cached_separately() {
+        let build = || {
+            SelectDyn::new()
+                .columns(&["my col"])
+                .from("t")
+                .where_clause(dyn_col("order").eq(1))
+        };
+        let cache = QueryCache::new();
+        let (generic, _) = cache.build(build());
+        let (backticks, _) = cache.build_for(build(), &Backticks);
+        let (again, _) = cache.build_for(build(), &Backticks);
+
+        assert_eq!(&*generic, "SELECT \"my col\" FROM t WHERE \"order\" = ?");
+        assert_eq!(&*backticks, "SELECT `my col` FROM t WHERE `order` = ?");
+        assert!(Arc::ptr_eq(&backticks, &again));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_shape_matcher() {
+        let query = DeleteDyn::new()
+            .from("users")
+            .where_clause(dyn_col("id").eq(1));
+        let shape = query.shape();
+        assert!(ShapeMatcher::check(&query, &shape));
+        assert!(!ShapeMatcher::check(&query, &shape[..shape.len() - 1]));
+
+        let mut longer = shape.clone();
+        longer.push(0);
+        assert!(!ShapeMatcher::check(&query, &longer));
+    }
+
+    #[test]
+    fn test_clear() {
+        let cache = QueryCache::new();
+        let _ = cache.build(DeleteDyn::new().from("users"));
+        assert!(!cache.is_empty());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
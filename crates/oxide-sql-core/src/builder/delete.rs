@@ -3,10 +3,17 @@
 //! This module provides string-based query building. For compile-time
 //! validated queries using schema traits, use `Delete` from `builder::typed`.
 
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
-use super::expr::ExprBuilder;
-use super::value::SqlValue;
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::cache::{CacheableQuery, hash_expr};
+use super::expr::{ChunkError, ExprBuilder, dyn_col};
+use super::ident::push_table_for;
+use super::query::Query;
+use super::render::{expr_len, push_expr};
+use super::value::{SqlValue, ToSqlValue};
 
 // Typestate markers
 
@@ -74,22 +81,57 @@ impl DeleteDyn<HasTable> {
     /// Builds the DELETE statement and returns SQL with parameters.
     ///
     /// **Warning**: If no WHERE clause is specified, this will delete ALL rows.
+    ///
+    /// Names are quoted as [`GenericDialect`] does; use
+    /// [`build_for`](Self::build_for) to quote for another dialect.
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("DELETE FROM ");
+        self.build_for(&GenericDialect)
+    }
+
+    /// Builds the DELETE statement, quoting the table name as `dialect`
+    /// does, and returns SQL with parameters.
+    #[must_use]
+    pub fn build_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(
+            16 + self.table.as_ref().map_or(0, String::len) + expr_len(self.where_clause.as_ref()),
+        );
+        let params = self.build_into_for(&mut sql, dialect);
+        (sql, params)
+    }
+
+    /// Renders the DELETE statement into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings, so one
+    /// buffer can be reused across statements.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.build_into_for(sql, &GenericDialect)
+    }
+
+    /// Renders the DELETE statement into `sql` like
+    /// [`build_into`](Self::build_into), quoting the table name as
+    /// `dialect` does.
+    pub fn build_into_for<D: Dialect + ?Sized>(
+        self,
+        sql: &mut String,
+        dialect: &D,
+    ) -> Vec<SqlValue> {
         let mut params = vec![];
 
+        sql.push_str("DELETE FROM ");
         if let Some(ref table) = self.table {
-            sql.push_str(table);
+            push_table_for(sql, table, dialect);
         }
 
-        if let Some(ref where_expr) = self.where_clause {
-            sql.push_str(" WHERE ");
-            sql.push_str(where_expr.sql());
-            params.extend(where_expr.params().iter().cloned());
-        }
+        push_expr(sql, " WHERE ", self.where_clause, &mut params, dialect);
 
-        (sql, params)
+        params
+    }
+
+    /// Builds the DELETE statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the DELETE statement and returns only the SQL string.
@@ -99,6 +141,46 @@ impl DeleteDyn<HasTable> {
         sql
     }
 
+    /// Builds one DELETE statement per chunk of `values`, each restricted
+    /// to `column IN (...)`.
+    ///
+    /// An existing WHERE clause is kept and ANDed with every chunk. Chunks
+    /// hold at most `chunk_size` values, and fewer if needed so that each
+    /// statement, WHERE parameters included, stays within
+    /// [`Dialect::max_parameters`]. Names are quoted as `dialect` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkError`] if `chunk_size` is zero or the WHERE clause
+    /// alone reaches the dialect's parameter limit.
+    pub fn where_in_chunked<'a, T: ToSqlValue, D: Dialect + ?Sized>(
+        self,
+        column: &str,
+        values: Vec<T>,
+        chunk_size: usize,
+        dialect: &'a D,
+    ) -> Result<impl Iterator<Item = (String, Vec<SqlValue>)> + use<'a, T, D>, ChunkError> {
+        let mut prefix = String::from("DELETE FROM ");
+        if let Some(ref table) = self.table {
+            push_table_for(&mut prefix, table, dialect);
+        }
+        let values = values.into_iter().map(ToSqlValue::to_sql_value).collect();
+        let chunks = ExprBuilder::in_list_chunks(
+            self.where_clause,
+            dyn_col(column).into(),
+            values,
+            chunk_size,
+            0,
+            dialect.max_parameters(),
+        )?;
+        Ok(chunks.map(move |expr| {
+            let mut sql = prefix.clone();
+            let mut params = vec![];
+            push_expr(&mut sql, " WHERE ", Some(expr), &mut params, dialect);
+            (sql, params)
+        }))
+    }
+
     /// Returns true if a WHERE clause is specified.
     #[must_use]
     pub const fn has_where_clause(&self) -> bool {
@@ -106,6 +188,24 @@ impl DeleteDyn<HasTable> {
     }
 }
 
+impl CacheableQuery for DeleteDyn<HasTable> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "delete".hash(state);
+        self.table.hash(state);
+        hash_expr(self.where_clause.as_ref(), state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        self.where_clause
+            .map(|e| e.into_parts().1)
+            .unwrap_or_default()
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.build_for(dialect)
+    }
+}
+
 /// A safe DELETE builder that requires a WHERE clause.
 ///
 /// This prevents accidental deletion of all rows.
@@ -159,6 +259,17 @@ impl SafeDeleteDynWithWhere {
         self.inner.build()
     }
 
+    /// Renders the DELETE statement into `sql` and returns the parameters.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.inner.build_into(sql)
+    }
+
+    /// Builds the DELETE statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
+    }
+
     /// Builds the DELETE statement and returns only the SQL string.
     #[must_use]
     pub fn build_sql(self) -> String {
@@ -170,6 +281,20 @@ impl SafeDeleteDynWithWhere {
 mod tests {
     use super::*;
     use crate::builder::dyn_col;
+    use crate::builder::test_support::Backticks;
+
+    /// A dialect accepting at most the given number of bound parameters.
+    struct MaxParams(usize);
+
+    impl Dialect for MaxParams {
+        fn name(&self) -> &'static str {
+            "max-params"
+        }
+
+        fn max_parameters(&self) -> usize {
+            self.0
+        }
+    }
 
     #[test]
     fn test_simple_delete() {
@@ -238,4 +363,99 @@ mod tests {
         assert_eq!(sql, "DELETE FROM users WHERE id = ?");
         assert!(matches!(&params[0], SqlValue::Text(s) if s == malicious));
     }
+
+    #[test]
+    fn test_delete_where_in_chunked() {
+        let statements: Vec<_> = DeleteDyn::new()
+            .from("users")
+            .where_in_chunked("id", vec![1, 2, 3, 4, 5], 100, &MaxParams(2))
+            .unwrap()
+            .collect();
+
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].0, "DELETE FROM users WHERE id IN (?, ?)");
+        assert_eq!(statements[0].1, vec![SqlValue::Int(1), SqlValue::Int(2)]);
+        assert_eq!(statements[2].0, "DELETE FROM users WHERE id IN (?)");
+        assert_eq!(statements[2].1, vec![SqlValue::Int(5)]);
+    }
+
+    #[test]
+    fn test_delete_quotes_as_the_dialect() {
+        let (sql, _) = DeleteDyn::new()
+            .from("order")
+            .where_clause(dyn_col("id").eq(1))
+            .build_for(&Backticks);
+        assert_eq!(sql, "DELETE FROM `order` WHERE id = ?");
+
+        let statements: Vec<_> = DeleteDyn::new()
+            .from("my table")
+            .where_in_chunked("user", vec![1], 100, &Backticks)
+            .unwrap()
+            .collect();
+        assert_eq!(
+            statements[0].0,
+            "DELETE FROM `my table` WHERE `user` IN (?)"
+        );
+    }
+
+    #[test]
+    fn test_delete_where_in_chunked_keeps_where() {
+        let statements: Vec<_> = DeleteDyn::new()
+            .from("sessions")
+            .where_clause(dyn_col("expired").eq(true).or(dyn_col("revoked").eq(true)))
+            .where_in_chunked("user_id", vec![10, 20, 30], 100, &MaxParams(4))
+            .unwrap()
+            .collect();
+
+        assert_eq!(
+            statements[0].0,
+            "DELETE FROM sessions WHERE (expired = ? OR revoked = ?) AND user_id IN (?, ?)"
+        );
+        assert_eq!(statements[0].1.len(), 4);
+        assert_eq!(statements[1].1.len(), 3);
+    }
+
+    #[test]
+    fn test_delete_where_in_chunked_chunk_size() {
+        let statements: Vec<_> = DeleteDyn::new()
+            .from("users")
+            .where_in_chunked("id", vec![1, 2, 3], 2, &MaxParams(100))
+            .unwrap()
+            .collect();
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].0, "DELETE FROM users WHERE id IN (?, ?)");
+        assert_eq!(statements[1].1, vec![SqlValue::Int(3)]);
+    }
+
+    #[test]
+    fn test_delete_where_in_chunked_tiny_limit() {
+        // The WHERE clause alone uses up the limit, so no value fits.
+        let result = DeleteDyn::new()
+            .from("users")
+            .where_clause(dyn_col("active").eq(false))
+            .where_in_chunked("id", vec![1, 2], 100, &MaxParams(1));
+        assert_eq!(
+            result.err(),
+            Some(ChunkError::ParameterLimit {
+                reserved: 1,
+                max_parameters: 1,
+            })
+        );
+
+        let result =
+            DeleteDyn::new()
+                .from("users")
+                .where_in_chunked("id", vec![1], 0, &MaxParams(100));
+        assert_eq!(result.err(), Some(ChunkError::ZeroChunkSize));
+    }
+
+    #[test]
+    fn test_delete_where_in_chunked_empty() {
+        let mut statements = DeleteDyn::new()
+            .from("users")
+            .where_in_chunked::<i64, _>("id", vec![], 100, &MaxParams(100))
+            .unwrap();
+        assert!(statements.next().is_none());
+    }
 }
@@ -2,10 +2,21 @@
 //!
 //! For compile-time validated column expressions, use `col` from `builder::typed`.
 
+use crate::ast::{BinaryOp, Expr, Literal, UnaryOp};
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::query::Query;
+use super::render::Fragment;
 use super::value::{SqlValue, ToSqlValue};
 
 /// Creates a column reference for dynamic (string-based) queries.
 ///
+/// Names that are not valid, possibly qualified identifiers are quoted when
+/// the statement is built, as the dialect passed to `build_for()` does, so
+/// `dyn_col("COUNT(*)")` refers to a column named `COUNT(*)`. Build
+/// expressions with [`builder::func`](super::func) or, for fixed SQL,
+/// [`ExprBuilder::raw`].
+///
 /// For compile-time validated queries, use `col` from `builder::typed`.
 #[must_use]
 pub fn dyn_col(name: &str) -> ColumnRef {
@@ -15,6 +26,51 @@ pub fn dyn_col(name: &str) -> ColumnRef {
     }
 }
 
+/// Creates an `EXISTS (subquery)` predicate.
+///
+/// The subquery may refer to columns of the outer query, qualified by
+/// table name or alias, to correlate the two.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::{ColumnRef, SelectDyn, dyn_col, not_exists};
+///
+/// let orders = SelectDyn::new()
+///     .columns_raw(&["1"])
+///     .from("orders AS o")
+///     .where_clause(
+///         dyn_col("o.user_id")
+///             .eq_expr(ColumnRef::qualified("u", "id"))
+///             .and(dyn_col("o.status").eq("open")),
+///     );
+///
+/// let (sql, params) = SelectDyn::new()
+///     .columns(&["u.id"])
+///     .from("users AS u")
+///     .where_clause(not_exists(orders))
+///     .build();
+///
+/// assert_eq!(
+///     sql,
+///     "SELECT u.id FROM users AS u WHERE NOT EXISTS \
+///      (SELECT 1 FROM orders AS o WHERE o.user_id = u.id AND o.status = ?)"
+/// );
+/// assert_eq!(params.len(), 1);
+/// ```
+#[must_use]
+pub fn exists(subquery: impl Into<Query>) -> ExprBuilder {
+    let (sql, params) = subquery.into().into_parts();
+    ExprBuilder::with_params(format!("EXISTS ({sql})"), params)
+}
+
+/// Creates a `NOT EXISTS (subquery)` predicate; see [`exists`].
+#[must_use]
+pub fn not_exists(subquery: impl Into<Query>) -> ExprBuilder {
+    let (sql, params) = subquery.into().into_parts();
+    ExprBuilder::with_params(format!("NOT EXISTS ({sql})"), params)
+}
+
 /// A column reference for dynamic (string-based) queries.
 #[derive(Debug, Clone)]
 pub struct ColumnRef {
@@ -34,12 +90,23 @@ impl ColumnRef {
         }
     }
 
-    /// Returns the SQL representation.
+    /// Returns the SQL representation, quoted as [`GenericDialect`] does.
     #[must_use]
     pub fn to_sql(&self) -> String {
+        self.to_sql_for(&GenericDialect)
+    }
+
+    /// Returns the SQL representation, quoted as `dialect` does.
+    #[must_use]
+    pub fn to_sql_for<D: Dialect + ?Sized>(&self, dialect: &D) -> String {
+        self.fragment().to_sql_for(dialect)
+    }
+
+    /// Returns the reference with its names left unquoted.
+    fn fragment(&self) -> Fragment {
         match &self.table {
-            Some(t) => format!("{t}.{}", self.name),
-            None => self.name.clone(),
+            Some(t) => Fragment::table(t).then_sql(".").then_column(&self.name),
+            None => Fragment::column(&self.name),
         }
     }
 
@@ -49,6 +116,13 @@ impl ColumnRef {
         ExprBuilder::binary(self.into(), "=", value.to_sql_value().into())
     }
 
+    /// Creates an equality expression against another expression, such as
+    /// a column of an outer query (`o.user_id = u.id`).
+    #[must_use]
+    pub fn eq_expr(self, other: impl Into<ExprBuilder>) -> ExprBuilder {
+        ExprBuilder::binary(self.into(), "=", other.into())
+    }
+
     /// Creates an inequality expression.
     #[must_use]
     pub fn not_eq<T: ToSqlValue>(self, value: T) -> ExprBuilder {
@@ -128,13 +202,118 @@ impl ColumnRef {
         let sql_values: Vec<SqlValue> = values.into_iter().map(ToSqlValue::to_sql_value).collect();
         ExprBuilder::in_list_impl(self.into(), sql_values, true)
     }
+
+    /// Gets the field `key` of this JSON column as text: `data ->> ?`.
+    ///
+    /// Use [`json_get_for`](Self::json_get_for) for dialects that spell
+    /// this differently, such as SQLite before 3.38.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::builder::dyn_col;
+    ///
+    /// let (sql, params) = dyn_col("data").json_get("name").eq("Ada").build();
+    /// assert_eq!(sql, "data ->> ? = ?");
+    /// assert_eq!(params.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn json_get(self, key: &str) -> ExprBuilder {
+        self.json_get_for(key, &GenericDialect::new())
+    }
+
+    /// Gets the field `key` of this JSON column as text, as `dialect`
+    /// spells it (see [`Dialect::json_get`]).
+    #[must_use]
+    pub fn json_get_for<D: Dialect + ?Sized>(self, key: &str, dialect: &D) -> ExprBuilder {
+        dialect.json_get(self.into(), key)
+    }
+
+    /// Compares this column under `collation` (`name COLLATE NOCASE`).
+    #[must_use]
+    pub fn collate(self, collation: &str) -> ExprBuilder {
+        ExprBuilder::from(self).collate(collation)
+    }
+}
+
+/// Error returned by the `where_in_chunked()` methods when no chunk of
+/// values can be formed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkError {
+    /// The requested chunk size was zero.
+    ZeroChunkSize,
+    /// The rest of the statement already uses every parameter the dialect
+    /// allows, leaving no room for even one value.
+    ParameterLimit {
+        /// The parameters the statement binds besides the IN list.
+        reserved: usize,
+        /// The dialect's limit (see [`Dialect::max_parameters`]).
+        max_parameters: usize,
+    },
+}
+
+impl std::fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroChunkSize => f.write_str("chunk size must be at least 1"),
+            Self::ParameterLimit {
+                reserved,
+                max_parameters,
+            } => write!(
+                f,
+                "statement binds {reserved} parameters besides the IN list, \
+                 leaving no room under the limit of {max_parameters}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChunkError {}
+
+/// Returns true if `expr` is the `NULL` literal.
+const fn is_null_literal(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal(Literal::Null))
+}
+
+/// Returns true if `s` matches `-?digits[.digits][(e|E)[+-]digits]`, the
+/// only numbers safe to inline into SQL.
+fn is_plain_number(s: &str) -> bool {
+    fn digits(s: &str) -> &str {
+        s.trim_start_matches(|c: char| c.is_ascii_digit())
+    }
+
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let rest = digits(s);
+    if rest.len() == s.len() {
+        return false;
+    }
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => {
+            let after = digits(fraction);
+            if after.len() == fraction.len() {
+                return false;
+            }
+            after
+        }
+        None => rest,
+    };
+    let Some(exponent) = rest.strip_prefix(['e', 'E']) else {
+        return rest.is_empty();
+    };
+    let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    !exponent.is_empty() && digits(exponent).is_empty()
 }
 
 /// A type-safe expression builder.
+///
+/// Column, table, and collation names are kept unquoted until the
+/// expression is built, so they are quoted as the statement's dialect does.
 #[derive(Debug, Clone)]
 pub struct ExprBuilder {
-    sql: String,
+    sql: Fragment,
     params: Vec<SqlValue>,
+    /// Whether the SQL is a binary operation without enclosing parentheses.
+    binary: bool,
 }
 
 impl ExprBuilder {
@@ -144,8 +323,9 @@ impl ExprBuilder {
     #[must_use]
     pub fn raw(sql: impl Into<String>) -> Self {
         Self {
-            sql: sql.into(),
+            sql: Fragment::sql(sql),
             params: vec![],
+            binary: false,
         }
     }
 
@@ -155,8 +335,9 @@ impl ExprBuilder {
     #[must_use]
     pub fn column(name: &str) -> Self {
         Self {
-            sql: String::from(name),
+            sql: Fragment::sql(name),
             params: vec![],
+            binary: false,
         }
     }
 
@@ -164,26 +345,61 @@ impl ExprBuilder {
     #[must_use]
     pub fn value<T: ToSqlValue>(value: T) -> Self {
         Self {
-            sql: String::from("?"),
+            sql: Fragment::sql("?"),
             params: vec![value.to_sql_value()],
+            binary: false,
+        }
+    }
+
+    /// Creates an expression from already rendered SQL and its parameters.
+    pub(crate) fn with_params(sql: String, params: Vec<SqlValue>) -> Self {
+        Self::from_parts(Fragment::sql(sql), params)
+    }
+
+    /// Creates an expression from a fragment and its parameters.
+    pub(crate) const fn from_parts(sql: Fragment, params: Vec<SqlValue>) -> Self {
+        Self {
+            sql,
+            params,
+            binary: false,
         }
     }
 
+    /// Consumes the builder and returns its unquoted fragment and
+    /// parameters.
+    pub(crate) fn into_parts(self) -> (Fragment, Vec<SqlValue>) {
+        (self.sql, self.params)
+    }
+
+    /// Returns the expression with its names left unquoted.
+    pub(crate) const fn fragment(&self) -> &Fragment {
+        &self.sql
+    }
+
     /// Creates a binary expression.
     fn binary(left: Self, op: &str, right: Self) -> Self {
         let mut params = left.params;
         params.extend(right.params);
         Self {
-            sql: format!("{} {op} {}", left.sql, right.sql),
+            sql: left.sql.then_sql(format!(" {op} ")).then(right.sql),
             params,
+            binary: true,
         }
     }
 
+    /// Creates an arithmetic expression, parenthesizing operands that are
+    /// binary expressions themselves so they keep their grouping.
+    fn arithmetic(left: Self, op: &str, right: Self) -> Self {
+        let group = |expr: Self| if expr.binary { expr.paren() } else { expr };
+        Self::binary(group(left), op, group(right))
+    }
+
     /// Creates a postfix expression.
     fn postfix(operand: Self, op: &str) -> Self {
         Self {
-            sql: format!("{} {op}", operand.sql),
+            sql: operand.sql.then_sql(format!(" {op}")),
             params: operand.params,
+            binary: false,
         }
     }
 
@@ -194,8 +410,9 @@ impl ExprBuilder {
         params.push(low);
         params.push(high);
         Self {
-            sql: format!("{} {keyword} ? AND ?", expr.sql),
+            sql: expr.sql.then_sql(format!(" {keyword} ? AND ?")),
             params,
+            binary: false,
         }
     }
 
@@ -206,9 +423,49 @@ impl ExprBuilder {
         let mut params = expr.params;
         params.extend(values);
         Self {
-            sql: format!("{} {keyword} ({})", expr.sql, placeholders.join(", ")),
+            sql: expr
+                .sql
+                .then_sql(format!(" {keyword} ({})", placeholders.join(", "))),
             params,
+            binary: false,
+        }
+    }
+
+    /// Splits `values` into `column IN (...)` conditions, ANDed with `base`
+    /// when given, of at most `chunk_size` values each, and fewer if needed
+    /// so that each condition plus `reserved` other parameters stays within
+    /// `max_parameters`.
+    ///
+    /// Used by the `where_in_chunked()` methods of UPDATE and DELETE builders.
+    pub(crate) fn in_list_chunks(
+        base: Option<Self>,
+        column: Self,
+        values: Vec<SqlValue>,
+        chunk_size: usize,
+        reserved: usize,
+        max_parameters: usize,
+    ) -> Result<impl Iterator<Item = Self> + use<>, ChunkError> {
+        if chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize);
+        }
+        let reserved = reserved + base.as_ref().map_or(0, |base| base.params.len());
+        let chunk_size = chunk_size.min(max_parameters.saturating_sub(reserved));
+        if chunk_size == 0 {
+            return Err(ChunkError::ParameterLimit {
+                reserved,
+                max_parameters,
+            });
         }
+        let mut values = values.into_iter().peekable();
+        Ok(std::iter::from_fn(move || {
+            values.peek()?;
+            let chunk: Vec<SqlValue> = values.by_ref().take(chunk_size).collect();
+            let in_list = Self::in_list_impl(column.clone(), chunk, false);
+            Some(match &base {
+                Some(base) => base.clone().paren().and(in_list),
+                None => in_list,
+            })
+        }))
     }
 
     /// Creates an AND expression.
@@ -227,8 +484,88 @@ impl ExprBuilder {
     #[must_use]
     pub fn paren(self) -> Self {
         Self {
-            sql: format!("({})", self.sql),
+            sql: Fragment::sql("(").then(self.sql).then_sql(")"),
+            params: self.params,
+            binary: false,
+        }
+    }
+
+    /// Applies a collation to the expression (`expr COLLATE NOCASE`).
+    ///
+    /// The collation name is quoted when the statement is built if it is
+    /// not a plain identifier; pass it already quoted (`"\"de_DE\""`) to
+    /// keep its case on PostgreSQL.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::builder::dyn_col;
+    ///
+    /// let (sql, _) = dyn_col("name").collate("NOCASE").eq("ada").build();
+    /// assert_eq!(sql, "name COLLATE NOCASE = ?");
+    /// ```
+    #[must_use]
+    pub fn collate(self, collation: &str) -> Self {
+        Self {
+            sql: self.sql.then_sql(" COLLATE ").then_table(collation),
             params: self.params,
+            binary: false,
+        }
+    }
+
+    /// Restricts an aggregate to the rows matching `condition`
+    /// (`COUNT(*) FILTER (WHERE ...)`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::builder::dyn_col;
+    /// use oxide_sql_core::builder::func::count_all;
+    ///
+    /// let (sql, params) = count_all().filter(dyn_col("total").gt(0)).build();
+    /// assert_eq!(sql, "COUNT(*) FILTER (WHERE total > ?)");
+    /// assert_eq!(params.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn filter(self, condition: Self) -> Self {
+        let mut params = self.params;
+        params.extend(condition.params);
+        Self {
+            sql: self
+                .sql
+                .then_sql(" FILTER (WHERE ")
+                .then(condition.sql)
+                .then_sql(")"),
+            params,
+            binary: false,
+        }
+    }
+
+    /// Orders the input of an ordered-set aggregate by `cols`, ascending
+    /// (`WITHIN GROUP (ORDER BY ...)`).
+    #[must_use]
+    pub fn within_group(self, cols: &[&str]) -> Self {
+        self.within_group_sql(cols, "")
+    }
+
+    /// Orders the input of an ordered-set aggregate by `cols`, descending.
+    #[must_use]
+    pub fn within_group_desc(self, cols: &[&str]) -> Self {
+        self.within_group_sql(cols, " DESC")
+    }
+
+    fn within_group_sql(self, cols: &[&str], direction: &str) -> Self {
+        let mut sql = self.sql.then_sql(" WITHIN GROUP (ORDER BY ");
+        for (i, col) in cols.iter().enumerate() {
+            if i > 0 {
+                sql.push_sql(", ");
+            }
+            sql = sql.then_column(col).then_sql(direction);
+        }
+        Self {
+            sql: sql.then_sql(")"),
+            params: self.params,
+            binary: false,
         }
     }
 
@@ -237,17 +574,93 @@ impl ExprBuilder {
     #[allow(clippy::should_implement_trait)]
     pub fn not(self) -> Self {
         Self {
-            sql: format!("NOT {}", self.sql),
+            sql: Fragment::sql("NOT ").then(self.sql),
             params: self.params,
+            binary: false,
         }
     }
 
+    /// Creates an addition expression (`expr + value`).
+    ///
+    /// A binary left operand is parenthesized, so chains apply in call
+    /// order: `price.mul(2).sub(1)` renders `(price * ?) - ?`.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: ToSqlValue>(self, value: T) -> Self {
+        Self::arithmetic(self, "+", Self::value(value))
+    }
+
+    /// Creates an addition expression with another expression
+    /// (`expr + other`).
+    #[must_use]
+    pub fn add_expr(self, other: impl Into<Self>) -> Self {
+        Self::arithmetic(self, "+", other.into())
+    }
+
+    /// Creates a subtraction expression (`expr - value`).
+    ///
+    /// A binary left operand is parenthesized, so chains apply in call
+    /// order: `price.mul(2).sub(1)` renders `(price * ?) - ?`.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub<T: ToSqlValue>(self, value: T) -> Self {
+        Self::arithmetic(self, "-", Self::value(value))
+    }
+
+    /// Creates a subtraction expression with another expression
+    /// (`expr - other`).
+    #[must_use]
+    pub fn sub_expr(self, other: impl Into<Self>) -> Self {
+        Self::arithmetic(self, "-", other.into())
+    }
+
+    /// Creates a multiplication expression (`expr * value`).
+    ///
+    /// A binary left operand is parenthesized, so chains apply in call
+    /// order: `price.mul(2).sub(1)` renders `(price * ?) - ?`.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn mul<T: ToSqlValue>(self, value: T) -> Self {
+        Self::arithmetic(self, "*", Self::value(value))
+    }
+
+    /// Creates a multiplication expression with another expression
+    /// (`expr * other`).
+    #[must_use]
+    pub fn mul_expr(self, other: impl Into<Self>) -> Self {
+        Self::arithmetic(self, "*", other.into())
+    }
+
+    /// Creates a division expression (`expr / value`).
+    ///
+    /// A binary left operand is parenthesized, so chains apply in call
+    /// order: `price.mul(2).sub(1)` renders `(price * ?) - ?`.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn div<T: ToSqlValue>(self, value: T) -> Self {
+        Self::arithmetic(self, "/", Self::value(value))
+    }
+
+    /// Creates a division expression with another expression
+    /// (`expr / other`).
+    #[must_use]
+    pub fn div_expr(self, other: impl Into<Self>) -> Self {
+        Self::arithmetic(self, "/", other.into())
+    }
+
     /// Creates an equality expression.
     #[must_use]
     pub fn eq<T: ToSqlValue>(self, value: T) -> Self {
         Self::binary(self, "=", value.to_sql_value().into())
     }
 
+    /// Creates an equality expression against another expression, such as
+    /// a column of an outer query (`o.user_id = u.id`).
+    #[must_use]
+    pub fn eq_expr(self, other: impl Into<Self>) -> Self {
+        Self::binary(self, "=", other.into())
+    }
+
     /// Creates an inequality expression.
     #[must_use]
     pub fn not_eq<T: ToSqlValue>(self, value: T) -> Self {
@@ -310,10 +723,17 @@ impl ExprBuilder {
         Self::in_list_impl(self, sql_values, true)
     }
 
-    /// Returns the SQL string.
+    /// Returns the SQL string, with names quoted as [`GenericDialect`]
+    /// does.
     #[must_use]
-    pub fn sql(&self) -> &str {
-        &self.sql
+    pub fn sql(&self) -> String {
+        self.sql_for(&GenericDialect)
+    }
+
+    /// Returns the SQL string, with names quoted as `dialect` does.
+    #[must_use]
+    pub fn sql_for<D: Dialect + ?Sized>(&self, dialect: &D) -> String {
+        self.sql.to_sql_for(dialect)
     }
 
     /// Returns the parameters.
@@ -322,18 +742,27 @@ impl ExprBuilder {
         &self.params
     }
 
-    /// Consumes the builder and returns the SQL and parameters.
+    /// Consumes the builder and returns the SQL and parameters, with names
+    /// quoted as [`GenericDialect`] does.
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        (self.sql, self.params)
+        self.build_for(&GenericDialect)
+    }
+
+    /// Consumes the builder and returns the SQL and parameters, with names
+    /// quoted as `dialect` does.
+    #[must_use]
+    pub fn build_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        (self.sql.to_sql_for(dialect), self.params)
     }
 }
 
 impl From<ColumnRef> for ExprBuilder {
     fn from(col: ColumnRef) -> Self {
         Self {
-            sql: col.to_sql(),
+            sql: col.fragment(),
             params: vec![],
+            binary: false,
         }
     }
 }
@@ -341,8 +770,246 @@ impl From<ColumnRef> for ExprBuilder {
 impl From<SqlValue> for ExprBuilder {
     fn from(value: SqlValue) -> Self {
         Self {
-            sql: String::from("?"),
+            sql: Fragment::sql("?"),
             params: vec![value],
+            binary: false,
+        }
+    }
+}
+
+/// Converts a parsed AST expression into a parameterized builder expression.
+///
+/// Every literal except `NULL` becomes a `?` placeholder with its value
+/// moved into the parameter list. Subqueries are rendered as-is, keeping
+/// their literals inline.
+///
+/// Date and time literals are bound as `CAST(? AS type)`; use
+/// [`ExprBuilder::from_ast_for`] for dialects without temporal types.
+impl From<&Expr> for ExprBuilder {
+    fn from(expr: &Expr) -> Self {
+        Self::from_ast_for(expr, &GenericDialect::new())
+    }
+}
+
+impl ExprBuilder {
+    /// Converts a parsed AST expression like `From<&Expr>`, binding date
+    /// and time literals as `dialect` spells them (see
+    /// [`Dialect::temporal_value`]).
+    #[must_use]
+    pub fn from_ast_for<D: Dialect + ?Sized>(expr: &Expr, dialect: &D) -> Self {
+        let mut builder = Self::raw(String::new());
+        builder.push_ast(expr, dialect);
+        builder
+    }
+
+    /// Appends a comparison operand, binding a `NULL` literal as a parameter.
+    fn push_operand<D: Dialect + ?Sized>(&mut self, expr: &Expr, dialect: &D) {
+        if is_null_literal(expr) {
+            self.sql.push_sql("?");
+            self.params.push(SqlValue::Null);
+        } else {
+            self.push_ast(expr, dialect);
+        }
+    }
+
+    /// Appends the rendering of an AST expression, in the same order as the
+    /// `Display` impl of [`Expr`] so placeholders line up with parameters.
+    fn push_ast<D: Dialect + ?Sized>(&mut self, expr: &Expr, dialect: &D) {
+        match expr {
+            Expr::Literal(Literal::Null) => self.sql.push_sql("NULL"),
+            // No parameter type holds these without losing precision, so
+            // they are inlined; anything but a plain number is bound as text.
+            Expr::Literal(Literal::BigNumber(digits)) if is_plain_number(digits) => {
+                self.sql.push_sql(digits);
+            }
+            Expr::Literal(lit) => {
+                // Typed literals are bound as the dialect spells them.
+                if let Some((type_name, text)) = lit.temporal() {
+                    let (sql, params) = dialect.temporal_value(type_name, text).into_parts();
+                    self.sql.append(sql);
+                    self.params.extend(params);
+                    return;
+                }
+                self.sql.push_sql("?");
+                self.params.push(match lit {
+                    Literal::Integer(n) => SqlValue::Int(*n),
+                    Literal::Float(f) => SqlValue::Float(*f),
+                    Literal::String(s)
+                    | Literal::BigNumber(s)
+                    | Literal::Date(s)
+                    | Literal::Time(s)
+                    | Literal::Timestamp(s)
+                    | Literal::Interval(s) => SqlValue::Text(s.clone()),
+                    Literal::Blob(b) => SqlValue::Blob(b.clone()),
+                    Literal::Boolean(b) => SqlValue::Bool(*b),
+                    Literal::Null => SqlValue::Null,
+                });
+            }
+            // A builder binds `None` as a parameter (`a = ?`); bind it again
+            // so the comparison means what it meant before the round trip.
+            Expr::Binary {
+                left,
+                op: op @ (BinaryOp::Eq | BinaryOp::NotEq),
+                right,
+            } if is_null_literal(left) || is_null_literal(right) => {
+                self.push_operand(left, dialect);
+                self.sql.push_sql(" ");
+                self.sql.push_sql(op.as_str());
+                self.sql.push_sql(" ");
+                self.push_operand(right, dialect);
+            }
+            Expr::Binary { left, op, right } => {
+                self.push_ast(left, dialect);
+                self.sql.push_sql(" ");
+                self.sql.push_sql(op.as_str());
+                self.sql.push_sql(" ");
+                self.push_ast(right, dialect);
+            }
+            Expr::Unary { op, operand } => {
+                self.sql.push_sql(match op {
+                    UnaryOp::Not => "NOT ",
+                    // `--` would start a comment.
+                    UnaryOp::Neg if operand.starts_with_minus() => "- ",
+                    UnaryOp::Neg => "-",
+                    UnaryOp::BitNot => "~",
+                });
+                self.push_ast(operand, dialect);
+            }
+            Expr::Function(func) => {
+                self.sql.push_sql(&func.name);
+                self.sql.push_sql("(");
+                if func.distinct {
+                    self.sql.push_sql("DISTINCT ");
+                }
+                self.push_ast_list(&func.args, dialect);
+                self.sql.push_sql(")");
+                for (i, order) in func.within_group.iter().enumerate() {
+                    self.sql.push_sql(if i == 0 {
+                        " WITHIN GROUP (ORDER BY "
+                    } else {
+                        ", "
+                    });
+                    self.push_ast(&order.expr, dialect);
+                    self.sql.push_sql(&format!(" {}", order.direction));
+                    if let Some(nulls) = &order.nulls {
+                        self.sql.push_sql(&format!(" {nulls}"));
+                    }
+                }
+                if !func.within_group.is_empty() {
+                    self.sql.push_sql(")");
+                }
+                if let Some(filter) = &func.filter {
+                    self.sql.push_sql(" FILTER (WHERE ");
+                    self.push_ast(filter, dialect);
+                    self.sql.push_sql(")");
+                }
+            }
+            Expr::IsNull { expr, negated } => {
+                self.push_ast(expr, dialect);
+                self.sql
+                    .push_sql(if *negated { " IS NOT NULL" } else { " IS NULL" });
+            }
+            Expr::In {
+                expr,
+                list,
+                negated,
+            } => {
+                self.push_ast(expr, dialect);
+                self.sql
+                    .push_sql(if *negated { " NOT IN (" } else { " IN (" });
+                self.push_ast_list(list, dialect);
+                self.sql.push_sql(")");
+            }
+            Expr::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => {
+                self.push_ast(expr, dialect);
+                self.sql.push_sql(if *negated {
+                    " NOT BETWEEN "
+                } else {
+                    " BETWEEN "
+                });
+                self.push_ast(low, dialect);
+                self.sql.push_sql(" AND ");
+                self.push_ast(high, dialect);
+            }
+            Expr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                self.sql.push_sql("CASE");
+                if let Some(op) = operand {
+                    self.sql.push_sql(" ");
+                    self.push_ast(op, dialect);
+                }
+                for (when, then) in when_clauses {
+                    self.sql.push_sql(" WHEN ");
+                    self.push_ast(when, dialect);
+                    self.sql.push_sql(" THEN ");
+                    self.push_ast(then, dialect);
+                }
+                if let Some(el) = else_clause {
+                    self.sql.push_sql(" ELSE ");
+                    self.push_ast(el, dialect);
+                }
+                self.sql.push_sql(" END");
+            }
+            Expr::Cast { expr, data_type } => {
+                self.sql.push_sql("CAST(");
+                self.push_ast(expr, dialect);
+                self.sql.push_sql(&format!(" AS {data_type})"));
+            }
+            Expr::Paren(inner) => {
+                self.sql.push_sql("(");
+                self.push_ast(inner, dialect);
+                self.sql.push_sql(")");
+            }
+            Expr::Collate { expr, collation } => {
+                self.push_ast(expr, dialect);
+                self.sql.push_sql(&format!(" COLLATE {collation}"));
+            }
+            Expr::Array(elements) => {
+                self.sql.push_sql("ARRAY[");
+                self.push_ast_list(elements, dialect);
+                self.sql.push_sql("]");
+            }
+            Expr::Quantified {
+                left,
+                op,
+                quantifier,
+                right,
+            } => {
+                self.push_ast(left, dialect);
+                self.sql.push_sql(" ");
+                self.sql.push_sql(op.as_str());
+                self.sql.push_sql(" ");
+                self.sql.push_sql(quantifier.as_str());
+                if matches!(right.as_ref(), Expr::Subquery(_)) {
+                    self.push_ast(right, dialect);
+                } else {
+                    self.sql.push_sql("(");
+                    self.push_ast(right, dialect);
+                    self.sql.push_sql(")");
+                }
+            }
+            Expr::Column { .. }
+            | Expr::Subquery(_)
+            | Expr::Exists(_)
+            | Expr::Parameter { .. }
+            | Expr::Wildcard { .. } => self.sql.push_sql(&expr.to_string()),
+        }
+    }
+
+    fn push_ast_list<D: Dialect + ?Sized>(&mut self, exprs: &[Expr], dialect: &D) {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                self.sql.push_sql(", ");
+            }
+            self.push_ast(expr, dialect);
         }
     }
 }
@@ -350,6 +1017,7 @@ impl From<SqlValue> for ExprBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builder::test_support::Backticks;
 
     #[test]
     fn test_column_eq() {
@@ -403,6 +1071,31 @@ mod tests {
         assert_eq!(expr.params().len(), 3);
     }
 
+    #[test]
+    fn test_arithmetic() {
+        let expr = ExprBuilder::column("views").add(1);
+        assert_eq!(expr.sql(), "views + ?");
+        assert_eq!(expr.params(), &[SqlValue::Int(1)]);
+
+        let expr = ExprBuilder::column("price").mul(2).sub(0.5).div(3);
+        assert_eq!(expr.sql(), "((price * ?) - ?) / ?");
+        assert_eq!(expr.params().len(), 3);
+    }
+
+    #[test]
+    fn test_arithmetic_with_expressions() {
+        let total = ExprBuilder::column("price").mul_expr(ExprBuilder::column("quantity"));
+        let expr = total.sub_expr(ExprBuilder::column("discount").add(1));
+        assert_eq!(expr.sql(), "(price * quantity) - (discount + ?)");
+        assert_eq!(expr.params(), &[SqlValue::Int(1)]);
+
+        let expr = ExprBuilder::column("a").div_expr(ExprBuilder::column("b").paren());
+        assert_eq!(expr.sql(), "a / (b)");
+
+        let expr = ExprBuilder::column("views").add(1).gt(10);
+        assert_eq!(expr.sql(), "views + ? > ?");
+    }
+
     #[test]
     fn test_qualified_column() {
         let expr = ColumnRef::qualified("users", "name").eq("Bob");
@@ -418,4 +1111,167 @@ mod tests {
         // The malicious input is stored safely as a parameter
         assert!(matches!(&expr.params()[0], SqlValue::Text(s) if s == malicious));
     }
+
+    #[test]
+    fn test_from_ast_parameterizes_literals() {
+        let ast = Expr::column("age")
+            .gt(Expr::integer(18))
+            .and(Expr::column("name").in_list(vec![Expr::string("a"), Expr::string("b")]))
+            .and(Expr::column("deleted_at").is_null());
+        let expr = ExprBuilder::from(&ast);
+        assert_eq!(
+            expr.sql(),
+            "age > ? AND name IN (?, ?) AND deleted_at IS NULL"
+        );
+        assert_eq!(
+            expr.params(),
+            &[
+                SqlValue::Int(18),
+                SqlValue::Text(String::from("a")),
+                SqlValue::Text(String::from("b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_ast_binds_null_comparisons() {
+        let ast = Expr::column("a").eq(Expr::null());
+        let expr = ExprBuilder::from(&ast);
+        assert_eq!(expr.sql(), "a = ?");
+        assert_eq!(expr.params(), &[SqlValue::Null]);
+
+        let ast = Expr::null()
+            .binary(BinaryOp::NotEq, Expr::column("b"))
+            .and(Expr::column("c").eq(Expr::integer(1)));
+        let expr = ExprBuilder::from(&ast);
+        assert_eq!(expr.sql(), "? != b AND c = ?");
+        assert_eq!(expr.params(), &[SqlValue::Null, SqlValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_from_ast_inlines_big_numbers() {
+        let ast = Expr::column("id")
+            .eq(Expr::Literal(Literal::BigNumber(String::from(
+                "18446744073709551616",
+            ))))
+            .or(Expr::column("id").eq(Expr::Literal(Literal::BigNumber(String::from("1 OR 1=1")))))
+            .or(Expr::column("id").eq(Expr::Literal(Literal::BigNumber(String::from("1--")))));
+        let expr = ExprBuilder::from(&ast);
+        assert_eq!(expr.sql(), "id = 18446744073709551616 OR id = ? OR id = ?");
+        assert_eq!(
+            expr.params(),
+            &[
+                SqlValue::Text(String::from("1 OR 1=1")),
+                SqlValue::Text(String::from("1--")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_plain_number() {
+        for number in ["0", "-12", "1.5", "-0.25e10", "3E+8", "6e-2"] {
+            assert!(is_plain_number(number), "{number}");
+        }
+        for not_number in [
+            "", "-", "1--", "1.", ".5", "1e", "1e+", "1.2.3", "1e5e5", "--1", "1 ",
+        ] {
+            assert!(!is_plain_number(not_number), "{not_number}");
+        }
+    }
+
+    #[test]
+    fn test_from_ast_casts_temporal_literals() {
+        let ast = Expr::column("created_at")
+            .gt_eq(Expr::date("2024-01-01"))
+            .and(Expr::column("created_at").lt(Expr::timestamp("2024-02-01 00:00:00")));
+        let expr = ExprBuilder::from(&ast);
+        assert_eq!(
+            expr.sql(),
+            "created_at >= CAST(? AS DATE) AND created_at < CAST(? AS TIMESTAMP)"
+        );
+        assert_eq!(
+            expr.params(),
+            &[
+                SqlValue::Text(String::from("2024-01-01")),
+                SqlValue::Text(String::from("2024-02-01 00:00:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_get() {
+        let (sql, params) = dyn_col("data").json_get("name").eq("Ada").build();
+        assert_eq!(sql, "data ->> ? = ?");
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Text(String::from("name")),
+                SqlValue::Text(String::from("Ada")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collate() {
+        let (sql, _) = dyn_col("name").collate("NOCASE").eq("ada").build();
+        assert_eq!(sql, "name COLLATE NOCASE = ?");
+
+        let (sql, _) = ExprBuilder::column("u.name").collate("\"de_DE\"").build();
+        assert_eq!(sql, "u.name COLLATE \"de_DE\"");
+
+        let (sql, _) = dyn_col("name").collate("C; DROP").build();
+        assert_eq!(sql, "name COLLATE \"C; DROP\"");
+    }
+
+    #[test]
+    fn test_build_for_quotes_as_the_dialect() {
+        let expr = || {
+            ColumnRef::qualified("my table", "order")
+                .collate("de DE")
+                .eq(1)
+                .and(
+                    crate::builder::func::count_all()
+                        .within_group_desc(&["my col"])
+                        .gt(2),
+                )
+        };
+        assert_eq!(
+            expr().sql_for(&Backticks),
+            "`my table`.`order` COLLATE `de DE` = ? AND \
+             COUNT(*) WITHIN GROUP (ORDER BY `my col` DESC) > ?"
+        );
+        assert_eq!(
+            expr().build().0,
+            "\"my table\".\"order\" COLLATE \"de DE\" = ? AND \
+             COUNT(*) WITHIN GROUP (ORDER BY \"my col\" DESC) > ?"
+        );
+        assert_eq!(dyn_col("select").to_sql_for(&Backticks), "`select`");
+    }
+
+    #[test]
+    fn test_collate_from_ast() {
+        let ast = Expr::column("name")
+            .collate("NOCASE")
+            .eq(Expr::string("ada"));
+        let (sql, params) = ExprBuilder::from(&ast).build();
+        assert_eq!(sql, "name COLLATE NOCASE = ?");
+        assert_eq!(params, vec![SqlValue::Text(String::from("ada"))]);
+    }
+
+    #[test]
+    fn test_filter_and_within_group_from_ast() {
+        let mut func = crate::ast::FunctionCall::new("PERCENTILE_CONT", vec![Expr::float(0.5)]);
+        func.within_group = vec![crate::ast::OrderBy {
+            expr: Expr::column("x"),
+            direction: crate::ast::OrderDirection::Desc,
+            nulls: None,
+        }];
+        func.filter = Some(Box::new(Expr::column("y").gt(Expr::integer(0))));
+        let (sql, params) = ExprBuilder::from(&Expr::Function(func)).build();
+        assert_eq!(
+            sql,
+            "PERCENTILE_CONT(?) WITHIN GROUP (ORDER BY x DESC) FILTER (WHERE y > ?)"
+        );
+        assert_eq!(params, vec![SqlValue::Float(0.5), SqlValue::Int(0)]);
+    }
 }
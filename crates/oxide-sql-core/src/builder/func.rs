@@ -0,0 +1,300 @@
+//! SQL function call builders.
+//!
+//! These produce [`ExprBuilder`]s that can be used anywhere an expression
+//! is accepted, including SET clauses via `set_expr()`:
+//!
+//! ```rust
+//! use oxide_sql_core::builder::{UpdateDyn, dyn_col};
+//! use oxide_sql_core::builder::func::{lower, now};
+//!
+//! let (sql, params) = UpdateDyn::new()
+//!     .table("users")
+//!     .set_expr("email", lower(dyn_col("email").into()))
+//!     .set_expr("updated_at", now())
+//!     .where_clause(dyn_col("id").eq(1))
+//!     .build();
+//!
+//! assert_eq!(
+//!     sql,
+//!     "UPDATE users SET email = LOWER(email), updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+//! );
+//! assert_eq!(params.len(), 1);
+//! ```
+
+use super::expr::ExprBuilder;
+use super::render::Fragment;
+use crate::dialect::{Dialect, GenericDialect};
+
+/// Creates a call to the SQL function `name` with the given arguments.
+///
+/// **Warning**: `name` is emitted as-is; never pass user input here.
+#[must_use]
+pub fn call(name: &str, args: Vec<ExprBuilder>) -> ExprBuilder {
+    let mut sql = Fragment::sql(format!("{name}("));
+    let mut params = vec![];
+    for (i, arg) in args.into_iter().enumerate() {
+        if i > 0 {
+            sql.push_sql(", ");
+        }
+        let (arg_sql, arg_params) = arg.into_parts();
+        sql.append(arg_sql);
+        params.extend(arg_params);
+    }
+    sql.push_sql(")");
+    ExprBuilder::from_parts(sql, params)
+}
+
+/// `LOWER(expr)`: converts text to lower case.
+#[must_use]
+pub fn lower(expr: ExprBuilder) -> ExprBuilder {
+    call("LOWER", vec![expr])
+}
+
+/// `UPPER(expr)`: converts text to upper case.
+#[must_use]
+pub fn upper(expr: ExprBuilder) -> ExprBuilder {
+    call("UPPER", vec![expr])
+}
+
+/// `TRIM(expr)`: removes leading and trailing spaces.
+#[must_use]
+pub fn trim(expr: ExprBuilder) -> ExprBuilder {
+    call("TRIM", vec![expr])
+}
+
+/// `LENGTH(expr)`: the length of a string.
+#[must_use]
+pub fn length(expr: ExprBuilder) -> ExprBuilder {
+    call("LENGTH", vec![expr])
+}
+
+/// `ABS(expr)`: the absolute value of a number.
+#[must_use]
+pub fn abs(expr: ExprBuilder) -> ExprBuilder {
+    call("ABS", vec![expr])
+}
+
+/// `COALESCE(a, b, ...)`: the first non-NULL argument.
+#[must_use]
+pub fn coalesce(exprs: Vec<ExprBuilder>) -> ExprBuilder {
+    call("COALESCE", exprs)
+}
+
+/// `COUNT(*)`: the number of rows.
+#[must_use]
+pub fn count_all() -> ExprBuilder {
+    ExprBuilder::raw("COUNT(*)")
+}
+
+/// `COUNT(expr)`: the number of non-NULL values.
+#[must_use]
+pub fn count(expr: ExprBuilder) -> ExprBuilder {
+    call("COUNT", vec![expr])
+}
+
+/// `SUM(expr)`: the sum of the values.
+#[must_use]
+pub fn sum(expr: ExprBuilder) -> ExprBuilder {
+    call("SUM", vec![expr])
+}
+
+/// `AVG(expr)`: the average of the values.
+#[must_use]
+pub fn avg(expr: ExprBuilder) -> ExprBuilder {
+    call("AVG", vec![expr])
+}
+
+/// `MIN(expr)`: the smallest value.
+#[must_use]
+pub fn min(expr: ExprBuilder) -> ExprBuilder {
+    call("MIN", vec![expr])
+}
+
+/// `MAX(expr)`: the largest value.
+#[must_use]
+pub fn max(expr: ExprBuilder) -> ExprBuilder {
+    call("MAX", vec![expr])
+}
+
+/// `PERCENTILE_CONT(?)`: the interpolated value at `fraction` of the
+/// ordering given with [`ExprBuilder::within_group`].
+///
+/// ```rust
+/// use oxide_sql_core::builder::func::percentile_cont;
+///
+/// let (sql, _) = percentile_cont(0.5).within_group(&["price"]).build();
+/// assert_eq!(sql, "PERCENTILE_CONT(?) WITHIN GROUP (ORDER BY price)");
+/// ```
+///
+/// Not supported by SQLite.
+#[must_use]
+pub fn percentile_cont(fraction: f64) -> ExprBuilder {
+    call("PERCENTILE_CONT", vec![ExprBuilder::value(fraction)])
+}
+
+/// `PERCENTILE_DISC(?)`: the first value at or after `fraction` of the
+/// ordering given with [`ExprBuilder::within_group`].
+///
+/// Not supported by SQLite.
+#[must_use]
+pub fn percentile_disc(fraction: f64) -> ExprBuilder {
+    call("PERCENTILE_DISC", vec![ExprBuilder::value(fraction)])
+}
+
+/// `CURRENT_TIMESTAMP`: the current date and time.
+///
+/// Rendered as the standard keyword rather than `NOW()`, which SQLite does
+/// not support.
+#[must_use]
+pub fn now() -> ExprBuilder {
+    ExprBuilder::raw("CURRENT_TIMESTAMP")
+}
+
+/// A date such as `"2024-01-01"`, bound as `CAST(? AS DATE)`.
+///
+/// The parameterized form of the literal `DATE '2024-01-01'`. Use
+/// [`date_for`] for dialects without a date type, such as SQLite.
+#[must_use]
+pub fn date(value: &str) -> ExprBuilder {
+    date_for(value, &GenericDialect::new())
+}
+
+/// A date bound as `dialect` spells it (see [`Dialect::temporal_value`]).
+#[must_use]
+pub fn date_for<D: Dialect + ?Sized>(value: &str, dialect: &D) -> ExprBuilder {
+    dialect.temporal_value("DATE", value)
+}
+
+/// A time of day such as `"10:00"`, bound as `CAST(? AS TIME)`.
+#[must_use]
+pub fn time(value: &str) -> ExprBuilder {
+    time_for(value, &GenericDialect::new())
+}
+
+/// A time of day bound as `dialect` spells it.
+#[must_use]
+pub fn time_for<D: Dialect + ?Sized>(value: &str, dialect: &D) -> ExprBuilder {
+    dialect.temporal_value("TIME", value)
+}
+
+/// A timestamp such as `"2024-01-01 10:00:00"`, bound as
+/// `CAST(? AS TIMESTAMP)`.
+#[must_use]
+pub fn timestamp(value: &str) -> ExprBuilder {
+    timestamp_for(value, &GenericDialect::new())
+}
+
+/// A timestamp bound as `dialect` spells it.
+#[must_use]
+pub fn timestamp_for<D: Dialect + ?Sized>(value: &str, dialect: &D) -> ExprBuilder {
+    dialect.temporal_value("TIMESTAMP", value)
+}
+
+/// An interval such as `"1 day"`, bound as `CAST(? AS INTERVAL)`.
+///
+/// Not supported by SQLite, which has no interval type.
+#[must_use]
+pub fn interval(value: &str) -> ExprBuilder {
+    interval_for(value, &GenericDialect::new())
+}
+
+/// An interval bound as `dialect` spells it.
+#[must_use]
+pub fn interval_for<D: Dialect + ?Sized>(value: &str, dialect: &D) -> ExprBuilder {
+    dialect.temporal_value("INTERVAL", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::value::SqlValue;
+    use crate::builder::{UpdateDyn, dyn_col};
+
+    #[test]
+    fn test_call_without_args() {
+        let (sql, params) = call("RANDOM", vec![]).build();
+        assert_eq!(sql, "RANDOM()");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_string_functions() {
+        assert_eq!(lower(dyn_col("name").into()).sql(), "LOWER(name)");
+        assert_eq!(upper(dyn_col("name").into()).sql(), "UPPER(name)");
+        assert_eq!(trim(ExprBuilder::column("name")).sql(), "TRIM(name)");
+        assert_eq!(length(ExprBuilder::column("name")).sql(), "LENGTH(name)");
+    }
+
+    #[test]
+    fn test_coalesce_binds_params_in_order() {
+        let (sql, params) = coalesce(vec![
+            ExprBuilder::column("nickname"),
+            ExprBuilder::value("anonymous"),
+        ])
+        .build();
+        assert_eq!(sql, "COALESCE(nickname, ?)");
+        assert_eq!(params, vec![SqlValue::Text(String::from("anonymous"))]);
+    }
+
+    #[test]
+    fn test_now() {
+        assert_eq!(now().sql(), "CURRENT_TIMESTAMP");
+    }
+
+    #[test]
+    fn test_temporal_values_are_bound() {
+        let (sql, params) = UpdateDyn::new()
+            .table("tasks")
+            .set_expr("due", date("2024-01-01"))
+            .build();
+        assert_eq!(sql, "UPDATE tasks SET due = CAST(? AS DATE)");
+        assert_eq!(params, vec![SqlValue::Text(String::from("2024-01-01"))]);
+        assert_eq!(time("10:00").sql(), "CAST(? AS TIME)");
+        assert_eq!(
+            timestamp("2024-01-01 10:00:00").sql(),
+            "CAST(? AS TIMESTAMP)"
+        );
+        assert_eq!(interval("1 day").sql(), "CAST(? AS INTERVAL)");
+    }
+
+    #[test]
+    fn test_aggregates() {
+        assert_eq!(count_all().sql(), "COUNT(*)");
+        assert_eq!(count(dyn_col("id").into()).sql(), "COUNT(id)");
+        assert_eq!(sum(dyn_col("total").into()).sql(), "SUM(total)");
+        assert_eq!(avg(dyn_col("total").into()).sql(), "AVG(total)");
+        assert_eq!(min(dyn_col("total").into()).sql(), "MIN(total)");
+        assert_eq!(max(dyn_col("total").into()).sql(), "MAX(total)");
+    }
+
+    #[test]
+    fn test_aggregate_filter_binds_params_in_order() {
+        let (sql, params) = sum(ExprBuilder::value(1))
+            .filter(dyn_col("status").eq("paid"))
+            .build();
+        assert_eq!(sql, "SUM(?) FILTER (WHERE status = ?)");
+        assert_eq!(
+            params,
+            vec![SqlValue::Int(1), SqlValue::Text(String::from("paid"))]
+        );
+    }
+
+    #[test]
+    fn test_ordered_set_aggregates() {
+        let (sql, params) = percentile_cont(0.5)
+            .within_group(&["price", "a b"])
+            .filter(dyn_col("active").eq(true))
+            .build();
+        assert_eq!(
+            sql,
+            "PERCENTILE_CONT(?) WITHIN GROUP (ORDER BY price, \"a b\") FILTER (WHERE active = ?)"
+        );
+        assert_eq!(params, vec![SqlValue::Float(0.5), SqlValue::Bool(true)]);
+
+        let (sql, _) = percentile_disc(0.9).within_group_desc(&["latency"]).build();
+        assert_eq!(
+            sql,
+            "PERCENTILE_DISC(?) WITHIN GROUP (ORDER BY latency DESC)"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Defensive limits for generated queries.
+//!
+//! A [`QueryGuard`] is a policy that a service sets up once and applies to
+//! every SELECT it builds from user-controlled filters, so no request can
+//! ask for an unbounded result.
+//!
+//! ```rust
+//! use oxide_sql_core::builder::{QueryGuard, SelectDyn, dyn_col};
+//!
+//! let guard = QueryGuard::new().max_rows(100);
+//!
+//! // A user asked for 10 000 rows; the guard caps it.
+//! let (sql, _) = SelectDyn::new()
+//!     .columns(&["id"])
+//!     .from("events")
+//!     .where_clause(dyn_col("kind").eq("click"))
+//!     .limit(10_000)
+//!     .guard(&guard)
+//!     .build();
+//!
+//! assert_eq!(sql, "SELECT id FROM events WHERE kind = ? LIMIT 100");
+//! ```
+
+/// A policy of defensive limits applied to SELECT builders with their
+/// `guard()` method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryGuard {
+    max_rows: Option<u64>,
+}
+
+impl QueryGuard {
+    /// Creates a guard without any limits.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { max_rows: None }
+    }
+
+    /// Caps the number of rows a query may return.
+    ///
+    /// Guarded queries get `LIMIT n`, or keep their own LIMIT if it is
+    /// lower.
+    #[must_use]
+    pub const fn max_rows(mut self, n: u64) -> Self {
+        self.max_rows = Some(n);
+        self
+    }
+
+    /// Returns the row cap, if any.
+    #[must_use]
+    pub const fn row_limit(&self) -> Option<u64> {
+        self.max_rows
+    }
+
+    /// Returns the LIMIT a query asking for `limit` rows should use.
+    #[must_use]
+    pub fn clamp_limit(&self, limit: Option<u64>) -> Option<u64> {
+        match (limit, self.max_rows) {
+            (Some(limit), Some(max)) => Some(limit.min(max)),
+            (limit, max) => limit.or(max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_limit() {
+        let guard = QueryGuard::new().max_rows(50);
+        assert_eq!(guard.clamp_limit(None), Some(50));
+        assert_eq!(guard.clamp_limit(Some(10)), Some(10));
+        assert_eq!(guard.clamp_limit(Some(500)), Some(50));
+        assert_eq!(QueryGuard::new().clamp_limit(Some(500)), Some(500));
+        assert_eq!(QueryGuard::new().clamp_limit(None), None);
+        assert_eq!(guard.row_limit(), Some(50));
+        assert_eq!(QueryGuard::new().row_limit(), None);
+    }
+}
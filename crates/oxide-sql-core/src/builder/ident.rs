@@ -0,0 +1,357 @@
+//! Identifier validation for dynamic builders.
+//!
+//! Dynamic builders take table and column names as strings. Names that are
+//! plain SQL identifiers (optionally qualified, e.g. `u.id`) or already
+//! quoted identifiers (e.g. `"order"`, as produced by
+//! [`Dialect::quote_identifier`]) are emitted unchanged, except that parts
+//! which are reserved keywords (see [`Dialect::reserved_keywords`],
+//! [`ANSI_RESERVED_KEYWORDS`](crate::dialect::ANSI_RESERVED_KEYWORDS) by
+//! default) are quoted. Anything else is wrapped in the dialect's
+//! identifier quotes with embedded quotes doubled, so it can only ever be
+//! read as a single identifier and never as additional SQL.
+//!
+//! Only the dialect's own identifier quote counts as quoting: for a dialect
+//! quoting with `"`, a backticked name is quoted again as a whole.
+//!
+//! The plain helpers quote for [`GenericDialect`]; the `_for` variants
+//! quote as a given dialect does.
+
+use crate::dialect::{Dialect, GenericDialect};
+
+/// Returns `true` if `name` is a plain, unquoted SQL identifier.
+///
+/// A plain identifier starts with an ASCII letter or underscore, followed
+/// by ASCII letters, digits, underscores, or `$`.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::is_valid_identifier;
+///
+/// assert!(is_valid_identifier("users"));
+/// assert!(is_valid_identifier("_tmp1"));
+/// assert!(!is_valid_identifier("users; DROP TABLE x"));
+/// assert!(!is_valid_identifier("1abc"));
+/// ```
+#[must_use]
+pub fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Returns `true` if `name` is correctly quoted with `quote`, the
+/// dialect's identifier quote, with any embedded quotes doubled.
+fn is_quoted_identifier(name: &str, quote: char) -> bool {
+    if name.len() < 2 || !name.starts_with(quote) || !name.ends_with(quote) {
+        return false;
+    }
+    let inner = &name[1..name.len() - 1];
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == quote && chars.next() != Some(quote) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns `true` if `name` is a plain or quoted identifier.
+fn is_identifier_part(name: &str, quote: char) -> bool {
+    is_valid_identifier(name) || is_quoted_identifier(name, quote)
+}
+
+/// Returns `true` if `name` is a possibly qualified identifier such as
+/// `id`, `u.id`, `main.users`, or `"my table".id`.
+fn is_qualified_identifier(name: &str, quote: char) -> bool {
+    split_qualified(name, quote)
+        .is_some_and(|mut parts| parts.all(|p| is_identifier_part(p, quote)))
+}
+
+/// Iterates over the parts of a qualified name, split on dots that are
+/// outside of quotes.
+struct QualifiedParts<'a> {
+    rest: Option<&'a str>,
+    quote: char,
+}
+
+impl<'a> Iterator for QualifiedParts<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest?;
+        let mut quoted = false;
+        for (i, c) in rest.char_indices() {
+            if c == self.quote {
+                quoted = !quoted;
+            } else if c == '.' && !quoted {
+                self.rest = Some(&rest[i + 1..]);
+                return Some(&rest[..i]);
+            }
+        }
+        self.rest = None;
+        Some(rest)
+    }
+}
+
+/// Splits a qualified name on dots that are outside of `quote`s, or
+/// returns `None` if a quote is left open.
+fn split_qualified(name: &str, quote: char) -> Option<QualifiedParts<'_>> {
+    if name.chars().filter(|c| *c == quote).count() % 2 != 0 {
+        return None;
+    }
+    Some(QualifiedParts {
+        rest: Some(name),
+        quote,
+    })
+}
+
+/// Appends one part of a valid identifier, quoting it if it is a plain
+/// identifier that is also a reserved keyword of `dialect`.
+fn push_part<D: Dialect + ?Sized>(sql: &mut String, part: &str, dialect: &D) {
+    if is_valid_identifier(part) && dialect.is_reserved_keyword(part) {
+        sql.push_str(&dialect.quote_identifier(part));
+    } else {
+        sql.push_str(part);
+    }
+}
+
+/// Appends a valid, possibly qualified identifier part by part.
+fn push_qualified<D: Dialect + ?Sized>(sql: &mut String, name: &str, dialect: &D) {
+    let Some(parts) = split_qualified(name, dialect.identifier_quote()) else {
+        sql.push_str(name);
+        return;
+    };
+    for (i, part) in parts.enumerate() {
+        if i > 0 {
+            sql.push('.');
+        }
+        push_part(sql, part, dialect);
+    }
+}
+
+/// Appends a column name to `sql` like [`sanitize_column_for`], without
+/// allocating for names that need no quoting.
+pub(crate) fn push_column_for<D: Dialect + ?Sized>(sql: &mut String, name: &str, dialect: &D) {
+    let quote = dialect.identifier_quote();
+    if name == "*" {
+        sql.push_str(name);
+    } else if is_qualified_identifier(name, quote) {
+        push_qualified(sql, name, dialect);
+    } else if let Some(table) = name.strip_suffix(".*")
+        && is_qualified_identifier(table, quote)
+    {
+        push_qualified(sql, table, dialect);
+        sql.push_str(".*");
+    } else {
+        sql.push_str(&dialect.quote_identifier(name));
+    }
+}
+
+/// Appends a table name to `sql` like [`sanitize_table_for`], without
+/// allocating for names that need no quoting.
+pub(crate) fn push_table_for<D: Dialect + ?Sized>(sql: &mut String, name: &str, dialect: &D) {
+    if is_qualified_identifier(name, dialect.identifier_quote()) {
+        push_qualified(sql, name, dialect);
+    } else {
+        sql.push_str(&dialect.quote_identifier(name));
+    }
+}
+
+/// Appends a table reference to `sql` like [`sanitize_table_ref_for`],
+/// without allocating for names that need no quoting.
+pub(crate) fn push_table_ref_for<D: Dialect + ?Sized>(sql: &mut String, table: &str, dialect: &D) {
+    let quote = dialect.identifier_quote();
+    let mut words = table.split_whitespace();
+    match (words.next(), words.next(), words.next(), words.next()) {
+        (Some(name), None, None, None) if is_qualified_identifier(name, quote) => {
+            push_qualified(sql, name, dialect);
+        }
+        (Some(name), Some(kw), Some(alias), None)
+            if kw.eq_ignore_ascii_case("AS")
+                && is_qualified_identifier(name, quote)
+                && is_identifier_part(alias, quote) =>
+        {
+            push_qualified(sql, name, dialect);
+            sql.push(' ');
+            sql.push_str(kw);
+            sql.push(' ');
+            push_part(sql, alias, dialect);
+        }
+        _ => sql.push_str(&dialect.quote_identifier(table)),
+    }
+}
+
+/// Renders a column name, keeping valid (qualified) identifiers and `*` as-is
+/// and quoting anything else.
+#[must_use]
+pub fn sanitize_column(name: &str) -> String {
+    sanitize_column_for(name, &GenericDialect)
+}
+
+/// Renders a column name like [`sanitize_column`], quoting as `dialect`
+/// does.
+#[must_use]
+pub fn sanitize_column_for<D: Dialect + ?Sized>(name: &str, dialect: &D) -> String {
+    let mut sql = String::with_capacity(name.len());
+    push_column_for(&mut sql, name, dialect);
+    sql
+}
+
+/// Renders a table name, keeping valid (qualified) identifiers as-is and
+/// quoting anything else.
+#[must_use]
+pub fn sanitize_table(name: &str) -> String {
+    sanitize_table_for(name, &GenericDialect)
+}
+
+/// Renders a table name like [`sanitize_table`], quoting as `dialect` does.
+#[must_use]
+pub fn sanitize_table_for<D: Dialect + ?Sized>(name: &str, dialect: &D) -> String {
+    let mut sql = String::with_capacity(name.len());
+    push_table_for(&mut sql, name, dialect);
+    sql
+}
+
+/// Renders a table reference that may carry an alias (`users AS u`),
+/// quoting the whole input if it is anything else.
+///
+/// The alias needs the `AS` keyword: `my table` is one table name, quoted
+/// as `"my table"` just as [`sanitize_table`] quotes it.
+#[must_use]
+pub fn sanitize_table_ref(table: &str) -> String {
+    sanitize_table_ref_for(table, &GenericDialect)
+}
+
+/// Renders a table reference like [`sanitize_table_ref`], quoting as
+/// `dialect` does.
+#[must_use]
+pub fn sanitize_table_ref_for<D: Dialect + ?Sized>(table: &str, dialect: &D) -> String {
+    let mut sql = String::with_capacity(table.len());
+    push_table_ref_for(&mut sql, table, dialect);
+    sql
+}
+
+/// Renders a collation name such as `NOCASE` or `pg_catalog."C"`, keeping
+/// valid (qualified) identifiers as-is and quoting anything else.
+#[must_use]
+pub fn sanitize_collation(name: &str) -> String {
+    sanitize_table(name)
+}
+
+/// Renders a collation name like [`sanitize_collation`], quoting as
+/// `dialect` does.
+#[must_use]
+pub fn sanitize_collation_for<D: Dialect + ?Sized>(name: &str, dialect: &D) -> String {
+    sanitize_table_for(name, dialect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::test_support::Backticks;
+
+    #[test]
+    fn test_valid_identifiers() {
+        assert!(is_valid_identifier("a"));
+        assert!(is_valid_identifier("user_id"));
+        assert!(is_valid_identifier("col$1"));
+        assert!(!is_valid_identifier(""));
+        assert!(!is_valid_identifier("a b"));
+        assert!(!is_valid_identifier("a-b"));
+        assert!(!is_valid_identifier("\"a\""));
+    }
+
+    #[test]
+    fn test_sanitize_column() {
+        assert_eq!(sanitize_column("id"), "id");
+        assert_eq!(sanitize_column("u.id"), "u.id");
+        assert_eq!(sanitize_column("*"), "*");
+        assert_eq!(sanitize_column("u.*"), "u.*");
+        assert_eq!(sanitize_column("\"order\""), "\"order\"");
+        assert_eq!(sanitize_column("\"a\"\"b\".c"), "\"a\"\"b\".c");
+        assert_eq!(sanitize_column("id = 1 OR 1"), "\"id = 1 OR 1\"");
+        assert_eq!(sanitize_column("a\"; --"), "\"a\"\"; --\"");
+    }
+
+    #[test]
+    fn test_reserved_keywords_are_quoted() {
+        assert_eq!(sanitize_column("order"), "\"order\"");
+        assert_eq!(sanitize_column("o.order"), "o.\"order\"");
+        assert_eq!(sanitize_column("user.*"), "\"user\".*");
+        assert_eq!(sanitize_column("orders"), "orders");
+        assert_eq!(sanitize_table("group"), "\"group\"");
+        assert_eq!(sanitize_table_ref("order AS o"), "\"order\" AS o");
+        assert_eq!(sanitize_table_ref("orders AS from"), "orders AS \"from\"");
+    }
+
+    #[test]
+    fn test_sanitize_table() {
+        assert_eq!(sanitize_table("users"), "users");
+        assert_eq!(sanitize_table("main.users"), "main.users");
+        assert_eq!(
+            sanitize_table("users; DROP TABLE x"),
+            "\"users; DROP TABLE x\""
+        );
+        assert_eq!(sanitize_table("\"bad\"name\""), "\"\"\"bad\"\"name\"\"\"");
+    }
+
+    #[test]
+    fn test_sanitize_with_dialect_quote() {
+        assert_eq!(sanitize_column_for("id", &Backticks), "id");
+        assert_eq!(sanitize_column_for("a`b c", &Backticks), "`a``b c`");
+        assert_eq!(sanitize_column_for("o.order", &Backticks), "o.`order`");
+        assert_eq!(sanitize_table_for("`my table`", &Backticks), "`my table`");
+        assert_eq!(
+            sanitize_table_ref_for("users; --", &Backticks),
+            "`users; --`"
+        );
+        assert_eq!(sanitize_collation_for("NOCASE", &Backticks), "NOCASE");
+    }
+
+    #[test]
+    fn test_only_the_dialect_quote_is_accepted() {
+        assert_eq!(sanitize_column("`a`"), "\"`a`\"");
+        assert_eq!(sanitize_column("`a`.b"), "\"`a`.b\"");
+        assert_eq!(sanitize_table_ref("`t` x"), "\"`t` x\"");
+        assert_eq!(sanitize_column_for("\"a\"", &Backticks), "`\"a\"`");
+        assert_eq!(sanitize_column_for("`a.b`.c", &Backticks), "`a.b`.c");
+    }
+
+    #[test]
+    fn test_sanitize_with_dialect_keywords() {
+        struct Pivots;
+        impl Dialect for Pivots {
+            fn name(&self) -> &'static str {
+                "pivots"
+            }
+            fn reserved_keywords(&self) -> &'static [&'static str] {
+                &["PIVOT"]
+            }
+        }
+
+        assert_eq!(sanitize_column_for("pivot", &Pivots), "\"pivot\"");
+        assert_eq!(sanitize_column("pivot"), "pivot");
+        assert_eq!(sanitize_table_for("order", &Pivots), "order");
+        assert_eq!(
+            sanitize_table_ref_for("t AS pivot", &Pivots),
+            "t AS \"pivot\""
+        );
+    }
+
+    #[test]
+    fn test_sanitize_table_ref() {
+        assert_eq!(sanitize_table_ref("users"), "users");
+        assert_eq!(sanitize_table_ref("users u"), "\"users u\"");
+        assert_eq!(sanitize_table_ref("my table"), sanitize_table("my table"));
+        assert_eq!(sanitize_table_ref("users AS u"), "users AS u");
+        assert_eq!(
+            sanitize_table_ref("users; DROP TABLE x"),
+            "\"users; DROP TABLE x\""
+        );
+        assert_eq!(sanitize_table_ref("users u WHERE 1"), "\"users u WHERE 1\"");
+    }
+}
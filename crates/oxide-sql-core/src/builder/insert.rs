@@ -3,8 +3,15 @@
 //! This module provides string-based query building. For compile-time
 //! validated queries using schema traits, use `Insert` from `builder::typed`.
 
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::cache::CacheableQuery;
+use super::ident::{push_column_for, push_table_for};
+use super::query::Query;
+use super::render::{list_len, push_placeholders};
 use super::value::{SqlValue, ToSqlValue};
 
 // Typestate markers
@@ -112,39 +119,85 @@ impl InsertDyn<HasTable, HasValues> {
     }
 
     /// Builds the INSERT statement and returns SQL with parameters.
+    ///
+    /// Names are quoted as [`GenericDialect`] does; use
+    /// [`build_for`](Self::build_for) to quote for another dialect.
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("INSERT INTO ");
-        let mut params = vec![];
+        self.build_for(&GenericDialect)
+    }
+
+    /// Builds the INSERT statement, quoting table and column names as
+    /// `dialect` does, and returns SQL with parameters.
+    #[must_use]
+    pub fn build_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(self.estimated_len());
+        let params = self.build_into_for(&mut sql, dialect);
+        (sql, params)
+    }
+
+    /// Renders the INSERT statement into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings, so one
+    /// buffer can be reused across statements.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.build_into_for(sql, &GenericDialect)
+    }
 
+    /// Renders the INSERT statement into `sql` like
+    /// [`build_into`](Self::build_into), quoting names as `dialect` does.
+    pub fn build_into_for<D: Dialect + ?Sized>(
+        self,
+        sql: &mut String,
+        dialect: &D,
+    ) -> Vec<SqlValue> {
+        sql.push_str("INSERT INTO ");
         if let Some(ref table) = self.table {
-            sql.push_str(table);
+            push_table_for(sql, table, dialect);
         }
 
         if !self.columns.is_empty() {
             sql.push_str(" (");
-            sql.push_str(&self.columns.join(", "));
+            for (i, column) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    sql.push_str(", ");
+                }
+                push_column_for(sql, column, dialect);
+            }
             sql.push(')');
         }
 
         sql.push_str(" VALUES ");
 
-        let row_strs: Vec<String> = self
-            .values
-            .iter()
-            .map(|row| {
-                let placeholders: Vec<&str> = row.iter().map(|_| "?").collect();
-                format!("({})", placeholders.join(", "))
-            })
-            .collect();
-
-        sql.push_str(&row_strs.join(", "));
-
-        for row in self.values {
+        let mut params = Vec::with_capacity(self.values.iter().map(Vec::len).sum());
+        for (i, row) in self.values.into_iter().enumerate() {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+            sql.push('(');
+            push_placeholders(sql, row.len());
+            sql.push(')');
             params.extend(row);
         }
 
-        (sql, params)
+        params
+    }
+
+    /// Estimates the length of the rendered SQL, for pre-sizing buffers.
+    fn estimated_len(&self) -> usize {
+        24 + self.table.as_ref().map_or(0, String::len)
+            + list_len(&self.columns)
+            + self
+                .values
+                .iter()
+                .map(|row| row.len() * 3 + 2)
+                .sum::<usize>()
+    }
+
+    /// Builds the INSERT statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the INSERT statement and returns only the SQL string.
@@ -155,11 +208,66 @@ impl InsertDyn<HasTable, HasValues> {
         let (sql, _) = self.build();
         sql
     }
+
+    /// Builds as many INSERT statements as needed to keep each within
+    /// [`Dialect::max_parameters`], splitting the rows in order.
+    ///
+    /// Returns a single statement when all rows fit. A row with more
+    /// values than the limit still gets a statement of its own. Names are
+    /// quoted as `dialect` does.
+    #[must_use]
+    pub fn build_split<D: Dialect + ?Sized>(self, dialect: &D) -> Vec<Query> {
+        let rows_per_statement = rows_per_statement(&self.values, dialect.max_parameters());
+        let mut rows = self.values.into_iter().peekable();
+        let mut queries = vec![];
+        while rows.peek().is_some() {
+            let chunk = InsertDyn::<HasTable, HasValues> {
+                table: self.table.clone(),
+                columns: self.columns.clone(),
+                values: rows.by_ref().take(rows_per_statement).collect(),
+                _state: PhantomData,
+            };
+            queries.push(Query::from(chunk.build_for(dialect)));
+        }
+        queries
+    }
+}
+
+/// Returns how many rows fit in one statement with at most `max_parameters`
+/// bound parameters, going by the widest row. Always at least one.
+fn rows_per_statement(rows: &[Vec<SqlValue>], max_parameters: usize) -> usize {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    max_parameters
+        .checked_div(width)
+        .unwrap_or(rows.len())
+        .max(1)
+}
+
+impl CacheableQuery for InsertDyn<HasTable, HasValues> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "insert".hash(state);
+        self.table.hash(state);
+        self.columns.hash(state);
+        for row in &self.values {
+            row.len().hash(state);
+        }
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        let mut params = Vec::with_capacity(self.values.iter().map(Vec::len).sum());
+        params.extend(self.values.into_iter().flatten());
+        params
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.build_for(dialect)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builder::test_support::Backticks;
 
     #[test]
     fn test_simple_insert() {
@@ -224,4 +332,127 @@ mod tests {
         // Malicious input is safely stored as parameter
         assert!(matches!(&params[0], SqlValue::Text(s) if s == malicious));
     }
+
+    #[test]
+    fn test_build_split_respects_max_parameters() {
+        struct TinyDialect;
+        impl Dialect for TinyDialect {
+            fn name(&self) -> &'static str {
+                "tiny"
+            }
+            fn max_parameters(&self) -> usize {
+                5
+            }
+        }
+
+        let rows: Vec<Vec<i64>> = (0..5).map(|i| vec![i, i * 10]).collect();
+        let queries = InsertDyn::new()
+            .into_table("points")
+            .columns(&["x", "y"])
+            .values_many(rows)
+            .build_split(&TinyDialect);
+
+        assert_eq!(queries.len(), 3);
+        assert_eq!(
+            queries[0].sql(),
+            "INSERT INTO points (x, y) VALUES (?, ?), (?, ?)"
+        );
+        assert_eq!(queries[2].sql(), "INSERT INTO points (x, y) VALUES (?, ?)");
+        let params: Vec<SqlValue> = queries.into_iter().flat_map(|q| q.into_parts().1).collect();
+        assert_eq!(params.len(), 10);
+        assert_eq!(params[9], SqlValue::Int(40));
+    }
+
+    #[test]
+    fn test_build_split_uses_generic_limit() {
+        let rows: Vec<Vec<i64>> = (0..1000).map(|i| vec![i]).collect();
+        let queries = InsertDyn::new()
+            .into_table("ids")
+            .columns(&["id"])
+            .values_many(rows)
+            .build_split(&GenericDialect::new());
+
+        let sizes: Vec<usize> = queries.iter().map(|q| q.params().len()).collect();
+        assert_eq!(sizes, [999, 1]);
+    }
+
+    #[test]
+    fn test_build_for_quotes_as_the_dialect() {
+        /// [`Backticks`] with room for two parameters per statement.
+        struct SmallBackticks;
+        impl Dialect for SmallBackticks {
+            fn name(&self) -> &'static str {
+                Backticks.name()
+            }
+            fn identifier_quote(&self) -> char {
+                Backticks.identifier_quote()
+            }
+            fn max_parameters(&self) -> usize {
+                2
+            }
+        }
+
+        let insert = || {
+            InsertDyn::new()
+                .into_table("my table")
+                .columns(&["order", "id"])
+                .values(vec![1, 2])
+                .and_values(vec![3, 4])
+        };
+        let (sql, _) = insert().build_for(&Backticks);
+        assert_eq!(
+            sql,
+            "INSERT INTO `my table` (`order`, id) VALUES (?, ?), (?, ?)"
+        );
+        let queries = insert().build_split(&SmallBackticks);
+        assert_eq!(queries.len(), 2);
+        assert_eq!(
+            queries[1].sql(),
+            "INSERT INTO `my table` (`order`, id) VALUES (?, ?)"
+        );
+    }
+
+    #[test]
+    fn test_build_split_single_statement_when_within_limit() {
+        let queries = InsertDyn::new()
+            .into_table("users")
+            .columns(&["name"])
+            .values(vec!["Alice"])
+            .and_values(vec!["Bob"])
+            .build_split(&crate::dialect::GenericDialect::new());
+
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].sql(), "INSERT INTO users (name) VALUES (?), (?)");
+    }
+
+    #[test]
+    fn test_rows_per_statement() {
+        let row = |n: usize| vec![SqlValue::Null; n];
+        assert_eq!(rows_per_statement(&[row(3), row(3)], 999), 333);
+        assert_eq!(rows_per_statement(&[row(2), row(4)], 10), 2);
+        assert_eq!(rows_per_statement(&[row(20)], 10), 1);
+        assert_eq!(rows_per_statement(&[row(0), row(0)], 10), 2);
+    }
+
+    #[test]
+    fn test_build_into_appends_to_buffer() {
+        let build = || {
+            InsertDyn::new()
+                .into_table("users")
+                .columns(&["name", "age"])
+                .values(vec![SqlValue::from("a"), SqlValue::from(1)])
+                .and_values(vec![SqlValue::from("b"), SqlValue::from(2)])
+        };
+
+        let mut sql = String::from("-- batch\n");
+        let params = build().build_into(&mut sql);
+
+        let (expected_sql, expected_params) = build().build();
+        assert_eq!(sql, format!("-- batch\n{expected_sql}"));
+        assert_eq!(params, expected_params);
+        assert_eq!(
+            expected_sql,
+            "INSERT INTO users (name, age) VALUES (?, ?), (?, ?)"
+        );
+    }
 }
@@ -0,0 +1,522 @@
+//! Dynamic MERGE statement builder using the typestate pattern.
+//!
+//! A MERGE needs a target table, a source with a join condition, and at
+//! least one WHEN clause; the builder only offers `build()` once all three
+//! are present.
+//!
+//! ```rust
+//! use oxide_sql_core::builder::{ExprBuilder, MergeDyn};
+//!
+//! let (sql, params) = MergeDyn::new()
+//!     .into_table("users")
+//!     .using("staging AS s", ExprBuilder::raw("users.id = s.id"))
+//!     .when_matched_update(vec![("name", ExprBuilder::column("s.name"))])
+//!     .when_not_matched_insert(
+//!         &["id", "name"],
+//!         vec![ExprBuilder::column("s.id"), ExprBuilder::column("s.name")],
+//!     )
+//!     .build();
+//!
+//! assert_eq!(
+//!     sql,
+//!     "MERGE INTO users USING staging AS s ON users.id = s.id \
+//!      WHEN MATCHED THEN UPDATE SET name = s.name \
+//!      WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)"
+//! );
+//! assert!(params.is_empty());
+//! ```
+
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::cache::{CacheableQuery, hash_expr};
+use super::expr::ExprBuilder;
+use super::ident::{push_column_for, push_table_for, push_table_ref_for};
+use super::query::Query;
+use super::render::{expr_len, push_expr};
+use super::value::SqlValue;
+
+// Typestate markers
+
+/// Marker: No target table specified yet.
+pub struct NoTable;
+/// Marker: Target table has been specified.
+pub struct HasTable;
+/// Marker: No source specified yet.
+pub struct NoSource;
+/// Marker: Source and join condition have been specified.
+pub struct HasSource;
+/// Marker: No WHEN clause specified yet.
+pub struct NoWhen;
+/// Marker: At least one WHEN clause has been specified.
+pub struct HasWhen;
+
+/// What a WHEN clause does with a row.
+#[derive(Clone)]
+enum Action {
+    Update(Vec<(String, ExprBuilder)>),
+    Delete,
+    Insert(Vec<String>, Vec<ExprBuilder>),
+}
+
+/// A `WHEN [NOT] MATCHED [AND condition] THEN action` clause.
+#[derive(Clone)]
+struct Clause {
+    matched: bool,
+    condition: Option<ExprBuilder>,
+    action: Action,
+}
+
+impl Clause {
+    /// Returns the clause's expressions in rendering order.
+    fn exprs(&self) -> impl Iterator<Item = &ExprBuilder> {
+        let action: Vec<&ExprBuilder> = match &self.action {
+            Action::Update(assignments) => assignments.iter().map(|(_, e)| e).collect(),
+            Action::Delete => vec![],
+            Action::Insert(_, values) => values.iter().collect(),
+        };
+        self.condition.iter().chain(action)
+    }
+}
+
+/// A dynamic MERGE statement builder using string-based names.
+pub struct MergeDyn<Table, Source, When> {
+    table: Option<String>,
+    source: Option<String>,
+    on: Option<ExprBuilder>,
+    clauses: Vec<Clause>,
+    _state: PhantomData<(Table, Source, When)>,
+}
+
+impl MergeDyn<NoTable, NoSource, NoWhen> {
+    /// Creates a new MERGE builder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            table: None,
+            source: None,
+            on: None,
+            clauses: vec![],
+            _state: PhantomData,
+        }
+    }
+}
+
+impl Default for MergeDyn<NoTable, NoSource, NoWhen> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Transition: NoTable -> HasTable
+impl MergeDyn<NoTable, NoSource, NoWhen> {
+    /// Specifies the table to merge into.
+    #[must_use]
+    pub fn into_table(self, table: &str) -> MergeDyn<HasTable, NoSource, NoWhen> {
+        MergeDyn {
+            table: Some(String::from(table)),
+            source: self.source,
+            on: self.on,
+            clauses: self.clauses,
+            _state: PhantomData,
+        }
+    }
+}
+
+// Transition: NoSource -> HasSource
+impl MergeDyn<HasTable, NoSource, NoWhen> {
+    /// Specifies the source table (optionally aliased, e.g. `staging AS s`)
+    /// and the condition matching its rows to the target's.
+    #[must_use]
+    pub fn using(self, source: &str, on: ExprBuilder) -> MergeDyn<HasTable, HasSource, NoWhen> {
+        MergeDyn {
+            table: self.table,
+            source: Some(String::from(source)),
+            on: Some(on),
+            clauses: self.clauses,
+            _state: PhantomData,
+        }
+    }
+}
+
+// Adding WHEN clauses: NoWhen -> HasWhen
+impl<When> MergeDyn<HasTable, HasSource, When> {
+    fn clause(
+        mut self,
+        matched: bool,
+        condition: Option<ExprBuilder>,
+        action: Action,
+    ) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clauses.push(Clause {
+            matched,
+            condition,
+            action,
+        });
+        MergeDyn {
+            table: self.table,
+            source: self.source,
+            on: self.on,
+            clauses: self.clauses,
+            _state: PhantomData,
+        }
+    }
+
+    /// Adds `WHEN MATCHED THEN UPDATE SET ...`.
+    #[must_use]
+    pub fn when_matched_update(
+        self,
+        assignments: Vec<(&str, ExprBuilder)>,
+    ) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clause(true, None, update(assignments))
+    }
+
+    /// Adds `WHEN MATCHED AND condition THEN UPDATE SET ...`.
+    #[must_use]
+    pub fn when_matched_update_if(
+        self,
+        condition: ExprBuilder,
+        assignments: Vec<(&str, ExprBuilder)>,
+    ) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clause(true, Some(condition), update(assignments))
+    }
+
+    /// Adds `WHEN MATCHED THEN DELETE`.
+    #[must_use]
+    pub fn when_matched_delete(self) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clause(true, None, Action::Delete)
+    }
+
+    /// Adds `WHEN MATCHED AND condition THEN DELETE`.
+    #[must_use]
+    pub fn when_matched_delete_if(
+        self,
+        condition: ExprBuilder,
+    ) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clause(true, Some(condition), Action::Delete)
+    }
+
+    /// Adds `WHEN NOT MATCHED THEN INSERT (columns) VALUES (values)`.
+    #[must_use]
+    pub fn when_not_matched_insert(
+        self,
+        columns: &[&str],
+        values: Vec<ExprBuilder>,
+    ) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clause(false, None, insert(columns, values))
+    }
+
+    /// Adds `WHEN NOT MATCHED AND condition THEN INSERT ...`.
+    #[must_use]
+    pub fn when_not_matched_insert_if(
+        self,
+        condition: ExprBuilder,
+        columns: &[&str],
+        values: Vec<ExprBuilder>,
+    ) -> MergeDyn<HasTable, HasSource, HasWhen> {
+        self.clause(false, Some(condition), insert(columns, values))
+    }
+}
+
+fn update(assignments: Vec<(&str, ExprBuilder)>) -> Action {
+    Action::Update(
+        assignments
+            .into_iter()
+            .map(|(column, value)| (String::from(column), value))
+            .collect(),
+    )
+}
+
+fn insert(columns: &[&str], values: Vec<ExprBuilder>) -> Action {
+    Action::Insert(columns.iter().map(|&c| String::from(c)).collect(), values)
+}
+
+impl MergeDyn<HasTable, HasSource, HasWhen> {
+    /// Builds the MERGE statement and returns SQL with parameters.
+    ///
+    /// Names are quoted as [`GenericDialect`] does; use
+    /// [`build_for`](Self::build_for) to quote for another dialect.
+    #[must_use]
+    pub fn build(self) -> (String, Vec<SqlValue>) {
+        self.build_for(&GenericDialect)
+    }
+
+    /// Builds the MERGE statement, quoting table and column names as
+    /// `dialect` does, and returns SQL with parameters.
+    #[must_use]
+    pub fn build_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(self.estimated_len());
+        let params = self.build_into_for(&mut sql, dialect);
+        (sql, params)
+    }
+
+    /// Renders the MERGE statement into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings, so one
+    /// buffer can be reused across statements.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.build_into_for(sql, &GenericDialect)
+    }
+
+    /// Renders the MERGE statement into `sql` like
+    /// [`build_into`](Self::build_into), quoting names as `dialect` does.
+    pub fn build_into_for<D: Dialect + ?Sized>(
+        self,
+        sql: &mut String,
+        dialect: &D,
+    ) -> Vec<SqlValue> {
+        let mut params = vec![];
+
+        sql.push_str("MERGE INTO ");
+        if let Some(ref table) = self.table {
+            push_table_for(sql, table, dialect);
+        }
+        if let Some(ref source) = self.source {
+            sql.push_str(" USING ");
+            push_table_ref_for(sql, source, dialect);
+        }
+        push_expr(sql, " ON ", self.on, &mut params, dialect);
+
+        for clause in self.clauses {
+            sql.push_str(if clause.matched {
+                " WHEN MATCHED"
+            } else {
+                " WHEN NOT MATCHED"
+            });
+            push_expr(sql, " AND ", clause.condition, &mut params, dialect);
+            match clause.action {
+                Action::Update(assignments) => {
+                    sql.push_str(" THEN UPDATE SET ");
+                    for (i, (column, value)) in assignments.into_iter().enumerate() {
+                        if i > 0 {
+                            sql.push_str(", ");
+                        }
+                        push_column_for(sql, &column, dialect);
+                        push_expr(sql, " = ", Some(value), &mut params, dialect);
+                    }
+                }
+                Action::Delete => sql.push_str(" THEN DELETE"),
+                Action::Insert(columns, values) => {
+                    sql.push_str(" THEN INSERT");
+                    if !columns.is_empty() {
+                        sql.push_str(" (");
+                        for (i, column) in columns.iter().enumerate() {
+                            if i > 0 {
+                                sql.push_str(", ");
+                            }
+                            push_column_for(sql, column, dialect);
+                        }
+                        sql.push(')');
+                    }
+                    sql.push_str(" VALUES (");
+                    for (i, value) in values.into_iter().enumerate() {
+                        push_expr(
+                            sql,
+                            if i > 0 { ", " } else { "" },
+                            Some(value),
+                            &mut params,
+                            dialect,
+                        );
+                    }
+                    sql.push(')');
+                }
+            }
+        }
+
+        params
+    }
+
+    /// Estimates the length of the rendered SQL, for pre-sizing buffers.
+    fn estimated_len(&self) -> usize {
+        24 + self.table.as_ref().map_or(0, String::len)
+            + self.source.as_ref().map_or(0, String::len)
+            + expr_len(self.on.as_ref())
+            + self
+                .clauses
+                .iter()
+                .map(|c| {
+                    40 + c
+                        .exprs()
+                        .map(|e| e.fragment().estimated_len() + 4)
+                        .sum::<usize>()
+                })
+                .sum::<usize>()
+    }
+
+    /// Builds the MERGE statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
+    }
+
+    /// Builds the MERGE statement and returns only the SQL string.
+    #[must_use]
+    pub fn build_sql(self) -> String {
+        let (sql, _) = self.build();
+        sql
+    }
+}
+
+impl CacheableQuery for MergeDyn<HasTable, HasSource, HasWhen> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "merge".hash(state);
+        self.table.hash(state);
+        self.source.hash(state);
+        hash_expr(self.on.as_ref(), state);
+        for clause in &self.clauses {
+            clause.matched.hash(state);
+            hash_expr(clause.condition.as_ref(), state);
+            match &clause.action {
+                Action::Update(assignments) => {
+                    "update".hash(state);
+                    for (column, value) in assignments {
+                        column.hash(state);
+                        value.fragment().hash(state);
+                    }
+                }
+                Action::Delete => "delete".hash(state),
+                Action::Insert(columns, values) => {
+                    "insert".hash(state);
+                    columns.hash(state);
+                    for value in values {
+                        value.fragment().hash(state);
+                    }
+                }
+            }
+        }
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        let mut params: Vec<SqlValue> = self.on.map(|e| e.into_parts().1).unwrap_or_default();
+        for clause in &self.clauses {
+            params.extend(clause.exprs().flat_map(|e| e.params().iter().cloned()));
+        }
+        params
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.build_for(dialect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::test_support::Backticks;
+    use crate::builder::{QueryCache, dyn_col};
+
+    fn merge() -> MergeDyn<HasTable, HasSource, NoWhen> {
+        MergeDyn::new()
+            .into_table("inventory")
+            .using("shipments AS s", ExprBuilder::raw("inventory.sku = s.sku"))
+    }
+
+    #[test]
+    fn test_merge_update_and_insert() {
+        let (sql, params) = merge()
+            .when_matched_update(vec![("qty", ExprBuilder::raw("inventory.qty + s.qty"))])
+            .when_not_matched_insert(
+                &["sku", "qty"],
+                vec![ExprBuilder::column("s.sku"), ExprBuilder::column("s.qty")],
+            )
+            .build();
+
+        assert_eq!(
+            sql,
+            "MERGE INTO inventory USING shipments AS s ON inventory.sku = s.sku \
+             WHEN MATCHED THEN UPDATE SET qty = inventory.qty + s.qty \
+             WHEN NOT MATCHED THEN INSERT (sku, qty) VALUES (s.sku, s.qty)"
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_merge_conditions_bind_params_in_order() {
+        let (sql, params) = merge()
+            .when_matched_delete_if(dyn_col("s.qty").eq(0))
+            .when_matched_update_if(
+                dyn_col("s.qty").gt(1),
+                vec![("note", ExprBuilder::value("restocked"))],
+            )
+            .when_not_matched_insert_if(
+                dyn_col("s.qty").gt(2),
+                &["sku"],
+                vec![ExprBuilder::column("s.sku")],
+            )
+            .build();
+
+        assert_eq!(
+            sql,
+            "MERGE INTO inventory USING shipments AS s ON inventory.sku = s.sku \
+             WHEN MATCHED AND s.qty = ? THEN DELETE \
+             WHEN MATCHED AND s.qty > ? THEN UPDATE SET note = ? \
+             WHEN NOT MATCHED AND s.qty > ? THEN INSERT (sku) VALUES (s.sku)"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Int(0),
+                SqlValue::Int(1),
+                SqlValue::Text(String::from("restocked")),
+                SqlValue::Int(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_sanitizes_names() {
+        let (sql, _) = MergeDyn::new()
+            .into_table("order")
+            .using("new; orders", ExprBuilder::raw("1 = 1"))
+            .when_matched_delete()
+            .build();
+        assert_eq!(
+            sql,
+            "MERGE INTO \"order\" USING \"new; orders\" ON 1 = 1 WHEN MATCHED THEN DELETE"
+        );
+    }
+
+    #[test]
+    fn test_merge_quotes_as_the_dialect() {
+        let (sql, _) = MergeDyn::new()
+            .into_table("order")
+            .using("staging AS s", dyn_col("s.id").eq_expr(dyn_col("order.id")))
+            .when_matched_update(vec![("my col", ExprBuilder::from(dyn_col("s.group")))])
+            .when_not_matched_insert(&["my col"], vec![ExprBuilder::value(1)])
+            .build_for(&Backticks);
+        assert_eq!(
+            sql,
+            "MERGE INTO `order` USING staging AS s ON s.id = `order`.id \
+             WHEN MATCHED THEN UPDATE SET `my col` = s.`group` \
+             WHEN NOT MATCHED THEN INSERT (`my col`) VALUES (?)"
+        );
+    }
+
+    #[test]
+    fn test_merge_round_trips_through_parser() {
+        let query = merge()
+            .when_matched_update(vec![("qty", ExprBuilder::column("s.qty"))])
+            .when_not_matched_insert(&[], vec![ExprBuilder::value(1)])
+            .build_query();
+        let statement = query.to_statement().unwrap();
+        assert_eq!(
+            statement.to_string(),
+            "MERGE INTO inventory USING shipments AS s ON inventory.sku = s.sku \
+             WHEN MATCHED THEN UPDATE SET qty = s.qty \
+             WHEN NOT MATCHED THEN INSERT VALUES (1)"
+        );
+    }
+
+    #[test]
+    fn test_merge_cache_reuses_sql() {
+        let cache = QueryCache::new();
+        let build = |qty: i64| merge().when_matched_update(vec![("qty", ExprBuilder::value(qty))]);
+        let (first, params) = cache.build(build(1));
+        let (second, params2) = cache.build(build(2));
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+        assert_eq!(params2, vec![SqlValue::Int(2)]);
+    }
+}
@@ -49,23 +49,39 @@
 //! assert_eq!(sql, "SELECT id, name FROM users WHERE active = ?");
 //! ```
 
+mod cache;
 mod delete;
 mod expr;
+pub mod func;
+mod guard;
+pub mod ident;
 mod insert;
+mod merge;
+mod query;
+mod render;
 mod select;
+#[cfg(test)]
+mod test_support;
 pub mod typed;
 mod update;
 pub mod value;
+pub mod window;
 
 // Dynamic (string-based) builders
 pub use delete::{DeleteDyn, SafeDeleteDyn, SafeDeleteDynWithWhere};
-pub use expr::{ColumnRef, ExprBuilder, dyn_col};
+pub use expr::{ChunkError, ColumnRef, ExprBuilder, dyn_col, exists, not_exists};
+pub use ident::is_valid_identifier;
 pub use insert::InsertDyn;
+pub use merge::MergeDyn;
 pub use select::SelectDyn;
 pub use update::UpdateDyn;
 
 // Type-safe builders (recommended - these are the default names)
-pub use typed::{Delete, Insert, Select, Update, col};
+pub use typed::{Delete, GroupingSet, Insert, Select, Update, col, qualified_col};
 
 // Common types
-pub use value::{SqlValue, ToSqlValue};
+pub use cache::{CacheableQuery, QueryCache};
+pub use guard::QueryGuard;
+pub use query::Query;
+pub use value::{DecodeError, DriverValue, FromSqlValue, PrimitiveValue, SqlValue, ToSqlValue};
+pub use window::{WindowFunction, WindowSpec};
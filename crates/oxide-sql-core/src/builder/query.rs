@@ -0,0 +1,379 @@
+//! Built query inspection.
+//!
+//! [`Query`] bundles the SQL string produced by a builder with its bound
+//! parameters, so it can be passed around, logged, and executed as a single
+//! value.
+
+use std::fmt;
+
+use crate::ast::visit::VisitExprs;
+use crate::ast::{Expr, Literal, Statement};
+use crate::lexer::Span;
+use crate::parser::{ParseError, Parser};
+
+use super::value::SqlValue;
+
+/// A built SQL statement together with its bound parameters.
+///
+/// Returned by the `build_query()` method of every statement builder.
+/// Use [`Query::sql`] and [`Query::params`] to hand the statement to a
+/// driver, or [`Query::into_parts`] to take ownership of both.
+///
+/// The [`Display`](fmt::Display) impl prints the parameterized SQL, exactly
+/// as it would be sent to the database.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::{SelectDyn, dyn_col};
+///
+/// let query = SelectDyn::new()
+///     .columns(&["id", "name"])
+///     .from("users")
+///     .where_clause(dyn_col("name").eq("O'Brien"))
+///     .build_query();
+///
+/// assert_eq!(query.sql(), "SELECT id, name FROM users WHERE name = ?");
+/// assert_eq!(query.params().len(), 1);
+/// assert_eq!(
+///     query.to_debug_string(),
+///     "SELECT id, name FROM users WHERE name = 'O''Brien'"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    sql: String,
+    params: Vec<SqlValue>,
+}
+
+impl Query {
+    /// Creates a query from SQL and its parameters.
+    #[must_use]
+    pub const fn new(sql: String, params: Vec<SqlValue>) -> Self {
+        Self { sql, params }
+    }
+
+    /// Returns the parameterized SQL string.
+    #[must_use]
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Returns the bound parameters, in placeholder order.
+    #[must_use]
+    pub fn params(&self) -> &[SqlValue] {
+        &self.params
+    }
+
+    /// Consumes the query, returning the SQL string and parameters.
+    #[must_use]
+    pub fn into_parts(self) -> (String, Vec<SqlValue>) {
+        (self.sql, self.params)
+    }
+
+    /// Returns the SQL with every `?` placeholder replaced by the inline
+    /// representation of its parameter.
+    ///
+    /// Placeholders inside quoted strings and identifiers are left alone.
+    /// Placeholders without a matching parameter are kept as `?`.
+    ///
+    /// **Warning**: This is intended for logging and debugging only. The
+    /// output must never be executed; always send [`Query::sql`] together
+    /// with [`Query::params`] to the database.
+    #[must_use]
+    pub fn to_debug_string(&self) -> String {
+        let mut out = String::with_capacity(self.sql.len());
+        let mut params = self.params.iter();
+        let mut quote: Option<char> = None;
+
+        for c in self.sql.chars() {
+            match quote {
+                Some(q) => {
+                    // A doubled quote closes and immediately reopens, which
+                    // keeps the state consistent without lookahead.
+                    if c == q {
+                        quote = None;
+                    }
+                    out.push(c);
+                }
+                None => match c {
+                    '\'' | '"' | '`' => {
+                        quote = Some(c);
+                        out.push(c);
+                    }
+                    '?' => match params.next() {
+                        Some(value) => out.push_str(&value.to_sql_inline()),
+                        None => out.push(c),
+                    },
+                    _ => out.push(c),
+                },
+            }
+        }
+
+        out
+    }
+
+    /// Parses the query back into an AST [`Statement`].
+    ///
+    /// Each `?` placeholder is replaced by a literal holding its parameter,
+    /// so the resulting tree carries the bound values.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if the SQL contains constructs the parser does
+    /// not support, typically coming from [`ExprBuilder::raw`] fragments, or
+    /// if a parameter has no literal form: a missing parameter, a NaN or
+    /// infinite float, or a UTC timestamp, UUID, or JSON value.
+    ///
+    /// [`ExprBuilder::raw`]: super::ExprBuilder::raw
+    pub fn to_statement(&self) -> Result<Statement, ParseError> {
+        let mut statement = Parser::new(&self.sql).parse_statement()?;
+        let mut result = Ok(());
+        statement.visit_exprs(&mut |expr| {
+            if result.is_ok() {
+                result = self.bind_expr(expr);
+            }
+        });
+        result.map(|()| statement)
+    }
+
+    /// Replaces the placeholders in `expr` and the expressions below it by
+    /// literals.
+    fn bind_expr(&self, expr: &mut Expr) -> Result<(), ParseError> {
+        match expr {
+            Expr::Parameter {
+                name: None,
+                position,
+            } => {
+                *expr = Expr::Literal(self.literal(*position)?);
+            }
+            Expr::Literal(_)
+            | Expr::Column { .. }
+            | Expr::Parameter { .. }
+            | Expr::Wildcard { .. } => {}
+            Expr::Binary { left, right, .. } | Expr::Quantified { left, right, .. } => {
+                self.bind_expr(left)?;
+                self.bind_expr(right)?;
+            }
+            Expr::Unary { operand: expr, .. }
+            | Expr::IsNull { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::Paren(expr) => self.bind_expr(expr)?,
+            Expr::Function(func) => {
+                self.bind_list(&mut func.args)?;
+                for order in &mut func.within_group {
+                    self.bind_expr(&mut order.expr)?;
+                }
+                if let Some(filter) = &mut func.filter {
+                    self.bind_expr(filter)?;
+                }
+            }
+            Expr::Subquery(query) | Expr::Exists(query) => {
+                let mut result = Ok(());
+                query.visit_exprs(&mut |expr| {
+                    if result.is_ok() {
+                        result = self.bind_expr(expr);
+                    }
+                });
+                result?;
+            }
+            Expr::Array(list) => self.bind_list(list)?,
+            Expr::In { expr, list, .. } => {
+                self.bind_expr(expr)?;
+                self.bind_list(list)?;
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.bind_expr(expr)?;
+                self.bind_expr(low)?;
+                self.bind_expr(high)?;
+            }
+            Expr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                if let Some(operand) = operand {
+                    self.bind_expr(operand)?;
+                }
+                for (when, then) in when_clauses {
+                    self.bind_expr(when)?;
+                    self.bind_expr(then)?;
+                }
+                if let Some(else_clause) = else_clause {
+                    self.bind_expr(else_clause)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn bind_list(&self, list: &mut [Expr]) -> Result<(), ParseError> {
+        list.iter_mut().try_for_each(|expr| self.bind_expr(expr))
+    }
+
+    /// Returns the literal for the parameter at 1-based `position`.
+    fn literal(&self, position: usize) -> Result<Literal, ParseError> {
+        let error = |message: String| ParseError::new(message, Span::new(0, self.sql.len()));
+        let value = position
+            .checked_sub(1)
+            .and_then(|i| self.params.get(i))
+            .ok_or_else(|| error(format!("no value bound to parameter {position}")))?;
+        let literal = match value {
+            SqlValue::Null => Literal::Null,
+            SqlValue::Bool(b) => Literal::Boolean(*b),
+            SqlValue::Int(n) => Literal::Integer(*n),
+            SqlValue::Float(f) if f.is_finite() => Literal::Float(*f),
+            SqlValue::Text(s) => Literal::String(s.clone()),
+            SqlValue::Blob(b) => Literal::Blob(b.clone()),
+            #[cfg(feature = "chrono")]
+            SqlValue::Timestamp(ts) => Literal::Timestamp(ts.to_string()),
+            #[cfg(feature = "rust_decimal")]
+            SqlValue::Decimal(d) => Literal::BigNumber(d.to_string()),
+            other => {
+                return Err(error(format!(
+                    "parameter {position} has no literal form: {other:?}"
+                )));
+            }
+        };
+        Ok(literal)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.sql)
+    }
+}
+
+impl From<Query> for (String, Vec<SqlValue>) {
+    fn from(query: Query) -> Self {
+        query.into_parts()
+    }
+}
+
+impl From<(String, Vec<SqlValue>)> for Query {
+    fn from((sql, params): (String, Vec<SqlValue>)) -> Self {
+        Self::new(sql, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let query = Query::new(String::from("SELECT ?"), vec![SqlValue::Int(1)]);
+        assert_eq!(query.sql(), "SELECT ?");
+        assert_eq!(query.params(), &[SqlValue::Int(1)]);
+        assert_eq!(query.to_string(), "SELECT ?");
+
+        let (sql, params) = query.into_parts();
+        assert_eq!(sql, "SELECT ?");
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_debug_string_interpolates_params() {
+        let query = Query::new(
+            String::from("SELECT * FROM t WHERE a = ? AND b = ? AND c IS ?"),
+            vec![
+                SqlValue::Text(String::from("it's")),
+                SqlValue::Float(1.5),
+                SqlValue::Null,
+            ],
+        );
+        assert_eq!(
+            query.to_debug_string(),
+            "SELECT * FROM t WHERE a = 'it''s' AND b = 1.5 AND c IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_debug_string_skips_quoted_placeholders() {
+        let query = Query::new(
+            String::from("SELECT '?', \"a?\", 'x''?' FROM t WHERE id = ?"),
+            vec![SqlValue::Int(7)],
+        );
+        assert_eq!(
+            query.to_debug_string(),
+            "SELECT '?', \"a?\", 'x''?' FROM t WHERE id = 7"
+        );
+    }
+
+    #[test]
+    fn test_debug_string_missing_params() {
+        let query = Query::new(String::from("SELECT ?, ?"), vec![SqlValue::Bool(true)]);
+        assert_eq!(query.to_debug_string(), "SELECT TRUE, ?");
+    }
+
+    #[test]
+    fn test_to_statement_binds_params_as_literals() {
+        let query = Query::new(
+            String::from("SELECT * FROM t WHERE a = ? AND b IN (?, ?) AND c = ?"),
+            vec![
+                SqlValue::Text(String::from("it's E'\\n'")),
+                SqlValue::Float(0.1),
+                SqlValue::Int(-3),
+                SqlValue::Null,
+            ],
+        );
+        let Statement::Select(select) = query.to_statement().unwrap() else {
+            panic!("expected a SELECT statement");
+        };
+        let mut literals = vec![];
+        let mut where_clause = select.where_clause;
+        where_clause.visit_exprs(&mut |expr| collect_literals(expr, &mut literals));
+        assert_eq!(
+            literals,
+            vec![
+                Literal::String(String::from("it's E'\\n'")),
+                Literal::Float(0.1),
+                Literal::Integer(-3),
+                Literal::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_statement_rejects_values_without_literal_form() {
+        let query = Query::new(
+            String::from("SELECT * FROM t WHERE a = ?"),
+            vec![SqlValue::Float(f64::NAN)],
+        );
+        let err = query.to_statement().unwrap_err();
+        assert!(err.message.contains("parameter 1 has no literal form"));
+
+        let query = Query::new(String::from("SELECT * FROM t WHERE a = ?"), vec![]);
+        let err = query.to_statement().unwrap_err();
+        assert!(err.message.contains("no value bound to parameter 1"));
+
+        #[cfg(feature = "uuid")]
+        {
+            let query = Query::new(
+                String::from("SELECT * FROM t WHERE id = ?"),
+                vec![SqlValue::Uuid(uuid::Uuid::nil())],
+            );
+            assert!(query.to_statement().is_err());
+        }
+    }
+
+    fn collect_literals(expr: &mut Expr, literals: &mut Vec<Literal>) {
+        match expr {
+            Expr::Literal(literal) => literals.push(literal.clone()),
+            Expr::Binary { left, right, .. } => {
+                collect_literals(left, literals);
+                collect_literals(right, literals);
+            }
+            Expr::In { list, .. } => {
+                for expr in list {
+                    collect_literals(expr, literals);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Helpers for rendering SQL directly into a caller-provided buffer.
+//!
+//! Used by the `build_into()` methods of the builders to avoid allocating
+//! intermediate strings for every clause.
+
+use std::fmt::Write;
+
+use crate::dialect::Dialect;
+
+use super::expr::ExprBuilder;
+use super::ident::{push_column_for, push_table_for, push_table_ref_for};
+use super::value::SqlValue;
+
+/// One piece of a [`Fragment`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Piece {
+    /// SQL emitted as-is.
+    Sql(String),
+    /// A column name, quoted like
+    /// [`sanitize_column_for`](super::ident::sanitize_column_for).
+    Column(String),
+    /// A table or collation name, quoted like
+    /// [`sanitize_table_for`](super::ident::sanitize_table_for).
+    Table(String),
+    /// A table reference, quoted like
+    /// [`sanitize_table_ref_for`](super::ident::sanitize_table_ref_for).
+    TableRef(String),
+}
+
+/// A clause of a dynamic builder, kept with its names unquoted until the
+/// statement is built for a dialect.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Fragment(Vec<Piece>);
+
+impl Fragment {
+    /// A fragment of SQL emitted as-is.
+    pub(crate) fn sql(sql: impl Into<String>) -> Self {
+        Self(vec![Piece::Sql(sql.into())])
+    }
+
+    /// A fragment holding one column name.
+    pub(crate) fn column(name: &str) -> Self {
+        Self::default().then_column(name)
+    }
+
+    /// A fragment holding one table name.
+    pub(crate) fn table(name: &str) -> Self {
+        Self::default().then_table(name)
+    }
+
+    /// A fragment holding one table reference, which may carry an alias.
+    pub(crate) fn table_ref(table: &str) -> Self {
+        Self::default().then_table_ref(table)
+    }
+
+    /// Appends SQL emitted as-is.
+    pub(crate) fn then_sql(mut self, sql: impl Into<String>) -> Self {
+        self.push_sql(&sql.into());
+        self
+    }
+
+    /// Appends SQL emitted as-is, extending a trailing SQL piece rather
+    /// than starting a new one.
+    pub(crate) fn push_sql(&mut self, sql: &str) {
+        match self.0.last_mut() {
+            Some(Piece::Sql(text)) => text.push_str(sql),
+            _ => self.0.push(Piece::Sql(String::from(sql))),
+        }
+    }
+
+    /// Appends all pieces of `other`.
+    pub(crate) fn then(mut self, other: Self) -> Self {
+        self.append(other);
+        self
+    }
+
+    /// Appends all pieces of `other` in place.
+    pub(crate) fn append(&mut self, other: Self) {
+        let mut pieces = other.0.into_iter();
+        if let Some(first) = pieces.next() {
+            match first {
+                Piece::Sql(text) => self.push_sql(&text),
+                piece => self.0.push(piece),
+            }
+        }
+        self.0.extend(pieces);
+    }
+
+    /// Appends a column name.
+    pub(crate) fn then_column(mut self, name: &str) -> Self {
+        self.0.push(Piece::Column(String::from(name)));
+        self
+    }
+
+    /// Appends column names separated by `", "`.
+    pub(crate) fn then_columns(self, names: &[&str]) -> Self {
+        names.iter().enumerate().fold(self, |fragment, (i, name)| {
+            let fragment = if i > 0 {
+                fragment.then_sql(", ")
+            } else {
+                fragment
+            };
+            fragment.then_column(name)
+        })
+    }
+
+    /// Appends a table name.
+    pub(crate) fn then_table(mut self, name: &str) -> Self {
+        self.0.push(Piece::Table(String::from(name)));
+        self
+    }
+
+    /// Appends a table reference, which may carry an alias.
+    pub(crate) fn then_table_ref(mut self, table: &str) -> Self {
+        self.0.push(Piece::TableRef(String::from(table)));
+        self
+    }
+
+    /// Appends the fragment to `sql`, quoting names as `dialect` does.
+    pub(crate) fn push_to<D: Dialect + ?Sized>(&self, sql: &mut String, dialect: &D) {
+        for piece in &self.0 {
+            match piece {
+                Piece::Sql(text) => sql.push_str(text),
+                Piece::Column(name) => push_column_for(sql, name, dialect),
+                Piece::Table(name) => push_table_for(sql, name, dialect),
+                Piece::TableRef(table) => push_table_ref_for(sql, table, dialect),
+            }
+        }
+    }
+
+    /// Renders the fragment, quoting names as `dialect` does.
+    pub(crate) fn to_sql_for<D: Dialect + ?Sized>(&self, dialect: &D) -> String {
+        let mut sql = String::with_capacity(self.estimated_len());
+        self.push_to(&mut sql, dialect);
+        sql
+    }
+
+    /// Returns the length of the fragment before quoting.
+    pub(crate) fn estimated_len(&self) -> usize {
+        self.0
+            .iter()
+            .map(|piece| match piece {
+                Piece::Sql(text)
+                | Piece::Column(text)
+                | Piece::Table(text)
+                | Piece::TableRef(text) => text.len(),
+            })
+            .sum()
+    }
+}
+
+/// Appends `items` separated by `", "`.
+pub(crate) fn push_list<S: AsRef<str>>(sql: &mut String, items: &[S]) {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        sql.push_str(item.as_ref());
+    }
+}
+
+/// Appends `fragments` separated by `", "`, quoting names as `dialect`
+/// does.
+pub(crate) fn push_fragments<D: Dialect + ?Sized>(
+    sql: &mut String,
+    fragments: &[Fragment],
+    dialect: &D,
+) {
+    for (i, fragment) in fragments.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        fragment.push_to(sql, dialect);
+    }
+}
+
+/// Appends `count` placeholders separated by `", "`.
+pub(crate) fn push_placeholders(sql: &mut String, count: usize) {
+    for i in 0..count {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        sql.push('?');
+    }
+}
+
+/// Appends `keyword` followed by the expression, quoting its names as
+/// `dialect` does and moving its parameters.
+pub(crate) fn push_expr<D: Dialect + ?Sized>(
+    sql: &mut String,
+    keyword: &str,
+    expr: Option<ExprBuilder>,
+    params: &mut Vec<SqlValue>,
+    dialect: &D,
+) {
+    if let Some(expr) = expr {
+        let (fragment, expr_params) = expr.into_parts();
+        sql.push_str(keyword);
+        fragment.push_to(sql, dialect);
+        params.extend(expr_params);
+    }
+}
+
+/// Appends ` LIMIT n` / ` OFFSET n` style clauses.
+pub(crate) fn push_number<N: std::fmt::Display>(sql: &mut String, keyword: &str, n: Option<N>) {
+    if let Some(n) = n {
+        // Writing to a `String` cannot fail.
+        let _ = write!(sql, "{keyword}{n}");
+    }
+}
+
+/// Returns the combined length of `items` when joined with `", "`.
+pub(crate) fn list_len<S: AsRef<str>>(items: &[S]) -> usize {
+    items.iter().map(|s| s.as_ref().len() + 2).sum()
+}
+
+/// Returns the combined length of `fragments` when joined with `", "`.
+pub(crate) fn fragments_len(fragments: &[Fragment]) -> usize {
+    fragments.iter().map(|f| f.estimated_len() + 2).sum()
+}
+
+/// Returns the length of an optional expression's SQL.
+pub(crate) fn expr_len(expr: Option<&ExprBuilder>) -> usize {
+    expr.map_or(0, |e| e.fragment().estimated_len() + 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::test_support::Backticks;
+    use crate::dialect::GenericDialect;
+
+    #[test]
+    fn test_push_list_and_placeholders() {
+        let mut sql = String::new();
+        push_list(&mut sql, &["a", "b", "c"]);
+        sql.push_str(" | ");
+        push_placeholders(&mut sql, 3);
+        assert_eq!(sql, "a, b, c | ?, ?, ?");
+    }
+
+    #[test]
+    fn test_push_expr_moves_params() {
+        let mut sql = String::from("DELETE FROM t");
+        let mut params = vec![];
+        push_expr(
+            &mut sql,
+            " WHERE ",
+            Some(ExprBuilder::column("id").eq(1)),
+            &mut params,
+            &GenericDialect,
+        );
+        push_number(&mut sql, " LIMIT ", Some(10));
+        push_number::<u64>(&mut sql, " OFFSET ", None);
+        assert_eq!(sql, "DELETE FROM t WHERE id = ? LIMIT 10");
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_fragments_quote_for_the_dialect() {
+        let fragments = [
+            Fragment::column("order"),
+            Fragment::sql("ROLLUP(")
+                .then_columns(&["a", "my col"])
+                .then_sql(")"),
+            Fragment::sql("CROSS JOIN ").then_table_ref("t AS from"),
+        ];
+        let mut sql = String::new();
+        push_fragments(&mut sql, &fragments, &GenericDialect);
+        assert_eq!(
+            sql,
+            "\"order\", ROLLUP(a, \"my col\"), CROSS JOIN t AS \"from\""
+        );
+        sql.clear();
+        push_fragments(&mut sql, &fragments, &Backticks);
+        assert_eq!(sql, "`order`, ROLLUP(a, `my col`), CROSS JOIN t AS `from`");
+    }
+}
@@ -5,9 +5,19 @@
 //!
 //! Invalid SQL constructs are caught at compile time.
 
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
+use crate::ast::{Expr, Literal, SelectStatement, Statement, TableRef};
+use crate::dialect::{Dialect, GenericDialect};
+use crate::lexer::Span;
+use crate::parser::ParseError;
+
+use super::cache::{CacheableQuery, hash_expr};
 use super::expr::ExprBuilder;
+use super::guard::QueryGuard;
+use super::query::Query;
+use super::render::{Fragment, expr_len, fragments_len, push_expr, push_fragments, push_number};
 use super::value::SqlValue;
 
 // Typestate markers (zero-sized types)
@@ -31,15 +41,18 @@ pub struct HasFrom;
 /// - `group_by()`, `having()`, `order_by()` follow SQL semantics
 pub struct SelectDyn<Cols, From> {
     distinct: bool,
-    columns: Vec<String>,
-    from: Option<String>,
-    joins: Vec<String>,
+    columns: Vec<Fragment>,
+    column_params: Vec<SqlValue>,
+    from: Option<Fragment>,
+    joins: Vec<Fragment>,
+    source_params: Vec<SqlValue>,
     where_clause: Option<ExprBuilder>,
-    group_by: Vec<String>,
+    group_by: Vec<Fragment>,
     having: Option<ExprBuilder>,
-    order_by: Vec<String>,
+    order_by: Vec<Fragment>,
     limit: Option<u64>,
     offset: Option<u64>,
+    guard: Option<QueryGuard>,
     _state: PhantomData<(Cols, From)>,
 }
 
@@ -50,14 +63,17 @@ impl SelectDyn<NoColumns, NoFrom> {
         Self {
             distinct: false,
             columns: vec![],
+            column_params: vec![],
             from: None,
             joins: vec![],
+            source_params: vec![],
             where_clause: None,
             group_by: vec![],
             having: None,
             order_by: vec![],
             limit: None,
             offset: None,
+            guard: None,
             _state: PhantomData,
         }
     }
@@ -72,19 +88,44 @@ impl Default for SelectDyn<NoColumns, NoFrom> {
 // Transition: NoColumns -> HasColumns
 impl<From> SelectDyn<NoColumns, From> {
     /// Specifies the columns to select.
+    ///
+    /// Entries are column names, possibly qualified (`u.id`, `u.*`); anything
+    /// that is not a valid identifier is quoted as a single identifier. Use
+    /// [`columns_raw`](Self::columns_raw) or [`expr`](SelectDyn::expr) for
+    /// expressions such as `COUNT(*)`.
     #[must_use]
     pub fn columns(self, cols: &[&str]) -> SelectDyn<HasColumns, From> {
+        let cols = cols.iter().map(|c| Fragment::column(c)).collect();
+        self.with_columns(cols)
+    }
+
+    /// Specifies the columns to select as SQL expressions (e.g. `COUNT(*)`),
+    /// emitted as-is.
+    ///
+    /// **Warning**: Never pass user input here; use a fixed list of
+    /// expressions.
+    #[must_use]
+    pub fn columns_raw(self, cols: &[&str]) -> SelectDyn<HasColumns, From> {
+        let cols = cols.iter().map(|c| Fragment::sql(*c)).collect();
+        self.with_columns(cols)
+    }
+
+    /// Moves to the `HasColumns` state with the rendered select list.
+    fn with_columns(self, columns: Vec<Fragment>) -> SelectDyn<HasColumns, From> {
         SelectDyn {
             distinct: self.distinct,
-            columns: cols.iter().map(|s| String::from(*s)).collect(),
+            columns,
+            column_params: self.column_params,
             from: self.from,
             joins: self.joins,
+            source_params: self.source_params,
             where_clause: self.where_clause,
             group_by: self.group_by,
             having: self.having,
             order_by: self.order_by,
             limit: self.limit,
             offset: self.offset,
+            guard: self.guard,
             _state: PhantomData,
         }
     }
@@ -94,15 +135,18 @@ impl<From> SelectDyn<NoColumns, From> {
     pub fn all(self) -> SelectDyn<HasColumns, From> {
         SelectDyn {
             distinct: self.distinct,
-            columns: vec![String::from("*")],
+            columns: vec![Fragment::sql("*")],
+            column_params: self.column_params,
             from: self.from,
             joins: self.joins,
+            source_params: self.source_params,
             where_clause: self.where_clause,
             group_by: self.group_by,
             having: self.having,
             order_by: self.order_by,
             limit: self.limit,
             offset: self.offset,
+            guard: self.guard,
             _state: PhantomData,
         }
     }
@@ -111,19 +155,55 @@ impl<From> SelectDyn<NoColumns, From> {
 // Transition: NoFrom -> HasFrom
 impl<Cols> SelectDyn<Cols, NoFrom> {
     /// Specifies the table to select from.
+    ///
+    /// The table may carry an alias introduced by `AS` (`users AS u`).
+    /// Anything else that is not a valid identifier is quoted as a single
+    /// identifier, so `from("my table")` selects from `"my table"`, as
+    /// [`UpdateDyn::table`](super::UpdateDyn::table) would update it.
     #[must_use]
     pub fn from(self, table: &str) -> SelectDyn<Cols, HasFrom> {
         SelectDyn {
             distinct: self.distinct,
             columns: self.columns,
-            from: Some(String::from(table)),
+            column_params: self.column_params,
+            from: Some(Fragment::table_ref(table)),
+            joins: self.joins,
+            source_params: self.source_params,
+            where_clause: self.where_clause,
+            group_by: self.group_by,
+            having: self.having,
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            guard: self.guard,
+            _state: PhantomData,
+        }
+    }
+
+    /// Selects from a table-valued expression, such as a set-returning
+    /// function call, as `source AS alias`.
+    ///
+    /// Parameters of the source are bound after those of the select list
+    /// and before those of the WHERE clause.
+    #[must_use]
+    pub fn from_expr(self, source: ExprBuilder, alias: &str) -> SelectDyn<Cols, HasFrom> {
+        let (sql, params) = source.into_parts();
+        let mut source_params = self.source_params;
+        source_params.extend(params);
+        SelectDyn {
+            distinct: self.distinct,
+            columns: self.columns,
+            column_params: self.column_params,
+            from: Some(sql.then_sql(" AS ").then_column(alias)),
             joins: self.joins,
+            source_params,
             where_clause: self.where_clause,
             group_by: self.group_by,
             having: self.having,
             order_by: self.order_by,
             limit: self.limit,
             offset: self.offset,
+            guard: self.guard,
             _state: PhantomData,
         }
     }
@@ -138,31 +218,69 @@ impl<Cols> SelectDyn<Cols, HasFrom> {
         self
     }
 
-    /// Adds an INNER JOIN.
+    /// Adds an INNER JOIN on the condition `on`.
+    ///
+    /// Parameters of the condition are bound in clause order, before those
+    /// of the WHERE clause.
     #[must_use]
-    pub fn join(mut self, table: &str, on: &str) -> Self {
-        self.joins.push(format!("INNER JOIN {table} ON {on}"));
-        self
+    pub fn join(self, table: &str, on: ExprBuilder) -> Self {
+        self.push_join("INNER JOIN", table, on)
     }
 
-    /// Adds a LEFT JOIN.
+    /// Adds a LEFT JOIN on the condition `on`.
+    ///
+    /// Parameters of the condition are bound in clause order, before those
+    /// of the WHERE clause.
     #[must_use]
-    pub fn left_join(mut self, table: &str, on: &str) -> Self {
-        self.joins.push(format!("LEFT JOIN {table} ON {on}"));
-        self
+    pub fn left_join(self, table: &str, on: ExprBuilder) -> Self {
+        self.push_join("LEFT JOIN", table, on)
     }
 
-    /// Adds a RIGHT JOIN.
+    /// Adds a RIGHT JOIN on the condition `on`.
+    ///
+    /// Parameters of the condition are bound in clause order, before those
+    /// of the WHERE clause.
     #[must_use]
-    pub fn right_join(mut self, table: &str, on: &str) -> Self {
-        self.joins.push(format!("RIGHT JOIN {table} ON {on}"));
+    pub fn right_join(self, table: &str, on: ExprBuilder) -> Self {
+        self.push_join("RIGHT JOIN", table, on)
+    }
+
+    /// Appends `keyword table ON on`, keeping the parameters of `on`.
+    fn push_join(mut self, keyword: &str, table: &str, on: ExprBuilder) -> Self {
+        let (on, params) = on.into_parts();
+        self.joins.push(
+            Fragment::sql(format!("{keyword} "))
+                .then_table_ref(table)
+                .then_sql(" ON ")
+                .then(on),
+        );
+        self.source_params.extend(params);
         self
     }
 
     /// Adds a CROSS JOIN.
     #[must_use]
     pub fn cross_join(mut self, table: &str) -> Self {
-        self.joins.push(format!("CROSS JOIN {table}"));
+        self.joins
+            .push(Fragment::sql("CROSS JOIN ").then_table_ref(table));
+        self
+    }
+
+    /// Adds a CROSS JOIN on a table-valued expression, as
+    /// `CROSS JOIN source AS alias`.
+    ///
+    /// The source may refer to columns of earlier tables, which is how
+    /// functions like SQLite's `json_each()` expand a column per row.
+    #[must_use]
+    pub fn cross_join_expr(mut self, source: ExprBuilder, alias: &str) -> Self {
+        let (sql, params) = source.into_parts();
+        self.joins.push(
+            Fragment::sql("CROSS JOIN ")
+                .then(sql)
+                .then_sql(" AS ")
+                .then_column(alias),
+        );
+        self.source_params.extend(params);
         self
     }
 }
@@ -175,14 +293,69 @@ impl<From> SelectDyn<HasColumns, From> {
         self.distinct = true;
         self
     }
+
+    /// Appends an expression to the select list as `expr AS alias`.
+    ///
+    /// Parameters of the expression are bound before those of the WHERE
+    /// clause. Use this for window functions built with
+    /// [`WindowFunction::over`](super::WindowFunction::over).
+    #[must_use]
+    pub fn expr(mut self, expr: ExprBuilder, alias: &str) -> Self {
+        let (sql, params) = expr.into_parts();
+        self.columns.push(sql.then_sql(" AS ").then_column(alias));
+        self.column_params.extend(params);
+        self
+    }
 }
 
 // Methods available with FROM (for grouping)
 impl SelectDyn<HasColumns, HasFrom> {
-    /// Adds a GROUP BY clause.
+    /// Sets the GROUP BY columns, replacing any set before.
+    ///
+    /// Entries are column names or aliases of the select list, quoted like
+    /// [`columns`](SelectDyn::columns). Chain
+    /// [`group_by_rollup`](Self::group_by_rollup) and friends after it to
+    /// add grouping elements (`GROUP BY a, CUBE(b, c)`).
     #[must_use]
     pub fn group_by(mut self, cols: &[&str]) -> Self {
-        self.group_by = cols.iter().map(|s| String::from(*s)).collect();
+        self.group_by = cols.iter().map(|c| Fragment::column(c)).collect();
+        self
+    }
+
+    /// Appends a `ROLLUP(...)` element to the GROUP BY clause, producing
+    /// subtotals for each prefix of `cols` and a grand total.
+    ///
+    /// Entries are quoted like [`group_by`](Self::group_by).
+    #[must_use]
+    pub fn group_by_rollup(mut self, cols: &[&str]) -> Self {
+        self.group_by
+            .push(Fragment::sql("ROLLUP(").then_columns(cols).then_sql(")"));
+        self
+    }
+
+    /// Appends a `CUBE(...)` element to the GROUP BY clause, producing
+    /// subtotals for every combination of `cols`.
+    ///
+    /// Entries are quoted like [`group_by`](Self::group_by).
+    #[must_use]
+    pub fn group_by_cube(mut self, cols: &[&str]) -> Self {
+        self.group_by
+            .push(Fragment::sql("CUBE(").then_columns(cols).then_sql(")"));
+        self
+    }
+
+    /// Appends a `GROUPING SETS (...)` element to the GROUP BY clause, with
+    /// one grouping per entry of `sets`; an empty set is the grand total.
+    ///
+    /// Entries are quoted like [`group_by`](Self::group_by).
+    #[must_use]
+    pub fn group_by_grouping_sets(mut self, sets: &[&[&str]]) -> Self {
+        let mut element = Fragment::sql("GROUPING SETS (");
+        for (i, set) in sets.iter().enumerate() {
+            let open = if i > 0 { ", (" } else { "(" };
+            element = element.then_sql(open).then_columns(set).then_sql(")");
+        }
+        self.group_by.push(element.then_sql(")"));
         self
     }
 
@@ -194,16 +367,22 @@ impl SelectDyn<HasColumns, HasFrom> {
     }
 
     /// Adds an ORDER BY clause.
+    ///
+    /// Entries are column names or aliases of the select list, quoted like
+    /// [`columns`](SelectDyn::columns).
     #[must_use]
     pub fn order_by(mut self, cols: &[&str]) -> Self {
-        self.order_by = cols.iter().map(|s| String::from(*s)).collect();
+        self.order_by = cols.iter().map(|c| Fragment::column(c)).collect();
         self
     }
 
     /// Adds an ORDER BY DESC clause.
     #[must_use]
     pub fn order_by_desc(mut self, cols: &[&str]) -> Self {
-        self.order_by = cols.iter().map(|s| format!("{s} DESC")).collect();
+        self.order_by = cols
+            .iter()
+            .map(|c| Fragment::column(c).then_sql(" DESC"))
+            .collect();
         self
     }
 
@@ -221,59 +400,113 @@ impl SelectDyn<HasColumns, HasFrom> {
         self
     }
 
+    /// Applies the limits of `guard`, capping the LIMIT clause at its
+    /// maximum row count.
+    ///
+    /// The cap is applied when the query is built, so a later
+    /// [`limit`](Self::limit) cannot exceed it. A later `guard()` call
+    /// replaces this one.
+    #[must_use]
+    pub const fn guard(mut self, guard: &QueryGuard) -> Self {
+        self.guard = Some(*guard);
+        self
+    }
+
+    /// Returns the LIMIT to render, capped by the guard.
+    fn effective_limit(&self) -> Option<u64> {
+        self.guard
+            .map_or(self.limit, |guard| guard.clamp_limit(self.limit))
+    }
+
     /// Builds the SELECT statement and returns SQL with parameters.
+    ///
+    /// Names are quoted as [`GenericDialect`] does; use
+    /// [`build_for`](Self::build_for) to quote for another dialect.
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("SELECT ");
-        let mut params = vec![];
+        self.build_for(&GenericDialect)
+    }
+
+    /// Builds the SELECT statement, quoting table and column names as
+    /// `dialect` does, and returns SQL with parameters.
+    #[must_use]
+    pub fn build_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(self.estimated_len());
+        let params = self.build_into_for(&mut sql, dialect);
+        (sql, params)
+    }
 
+    /// Renders the SELECT statement into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings, so one
+    /// buffer can be reused across statements.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.build_into_for(sql, &GenericDialect)
+    }
+
+    /// Renders the SELECT statement into `sql` like
+    /// [`build_into`](Self::build_into), quoting names as `dialect` does.
+    pub fn build_into_for<D: Dialect + ?Sized>(
+        self,
+        sql: &mut String,
+        dialect: &D,
+    ) -> Vec<SqlValue> {
+        let limit = self.effective_limit();
+        let mut params = self.column_params;
+        params.extend(self.source_params);
+
+        sql.push_str("SELECT ");
         if self.distinct {
             sql.push_str("DISTINCT ");
         }
-
-        sql.push_str(&self.columns.join(", "));
+        push_fragments(sql, &self.columns, dialect);
 
         if let Some(ref table) = self.from {
             sql.push_str(" FROM ");
-            sql.push_str(table);
+            table.push_to(sql, dialect);
         }
 
         for join in &self.joins {
             sql.push(' ');
-            sql.push_str(join);
+            join.push_to(sql, dialect);
         }
 
-        if let Some(ref where_expr) = self.where_clause {
-            sql.push_str(" WHERE ");
-            sql.push_str(where_expr.sql());
-            params.extend(where_expr.params().iter().cloned());
-        }
+        push_expr(sql, " WHERE ", self.where_clause, &mut params, dialect);
 
         if !self.group_by.is_empty() {
             sql.push_str(" GROUP BY ");
-            sql.push_str(&self.group_by.join(", "));
+            push_fragments(sql, &self.group_by, dialect);
         }
 
-        if let Some(ref having_expr) = self.having {
-            sql.push_str(" HAVING ");
-            sql.push_str(having_expr.sql());
-            params.extend(having_expr.params().iter().cloned());
-        }
+        push_expr(sql, " HAVING ", self.having, &mut params, dialect);
 
         if !self.order_by.is_empty() {
             sql.push_str(" ORDER BY ");
-            sql.push_str(&self.order_by.join(", "));
+            push_fragments(sql, &self.order_by, dialect);
         }
 
-        if let Some(n) = self.limit {
-            sql.push_str(&format!(" LIMIT {n}"));
-        }
+        push_number(sql, " LIMIT ", limit);
+        push_number(sql, " OFFSET ", self.offset);
 
-        if let Some(n) = self.offset {
-            sql.push_str(&format!(" OFFSET {n}"));
-        }
+        params
+    }
 
-        (sql, params)
+    /// Estimates the length of the rendered SQL, for pre-sizing buffers.
+    fn estimated_len(&self) -> usize {
+        32 + fragments_len(&self.columns)
+            + self.from.as_ref().map_or(0, Fragment::estimated_len)
+            + fragments_len(&self.joins)
+            + expr_len(self.where_clause.as_ref())
+            + fragments_len(&self.group_by)
+            + expr_len(self.having.as_ref())
+            + fragments_len(&self.order_by)
+            + 40
+    }
+
+    /// Builds the SELECT statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the SELECT statement and returns only the SQL string.
@@ -285,12 +518,333 @@ impl SelectDyn<HasColumns, HasFrom> {
         let (sql, _params) = self.build();
         sql
     }
+
+    /// Converts the builder into a parsed [`SelectStatement`].
+    ///
+    /// Bound parameters become literals in the resulting tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if a raw fragment cannot be parsed.
+    pub fn to_ast(self) -> Result<SelectStatement, ParseError> {
+        select_from_query(&self.build_query())
+    }
+
+    /// Creates a builder from a parsed [`SelectStatement`].
+    fn from_statement(stmt: &SelectStatement) -> Option<Self> {
+        // `a JOIN b JOIN c` nests to the left: unwind it into the first
+        // table and its joins, in order.
+        let mut from = stmt.from.as_ref();
+        let mut clauses = vec![];
+        while let Some(TableRef::Join { left, join }) = from {
+            clauses.push(join.as_ref());
+            from = Some(left);
+        }
+        let mut source_params = vec![];
+        let joins = clauses
+            .into_iter()
+            .rev()
+            .map(|join| match &join.on {
+                Some(on) => {
+                    let (on, params) = ExprBuilder::from(on).build();
+                    source_params.extend(params);
+                    Fragment::sql(format!("{} {} ON {on}", join.join_type, join.table))
+                }
+                None => Fragment::sql(join.to_string()),
+            })
+            .collect();
+        Some(Self {
+            distinct: stmt.distinct,
+            columns: stmt
+                .columns
+                .iter()
+                .map(|c| Fragment::sql(c.to_string()))
+                .collect(),
+            column_params: vec![],
+            from: from.map(|from| Fragment::sql(from.to_string())),
+            joins,
+            source_params,
+            where_clause: stmt.where_clause.as_ref().map(ExprBuilder::from),
+            group_by: stmt
+                .group_by
+                .iter()
+                .map(|c| Fragment::sql(c.to_string()))
+                .collect(),
+            having: stmt.having.as_ref().map(ExprBuilder::from),
+            order_by: stmt
+                .order_by
+                .iter()
+                .map(|c| Fragment::sql(c.to_string()))
+                .collect(),
+            limit: literal_u64(stmt.limit.as_ref())?,
+            offset: literal_u64(stmt.offset.as_ref())?,
+            guard: None,
+            _state: PhantomData,
+        })
+    }
+}
+
+impl From<SelectDyn<HasColumns, HasFrom>> for Query {
+    fn from(select: SelectDyn<HasColumns, HasFrom>) -> Self {
+        select.build_query()
+    }
+}
+
+impl CacheableQuery for SelectDyn<HasColumns, HasFrom> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "select".hash(state);
+        self.distinct.hash(state);
+        self.columns.hash(state);
+        self.from.hash(state);
+        self.joins.hash(state);
+        hash_expr(self.where_clause.as_ref(), state);
+        self.group_by.hash(state);
+        hash_expr(self.having.as_ref(), state);
+        self.order_by.hash(state);
+        self.effective_limit().hash(state);
+        self.offset.hash(state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        let mut params = self.column_params;
+        params.extend(self.source_params);
+        params.extend(self.where_clause.into_iter().flat_map(|e| e.into_parts().1));
+        params.extend(self.having.into_iter().flat_map(|e| e.into_parts().1));
+        params
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.build_for(dialect)
+    }
+}
+
+impl SelectStatement {
+    /// Converts the statement into a [`SelectDyn`] builder.
+    ///
+    /// Literals in WHERE and HAVING become bound parameters; the other
+    /// clauses are carried over as rendered SQL. Returns `None` when LIMIT
+    /// or OFFSET is not a non-negative integer literal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::ast::Statement;
+    /// use oxide_sql_core::Parser;
+    ///
+    /// let Statement::Select(stmt) = Parser::new("SELECT id FROM users WHERE age > 18")
+    ///     .parse_statement()
+    ///     .unwrap()
+    /// else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// let (sql, params) = stmt.to_builder().unwrap().build();
+    /// assert_eq!(sql, "SELECT id FROM users WHERE age > ?");
+    /// assert_eq!(params.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn to_builder(&self) -> Option<SelectDyn<HasColumns, HasFrom>> {
+        SelectDyn::from_statement(self)
+    }
+}
+
+/// Parses a built query and extracts the SELECT statement.
+///
+/// A raw fragment can make the query parse as something else; that is
+/// reported as a `ParseError` spanning the whole query.
+pub(crate) fn select_from_query(query: &Query) -> Result<SelectStatement, ParseError> {
+    match query.to_statement()? {
+        Statement::Select(select) => Ok(select),
+        _ => Err(ParseError::new(
+            "expected a SELECT statement",
+            Span::new(0, query.sql().len()),
+        )),
+    }
+}
+
+/// Reads an optional LIMIT/OFFSET expression as a `u64`.
+///
+/// The outer `Option` is `None` when the expression cannot be represented.
+fn literal_u64(expr: Option<&Expr>) -> Option<Option<u64>> {
+    match expr {
+        None => Some(None),
+        Some(Expr::Literal(Literal::Integer(n))) => u64::try_from(*n).ok().map(Some),
+        Some(_) => None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builder::dyn_col;
+    use crate::builder::test_support::Backticks;
+    use crate::builder::{ColumnRef, dyn_col};
+
+    #[test]
+    fn test_build_for_quotes_as_the_dialect() {
+        let select = || {
+            SelectDyn::new()
+                .columns(&["order", "my col", "u.*"])
+                .expr(dyn_col("a").eq(1), "from")
+                .from("users AS u")
+                .join("group AS g", dyn_col("g.id").eq_expr(dyn_col("u.group_id")))
+                .cross_join("my-table")
+                .where_clause(
+                    dyn_col("my col").eq(2).and(
+                        ColumnRef::qualified("u", "order")
+                            .collate("my coll")
+                            .eq("x"),
+                    ),
+                )
+                .group_by(&["select"])
+                .group_by_grouping_sets(&[&["a", "b c"], &[]])
+                .order_by_desc(&["order"])
+        };
+
+        let (sql, params) = select().build_for(&Backticks);
+        assert_eq!(
+            sql,
+            "SELECT `order`, `my col`, u.*, a = ? AS `from` FROM users AS u \
+             INNER JOIN `group` AS g ON g.id = u.group_id CROSS JOIN `my-table` \
+             WHERE `my col` = ? AND u.`order` COLLATE `my coll` = ? \
+             GROUP BY `select`, GROUPING SETS ((a, `b c`), ()) ORDER BY `order` DESC"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Int(1),
+                SqlValue::Int(2),
+                SqlValue::Text(String::from("x"))
+            ]
+        );
+        assert_eq!(
+            select().build().0,
+            "SELECT \"order\", \"my col\", u.*, a = ? AS \"from\" FROM users AS u \
+             INNER JOIN \"group\" AS g ON g.id = u.group_id CROSS JOIN \"my-table\" \
+             WHERE \"my col\" = ? AND u.\"order\" COLLATE \"my coll\" = ? \
+             GROUP BY \"select\", GROUPING SETS ((a, \"b c\"), ()) ORDER BY \"order\" DESC"
+        );
+    }
+
+    #[test]
+    fn test_from_quotes_like_update_and_delete() {
+        let (sql, _) = SelectDyn::new()
+            .columns(&["my col"])
+            .from("my table")
+            .where_clause(dyn_col("my col").eq(1))
+            .build_for(&Backticks);
+        assert_eq!(sql, "SELECT `my col` FROM `my table` WHERE `my col` = ?");
+
+        let (update, _) = crate::builder::UpdateDyn::new()
+            .table("my table")
+            .set("a", 1)
+            .build();
+        assert!(update.starts_with("UPDATE \"my table\" "));
+        assert!(
+            SelectDyn::new()
+                .all()
+                .from("my table")
+                .build_sql()
+                .ends_with(" FROM \"my table\"")
+        );
+    }
+
+    #[test]
+    fn test_select_from_and_cross_join_expr() {
+        use crate::builder::func::call;
+
+        let (sql, params) = SelectDyn::new()
+            .columns(&["value"])
+            .from_expr(
+                call(
+                    "generate_series",
+                    vec![ExprBuilder::value(1), ExprBuilder::value(3)],
+                ),
+                "s",
+            )
+            .cross_join_expr(call("json_each", vec![ExprBuilder::value("[1, 2]")]), "j")
+            .where_clause(dyn_col("s.value").gt(1))
+            .build();
+
+        assert_eq!(
+            sql,
+            "SELECT value FROM generate_series(?, ?) AS s \
+             CROSS JOIN json_each(?) AS j WHERE s.value > ?"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Int(1),
+                SqlValue::Int(3),
+                SqlValue::Text(String::from("[1, 2]")),
+                SqlValue::Int(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_where_exists() {
+        use crate::builder::{exists, not_exists};
+
+        let active = SelectDyn::new()
+            .columns_raw(&["1"])
+            .from("sessions")
+            .where_clause(dyn_col("sessions.user_id").eq_expr(dyn_col("users.id")));
+        let banned = SelectDyn::new()
+            .columns_raw(&["1"])
+            .from("bans")
+            .where_clause(
+                dyn_col("bans.user_id")
+                    .eq_expr(dyn_col("users.id"))
+                    .and(dyn_col("bans.reason").eq("spam")),
+            );
+
+        let (sql, params) = SelectDyn::new()
+            .columns(&["id"])
+            .from("users")
+            .where_clause(
+                dyn_col("age")
+                    .gt(18)
+                    .and(exists(active))
+                    .and(not_exists(banned)),
+            )
+            .build();
+
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE age > ? \
+             AND EXISTS (SELECT 1 FROM sessions WHERE sessions.user_id = users.id) \
+             AND NOT EXISTS (SELECT 1 FROM bans WHERE bans.user_id = users.id AND bans.reason = ?)"
+        );
+        assert_eq!(
+            params,
+            vec![SqlValue::Int(18), SqlValue::Text(String::from("spam"))]
+        );
+        crate::parser::Parser::new(&sql).parse_statement().unwrap();
+    }
+
+    #[test]
+    fn test_select_guard_caps_limit() {
+        let guard = QueryGuard::new().max_rows(100);
+        let select = || SelectDyn::new().columns(&["id"]).from("users");
+
+        assert_eq!(
+            select().guard(&guard).build_sql(),
+            "SELECT id FROM users LIMIT 100"
+        );
+        assert_eq!(
+            select().limit(5).offset(10).guard(&guard).build_sql(),
+            "SELECT id FROM users LIMIT 5 OFFSET 10"
+        );
+        assert_eq!(
+            select().limit(1000).guard(&guard).build_sql(),
+            "SELECT id FROM users LIMIT 100"
+        );
+        // The guard caps the limit at build time, whatever the call order.
+        assert_eq!(
+            select().guard(&guard).limit(1000).build_sql(),
+            "SELECT id FROM users LIMIT 100"
+        );
+    }
 
     #[test]
     fn test_simple_select() {
@@ -336,20 +890,20 @@ mod tests {
     fn test_select_with_join() {
         let (sql, _) = SelectDyn::new()
             .columns(&["u.id", "o.amount"])
-            .from("users u")
-            .join("orders o", "u.id = o.user_id")
+            .from("users AS u")
+            .join("orders AS o", dyn_col("u.id").eq_expr(dyn_col("o.user_id")))
             .build();
 
         assert_eq!(
             sql,
-            "SELECT u.id, o.amount FROM users u INNER JOIN orders o ON u.id = o.user_id"
+            "SELECT u.id, o.amount FROM users AS u INNER JOIN orders AS o ON u.id = o.user_id"
         );
     }
 
     #[test]
     fn test_select_with_group_by() {
         let (sql, _) = SelectDyn::new()
-            .columns(&["status", "COUNT(*)"])
+            .columns_raw(&["status", "COUNT(*)"])
             .from("orders")
             .group_by(&["status"])
             .build();
@@ -357,6 +911,70 @@ mod tests {
         assert_eq!(sql, "SELECT status, COUNT(*) FROM orders GROUP BY status");
     }
 
+    #[test]
+    fn test_select_with_rollup_and_cube() {
+        let (sql, _) = SelectDyn::new()
+            .columns_raw(&["region", "city", "SUM(total)"])
+            .from("orders")
+            .group_by_rollup(&["region", "city"])
+            .build();
+        assert_eq!(
+            sql,
+            "SELECT region, city, SUM(total) FROM orders GROUP BY ROLLUP(region, city)"
+        );
+
+        let (sql, _) = SelectDyn::new()
+            .columns_raw(&["region", "city", "SUM(total)"])
+            .from("orders")
+            .group_by_cube(&["region", "city"])
+            .build();
+        assert_eq!(
+            sql,
+            "SELECT region, city, SUM(total) FROM orders GROUP BY CUBE(region, city)"
+        );
+    }
+
+    #[test]
+    fn test_select_with_grouping_sets() {
+        let query = SelectDyn::new()
+            .columns_raw(&["region", "city", "SUM(total)"])
+            .from("orders")
+            .group_by_grouping_sets(&[&["region"], &["region", "city"], &[]])
+            .build_query();
+        assert_eq!(
+            query.sql(),
+            "SELECT region, city, SUM(total) FROM orders \
+             GROUP BY GROUPING SETS ((region), (region, city), ())"
+        );
+        let ast = query.to_statement().unwrap();
+        assert_eq!(ast.to_string(), query.sql());
+    }
+
+    #[test]
+    fn test_select_mixes_grouping_elements() {
+        let (sql, _) = SelectDyn::new()
+            .columns(&["a", "b", "c"])
+            .from("t")
+            .group_by(&["a"])
+            .group_by_cube(&["b", "c"])
+            .group_by_rollup(&["order"])
+            .build();
+        assert_eq!(
+            sql,
+            "SELECT a, b, c FROM t GROUP BY a, CUBE(b, c), ROLLUP(\"order\")"
+        );
+
+        let (sql, _) = SelectDyn::new()
+            .columns(&["a"])
+            .from("t")
+            .group_by_grouping_sets(&[&["a; DROP TABLE t"], &[]])
+            .build();
+        assert_eq!(
+            sql,
+            "SELECT a FROM t GROUP BY GROUPING SETS ((\"a; DROP TABLE t\"), ())"
+        );
+    }
+
     #[test]
     fn test_select_with_order_by() {
         let (sql, _) = SelectDyn::new()
@@ -368,6 +986,34 @@ mod tests {
         assert_eq!(sql, "SELECT id, name FROM users ORDER BY name");
     }
 
+    #[test]
+    fn test_select_sanitizes_names() {
+        let (sql, params) = SelectDyn::new()
+            .columns(&["u.id", "name; DROP TABLE users"])
+            .from("users AS u")
+            .join(
+                "orders AS o",
+                dyn_col("o.user_id")
+                    .eq_expr(dyn_col("u.id"))
+                    .and(dyn_col("o.status").eq("open")),
+            )
+            .where_clause(dyn_col("u.active").eq(true))
+            .group_by(&["u.id", "1 OR 1=1"])
+            .order_by_desc(&["id --"])
+            .build();
+
+        assert_eq!(
+            sql,
+            "SELECT u.id, \"name; DROP TABLE users\" FROM users AS u \
+             INNER JOIN orders AS o ON o.user_id = u.id AND o.status = ? \
+             WHERE u.active = ? GROUP BY u.id, \"1 OR 1=1\" ORDER BY \"id --\" DESC"
+        );
+        assert_eq!(
+            params,
+            vec![SqlValue::Text(String::from("open")), SqlValue::Bool(true)]
+        );
+    }
+
     #[test]
     fn test_select_with_limit_offset() {
         let (sql, _) = SelectDyn::new()
@@ -383,9 +1029,9 @@ mod tests {
     #[test]
     fn test_complex_select() {
         let (sql, params) = SelectDyn::new()
-            .columns(&["u.id", "u.name", "COUNT(o.id) as order_count"])
-            .from("users u")
-            .left_join("orders o", "u.id = o.user_id")
+            .columns_raw(&["u.id", "u.name", "COUNT(o.id) as order_count"])
+            .from("users AS u")
+            .left_join("orders AS o", dyn_col("u.id").eq_expr(dyn_col("o.user_id")))
             .where_clause(
                 dyn_col("u.active")
                     .eq(true)
@@ -397,8 +1043,8 @@ mod tests {
             .build();
 
         assert!(sql.contains("SELECT u.id, u.name, COUNT(o.id) as order_count"));
-        assert!(sql.contains("FROM users u"));
-        assert!(sql.contains("LEFT JOIN orders o ON u.id = o.user_id"));
+        assert!(sql.contains("FROM users AS u"));
+        assert!(sql.contains("LEFT JOIN orders AS o ON u.id = o.user_id"));
         assert!(sql.contains("WHERE u.active = ? AND o.status != ?"));
         assert!(sql.contains("GROUP BY u.id, u.name"));
         assert!(sql.contains("ORDER BY order_count DESC"));
@@ -406,6 +1052,217 @@ mod tests {
         assert_eq!(params.len(), 2);
     }
 
+    #[test]
+    fn test_select_window_expr() {
+        use crate::builder::window::{WindowFunction, partition_by};
+
+        let running_total = WindowFunction::new("SUM", vec![ExprBuilder::value(2)])
+            .over(partition_by(&["user_id"]).order_by(&["created_at"]));
+        let (sql, params) = SelectDyn::new()
+            .columns(&["id"])
+            .expr(running_total, "weighted")
+            .from("orders")
+            .where_clause(dyn_col("status").eq("paid"))
+            .build();
+
+        assert_eq!(
+            sql,
+            "SELECT id, SUM(?) OVER (PARTITION BY user_id ORDER BY created_at) AS weighted \
+             FROM orders WHERE status = ?"
+        );
+        assert_eq!(
+            params,
+            vec![SqlValue::Int(2), SqlValue::Text(String::from("paid"))]
+        );
+    }
+
+    #[test]
+    fn test_build_query() {
+        let query = SelectDyn::new()
+            .columns(&["id"])
+            .from("users")
+            .where_clause(dyn_col("name").eq("alice"))
+            .build_query();
+        assert_eq!(query.sql(), "SELECT id FROM users WHERE name = ?");
+        assert_eq!(query.params(), &[SqlValue::Text(String::from("alice"))]);
+        assert_eq!(
+            query.to_debug_string(),
+            "SELECT id FROM users WHERE name = 'alice'"
+        );
+    }
+
+    #[test]
+    fn test_build_into_reuses_buffer() {
+        let mut sql = String::with_capacity(128);
+        let params = SelectDyn::new()
+            .columns(&["status"])
+            .from("orders")
+            .distinct()
+            .where_clause(dyn_col("total").gt(10))
+            .group_by(&["status"])
+            .having(dyn_col("total").gt(2))
+            .order_by(&["status"])
+            .limit(5)
+            .offset(10)
+            .build_into(&mut sql);
+        assert_eq!(
+            sql,
+            "SELECT DISTINCT status FROM orders WHERE total > ? GROUP BY status \
+             HAVING total > ? ORDER BY status LIMIT 5 OFFSET 10"
+        );
+        assert_eq!(params, vec![SqlValue::Int(10), SqlValue::Int(2)]);
+
+        sql.clear();
+        let params = SelectDyn::new()
+            .columns(&["id"])
+            .from("users")
+            .build_into(&mut sql);
+        assert_eq!(sql, "SELECT id FROM users");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_to_ast() {
+        let stmt = SelectDyn::new()
+            .columns(&["id", "name"])
+            .from("users")
+            .where_clause(dyn_col("age").gt(18).and(dyn_col("name").eq("bob")))
+            .order_by_desc(&["id"])
+            .limit(5)
+            .to_ast()
+            .unwrap();
+
+        assert_eq!(stmt.columns.len(), 2);
+        assert_eq!(stmt.limit, Some(Expr::integer(5)));
+        assert_eq!(
+            stmt.to_string(),
+            "SELECT id, name FROM users WHERE age > 18 AND name = 'bob' \
+             ORDER BY id DESC LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn test_ast_round_trip() {
+        let stmt = SelectDyn::new()
+            .columns_raw(&["status", "COUNT(*)"])
+            .distinct()
+            .from("orders")
+            .where_clause(dyn_col("total").gt(100))
+            .group_by(&["status"])
+            .having(ExprBuilder::raw("COUNT(*) > 2"))
+            .offset(10)
+            .to_ast()
+            .unwrap();
+
+        let (sql, params) = stmt.to_builder().unwrap().build();
+        assert_eq!(
+            sql,
+            "SELECT DISTINCT status, COUNT(*) FROM orders WHERE total > ? \
+             GROUP BY status HAVING COUNT(*) > ? OFFSET 10"
+        );
+        assert_eq!(params, vec![SqlValue::Int(100), SqlValue::Int(2)]);
+    }
+
+    #[test]
+    fn test_ast_round_trip_keeps_joins() {
+        let select = || {
+            SelectDyn::new()
+                .columns(&["u.name", "o.total"])
+                .from("users AS u")
+                .join("orders AS o", dyn_col("o.user_id").eq_expr(dyn_col("u.id")))
+                .left_join(
+                    "coupons AS c",
+                    dyn_col("c.order_id")
+                        .eq_expr(dyn_col("o.id"))
+                        .and(dyn_col("c.kind").eq("promo")),
+                )
+                .where_clause(dyn_col("o.total").gt(10))
+        };
+        let expected = select().build();
+
+        let stmt = select().to_ast().unwrap();
+        assert_eq!(stmt.to_builder().unwrap().build(), expected);
+        assert_eq!(
+            expected.1,
+            vec![SqlValue::Text(String::from("promo")), SqlValue::Int(10)]
+        );
+    }
+
+    #[test]
+    fn test_ast_round_trip_binds_null_comparisons() {
+        let select = || {
+            SelectDyn::new()
+                .columns(&["id"])
+                .from("users")
+                .where_clause(
+                    dyn_col("a")
+                        .eq(None::<i64>)
+                        .or(dyn_col("b").not_eq(None::<&str>)),
+                )
+        };
+        let expected = select().build();
+
+        let stmt = select().to_ast().unwrap();
+        assert_eq!(stmt.to_builder().unwrap().build(), expected);
+        assert_eq!(
+            expected,
+            (
+                String::from("SELECT id FROM users WHERE a = ? OR b != ?"),
+                vec![SqlValue::Null, SqlValue::Null]
+            )
+        );
+    }
+
+    #[test]
+    fn test_ast_round_trip_keeps_double_negation_apart() {
+        let Statement::Select(stmt) = crate::Parser::new("SELECT a FROM t WHERE - -a > 1")
+            .parse_statement()
+            .unwrap()
+        else {
+            unreachable!()
+        };
+        let (sql, params) = stmt.to_builder().unwrap().build();
+        assert_eq!(sql, "SELECT a FROM t WHERE - -a > ?");
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_select_from_query_rejects_other_statements() {
+        let query = Query::new(String::from("DELETE FROM users"), vec![]);
+        let err = select_from_query(&query).unwrap_err();
+        assert_eq!(err.message, "expected a SELECT statement");
+    }
+
+    #[test]
+    fn test_to_builder_rejects_non_literal_limit() {
+        let Statement::Select(stmt) = crate::Parser::new("SELECT a FROM t LIMIT 1 + 1")
+            .parse_statement()
+            .unwrap()
+        else {
+            unreachable!()
+        };
+        assert!(stmt.to_builder().is_none());
+    }
+
+    #[test]
+    fn test_from_quotes_invalid_table() {
+        let (sql, _) = SelectDyn::new()
+            .columns(&["id"])
+            .from("users; DROP TABLE x")
+            .build();
+        assert_eq!(sql, "SELECT id FROM \"users; DROP TABLE x\"");
+    }
+
+    #[test]
+    fn test_join_quotes_invalid_table() {
+        let (sql, _) = SelectDyn::new()
+            .all()
+            .from("users AS u")
+            .cross_join("orders--")
+            .build();
+        assert_eq!(sql, "SELECT * FROM users AS u CROSS JOIN \"orders--\"");
+    }
+
     // Compile-time tests (these would fail to compile if uncommented)
 
     // This would fail to compile: SELECT without FROM
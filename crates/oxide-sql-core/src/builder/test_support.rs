@@ -0,0 +1,16 @@
+//! Fixtures shared by the builder tests.
+
+use crate::dialect::Dialect;
+
+/// A dialect quoting identifiers with backticks.
+pub(crate) struct Backticks;
+
+impl Dialect for Backticks {
+    fn name(&self) -> &'static str {
+        "backticks"
+    }
+
+    fn identifier_quote(&self) -> char {
+        '`'
+    }
+}
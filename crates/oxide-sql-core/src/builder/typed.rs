@@ -32,23 +32,63 @@
 //! assert_eq!(sql, "SELECT id, name, email FROM users");
 //! ```
 
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
-use crate::schema::{Column, Selectable, Table};
+use crate::ast::SelectStatement;
+use crate::dialect::{Dialect, GenericDialect};
+use crate::parser::ParseError;
+use crate::schema::{Column, DecodeRow, Selectable, Table, contains_name};
 
-use super::expr::ExprBuilder;
-use super::value::SqlValue;
+use super::cache::{CacheableQuery, hash_expr};
+use super::expr::{ChunkError, ExprBuilder};
+use super::guard::QueryGuard;
+use super::ident::push_table_for;
+use super::query::Query;
+use super::render::{expr_len, list_len, push_expr, push_list, push_number, push_placeholders};
+use super::select::select_from_query;
+use super::value::{DecodeError, SqlValue, ToSqlValue};
 
 // Typestate markers
 /// Marker: No columns selected yet.
 pub struct NoColumns;
 /// Marker: Columns have been selected.
-pub struct HasColumns;
+///
+/// `S` is the [`Selectable`] passed to [`Select::select`], or `()` for
+/// [`Select::select_all`].
+pub struct HasColumns<S = ()>(PhantomData<S>);
 /// Marker: No table specified yet.
 pub struct NoFrom;
 /// Marker: Table has been specified.
 pub struct HasFrom;
 
+/// One grouping of [`Select::group_by_grouping_sets`], over columns of
+/// table `T`.
+pub struct GroupingSet<T: Table> {
+    columns: &'static [&'static str],
+    _table: PhantomData<T>,
+}
+
+impl<T: Table> GroupingSet<T> {
+    /// Groups by the columns of `S`.
+    #[must_use]
+    pub fn of<S: Selectable<T>>() -> Self {
+        Self {
+            columns: S::column_names(),
+            _table: PhantomData,
+        }
+    }
+
+    /// The empty grouping `()`, i.e. the grand total.
+    #[must_use]
+    pub const fn total() -> Self {
+        Self {
+            columns: &[],
+            _table: PhantomData,
+        }
+    }
+}
+
 /// A type-safe SELECT query builder that validates column names at compile time.
 ///
 /// This builder uses generic type parameters to ensure that:
@@ -64,9 +104,11 @@ where
     columns: Vec<&'static str>,
     from: Option<&'static str>,
     where_clause: Option<ExprBuilder>,
-    order_by: Vec<(&'static str, bool)>,
+    group_by: Vec<String>,
+    order_by: Vec<(&'static str, Option<String>, bool)>,
     limit: Option<i64>,
     offset: Option<i64>,
+    guard: Option<QueryGuard>,
     _table: PhantomData<T>,
     _cols: PhantomData<Cols>,
     _from: PhantomData<From>,
@@ -80,9 +122,11 @@ impl<T: Table> Select<T, NoColumns, NoFrom> {
             columns: vec![],
             from: None,
             where_clause: None,
+            group_by: vec![],
             order_by: vec![],
             limit: None,
             offset: None,
+            guard: None,
             _table: PhantomData,
             _cols: PhantomData,
             _from: PhantomData,
@@ -102,15 +146,53 @@ impl<T: Table, From> Select<T, NoColumns, From> {
     /// The columns must implement `Selectable<T>` and belong to the table `T`.
     /// This is enforced at compile time.
     #[must_use]
-    pub fn select<S: Selectable<T>>(self) -> Select<T, HasColumns, From> {
+    pub fn select<S: Selectable<T>>(self) -> Select<T, HasColumns<S>, From> {
         let column_names = S::column_names();
         Select {
             columns: column_names.to_vec(),
             from: self.from,
             where_clause: self.where_clause,
+            group_by: self.group_by,
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            guard: self.guard,
+            _table: PhantomData,
+            _cols: PhantomData,
+            _from: PhantomData,
+        }
+    }
+
+    /// Selects all columns from the table except those in `S`.
+    ///
+    /// Useful for leaving out large or sensitive columns, e.g.
+    /// `select_all_except::<(UserColumns::PasswordHash,)>()`. The remaining
+    /// columns keep their declaration order.
+    ///
+    /// Excluding every column of the table is a compile-time error, since
+    /// it would leave nothing to select.
+    #[must_use]
+    pub fn select_all_except<S: Selectable<T>>(self) -> Select<T, HasColumns, From> {
+        const {
+            assert!(
+                !excludes_all(T::COLUMNS, S::COLUMN_NAMES),
+                "select_all_except() must leave at least one column to select"
+            );
+        }
+        let excluded = S::column_names();
+        Select {
+            columns: T::COLUMNS
+                .iter()
+                .copied()
+                .filter(|name| !excluded.contains(name))
+                .collect(),
+            from: self.from,
+            where_clause: self.where_clause,
+            group_by: self.group_by,
             order_by: self.order_by,
             limit: self.limit,
             offset: self.offset,
+            guard: self.guard,
             _table: PhantomData,
             _cols: PhantomData,
             _from: PhantomData,
@@ -124,9 +206,11 @@ impl<T: Table, From> Select<T, NoColumns, From> {
             columns: T::COLUMNS.to_vec(),
             from: self.from,
             where_clause: self.where_clause,
+            group_by: self.group_by,
             order_by: self.order_by,
             limit: self.limit,
             offset: self.offset,
+            guard: self.guard,
             _table: PhantomData,
             _cols: PhantomData,
             _from: PhantomData,
@@ -134,6 +218,18 @@ impl<T: Table, From> Select<T, NoColumns, From> {
     }
 }
 
+/// Returns `true` if every name in `columns` is in `excluded`.
+const fn excludes_all(columns: &[&str], excluded: &[&str]) -> bool {
+    let mut i = 0;
+    while i < columns.len() {
+        if !contains_name(excluded, columns[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 impl<T: Table, Cols> Select<T, Cols, NoFrom> {
     /// Specifies the table to query from.
     ///
@@ -144,9 +240,11 @@ impl<T: Table, Cols> Select<T, Cols, NoFrom> {
             columns: self.columns,
             from: Some(T::NAME),
             where_clause: self.where_clause,
+            group_by: self.group_by,
             order_by: self.order_by,
             limit: self.limit,
             offset: self.offset,
+            guard: self.guard,
             _table: PhantomData,
             _cols: PhantomData,
             _from: PhantomData,
@@ -170,10 +268,67 @@ impl<T: Table, Cols> Select<T, Cols, HasFrom> {
         self
     }
 
+    /// Appends the columns of `S` to the GROUP BY clause.
+    #[must_use]
+    pub fn group_by<S: Selectable<T>>(mut self) -> Self {
+        self.group_by
+            .extend(S::column_names().iter().map(|c| String::from(*c)));
+        self
+    }
+
+    /// Appends a `ROLLUP(...)` element over the columns of `S` to the
+    /// GROUP BY clause, producing subtotals for each prefix and a grand
+    /// total.
+    #[must_use]
+    pub fn group_by_rollup<S: Selectable<T>>(mut self) -> Self {
+        self.group_by
+            .push(format!("ROLLUP({})", S::column_names().join(", ")));
+        self
+    }
+
+    /// Appends a `CUBE(...)` element over the columns of `S` to the
+    /// GROUP BY clause, producing subtotals for every combination.
+    #[must_use]
+    pub fn group_by_cube<S: Selectable<T>>(mut self) -> Self {
+        self.group_by
+            .push(format!("CUBE({})", S::column_names().join(", ")));
+        self
+    }
+
+    /// Appends a `GROUPING SETS (...)` element to the GROUP BY clause, with
+    /// one grouping per entry of `sets`.
+    #[must_use]
+    pub fn group_by_grouping_sets(mut self, sets: &[GroupingSet<T>]) -> Self {
+        let sets: Vec<String> = sets
+            .iter()
+            .map(|set| format!("({})", set.columns.join(", ")))
+            .collect();
+        self.group_by
+            .push(format!("GROUPING SETS ({})", sets.join(", ")));
+        self
+    }
+
     /// Adds an ORDER BY clause for a column.
     #[must_use]
     pub fn order_by<C: Column<Table = T>>(mut self, _col: C, ascending: bool) -> Self {
-        self.order_by.push((C::NAME, ascending));
+        self.order_by.push((C::NAME, None, ascending));
+        self
+    }
+
+    /// Adds an ORDER BY clause for a column compared under `collation`
+    /// (`name COLLATE NOCASE`).
+    ///
+    /// The collation name is quoted if it is not a plain identifier; pass
+    /// it already quoted (`"\"de_DE\""`) to keep its case on PostgreSQL.
+    #[must_use]
+    pub fn order_by_collate<C: Column<Table = T>>(
+        mut self,
+        _col: C,
+        collation: &str,
+        ascending: bool,
+    ) -> Self {
+        self.order_by
+            .push((C::NAME, Some(String::from(collation)), ascending));
         self
     }
 
@@ -190,17 +345,74 @@ impl<T: Table, Cols> Select<T, Cols, HasFrom> {
         self.offset = Some(offset);
         self
     }
+
+    /// Applies the limits of `guard`, capping the LIMIT clause at its
+    /// maximum row count. A negative LIMIT counts as no limit.
+    ///
+    /// The cap is applied when the query is built, so a later
+    /// [`limit`](Self::limit) cannot exceed it. A later `guard()` call
+    /// replaces this one.
+    #[must_use]
+    pub const fn guard(mut self, guard: &QueryGuard) -> Self {
+        self.guard = Some(*guard);
+        self
+    }
+
+    /// Returns the LIMIT to render, capped by the guard.
+    fn effective_limit(&self) -> Option<i64> {
+        let Some(guard) = self.guard else {
+            return self.limit;
+        };
+        let limit = self.limit.and_then(|n| u64::try_from(n).ok());
+        guard
+            .clamp_limit(limit)
+            .map_or(self.limit, |n| Some(i64::try_from(n).unwrap_or(i64::MAX)))
+    }
 }
 
-impl<T: Table> Select<T, HasColumns, HasFrom> {
+impl<T: Table, S> Select<T, HasColumns<S>, HasFrom> {
     /// Builds the query and returns (SQL, parameters).
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("SELECT ");
+        self.render_for(&GenericDialect)
+    }
+
+    /// Builds the query, quoting names inside expressions as `dialect`
+    /// does.
+    fn render_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(
+            48 + list_len(&self.columns)
+                + T::NAME.len()
+                + expr_len(self.where_clause.as_ref())
+                + list_len(&self.group_by)
+                + self
+                    .order_by
+                    .iter()
+                    .map(|(col, collation, _)| {
+                        col.len() + 7 + collation.as_ref().map_or(0, |c| c.len() + 9)
+                    })
+                    .sum::<usize>(),
+        );
+        let params = self.render_into(&mut sql, dialect);
+        (sql, params)
+    }
+
+    /// Renders the query into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.render_into(sql, &GenericDialect)
+    }
+
+    /// Renders the query into `sql`, quoting names inside expressions as
+    /// `dialect` does.
+    fn render_into<D: Dialect + ?Sized>(self, sql: &mut String, dialect: &D) -> Vec<SqlValue> {
+        let limit = self.effective_limit();
         let mut params = vec![];
 
         // Columns
-        sql.push_str(&self.columns.join(", "));
+        sql.push_str("SELECT ");
+        push_list(sql, &self.columns);
 
         // FROM
         if let Some(table) = self.from {
@@ -209,40 +421,38 @@ impl<T: Table> Select<T, HasColumns, HasFrom> {
         }
 
         // WHERE
-        if let Some(ref where_expr) = self.where_clause {
-            sql.push_str(" WHERE ");
-            sql.push_str(where_expr.sql());
-            params.extend(where_expr.params().iter().cloned());
+        push_expr(sql, " WHERE ", self.where_clause, &mut params, dialect);
+
+        // GROUP BY
+        if !self.group_by.is_empty() {
+            sql.push_str(" GROUP BY ");
+            push_list(sql, &self.group_by);
         }
 
         // ORDER BY
-        if !self.order_by.is_empty() {
-            sql.push_str(" ORDER BY ");
-            let orders: Vec<String> = self
-                .order_by
-                .iter()
-                .map(|(col, asc)| {
-                    if *asc {
-                        (*col).to_string()
-                    } else {
-                        format!("{} DESC", col)
-                    }
-                })
-                .collect();
-            sql.push_str(&orders.join(", "));
+        for (i, (col, collation, asc)) in self.order_by.iter().enumerate() {
+            sql.push_str(if i == 0 { " ORDER BY " } else { ", " });
+            sql.push_str(col);
+            if let Some(collation) = collation {
+                sql.push_str(" COLLATE ");
+                push_table_for(sql, collation, dialect);
+            }
+            if !asc {
+                sql.push_str(" DESC");
+            }
         }
 
-        // LIMIT
-        if let Some(limit) = self.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
-        }
+        // LIMIT / OFFSET
+        push_number(sql, " LIMIT ", limit);
+        push_number(sql, " OFFSET ", self.offset);
 
-        // OFFSET
-        if let Some(offset) = self.offset {
-            sql.push_str(&format!(" OFFSET {}", offset));
-        }
+        params
+    }
 
-        (sql, params)
+    /// Builds the query and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the query and returns only the SQL string.
@@ -251,6 +461,58 @@ impl<T: Table> Select<T, HasColumns, HasFrom> {
         let (sql, _) = self.build();
         sql
     }
+
+    /// Converts the query into a parsed [`SelectStatement`].
+    ///
+    /// Bound parameters become literals in the resulting tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if a raw fragment cannot be parsed.
+    pub fn to_ast(self) -> Result<SelectStatement, ParseError> {
+        select_from_query(&self.build_query())
+    }
+}
+
+impl<T: Table, S> From<Select<T, HasColumns<S>, HasFrom>> for Query {
+    fn from(select: Select<T, HasColumns<S>, HasFrom>) -> Self {
+        select.build_query()
+    }
+}
+
+impl<T: Table, S: DecodeRow<T>, From> Select<T, HasColumns<S>, From> {
+    /// Returns the function that decodes result rows of this query into
+    /// `S::Output`, e.g. `(i64, String)` for `select::<(Id, Name)>()`.
+    ///
+    /// Rows must contain the values in selection order; no column names
+    /// are looked up at runtime.
+    #[must_use]
+    pub fn row_decoder(&self) -> fn(Vec<SqlValue>) -> Result<S::Output, DecodeError> {
+        S::decode_row
+    }
+}
+
+impl<T: Table, S> CacheableQuery for Select<T, HasColumns<S>, HasFrom> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "select".hash(state);
+        self.columns.hash(state);
+        self.from.hash(state);
+        hash_expr(self.where_clause.as_ref(), state);
+        self.group_by.hash(state);
+        self.order_by.hash(state);
+        self.effective_limit().hash(state);
+        self.offset.hash(state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        self.where_clause
+            .map(|e| e.into_parts().1)
+            .unwrap_or_default()
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.render_for(dialect)
+    }
 }
 
 // ============================================================================
@@ -332,15 +594,29 @@ impl<T: Table> Insert<T, HasValues> {
     /// Builds the query and returns (SQL, parameters).
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("INSERT INTO ");
+        let mut sql = String::with_capacity(24 + T::NAME.len() + list_len(&self.columns) * 2);
+        let params = self.build_into(&mut sql);
+        (sql, params)
+    }
+
+    /// Renders the query into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        sql.push_str("INSERT INTO ");
         sql.push_str(T::NAME);
         sql.push_str(" (");
-        sql.push_str(&self.columns.join(", "));
+        push_list(sql, &self.columns);
         sql.push_str(") VALUES (");
-        let placeholders: Vec<&str> = self.values.iter().map(|_| "?").collect();
-        sql.push_str(&placeholders.join(", "));
+        push_placeholders(sql, self.values.len());
         sql.push(')');
-        (sql, self.values)
+        self.values
+    }
+
+    /// Builds the query and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the query and returns only the SQL string.
@@ -351,6 +627,24 @@ impl<T: Table> Insert<T, HasValues> {
     }
 }
 
+impl<T: Table> CacheableQuery for Insert<T, HasValues> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "insert".hash(state);
+        T::NAME.hash(state);
+        self.columns.hash(state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        self.values
+    }
+
+    fn render<D: Dialect + ?Sized>(self, _dialect: &D) -> (String, Vec<SqlValue>) {
+        // Table and column names come from the schema and are emitted
+        // as-is, so there is nothing to quote for the dialect.
+        self.build()
+    }
+}
+
 // ============================================================================
 // Update
 // ============================================================================
@@ -372,7 +666,7 @@ pub struct Update<T, Set>
 where
     T: Table,
 {
-    sets: Vec<(&'static str, SqlValue)>,
+    sets: Vec<(&'static str, ExprBuilder)>,
     where_clause: Option<ExprBuilder>,
     _table: PhantomData<T>,
     _set: PhantomData<Set>,
@@ -397,7 +691,24 @@ impl<T: Table> Update<T, NoSet> {
         C: Column<Table = T>,
         V: Into<SqlValue>,
     {
-        self.sets.push((C::NAME, value.into()));
+        self.sets.push((C::NAME, ExprBuilder::from(value.into())));
+        Update {
+            sets: self.sets,
+            where_clause: self.where_clause,
+            _table: PhantomData,
+            _set: PhantomData,
+        }
+    }
+
+    /// Sets a column to an expression, e.g.
+    /// `set_expr(User::views(), col(User::views()).add(1))`.
+    #[must_use]
+    pub fn set_expr<C: Column<Table = T>>(
+        mut self,
+        _col: C,
+        expr: ExprBuilder,
+    ) -> Update<T, HasSet> {
+        self.sets.push((C::NAME, expr));
         Update {
             sets: self.sets,
             where_clause: self.where_clause,
@@ -421,7 +732,14 @@ impl<T: Table> Update<T, HasSet> {
         C: Column<Table = T>,
         V: Into<SqlValue>,
     {
-        self.sets.push((C::NAME, value.into()));
+        self.sets.push((C::NAME, ExprBuilder::from(value.into())));
+        self
+    }
+
+    /// Sets another column to an expression.
+    #[must_use]
+    pub fn set_expr<C: Column<Table = T>>(mut self, _col: C, expr: ExprBuilder) -> Self {
+        self.sets.push((C::NAME, expr));
         self
     }
 
@@ -439,29 +757,101 @@ impl<T: Table> Update<T, HasSet> {
         self
     }
 
+    /// Builds one UPDATE statement per chunk of `values`, each restricted
+    /// to `col IN (...)`.
+    ///
+    /// An existing WHERE clause is kept and ANDed with every chunk. Chunks
+    /// hold at most `chunk_size` values, and fewer if needed so that each
+    /// statement, SET and WHERE parameters included, stays within
+    /// [`Dialect::max_parameters`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkError`] if `chunk_size` is zero or the SET and WHERE
+    /// clauses alone reach the dialect's parameter limit.
+    pub fn where_in_chunked<C: Column<Table = T>, V: ToSqlValue, D: Dialect + ?Sized>(
+        self,
+        _col: C,
+        values: Vec<V>,
+        chunk_size: usize,
+        dialect: &D,
+    ) -> Result<impl Iterator<Item = (String, Vec<SqlValue>)> + use<T, C, V, D>, ChunkError> {
+        let sets = self.sets;
+        let set_params = sets.iter().map(|(_, expr)| expr.params().len()).sum();
+        let values = values.into_iter().map(ToSqlValue::to_sql_value).collect();
+        let chunks = ExprBuilder::in_list_chunks(
+            self.where_clause,
+            ExprBuilder::column(C::NAME),
+            values,
+            chunk_size,
+            set_params,
+            dialect.max_parameters(),
+        )?;
+        Ok(chunks.map(move |expr| {
+            Update::<T, HasSet> {
+                sets: sets.clone(),
+                where_clause: Some(expr),
+                _table: PhantomData,
+                _set: PhantomData,
+            }
+            .build()
+        }))
+    }
+
     /// Builds the query and returns (SQL, parameters).
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("UPDATE ");
-        sql.push_str(T::NAME);
-        sql.push_str(" SET ");
+        self.render_for(&GenericDialect)
+    }
 
-        let set_clauses: Vec<String> = self
-            .sets
-            .iter()
-            .map(|(col, _)| format!("{} = ?", col))
-            .collect();
-        sql.push_str(&set_clauses.join(", "));
+    /// Builds the query, quoting names inside expressions as `dialect`
+    /// does.
+    fn render_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(
+            16 + T::NAME.len()
+                + self
+                    .sets
+                    .iter()
+                    .map(|(col, expr)| col.len() + expr.fragment().estimated_len() + 5)
+                    .sum::<usize>()
+                + expr_len(self.where_clause.as_ref()),
+        );
+        let params = self.render_into(&mut sql, dialect);
+        (sql, params)
+    }
 
-        let mut params: Vec<SqlValue> = self.sets.into_iter().map(|(_, v)| v).collect();
+    /// Renders the query into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.render_into(sql, &GenericDialect)
+    }
 
-        if let Some(ref where_expr) = self.where_clause {
-            sql.push_str(" WHERE ");
-            sql.push_str(where_expr.sql());
-            params.extend(where_expr.params().iter().cloned());
+    /// Renders the query into `sql`, quoting names inside expressions as
+    /// `dialect` does.
+    fn render_into<D: Dialect + ?Sized>(self, sql: &mut String, dialect: &D) -> Vec<SqlValue> {
+        let mut params = vec![];
+
+        sql.push_str("UPDATE ");
+        sql.push_str(T::NAME);
+        sql.push_str(" SET ");
+        for (i, (col, expr)) in self.sets.into_iter().enumerate() {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+            sql.push_str(col);
+            push_expr(sql, " = ", Some(expr), &mut params, dialect);
         }
 
-        (sql, params)
+        push_expr(sql, " WHERE ", self.where_clause, &mut params, dialect);
+
+        params
+    }
+
+    /// Builds the query and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the query and returns only the SQL string.
@@ -472,6 +862,31 @@ impl<T: Table> Update<T, HasSet> {
     }
 }
 
+impl<T: Table> CacheableQuery for Update<T, HasSet> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "update".hash(state);
+        T::NAME.hash(state);
+        for (col, expr) in &self.sets {
+            col.hash(state);
+            expr.fragment().hash(state);
+        }
+        hash_expr(self.where_clause.as_ref(), state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        self.sets
+            .into_iter()
+            .map(|(_, expr)| expr)
+            .chain(self.where_clause)
+            .flat_map(|e| e.into_parts().1)
+            .collect()
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.render_for(dialect)
+    }
+}
+
 // ============================================================================
 // Delete
 // ============================================================================
@@ -516,20 +931,81 @@ impl<T: Table> Delete<T> {
         self
     }
 
+    /// Builds one DELETE statement per chunk of `values`, each restricted
+    /// to `col IN (...)`.
+    ///
+    /// An existing WHERE clause is kept and ANDed with every chunk. Chunks
+    /// hold at most `chunk_size` values, and fewer if needed so that each
+    /// statement, WHERE parameters included, stays within
+    /// [`Dialect::max_parameters`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkError`] if `chunk_size` is zero or the WHERE clause
+    /// alone reaches the dialect's parameter limit.
+    pub fn where_in_chunked<C: Column<Table = T>, V: ToSqlValue, D: Dialect + ?Sized>(
+        self,
+        _col: C,
+        values: Vec<V>,
+        chunk_size: usize,
+        dialect: &D,
+    ) -> Result<impl Iterator<Item = (String, Vec<SqlValue>)> + use<T, C, V, D>, ChunkError> {
+        let values = values.into_iter().map(ToSqlValue::to_sql_value).collect();
+        let chunks = ExprBuilder::in_list_chunks(
+            self.where_clause,
+            ExprBuilder::column(C::NAME),
+            values,
+            chunk_size,
+            0,
+            dialect.max_parameters(),
+        )?;
+        Ok(chunks.map(|expr| {
+            Self {
+                where_clause: Some(expr),
+                _table: PhantomData,
+            }
+            .build()
+        }))
+    }
+
     /// Builds the query and returns (SQL, parameters).
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("DELETE FROM ");
-        sql.push_str(T::NAME);
+        self.render_for(&GenericDialect)
+    }
+
+    /// Builds the query, quoting names inside expressions as `dialect`
+    /// does.
+    fn render_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql =
+            String::with_capacity(16 + T::NAME.len() + expr_len(self.where_clause.as_ref()));
+        let params = self.render_into(&mut sql, dialect);
+        (sql, params)
+    }
+
+    /// Renders the query into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.render_into(sql, &GenericDialect)
+    }
+
+    /// Renders the query into `sql`, quoting names inside expressions as
+    /// `dialect` does.
+    fn render_into<D: Dialect + ?Sized>(self, sql: &mut String, dialect: &D) -> Vec<SqlValue> {
         let mut params = vec![];
 
-        if let Some(ref where_expr) = self.where_clause {
-            sql.push_str(" WHERE ");
-            sql.push_str(where_expr.sql());
-            params.extend(where_expr.params().iter().cloned());
-        }
+        sql.push_str("DELETE FROM ");
+        sql.push_str(T::NAME);
+        push_expr(sql, " WHERE ", self.where_clause, &mut params, dialect);
 
-        (sql, params)
+        params
+    }
+
+    /// Builds the query and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the query and returns only the SQL string.
@@ -540,6 +1016,24 @@ impl<T: Table> Delete<T> {
     }
 }
 
+impl<T: Table> CacheableQuery for Delete<T> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "delete".hash(state);
+        T::NAME.hash(state);
+        hash_expr(self.where_clause.as_ref(), state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        self.where_clause
+            .map(|e| e.into_parts().1)
+            .unwrap_or_default()
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.render_for(dialect)
+    }
+}
+
 impl<T: Table> Default for Delete<T> {
     fn default() -> Self {
         Self::new()
@@ -560,6 +1054,15 @@ pub fn col<C: Column>(_col: C) -> ExprBuilder {
     ExprBuilder::column(C::NAME)
 }
 
+/// Creates a type-safe column expression qualified by its table name
+/// (`users.id`).
+///
+/// Use this to refer to a column of the outer query from a correlated
+/// subquery, e.g. in [`exists`](super::exists).
+pub fn qualified_col<C: Column>(_col: C) -> ExprBuilder {
+    ExprBuilder::column(&format!("{}.{}", <C::Table as Table>::NAME, C::NAME))
+}
+
 #[cfg(test)]
 mod tests {
     // Note: Tests require the derive macro to be available,
@@ -3,9 +3,16 @@
 //! This module provides string-based query building. For compile-time
 //! validated queries using schema traits, use `Update` from `builder::typed`.
 
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
-use super::expr::ExprBuilder;
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::cache::{CacheableQuery, hash_expr};
+use super::expr::{ChunkError, ExprBuilder, dyn_col};
+use super::ident::{push_column_for, push_table_for};
+use super::query::Query;
+use super::render::{expr_len, push_expr};
 use super::value::{SqlValue, ToSqlValue};
 
 // Typestate markers
@@ -20,9 +27,10 @@ pub struct NoSet;
 pub struct HasSet;
 
 /// An assignment in the SET clause.
+#[derive(Clone)]
 struct Assignment {
     column: String,
-    value: SqlValue,
+    value: ExprBuilder,
 }
 
 /// A dynamic UPDATE statement builder using string-based column names.
@@ -77,7 +85,21 @@ impl UpdateDyn<HasTable, NoSet> {
             table: self.table,
             assignments: vec![Assignment {
                 column: String::from(column),
-                value: value.to_sql_value(),
+                value: ExprBuilder::value(value),
+            }],
+            where_clause: self.where_clause,
+            _state: PhantomData,
+        }
+    }
+
+    /// Adds a SET assignment to an expression, e.g. `views = views + 1`.
+    #[must_use]
+    pub fn set_expr(self, column: &str, expr: ExprBuilder) -> UpdateDyn<HasTable, HasSet> {
+        UpdateDyn {
+            table: self.table,
+            assignments: vec![Assignment {
+                column: String::from(column),
+                value: expr,
             }],
             where_clause: self.where_clause,
             _state: PhantomData,
@@ -92,7 +114,17 @@ impl UpdateDyn<HasTable, HasSet> {
     pub fn set<T: ToSqlValue>(mut self, column: &str, value: T) -> Self {
         self.assignments.push(Assignment {
             column: String::from(column),
-            value: value.to_sql_value(),
+            value: ExprBuilder::value(value),
+        });
+        self
+    }
+
+    /// Adds another SET assignment to an expression.
+    #[must_use]
+    pub fn set_expr(mut self, column: &str, expr: ExprBuilder) -> Self {
+        self.assignments.push(Assignment {
+            column: String::from(column),
+            value: expr,
         });
         self
     }
@@ -104,36 +136,120 @@ impl UpdateDyn<HasTable, HasSet> {
         self
     }
 
+    /// Builds one UPDATE statement per chunk of `values`, each restricted
+    /// to `column IN (...)`.
+    ///
+    /// An existing WHERE clause is kept and ANDed with every chunk. Chunks
+    /// hold at most `chunk_size` values, and fewer if needed so that each
+    /// statement, SET and WHERE parameters included, stays within
+    /// [`Dialect::max_parameters`]. Names are quoted as `dialect` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkError`] if `chunk_size` is zero or the SET and WHERE
+    /// clauses alone reach the dialect's parameter limit.
+    pub fn where_in_chunked<'a, T: ToSqlValue, D: Dialect + ?Sized>(
+        self,
+        column: &str,
+        values: Vec<T>,
+        chunk_size: usize,
+        dialect: &'a D,
+    ) -> Result<impl Iterator<Item = (String, Vec<SqlValue>)> + use<'a, T, D>, ChunkError> {
+        let where_clause = self.where_clause;
+        let mut prefix = String::new();
+        let set_params = UpdateDyn::<HasTable, HasSet> {
+            table: self.table,
+            assignments: self.assignments,
+            where_clause: None,
+            _state: PhantomData,
+        }
+        .build_into_for(&mut prefix, dialect);
+        let values = values.into_iter().map(ToSqlValue::to_sql_value).collect();
+        let chunks = ExprBuilder::in_list_chunks(
+            where_clause,
+            dyn_col(column).into(),
+            values,
+            chunk_size,
+            set_params.len(),
+            dialect.max_parameters(),
+        )?;
+        Ok(chunks.map(move |expr| {
+            let mut sql = prefix.clone();
+            let mut params = set_params.clone();
+            push_expr(&mut sql, " WHERE ", Some(expr), &mut params, dialect);
+            (sql, params)
+        }))
+    }
+
     /// Builds the UPDATE statement and returns SQL with parameters.
+    ///
+    /// Names are quoted as [`GenericDialect`] does; use
+    /// [`build_for`](Self::build_for) to quote for another dialect.
     #[must_use]
     pub fn build(self) -> (String, Vec<SqlValue>) {
-        let mut sql = String::from("UPDATE ");
+        self.build_for(&GenericDialect)
+    }
+
+    /// Builds the UPDATE statement, quoting table and column names as
+    /// `dialect` does, and returns SQL with parameters.
+    #[must_use]
+    pub fn build_for<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        let mut sql = String::with_capacity(self.estimated_len());
+        let params = self.build_into_for(&mut sql, dialect);
+        (sql, params)
+    }
+
+    /// Renders the UPDATE statement into `sql` and returns the parameters.
+    ///
+    /// Appends to `sql` without allocating intermediate strings, so one
+    /// buffer can be reused across statements.
+    pub fn build_into(self, sql: &mut String) -> Vec<SqlValue> {
+        self.build_into_for(sql, &GenericDialect)
+    }
+
+    /// Renders the UPDATE statement into `sql` like
+    /// [`build_into`](Self::build_into), quoting names as `dialect` does.
+    pub fn build_into_for<D: Dialect + ?Sized>(
+        self,
+        sql: &mut String,
+        dialect: &D,
+    ) -> Vec<SqlValue> {
         let mut params = vec![];
 
+        sql.push_str("UPDATE ");
         if let Some(ref table) = self.table {
-            sql.push_str(table);
+            push_table_for(sql, table, dialect);
         }
 
         sql.push_str(" SET ");
+        for (i, assignment) in self.assignments.into_iter().enumerate() {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+            push_column_for(sql, &assignment.column, dialect);
+            push_expr(sql, " = ", Some(assignment.value), &mut params, dialect);
+        }
 
-        let set_parts: Vec<String> = self
-            .assignments
-            .iter()
-            .map(|a| format!("{} = ?", a.column))
-            .collect();
-        sql.push_str(&set_parts.join(", "));
+        push_expr(sql, " WHERE ", self.where_clause, &mut params, dialect);
 
-        for assignment in self.assignments {
-            params.push(assignment.value);
-        }
+        params
+    }
 
-        if let Some(ref where_expr) = self.where_clause {
-            sql.push_str(" WHERE ");
-            sql.push_str(where_expr.sql());
-            params.extend(where_expr.params().iter().cloned());
-        }
+    /// Estimates the length of the rendered SQL, for pre-sizing buffers.
+    fn estimated_len(&self) -> usize {
+        16 + self.table.as_ref().map_or(0, String::len)
+            + self
+                .assignments
+                .iter()
+                .map(|a| a.column.len() + a.value.fragment().estimated_len() + 5)
+                .sum::<usize>()
+            + expr_len(self.where_clause.as_ref())
+    }
 
-        (sql, params)
+    /// Builds the UPDATE statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
     }
 
     /// Builds the UPDATE statement and returns only the SQL string.
@@ -144,10 +260,50 @@ impl UpdateDyn<HasTable, HasSet> {
     }
 }
 
+impl CacheableQuery for UpdateDyn<HasTable, HasSet> {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        "update".hash(state);
+        self.table.hash(state);
+        for assignment in &self.assignments {
+            assignment.column.hash(state);
+            assignment.value.fragment().hash(state);
+        }
+        hash_expr(self.where_clause.as_ref(), state);
+    }
+
+    fn into_params(self) -> Vec<SqlValue> {
+        self.assignments
+            .into_iter()
+            .map(|a| a.value)
+            .chain(self.where_clause)
+            .flat_map(|e| e.into_parts().1)
+            .collect()
+    }
+
+    fn render<D: Dialect + ?Sized>(self, dialect: &D) -> (String, Vec<SqlValue>) {
+        self.build_for(dialect)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::builder::dyn_col;
+    use crate::builder::func::{coalesce, lower};
+    use crate::builder::test_support::Backticks;
+
+    /// A dialect accepting at most the given number of bound parameters.
+    struct MaxParams(usize);
+
+    impl Dialect for MaxParams {
+        fn name(&self) -> &'static str {
+            "max-params"
+        }
+
+        fn max_parameters(&self) -> usize {
+            self.0
+        }
+    }
 
     #[test]
     fn test_simple_update() {
@@ -157,6 +313,40 @@ mod tests {
         assert_eq!(params.len(), 1);
     }
 
+    #[test]
+    fn test_update_quotes_as_the_dialect() {
+        let update = || UpdateDyn::new().table("order").set("my col", 1);
+        let (sql, params) = update().build_for(&Backticks);
+        assert_eq!(sql, "UPDATE `order` SET `my col` = ?");
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+
+        let statements: Vec<_> = update()
+            .where_in_chunked("user", vec![2, 3], 100, &Backticks)
+            .unwrap()
+            .collect();
+        assert_eq!(
+            statements[0].0,
+            "UPDATE `order` SET `my col` = ? WHERE `user` IN (?, ?)"
+        );
+        assert_eq!(
+            statements[0].1,
+            vec![SqlValue::Int(1), SqlValue::Int(2), SqlValue::Int(3)]
+        );
+    }
+
+    #[test]
+    fn test_update_quotes_expressions_as_the_dialect() {
+        let (sql, _) = UpdateDyn::new()
+            .table("t")
+            .set_expr("my col", ExprBuilder::from(dyn_col("my col")).add(1))
+            .where_clause(dyn_col("my col").eq(1).and(dyn_col("order").eq(2)))
+            .build_for(&Backticks);
+        assert_eq!(
+            sql,
+            "UPDATE t SET `my col` = `my col` + ? WHERE `my col` = ? AND `order` = ?"
+        );
+    }
+
     #[test]
     fn test_update_multiple_columns() {
         let (sql, params) = UpdateDyn::new()
@@ -194,4 +384,75 @@ mod tests {
         assert_eq!(sql, "UPDATE users SET name = ? WHERE id = ?");
         assert!(matches!(&params[0], SqlValue::Text(s) if s == malicious));
     }
+
+    #[test]
+    fn test_update_set_expr() {
+        let (sql, params) = UpdateDyn::new()
+            .table("posts")
+            .set_expr(
+                "views",
+                coalesce(vec![ExprBuilder::column("views"), ExprBuilder::value(0)]).add(1),
+            )
+            .set("title", "Hello")
+            .set_expr("slug", lower(ExprBuilder::value("Hello")))
+            .where_clause(dyn_col("id").eq(7))
+            .build();
+
+        assert_eq!(
+            sql,
+            "UPDATE posts SET views = COALESCE(views, ?) + ?, title = ?, slug = LOWER(?) \
+             WHERE id = ?"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Int(0),
+                SqlValue::Int(1),
+                SqlValue::Text(String::from("Hello")),
+                SqlValue::Text(String::from("Hello")),
+                SqlValue::Int(7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_counter_increment() {
+        let (sql, params) = UpdateDyn::new()
+            .table("posts")
+            .set_expr("views", ExprBuilder::column("views").add(1))
+            .build();
+
+        assert_eq!(sql, "UPDATE posts SET views = views + ?");
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_update_where_in_chunked() {
+        let statements: Vec<_> = UpdateDyn::new()
+            .table("users")
+            .set("active", false)
+            .where_in_chunked("id", vec![1, 2, 3], 100, &MaxParams(3))
+            .unwrap()
+            .collect();
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(
+            statements[0].0,
+            "UPDATE users SET active = ? WHERE id IN (?, ?)"
+        );
+        assert_eq!(
+            statements[1].1,
+            vec![SqlValue::Bool(false), SqlValue::Int(3)]
+        );
+
+        // The SET clause alone uses up the limit.
+        let result = UpdateDyn::new()
+            .table("users")
+            .set("active", false)
+            .where_in_chunked("id", vec![1], 100, &MaxParams(1));
+        assert!(matches!(
+            result,
+            Err(ChunkError::ParameterLimit { reserved: 1, .. })
+        ));
+    }
 }
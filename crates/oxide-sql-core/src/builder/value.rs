@@ -1,11 +1,30 @@
 //! SQL values and parameter handling.
 //!
 //! This module provides safe handling of SQL values to prevent SQL injection.
+//!
+//! # Optional value types
+//!
+//! Additional variants are available behind Cargo features:
+//!
+//! | Feature        | Rust type                                      | Variant       |
+//! |----------------|------------------------------------------------|---------------|
+//! | `chrono`       | `chrono::NaiveDateTime`                        | `Timestamp`   |
+//! | `chrono`       | `chrono::DateTime<Utc>`                        | `TimestampTz` |
+//! | `uuid`         | `uuid::Uuid`                                   | `Uuid`        |
+//! | `rust_decimal` | `rust_decimal::Decimal`                        | `Decimal`     |
+//! | `serde_json`   | `serde_json::Value`                            | `Json`        |
+//!
+//! Because enabling a feature adds variants, `SqlValue` is
+//! `#[non_exhaustive]`. Database adapters should not match on it directly:
+//! implement [`DriverValue`] instead, which converts every variant and lets
+//! the adapter bind the optional types natively, or lower them with
+//! [`SqlValue::into_primitive`] to the fixed set of [`PrimitiveValue`]s.
 
 /// A SQL value that can be used as a parameter.
 ///
 /// All values are properly escaped or parameterized to prevent SQL injection.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum SqlValue {
     /// NULL value.
     Null,
@@ -19,6 +38,21 @@ pub enum SqlValue {
     Text(String),
     /// Binary blob value.
     Blob(Vec<u8>),
+    /// Timestamp without time zone.
+    #[cfg(feature = "chrono")]
+    Timestamp(chrono::NaiveDateTime),
+    /// Timestamp in UTC.
+    #[cfg(feature = "chrono")]
+    TimestampTz(chrono::DateTime<chrono::Utc>),
+    /// UUID value.
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    /// Exact decimal value.
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
+    /// JSON document.
+    #[cfg(feature = "serde_json")]
+    Json(serde_json::Value),
 }
 
 impl SqlValue {
@@ -47,6 +81,37 @@ impl SqlValue {
                 let hex: String = b.iter().map(|byte| format!("{byte:02X}")).collect();
                 format!("X'{hex}'")
             }
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(d) => format!("{d}"),
+            #[cfg(any(feature = "chrono", feature = "uuid", feature = "serde_json"))]
+            _ => self.clone().into_primitive().to_sql_inline(),
+        }
+    }
+
+    /// Lowers feature-gated variants to one of the base variants.
+    ///
+    /// Timestamps become ISO 8601 text (`YYYY-MM-DD HH:MM:SS`, with a
+    /// `+00:00` suffix for UTC), UUIDs become hyphenated text, decimals and
+    /// JSON become their textual representation. Base variants are returned
+    /// unchanged.
+    ///
+    /// This is the conversion drivers without native support for these types
+    /// should apply before binding parameters.
+    #[must_use]
+    #[allow(clippy::match_single_binding)] // Without features, only base variants exist.
+    pub fn into_primitive(self) -> Self {
+        match self {
+            #[cfg(feature = "chrono")]
+            Self::Timestamp(ts) => Self::Text(ts.to_string()),
+            #[cfg(feature = "chrono")]
+            Self::TimestampTz(ts) => Self::Text(format!("{}+00:00", ts.naive_utc())),
+            #[cfg(feature = "uuid")]
+            Self::Uuid(u) => Self::Text(u.hyphenated().to_string()),
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(d) => Self::Text(d.to_string()),
+            #[cfg(feature = "serde_json")]
+            Self::Json(v) => Self::Text(v.to_string()),
+            other => other,
         }
     }
 
@@ -57,6 +122,149 @@ impl SqlValue {
     }
 }
 
+/// The base SQL values every database driver understands.
+///
+/// Unlike [`SqlValue`], this enum does not grow with Cargo features, so
+/// adapters can match on it exhaustively.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrimitiveValue {
+    /// NULL value.
+    Null,
+    /// Boolean value.
+    Bool(bool),
+    /// Integer value.
+    Int(i64),
+    /// Float value.
+    Float(f64),
+    /// Text value.
+    Text(String),
+    /// Binary blob value.
+    Blob(Vec<u8>),
+}
+
+impl From<SqlValue> for PrimitiveValue {
+    /// Lowers the value with [`SqlValue::into_primitive`].
+    fn from(value: SqlValue) -> Self {
+        match value.into_primitive() {
+            SqlValue::Null => Self::Null,
+            SqlValue::Bool(b) => Self::Bool(b),
+            SqlValue::Int(n) => Self::Int(n),
+            SqlValue::Float(f) => Self::Float(f),
+            SqlValue::Text(s) => Self::Text(s),
+            SqlValue::Blob(b) => Self::Blob(b),
+            #[cfg(any(
+                feature = "chrono",
+                feature = "uuid",
+                feature = "rust_decimal",
+                feature = "serde_json"
+            ))]
+            _ => unreachable!("into_primitive() only returns base variants"),
+        }
+    }
+}
+
+impl From<PrimitiveValue> for SqlValue {
+    fn from(value: PrimitiveValue) -> Self {
+        match value {
+            PrimitiveValue::Null => Self::Null,
+            PrimitiveValue::Bool(b) => Self::Bool(b),
+            PrimitiveValue::Int(n) => Self::Int(n),
+            PrimitiveValue::Float(f) => Self::Float(f),
+            PrimitiveValue::Text(s) => Self::Text(s),
+            PrimitiveValue::Blob(b) => Self::Blob(b),
+        }
+    }
+}
+
+/// Conversion of bound parameters into a database driver's own value type.
+///
+/// Adapters implement [`from_primitive`](Self::from_primitive) for the base
+/// values. The feature-gated types are lowered to text by default; override
+/// their methods to bind them natively. [`from_sql_value`](Self::from_sql_value)
+/// then converts any [`SqlValue`], whichever features are enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::value::{DriverValue, PrimitiveValue, SqlValue};
+///
+/// /// A driver that binds everything as text, or NULL.
+/// struct TextParam(Option<String>);
+///
+/// impl DriverValue for TextParam {
+///     fn from_primitive(value: PrimitiveValue) -> Self {
+///         Self(match value {
+///             PrimitiveValue::Null => None,
+///             PrimitiveValue::Bool(b) => Some(b.to_string()),
+///             PrimitiveValue::Int(n) => Some(n.to_string()),
+///             PrimitiveValue::Float(f) => Some(f.to_string()),
+///             PrimitiveValue::Text(s) => Some(s),
+///             PrimitiveValue::Blob(b) => Some(String::from_utf8_lossy(&b).into_owned()),
+///         })
+///     }
+/// }
+///
+/// let params: Vec<TextParam> = TextParam::from_sql_values(vec![SqlValue::Int(7), SqlValue::Null]);
+/// assert_eq!(params[0].0.as_deref(), Some("7"));
+/// assert!(params[1].0.is_none());
+/// ```
+pub trait DriverValue: Sized {
+    /// Converts one of the base values.
+    fn from_primitive(value: PrimitiveValue) -> Self;
+
+    /// Converts a timestamp without time zone.
+    #[cfg(feature = "chrono")]
+    fn from_timestamp(value: chrono::NaiveDateTime) -> Self {
+        Self::from_primitive(SqlValue::Timestamp(value).into())
+    }
+
+    /// Converts a timestamp in UTC.
+    #[cfg(feature = "chrono")]
+    fn from_timestamp_tz(value: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::from_primitive(SqlValue::TimestampTz(value).into())
+    }
+
+    /// Converts a UUID.
+    #[cfg(feature = "uuid")]
+    fn from_uuid(value: uuid::Uuid) -> Self {
+        Self::from_primitive(SqlValue::Uuid(value).into())
+    }
+
+    /// Converts an exact decimal.
+    #[cfg(feature = "rust_decimal")]
+    fn from_decimal(value: rust_decimal::Decimal) -> Self {
+        Self::from_primitive(SqlValue::Decimal(value).into())
+    }
+
+    /// Converts a JSON document.
+    #[cfg(feature = "serde_json")]
+    fn from_json(value: serde_json::Value) -> Self {
+        Self::from_primitive(SqlValue::Json(value).into())
+    }
+
+    /// Converts any [`SqlValue`], dispatching to the methods above.
+    fn from_sql_value(value: SqlValue) -> Self {
+        match value {
+            #[cfg(feature = "chrono")]
+            SqlValue::Timestamp(ts) => Self::from_timestamp(ts),
+            #[cfg(feature = "chrono")]
+            SqlValue::TimestampTz(ts) => Self::from_timestamp_tz(ts),
+            #[cfg(feature = "uuid")]
+            SqlValue::Uuid(u) => Self::from_uuid(u),
+            #[cfg(feature = "rust_decimal")]
+            SqlValue::Decimal(d) => Self::from_decimal(d),
+            #[cfg(feature = "serde_json")]
+            SqlValue::Json(v) => Self::from_json(v),
+            base => Self::from_primitive(base.into()),
+        }
+    }
+
+    /// Converts a list of parameters, as returned by the builders.
+    fn from_sql_values(values: Vec<SqlValue>) -> Vec<Self> {
+        values.into_iter().map(Self::from_sql_value).collect()
+    }
+}
+
 /// Trait for types that can be converted to SQL values.
 pub trait ToSqlValue {
     /// Converts the value to a `SqlValue`.
@@ -99,6 +307,14 @@ impl ToSqlValue for i8 {
     }
 }
 
+/// Values above `i64::MAX`, which no integer parameter holds, are bound as
+/// decimal text.
+impl ToSqlValue for u64 {
+    fn to_sql_value(self) -> SqlValue {
+        i64::try_from(self).map_or_else(|_| SqlValue::Text(self.to_string()), SqlValue::Int)
+    }
+}
+
 impl ToSqlValue for u32 {
     fn to_sql_value(self) -> SqlValue {
         SqlValue::Int(i64::from(self))
@@ -251,6 +467,306 @@ impl From<Vec<u8>> for SqlValue {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl ToSqlValue for chrono::NaiveDateTime {
+    fn to_sql_value(self) -> SqlValue {
+        SqlValue::Timestamp(self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for SqlValue {
+    fn from(value: chrono::NaiveDateTime) -> Self {
+        Self::Timestamp(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToSqlValue for chrono::DateTime<chrono::Utc> {
+    fn to_sql_value(self) -> SqlValue {
+        SqlValue::TimestampTz(self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for SqlValue {
+    fn from(value: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::TimestampTz(value)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ToSqlValue for uuid::Uuid {
+    fn to_sql_value(self) -> SqlValue {
+        SqlValue::Uuid(self)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for SqlValue {
+    fn from(value: uuid::Uuid) -> Self {
+        Self::Uuid(value)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl ToSqlValue for rust_decimal::Decimal {
+    fn to_sql_value(self) -> SqlValue {
+        SqlValue::Decimal(self)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for SqlValue {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl ToSqlValue for serde_json::Value {
+    fn to_sql_value(self) -> SqlValue {
+        SqlValue::Json(self)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Value> for SqlValue {
+    fn from(value: serde_json::Value) -> Self {
+        Self::Json(value)
+    }
+}
+
+// Decoding result values
+
+/// Error returned when a result row cannot be decoded into Rust types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The row has a different number of values than selected columns.
+    ColumnCount {
+        /// The number of selected columns.
+        expected: usize,
+        /// The number of values in the row.
+        found: usize,
+    },
+    /// A value cannot be converted to the column's Rust type.
+    TypeMismatch {
+        /// The position of the value in the row.
+        index: usize,
+        /// The Rust type that was expected.
+        expected: &'static str,
+        /// The value that was found.
+        found: SqlValue,
+    },
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ColumnCount { expected, found } => {
+                write!(f, "expected {expected} columns, found {found}")
+            }
+            Self::TypeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(f, "column {index}: expected {expected}, found {found:?}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Trait for types that can be read back from SQL values.
+///
+/// This is the inverse of [`ToSqlValue`] and is used to decode result rows
+/// into the Rust types of the selected columns.
+pub trait FromSqlValue: Sized {
+    /// Converts the value, handing it back unchanged if the type does not
+    /// match.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original value if it cannot be represented as `Self`.
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue>;
+}
+
+impl FromSqlValue for SqlValue {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        Ok(value)
+    }
+}
+
+impl FromSqlValue for bool {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Bool(b) => Ok(b),
+            // SQLite stores booleans as 0/1 integers.
+            SqlValue::Int(0) => Ok(false),
+            SqlValue::Int(1) => Ok(true),
+            other => Err(other),
+        }
+    }
+}
+
+impl FromSqlValue for i64 {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Int(n) => Ok(n),
+            other => Err(other),
+        }
+    }
+}
+
+macro_rules! impl_from_sql_value_int {
+    ($($ty:ty),+) => {
+        $(
+            impl FromSqlValue for $ty {
+                fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+                    match value {
+                        SqlValue::Int(n) => Self::try_from(n).map_err(|_| SqlValue::Int(n)),
+                        other => Err(other),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_sql_value_int!(i32, i16, i8, u32, u16, u8);
+
+/// Also reads the decimal text that [`ToSqlValue`] binds for values above
+/// `i64::MAX`.
+impl FromSqlValue for u64 {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Int(n) => Self::try_from(n).map_err(|_| SqlValue::Int(n)),
+            SqlValue::Text(s) => s.parse().map_err(|_| SqlValue::Text(s)),
+            other => Err(other),
+        }
+    }
+}
+
+impl FromSqlValue for f64 {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Float(f) => Ok(f),
+            #[allow(clippy::cast_precision_loss)]
+            SqlValue::Int(n) => Ok(n as Self),
+            other => Err(other),
+        }
+    }
+}
+
+impl FromSqlValue for f32 {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        #[allow(clippy::cast_possible_truncation)]
+        f64::from_sql_value(value).map(|f| f as Self)
+    }
+}
+
+impl FromSqlValue for String {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Text(s) => Ok(s),
+            other => Err(other),
+        }
+    }
+}
+
+impl FromSqlValue for Vec<u8> {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Blob(b) => Ok(b),
+            other => Err(other),
+        }
+    }
+}
+
+impl<T: FromSqlValue> FromSqlValue for Option<T> {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Null => Ok(None),
+            other => T::from_sql_value(other).map(Some),
+        }
+    }
+}
+
+/// Decodes the value at `index` of a row, recording its position on error.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::TypeMismatch`] if the value cannot be converted.
+pub fn decode_value<T: FromSqlValue>(index: usize, value: SqlValue) -> Result<T, DecodeError> {
+    T::from_sql_value(value).map_err(|found| DecodeError::TypeMismatch {
+        index,
+        expected: std::any::type_name::<T>(),
+        found,
+    })
+}
+
+#[cfg(feature = "chrono")]
+impl FromSqlValue for chrono::NaiveDateTime {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Timestamp(ts) => Ok(ts),
+            SqlValue::Text(s) => Self::parse_from_str(&s, "%Y-%m-%d %H:%M:%S%.f")
+                .or_else(|_| Self::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f"))
+                .map_err(|_| SqlValue::Text(s)),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromSqlValue for chrono::DateTime<chrono::Utc> {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::TimestampTz(ts) => Ok(ts),
+            SqlValue::Text(s) => chrono::DateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S%.f%:z")
+                .or_else(|_| chrono::DateTime::parse_from_rfc3339(&s))
+                .map(|ts| ts.with_timezone(&chrono::Utc))
+                .map_err(|_| SqlValue::Text(s)),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl FromSqlValue for uuid::Uuid {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Uuid(u) => Ok(u),
+            SqlValue::Text(s) => Self::parse_str(&s).map_err(|_| SqlValue::Text(s)),
+            SqlValue::Blob(b) => Self::from_slice(&b).map_err(|_| SqlValue::Blob(b)),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl FromSqlValue for rust_decimal::Decimal {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Decimal(d) => Ok(d),
+            SqlValue::Int(n) => Ok(Self::from(n)),
+            SqlValue::Text(s) => s.parse().map_err(|_| SqlValue::Text(s)),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl FromSqlValue for serde_json::Value {
+    fn from_sql_value(value: SqlValue) -> Result<Self, SqlValue> {
+        match value {
+            SqlValue::Json(v) => Ok(v),
+            SqlValue::Text(s) => serde_json::from_str(&s).map_err(|_| SqlValue::Text(s)),
+            other => Err(other),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,4 +839,187 @@ mod tests {
         assert_eq!(None::<i32>.to_sql_value(), SqlValue::Null);
         assert_eq!(Some(42_i32).to_sql_value(), SqlValue::Int(42));
     }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_values() {
+        let naive = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        assert_eq!(naive.to_sql_value(), SqlValue::Timestamp(naive));
+        assert_eq!(
+            SqlValue::Timestamp(naive).to_sql_inline(),
+            "'2024-01-02 03:04:05'"
+        );
+        assert_eq!(
+            SqlValue::from(naive.and_utc()).to_sql_inline(),
+            "'2024-01-02 03:04:05+00:00'"
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_value() {
+        let id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+        assert_eq!(
+            SqlValue::from(id).into_primitive(),
+            SqlValue::Text(String::from("67e55044-10b1-426f-9247-bb680e5fe0c8"))
+        );
+        assert_eq!(
+            id.to_sql_value().to_sql_inline(),
+            "'67e55044-10b1-426f-9247-bb680e5fe0c8'"
+        );
+    }
+
+    /// A driver value recording which conversion was used.
+    #[derive(Debug, PartialEq)]
+    enum TestParam {
+        Primitive(PrimitiveValue),
+        #[cfg(feature = "uuid")]
+        NativeUuid(uuid::Uuid),
+    }
+
+    impl DriverValue for TestParam {
+        fn from_primitive(value: PrimitiveValue) -> Self {
+            Self::Primitive(value)
+        }
+
+        #[cfg(feature = "uuid")]
+        fn from_uuid(value: uuid::Uuid) -> Self {
+            Self::NativeUuid(value)
+        }
+    }
+
+    #[test]
+    fn test_driver_value_converts_base_values() {
+        let params = TestParam::from_sql_values(vec![
+            SqlValue::Null,
+            SqlValue::Int(1),
+            SqlValue::Text(String::from("a")),
+        ]);
+        assert_eq!(
+            params,
+            vec![
+                TestParam::Primitive(PrimitiveValue::Null),
+                TestParam::Primitive(PrimitiveValue::Int(1)),
+                TestParam::Primitive(PrimitiveValue::Text(String::from("a"))),
+            ]
+        );
+        assert_eq!(
+            SqlValue::from(PrimitiveValue::Blob(vec![1])),
+            SqlValue::Blob(vec![1])
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_driver_value_overrides_feature_types() {
+        let id = uuid::Uuid::from_u128(1);
+        assert_eq!(
+            TestParam::from_sql_value(SqlValue::Uuid(id)),
+            TestParam::NativeUuid(id)
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_driver_value_lowers_by_default() {
+        assert_eq!(
+            TestParam::from_sql_value(SqlValue::Json(serde_json::json!([1]))),
+            TestParam::Primitive(PrimitiveValue::Text(String::from("[1]")))
+        );
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_decimal_value() {
+        let d = rust_decimal::Decimal::new(12345, 2);
+        assert_eq!(SqlValue::from(d).to_sql_inline(), "123.45");
+        assert_eq!(
+            d.to_sql_value().into_primitive(),
+            SqlValue::Text(String::from("123.45"))
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json_value() {
+        let doc = serde_json::json!({"name": "O'Brien"});
+        assert_eq!(
+            SqlValue::from(doc).to_sql_inline(),
+            "'{\"name\":\"O''Brien\"}'"
+        );
+    }
+
+    #[test]
+    fn test_into_primitive_keeps_base_variants() {
+        assert_eq!(SqlValue::Int(1).into_primitive(), SqlValue::Int(1));
+        assert_eq!(SqlValue::Null.into_primitive(), SqlValue::Null);
+    }
+
+    #[test]
+    fn test_from_sql_value() {
+        assert_eq!(i64::from_sql_value(SqlValue::Int(7)), Ok(7));
+        assert_eq!(
+            u8::from_sql_value(SqlValue::Int(300)),
+            Err(SqlValue::Int(300))
+        );
+        assert_eq!(
+            u64::from_sql_value(SqlValue::Int(i64::MAX)),
+            Ok(u64::MAX >> 1)
+        );
+        assert_eq!(
+            u64::from_sql_value(SqlValue::Int(-1)),
+            Err(SqlValue::Int(-1))
+        );
+        assert_eq!(u64::from_sql_value(u64::MAX.to_sql_value()), Ok(u64::MAX));
+        assert_eq!(7_u64.to_sql_value(), SqlValue::Int(7));
+        assert_eq!(bool::from_sql_value(SqlValue::Int(1)), Ok(true));
+        assert_eq!(f64::from_sql_value(SqlValue::Int(2)), Ok(2.0));
+        assert_eq!(
+            String::from_sql_value(SqlValue::Text(String::from("a"))),
+            Ok(String::from("a"))
+        );
+        assert_eq!(Option::<i64>::from_sql_value(SqlValue::Null), Ok(None));
+        assert_eq!(Option::<i64>::from_sql_value(SqlValue::Int(3)), Ok(Some(3)));
+    }
+
+    #[test]
+    fn test_decode_value_reports_position() {
+        let err = decode_value::<i64>(2, SqlValue::Text(String::from("x"))).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::TypeMismatch {
+                index: 2,
+                expected: "i64",
+                found: SqlValue::Text(String::from("x")),
+            }
+        );
+        assert_eq!(err.to_string(), "column 2: expected i64, found Text(\"x\")");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_round_trip() {
+        let ts = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        let text = SqlValue::Timestamp(ts).into_primitive();
+        assert_eq!(chrono::NaiveDateTime::from_sql_value(text), Ok(ts));
+        let text = SqlValue::TimestampTz(ts.and_utc()).into_primitive();
+        assert_eq!(
+            chrono::DateTime::<chrono::Utc>::from_sql_value(text),
+            Ok(ts.and_utc())
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_round_trip() {
+        let id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+        let text = SqlValue::Uuid(id).into_primitive();
+        assert_eq!(uuid::Uuid::from_sql_value(text), Ok(id));
+    }
 }
@@ -0,0 +1,320 @@
+//! Window function builders for dynamic queries.
+//!
+//! A window function is built in two parts: the function itself (e.g.
+//! [`row_number`]) and the window it runs over (a [`WindowSpec`], usually
+//! started with [`partition_by`] or [`order_by`]). Calling
+//! [`WindowFunction::over`] combines both into an [`ExprBuilder`] that can
+//! be added to a select list with [`SelectDyn::expr`](super::SelectDyn::expr).
+//!
+//! ```rust
+//! use oxide_sql_core::builder::SelectDyn;
+//! use oxide_sql_core::builder::window::{partition_by, row_number};
+//!
+//! let (sql, _) = SelectDyn::new()
+//!     .columns(&["id", "department"])
+//!     .expr(
+//!         row_number().over(partition_by(&["department"]).order_by_desc(&["salary"])),
+//!         "rank",
+//!     )
+//!     .from("employees")
+//!     .build();
+//!
+//! assert_eq!(
+//!     sql,
+//!     "SELECT id, department, ROW_NUMBER() OVER (PARTITION BY department \
+//!      ORDER BY salary DESC) AS rank FROM employees"
+//! );
+//! ```
+//!
+//! Not every database supports window functions. Use
+//! [`WindowFunction::over_checked`] to consult
+//! [`Dialect::supports_window_functions`] first.
+
+use crate::dialect::{Dialect, GenericDialect};
+
+use super::expr::{ExprBuilder, dyn_col};
+use super::func::call;
+use super::render::Fragment;
+
+/// The window a [`WindowFunction`] is evaluated over
+/// (`PARTITION BY ... ORDER BY ...`).
+///
+/// Column names are quoted when the statement is built, as its dialect
+/// does.
+#[derive(Debug, Clone, Default)]
+pub struct WindowSpec {
+    partition_by: Vec<Fragment>,
+    order_by: Vec<Fragment>,
+}
+
+impl WindowSpec {
+    /// Creates an empty window covering all rows (`OVER ()`).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds PARTITION BY columns.
+    #[must_use]
+    pub fn partition_by(mut self, cols: &[&str]) -> Self {
+        self.partition_by
+            .extend(cols.iter().map(|c| Fragment::column(c)));
+        self
+    }
+
+    /// Adds ORDER BY columns (ascending).
+    #[must_use]
+    pub fn order_by(mut self, cols: &[&str]) -> Self {
+        self.order_by
+            .extend(cols.iter().map(|c| Fragment::column(c)));
+        self
+    }
+
+    /// Adds ORDER BY columns (descending).
+    #[must_use]
+    pub fn order_by_desc(mut self, cols: &[&str]) -> Self {
+        self.order_by
+            .extend(cols.iter().map(|c| Fragment::column(c).then_sql(" DESC")));
+        self
+    }
+
+    /// Renders the window definition without the surrounding parentheses,
+    /// quoting names as [`GenericDialect`] does.
+    #[must_use]
+    pub fn to_sql(&self) -> String {
+        self.to_sql_for(&GenericDialect)
+    }
+
+    /// Renders the window definition without the surrounding parentheses,
+    /// quoting names as `dialect` does.
+    #[must_use]
+    pub fn to_sql_for<D: Dialect + ?Sized>(&self, dialect: &D) -> String {
+        self.clone().fragment().to_sql_for(dialect)
+    }
+
+    /// Returns the window definition with its names left unquoted.
+    fn fragment(self) -> Fragment {
+        let mut sql = Fragment::default();
+        let clauses = [
+            ("PARTITION BY ", self.partition_by),
+            ("ORDER BY ", self.order_by),
+        ];
+        for (keyword, items) in clauses.into_iter().filter(|(_, items)| !items.is_empty()) {
+            if sql.estimated_len() > 0 {
+                sql.push_sql(" ");
+            }
+            sql.push_sql(keyword);
+            for (i, item) in items.into_iter().enumerate() {
+                if i > 0 {
+                    sql.push_sql(", ");
+                }
+                sql.append(item);
+            }
+        }
+        sql
+    }
+}
+
+/// Starts a window specification with PARTITION BY columns.
+#[must_use]
+pub fn partition_by(cols: &[&str]) -> WindowSpec {
+    WindowSpec::new().partition_by(cols)
+}
+
+/// Starts a window specification with ascending ORDER BY columns.
+#[must_use]
+pub fn order_by(cols: &[&str]) -> WindowSpec {
+    WindowSpec::new().order_by(cols)
+}
+
+/// Starts a window specification with descending ORDER BY columns.
+#[must_use]
+pub fn order_by_desc(cols: &[&str]) -> WindowSpec {
+    WindowSpec::new().order_by_desc(cols)
+}
+
+/// A function call that still needs an OVER clause.
+#[derive(Debug, Clone)]
+pub struct WindowFunction {
+    name: String,
+    args: Vec<ExprBuilder>,
+}
+
+impl WindowFunction {
+    /// Creates a window function call from a name and arguments.
+    ///
+    /// Use this for aggregates used as window functions, e.g.
+    /// `WindowFunction::new("SUM", vec![ExprBuilder::column("amount")])`.
+    ///
+    /// **Warning**: `name` is emitted as-is; never pass user input here.
+    #[must_use]
+    pub fn new(name: &str, args: Vec<ExprBuilder>) -> Self {
+        Self {
+            name: String::from(name),
+            args,
+        }
+    }
+
+    /// Applies the function over `window`, producing an expression.
+    #[must_use]
+    pub fn over(self, window: WindowSpec) -> ExprBuilder {
+        let (sql, params) = call(&self.name, self.args).into_parts();
+        let sql = sql
+            .then_sql(" OVER (")
+            .then(window.fragment())
+            .then_sql(")");
+        ExprBuilder::from_parts(sql, params)
+    }
+
+    /// Like [`over`](Self::over), but returns `None` if `dialect` does not
+    /// support window functions.
+    #[must_use]
+    pub fn over_checked<D: Dialect + ?Sized>(
+        self,
+        window: WindowSpec,
+        dialect: &D,
+    ) -> Option<ExprBuilder> {
+        dialect
+            .supports_window_functions()
+            .then(|| self.over(window))
+    }
+}
+
+/// Creates a window function taking a single column argument.
+fn column_function(name: &str, col: &str) -> WindowFunction {
+    WindowFunction::new(name, vec![dyn_col(col).into()])
+}
+
+/// `ROW_NUMBER()`: the number of the current row within its partition.
+#[must_use]
+pub fn row_number() -> WindowFunction {
+    WindowFunction::new("ROW_NUMBER", vec![])
+}
+
+/// `RANK()`: the rank of the current row, with gaps.
+#[must_use]
+pub fn rank() -> WindowFunction {
+    WindowFunction::new("RANK", vec![])
+}
+
+/// `DENSE_RANK()`: the rank of the current row, without gaps.
+#[must_use]
+pub fn dense_rank() -> WindowFunction {
+    WindowFunction::new("DENSE_RANK", vec![])
+}
+
+/// `NTILE(n)`: the bucket number of the current row when rows are split
+/// into `n` groups.
+#[must_use]
+pub fn ntile(n: u64) -> WindowFunction {
+    WindowFunction::new("NTILE", vec![ExprBuilder::raw(n.to_string())])
+}
+
+/// `LAG(col)`: the value of `col` in the previous row.
+#[must_use]
+pub fn lag(col: &str) -> WindowFunction {
+    column_function("LAG", col)
+}
+
+/// `LEAD(col)`: the value of `col` in the next row.
+#[must_use]
+pub fn lead(col: &str) -> WindowFunction {
+    column_function("LEAD", col)
+}
+
+/// `FIRST_VALUE(col)`: the value of `col` in the first row of the window.
+#[must_use]
+pub fn first_value(col: &str) -> WindowFunction {
+    column_function("FIRST_VALUE", col)
+}
+
+/// `LAST_VALUE(col)`: the value of `col` in the last row of the window.
+#[must_use]
+pub fn last_value(col: &str) -> WindowFunction {
+    column_function("LAST_VALUE", col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SqlValue;
+    use crate::builder::test_support::Backticks;
+    use crate::dialect::GenericDialect;
+
+    #[test]
+    fn test_row_number_over_partition() {
+        let (sql, params) = row_number()
+            .over(partition_by(&["dept"]).order_by(&["hired_at"]))
+            .build();
+        assert_eq!(
+            sql,
+            "ROW_NUMBER() OVER (PARTITION BY dept ORDER BY hired_at)"
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_empty_window() {
+        let (sql, _) = rank().over(WindowSpec::new()).build();
+        assert_eq!(sql, "RANK() OVER ()");
+    }
+
+    #[test]
+    fn test_function_arguments() {
+        let (sql, _) = lag("price").over(order_by_desc(&["day"])).build();
+        assert_eq!(sql, "LAG(price) OVER (ORDER BY day DESC)");
+
+        let (sql, _) = ntile(4).over(order_by(&["score"])).build();
+        assert_eq!(sql, "NTILE(4) OVER (ORDER BY score)");
+
+        let (sql, params) = WindowFunction::new("SUM", vec![ExprBuilder::value(1)])
+            .over(partition_by(&["a", "b"]))
+            .build();
+        assert_eq!(sql, "SUM(?) OVER (PARTITION BY a, b)");
+        assert_eq!(params, vec![SqlValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_window_columns_are_sanitized() {
+        let (sql, _) = first_value("x; --").over(partition_by(&["a b"])).build();
+        assert_eq!(sql, "FIRST_VALUE(\"x; --\") OVER (PARTITION BY \"a b\")");
+    }
+
+    #[test]
+    fn test_window_columns_quote_as_the_dialect() {
+        let window = partition_by(&["my dept"]).order_by_desc(&["order"]);
+        assert_eq!(
+            window.to_sql_for(&Backticks),
+            "PARTITION BY `my dept` ORDER BY `order` DESC"
+        );
+        let (sql, _) = lag("a b").over(window).build_for(&Backticks);
+        assert_eq!(
+            sql,
+            "LAG(`a b`) OVER (PARTITION BY `my dept` ORDER BY `order` DESC)"
+        );
+    }
+
+    #[test]
+    fn test_over_checked() {
+        struct NoWindows;
+        impl Dialect for NoWindows {
+            fn name(&self) -> &'static str {
+                "no-windows"
+            }
+            fn supports_window_functions(&self) -> bool {
+                false
+            }
+        }
+
+        assert!(
+            dense_rank()
+                .over_checked(WindowSpec::new(), &GenericDialect::new())
+                .is_some()
+        );
+        assert!(
+            dense_rank()
+                .over_checked(WindowSpec::new(), &NoWindows)
+                .is_none()
+        );
+    }
+}
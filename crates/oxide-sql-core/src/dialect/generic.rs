@@ -32,5 +32,25 @@ mod tests {
         assert_eq!(dialect.parameter_placeholder(), "?");
         assert!(!dialect.supports_returning());
         assert!(!dialect.supports_upsert());
+        assert_eq!(dialect.max_parameters(), 999);
+    }
+
+    #[test]
+    fn test_quote_identifier_escapes_quotes() {
+        let dialect = GenericDialect::new();
+        assert_eq!(dialect.quote_identifier("order"), "\"order\"");
+        assert_eq!(dialect.quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_reserved_keywords() {
+        let dialect = GenericDialect::new();
+        assert!(dialect.is_reserved_keyword("user"));
+        assert!(!dialect.is_reserved_keyword("users"));
+        assert!(dialect.requires_quoting("select"));
+        assert!(dialect.requires_quoting("my col"));
+        assert!(!dialect.requires_quoting("my_col"));
+        assert_eq!(dialect.quote_identifier_if_needed("order"), "\"order\"");
+        assert_eq!(dialect.quote_identifier_if_needed("orders"), "orders");
     }
 }
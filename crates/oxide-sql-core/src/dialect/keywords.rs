@@ -0,0 +1,126 @@
+//! Reserved keyword lists.
+
+use std::cmp::Ordering;
+
+/// Words reserved by standard SQL and by the common databases, in upper case
+/// and sorted.
+///
+/// This is the default for [`Dialect::reserved_keywords`](super::Dialect::reserved_keywords)
+/// and the list dynamic builders use to decide which names to quote.
+pub const ANSI_RESERVED_KEYWORDS: &[&str] = &[
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_USER",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FETCH",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GRANT",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RIGHT",
+    "SELECT",
+    "SESSION_USER",
+    "SET",
+    "SOME",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// Returns whether the sorted, upper-case `list` contains `word`, ignoring
+/// the case of `word`.
+pub(super) fn contains(list: &[&str], word: &str) -> bool {
+    list.binary_search_by(|kw| cmp_upper(kw, word)).is_ok()
+}
+
+/// Compares an upper-case keyword with `word` as if `word` were upper case.
+fn cmp_upper(keyword: &str, word: &str) -> Ordering {
+    keyword
+        .bytes()
+        .cmp(word.bytes().map(|b| b.to_ascii_uppercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi_list_is_sorted_and_upper_case() {
+        assert!(ANSI_RESERVED_KEYWORDS.is_sorted());
+        assert!(
+            ANSI_RESERVED_KEYWORDS
+                .iter()
+                .all(|kw| kw.bytes().all(|b| b.is_ascii_uppercase() || b == b'_'))
+        );
+    }
+
+    #[test]
+    fn test_contains_ignores_case() {
+        assert!(contains(ANSI_RESERVED_KEYWORDS, "order"));
+        assert!(contains(ANSI_RESERVED_KEYWORDS, "Select"));
+        assert!(contains(ANSI_RESERVED_KEYWORDS, "CURRENT_user"));
+        assert!(!contains(ANSI_RESERVED_KEYWORDS, "orders"));
+        assert!(!contains(ANSI_RESERVED_KEYWORDS, "name"));
+        assert!(!contains(ANSI_RESERVED_KEYWORDS, ""));
+    }
+}
@@ -4,8 +4,13 @@
 //! a trait for dialect-specific behavior.
 
 mod generic;
+mod keywords;
 
 pub use generic::GenericDialect;
+pub use keywords::ANSI_RESERVED_KEYWORDS;
+
+use crate::builder::{ExprBuilder, SqlValue, is_valid_identifier};
+use crate::lexer::LexerOptions;
 
 /// Trait for SQL dialect-specific behavior.
 pub trait Dialect {
@@ -42,9 +47,95 @@ pub trait Dialect {
         true
     }
 
-    /// Quotes an identifier if necessary.
+    /// Returns the maximum number of bound parameters in one statement.
+    ///
+    /// The default is 999, the historical SQLite limit and the lowest in
+    /// common use.
+    fn max_parameters(&self) -> usize {
+        999
+    }
+
+    /// Returns whether the dialect supports window functions (`OVER (...)`).
+    fn supports_window_functions(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the dialect supports arrays: `ARRAY[...]`
+    /// constructors and `type[]` types.
+    fn supports_arrays(&self) -> bool {
+        false
+    }
+
+    /// Returns the lexer options for parsing this dialect's SQL.
+    fn lexer_options(&self) -> LexerOptions {
+        LexerOptions::new()
+    }
+
+    /// Returns the words this dialect reserves, in upper case and sorted.
+    ///
+    /// Reserved words can only be used as identifiers when quoted. The
+    /// default is [`ANSI_RESERVED_KEYWORDS`].
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        ANSI_RESERVED_KEYWORDS
+    }
+
+    /// Returns whether `word` is a reserved keyword, ignoring case.
+    fn is_reserved_keyword(&self, word: &str) -> bool {
+        keywords::contains(self.reserved_keywords(), word)
+    }
+
+    /// Returns whether `name` must be quoted to be used as an identifier,
+    /// either because it is not a plain identifier or because it is
+    /// reserved.
+    fn requires_quoting(&self, name: &str) -> bool {
+        !is_valid_identifier(name) || self.is_reserved_keyword(name)
+    }
+
+    /// Quotes an identifier only if [`requires_quoting`](Self::requires_quoting)
+    /// says so.
+    fn quote_identifier_if_needed(&self, name: &str) -> String {
+        if self.requires_quoting(name) {
+            self.quote_identifier(name)
+        } else {
+            String::from(name)
+        }
+    }
+
+    /// Quotes an identifier, doubling any embedded quote characters.
     fn quote_identifier(&self, name: &str) -> String {
         let quote = self.identifier_quote();
-        format!("{quote}{name}{quote}")
+        let escaped = name.replace(quote, &format!("{quote}{quote}"));
+        format!("{quote}{escaped}{quote}")
+    }
+
+    /// Renders a value as an inline SQL literal for this dialect.
+    ///
+    /// **Warning**: Prefer parameterized queries. This is meant for DDL
+    /// defaults, generated scripts, and debugging output.
+    fn render_value(&self, value: &SqlValue) -> String {
+        value.to_sql_inline()
+    }
+
+    /// Returns the text of the field `key` of the JSON in `target`, with
+    /// `key` bound as a parameter.
+    ///
+    /// The default is `target ->> ?`, understood by PostgreSQL, DuckDB, and
+    /// SQLite 3.38+.
+    fn json_get(&self, target: ExprBuilder, key: &str) -> ExprBuilder {
+        let (sql, mut params) = target.into_parts();
+        params.push(SqlValue::Text(String::from(key)));
+        ExprBuilder::from_parts(sql.then_sql(" ->> ?"), params)
+    }
+
+    /// Returns `value` bound as a parameter of the temporal type
+    /// `type_name` (`DATE`, `TIME`, `TIMESTAMP`, or `INTERVAL`).
+    ///
+    /// The default is `CAST(? AS type_name)`, for dialects with real
+    /// temporal types.
+    fn temporal_value(&self, type_name: &str, value: &str) -> ExprBuilder {
+        ExprBuilder::with_params(
+            format!("CAST(? AS {type_name})"),
+            vec![SqlValue::Text(String::from(value))],
+        )
     }
 }
@@ -0,0 +1,35 @@
+//! Lexer error types.
+
+use super::Span;
+
+/// An error produced while scanning a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexError {
+    /// The error message.
+    pub message: String,
+    /// The location of the offending input.
+    pub span: Span,
+}
+
+impl LexError {
+    /// Creates a new lex error.
+    #[must_use]
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at position {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for LexError {}
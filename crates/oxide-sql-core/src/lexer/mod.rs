@@ -1,11 +1,35 @@
 //! SQL Lexer/Tokenizer
 //!
 //! This module provides a hand-written lexer for SQL that produces a stream of tokens.
+//!
+//! Besides [`Lexer::tokenize`], a lexer can be turned into an iterator with
+//! lookahead via [`Lexer::tokens`] or, for tools that only need token kinds
+//! and positions, [`Lexer::raw_tokens`], which borrows token text from the
+//! input instead of allocating:
+//!
+//! ```
+//! use oxide_sql_core::lexer::{Lexer, RawTokenKind};
+//!
+//! let sql = "SELECT name FROM users -- all of them";
+//! let keywords: Vec<&str> = Lexer::new(sql)
+//!     .raw_tokens()
+//!     .filter_map(Result::ok)
+//!     .filter(|t| matches!(t.kind, RawTokenKind::Keyword(_)))
+//!     .map(|t| t.text)
+//!     .collect();
+//! assert_eq!(keywords, ["SELECT", "FROM"]);
+//! ```
 
+mod error;
+mod options;
 mod span;
+mod stream;
 mod token;
 mod tokenizer;
 
+pub use error::LexError;
+pub use options::{IdentifierCase, KeywordCase, LexerOptions};
 pub use span::Span;
-pub use token::{Keyword, Token, TokenKind};
+pub use stream::{RawTokens, StreamToken, TokenStream, Tokens};
+pub use token::{Keyword, RawToken, RawTokenKind, Token, TokenKind};
 pub use tokenizer::Lexer;
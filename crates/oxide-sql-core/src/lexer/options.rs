@@ -0,0 +1,125 @@
+//! Lexer configuration.
+
+/// How keywords are recognized.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCase {
+    /// Keywords match in any case (`select`, `Select`, `SELECT`).
+    #[default]
+    Insensitive,
+    /// Only upper-case words are keywords; `select` is an identifier.
+    Upper,
+}
+
+/// How unquoted identifiers are cased. Quoted identifiers are always kept
+/// exactly as written.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierCase {
+    /// Keep identifiers as written.
+    #[default]
+    Preserve,
+    /// Fold identifiers to lower case, as PostgreSQL does.
+    Lower,
+    /// Fold identifiers to upper case, as the SQL standard specifies.
+    Upper,
+}
+
+impl IdentifierCase {
+    /// Applies this folding rule to an unquoted identifier.
+    pub(crate) fn fold(self, name: &mut String) {
+        match self {
+            Self::Preserve => {}
+            Self::Lower => *name = name.to_lowercase(),
+            Self::Upper => *name = name.to_uppercase(),
+        }
+    }
+}
+
+/// Options controlling how the lexer treats letter case and identifier
+/// quoting.
+///
+/// The default matches keywords case-insensitively, preserves the case of
+/// identifiers, lexes `[` as a bracket, and only reads standard `'...'`
+/// strings.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::lexer::{IdentifierCase, Lexer, LexerOptions, TokenKind};
+///
+/// let options = LexerOptions::new().identifier_case(IdentifierCase::Lower);
+/// let mut lexer = Lexer::with_options("UserName \"UserName\"", options);
+/// assert_eq!(lexer.next_token().kind, TokenKind::Identifier("username".into()));
+/// assert_eq!(lexer.next_token().kind, TokenKind::Identifier("UserName".into()));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LexerOptions {
+    /// How keywords are recognized.
+    pub keyword_case: KeywordCase,
+    /// How unquoted identifiers are cased.
+    pub identifier_case: IdentifierCase,
+    /// Whether `[name]` is a quoted identifier, as in SQL Server and SQLite.
+    /// When off, `[` and `]` are bracket tokens.
+    pub bracket_identifiers: bool,
+    /// Whether `E'...'` is a string with backslash escapes, as in
+    /// PostgreSQL.
+    pub escape_strings: bool,
+    /// Whether `$$...$$` and `$tag$...$tag$` are dollar-quoted strings, as
+    /// in PostgreSQL.
+    pub dollar_quoted_strings: bool,
+}
+
+impl LexerOptions {
+    /// Creates the default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            keyword_case: KeywordCase::Insensitive,
+            identifier_case: IdentifierCase::Preserve,
+            bracket_identifiers: false,
+            escape_strings: false,
+            dollar_quoted_strings: false,
+        }
+    }
+
+    /// Returns options for PostgreSQL syntax: escape strings and
+    /// dollar-quoted strings are enabled.
+    #[must_use]
+    pub const fn postgres() -> Self {
+        Self::new().escape_strings(true).dollar_quoted_strings(true)
+    }
+
+    /// Sets how keywords are recognized.
+    #[must_use]
+    pub const fn keyword_case(mut self, case: KeywordCase) -> Self {
+        self.keyword_case = case;
+        self
+    }
+
+    /// Sets how unquoted identifiers are cased.
+    #[must_use]
+    pub const fn identifier_case(mut self, case: IdentifierCase) -> Self {
+        self.identifier_case = case;
+        self
+    }
+
+    /// Sets whether `[name]` is a quoted identifier.
+    #[must_use]
+    pub const fn bracket_identifiers(mut self, enabled: bool) -> Self {
+        self.bracket_identifiers = enabled;
+        self
+    }
+
+    /// Sets whether `E'...'` is a string with backslash escapes.
+    #[must_use]
+    pub const fn escape_strings(mut self, enabled: bool) -> Self {
+        self.escape_strings = enabled;
+        self
+    }
+
+    /// Sets whether `$$...$$` is a dollar-quoted string.
+    #[must_use]
+    pub const fn dollar_quoted_strings(mut self, enabled: bool) -> Self {
+        self.dollar_quoted_strings = enabled;
+        self
+    }
+}
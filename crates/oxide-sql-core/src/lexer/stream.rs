@@ -0,0 +1,250 @@
+//! Token iterators with lookahead.
+
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use super::{LexError, Lexer, RawToken, Token, TokenKind};
+
+/// A token type that a [`TokenStream`] can produce.
+pub trait StreamToken<'a>: Sized {
+    /// Scans the next token from `lexer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LexError`] if the input at the current position is not a
+    /// valid token.
+    fn scan(lexer: &mut Lexer<'a>) -> Result<Self, LexError>;
+
+    /// Returns true if this token marks the end of input.
+    fn is_end(&self) -> bool;
+}
+
+impl<'a> StreamToken<'a> for Token {
+    fn scan(lexer: &mut Lexer<'a>) -> Result<Self, LexError> {
+        let token = lexer.next_token();
+        match token.kind {
+            TokenKind::Error(message) => Err(LexError::new(message, token.span)),
+            _ => Ok(token),
+        }
+    }
+
+    fn is_end(&self) -> bool {
+        self.is_eof()
+    }
+}
+
+impl<'a> StreamToken<'a> for RawToken<'a> {
+    fn scan(lexer: &mut Lexer<'a>) -> Result<Self, LexError> {
+        lexer.next_raw()
+    }
+
+    fn is_end(&self) -> bool {
+        self.is_eof()
+    }
+}
+
+/// An iterator over the tokens of a SQL string, with arbitrary lookahead.
+///
+/// Yields every token up to, but not including, the end of input. Lexical
+/// errors are yielded as `Err` items and scanning resumes after the offending
+/// input, so a stream always covers the whole string.
+pub struct TokenStream<'a, T> {
+    lexer: Lexer<'a>,
+    /// Tokens scanned by `peek_n()` but not yet yielded.
+    lookahead: VecDeque<Result<T, LexError>>,
+    done: bool,
+}
+
+/// A stream of owned [`Token`]s.
+pub type Tokens<'a> = TokenStream<'a, Token>;
+
+/// A stream of [`RawToken`]s borrowed from the input. Scanning does not
+/// allocate unless [`TokenStream::peek_n`] needs to buffer tokens.
+pub type RawTokens<'a> = TokenStream<'a, RawToken<'a>>;
+
+impl<'a, T: StreamToken<'a>> TokenStream<'a, T> {
+    /// Creates a stream that reads from `lexer`.
+    #[must_use]
+    pub const fn new(lexer: Lexer<'a>) -> Self {
+        Self {
+            lexer,
+            lookahead: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Returns the next item without consuming it.
+    pub fn peek(&mut self) -> Option<&Result<T, LexError>> {
+        self.peek_n(0)
+    }
+
+    /// Returns the item `n` positions ahead without consuming anything.
+    ///
+    /// `peek_n(0)` is the item the next call to `next()` returns. Returns
+    /// `None` if the input ends first.
+    pub fn peek_n(&mut self, n: usize) -> Option<&Result<T, LexError>> {
+        while self.lookahead.len() <= n {
+            let item = self.scan()?;
+            self.lookahead.push_back(item);
+        }
+        self.lookahead.get(n)
+    }
+
+    /// Scans the next item from the lexer, bypassing the lookahead buffer.
+    fn scan(&mut self) -> Option<Result<T, LexError>> {
+        if self.done {
+            return None;
+        }
+        match T::scan(&mut self.lexer) {
+            Ok(token) if token.is_end() => {
+                self.done = true;
+                None
+            }
+            item => Some(item),
+        }
+    }
+}
+
+impl<'a, T: StreamToken<'a>> Iterator for TokenStream<'a, T> {
+    type Item = Result<T, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lookahead.pop_front().or_else(|| self.scan())
+    }
+}
+
+impl<'a, T: StreamToken<'a>> FusedIterator for TokenStream<'a, T> {}
+
+impl<'a> Lexer<'a> {
+    /// Returns an iterator over the owned tokens of the remaining input.
+    #[must_use]
+    pub const fn tokens(self) -> Tokens<'a> {
+        TokenStream::new(self)
+    }
+
+    /// Returns an iterator over the remaining input's tokens that borrows
+    /// their text instead of allocating.
+    #[must_use]
+    pub const fn raw_tokens(self) -> RawTokens<'a> {
+        TokenStream::new(self)
+    }
+}
+
+impl<'a> IntoIterator for Lexer<'a> {
+    type Item = Result<Token, LexError>;
+    type IntoIter = Tokens<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{Keyword, RawTokenKind, Span};
+
+    #[test]
+    fn test_iterator_stops_before_eof() {
+        let kinds: Vec<TokenKind> = Lexer::new("SELECT a FROM t")
+            .into_iter()
+            .map(|t| t.unwrap().kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Keyword(Keyword::Select),
+                TokenKind::Identifier(String::from("a")),
+                TokenKind::Keyword(Keyword::From),
+                TokenKind::Identifier(String::from("t")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_peek_n_does_not_consume() {
+        let mut tokens = Lexer::new("a + 1").tokens();
+        assert_eq!(
+            tokens.peek_n(2).unwrap().as_ref().unwrap().kind,
+            TokenKind::Integer(1)
+        );
+        assert_eq!(tokens.peek_n(3), None);
+        assert_eq!(
+            tokens.peek().unwrap().as_ref().unwrap().kind,
+            TokenKind::Identifier(String::from("a"))
+        );
+
+        let kinds: Vec<TokenKind> = tokens.map(|t| t.unwrap().kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Identifier(String::from("a")),
+                TokenKind::Plus,
+                TokenKind::Integer(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors_are_yielded_and_scanning_resumes() {
+        let items: Vec<Result<Token, LexError>> = Lexer::new("a ! b").into_iter().collect();
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[1],
+            Err(LexError::new("Unexpected character: !", Span::new(2, 3)))
+        );
+        assert_eq!(
+            items[2].as_ref().unwrap().kind,
+            TokenKind::Identifier(String::from("b"))
+        );
+    }
+
+    #[test]
+    fn test_raw_tokens_borrow_source_text() {
+        let sql = "SELECT \"my col\", 'it''s', X'CAFE', 1.5 -- done";
+        let tokens: Vec<RawToken<'_>> = Lexer::new(sql).raw_tokens().map(Result::unwrap).collect();
+        let summary: Vec<(RawTokenKind, &str)> =
+            tokens.iter().map(|t| (t.kind.clone(), t.text)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (RawTokenKind::Keyword(Keyword::Select), "SELECT"),
+                (RawTokenKind::QuotedIdentifier, "\"my col\""),
+                (RawTokenKind::Symbol(TokenKind::Comma), ","),
+                (RawTokenKind::String, "'it''s'"),
+                (RawTokenKind::Symbol(TokenKind::Comma), ","),
+                (RawTokenKind::Blob, "X'CAFE'"),
+                (RawTokenKind::Symbol(TokenKind::Comma), ","),
+                (RawTokenKind::Float, "1.5"),
+            ]
+        );
+        for token in &tokens {
+            assert_eq!(&sql[token.span.start..token.span.end], token.text);
+        }
+    }
+
+    #[test]
+    fn test_raw_token_to_token_decodes_values() {
+        let mut lexer = Lexer::new("'it''s' X'CAFE' \"a\"\"b\"");
+        let decoded: Vec<TokenKind> = (0..3)
+            .map(|_| lexer.next_raw().unwrap().to_token().kind)
+            .collect();
+        assert_eq!(
+            decoded,
+            vec![
+                TokenKind::String(String::from("it's")),
+                TokenKind::Blob(vec![0xCA, 0xFE]),
+                TokenKind::Identifier(String::from("a\"b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_is_fused() {
+        let mut tokens = Lexer::new("a").raw_tokens();
+        assert!(tokens.next().is_some());
+        assert!(tokens.next().is_none());
+        assert!(tokens.next().is_none());
+        assert!(tokens.peek().is_none());
+    }
+}
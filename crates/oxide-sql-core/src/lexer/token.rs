@@ -134,6 +134,7 @@ pub enum Keyword {
     Cast,
     Coalesce,
     Nullif,
+    Collate,
 
     // Transaction
     Begin,
@@ -156,12 +157,148 @@ pub enum Keyword {
 }
 
 impl Keyword {
+    /// Every keyword, in declaration order.
+    const ALL: [Self; 122] = [
+        Self::Select,
+        Self::From,
+        Self::Where,
+        Self::Order,
+        Self::By,
+        Self::Group,
+        Self::Having,
+        Self::Limit,
+        Self::Offset,
+        Self::Distinct,
+        Self::All,
+        Self::Join,
+        Self::Inner,
+        Self::Left,
+        Self::Right,
+        Self::Full,
+        Self::Outer,
+        Self::Cross,
+        Self::On,
+        Self::Using,
+        Self::Union,
+        Self::Intersect,
+        Self::Except,
+        Self::Insert,
+        Self::Into,
+        Self::Values,
+        Self::Update,
+        Self::Set,
+        Self::Delete,
+        Self::Create,
+        Self::Drop,
+        Self::Alter,
+        Self::Table,
+        Self::Index,
+        Self::View,
+        Self::Database,
+        Self::Schema,
+        Self::Trigger,
+        Self::Primary,
+        Self::Key,
+        Self::Foreign,
+        Self::References,
+        Self::Unique,
+        Self::Check,
+        Self::Default,
+        Self::Constraint,
+        Self::Cascade,
+        Self::Restrict,
+        Self::And,
+        Self::Or,
+        Self::Not,
+        Self::In,
+        Self::Between,
+        Self::Like,
+        Self::Is,
+        Self::Null,
+        Self::True,
+        Self::False,
+        Self::Exists,
+        Self::Asc,
+        Self::Desc,
+        Self::Nulls,
+        Self::First,
+        Self::Last,
+        Self::Count,
+        Self::Sum,
+        Self::Avg,
+        Self::Min,
+        Self::Max,
+        Self::Int,
+        Self::Integer,
+        Self::Smallint,
+        Self::Bigint,
+        Self::Real,
+        Self::Double,
+        Self::Float,
+        Self::Decimal,
+        Self::Numeric,
+        Self::Char,
+        Self::Varchar,
+        Self::Text,
+        Self::Blob,
+        Self::Boolean,
+        Self::Date,
+        Self::Time,
+        Self::Timestamp,
+        Self::Datetime,
+        Self::Autoincrement,
+        Self::If,
+        Self::Temporary,
+        Self::Temp,
+        Self::Conflict,
+        Self::Replace,
+        Self::Abort,
+        Self::Rollback,
+        Self::Fail,
+        Self::Ignore,
+        Self::As,
+        Self::Case,
+        Self::When,
+        Self::Then,
+        Self::Else,
+        Self::End,
+        Self::Cast,
+        Self::Coalesce,
+        Self::Nullif,
+        Self::Collate,
+        Self::Begin,
+        Self::Commit,
+        Self::Transaction,
+        Self::With,
+        Self::Recursive,
+        Self::Over,
+        Self::Partition,
+        Self::Window,
+        Self::Rows,
+        Self::Range,
+        Self::Unbounded,
+        Self::Preceding,
+        Self::Following,
+        Self::Current,
+        Self::Row,
+    ];
+
+    /// Length of the longest keyword (`AUTOINCREMENT`).
+    const MAX_LEN: usize = 13;
+
     /// Attempts to parse a keyword from a string (case-insensitive).
     #[must_use]
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
-        // Convert to uppercase for comparison
-        match s.to_ascii_uppercase().as_str() {
+        // Uppercase into a stack buffer: this runs for every identifier the
+        // lexer sees, so it must not allocate.
+        let mut buf = [0_u8; Self::MAX_LEN];
+        let upper = buf.get_mut(..s.len())?;
+        upper.copy_from_slice(s.as_bytes());
+        upper.make_ascii_uppercase();
+        let upper = core::str::from_utf8(upper).ok()?;
+
+        match upper {
             "SELECT" => Some(Self::Select),
             "FROM" => Some(Self::From),
             "WHERE" => Some(Self::Where),
@@ -268,6 +405,7 @@ impl Keyword {
             "CAST" => Some(Self::Cast),
             "COALESCE" => Some(Self::Coalesce),
             "NULLIF" => Some(Self::Nullif),
+            "COLLATE" => Some(Self::Collate),
             "BEGIN" => Some(Self::Begin),
             "COMMIT" => Some(Self::Commit),
             "TRANSACTION" => Some(Self::Transaction),
@@ -397,6 +535,7 @@ impl Keyword {
             Self::Cast => "CAST",
             Self::Coalesce => "COALESCE",
             Self::Nullif => "NULLIF",
+            Self::Collate => "COLLATE",
             Self::Begin => "BEGIN",
             Self::Commit => "COMMIT",
             Self::Transaction => "TRANSACTION",
@@ -416,6 +555,22 @@ impl Keyword {
     }
 }
 
+// `from_str` uppercases into a `MAX_LEN` buffer, so a longer keyword would
+// never match; `ALL` must list the variants in order so none is missed.
+const _: () = {
+    let mut longest = 0;
+    let mut i = 0;
+    while i < Keyword::ALL.len() {
+        assert!(Keyword::ALL[i] as usize == i);
+        let len = Keyword::ALL[i].as_str().len();
+        if len > longest {
+            longest = len;
+        }
+        i += 1;
+    }
+    assert!(longest == Keyword::MAX_LEN);
+};
+
 /// The kind of token.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
@@ -424,6 +579,9 @@ pub enum TokenKind {
     Integer(i64),
     /// Float literal (e.g., 3.14)
     Float(f64),
+    /// Numeric literal too large for `Integer` or `Float`, as written
+    /// (e.g., 99999999999999999999)
+    BigNumber(String),
     /// String literal (e.g., 'hello')
     String(String),
     /// Blob literal (e.g., X'1234')
@@ -470,6 +628,14 @@ pub enum TokenKind {
     LeftShift,
     /// >>
     RightShift,
+    /// -> (JSON field)
+    Arrow,
+    /// ->> (JSON field as text)
+    LongArrow,
+    /// #> (JSON path)
+    HashArrow,
+    /// #>> (JSON path as text)
+    HashLongArrow,
 
     // Delimiters
     /// (
@@ -540,6 +706,75 @@ impl Token {
     }
 }
 
+/// The kind of a [`RawToken`].
+///
+/// Unlike [`TokenKind`], literal kinds carry no value; the token's text holds
+/// the literal exactly as written.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawTokenKind {
+    /// Integer literal, decimal or hexadecimal (`0x1F`)
+    Integer,
+    /// Floating-point literal
+    Float,
+    /// String literal, including its quotes
+    String,
+    /// Escape string literal (`E'...'`), including its prefix and quotes
+    EscapeString,
+    /// Dollar-quoted string literal (`$tag$...$tag$`), including its
+    /// delimiters
+    DollarString,
+    /// Blob literal, including the `X'` prefix and closing quote
+    Blob,
+    /// Unquoted identifier
+    Identifier,
+    /// Quoted identifier (`"name"` or `` `name` ``), including its quotes
+    QuotedIdentifier,
+    /// Keyword
+    Keyword(Keyword),
+    /// Operator or delimiter; never a kind that owns data
+    Symbol(TokenKind),
+    /// End of input
+    Eof,
+}
+
+/// A token that borrows its text from the input.
+///
+/// Produced by [`Lexer::next_raw`](super::Lexer::next_raw) without allocating,
+/// which suits tools such as syntax highlighters that only need token
+/// boundaries and kinds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawToken<'a> {
+    /// The kind of token.
+    pub kind: RawTokenKind,
+    /// The source text of the token.
+    pub text: &'a str,
+    /// The location in the source code.
+    pub span: Span,
+}
+
+impl<'a> RawToken<'a> {
+    /// Creates a new raw token.
+    #[must_use]
+    pub const fn new(kind: RawTokenKind, text: &'a str, span: Span) -> Self {
+        Self { kind, text, span }
+    }
+
+    /// Returns true if this is an EOF token.
+    #[must_use]
+    pub const fn is_eof(&self) -> bool {
+        matches!(self.kind, RawTokenKind::Eof)
+    }
+
+    /// Returns the keyword if this is a keyword token.
+    #[must_use]
+    pub const fn as_keyword(&self) -> Option<Keyword> {
+        match &self.kind {
+            RawTokenKind::Keyword(kw) => Some(*kw),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -550,6 +785,21 @@ mod tests {
         assert_eq!(Keyword::from_str("select"), Some(Keyword::Select));
         assert_eq!(Keyword::from_str("SeLeCt"), Some(Keyword::Select));
         assert_eq!(Keyword::from_str("not_a_keyword"), None);
+        assert_eq!(
+            Keyword::from_str("autoincrement"),
+            Some(Keyword::Autoincrement)
+        );
+        assert_eq!(Keyword::from_str("autoincrements"), None);
+        assert_eq!(Keyword::from_str("sélect"), None);
+    }
+
+    #[test]
+    fn test_keyword_round_trips() {
+        for keyword in Keyword::ALL {
+            assert_eq!(Keyword::from_str(keyword.as_str()), Some(keyword));
+            let lower = keyword.as_str().to_ascii_lowercase();
+            assert_eq!(Keyword::from_str(&lower), Some(keyword));
+        }
     }
 
     #[test]
@@ -1,8 +1,11 @@
 //! SQL Tokenizer implementation.
 
-use super::{Keyword, Span, Token, TokenKind};
+use super::{
+    Keyword, KeywordCase, LexError, LexerOptions, RawToken, RawTokenKind, Span, Token, TokenKind,
+};
 
 /// A lexer that tokenizes SQL input.
+#[derive(Clone)]
 pub struct Lexer<'a> {
     /// The input source code.
     input: &'a str,
@@ -10,19 +13,40 @@ pub struct Lexer<'a> {
     pos: usize,
     /// The byte position of the start of the current token.
     start: usize,
+    /// Case handling for keywords and identifiers.
+    options: LexerOptions,
 }
 
 impl<'a> Lexer<'a> {
     /// Creates a new lexer for the given input.
     #[must_use]
     pub const fn new(input: &'a str) -> Self {
+        Self::with_options(input, LexerOptions::new())
+    }
+
+    /// Creates a new lexer with the given options.
+    #[must_use]
+    pub const fn with_options(input: &'a str, options: LexerOptions) -> Self {
         Self {
             input,
             pos: 0,
             start: 0,
+            options,
         }
     }
 
+    /// Returns the options this lexer was created with.
+    #[must_use]
+    pub const fn options(&self) -> LexerOptions {
+        self.options
+    }
+
+    /// Returns the input being tokenized.
+    #[must_use]
+    pub const fn input(&self) -> &'a str {
+        self.input
+    }
+
     /// Returns the current character without advancing.
     fn peek(&self) -> Option<char> {
         self.input[self.pos..].chars().next()
@@ -86,13 +110,13 @@ impl<'a> Lexer<'a> {
         Span::new(self.start, self.pos)
     }
 
-    /// Creates a token with the current span.
-    fn make_token(&self, kind: TokenKind) -> Token {
-        Token::new(kind, self.make_span())
+    /// Creates an error covering the current token.
+    fn error(&self, message: &str) -> LexError {
+        LexError::new(message, self.make_span())
     }
 
     /// Scans an identifier or keyword.
-    fn scan_identifier(&mut self) -> Token {
+    fn scan_identifier(&mut self) -> RawTokenKind {
         while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
             self.advance();
         }
@@ -100,58 +124,111 @@ impl<'a> Lexer<'a> {
         let text = &self.input[self.start..self.pos];
 
         // Check if it's a keyword
-        if let Some(keyword) = Keyword::from_str(text) {
-            self.make_token(TokenKind::Keyword(keyword))
-        } else {
-            self.make_token(TokenKind::Identifier(String::from(text)))
+        match Keyword::from_str(text) {
+            Some(kw)
+                if self.options.keyword_case == KeywordCase::Insensitive || text == kw.as_str() =>
+            {
+                RawTokenKind::Keyword(kw)
+            }
+            _ => RawTokenKind::Identifier,
         }
     }
 
-    /// Scans a quoted identifier (e.g., "column name" or `column name`).
-    fn scan_quoted_identifier(&mut self, quote: char) -> Token {
-        self.advance(); // consume opening quote
-        let content_start = self.pos;
-
+    /// Scans the rest of a quoted literal or identifier, up to and including
+    /// the closing `quote`. Doubled quotes are part of the content.
+    ///
+    /// Returns `false` if the input ends first.
+    fn scan_quoted(&mut self, quote: char) -> bool {
         loop {
-            match self.peek() {
+            match self.advance() {
                 Some(c) if c == quote => {
                     // Check for escaped quote (double quote)
-                    if self.peek_next() == Some(quote) {
-                        self.advance();
+                    if self.peek() == Some(quote) {
                         self.advance();
                     } else {
-                        break;
+                        return true;
                     }
                 }
-                Some(_) => {
-                    self.advance();
+                Some(_) => {}
+                None => return false,
+            }
+        }
+    }
+
+    /// Scans the rest of an `E'...'` string after the opening quote. A
+    /// backslash escapes the next character, including a quote.
+    ///
+    /// Returns `false` if the input ends first.
+    fn scan_escape_string(&mut self) -> bool {
+        loop {
+            match self.advance() {
+                Some('\\') => {
+                    if self.advance().is_none() {
+                        return false;
+                    }
                 }
-                None => {
-                    return self.make_token(TokenKind::Error(String::from(
-                        "Unterminated quoted identifier",
-                    )));
+                Some('\'') => {
+                    if self.peek() == Some('\'') {
+                        self.advance();
+                    } else {
+                        return true;
+                    }
                 }
+                Some(_) => {}
+                None => return false,
             }
         }
+    }
 
-        let content = &self.input[content_start..self.pos];
-        self.advance(); // consume closing quote
+    /// Scans the rest of a `$tag$...$tag$` string after the first `$`.
+    fn scan_dollar_string(&mut self) -> Result<RawTokenKind, LexError> {
+        if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error("Unexpected character: $"));
+        }
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.advance();
+        }
+        if self.peek() != Some('$') {
+            return Err(self.error("Unexpected character: $"));
+        }
+        self.advance();
 
-        // Handle escaped quotes
-        let unescaped = content.replace(&format!("{quote}{quote}"), &quote.to_string());
-        self.make_token(TokenKind::Identifier(unescaped))
+        let delimiter = &self.input[self.start..self.pos];
+        if let Some(end) = self.input[self.pos..].find(delimiter) {
+            self.pos += end + delimiter.len();
+            Ok(RawTokenKind::DollarString)
+        } else {
+            self.pos = self.input.len();
+            Err(self.error("Unterminated dollar-quoted string"))
+        }
     }
 
     /// Scans a number (integer or float).
-    fn scan_number(&mut self) -> Token {
-        let mut is_float = false;
+    fn scan_number(&mut self, first: char) -> Result<RawTokenKind, LexError> {
+        // Hexadecimal integer (0x1F)
+        if first == '0'
+            && self.peek().is_some_and(|c| c == 'x' || c == 'X')
+            && self.peek_next().is_some_and(|c| c.is_ascii_hexdigit())
+        {
+            self.advance(); // consume x/X
+            while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                self.advance();
+            }
+            return Ok(RawTokenKind::Integer);
+        }
+
+        // A leading `.` has already been consumed for numbers like `.5`
+        let mut is_float = first == '.';
 
         while self.peek().is_some_and(|c| c.is_ascii_digit()) {
             self.advance();
         }
 
         // Check for decimal point
-        if self.peek() == Some('.') && self.peek_next().is_some_and(|c| c.is_ascii_digit()) {
+        if !is_float
+            && self.peek() == Some('.')
+            && self.peek_next().is_some_and(|c| c.is_ascii_digit())
+        {
             is_float = true;
             self.advance(); // consume .
             while self.peek().is_some_and(|c| c.is_ascii_digit()) {
@@ -166,226 +243,241 @@ impl<'a> Lexer<'a> {
             if self.peek().is_some_and(|c| c == '+' || c == '-') {
                 self.advance();
             }
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error("Missing exponent digits"));
+            }
             while self.peek().is_some_and(|c| c.is_ascii_digit()) {
                 self.advance();
             }
         }
 
-        let text = &self.input[self.start..self.pos];
-
-        if is_float {
-            match text.parse::<f64>() {
-                Ok(f) => self.make_token(TokenKind::Float(f)),
-                Err(e) => self.make_token(TokenKind::Error(format!("Invalid float: {e}"))),
-            }
+        Ok(if is_float {
+            RawTokenKind::Float
         } else {
-            match text.parse::<i64>() {
-                Ok(i) => self.make_token(TokenKind::Integer(i)),
-                Err(e) => self.make_token(TokenKind::Error(format!("Invalid integer: {e}"))),
-            }
-        }
-    }
-
-    /// Scans a string literal.
-    fn scan_string(&mut self, quote: char) -> Token {
-        self.advance(); // consume opening quote
-        let mut value = String::new();
-
-        loop {
-            match self.peek() {
-                Some(c) if c == quote => {
-                    // Check for escaped quote (double quote)
-                    if self.peek_next() == Some(quote) {
-                        value.push(quote);
-                        self.advance();
-                        self.advance();
-                    } else {
-                        break;
-                    }
-                }
-                Some(c) => {
-                    value.push(c);
-                    self.advance();
-                }
-                None => {
-                    return self.make_token(TokenKind::Error(String::from(
-                        "Unterminated string literal",
-                    )));
-                }
-            }
-        }
-
-        self.advance(); // consume closing quote
-        self.make_token(TokenKind::String(value))
+            RawTokenKind::Integer
+        })
     }
 
-    /// Scans a blob literal (X'...' or x'...').
-    fn scan_blob(&mut self) -> Token {
-        self.advance(); // consume X/x
-        if self.peek() != Some('\'') {
-            return self.scan_identifier();
-        }
-        self.advance(); // consume opening quote
-
-        let mut bytes = Vec::new();
-        let mut hex_chars = String::new();
+    /// Scans the rest of a blob literal after `X'`.
+    fn scan_blob(&mut self) -> Result<RawTokenKind, LexError> {
+        let mut odd = false;
 
         loop {
             match self.peek() {
                 Some('\'') => break,
                 Some(c) if c.is_ascii_hexdigit() => {
-                    hex_chars.push(c);
+                    odd = !odd;
                     self.advance();
-
-                    if hex_chars.len() == 2 {
-                        if let Ok(byte) = u8::from_str_radix(&hex_chars, 16) {
-                            bytes.push(byte);
-                        }
-                        hex_chars.clear();
-                    }
                 }
                 Some(c) if c.is_whitespace() => {
                     self.advance();
                 }
-                Some(_) => {
-                    return self.make_token(TokenKind::Error(String::from(
-                        "Invalid character in blob literal",
-                    )));
-                }
-                None => {
-                    return self
-                        .make_token(TokenKind::Error(String::from("Unterminated blob literal")));
-                }
+                Some(_) => return Err(self.error("Invalid character in blob literal")),
+                None => return Err(self.error("Unterminated blob literal")),
             }
         }
 
-        if !hex_chars.is_empty() {
-            return self.make_token(TokenKind::Error(String::from(
-                "Odd number of hex digits in blob literal",
-            )));
+        if odd {
+            return Err(self.error("Odd number of hex digits in blob literal"));
         }
 
         self.advance(); // consume closing quote
-        self.make_token(TokenKind::Blob(bytes))
+        Ok(RawTokenKind::Blob)
     }
 
-    /// Scans the next token.
-    #[must_use]
-    pub fn next_token(&mut self) -> Token {
+    /// Scans the next token without allocating.
+    ///
+    /// The returned token borrows its text from the input; literals are not
+    /// unescaped or parsed. Use [`next_token`](Self::next_token) for tokens
+    /// with owned, decoded values.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LexError`] for unterminated literals and characters that
+    /// cannot start a token. The lexer skips past the offending input, so
+    /// scanning can continue afterwards.
+    pub fn next_raw(&mut self) -> Result<RawToken<'a>, LexError> {
         self.skip_whitespace_and_comments();
         self.start = self.pos;
 
-        let c = match self.advance() {
-            Some(c) => c,
-            None => return self.make_token(TokenKind::Eof),
+        let kind = self.scan_raw()?;
+        Ok(RawToken::new(
+            kind,
+            &self.input[self.start..self.pos],
+            self.make_span(),
+        ))
+    }
+
+    /// Scans the kind of the token starting at `self.start`.
+    fn scan_raw(&mut self) -> Result<RawTokenKind, LexError> {
+        let Some(c) = self.advance() else {
+            return Ok(RawTokenKind::Eof);
         };
 
-        match c {
+        let symbol = match c {
             // Single-character tokens
-            '(' => self.make_token(TokenKind::LeftParen),
-            ')' => self.make_token(TokenKind::RightParen),
-            '[' => self.make_token(TokenKind::LeftBracket),
-            ']' => self.make_token(TokenKind::RightBracket),
-            ',' => self.make_token(TokenKind::Comma),
-            ';' => self.make_token(TokenKind::Semicolon),
-            '+' => self.make_token(TokenKind::Plus),
-            '-' => self.make_token(TokenKind::Minus),
-            '*' => self.make_token(TokenKind::Star),
-            '/' => self.make_token(TokenKind::Slash),
-            '%' => self.make_token(TokenKind::Percent),
-            '~' => self.make_token(TokenKind::BitNot),
-            '?' => self.make_token(TokenKind::Question),
-            '@' => self.make_token(TokenKind::At),
+            '(' => TokenKind::LeftParen,
+            ')' => TokenKind::RightParen,
+            '[' if self.options.bracket_identifiers => {
+                return if self.scan_quoted(']') {
+                    Ok(RawTokenKind::QuotedIdentifier)
+                } else {
+                    Err(self.error("Unterminated quoted identifier"))
+                };
+            }
+            '[' => TokenKind::LeftBracket,
+            ']' => TokenKind::RightBracket,
+            ',' => TokenKind::Comma,
+            ';' => TokenKind::Semicolon,
+            '+' => TokenKind::Plus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '%' => TokenKind::Percent,
+            '~' => TokenKind::BitNot,
+            '?' => TokenKind::Question,
+            '@' => TokenKind::At,
 
             // Potentially multi-character tokens
-            '.' => self.make_token(TokenKind::Dot),
+            '.' if self.peek().is_some_and(|c| c.is_ascii_digit()) => {
+                return self.scan_number(c);
+            }
+            '.' => TokenKind::Dot,
             ':' => {
                 if self.peek() == Some(':') {
                     self.advance();
-                    self.make_token(TokenKind::DoubleColon)
+                    TokenKind::DoubleColon
                 } else {
-                    self.make_token(TokenKind::Colon)
+                    TokenKind::Colon
+                }
+            }
+            '-' | '#' if self.peek() == Some('>') => {
+                self.advance();
+                let long = self.peek() == Some('>');
+                if long {
+                    self.advance();
+                }
+                match (c, long) {
+                    ('-', false) => TokenKind::Arrow,
+                    ('-', true) => TokenKind::LongArrow,
+                    (_, false) => TokenKind::HashArrow,
+                    (_, true) => TokenKind::HashLongArrow,
                 }
             }
-            '=' => self.make_token(TokenKind::Eq),
+            '-' => TokenKind::Minus,
+            '=' => TokenKind::Eq,
             '<' => {
                 if self.peek() == Some('=') {
                     self.advance();
-                    self.make_token(TokenKind::LtEq)
+                    TokenKind::LtEq
                 } else if self.peek() == Some('>') {
                     self.advance();
-                    self.make_token(TokenKind::NotEq)
+                    TokenKind::NotEq
                 } else if self.peek() == Some('<') {
                     self.advance();
-                    self.make_token(TokenKind::LeftShift)
+                    TokenKind::LeftShift
                 } else {
-                    self.make_token(TokenKind::Lt)
+                    TokenKind::Lt
                 }
             }
             '>' => {
                 if self.peek() == Some('=') {
                     self.advance();
-                    self.make_token(TokenKind::GtEq)
+                    TokenKind::GtEq
                 } else if self.peek() == Some('>') {
                     self.advance();
-                    self.make_token(TokenKind::RightShift)
+                    TokenKind::RightShift
                 } else {
-                    self.make_token(TokenKind::Gt)
+                    TokenKind::Gt
                 }
             }
             '!' => {
                 if self.peek() == Some('=') {
                     self.advance();
-                    self.make_token(TokenKind::NotEq)
+                    TokenKind::NotEq
                 } else {
-                    self.make_token(TokenKind::Error(String::from("Unexpected character: !")))
+                    return Err(self.error("Unexpected character: !"));
                 }
             }
             '|' => {
                 if self.peek() == Some('|') {
                     self.advance();
-                    self.make_token(TokenKind::Concat)
+                    TokenKind::Concat
                 } else {
-                    self.make_token(TokenKind::BitOr)
+                    TokenKind::BitOr
                 }
             }
-            '&' => self.make_token(TokenKind::BitAnd),
+            '&' => TokenKind::BitAnd,
 
             // String literals
             '\'' => {
-                self.pos = self.start; // Reset position to scan from quote
-                self.scan_string('\'')
+                return if self.scan_quoted('\'') {
+                    Ok(RawTokenKind::String)
+                } else {
+                    Err(self.error("Unterminated string literal"))
+                };
             }
 
             // Quoted identifiers
-            '"' => {
-                self.pos = self.start;
-                self.scan_quoted_identifier('"')
+            '"' | '`' => {
+                return if self.scan_quoted(c) {
+                    Ok(RawTokenKind::QuotedIdentifier)
+                } else {
+                    Err(self.error("Unterminated quoted identifier"))
+                };
             }
-            '`' => {
-                self.pos = self.start;
-                self.scan_quoted_identifier('`')
+
+            // Escape string literals
+            'E' | 'e' if self.options.escape_strings && self.peek() == Some('\'') => {
+                self.advance(); // consume opening quote
+                return if self.scan_escape_string() {
+                    Ok(RawTokenKind::EscapeString)
+                } else {
+                    Err(self.error("Unterminated string literal"))
+                };
             }
 
+            // Dollar-quoted string literals
+            '$' if self.options.dollar_quoted_strings => return self.scan_dollar_string(),
+
             // Blob literals
             'X' | 'x' if self.peek() == Some('\'') => {
-                self.pos = self.start;
-                self.scan_blob()
+                self.advance(); // consume opening quote
+                return self.scan_blob();
             }
 
             // Numbers
-            c if c.is_ascii_digit() => {
-                self.pos = self.start;
-                self.scan_number()
-            }
+            c if c.is_ascii_digit() => return self.scan_number(c),
 
             // Identifiers and keywords
-            c if c.is_alphabetic() || c == '_' => {
-                self.pos = self.start;
-                self.scan_identifier()
+            c if c.is_alphabetic() || c == '_' => return Ok(self.scan_identifier()),
+
+            _ => {
+                return Err(LexError::new(
+                    format!("Unexpected character: {c}"),
+                    self.make_span(),
+                ));
             }
+        };
 
-            _ => self.make_token(TokenKind::Error(format!("Unexpected character: {c}"))),
+        Ok(RawTokenKind::Symbol(symbol))
+    }
+
+    /// Scans the next token.
+    ///
+    /// Errors are reported as [`TokenKind::Error`] tokens.
+    #[must_use]
+    pub fn next_token(&mut self) -> Token {
+        match self.next_raw() {
+            Ok(raw) => {
+                let mut token = raw.to_token();
+                if raw.kind == RawTokenKind::Identifier
+                    && let TokenKind::Identifier(name) = &mut token.kind
+                {
+                    self.options.identifier_case.fold(name);
+                }
+                token
+            }
+            Err(err) => Token::new(TokenKind::Error(err.message), err.span),
         }
     }
 
@@ -405,9 +497,176 @@ impl<'a> Lexer<'a> {
     }
 }
 
+impl RawToken<'_> {
+    /// Converts this token into an owned [`Token`], unescaping string
+    /// literals and identifiers and parsing numbers.
+    ///
+    /// Decimal integers beyond `i64` and floats beyond `f64` become
+    /// [`TokenKind::BigNumber`] with their text as written. Hexadecimal
+    /// integers are read as 64-bit two's complement, so `0xFFFFFFFFFFFFFFFF`
+    /// is `-1`; longer ones become [`TokenKind::Error`].
+    #[must_use]
+    pub fn to_token(&self) -> Token {
+        let kind = match &self.kind {
+            RawTokenKind::Integer => parse_integer(self.text),
+            RawTokenKind::Float => match self.text.parse::<f64>() {
+                Ok(f) if f.is_finite() => TokenKind::Float(f),
+                Ok(_) => TokenKind::BigNumber(String::from(self.text)),
+                Err(e) => TokenKind::Error(format!("Invalid float: {e}")),
+            },
+            RawTokenKind::String => TokenKind::String(unquote(self.text)),
+            RawTokenKind::EscapeString => match unescape(&self.text[2..self.text.len() - 1]) {
+                Ok(value) => TokenKind::String(value),
+                Err(message) => TokenKind::Error(message),
+            },
+            RawTokenKind::DollarString => {
+                let delimiter = self.text[1..].find('$').map_or(0, |i| i + 2);
+                let content = &self.text[delimiter..self.text.len() - delimiter];
+                TokenKind::String(String::from(content))
+            }
+            RawTokenKind::QuotedIdentifier => TokenKind::Identifier(unquote(self.text)),
+            RawTokenKind::Blob => TokenKind::Blob(decode_blob(self.text)),
+            RawTokenKind::Identifier => TokenKind::Identifier(String::from(self.text)),
+            RawTokenKind::Keyword(kw) => TokenKind::Keyword(*kw),
+            RawTokenKind::Symbol(kind) => kind.clone(),
+            RawTokenKind::Eof => TokenKind::Eof,
+        };
+        Token::new(kind, self.span)
+    }
+}
+
+/// Strips the surrounding quotes from a quoted literal or identifier and
+/// collapses doubled closing quotes (`''`, `""`, or `]]`) into one.
+fn unquote(text: &str) -> String {
+    let mut chars = text.chars();
+    let (Some(_), Some(quote)) = (chars.next(), chars.next_back()) else {
+        return String::new();
+    };
+    let content = chars.as_str();
+    if !content.contains(quote) {
+        return String::from(content);
+    }
+
+    let mut value = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        value.push(c);
+        if c == quote {
+            // The lexer only accepts quotes inside literals in pairs.
+            chars.next();
+        }
+    }
+    value
+}
+
+/// Decodes the content of an `E'...'` string: backslash escapes (`\n`,
+/// `\t`, `\x41`, `\101`, `\u00e9`, ...) and doubled quotes.
+///
+/// Octal and hex escapes name a code point rather than a byte.
+fn unescape(content: &str) -> Result<String, String> {
+    let mut value = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let Some(escape) = chars.next() else {
+                    return Err(String::from("Unterminated escape sequence"));
+                };
+                let (radix, max_digits) = match escape {
+                    'b' => {
+                        value.push('\u{8}');
+                        continue;
+                    }
+                    'f' => {
+                        value.push('\u{c}');
+                        continue;
+                    }
+                    'n' => {
+                        value.push('\n');
+                        continue;
+                    }
+                    'r' => {
+                        value.push('\r');
+                        continue;
+                    }
+                    't' => {
+                        value.push('\t');
+                        continue;
+                    }
+                    '0'..='7' => (8, 3),
+                    'x' => (16, 2),
+                    'u' => (16, 4),
+                    'U' => (16, 8),
+                    other => {
+                        value.push(other);
+                        continue;
+                    }
+                };
+
+                let mut code = escape.to_digit(8).unwrap_or(0);
+                let mut digits = u32::from(radix == 8);
+                while digits < max_digits
+                    && let Some(d) = chars.peek().and_then(|c| c.to_digit(radix))
+                {
+                    code = code * radix + d;
+                    digits += 1;
+                    chars.next();
+                }
+                if radix == 16 && (digits == 0 || (escape != 'x' && digits < max_digits)) {
+                    return Err(format!("Invalid escape sequence: \\{escape}"));
+                }
+                let Some(decoded) = char::from_u32(code) else {
+                    return Err(format!("Invalid code point in escape: {code:#x}"));
+                };
+                value.push(decoded);
+            }
+            '\'' => {
+                // Quotes only appear doubled inside the literal.
+                chars.next();
+                value.push(c);
+            }
+            _ => value.push(c),
+        }
+    }
+    Ok(value)
+}
+
+/// Decodes the hex digits of a `X'...'` blob literal.
+fn decode_blob(text: &str) -> Vec<u8> {
+    let digits: Vec<u8> = text
+        .bytes()
+        .skip(2)
+        .filter(u8::is_ascii_hexdigit)
+        .map(|b| match b {
+            b'0'..=b'9' => b - b'0',
+            b'a'..=b'f' => b - b'a' + 10,
+            _ => b - b'A' + 10,
+        })
+        .collect();
+    digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect()
+}
+
+/// Parses a decimal or hexadecimal integer literal.
+fn parse_integer(text: &str) -> TokenKind {
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        return match u64::from_str_radix(hex, 16) {
+            Ok(n) => TokenKind::Integer(n.cast_signed()),
+            Err(_) => TokenKind::Error(format!("Hex literal too large: {text}")),
+        };
+    }
+    match text.parse::<i64>() {
+        Ok(n) => TokenKind::Integer(n),
+        Err(_) => TokenKind::BigNumber(String::from(text)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lexer::IdentifierCase;
 
     fn tokenize(input: &str) -> Vec<Token> {
         Lexer::new(input).tokenize()
@@ -481,6 +740,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_upper_case_keywords_only() {
+        let options = LexerOptions::new().keyword_case(KeywordCase::Upper);
+        let tokens = Lexer::with_options("SELECT select Select", options).tokenize();
+        let kinds: Vec<TokenKind> = tokens.into_iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Keyword(Keyword::Select),
+                TokenKind::Identifier(String::from("select")),
+                TokenKind::Identifier(String::from("Select")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identifier_case_folding() {
+        let fold = |case| {
+            let options = LexerOptions::new().identifier_case(case);
+            let tokens = Lexer::with_options("MixedCase \"MixedCase\" select", options).tokenize();
+            tokens.into_iter().map(|t| t.kind).collect::<Vec<_>>()
+        };
+        let ident = |s: &str| TokenKind::Identifier(String::from(s));
+        let select = TokenKind::Keyword(Keyword::Select);
+
+        assert_eq!(
+            fold(IdentifierCase::Preserve),
+            vec![
+                ident("MixedCase"),
+                ident("MixedCase"),
+                select.clone(),
+                TokenKind::Eof
+            ]
+        );
+        assert_eq!(
+            fold(IdentifierCase::Lower),
+            vec![
+                ident("mixedcase"),
+                ident("MixedCase"),
+                select.clone(),
+                TokenKind::Eof
+            ]
+        );
+        assert_eq!(
+            fold(IdentifierCase::Upper),
+            vec![
+                ident("MIXEDCASE"),
+                ident("MixedCase"),
+                select,
+                TokenKind::Eof
+            ]
+        );
+    }
+
     #[test]
     fn test_identifiers() {
         assert_eq!(
@@ -506,6 +820,127 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_escaped_quoted_identifiers() {
+        assert_eq!(
+            token_kinds("\"weird \"\"name\"\"\" `a``b`"),
+            vec![
+                TokenKind::Identifier(String::from("weird \"name\"")),
+                TokenKind::Identifier(String::from("a`b")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bracket_identifiers() {
+        assert_eq!(
+            token_kinds("[a b]"),
+            vec![
+                TokenKind::LeftBracket,
+                TokenKind::Identifier(String::from("a")),
+                TokenKind::Identifier(String::from("b")),
+                TokenKind::RightBracket,
+                TokenKind::Eof,
+            ]
+        );
+
+        let options = LexerOptions::new().bracket_identifiers(true);
+        let tokens = Lexer::with_options("[a b] [x]]y] [open", options).tokenize();
+        let kinds: Vec<TokenKind> = tokens.into_iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Identifier(String::from("a b")),
+                TokenKind::Identifier(String::from("x]y")),
+                TokenKind::Error(String::from("Unterminated quoted identifier")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    fn postgres_kinds(input: &str) -> Vec<TokenKind> {
+        let tokens = Lexer::with_options(input, LexerOptions::postgres()).tokenize();
+        tokens.into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn test_escape_strings() {
+        assert_eq!(
+            postgres_kinds(r"E'a\nb' e'it\'s' E'\x41\101\u00e9\U0001F600' E'x''y\\'"),
+            vec![
+                TokenKind::String(String::from("a\nb")),
+                TokenKind::String(String::from("it's")),
+                TokenKind::String(String::from("AAé😀")),
+                TokenKind::String(String::from("x'y\\")),
+                TokenKind::Eof,
+            ]
+        );
+        assert_eq!(
+            postgres_kinds(r"E'\u12' E'open\'"),
+            vec![
+                TokenKind::Error(String::from("Invalid escape sequence: \\u")),
+                TokenKind::Error(String::from("Unterminated string literal")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape_strings_are_off_by_default() {
+        assert_eq!(
+            token_kinds(r"E'a\n'"),
+            vec![
+                TokenKind::Identifier(String::from("E")),
+                TokenKind::String(String::from("a\\n")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dollar_quoted_strings() {
+        assert_eq!(
+            postgres_kinds("$$it's $1$$ $fn$a $$ b$fn$ $$$$"),
+            vec![
+                TokenKind::String(String::from("it's $1")),
+                TokenKind::String(String::from("a $$ b")),
+                TokenKind::String(String::new()),
+                TokenKind::Eof,
+            ]
+        );
+        assert_eq!(
+            postgres_kinds("$1 $tag$open"),
+            vec![
+                TokenKind::Error(String::from("Unexpected character: $")),
+                TokenKind::Integer(1),
+                TokenKind::Error(String::from("Unterminated dollar-quoted string")),
+                TokenKind::Eof,
+            ]
+        );
+        assert_eq!(
+            token_kinds("$$"),
+            vec![
+                TokenKind::Error(String::from("Unexpected character: $")),
+                TokenKind::Error(String::from("Unexpected character: $")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_identifiers() {
+        assert_eq!(
+            token_kinds("café 名前 _ñ1"),
+            vec![
+                TokenKind::Identifier(String::from("café")),
+                TokenKind::Identifier(String::from("名前")),
+                TokenKind::Identifier(String::from("_ñ1")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
     #[test]
     fn test_integers() {
         assert_eq!(
@@ -533,6 +968,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_leading_dot_floats() {
+        assert_eq!(
+            token_kinds(".5 t.a"),
+            vec![
+                TokenKind::Float(0.5),
+                TokenKind::Identifier(String::from("t")),
+                TokenKind::Dot,
+                TokenKind::Identifier(String::from("a")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hex_integers() {
+        assert_eq!(
+            token_kinds("0x1F 0XfF 0xFFFFFFFFFFFFFFFF 0x"),
+            vec![
+                TokenKind::Integer(31),
+                TokenKind::Integer(255),
+                TokenKind::Integer(-1),
+                TokenKind::Integer(0),
+                TokenKind::Identifier(String::from("x")),
+                TokenKind::Eof,
+            ]
+        );
+        assert!(matches!(
+            &tokenize("0x10000000000000000")[0].kind,
+            TokenKind::Error(msg) if msg.starts_with("Hex literal too large")
+        ));
+    }
+
+    #[test]
+    fn test_big_numbers() {
+        assert_eq!(
+            token_kinds("9223372036854775807 9223372036854775808 1e400"),
+            vec![
+                TokenKind::Integer(i64::MAX),
+                TokenKind::BigNumber(String::from("9223372036854775808")),
+                TokenKind::BigNumber(String::from("1e400")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_exponent_digits() {
+        let err = Lexer::new("1e+ 2").next_raw().unwrap_err();
+        assert_eq!(err.message, "Missing exponent digits");
+        assert_eq!(err.span, Span::new(0, 3));
+    }
+
     #[test]
     fn test_strings() {
         assert_eq!(
@@ -553,6 +1041,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_escaped_quotes_next_to_delimiters() {
+        assert_eq!(
+            token_kinds("'''' 'a''''b' \"x\"\"\" `é``é`"),
+            vec![
+                TokenKind::String(String::from("'")),
+                TokenKind::String(String::from("a''b")),
+                TokenKind::Identifier(String::from("x\"")),
+                TokenKind::Identifier(String::from("é`é")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
     #[test]
     fn test_blob() {
         let tokens = tokenize("X'48454C4C4F'");
@@ -616,6 +1118,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_operators() {
+        assert_eq!(
+            token_kinds("a->b ->> c #> d #>> e - 1"),
+            vec![
+                TokenKind::Identifier(String::from("a")),
+                TokenKind::Arrow,
+                TokenKind::Identifier(String::from("b")),
+                TokenKind::LongArrow,
+                TokenKind::Identifier(String::from("c")),
+                TokenKind::HashArrow,
+                TokenKind::Identifier(String::from("d")),
+                TokenKind::HashLongArrow,
+                TokenKind::Identifier(String::from("e")),
+                TokenKind::Minus,
+                TokenKind::Integer(1),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
     #[test]
     fn test_bitwise_operators() {
         assert_eq!(
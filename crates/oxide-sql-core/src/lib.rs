@@ -11,7 +11,7 @@
 //!
 //! ## Defining Tables with `#[derive(Table)]`
 //!
-//! The `#[derive(Table)]` macro (from [`oxide-sql-derive`]) turns a plain
+//! The `#[derive(Table)]` macro (from `oxide-sql-derive`) turns a plain
 //! struct into a full schema definition with compile-time checked column
 //! names, types, and metadata.
 //!
@@ -313,8 +313,9 @@
 //!
 //! The migrations module provides a Django-like system for evolving
 //! database schemas. Each migration is a struct implementing the
-//! [`Migration`] trait with `up()` (apply) and `down()` (rollback)
-//! methods that return a list of [`Operation`]s.
+//! [`Migration`](migrations::Migration) trait with `up()` (apply) and
+//! `down()` (rollback) methods that return a list of
+//! [`Operation`](migrations::Operation)s.
 //!
 //! ### Defining a migration
 //!
@@ -387,8 +388,8 @@
 //!
 //! ### Operations
 //!
-//! [`Operation`] covers all DDL changes. Beyond `CreateTable`, the most
-//! common factory methods are:
+//! [`Operation`](migrations::Operation) covers all DDL changes. Beyond
+//! `CreateTable`, the most common factory methods are:
 //!
 //! ```rust
 //! use oxide_sql_core::migrations::{Operation, varchar};
@@ -507,8 +508,11 @@
 //! // 2. Register all migrations
 //! runner.register::<Mig0001>();
 //!
-//! // 3. Validate dependencies (detects cycles / missing deps)
-//! runner.validate().expect("dependency graph is valid");
+//! // 3. Validate: checks dependencies (cycles / missing deps) and
+//! //    simulates every `up()` on an empty schema. If the first migration
+//! //    alters tables that already exist, use `validate_against()` with a
+//! //    `SchemaSnapshot` of that schema instead.
+//! runner.validate().expect("migrations are valid");
 //!
 //! // 4. Build state from the database (here: empty = fresh DB)
 //! let state = MigrationState::new();
@@ -632,6 +636,7 @@
 //!
 //! // Simulate "current" DB state: articles without the category column.
 //! let current = TableSnapshot {
+//!     schema: None,
 //!     name: "articles".to_string(),
 //!     columns: vec![
 //!         oxide_sql_core::migrations::ColumnSnapshot {
@@ -642,6 +647,8 @@
 //!             unique: false,
 //!             autoincrement: true,
 //!             default: None,
+//!             collation: None,
+//!             generated: None,
 //!         },
 //!         oxide_sql_core::migrations::ColumnSnapshot {
 //!             name: "title".into(),
@@ -651,6 +658,8 @@
 //!             unique: false,
 //!             autoincrement: false,
 //!             default: None,
+//!             collation: None,
+//!             generated: None,
 //!         },
 //!         oxide_sql_core::migrations::ColumnSnapshot {
 //!             name: "body".into(),
@@ -660,6 +669,8 @@
 //!             unique: false,
 //!             autoincrement: false,
 //!             default: None,
+//!             collation: None,
+//!             generated: None,
 //!         },
 //!         oxide_sql_core::migrations::ColumnSnapshot {
 //!             name: "published".into(),
@@ -671,10 +682,13 @@
 //!             default: Some(oxide_sql_core::migrations::DefaultValue::Expression(
 //!                 "FALSE".into(),
 //!             )),
+//!             collation: None,
+//!             generated: None,
 //!         },
 //!     ],
 //!     indexes: vec![],
 //!     foreign_keys: vec![],
+//!     options: vec![],
 //! };
 //!
 //! let diff = auto_diff_table::<ArticleV2Table>(&current, &dialect);
@@ -704,6 +718,7 @@
 //!
 //! // "current" has a "users" table; "desired" adds a "posts" table.
 //! current.add_table(TableSnapshot {
+//!     schema: None,
 //!     name: "users".into(),
 //!     columns: vec![ColumnSnapshot {
 //!         name: "id".into(),
@@ -713,11 +728,15 @@
 //!         unique: false,
 //!         autoincrement: true,
 //!         default: None,
+//!         collation: None,
+//!         generated: None,
 //!     }],
 //!     indexes: vec![],
 //!     foreign_keys: vec![],
+//!     options: vec![],
 //! });
 //! desired.add_table(TableSnapshot {
+//!     schema: None,
 //!     name: "users".into(),
 //!     columns: vec![ColumnSnapshot {
 //!         name: "id".into(),
@@ -727,11 +746,15 @@
 //!         unique: false,
 //!         autoincrement: true,
 //!         default: None,
+//!         collation: None,
+//!         generated: None,
 //!     }],
 //!     indexes: vec![],
 //!     foreign_keys: vec![],
+//!     options: vec![],
 //! });
 //! desired.add_table(TableSnapshot {
+//!     schema: None,
 //!     name: "posts".into(),
 //!     columns: vec![ColumnSnapshot {
 //!         name: "id".into(),
@@ -741,9 +764,12 @@
 //!         unique: false,
 //!         autoincrement: true,
 //!         default: None,
+//!         collation: None,
+//!         generated: None,
 //!     }],
 //!     indexes: vec![],
 //!     foreign_keys: vec![],
+//!     options: vec![],
 //! });
 //!
 //! let diff = auto_diff_schema(&current, &desired);
@@ -786,6 +812,7 @@
 //!
 //! let mut current = SchemaSnapshot::new();
 //! current.add_table(TableSnapshot {
+//!     schema: None,
 //!     name: "users".into(),
 //!     columns: vec![ColumnSnapshot {
 //!         name: "id".into(),
@@ -795,14 +822,18 @@
 //!         unique: false,
 //!         autoincrement: false,
 //!         default: None,
+//!         collation: None,
+//!         generated: None,
 //!     }],
 //!     indexes: vec![],
 //!     foreign_keys: vec![],
+//!     options: vec![],
 //! });
 //!
 //! // "desired" has the same structure but the table is named "accounts".
 //! let mut desired = SchemaSnapshot::new();
 //! desired.add_table(TableSnapshot {
+//!     schema: None,
 //!     name: "accounts".into(),
 //!     columns: vec![ColumnSnapshot {
 //!         name: "id".into(),
@@ -812,9 +843,12 @@
 //!         unique: false,
 //!         autoincrement: false,
 //!         default: None,
+//!         collation: None,
+//!         generated: None,
 //!     }],
 //!     indexes: vec![],
 //!     foreign_keys: vec![],
+//!     options: vec![],
 //! });
 //!
 //! let diff = auto_diff_schema(&current, &desired);
@@ -894,15 +928,16 @@ pub mod schema;
 
 pub use ast::{Expr, Statement};
 pub use builder::{
-    Delete, DeleteDyn, Insert, InsertDyn, Select, SelectDyn, Update, UpdateDyn, col, dyn_col,
+    Delete, DeleteDyn, Insert, InsertDyn, Query, Select, SelectDyn, Update, UpdateDyn, col, dyn_col,
 };
 pub use lexer::{Lexer, Token, TokenKind};
 pub use migrations::{
     AmbiguousChange, ColumnSnapshot, DiffWarning, ForeignKeySnapshot, IndexSnapshot, Introspect,
-    SchemaDiff, SchemaSnapshot, TableSnapshot, auto_diff_schema, auto_diff_table,
-    generate_migration_code,
+    SchemaDiff, SchemaSnapshot, TableSnapshot, auto_diff_schema, auto_diff_schema_for_dialect,
+    auto_diff_table, generate_migration_code, initial_from_tables,
 };
 pub use parser::{ParseError, Parser};
 pub use schema::{
-    Column, ColumnSchema, RustTypeMapping, Selectable, Table, TableSchema, TypedColumn,
+    Column, ColumnSchema, DecodeRow, GeneratedSchema, IndexSchema, ReferenceSchema,
+    RustTypeMapping, Selectable, Table, TableSchema, TypedColumn,
 };
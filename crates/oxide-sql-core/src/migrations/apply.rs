@@ -0,0 +1,687 @@
+//! Checked application of operations onto a schema snapshot.
+//!
+//! [`Operation::apply_to`] replays an operation against a
+//! [`SchemaSnapshot`] the way the database would, so logically invalid
+//! migrations (dropping a column that does not exist, creating a table
+//! twice) are caught before any SQL runs.
+
+use super::column_builder::ColumnDefinition;
+use super::operation::{AlterColumnChange, IndexColumn, Operation, TableConstraint};
+use super::snapshot::{
+    ColumnSnapshot, ForeignKeySnapshot, IndexSnapshot, SchemaSnapshot, TableSnapshot,
+};
+
+/// Why an operation cannot be applied to a schema.
+///
+/// Table names are qualified with their schema, e.g. `billing.invoices`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyError {
+    /// The table does not exist.
+    UnknownTable(String),
+    /// A table with this name already exists.
+    TableExists(String),
+    /// The column does not exist in the table.
+    UnknownColumn {
+        /// Table name.
+        table: String,
+        /// Column name.
+        column: String,
+    },
+    /// A column with this name already exists in the table.
+    ColumnExists {
+        /// Table name.
+        table: String,
+        /// Column name.
+        column: String,
+    },
+    /// The index does not exist.
+    UnknownIndex(String),
+    /// An index with this name already exists.
+    IndexExists(String),
+    /// The foreign key constraint does not exist on the table.
+    UnknownForeignKey {
+        /// Table name.
+        table: String,
+        /// Constraint name.
+        name: String,
+    },
+    /// The schema still holds tables and the drop is not `CASCADE`.
+    SchemaNotEmpty(String),
+}
+
+impl std::fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownTable(table) => write!(f, "Table '{}' does not exist", table),
+            Self::TableExists(table) => write!(f, "Table '{}' already exists", table),
+            Self::UnknownColumn { table, column } => {
+                write!(f, "Column '{}' does not exist in '{}'", column, table)
+            }
+            Self::ColumnExists { table, column } => {
+                write!(f, "Column '{}' already exists in '{}'", column, table)
+            }
+            Self::UnknownIndex(name) => write!(f, "Index '{}' does not exist", name),
+            Self::IndexExists(name) => write!(f, "Index '{}' already exists", name),
+            Self::UnknownForeignKey { table, name } => {
+                write!(f, "Foreign key '{}' does not exist on '{}'", name, table)
+            }
+            Self::SchemaNotEmpty(schema) => write!(f, "Schema '{}' is not empty", schema),
+        }
+    }
+}
+
+impl std::error::Error for ApplyError {}
+
+impl Operation {
+    /// Applies the operation to `schema` as the database would,
+    /// checking that everything it touches exists (or, when created,
+    /// does not exist yet).
+    ///
+    /// Triggers, sequences, and raw SQL do not change the tables of a
+    /// snapshot and always succeed. On error, `schema` is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ApplyError`] describing why the operation would
+    /// fail.
+    pub fn apply_to(&self, schema: &mut SchemaSnapshot) -> Result<(), ApplyError> {
+        match self {
+            Self::CreateTable(op) => {
+                let key = qualify(op.schema.as_deref(), &op.name);
+                if schema.tables.contains_key(&key) {
+                    return if op.if_not_exists {
+                        Ok(())
+                    } else {
+                        Err(ApplyError::TableExists(key))
+                    };
+                }
+                let mut table = TableSnapshot {
+                    schema: op.schema.clone(),
+                    name: op.name.clone(),
+                    columns: Vec::with_capacity(op.columns.len()),
+                    indexes: vec![],
+                    foreign_keys: vec![],
+                    options: op.options.clone(),
+                };
+                for column in &op.columns {
+                    if table.column(&column.name).is_some() {
+                        return Err(ApplyError::ColumnExists {
+                            table: key,
+                            column: column.name.clone(),
+                        });
+                    }
+                    table.columns.push(column_snapshot(column));
+                    table.foreign_keys.extend(column_foreign_key(column));
+                }
+                for constraint in &op.constraints {
+                    if let TableConstraint::ForeignKey {
+                        name,
+                        columns,
+                        references_table,
+                        references_columns,
+                        on_delete,
+                        on_update,
+                    } = constraint
+                    {
+                        check_columns(&table, &key, columns)?;
+                        table.foreign_keys.push(ForeignKeySnapshot {
+                            name: name.clone(),
+                            columns: columns.clone(),
+                            references_table: references_table.clone(),
+                            references_columns: references_columns.clone(),
+                            on_delete: *on_delete,
+                            on_update: *on_update,
+                        });
+                    }
+                }
+                schema.tables.insert(key, table);
+                Ok(())
+            }
+            Self::CreatePartition(op) => {
+                let parent_key = qualify(op.schema.as_deref(), &op.parent);
+                let key = qualify(op.schema.as_deref(), &op.name);
+                let parent = schema
+                    .tables
+                    .get(&parent_key)
+                    .ok_or(ApplyError::UnknownTable(parent_key))?;
+                if schema.tables.contains_key(&key) {
+                    return if op.if_not_exists {
+                        Ok(())
+                    } else {
+                        Err(ApplyError::TableExists(key))
+                    };
+                }
+                let partition = TableSnapshot {
+                    schema: op.schema.clone(),
+                    name: op.name.clone(),
+                    columns: parent.columns.clone(),
+                    indexes: vec![],
+                    foreign_keys: vec![],
+                    options: vec![],
+                };
+                schema.tables.insert(key, partition);
+                Ok(())
+            }
+            Self::DropTable(op) => {
+                let key = qualify(op.schema.as_deref(), &op.name);
+                if schema.tables.remove(&key).is_none() && !op.if_exists {
+                    return Err(ApplyError::UnknownTable(key));
+                }
+                Ok(())
+            }
+            Self::RenameTable(op) => {
+                let old_key = qualify(op.schema.as_deref(), &op.old_name);
+                let new_key = qualify(op.schema.as_deref(), &op.new_name);
+                if !schema.tables.contains_key(&old_key) {
+                    return Err(ApplyError::UnknownTable(old_key));
+                }
+                if schema.tables.contains_key(&new_key) {
+                    return Err(ApplyError::TableExists(new_key));
+                }
+                let mut table = schema.tables.remove(&old_key).expect("checked above");
+                table.name.clone_from(&op.new_name);
+                schema.tables.insert(new_key.clone(), table);
+                // Foreign keys follow the table they reference
+                for table in schema.tables.values_mut() {
+                    let in_schema = table.schema.clone();
+                    for fk in &mut table.foreign_keys {
+                        if references(in_schema.as_deref(), fk, &old_key) {
+                            fk.references_table = if fk.references_table == old_key {
+                                new_key.clone()
+                            } else {
+                                op.new_name.clone()
+                            };
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Self::AddColumn(op) => {
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                if table.column(&op.column.name).is_some() {
+                    return Err(ApplyError::ColumnExists {
+                        table: key,
+                        column: op.column.name.clone(),
+                    });
+                }
+                table.columns.push(column_snapshot(&op.column));
+                table.foreign_keys.extend(column_foreign_key(&op.column));
+                Ok(())
+            }
+            Self::DropColumn(op) => {
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                let at = column_position(table, &key, &op.column)?;
+                table.columns.remove(at);
+                // Indexes and foreign keys on the column go with it
+                let column = IndexColumn::Column(op.column.clone());
+                table.indexes.retain(|i| !i.columns.contains(&column));
+                table
+                    .foreign_keys
+                    .retain(|fk| !fk.columns.contains(&op.column));
+                for table in schema.tables.values_mut() {
+                    let in_schema = table.schema.clone();
+                    table.foreign_keys.retain(|fk| {
+                        !(references(in_schema.as_deref(), fk, &key)
+                            && fk.references_columns.contains(&op.column))
+                    });
+                }
+                Ok(())
+            }
+            Self::RenameColumn(op) => {
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                let at = column_position(table, &key, &op.old_name)?;
+                if table.column(&op.new_name).is_some() {
+                    return Err(ApplyError::ColumnExists {
+                        table: key,
+                        column: op.new_name.clone(),
+                    });
+                }
+                table.columns[at].name.clone_from(&op.new_name);
+                for index in &mut table.indexes {
+                    for column in &mut index.columns {
+                        if let IndexColumn::Column(name) = column
+                            && *name == op.old_name
+                        {
+                            name.clone_from(&op.new_name);
+                        }
+                    }
+                }
+                for fk in &mut table.foreign_keys {
+                    rename(&mut fk.columns, &op.old_name, &op.new_name);
+                }
+                for table in schema.tables.values_mut() {
+                    let in_schema = table.schema.clone();
+                    for fk in &mut table.foreign_keys {
+                        if references(in_schema.as_deref(), fk, &key) {
+                            rename(&mut fk.references_columns, &op.old_name, &op.new_name);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Self::AlterColumn(op) => {
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                let at = column_position(table, &key, &op.column)?;
+                let column = &mut table.columns[at];
+                match &op.change {
+                    AlterColumnChange::SetDataType(data_type) => {
+                        column.data_type = data_type.clone();
+                    }
+                    AlterColumnChange::SetNullable(nullable) => column.nullable = *nullable,
+                    AlterColumnChange::SetDefault(default) => {
+                        column.default = Some(default.clone());
+                    }
+                    AlterColumnChange::DropDefault => column.default = None,
+                    AlterColumnChange::SetUnique(unique) => column.unique = *unique,
+                    AlterColumnChange::SetAutoincrement(autoincrement) => {
+                        column.autoincrement = *autoincrement;
+                    }
+                    AlterColumnChange::SetCollation {
+                        data_type,
+                        collation,
+                    } => {
+                        column.data_type = data_type.clone();
+                        column.collation.clone_from(collation);
+                    }
+                }
+                Ok(())
+            }
+            Self::CreateIndex(op) => {
+                let exists = schema
+                    .tables
+                    .values()
+                    .filter(|t| t.schema == op.schema)
+                    .any(|t| t.indexes.iter().any(|i| i.name == op.name));
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                if exists {
+                    return if op.if_not_exists {
+                        Ok(())
+                    } else {
+                        Err(ApplyError::IndexExists(op.name.clone()))
+                    };
+                }
+                for column in &op.columns {
+                    if let IndexColumn::Column(name) = column {
+                        column_position(table, &key, name)?;
+                    }
+                }
+                table.indexes.push(IndexSnapshot {
+                    name: op.name.clone(),
+                    columns: op.columns.clone(),
+                    unique: op.unique,
+                    index_type: op.index_type,
+                    condition: op.condition.clone(),
+                });
+                Ok(())
+            }
+            Self::DropIndex(op) => {
+                let table = schema.tables.values_mut().find(|t| {
+                    t.schema == op.schema
+                        && op.table.as_ref().is_none_or(|name| *name == t.name)
+                        && t.indexes.iter().any(|i| i.name == op.name)
+                });
+                match table {
+                    Some(table) => table.indexes.retain(|i| i.name != op.name),
+                    None if op.if_exists => {}
+                    None => return Err(ApplyError::UnknownIndex(op.name.clone())),
+                }
+                Ok(())
+            }
+            Self::AddForeignKey(op) => {
+                let references = qualify(op.schema.as_deref(), &op.references_table);
+                let referenced = schema
+                    .tables
+                    .get(&references)
+                    .ok_or(ApplyError::UnknownTable(references.clone()))?;
+                check_columns(referenced, &references, &op.references_columns)?;
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                check_columns(table, &key, &op.columns)?;
+                table.foreign_keys.push(ForeignKeySnapshot {
+                    name: op.name.clone(),
+                    columns: op.columns.clone(),
+                    references_table: op.references_table.clone(),
+                    references_columns: op.references_columns.clone(),
+                    on_delete: op.on_delete,
+                    on_update: op.on_update,
+                });
+                Ok(())
+            }
+            Self::DropForeignKey(op) => {
+                let (key, table) = table_mut(schema, op.schema.as_deref(), &op.table)?;
+                let before = table.foreign_keys.len();
+                table
+                    .foreign_keys
+                    .retain(|fk| fk.name.as_deref() != Some(op.name.as_str()));
+                if table.foreign_keys.len() == before {
+                    return Err(ApplyError::UnknownForeignKey {
+                        table: key,
+                        name: op.name.clone(),
+                    });
+                }
+                Ok(())
+            }
+            Self::DropSchema(op) => {
+                let in_schema = |t: &TableSnapshot| t.schema.as_deref() == Some(op.name.as_str());
+                if !op.cascade && schema.tables.values().any(in_schema) {
+                    return Err(ApplyError::SchemaNotEmpty(op.name.clone()));
+                }
+                schema.tables.retain(|_, t| !in_schema(t));
+                Ok(())
+            }
+            Self::CreateSchema(_)
+            | Self::CreateTrigger(_)
+            | Self::DropTrigger(_)
+            | Self::CreateSequence(_)
+            | Self::AlterSequence(_)
+            | Self::DropSequence(_)
+            | Self::RunSql(_) => Ok(()),
+        }
+    }
+}
+
+/// Returns the snapshot key of a table.
+fn qualify(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{schema}.{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// Returns whether `fk`, declared on a table in `schema`, references
+/// the table stored under `key`.
+fn references(schema: Option<&str>, fk: &ForeignKeySnapshot, key: &str) -> bool {
+    fk.references_table == key || qualify(schema, &fk.references_table) == key
+}
+
+/// Renames `old` to `new` in a list of column names.
+fn rename(names: &mut [String], old: &str, new: &str) {
+    for name in names.iter_mut().filter(|name| *name == old) {
+        *name = new.to_string();
+    }
+}
+
+/// Looks up a table for modification, returning its key for errors.
+fn table_mut<'a>(
+    schema: &'a mut SchemaSnapshot,
+    table_schema: Option<&str>,
+    name: &str,
+) -> Result<(String, &'a mut TableSnapshot), ApplyError> {
+    let key = qualify(table_schema, name);
+    match schema.tables.get_mut(&key) {
+        Some(table) => Ok((key, table)),
+        None => Err(ApplyError::UnknownTable(key)),
+    }
+}
+
+/// Returns the index of `column` in `table`.
+fn column_position(table: &TableSnapshot, key: &str, column: &str) -> Result<usize, ApplyError> {
+    table
+        .columns
+        .iter()
+        .position(|c| c.name == column)
+        .ok_or_else(|| ApplyError::UnknownColumn {
+            table: key.to_string(),
+            column: column.to_string(),
+        })
+}
+
+/// Checks that every column in `columns` exists in `table`.
+fn check_columns(table: &TableSnapshot, key: &str, columns: &[String]) -> Result<(), ApplyError> {
+    for column in columns {
+        column_position(table, key, column)?;
+    }
+    Ok(())
+}
+
+/// Converts a column definition into its snapshot.
+fn column_snapshot(column: &ColumnDefinition) -> ColumnSnapshot {
+    ColumnSnapshot {
+        name: column.name.clone(),
+        data_type: column.data_type.clone(),
+        nullable: column.nullable && !column.primary_key,
+        primary_key: column.primary_key,
+        unique: column.unique,
+        autoincrement: column.autoincrement,
+        default: column.default.clone(),
+        collation: column.collation.clone(),
+        generated: column.generated.clone(),
+    }
+}
+
+/// Returns the unnamed foreign key declared inline on a column.
+fn column_foreign_key(column: &ColumnDefinition) -> Option<ForeignKeySnapshot> {
+    column.references.as_ref().map(|fk| ForeignKeySnapshot {
+        name: None,
+        columns: vec![column.name.clone()],
+        references_table: fk.table.clone(),
+        references_columns: vec![fk.column.clone()],
+        on_delete: fk.on_delete,
+        on_update: fk.on_update,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::DataType;
+    use crate::migrations::column_builder::{bigint, varchar};
+    use crate::migrations::table_builder::CreateTableBuilder;
+
+    fn users() -> Operation {
+        CreateTableBuilder::new()
+            .name("users")
+            .column(bigint("id").primary_key().build())
+            .column(varchar("email", 255).build())
+            .build()
+            .into()
+    }
+
+    #[test]
+    fn create_and_alter_table() {
+        let mut schema = SchemaSnapshot::new();
+        users().apply_to(&mut schema).unwrap();
+        Operation::add_column("users", varchar("name", 100).not_null().build())
+            .apply_to(&mut schema)
+            .unwrap();
+        Operation::rename_column("users", "email", "mail")
+            .apply_to(&mut schema)
+            .unwrap();
+
+        let table = &schema.tables["users"];
+        let names: Vec<_> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["id", "mail", "name"]);
+        assert_eq!(
+            table.column("name").unwrap().data_type,
+            DataType::Varchar(Some(100))
+        );
+        assert!(!table.column("id").unwrap().nullable);
+    }
+
+    #[test]
+    fn invalid_operations_are_rejected() {
+        let mut schema = SchemaSnapshot::new();
+        users().apply_to(&mut schema).unwrap();
+
+        assert_eq!(
+            users().apply_to(&mut schema),
+            Err(ApplyError::TableExists("users".to_string()))
+        );
+        assert_eq!(
+            Operation::drop_column("users", "missing").apply_to(&mut schema),
+            Err(ApplyError::UnknownColumn {
+                table: "users".to_string(),
+                column: "missing".to_string(),
+            })
+        );
+        assert_eq!(
+            Operation::add_column("users", varchar("email", 10).build()).apply_to(&mut schema),
+            Err(ApplyError::ColumnExists {
+                table: "users".to_string(),
+                column: "email".to_string(),
+            })
+        );
+        assert_eq!(
+            Operation::drop_table("posts")
+                .in_schema("blog")
+                .apply_to(&mut schema),
+            Err(ApplyError::UnknownTable("blog.posts".to_string()))
+        );
+        assert!(
+            Operation::drop_table_if_exists("posts")
+                .apply_to(&mut schema)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn indexes_and_schemas() {
+        let mut schema = SchemaSnapshot::new();
+        users().in_schema("auth").apply_to(&mut schema).unwrap();
+        let index = Operation::CreateIndex(crate::migrations::operation::CreateIndexOp {
+            schema: Some("auth".into()),
+            name: "idx_users_email".into(),
+            table: "users".into(),
+            columns: vec!["email".into()],
+            unique: true,
+            index_type: crate::migrations::operation::IndexType::BTree,
+            if_not_exists: false,
+            condition: None,
+        });
+        index.apply_to(&mut schema).unwrap();
+        assert_eq!(
+            index.apply_to(&mut schema),
+            Err(ApplyError::IndexExists("idx_users_email".to_string()))
+        );
+
+        assert_eq!(
+            Operation::drop_schema("auth").apply_to(&mut schema),
+            Err(ApplyError::SchemaNotEmpty("auth".to_string()))
+        );
+        Operation::drop_table("users")
+            .in_schema("auth")
+            .apply_to(&mut schema)
+            .unwrap();
+        assert!(schema.tables.is_empty());
+    }
+
+    fn posts() -> Operation {
+        CreateTableBuilder::new()
+            .name("posts")
+            .column(bigint("id").primary_key().build())
+            .column(bigint("author_id").references("users", "id").build())
+            .build()
+            .into()
+    }
+
+    fn email_index() -> Operation {
+        Operation::CreateIndex(crate::migrations::operation::CreateIndexOp {
+            schema: None,
+            name: "idx_users_email".into(),
+            table: "users".into(),
+            columns: vec!["email".into()],
+            unique: true,
+            index_type: crate::migrations::operation::IndexType::BTree,
+            if_not_exists: false,
+            condition: None,
+        })
+    }
+
+    #[test]
+    fn renames_carry_indexes_and_foreign_keys() {
+        let mut schema = SchemaSnapshot::new();
+        for op in [users(), posts(), email_index()] {
+            op.apply_to(&mut schema).unwrap();
+        }
+        Operation::rename_column("users", "email", "mail")
+            .apply_to(&mut schema)
+            .unwrap();
+        Operation::rename_column("users", "id", "user_id")
+            .apply_to(&mut schema)
+            .unwrap();
+        Operation::rename_table("users", "accounts")
+            .apply_to(&mut schema)
+            .unwrap();
+
+        let accounts = &schema.tables["accounts"];
+        assert_eq!(
+            accounts.indexes[0].columns,
+            [IndexColumn::Column("mail".into())]
+        );
+        let fk = &schema.tables["posts"].foreign_keys[0];
+        assert_eq!(fk.references_table, "accounts");
+        assert_eq!(fk.references_columns, ["user_id"]);
+
+        // The renamed index can still be dropped from the renamed table.
+        Operation::DropIndex(crate::migrations::operation::DropIndexOp {
+            schema: None,
+            name: "idx_users_email".into(),
+            table: Some("accounts".into()),
+            if_exists: false,
+        })
+        .apply_to(&mut schema)
+        .unwrap();
+        assert!(schema.tables["accounts"].indexes.is_empty());
+    }
+
+    #[test]
+    fn dropped_columns_take_their_indexes_and_foreign_keys() {
+        let mut schema = SchemaSnapshot::new();
+        for op in [users(), posts(), email_index()] {
+            op.apply_to(&mut schema).unwrap();
+        }
+        Operation::drop_column("users", "email")
+            .apply_to(&mut schema)
+            .unwrap();
+        assert!(schema.tables["users"].indexes.is_empty());
+        let drop_index = Operation::DropIndex(crate::migrations::operation::DropIndexOp {
+            schema: None,
+            name: "idx_users_email".into(),
+            table: None,
+            if_exists: false,
+        });
+        assert_eq!(
+            drop_index.apply_to(&mut schema),
+            Err(ApplyError::UnknownIndex("idx_users_email".to_string()))
+        );
+
+        // Dropping a referenced column drops the foreign keys to it.
+        Operation::drop_column("users", "id")
+            .apply_to(&mut schema)
+            .unwrap();
+        assert!(schema.tables["posts"].foreign_keys.is_empty());
+
+        let mut schema = SchemaSnapshot::new();
+        for op in [users(), posts()] {
+            op.apply_to(&mut schema).unwrap();
+        }
+        Operation::drop_column("posts", "author_id")
+            .apply_to(&mut schema)
+            .unwrap();
+        assert!(schema.tables["posts"].foreign_keys.is_empty());
+    }
+
+    #[test]
+    fn partitions_live_in_the_parent_schema() {
+        let mut schema = SchemaSnapshot::new();
+        users().in_schema("auth").apply_to(&mut schema).unwrap();
+        let partition = Operation::create_partition(
+            "users_eu",
+            "users",
+            crate::migrations::operation::PartitionBound::List(vec!["'eu'".into()]),
+        );
+
+        assert_eq!(
+            partition.clone().apply_to(&mut schema),
+            Err(ApplyError::UnknownTable("users".to_string()))
+        );
+        let partition = partition.in_schema("auth");
+        partition.apply_to(&mut schema).unwrap();
+        let table = &schema.tables["auth.users_eu"];
+        assert_eq!(table.schema.as_deref(), Some("auth"));
+        assert_eq!(table.columns.len(), 2);
+        assert_eq!(
+            partition.apply_to(&mut schema),
+            Err(ApplyError::TableExists("auth.users_eu".to_string()))
+        );
+    }
+}
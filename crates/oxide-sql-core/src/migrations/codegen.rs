@@ -1,59 +1,274 @@
 //! Migration code generation.
 //!
-//! Generates Rust source code implementing the [`Migration`] trait
-//! from a [`SchemaDiff`], enabling `makemigrations`-style tooling.
+//! Generates Rust source code implementing the
+//! [`Migration`](super::Migration) trait from a [`SchemaDiff`], enabling
+//! `makemigrations`-style tooling.
 
-use super::column_builder::DefaultValue;
+use super::column_builder::{DefaultValue, ForeignKeyAction};
 use super::diff::SchemaDiff;
-use super::operation::{AlterColumnChange, CreateTableOp, Operation};
+use super::operation::{
+    AlterColumnChange, CreateSequenceOp, CreateTableOp, IndexColumn, Operation, TableOption,
+};
 use crate::ast::DataType;
 
-/// Generates a Rust source string containing a `Migration` impl
-/// for the given diff.
+/// Metadata of a generated migration.
+///
+/// Converts from a bare ID, so `generate_migration_code("0002_add_email",
+/// &diff)` works when there is nothing else to say.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationMetadata {
+    /// The migration ID (e.g. `"0002_add_email"`).
+    pub id: String,
+    /// IDs of the migrations that must run first, usually just the
+    /// previous one.
+    pub dependencies: Vec<String>,
+    /// Description, emitted as the doc comment of the struct.
+    pub description: Option<String>,
+}
+
+impl MigrationMetadata {
+    /// Creates metadata for the migration `id` without dependencies.
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            dependencies: Vec::new(),
+            description: None,
+        }
+    }
+
+    /// Adds a migration this one depends on.
+    #[must_use]
+    pub fn depends_on(mut self, id: impl Into<String>) -> Self {
+        self.dependencies.push(id.into());
+        self
+    }
+
+    /// Sets the description.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl From<&str> for MigrationMetadata {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for MigrationMetadata {
+    fn from(id: String) -> Self {
+        Self::new(id)
+    }
+}
+
+/// Generates a Rust source file containing a `Migration` impl for
+/// the given diff.
 ///
 /// # Arguments
 ///
-/// * `id` — The migration ID (e.g. `"0002_add_email"`).
+/// * `metadata` — The migration ID, dependencies, and description;
+///   a bare ID converts into [`MigrationMetadata`].
 /// * `diff` — The schema diff to translate into code.
 ///
 /// # Returns
 ///
-/// A Rust source string that, when compiled, produces a struct
-/// implementing the `Migration` trait with `up()` and `down()`
-/// methods.
+/// Rust source, laid out as `rustfmt` would, that compiles to a struct
+/// implementing the `Migration` trait. Operations are put in a safe
+/// order (schemas and tables are created before they are used and
+/// dropped after everything in them), `down()` reverses `up()` with a
+/// `TODO` comment for every operation that cannot be reversed, and
+/// only the names the code uses are imported.
 #[must_use]
-pub fn generate_migration_code(id: &str, diff: &SchemaDiff) -> String {
-    let struct_name = id_to_struct_name(id);
-    let up_body = render_operations(&diff.operations);
-    let down_body = render_down(&diff.operations);
-
-    format!(
-        "use oxide_sql_core::migrations::{{\n\
-         \x20   Migration, Operation, CreateTableBuilder,\n\
-         \x20   bigint, varchar, text, integer, smallint,\n\
-         \x20   boolean, timestamp, datetime, date, time,\n\
-         \x20   real, double, decimal, numeric, blob, binary,\n\
-         \x20   varbinary, char,\n\
-         }};\n\
-         \n\
-         pub struct {struct_name};\n\
-         \n\
-         impl Migration for {struct_name} {{\n\
-         \x20   const ID: &'static str = \"{id}\";\n\
-         \n\
+pub fn generate_migration_code(
+    metadata: impl Into<MigrationMetadata>,
+    diff: &SchemaDiff,
+) -> String {
+    let metadata = metadata.into();
+    let struct_name = id_to_struct_name(&metadata.id);
+    let operations = safe_order(&diff.operations);
+    let up_body = render_operations(&operations);
+    let down_body = render_down(&operations);
+
+    let mut out = render_imports(&format!("{up_body}{down_body}"));
+    out.push('\n');
+    if let Some(ref description) = metadata.description {
+        for line in description.lines() {
+            out.push_str(format!("/// {line}").trim_end());
+            out.push('\n');
+        }
+    }
+    out.push_str(&format!(
+        "pub struct {struct_name};\n\nimpl Migration for {struct_name} {{\n"
+    ));
+    out.push_str(&format!(
+        "    const ID: &'static str = \"{}\";\n",
+        escape_str(&metadata.id)
+    ));
+    if !metadata.dependencies.is_empty() {
+        let deps: Vec<String> = metadata
+            .dependencies
+            .iter()
+            .map(|d| format!("\"{}\"", escape_str(d)))
+            .collect();
+        out.push_str(&format!(
+            "    const DEPENDENCIES: &'static [&'static str] = &[{}];\n",
+            deps.join(", ")
+        ));
+    }
+    out.push_str(&format!(
+        "\n\
          \x20   fn up() -> Vec<Operation> {{\n\
-         \x20       vec![\n\
-         {up_body}\
-         \x20       ]\n\
+         {}\
          \x20   }}\n\
          \n\
          \x20   fn down() -> Vec<Operation> {{\n\
-         \x20       vec![\n\
-         {down_body}\
-         \x20       ]\n\
+         {}\
          \x20   }}\n\
-         }}\n"
-    )
+         }}\n",
+        render_vec(&up_body),
+        render_vec(&down_body)
+    ));
+    out
+}
+
+/// Names the generated code may use, with the path they are
+/// imported from.
+const IMPORTS: &[(&str, &str)] = &[
+    ("DataType", "oxide_sql_core::ast"),
+    ("AlterColumnChange", "oxide_sql_core::migrations"),
+    ("AlterColumnOp", "oxide_sql_core::migrations"),
+    ("CreateIndexOp", "oxide_sql_core::migrations"),
+    ("CreateSchemaOp", "oxide_sql_core::migrations"),
+    ("CreateSequenceOp", "oxide_sql_core::migrations"),
+    ("CreateTableBuilder", "oxide_sql_core::migrations"),
+    ("DefaultValue", "oxide_sql_core::migrations"),
+    ("DropIndexOp", "oxide_sql_core::migrations"),
+    ("ForeignKeyAction", "oxide_sql_core::migrations"),
+    ("IndexColumn", "oxide_sql_core::migrations"),
+    ("IndexType", "oxide_sql_core::migrations"),
+    ("Migration", "oxide_sql_core::migrations"),
+    ("Operation", "oxide_sql_core::migrations"),
+    ("array", "oxide_sql_core::migrations"),
+    ("bigint", "oxide_sql_core::migrations"),
+    ("binary", "oxide_sql_core::migrations"),
+    ("blob", "oxide_sql_core::migrations"),
+    ("boolean", "oxide_sql_core::migrations"),
+    ("char", "oxide_sql_core::migrations"),
+    ("date", "oxide_sql_core::migrations"),
+    ("datetime", "oxide_sql_core::migrations"),
+    ("decimal", "oxide_sql_core::migrations"),
+    ("double", "oxide_sql_core::migrations"),
+    ("integer", "oxide_sql_core::migrations"),
+    ("numeric", "oxide_sql_core::migrations"),
+    ("real", "oxide_sql_core::migrations"),
+    ("smallint", "oxide_sql_core::migrations"),
+    ("text", "oxide_sql_core::migrations"),
+    ("time", "oxide_sql_core::migrations"),
+    ("timestamp", "oxide_sql_core::migrations"),
+    ("varbinary", "oxide_sql_core::migrations"),
+    ("varchar", "oxide_sql_core::migrations"),
+];
+
+/// Renders the `use` items for the names `body` refers to.
+fn render_imports(body: &str) -> String {
+    let mut out = String::new();
+    for path in ["oxide_sql_core::ast", "oxide_sql_core::migrations"] {
+        let names: Vec<&str> = IMPORTS
+            .iter()
+            .filter(|(name, from)| {
+                *from == path && (matches!(*name, "Migration" | "Operation") || uses(body, name))
+            })
+            .map(|(name, _)| *name)
+            .collect();
+        match names.as_slice() {
+            [] => {}
+            [name] => out.push_str(&format!("use {path}::{name};\n")),
+            _ => {
+                let line = format!("use {path}::{{{}}};", names.join(", "));
+                if line.len() <= 100 {
+                    out.push_str(&line);
+                    out.push('\n');
+                } else {
+                    out.push_str(&format!("use {path}::{{\n"));
+                    out.push_str(&wrap_list(&names, "    ", 100));
+                    out.push_str("};\n");
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Returns whether `code` refers to `name` unqualified, i.e. as a
+/// call (`name(`), a path (`name::`), or a struct literal
+/// (`name {`).
+fn uses(code: &str, name: &str) -> bool {
+    code.match_indices(name).any(|(at, _)| {
+        let before = code[..at].chars().next_back();
+        let after = &code[at + name.len()..];
+        !before.is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '"' | '.'))
+            && (after.starts_with('(') || after.starts_with("::") || after.starts_with(" {"))
+    })
+}
+
+/// Lays out `items` as comma-separated lines no wider than `width`.
+fn wrap_list(items: &[&str], indent: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut line = String::from(indent);
+    for item in items {
+        if line.len() > indent.len() && line.len() + item.len() + 2 > width {
+            out.push_str(line.trim_end());
+            out.push('\n');
+            line = String::from(indent);
+        }
+        line.push_str(item);
+        line.push_str(", ");
+    }
+    out.push_str(line.trim_end());
+    out.push('\n');
+    out
+}
+
+/// Renders the body of `up()`/`down()`: a `vec![..]` of the rendered
+/// operations, or `Vec::new()` when there are none.
+fn render_vec(items: &str) -> String {
+    if items.is_empty() {
+        "        Vec::new()\n".to_string()
+    } else {
+        format!("        vec![\n{items}        ]\n")
+    }
+}
+
+/// Returns the operations in an order that is safe to run: schemas,
+/// sequences, and tables first, then column and index changes, and
+/// drops last, innermost objects before the ones containing them.
+/// The sort is stable, so operations of the same kind keep the order
+/// they were given in.
+fn safe_order(ops: &[Operation]) -> Vec<Operation> {
+    let phase = |op: &Operation| match op {
+        Operation::CreateSchema(_) => 0,
+        Operation::CreateSequence(_) | Operation::AlterSequence(_) => 1,
+        Operation::CreateTable(_) | Operation::CreatePartition(_) | Operation::RenameTable(_) => 2,
+        Operation::AddColumn(_) | Operation::RenameColumn(_) => 3,
+        Operation::AlterColumn(_) => 4,
+        Operation::CreateIndex(_)
+        | Operation::DropIndex(_)
+        | Operation::AddForeignKey(_)
+        | Operation::DropForeignKey(_)
+        | Operation::CreateTrigger(_)
+        | Operation::DropTrigger(_)
+        | Operation::RunSql(_) => 5,
+        Operation::DropColumn(_) => 6,
+        Operation::DropTable(_) => 7,
+        Operation::DropSequence(_) => 8,
+        Operation::DropSchema(_) => 9,
+    };
+    let mut ordered = ops.to_vec();
+    ordered.sort_by_key(phase);
+    ordered
 }
 
 // ================================================================
@@ -146,6 +361,30 @@ fn op_summary(op: &Operation) -> String {
         Operation::DropForeignKey(fk) => {
             format!("DropForeignKey({}.{})", fk.table, fk.name)
         }
+        Operation::CreateTrigger(ct) => {
+            format!("CreateTrigger({} on {})", ct.name, ct.table)
+        }
+        Operation::DropTrigger(dt) => {
+            format!("DropTrigger({} on {})", dt.name, dt.table)
+        }
+        Operation::CreateSequence(cs) => {
+            format!("CreateSequence({})", cs.name)
+        }
+        Operation::AlterSequence(asq) => {
+            format!("AlterSequence({})", asq.name)
+        }
+        Operation::DropSequence(ds) => {
+            format!("DropSequence({})", ds.name)
+        }
+        Operation::CreatePartition(cp) => {
+            format!("CreatePartition({} of {})", cp.name, cp.parent)
+        }
+        Operation::CreateSchema(cs) => {
+            format!("CreateSchema({})", cs.name)
+        }
+        Operation::DropSchema(ds) => {
+            format!("DropSchema({})", ds.name)
+        }
         Operation::RunSql(_) => "RunSql(...)".to_string(),
     }
 }
@@ -155,65 +394,144 @@ fn render_operation(op: &Operation) -> String {
     match op {
         Operation::CreateTable(ct) => render_create_table(ct),
         Operation::DropTable(dt) => {
-            format!("Operation::drop_table(\"{}\")", dt.name)
+            format!(
+                "Operation::drop_table(\"{}\"){}",
+                dt.name,
+                render_in_schema(dt.schema.as_deref())
+            )
         }
         Operation::RenameTable(rt) => {
             format!(
-                "Operation::rename_table(\"{}\", \"{}\")",
-                rt.old_name, rt.new_name
+                "Operation::rename_table(\"{}\", \"{}\"){}",
+                rt.old_name,
+                rt.new_name,
+                render_in_schema(rt.schema.as_deref())
             )
         }
         Operation::AddColumn(ac) => {
             format!(
-                "Operation::add_column(\"{}\", {})",
+                "Operation::add_column(\"{}\", {}){}",
                 ac.table,
-                render_column_builder(&ac.column.name, &ac.column)
+                render_column_builder(&ac.column.name, &ac.column),
+                render_in_schema(ac.schema.as_deref())
             )
         }
         Operation::DropColumn(dc) => {
             format!(
-                "Operation::drop_column(\"{}\", \"{}\")",
-                dc.table, dc.column
+                "Operation::drop_column(\"{}\", \"{}\"){}",
+                dc.table,
+                dc.column,
+                render_in_schema(dc.schema.as_deref())
             )
         }
         Operation::RenameColumn(rc) => {
             format!(
-                "Operation::rename_column(\"{}\", \"{}\", \"{}\")",
-                rc.table, rc.old_name, rc.new_name
+                "Operation::rename_column(\"{}\", \"{}\", \"{}\"){}",
+                rc.table,
+                rc.old_name,
+                rc.new_name,
+                render_in_schema(rc.schema.as_deref())
             )
         }
         Operation::AlterColumn(ac) => render_alter_column(ac),
         Operation::CreateIndex(ci) => {
+            let columns: Vec<String> = ci
+                .columns
+                .iter()
+                .map(|c| match c {
+                    IndexColumn::Column(name) => format!("\"{name}\".into()"),
+                    IndexColumn::Expr(expr) => format!(
+                        "IndexColumn::parse(\"{}\").unwrap()",
+                        escape_str(&expr.to_string())
+                    ),
+                })
+                .collect();
+            let condition = ci.condition.as_ref().map_or_else(
+                || "None".to_string(),
+                |cond| {
+                    format!(
+                        "Some(oxide_sql_core::Parser::new(\"{}\").parse_expr().unwrap())",
+                        escape_str(&cond.to_string())
+                    )
+                },
+            );
+            render_struct(
+                "Operation::CreateIndex(CreateIndexOp",
+                &[
+                    ("schema", render_schema(ci.schema.as_deref())),
+                    ("name", format!("\"{}\".into()", ci.name)),
+                    ("table", format!("\"{}\".into()", ci.table)),
+                    ("columns", format!("vec![{}]", columns.join(", "))),
+                    ("unique", ci.unique.to_string()),
+                    ("index_type", format!("IndexType::{:?}", ci.index_type)),
+                    ("if_not_exists", ci.if_not_exists.to_string()),
+                    ("condition", condition),
+                ],
+            )
+        }
+        Operation::DropIndex(di) => render_struct(
+            "Operation::DropIndex(DropIndexOp",
+            &[
+                ("schema", render_schema(di.schema.as_deref())),
+                ("name", format!("\"{}\".into()", di.name)),
+                (
+                    "table",
+                    di.table
+                        .as_ref()
+                        .map_or_else(|| "None".to_string(), |t| format!("Some(\"{t}\".into())")),
+                ),
+                ("if_exists", di.if_exists.to_string()),
+            ],
+        ),
+        Operation::AddForeignKey(_) | Operation::DropForeignKey(_) => {
+            format!("// TODO: manually write FK operation: {:?}", op_summary(op))
+        }
+        Operation::CreateTrigger(_) => {
             format!(
-                "Operation::CreateIndex(CreateIndexOp {{ \
-                 name: \"{}\".into(), \
-                 table: \"{}\".into(), \
-                 columns: vec![{}], \
-                 unique: {}, \
-                 index_type: IndexType::BTree, \
-                 if_not_exists: false, \
-                 condition: None \
-                 }})",
-                ci.name,
-                ci.table,
-                ci.columns
-                    .iter()
-                    .map(|c| format!("\"{c}\".into()"))
-                    .collect::<Vec<_>>()
-                    .join(", "),
-                ci.unique,
+                "// TODO: manually write trigger operation: {:?}",
+                op_summary(op)
             )
         }
-        Operation::DropIndex(di) => {
+        Operation::DropTrigger(dt) => {
             format!(
-                "Operation::DropIndex(DropIndexOp {{ \
-                 name: \"{}\".into(), table: None, \
-                 if_exists: false }})",
-                di.name
+                "Operation::drop_trigger(\"{}\", \"{}\"){}",
+                dt.name,
+                dt.table,
+                render_in_schema(dt.schema.as_deref())
             )
         }
-        Operation::AddForeignKey(_) | Operation::DropForeignKey(_) => {
-            format!("// TODO: manually write FK operation: {:?}", op_summary(op))
+        Operation::CreateSequence(cs) => render_create_sequence(cs),
+        Operation::AlterSequence(_) => {
+            format!(
+                "// TODO: manually write sequence operation: {:?}",
+                op_summary(op)
+            )
+        }
+        Operation::DropSequence(ds) => {
+            format!(
+                "Operation::drop_sequence(\"{}\"){}",
+                ds.name,
+                render_in_schema(ds.schema.as_deref())
+            )
+        }
+        Operation::CreatePartition(_) => {
+            format!(
+                "// TODO: manually write partition operation: {:?}",
+                op_summary(op)
+            )
+        }
+        Operation::CreateSchema(cs) if cs.if_not_exists => render_struct(
+            "Operation::CreateSchema(CreateSchemaOp",
+            &[
+                ("name", format!("\"{}\".into()", cs.name)),
+                ("if_not_exists", "true".to_string()),
+            ],
+        ),
+        Operation::CreateSchema(cs) => {
+            format!("Operation::create_schema(\"{}\")", cs.name)
+        }
+        Operation::DropSchema(ds) => {
+            format!("Operation::drop_schema(\"{}\")", ds.name)
         }
         Operation::RunSql(rs) => {
             if let Some(ref down) = rs.down_sql {
@@ -232,6 +550,9 @@ fn render_operation(op: &Operation) -> String {
 /// Renders a `CreateTableBuilder` chain.
 fn render_create_table(ct: &CreateTableOp) -> String {
     let mut s = String::from("CreateTableBuilder::new()\n");
+    if let Some(ref schema) = ct.schema {
+        s.push_str(&format!("                .schema(\"{schema}\")\n"));
+    }
     s.push_str(&format!("                .name(\"{}\")\n", ct.name));
     for col in &ct.columns {
         s.push_str(&format!(
@@ -242,11 +563,82 @@ fn render_create_table(ct: &CreateTableOp) -> String {
     if ct.if_not_exists {
         s.push_str("                .if_not_exists()\n");
     }
+    if let Some(ref partition) = ct.partition_by {
+        s.push_str(&format!(
+            "                .partition_by(oxide_sql_core::migrations::PartitionStrategy::{:?}, &[{}])\n",
+            partition.strategy,
+            partition
+                .columns
+                .iter()
+                .map(|c| format!("\"{c}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    for option in &ct.options {
+        s.push_str(&format!(
+            "                .option(oxide_sql_core::migrations::TableOption::{})\n",
+            render_table_option(option)
+        ));
+    }
     s.push_str("                .build()\n");
     s.push_str("                .into()");
     s
 }
 
+/// Renders `head { field: value, .. })` as a multi-line struct
+/// literal wrapped in a variant, indented for the `vec![..]` in
+/// `up()`/`down()`.
+fn render_struct(head: &str, fields: &[(&str, String)]) -> String {
+    let mut s = format!("{head} {{\n");
+    for (name, value) in fields {
+        s.push_str(&format!("                {name}: {value},\n"));
+    }
+    s.push_str("            })");
+    s
+}
+
+/// Renders an optional schema as an `Option<String>` expression.
+fn render_schema(schema: Option<&str>) -> String {
+    schema.map_or_else(|| "None".to_string(), |s| format!("Some(\"{s}\".into())"))
+}
+
+/// Renders the `.in_schema(..)` call for an operation outside the
+/// default schema, or nothing.
+fn render_in_schema(schema: Option<&str>) -> String {
+    schema.map_or_else(String::new, |s| format!(".in_schema(\"{s}\")"))
+}
+
+/// Renders a `TableOption` variant (without the enum path).
+fn render_table_option(option: &TableOption) -> String {
+    match option {
+        TableOption::Tablespace(name) => format!("Tablespace(\"{}\".into())", escape_str(name)),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Renders a `CreateSequenceOp` chain.
+fn render_create_sequence(cs: &CreateSequenceOp) -> String {
+    let mut s = format!("CreateSequenceOp::new(\"{}\")", cs.name);
+    if let Some(ref schema) = cs.schema {
+        s.push_str(&format!(".schema(\"{schema}\")"));
+    }
+    if let Some(start) = cs.start {
+        s.push_str(&format!(".start({start})"));
+    }
+    if let Some(increment) = cs.increment {
+        s.push_str(&format!(".increment({increment})"));
+    }
+    if cs.cycle {
+        s.push_str(".cycle()");
+    }
+    if cs.if_not_exists {
+        s.push_str(".if_not_exists()");
+    }
+    s.push_str(".into()");
+    s
+}
+
 /// Renders a column builder expression.
 fn render_column_builder(_name: &str, col: &super::column_builder::ColumnDefinition) -> String {
     let type_fn = match &col.data_type {
@@ -307,6 +699,12 @@ fn render_column_builder(_name: &str, col: &super::column_builder::ColumnDefinit
         DataType::Char(Some(len)) => {
             format!("char(\"{}\", {len})", col.name)
         }
+        DataType::Array(element) => {
+            format!(
+                "array(\"{}\", oxide_sql_core::ast::DataType::{element:?})",
+                col.name
+            )
+        }
         _ => format!("text(\"{}\")", col.name),
     };
 
@@ -323,6 +721,28 @@ fn render_column_builder(_name: &str, col: &super::column_builder::ColumnDefinit
     if col.unique {
         chain.push_str(".unique()");
     }
+    if let Some(ref collation) = col.collation {
+        chain.push_str(&format!(".collation(\"{}\")", escape_str(collation)));
+    }
+    if let Some(ref fk) = col.references {
+        if fk.on_delete.is_none() && fk.on_update.is_none() {
+            chain.push_str(&format!(".references(\"{}\", \"{}\")", fk.table, fk.column));
+        } else {
+            let action = |action: Option<ForeignKeyAction>| {
+                action.map_or_else(
+                    || "None".to_string(),
+                    |a| format!("Some(ForeignKeyAction::{a:?})"),
+                )
+            };
+            chain.push_str(&format!(
+                ".references_full(\"{}\", \"{}\", {}, {})",
+                fk.table,
+                fk.column,
+                action(fk.on_delete),
+                action(fk.on_update)
+            ));
+        }
+    }
     if let Some(ref default) = col.default {
         match default {
             DefaultValue::Boolean(b) => {
@@ -345,6 +765,17 @@ fn render_column_builder(_name: &str, col: &super::column_builder::ColumnDefinit
             }
         }
     }
+    if let Some(ref generated) = col.generated {
+        let method = if generated.stored {
+            "generated_stored"
+        } else {
+            "generated"
+        };
+        chain.push_str(&format!(
+            ".{method}(\"{}\")",
+            escape_str(&generated.expression)
+        ));
+    }
     chain.push_str(".build()");
     chain
 }
@@ -368,13 +799,28 @@ fn render_alter_column(ac: &super::operation::AlterColumnOp) -> String {
         AlterColumnChange::SetAutoincrement(a) => {
             format!("AlterColumnChange::SetAutoincrement({a})")
         }
+        AlterColumnChange::SetCollation {
+            data_type,
+            collation,
+        } => {
+            let collation = match collation {
+                Some(c) => format!("Some(\"{}\".into())", escape_str(c)),
+                None => "None".to_string(),
+            };
+            format!(
+                "AlterColumnChange::SetCollation {{ data_type: DataType::{data_type:?}, \
+                 collation: {collation} }}"
+            )
+        }
     };
-    format!(
-        "Operation::AlterColumn(AlterColumnOp {{ \
-         table: \"{}\".into(), \
-         column: \"{}\".into(), \
-         change: {} }})",
-        ac.table, ac.column, change
+    render_struct(
+        "Operation::AlterColumn(AlterColumnOp",
+        &[
+            ("schema", render_schema(ac.schema.as_deref())),
+            ("table", format!("\"{}\".into()", ac.table)),
+            ("column", format!("\"{}\".into()", ac.column)),
+            ("change", change),
+        ],
     )
 }
 
@@ -410,7 +856,7 @@ mod tests {
     use super::*;
     use crate::migrations::column_builder::varchar;
     use crate::migrations::diff::SchemaDiff;
-    use crate::migrations::operation::Operation;
+    use crate::migrations::operation::{AlterColumnOp, Operation};
     use crate::migrations::table_builder::CreateTableBuilder;
 
     #[test]
@@ -442,6 +888,24 @@ mod tests {
         assert!(code.contains("drop_column"));
     }
 
+    #[test]
+    fn generate_array_column() {
+        let diff = SchemaDiff {
+            operations: vec![Operation::add_column(
+                "posts",
+                crate::migrations::column_builder::array("tags", DataType::Varchar(Some(40)))
+                    .build(),
+            )],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0003_add_tags", &diff);
+        assert!(
+            code.contains(r#"array("tags", oxide_sql_core::ast::DataType::Varchar(Some(40)))"#)
+        );
+    }
+
     #[test]
     fn generate_create_table_migration() {
         let op: Operation = CreateTableBuilder::new()
@@ -470,4 +934,218 @@ mod tests {
         // down should have drop_table
         assert!(code.contains("drop_table"));
     }
+
+    #[test]
+    fn generate_sequence_migration() {
+        let diff = SchemaDiff {
+            operations: vec![
+                CreateSequenceOp::new("seq_t_id")
+                    .start(1)
+                    .if_not_exists()
+                    .into(),
+            ],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0004_add_sequence", &diff);
+        assert!(
+            code.contains(r#"CreateSequenceOp::new("seq_t_id").start(1).if_not_exists().into()"#)
+        );
+        assert!(code.contains(r#"Operation::drop_sequence("seq_t_id")"#));
+    }
+
+    #[test]
+    fn generate_sequence_in_schema() {
+        let diff = SchemaDiff {
+            operations: vec![CreateSequenceOp::new("seq_t_id").schema("app").into()],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0005_add_sequence", &diff);
+        assert!(code.contains(r#"CreateSequenceOp::new("seq_t_id").schema("app").into()"#));
+        assert!(code.contains(r#"Operation::drop_sequence("seq_t_id").in_schema("app")"#));
+    }
+
+    #[test]
+    fn generate_table_options() {
+        let op: Operation = CreateTableBuilder::new()
+            .name("kv")
+            .column(varchar("key", 64).primary_key().build())
+            .option(TableOption::Strict)
+            .option(TableOption::Tablespace("fast".to_string()))
+            .build()
+            .into();
+        let diff = SchemaDiff {
+            operations: vec![op],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0005_kv", &diff);
+        assert!(code.contains(".option(oxide_sql_core::migrations::TableOption::Strict)"));
+        assert!(code.contains(
+            r#".option(oxide_sql_core::migrations::TableOption::Tablespace("fast".into()))"#
+        ));
+    }
+
+    #[test]
+    fn generate_schema_qualified_operations() {
+        let diff = SchemaDiff {
+            operations: vec![
+                Operation::create_schema("billing"),
+                Operation::drop_column("invoices", "note").in_schema("billing"),
+            ],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0006_billing", &diff);
+        assert!(code.contains(r#"Operation::create_schema("billing")"#));
+        assert!(
+            code.contains(r#"Operation::drop_column("invoices", "note").in_schema("billing")"#)
+        );
+        assert!(code.contains("cannot auto-reverse"));
+    }
+
+    #[test]
+    fn generate_with_metadata() {
+        let diff = SchemaDiff {
+            operations: vec![Operation::drop_table("legacy")],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+        let metadata = MigrationMetadata::new("0003_drop_legacy")
+            .depends_on("0002_add_email")
+            .description("Drops the legacy table.");
+
+        let code = generate_migration_code(metadata, &diff);
+        assert!(code.contains("/// Drops the legacy table.\npub struct Migration0003DropLegacy;"));
+        assert!(
+            code.contains(r#"const DEPENDENCIES: &'static [&'static str] = &["0002_add_email"];"#)
+        );
+        assert!(
+            code.contains("    fn down() -> Vec<Operation> {\n        vec![\n            // TODO")
+        );
+    }
+
+    #[test]
+    fn generate_orders_operations_safely() {
+        let diff = SchemaDiff {
+            operations: vec![
+                Operation::drop_table("old"),
+                Operation::drop_column("users", "legacy"),
+                Operation::add_column("users", varchar("email", 255).build()),
+                Operation::create_schema("billing"),
+            ],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0004_mixed", &diff);
+        let position = |needle: &str| code.find(needle).unwrap();
+        assert!(position("create_schema") < position("add_column"));
+        assert!(position("add_column") < position(r#"drop_column("users", "legacy")"#));
+        assert!(position(r#"drop_column("users", "legacy")"#) < position("drop_table"));
+    }
+
+    #[test]
+    fn generate_imports_only_used_names() {
+        let diff = SchemaDiff {
+            operations: vec![Operation::AlterColumn(AlterColumnOp {
+                schema: None,
+                table: "users".into(),
+                column: "age".into(),
+                change: AlterColumnChange::SetDataType(DataType::Bigint),
+            })],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0005_age", &diff);
+        assert!(code.starts_with(
+            "use oxide_sql_core::ast::DataType;\n\
+             use oxide_sql_core::migrations::{AlterColumnChange, AlterColumnOp, Migration, Operation};\n"
+        ));
+        assert!(code.contains(
+            "            Operation::AlterColumn(AlterColumnOp {\n\
+             \x20               schema: None,\n\
+             \x20               table: \"users\".into(),\n"
+        ));
+    }
+
+    #[test]
+    fn generate_empty_migration() {
+        let diff = SchemaDiff {
+            operations: vec![],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0006_empty", &diff);
+        assert!(code.starts_with("use oxide_sql_core::migrations::{Migration, Operation};\n"));
+        assert!(code.contains("    fn up() -> Vec<Operation> {\n        Vec::new()\n    }"));
+    }
+
+    #[test]
+    fn generate_column_references() {
+        let op: Operation = CreateTableBuilder::new()
+            .name("posts")
+            .column(
+                crate::migrations::column_builder::bigint("author_id")
+                    .not_null()
+                    .references("users", "id")
+                    .build(),
+            )
+            .column(
+                crate::migrations::column_builder::bigint("editor_id")
+                    .references_on_delete("users", "id", ForeignKeyAction::SetNull)
+                    .build(),
+            )
+            .build()
+            .into();
+        let diff = SchemaDiff {
+            operations: vec![op],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0002_posts", &diff);
+        assert!(
+            code.contains(r#"bigint("author_id").not_null().references("users", "id").build()"#)
+        );
+        assert!(code.contains(
+            r#"bigint("editor_id").references_full("users", "id", Some(ForeignKeyAction::SetNull), None).build()"#
+        ));
+        assert!(code.contains("ForeignKeyAction, Migration"));
+    }
+
+    #[test]
+    fn generate_generated_columns() {
+        let op: Operation = CreateTableBuilder::new()
+            .name("line_items")
+            .column(crate::migrations::column_builder::real("price").build())
+            .column(
+                crate::migrations::column_builder::real("total")
+                    .generated_stored("price * 2")
+                    .build(),
+            )
+            .column(
+                crate::migrations::column_builder::text("label")
+                    .generated("upper(\"name\")")
+                    .build(),
+            )
+            .build()
+            .into();
+        let diff = SchemaDiff {
+            operations: vec![op],
+            ambiguous: vec![],
+            warnings: vec![],
+        };
+
+        let code = generate_migration_code("0007_line_items", &diff);
+        assert!(code.contains(r#"real("total").generated_stored("price * 2").build()"#));
+        assert!(code.contains(r#"text("label").generated("upper(\"name\")").build()"#));
+    }
 }
@@ -3,7 +3,8 @@
 //! Provides a fluent API for defining columns in migrations with compile-time
 //! validation of constraints.
 
-use crate::ast::DataType;
+use crate::ast::{DataType, Expr, Literal, UnaryOp};
+use crate::parser::Parser;
 
 /// A reference to a foreign key in another table.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,6 +84,178 @@ impl DefaultValue {
             Self::Expression(expr) => expr.clone(),
         }
     }
+
+    /// Returns the canonical form of this default, so that spellings
+    /// that differ only in rendering compare equal.
+    ///
+    /// Expressions are parsed: literals become the matching variant
+    /// (`'a'` becomes `String("a")`, `-1` becomes `Integer(-1)`),
+    /// redundant parentheses are dropped, and the standard
+    /// `CURRENT_TIMESTAMP`, `CURRENT_DATE`, and `CURRENT_TIME` are
+    /// spelled in upper case without parentheses. Floats without a
+    /// fractional part become integers.
+    ///
+    /// This is dialect-agnostic; dialects map their own spellings
+    /// (such as SQLite's `datetime('now')`) in
+    /// [`RustTypeMapping::normalize_default`](crate::schema::RustTypeMapping::normalize_default).
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        match self {
+            Self::Float(f) => f
+                .to_string()
+                .parse::<i64>()
+                .map_or(Self::Float(*f), Self::Integer),
+            Self::Expression(sql) => normalize_expression(sql),
+            other => other.clone(),
+        }
+    }
+
+    /// Returns the [normalized](Self::normalized) default, with an
+    /// expression found in `aliases` replaced by its canonical
+    /// spelling.
+    ///
+    /// Alias keys are lower case without whitespace and are matched
+    /// against the expression in that form, outer parentheses removed.
+    pub(crate) fn normalized_with(&self, aliases: &[(&str, &str)]) -> Self {
+        let normalized = self.normalized();
+        if let Self::Expression(sql) = &normalized {
+            let key = expression_key(sql);
+            if let Some((_, canonical)) = aliases.iter().find(|(alias, _)| *alias == key) {
+                return Self::Expression((*canonical).to_string());
+            }
+        }
+        normalized
+    }
+
+    /// Returns whether two defaults are equivalent for a column of
+    /// `data_type`.
+    ///
+    /// Both sides are [normalized](Self::normalized) first. For
+    /// `BOOLEAN` columns, `FALSE`, `0`, and `'false'` are treated as
+    /// the same value. The schema diff compares defaults with
+    /// [`RustTypeMapping::defaults_equivalent`](crate::schema::RustTypeMapping::defaults_equivalent)
+    /// instead, which also
+    /// accounts for the dialect.
+    #[must_use]
+    pub fn is_equivalent(&self, other: &Self, data_type: &DataType) -> bool {
+        same_default(&self.normalized(), &other.normalized(), || {
+            matches!(data_type, DataType::Boolean)
+        })
+    }
+
+    /// The truth value of a normalized boolean-like default.
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(b) => Some(*b),
+            Self::Integer(0) => Some(false),
+            Self::Integer(1) => Some(true),
+            Self::String(s) => match s.to_ascii_lowercase().as_str() {
+                "false" | "f" | "0" => Some(false),
+                "true" | "t" | "1" => Some(true),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Returns whether two normalized defaults are the same value, treating
+/// the spellings of a boolean alike when `stores_booleans` says the
+/// column holds booleans.
+pub(crate) fn same_default(
+    a: &DefaultValue,
+    b: &DefaultValue,
+    stores_booleans: impl FnOnce() -> bool,
+) -> bool {
+    a == b || (a.as_bool().is_some() && a.as_bool() == b.as_bool() && stores_booleans())
+}
+
+/// Returns `sql` in lower case without whitespace or outer parentheses.
+fn expression_key(sql: &str) -> String {
+    let compact: String = sql
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let mut key = compact.as_str();
+    while let Some(inner) = key.strip_prefix('(').and_then(|k| k.strip_suffix(')')) {
+        key = inner;
+    }
+    key.to_string()
+}
+
+/// Canonicalizes a raw SQL default expression.
+fn normalize_expression(sql: &str) -> DefaultValue {
+    let current = match expression_key(sql).as_str() {
+        "current_timestamp" | "current_timestamp()" => Some("CURRENT_TIMESTAMP"),
+        "current_date" | "current_date()" => Some("CURRENT_DATE"),
+        "current_time" | "current_time()" => Some("CURRENT_TIME"),
+        _ => None,
+    };
+    if let Some(current) = current {
+        return DefaultValue::Expression(current.to_string());
+    }
+
+    let Ok(mut expr) = Parser::new(sql).parse_expr() else {
+        return DefaultValue::Expression(sql.trim().to_string());
+    };
+    while let Expr::Paren(inner) = expr {
+        expr = *inner;
+    }
+    match expr {
+        Expr::Literal(Literal::Null) => DefaultValue::Null,
+        Expr::Literal(Literal::Boolean(b)) => DefaultValue::Boolean(b),
+        Expr::Literal(Literal::Integer(i)) => DefaultValue::Integer(i),
+        Expr::Literal(Literal::Float(f)) => DefaultValue::Float(f).normalized(),
+        Expr::Literal(Literal::String(s)) => DefaultValue::String(s),
+        Expr::Unary {
+            op: UnaryOp::Neg,
+            ref operand,
+        } => match operand.as_ref() {
+            Expr::Literal(Literal::Integer(i)) => i.checked_neg().map_or_else(
+                || DefaultValue::Expression(expr.to_string()),
+                DefaultValue::Integer,
+            ),
+            Expr::Literal(Literal::Float(f)) => DefaultValue::Float(-f).normalized(),
+            _ => DefaultValue::Expression(expr.to_string()),
+        },
+        expr => DefaultValue::Expression(expr.to_string()),
+    }
+}
+
+/// A generated (computed) column's expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedColumn {
+    /// The SQL expression computing the column's value.
+    pub expression: String,
+    /// Whether the value is stored on write (`STORED`) rather than
+    /// computed on read (`VIRTUAL`).
+    pub stored: bool,
+}
+
+impl GeneratedColumn {
+    /// Returns the `GENERATED ALWAYS AS (...)` clause.
+    #[must_use]
+    pub fn to_sql(&self) -> String {
+        format!(
+            "GENERATED ALWAYS AS ({}) {}",
+            self.expression,
+            if self.stored { "STORED" } else { "VIRTUAL" }
+        )
+    }
+
+    /// Returns whether two generated columns compute the same value the
+    /// same way. Expressions are compared by their rendered SQL, so
+    /// formatting doesn't count as a change.
+    #[must_use]
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        fn canonical(sql: &str) -> String {
+            Parser::new(sql)
+                .parse_expr()
+                .map_or_else(|_| sql.trim().to_string(), |expr| expr.to_string())
+        }
+        self.stored == other.stored && canonical(&self.expression) == canonical(&other.expression)
+    }
 }
 
 /// A complete column definition for migrations.
@@ -108,6 +281,8 @@ pub struct ColumnDefinition {
     pub check: Option<String>,
     /// Collation for string columns.
     pub collation: Option<String>,
+    /// Generated column expression, if the column is computed.
+    pub generated: Option<GeneratedColumn>,
 }
 
 impl ColumnDefinition {
@@ -125,6 +300,7 @@ impl ColumnDefinition {
             references: None,
             check: None,
             collation: None,
+            generated: None,
         }
     }
 }
@@ -144,6 +320,7 @@ pub struct ColumnBuilder {
     references: Option<ForeignKeyRef>,
     check: Option<String>,
     collation: Option<String>,
+    generated: Option<GeneratedColumn>,
 }
 
 impl ColumnBuilder {
@@ -161,6 +338,7 @@ impl ColumnBuilder {
             references: None,
             check: None,
             collation: None,
+            generated: None,
         }
     }
 
@@ -303,6 +481,28 @@ impl ColumnBuilder {
         self
     }
 
+    /// Makes the column a virtual generated column, computed from
+    /// `expr` when read.
+    #[must_use]
+    pub fn generated(mut self, expr: impl Into<String>) -> Self {
+        self.generated = Some(GeneratedColumn {
+            expression: expr.into(),
+            stored: false,
+        });
+        self
+    }
+
+    /// Makes the column a stored generated column, computed from `expr`
+    /// when the row is written.
+    #[must_use]
+    pub fn generated_stored(mut self, expr: impl Into<String>) -> Self {
+        self.generated = Some(GeneratedColumn {
+            expression: expr.into(),
+            stored: true,
+        });
+        self
+    }
+
     /// Builds the column definition.
     #[must_use]
     pub fn build(self) -> ColumnDefinition {
@@ -317,6 +517,7 @@ impl ColumnBuilder {
             references: self.references,
             check: self.check,
             collation: self.collation,
+            generated: self.generated,
         }
     }
 }
@@ -445,9 +646,122 @@ pub fn boolean(name: impl Into<String>) -> ColumnBuilder {
     ColumnBuilder::new(name, DataType::Boolean)
 }
 
+/// Creates an array column builder whose elements have type `element`
+/// (`INTEGER[]` in PostgreSQL and DuckDB, JSON text in SQLite).
+#[must_use]
+pub fn array(name: impl Into<String>, element: DataType) -> ColumnBuilder {
+    ColumnBuilder::new(name, DataType::Array(Box::new(element)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::migrations::{PostgresDialect, SqliteDialect};
+    use crate::schema::RustTypeMapping;
+
+    #[test]
+    fn test_default_normalized_literals() {
+        let cases = [
+            ("NULL", DefaultValue::Null),
+            ("true", DefaultValue::Boolean(true)),
+            ("(0)", DefaultValue::Integer(0)),
+            ("-7", DefaultValue::Integer(-7)),
+            ("2.50", DefaultValue::Float(2.5)),
+            ("-1.0", DefaultValue::Integer(-1)),
+            ("'it''s'", DefaultValue::String("it's".into())),
+        ];
+        for (sql, expected) in cases {
+            assert_eq!(
+                DefaultValue::Expression(sql.into()).normalized(),
+                expected,
+                "{sql}"
+            );
+        }
+        assert_eq!(
+            DefaultValue::Float(3.0).normalized(),
+            DefaultValue::Integer(3)
+        );
+    }
+
+    #[test]
+    fn test_default_normalized_current_time() {
+        for sql in [
+            "CURRENT_TIMESTAMP",
+            "current_timestamp",
+            "CURRENT_TIMESTAMP()",
+            "(CURRENT_TIMESTAMP)",
+        ] {
+            assert_eq!(
+                DefaultValue::Expression(sql.into()).normalized(),
+                DefaultValue::Expression("CURRENT_TIMESTAMP".into()),
+                "{sql}"
+            );
+        }
+        // Dialect-specific spellings are left to the dialect.
+        let sqlite = SqliteDialect::new();
+        for sql in ["datetime('now')", "DATETIME( 'now' )"] {
+            let value = DefaultValue::Expression(sql.into());
+            assert_ne!(
+                value.normalized(),
+                DefaultValue::Expression("CURRENT_TIMESTAMP".into())
+            );
+            assert_eq!(
+                sqlite.normalize_default(&value),
+                DefaultValue::Expression("CURRENT_TIMESTAMP".into()),
+                "{sql}"
+            );
+        }
+        assert_eq!(
+            sqlite.normalize_default(&DefaultValue::Expression("date('now')".into())),
+            DefaultValue::Expression("CURRENT_DATE".into())
+        );
+        assert_eq!(
+            PostgresDialect::new().normalize_default(&DefaultValue::Expression("NOW()".into())),
+            DefaultValue::Expression("CURRENT_TIMESTAMP".into())
+        );
+        // Other expressions are re-rendered, not rewritten.
+        assert_eq!(
+            DefaultValue::Expression("lower( 'A' )".into()).normalized(),
+            DefaultValue::Expression("lower('A')".into())
+        );
+    }
+
+    #[test]
+    fn test_default_boolean_equivalence() {
+        let falses = [
+            DefaultValue::Boolean(false),
+            DefaultValue::Integer(0),
+            DefaultValue::Expression("FALSE".into()),
+            DefaultValue::Expression("0".into()),
+            DefaultValue::String("false".into()),
+            DefaultValue::Expression("'f'".into()),
+        ];
+        let sqlite = SqliteDialect::new();
+        for a in &falses {
+            for b in &falses {
+                assert!(a.is_equivalent(b, &DataType::Boolean), "{a:?} vs {b:?}");
+                // SQLite stores booleans in integer columns.
+                assert!(
+                    sqlite.defaults_equivalent(a, b, &DataType::Integer),
+                    "{a:?} vs {b:?}"
+                );
+            }
+            assert!(!a.is_equivalent(&DefaultValue::Boolean(true), &DataType::Boolean));
+        }
+        assert!(
+            !DefaultValue::Integer(0)
+                .is_equivalent(&DefaultValue::Boolean(false), &DataType::Integer)
+        );
+        assert!(
+            !DefaultValue::Integer(0)
+                .is_equivalent(&DefaultValue::String("false".into()), &DataType::Text)
+        );
+        assert!(!sqlite.defaults_equivalent(
+            &DefaultValue::Integer(2),
+            &DefaultValue::Boolean(true),
+            &DataType::Integer
+        ));
+    }
 
     #[test]
     fn test_basic_column() {
@@ -513,6 +827,31 @@ mod tests {
         assert_eq!(col.check, Some("age >= 0".to_string()));
     }
 
+    #[test]
+    fn test_generated_column() {
+        let col = integer("total").generated("price * quantity").build();
+        let generated = col.generated.unwrap();
+        assert_eq!(
+            generated.to_sql(),
+            "GENERATED ALWAYS AS (price * quantity) VIRTUAL"
+        );
+
+        let stored = integer("total")
+            .generated_stored("price*quantity")
+            .build()
+            .generated
+            .unwrap();
+        assert_eq!(
+            stored.to_sql(),
+            "GENERATED ALWAYS AS (price*quantity) STORED"
+        );
+        assert!(!generated.is_equivalent(&stored));
+        assert!(stored.is_equivalent(&GeneratedColumn {
+            expression: String::from("price  *  quantity"),
+            stored: true,
+        }));
+    }
+
     #[test]
     fn test_default_value_to_sql() {
         assert_eq!(DefaultValue::Null.to_sql(), "NULL");
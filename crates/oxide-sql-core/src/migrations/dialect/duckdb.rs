@@ -4,7 +4,8 @@ use super::MigrationDialect;
 use crate::ast::DataType;
 use crate::migrations::column_builder::{ColumnDefinition, DefaultValue};
 use crate::migrations::operation::{
-    AlterColumnChange, AlterColumnOp, CreateTableOp, DropIndexOp, RenameColumnOp, RenameTableOp,
+    AlterColumnChange, AlterColumnOp, AlterSequenceOp, CreateSequenceOp, CreateTableOp,
+    DropIndexOp, DropSequenceOp, Operation, RenameColumnOp, RenameTableOp,
 };
 use crate::schema::RustTypeMapping;
 
@@ -12,9 +13,10 @@ use crate::schema::RustTypeMapping;
 ///
 /// DuckDB does not support `AUTOINCREMENT` or `SERIAL`/`BIGSERIAL`.
 /// Instead, auto-increment is implemented via `CREATE SEQUENCE` +
-/// `DEFAULT nextval('seq_<table>_<column>')`.  The [`create_table`]
-/// override emits the sequence DDL automatically for every column
-/// marked with `autoincrement`.
+/// `DEFAULT nextval('seq_<table>_<column>')`.  The
+/// [`create_table`](MigrationDialect::create_table) override emits the
+/// sequence DDL automatically for every column marked with
+/// `autoincrement`.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct DuckDbDialect;
 
@@ -25,6 +27,19 @@ impl DuckDbDialect {
         Self
     }
 
+    /// Returns the name of the sequence backing an autoincrement
+    /// column, `seq_<table>_<column>`.
+    #[must_use]
+    pub fn sequence_name(table: &str, column: &str) -> String {
+        format!("seq_{table}_{column}")
+    }
+
+    /// Returns `nextval('<sequence>')`, the default drawing from a
+    /// sequence.
+    fn nextval(sequence: &str) -> String {
+        format!("nextval('{}')", sequence.replace('\'', "''"))
+    }
+
     /// Generates a column definition with sequence-backed default for
     /// autoincrement columns, using the given table name to build the
     /// sequence name.
@@ -44,7 +59,8 @@ impl DuckDbDialect {
         }
 
         if col.autoincrement && col.default.is_none() {
-            sql.push_str(&format!(" DEFAULT nextval('seq_{}_{}')", table, col.name,));
+            sql.push_str(" DEFAULT ");
+            sql.push_str(&Self::nextval(&Self::sequence_name(table, &col.name)));
         } else if let Some(ref default) = col.default {
             sql.push_str(" DEFAULT ");
             sql.push_str(&self.render_default(default));
@@ -71,7 +87,7 @@ impl DuckDbDialect {
         }
 
         if let Some(ref collation) = col.collation {
-            sql.push_str(&format!(" COLLATE \"{}\"", collation));
+            sql.push_str(&format!(" COLLATE {}", self.quote_identifier(collation)));
         }
 
         sql
@@ -83,6 +99,10 @@ impl MigrationDialect for DuckDbDialect {
         "duckdb"
     }
 
+    fn supports_add_foreign_key(&self) -> bool {
+        false
+    }
+
     fn map_data_type(&self, dt: &DataType) -> String {
         match dt {
             DataType::Smallint => "SMALLINT".to_string(),
@@ -123,6 +143,7 @@ impl MigrationDialect for DuckDbDialect {
             DataType::Timestamp => "TIMESTAMP".to_string(),
             DataType::Datetime => "TIMESTAMP".to_string(),
             DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::Array(inner) => format!("{}[]", self.map_data_type(inner)),
             DataType::Custom(name) => name.clone(),
         }
     }
@@ -133,15 +154,16 @@ impl MigrationDialect for DuckDbDialect {
     }
 
     fn create_table(&self, op: &CreateTableOp) -> String {
+        if op.partition_by.is_some() {
+            return self.partitioning_unsupported(&op.name);
+        }
         // Emit CREATE SEQUENCE for every autoincrement column.
         let mut sql = String::new();
         for col in &op.columns {
             if col.autoincrement {
                 sql.push_str(&format!(
-                    "CREATE SEQUENCE IF NOT EXISTS \
-                     \"seq_{table}_{col}\" START 1;\n",
-                    table = op.name,
-                    col = col.name,
+                    "CREATE SEQUENCE IF NOT EXISTS {} START 1;\n",
+                    self.quote_identifier(&Self::sequence_name(&op.name, &col.name)),
                 ));
             }
         }
@@ -150,7 +172,7 @@ impl MigrationDialect for DuckDbDialect {
         if op.if_not_exists {
             sql.push_str("IF NOT EXISTS ");
         }
-        sql.push_str(&self.quote_identifier(&op.name));
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
         sql.push_str(" (\n");
 
         let column_defs: Vec<String> = op
@@ -190,7 +212,7 @@ impl MigrationDialect for DuckDbDialect {
     fn rename_table(&self, op: &RenameTableOp) -> String {
         format!(
             "ALTER TABLE {} RENAME TO {}",
-            self.quote_identifier(&op.old_name),
+            self.quote_table(op.schema.as_deref(), &op.old_name),
             self.quote_identifier(&op.new_name)
         )
     }
@@ -198,14 +220,64 @@ impl MigrationDialect for DuckDbDialect {
     fn rename_column(&self, op: &RenameColumnOp) -> String {
         format!(
             "ALTER TABLE {} RENAME COLUMN {} TO {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.quote_identifier(&op.old_name),
             self.quote_identifier(&op.new_name)
         )
     }
 
+    fn alter_sequence(&self, op: &AlterSequenceOp) -> String {
+        format!(
+            "-- DuckDB does not support ALTER SEQUENCE; \
+             drop and recreate {} instead",
+            op.name
+        )
+    }
+
+    fn autoincrement_change(
+        &self,
+        table: &str,
+        column: &str,
+        enabled: bool,
+    ) -> Option<Vec<Operation>> {
+        // Autoincrement is a sequence plus a nextval() default, so
+        // toggling it only touches the sequence and the default.
+        let sequence = Self::sequence_name(table, column);
+        let ops = if enabled {
+            vec![
+                CreateSequenceOp::new(&sequence)
+                    .start(1)
+                    .if_not_exists()
+                    .into(),
+                Operation::AlterColumn(AlterColumnOp {
+                    schema: None,
+                    table: table.to_string(),
+                    column: column.to_string(),
+                    change: AlterColumnChange::SetDefault(DefaultValue::Expression(Self::nextval(
+                        &sequence,
+                    ))),
+                }),
+            ]
+        } else {
+            vec![
+                Operation::AlterColumn(AlterColumnOp {
+                    schema: None,
+                    table: table.to_string(),
+                    column: column.to_string(),
+                    change: AlterColumnChange::DropDefault,
+                }),
+                Operation::DropSequence(DropSequenceOp {
+                    schema: None,
+                    name: sequence,
+                    if_exists: true,
+                }),
+            ]
+        };
+        Some(ops)
+    }
+
     fn alter_column(&self, op: &AlterColumnOp) -> String {
-        let table = self.quote_identifier(&op.table);
+        let table = self.quote_table(op.schema.as_deref(), &op.table);
         let column = self.quote_identifier(&op.column);
 
         match &op.change {
@@ -254,6 +326,21 @@ impl MigrationDialect for DuckDbDialect {
                     op.table, op.column
                 )
             }
+            AlterColumnChange::SetCollation {
+                data_type,
+                collation,
+            } => {
+                let mut sql = format!(
+                    "ALTER TABLE {} ALTER COLUMN {} SET DATA TYPE {}",
+                    table,
+                    column,
+                    self.map_data_type(data_type)
+                );
+                if let Some(collation) = collation {
+                    sql.push_str(&format!(" COLLATE {}", self.quote_identifier(collation)));
+                }
+                sql
+            }
         }
     }
 
@@ -262,14 +349,14 @@ impl MigrationDialect for DuckDbDialect {
         if op.if_exists {
             sql.push_str("IF EXISTS ");
         }
-        sql.push_str(&self.quote_identifier(&op.name));
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
         sql
     }
 
     fn drop_foreign_key(&self, op: &super::super::operation::DropForeignKeyOp) -> String {
         format!(
             "ALTER TABLE {} DROP CONSTRAINT {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.quote_identifier(&op.name)
         )
     }
@@ -277,6 +364,9 @@ impl MigrationDialect for DuckDbDialect {
 
 impl RustTypeMapping for DuckDbDialect {
     fn map_type(&self, rust_type: &str) -> DataType {
+        if let Some(element) = super::vec_element(rust_type) {
+            return DataType::Array(Box::new(self.map_type(element)));
+        }
         match rust_type {
             "bool" => DataType::Boolean,
             "i8" | "i16" | "u8" | "u16" => DataType::Smallint,
@@ -291,6 +381,14 @@ impl RustTypeMapping for DuckDbDialect {
             _ => DataType::Text,
         }
     }
+
+    fn normalize_default(&self, value: &DefaultValue) -> DefaultValue {
+        value.normalized_with(&[
+            ("now()", "CURRENT_TIMESTAMP"),
+            ("get_current_timestamp()", "CURRENT_TIMESTAMP"),
+            ("today()", "CURRENT_DATE"),
+        ])
+    }
 }
 
 #[cfg(test)]
@@ -317,6 +415,14 @@ mod tests {
         assert_eq!(d.map_data_type(&DataType::Real), "REAL");
         assert_eq!(d.map_data_type(&DataType::Date), "DATE");
         assert_eq!(d.map_data_type(&DataType::Time), "TIME");
+        assert_eq!(
+            d.map_data_type(&DataType::Array(Box::new(DataType::Double))),
+            "DOUBLE[]"
+        );
+        assert_eq!(
+            d.map_type("Vec<f64>"),
+            DataType::Array(Box::new(DataType::Double))
+        );
         assert_eq!(
             d.map_data_type(&DataType::Decimal {
                 precision: Some(10),
@@ -404,6 +510,7 @@ mod tests {
         let d = DuckDbDialect::new();
 
         let op = DropTableOp {
+            schema: None,
             name: "users".to_string(),
             if_exists: false,
             cascade: false,
@@ -411,6 +518,7 @@ mod tests {
         assert_eq!(d.drop_table(&op), "DROP TABLE \"users\"");
 
         let op = DropTableOp {
+            schema: None,
             name: "users".to_string(),
             if_exists: true,
             cascade: true,
@@ -422,6 +530,7 @@ mod tests {
     fn test_rename_table() {
         let d = DuckDbDialect::new();
         let op = RenameTableOp {
+            schema: None,
             old_name: "old_users".to_string(),
             new_name: "users".to_string(),
         };
@@ -459,6 +568,7 @@ mod tests {
     fn test_rename_column() {
         let d = DuckDbDialect::new();
         let op = RenameColumnOp {
+            schema: None,
             table: "users".to_string(),
             old_name: "name".to_string(),
             new_name: "full_name".to_string(),
@@ -474,6 +584,7 @@ mod tests {
     fn test_alter_column_set_data_type() {
         let d = DuckDbDialect::new();
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "age".to_string(),
             change: AlterColumnChange::SetDataType(DataType::Bigint),
@@ -485,10 +596,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_alter_column_set_collation() {
+        let d = DuckDbDialect::new();
+        let op = AlterColumnOp {
+            schema: None,
+            table: "users".to_string(),
+            column: "name".to_string(),
+            change: AlterColumnChange::SetCollation {
+                data_type: DataType::Text,
+                collation: Some("nocase".to_string()),
+            },
+        };
+        assert_eq!(
+            d.alter_column(&op),
+            "ALTER TABLE \"users\" ALTER COLUMN \"name\" \
+             SET DATA TYPE TEXT COLLATE \"nocase\""
+        );
+    }
+
     #[test]
     fn test_alter_column_set_not_null() {
         let d = DuckDbDialect::new();
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "email".to_string(),
             change: AlterColumnChange::SetNullable(false),
@@ -503,6 +634,7 @@ mod tests {
     fn test_alter_column_drop_not_null() {
         let d = DuckDbDialect::new();
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "email".to_string(),
             change: AlterColumnChange::SetNullable(true),
@@ -517,6 +649,7 @@ mod tests {
     fn test_alter_column_set_default() {
         let d = DuckDbDialect::new();
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "active".to_string(),
             change: AlterColumnChange::SetDefault(DefaultValue::Boolean(true)),
@@ -532,6 +665,7 @@ mod tests {
     fn test_alter_column_drop_default() {
         let d = DuckDbDialect::new();
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "active".to_string(),
             change: AlterColumnChange::DropDefault,
@@ -546,9 +680,10 @@ mod tests {
     fn test_create_index() {
         let d = DuckDbDialect::new();
         let op = crate::migrations::operation::CreateIndexOp {
+            schema: None,
             name: "idx_users_email".to_string(),
             table: "users".to_string(),
-            columns: vec!["email".to_string()],
+            columns: vec!["email".into()],
             unique: true,
             index_type: crate::migrations::operation::IndexType::BTree,
             if_not_exists: true,
@@ -566,6 +701,7 @@ mod tests {
         let d = DuckDbDialect::new();
 
         let op = crate::migrations::operation::DropIndexOp {
+            schema: None,
             name: "idx_users_email".to_string(),
             table: None,
             if_exists: false,
@@ -573,6 +709,7 @@ mod tests {
         assert_eq!(d.drop_index(&op), "DROP INDEX \"idx_users_email\"");
 
         let op = crate::migrations::operation::DropIndexOp {
+            schema: None,
             name: "idx_users_email".to_string(),
             table: None,
             if_exists: true,
@@ -587,6 +724,7 @@ mod tests {
     fn test_drop_foreign_key() {
         let d = DuckDbDialect::new();
         let op = crate::migrations::operation::DropForeignKeyOp {
+            schema: None,
             table: "invoices".to_string(),
             name: "fk_invoices_user".to_string(),
         };
@@ -665,4 +803,87 @@ mod tests {
             sqls[1]
         );
     }
+
+    #[test]
+    fn test_triggers_unsupported() {
+        use crate::migrations::operation::{CreateTriggerOp, TriggerEvent, TriggerTiming};
+        use crate::migrations::{Migration, MigrationError, MigrationRunner};
+
+        struct Audit;
+        impl Migration for Audit {
+            const ID: &'static str = "0001_audit";
+            fn up() -> Vec<Operation> {
+                vec![
+                    CreateTriggerOp::new(
+                        "t_audit",
+                        "t",
+                        TriggerTiming::After,
+                        TriggerEvent::Insert,
+                    )
+                    .into(),
+                ]
+            }
+            fn down() -> Vec<Operation> {
+                vec![Operation::drop_trigger("t_audit", "t")]
+            }
+        }
+
+        let dialect = DuckDbDialect::new();
+        assert_eq!(
+            dialect
+                .unsupported_reason(&Operation::drop_trigger("t_audit", "t"))
+                .as_deref(),
+            Some("duckdb does not support triggers; cannot drop t_audit on t")
+        );
+
+        let mut runner = MigrationRunner::new(dialect);
+        runner.register::<Audit>();
+        assert_eq!(
+            runner.validate(),
+            Err(MigrationError::UnsupportedOperation {
+                migration: "0001_audit".to_string(),
+                reason: "duckdb does not support triggers; cannot create t_audit on t".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sequence_sql() {
+        use crate::migrations::operation::AlterSequenceOp;
+
+        let dialect = DuckDbDialect::new();
+        let op = CreateSequenceOp::new("seq_ids").start(1);
+        assert_eq!(
+            dialect.generate_sql(&op.into()),
+            "CREATE SEQUENCE \"seq_ids\" START WITH 1"
+        );
+        let op = AlterSequenceOp {
+            schema: None,
+            name: "seq_ids".to_string(),
+            restart: Some(10),
+            increment: None,
+            cycle: None,
+        };
+        assert!(
+            dialect
+                .generate_sql(&op.into())
+                .starts_with("-- DuckDB does not support")
+        );
+    }
+
+    #[test]
+    fn test_partitioning_unsupported() {
+        use crate::migrations::operation::PartitionStrategy;
+
+        let dialect = DuckDbDialect::new();
+        let op = CreateTableBuilder::new()
+            .name("events")
+            .column(integer("id").autoincrement().build())
+            .partition_by(PartitionStrategy::List, &["id"])
+            .build();
+        assert_eq!(
+            dialect.create_table(&op),
+            "-- duckdb does not support table partitioning; cannot create events"
+        );
+    }
 }
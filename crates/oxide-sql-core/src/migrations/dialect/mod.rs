@@ -13,11 +13,14 @@ pub use postgres::PostgresDialect;
 pub use sqlite::SqliteDialect;
 
 use crate::ast::DataType;
+use crate::builder::is_valid_identifier;
 
 use super::column_builder::{ColumnDefinition, DefaultValue};
 use super::operation::{
-    AddColumnOp, AlterColumnOp, CreateIndexOp, CreateTableOp, DropColumnOp, DropIndexOp,
-    DropTableOp, IndexType, Operation, RenameColumnOp, RenameTableOp, TableConstraint,
+    AddColumnOp, AlterColumnOp, AlterSequenceOp, CreateIndexOp, CreatePartitionOp, CreateSchemaOp,
+    CreateSequenceOp, CreateTableOp, CreateTriggerOp, DropColumnOp, DropIndexOp, DropSchemaOp,
+    DropSequenceOp, DropTableOp, DropTriggerOp, IndexColumn, IndexType, Operation, PartitionBound,
+    PartitionBy, RenameColumnOp, RenameTableOp, TableConstraint, TableOption, TriggerEvent,
 };
 
 /// Trait for dialect-specific SQL generation for migrations.
@@ -39,17 +42,28 @@ pub trait MigrationDialect {
             Operation::DropIndex(op) => self.drop_index(op),
             Operation::AddForeignKey(op) => self.add_foreign_key(op),
             Operation::DropForeignKey(op) => self.drop_foreign_key(op),
+            Operation::CreateTrigger(op) => self.create_trigger(op),
+            Operation::DropTrigger(op) => self.drop_trigger(op),
+            Operation::CreateSequence(op) => self.create_sequence(op),
+            Operation::AlterSequence(op) => self.alter_sequence(op),
+            Operation::DropSequence(op) => self.drop_sequence(op),
+            Operation::CreatePartition(op) => self.create_partition(op),
+            Operation::CreateSchema(op) => self.create_schema(op),
+            Operation::DropSchema(op) => self.drop_schema(op),
             Operation::RunSql(op) => op.up_sql.clone(),
         }
     }
 
     /// Generates SQL for CREATE TABLE.
     fn create_table(&self, op: &CreateTableOp) -> String {
-        let mut sql = String::from("CREATE TABLE ");
+        if op.partition_by.is_some() && !self.supports_partitioning() {
+            return self.partitioning_unsupported(&op.name);
+        }
+        let mut sql = format!("CREATE {}TABLE ", self.table_modifiers(&op.options));
         if op.if_not_exists {
             sql.push_str("IF NOT EXISTS ");
         }
-        sql.push_str(&self.quote_identifier(&op.name));
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
         sql.push_str(" (\n");
 
         // Columns
@@ -72,6 +86,171 @@ pub trait MigrationDialect {
         }
 
         sql.push_str("\n)");
+        if let Some(ref partition) = op.partition_by {
+            sql.push_str(&self.partition_by(partition));
+        }
+        sql.push_str(&self.table_options(&op.options));
+        sql
+    }
+
+    /// Renders the keywords between `CREATE` and `TABLE` (e.g.
+    /// `UNLOGGED `) for the options this dialect understands.
+    fn table_modifiers(&self, _options: &[TableOption]) -> String {
+        String::new()
+    }
+
+    /// Renders the options after the column list (e.g.
+    /// ` WITHOUT ROWID`) that this dialect understands; the rest are
+    /// ignored.
+    fn table_options(&self, _options: &[TableOption]) -> String {
+        String::new()
+    }
+
+    /// Returns whether the dialect supports declarative partitioning
+    /// (`PARTITION BY` and `PARTITION OF`).
+    fn supports_partitioning(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the dialect can add a foreign key to an existing
+    /// table (`ALTER TABLE ... ADD FOREIGN KEY`).
+    fn supports_add_foreign_key(&self) -> bool {
+        true
+    }
+
+    /// Returns whether `CREATE TABLE` accepts a foreign key to a table
+    /// that does not exist yet.
+    fn supports_forward_foreign_keys(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the dialect supports `CREATE TRIGGER` and
+    /// `DROP TRIGGER`.
+    fn supports_triggers(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the dialect supports statement-level
+    /// (`FOR EACH STATEMENT`) triggers.
+    fn supports_statement_triggers(&self) -> bool {
+        self.supports_triggers()
+    }
+
+    /// Renders the `PARTITION BY` clause of a partitioned table.
+    fn partition_by(&self, partition: &PartitionBy) -> String {
+        let cols: Vec<String> = partition
+            .columns
+            .iter()
+            .map(|c| self.quote_identifier(c))
+            .collect();
+        format!(
+            " PARTITION BY {} ({})",
+            partition.strategy.as_sql(),
+            cols.join(", ")
+        )
+    }
+
+    /// Returns why this dialect cannot run `operation`, or `None` if it
+    /// can.
+    ///
+    /// [`MigrationRunner`](crate::migrations::MigrationRunner) rejects
+    /// migrations containing such operations instead of emitting the
+    /// placeholder comment that [`generate_sql`](Self::generate_sql)
+    /// renders for them. Tables whose `WITHOUT ROWID` option the dialect
+    /// renders are also rejected when they lack a PRIMARY KEY or use
+    /// AUTOINCREMENT, which SQLite refuses, and triggers are rejected
+    /// by dialects without (statement-level) trigger support.
+    fn unsupported_reason(&self, operation: &Operation) -> Option<String> {
+        match operation {
+            Operation::CreateTrigger(op) if !self.supports_triggers() => {
+                return Some(format!(
+                    "{} does not support triggers; cannot create {} on {}",
+                    self.name(),
+                    op.name,
+                    op.table
+                ));
+            }
+            Operation::CreateTrigger(op)
+                if !op.for_each_row && !self.supports_statement_triggers() =>
+            {
+                return Some(format!(
+                    "{} does not support FOR EACH STATEMENT triggers; cannot create {} on {}",
+                    self.name(),
+                    op.name,
+                    op.table
+                ));
+            }
+            Operation::DropTrigger(op) if !self.supports_triggers() => {
+                return Some(format!(
+                    "{} does not support triggers; cannot drop {} on {}",
+                    self.name(),
+                    op.name,
+                    op.table
+                ));
+            }
+            _ => {}
+        }
+        if let Operation::CreateTable(op) = operation {
+            let without_rowid = [TableOption::WithoutRowid];
+            let renders = !self.table_modifiers(&without_rowid).is_empty()
+                || !self.table_options(&without_rowid).is_empty();
+            if let Some(conflict) = op.without_rowid_conflict().filter(|_| renders) {
+                return Some(format!("cannot create {}: {conflict}", op.name));
+            }
+        }
+        let table = match operation {
+            Operation::CreateTable(op) if op.partition_by.is_some() => &op.name,
+            Operation::CreatePartition(op) => &op.name,
+            _ => return None,
+        };
+        if self.supports_partitioning() {
+            return None;
+        }
+        Some(format!(
+            "{} does not support table partitioning; cannot create {}",
+            self.name(),
+            table
+        ))
+    }
+
+    /// Returns the comment emitted in place of partitioning DDL by
+    /// dialects that do not support it.
+    fn partitioning_unsupported(&self, table: &str) -> String {
+        format!(
+            "-- {} does not support table partitioning; cannot create {}",
+            self.name(),
+            table
+        )
+    }
+
+    /// Generates SQL for a partition of a partitioned table.
+    fn create_partition(&self, op: &CreatePartitionOp) -> String {
+        if !self.supports_partitioning() {
+            return self.partitioning_unsupported(&op.name);
+        }
+        let mut sql = String::from("CREATE TABLE ");
+        if op.if_not_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        sql.push_str(&format!(
+            "{} PARTITION OF {} ",
+            self.quote_table(op.schema.as_deref(), &op.name),
+            self.quote_table(op.schema.as_deref(), &op.parent)
+        ));
+        match &op.bound {
+            PartitionBound::Range { from, to } => sql.push_str(&format!(
+                "FOR VALUES FROM ({}) TO ({})",
+                from.join(", "),
+                to.join(", ")
+            )),
+            PartitionBound::List(values) => {
+                sql.push_str(&format!("FOR VALUES IN ({})", values.join(", ")));
+            }
+            PartitionBound::Hash { modulus, remainder } => sql.push_str(&format!(
+                "FOR VALUES WITH (MODULUS {modulus}, REMAINDER {remainder})"
+            )),
+            PartitionBound::Default => sql.push_str("DEFAULT"),
+        }
         sql
     }
 
@@ -81,7 +260,7 @@ pub trait MigrationDialect {
         if op.if_exists {
             sql.push_str("IF EXISTS ");
         }
-        sql.push_str(&self.quote_identifier(&op.name));
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
         if op.cascade {
             sql.push_str(" CASCADE");
         }
@@ -95,7 +274,7 @@ pub trait MigrationDialect {
     fn add_column(&self, op: &AddColumnOp) -> String {
         format!(
             "ALTER TABLE {} ADD COLUMN {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.column_definition(&op.column)
         )
     }
@@ -104,7 +283,7 @@ pub trait MigrationDialect {
     fn drop_column(&self, op: &DropColumnOp) -> String {
         format!(
             "ALTER TABLE {} DROP COLUMN {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.quote_identifier(&op.column)
         )
     }
@@ -125,40 +304,59 @@ pub trait MigrationDialect {
         if op.if_not_exists {
             sql.push_str("IF NOT EXISTS ");
         }
-        sql.push_str(&self.quote_identifier(&op.name));
-        sql.push_str(" ON ");
-        sql.push_str(&self.quote_identifier(&op.table));
+        // The schema goes on either the index or the table, never both
+        let schema = op.schema.as_deref();
+        if self.qualifies_index_name() {
+            sql.push_str(&self.quote_table(schema, &op.name));
+            sql.push_str(" ON ");
+            sql.push_str(&self.quote_identifier(&op.table));
+        } else {
+            sql.push_str(&self.quote_identifier(&op.name));
+            sql.push_str(" ON ");
+            sql.push_str(&self.quote_table(schema, &op.table));
+        }
 
         // Index type (if supported and not default)
         if op.index_type != IndexType::BTree {
             sql.push_str(&format!(" USING {}", self.index_type_sql(&op.index_type)));
         }
 
-        // Columns
+        // Columns and expressions
         sql.push_str(" (");
         let cols: Vec<String> = op
             .columns
             .iter()
-            .map(|c| self.quote_identifier(c))
+            .map(|c| match c {
+                IndexColumn::Column(name) => self.quote_identifier(name),
+                IndexColumn::Expr(expr) => format!("({expr})"),
+            })
             .collect();
         sql.push_str(&cols.join(", "));
         sql.push(')');
 
         // Partial index condition
         if let Some(ref condition) = op.condition {
-            sql.push_str(" WHERE ");
-            sql.push_str(condition);
+            sql.push_str(&format!(" WHERE {condition}"));
         }
 
         sql
     }
 
+    /// Returns whether `CREATE INDEX` puts the schema on the index
+    /// name (SQLite) rather than on the table name.
+    fn qualifies_index_name(&self) -> bool {
+        false
+    }
+
     /// Generates SQL for DROP INDEX.
     fn drop_index(&self, op: &DropIndexOp) -> String;
 
     /// Generates SQL for ADD FOREIGN KEY.
     fn add_foreign_key(&self, op: &super::operation::AddForeignKeyOp) -> String {
-        let mut sql = format!("ALTER TABLE {} ADD ", self.quote_identifier(&op.table));
+        let mut sql = format!(
+            "ALTER TABLE {} ADD ",
+            self.quote_table(op.schema.as_deref(), &op.table)
+        );
         if let Some(ref name) = op.name {
             sql.push_str(&format!("CONSTRAINT {} ", self.quote_identifier(name)));
         }
@@ -195,6 +393,125 @@ pub trait MigrationDialect {
     /// Generates SQL for DROP FOREIGN KEY.
     fn drop_foreign_key(&self, op: &super::operation::DropForeignKeyOp) -> String;
 
+    /// Generates SQL for CREATE TRIGGER.
+    ///
+    /// Dialects without trigger support emit a comment instead.
+    fn create_trigger(&self, op: &CreateTriggerOp) -> String {
+        format!(
+            "-- {} does not support triggers; cannot create {} on {}",
+            self.name(),
+            op.name,
+            op.table
+        )
+    }
+
+    /// Generates SQL for DROP TRIGGER.
+    fn drop_trigger(&self, op: &DropTriggerOp) -> String {
+        format!(
+            "-- {} does not support triggers; cannot drop {} on {}",
+            self.name(),
+            op.name,
+            op.table
+        )
+    }
+
+    /// Renders the event of a trigger, e.g. `UPDATE OF "email"`.
+    fn trigger_event(&self, event: &TriggerEvent) -> String {
+        match event {
+            TriggerEvent::Insert => "INSERT".to_string(),
+            TriggerEvent::Update(columns) if columns.is_empty() => "UPDATE".to_string(),
+            TriggerEvent::Update(columns) => {
+                let cols: Vec<String> = columns.iter().map(|c| self.quote_identifier(c)).collect();
+                format!("UPDATE OF {}", cols.join(", "))
+            }
+            TriggerEvent::Delete => "DELETE".to_string(),
+        }
+    }
+
+    /// Generates SQL for CREATE SEQUENCE.
+    fn create_sequence(&self, op: &CreateSequenceOp) -> String {
+        let mut sql = String::from("CREATE SEQUENCE ");
+        if op.if_not_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
+        if let Some(increment) = op.increment {
+            sql.push_str(&format!(" INCREMENT BY {increment}"));
+        }
+        if let Some(start) = op.start {
+            sql.push_str(&format!(" START WITH {start}"));
+        }
+        if op.cycle {
+            sql.push_str(" CYCLE");
+        }
+        sql
+    }
+
+    /// Generates SQL for ALTER SEQUENCE.
+    fn alter_sequence(&self, op: &AlterSequenceOp) -> String {
+        let mut sql = format!(
+            "ALTER SEQUENCE {}",
+            self.quote_table(op.schema.as_deref(), &op.name)
+        );
+        if let Some(increment) = op.increment {
+            sql.push_str(&format!(" INCREMENT BY {increment}"));
+        }
+        if let Some(restart) = op.restart {
+            sql.push_str(&format!(" RESTART WITH {restart}"));
+        }
+        match op.cycle {
+            Some(true) => sql.push_str(" CYCLE"),
+            Some(false) => sql.push_str(" NO CYCLE"),
+            None => {}
+        }
+        sql
+    }
+
+    /// Generates SQL for DROP SEQUENCE.
+    fn drop_sequence(&self, op: &DropSequenceOp) -> String {
+        let mut sql = String::from("DROP SEQUENCE ");
+        if op.if_exists {
+            sql.push_str("IF EXISTS ");
+        }
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
+        sql
+    }
+
+    /// Generates SQL for CREATE SCHEMA.
+    fn create_schema(&self, op: &CreateSchemaOp) -> String {
+        let mut sql = String::from("CREATE SCHEMA ");
+        if op.if_not_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        sql.push_str(&self.quote_identifier(&op.name));
+        sql
+    }
+
+    /// Generates SQL for DROP SCHEMA.
+    fn drop_schema(&self, op: &DropSchemaOp) -> String {
+        let mut sql = String::from("DROP SCHEMA ");
+        if op.if_exists {
+            sql.push_str("IF EXISTS ");
+        }
+        sql.push_str(&self.quote_identifier(&op.name));
+        if op.cascade {
+            sql.push_str(" CASCADE");
+        }
+        sql
+    }
+
+    /// Returns the operations that switch a column's autoincrement
+    /// strategy in place, or `None` if the dialect cannot do so
+    /// (the change then stays a [`DiffWarning`](super::DiffWarning)).
+    fn autoincrement_change(
+        &self,
+        _table: &str,
+        _column: &str,
+        _enabled: bool,
+    ) -> Option<Vec<Operation>> {
+        None
+    }
+
     /// Generates SQL for a column definition.
     fn column_definition(&self, col: &ColumnDefinition) -> String {
         let mut sql = format!(
@@ -225,6 +542,11 @@ pub trait MigrationDialect {
             sql.push_str(&self.render_default(default));
         }
 
+        if let Some(ref generated) = col.generated {
+            sql.push(' ');
+            sql.push_str(&generated.to_sql());
+        }
+
         if let Some(ref fk) = col.references {
             sql.push_str(" REFERENCES ");
             sql.push_str(&self.quote_identifier(&fk.table));
@@ -246,7 +568,12 @@ pub trait MigrationDialect {
         }
 
         if let Some(ref collation) = col.collation {
-            sql.push_str(&format!(" COLLATE {}", collation));
+            sql.push_str(" COLLATE ");
+            if is_valid_identifier(collation) {
+                sql.push_str(collation);
+            } else {
+                sql.push_str(&self.quote_identifier(collation));
+            }
         }
 
         sql
@@ -335,10 +662,24 @@ pub trait MigrationDialect {
         '"'
     }
 
-    /// Quotes an identifier.
+    /// Quotes an identifier, doubling any embedded quote characters.
     fn quote_identifier(&self, name: &str) -> String {
         let q = self.quote_char();
-        format!("{q}{name}{q}")
+        let escaped = name.replace(q, &format!("{q}{q}"));
+        format!("{q}{escaped}{q}")
+    }
+
+    /// Quotes a table (or index) name, qualified with its schema when
+    /// one is given.
+    fn quote_table(&self, schema: Option<&str>, name: &str) -> String {
+        match schema {
+            Some(schema) => format!(
+                "{}.{}",
+                self.quote_identifier(schema),
+                self.quote_identifier(name)
+            ),
+            None => self.quote_identifier(name),
+        }
     }
 
     /// Returns the AUTOINCREMENT keyword for this dialect.
@@ -354,3 +695,10 @@ pub trait MigrationDialect {
         }
     }
 }
+
+/// Returns the element type of a Rust `Vec<T>` type name, except
+/// `Vec<u8>`, which maps to a binary type.
+fn vec_element(rust_type: &str) -> Option<&str> {
+    let element = rust_type.strip_prefix("Vec<")?.strip_suffix('>')?.trim();
+    (element != "u8").then_some(element)
+}
@@ -4,7 +4,8 @@ use super::MigrationDialect;
 use crate::ast::DataType;
 use crate::migrations::column_builder::{ColumnDefinition, DefaultValue};
 use crate::migrations::operation::{
-    AlterColumnChange, AlterColumnOp, DropIndexOp, RenameColumnOp, RenameTableOp,
+    AlterColumnChange, AlterColumnOp, CreateTriggerOp, DropIndexOp, DropTriggerOp, RenameColumnOp,
+    RenameTableOp, TableOption, TriggerEvent,
 };
 use crate::schema::RustTypeMapping;
 
@@ -65,10 +66,39 @@ impl MigrationDialect for PostgresDialect {
             DataType::Timestamp => "TIMESTAMP".to_string(),
             DataType::Datetime => "TIMESTAMP".to_string(), // PostgreSQL uses TIMESTAMP
             DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::Array(inner) => format!("{}[]", self.map_data_type(inner)),
             DataType::Custom(name) => name.clone(),
         }
     }
 
+    fn supports_partitioning(&self) -> bool {
+        true
+    }
+
+    fn supports_triggers(&self) -> bool {
+        true
+    }
+
+    fn table_modifiers(&self, options: &[TableOption]) -> String {
+        if options.contains(&TableOption::Unlogged) {
+            "UNLOGGED ".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    fn table_options(&self, options: &[TableOption]) -> String {
+        options
+            .iter()
+            .filter_map(|option| match option {
+                TableOption::Tablespace(name) => {
+                    Some(format!(" TABLESPACE {}", self.quote_identifier(name)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     fn autoincrement_keyword(&self) -> String {
         // PostgreSQL uses SERIAL types instead of AUTOINCREMENT keyword
         // However, when PRIMARY KEY is specified with BIGINT, we don't change the type
@@ -106,6 +136,11 @@ impl MigrationDialect for PostgresDialect {
             sql.push_str(&self.render_default(default));
         }
 
+        if let Some(ref generated) = col.generated {
+            sql.push(' ');
+            sql.push_str(&generated.to_sql());
+        }
+
         if let Some(ref fk) = col.references {
             sql.push_str(" REFERENCES ");
             sql.push_str(&self.quote_identifier(&fk.table));
@@ -127,7 +162,7 @@ impl MigrationDialect for PostgresDialect {
         }
 
         if let Some(ref collation) = col.collation {
-            sql.push_str(&format!(" COLLATE \"{}\"", collation));
+            sql.push_str(&format!(" COLLATE {}", self.quote_identifier(collation)));
         }
 
         sql
@@ -149,7 +184,7 @@ impl MigrationDialect for PostgresDialect {
     fn rename_table(&self, op: &RenameTableOp) -> String {
         format!(
             "ALTER TABLE {} RENAME TO {}",
-            self.quote_identifier(&op.old_name),
+            self.quote_table(op.schema.as_deref(), &op.old_name),
             self.quote_identifier(&op.new_name)
         )
     }
@@ -157,14 +192,14 @@ impl MigrationDialect for PostgresDialect {
     fn rename_column(&self, op: &RenameColumnOp) -> String {
         format!(
             "ALTER TABLE {} RENAME COLUMN {} TO {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.quote_identifier(&op.old_name),
             self.quote_identifier(&op.new_name)
         )
     }
 
     fn alter_column(&self, op: &AlterColumnOp) -> String {
-        let table = self.quote_identifier(&op.table);
+        let table = self.quote_table(op.schema.as_deref(), &op.table);
         let column = self.quote_identifier(&op.column);
 
         match &op.change {
@@ -213,6 +248,18 @@ impl MigrationDialect for PostgresDialect {
                     op.table, op.column
                 )
             }
+            AlterColumnChange::SetCollation {
+                data_type,
+                collation,
+            } => {
+                format!(
+                    "ALTER TABLE {} ALTER COLUMN {} TYPE {} COLLATE {}",
+                    table,
+                    column,
+                    self.map_data_type(data_type),
+                    self.quote_identifier(collation.as_deref().unwrap_or("default"))
+                )
+            }
         }
     }
 
@@ -221,21 +268,88 @@ impl MigrationDialect for PostgresDialect {
         if op.if_exists {
             sql.push_str("IF EXISTS ");
         }
-        sql.push_str(&self.quote_identifier(&op.name));
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
         sql
     }
 
     fn drop_foreign_key(&self, op: &super::super::operation::DropForeignKeyOp) -> String {
         format!(
             "ALTER TABLE {} DROP CONSTRAINT {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.quote_identifier(&op.name)
         )
     }
+
+    fn create_trigger(&self, op: &CreateTriggerOp) -> String {
+        // PostgreSQL triggers run a function, so the body becomes a
+        // PL/pgSQL function named after the trigger.
+        let schema = op.schema.as_deref();
+        let function = self.quote_table(schema, &format!("{}_fn", op.name));
+        let mut body = String::new();
+        for statement in &op.body {
+            body.push_str(&format!("    {statement};\n"));
+        }
+        // BEFORE row triggers skip the change when they return NULL.
+        let returns = match (op.for_each_row, &op.event) {
+            (false, _) => "NULL",
+            (true, TriggerEvent::Delete) => "OLD",
+            (true, _) => "NEW",
+        };
+        let mut tag = String::from("$$");
+        let mut n = 0;
+        while body.contains(&tag) {
+            n += 1;
+            tag = format!("$body{n}$");
+        }
+
+        let mut sql = format!(
+            "CREATE OR REPLACE FUNCTION {function}() RETURNS trigger AS {tag}\n\
+             BEGIN\n{body}    RETURN {returns};\nEND;\n{tag} LANGUAGE plpgsql;\n"
+        );
+        // PostgreSQL has no CREATE TRIGGER IF NOT EXISTS; replacing is
+        // the idempotent equivalent.
+        sql.push_str(if op.if_not_exists {
+            "CREATE OR REPLACE TRIGGER "
+        } else {
+            "CREATE TRIGGER "
+        });
+        sql.push_str(&format!(
+            "{} {} {} ON {} FOR EACH {}",
+            self.quote_identifier(&op.name),
+            op.timing.as_sql(),
+            self.trigger_event(&op.event),
+            self.quote_table(schema, &op.table),
+            if op.for_each_row { "ROW" } else { "STATEMENT" }
+        ));
+        if let Some(ref condition) = op.condition {
+            sql.push_str(&format!(" WHEN ({condition})"));
+        }
+        sql.push_str(&format!(" EXECUTE FUNCTION {function}()"));
+        sql
+    }
+
+    fn drop_trigger(&self, op: &DropTriggerOp) -> String {
+        let schema = op.schema.as_deref();
+        let mut sql = String::from("DROP TRIGGER ");
+        if op.if_exists {
+            sql.push_str("IF EXISTS ");
+        }
+        // The trigger takes the schema of its table.
+        sql.push_str(&format!(
+            "{} ON {};\nDROP FUNCTION IF EXISTS {}()",
+            self.quote_identifier(&op.name),
+            self.quote_table(schema, &op.table),
+            self.quote_table(schema, &format!("{}_fn", op.name))
+        ));
+        sql
+    }
 }
 
 impl RustTypeMapping for PostgresDialect {
     fn map_type(&self, rust_type: &str) -> DataType {
+        if let Some(element) = super::vec_element(rust_type) {
+            return DataType::Array(Box::new(self.map_type(element)));
+        }
         match rust_type {
             "bool" => DataType::Boolean,
             "i8" | "i16" | "u8" | "u16" => DataType::Smallint,
@@ -250,13 +364,17 @@ impl RustTypeMapping for PostgresDialect {
             _ => DataType::Text,
         }
     }
+
+    fn normalize_default(&self, value: &DefaultValue) -> DefaultValue {
+        value.normalized_with(&[("now()", "CURRENT_TIMESTAMP")])
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::migrations::column_builder::{bigint, varchar};
-    use crate::migrations::table_builder::CreateTableBuilder;
+    use crate::migrations::table_builder::{CreateIndexBuilder, CreateTableBuilder};
 
     #[test]
     fn test_postgres_data_types() {
@@ -278,6 +396,26 @@ mod tests {
             }),
             "DECIMAL(10, 2)"
         );
+        assert_eq!(
+            dialect.map_data_type(&DataType::Array(Box::new(DataType::Varchar(Some(20))))),
+            "VARCHAR(20)[]"
+        );
+    }
+
+    #[test]
+    fn test_postgres_maps_vec_to_array() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            dialect.map_type("Vec<i32>"),
+            DataType::Array(Box::new(DataType::Integer))
+        );
+        assert_eq!(
+            dialect.map_type("Vec<Vec<String>>"),
+            DataType::Array(Box::new(DataType::Array(Box::new(DataType::Varchar(
+                Some(255)
+            )))))
+        );
+        assert_eq!(dialect.map_type("Vec<u8>"), DataType::Blob);
     }
 
     #[test]
@@ -301,6 +439,7 @@ mod tests {
 
         // Set NOT NULL
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "email".to_string(),
             change: AlterColumnChange::SetNullable(false),
@@ -312,6 +451,7 @@ mod tests {
 
         // Drop NOT NULL
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "email".to_string(),
             change: AlterColumnChange::SetNullable(true),
@@ -323,6 +463,7 @@ mod tests {
 
         // Change type
         let op = AlterColumnOp {
+            schema: None,
             table: "users".to_string(),
             column: "age".to_string(),
             change: AlterColumnChange::SetDataType(DataType::Bigint),
@@ -331,12 +472,59 @@ mod tests {
             dialect.alter_column(&op),
             "ALTER TABLE \"users\" ALTER COLUMN \"age\" TYPE BIGINT"
         );
+
+        // Change collation
+        let op = AlterColumnOp {
+            schema: None,
+            table: "users".to_string(),
+            column: "email".to_string(),
+            change: AlterColumnChange::SetCollation {
+                data_type: DataType::Text,
+                collation: Some("C".to_string()),
+            },
+        };
+        assert_eq!(
+            dialect.alter_column(&op),
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" TYPE TEXT COLLATE \"C\""
+        );
+
+        // Reset collation
+        let op = AlterColumnOp {
+            schema: None,
+            table: "users".to_string(),
+            column: "email".to_string(),
+            change: AlterColumnChange::SetCollation {
+                data_type: DataType::Text,
+                collation: None,
+            },
+        };
+        assert_eq!(
+            dialect.alter_column(&op),
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" TYPE TEXT COLLATE \"default\""
+        );
+
+        // Quotes in the collation name are escaped
+        let op = AlterColumnOp {
+            schema: None,
+            table: "users".to_string(),
+            column: "email".to_string(),
+            change: AlterColumnChange::SetCollation {
+                data_type: DataType::Text,
+                collation: Some("x\" ; DROP TABLE users; --".to_string()),
+            },
+        };
+        assert_eq!(
+            dialect.alter_column(&op),
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" TYPE TEXT \
+             COLLATE \"x\"\" ; DROP TABLE users; --\""
+        );
     }
 
     #[test]
     fn test_drop_foreign_key() {
         let dialect = PostgresDialect::new();
         let op = super::super::super::operation::DropForeignKeyOp {
+            schema: None,
             table: "invoices".to_string(),
             name: "fk_invoices_user".to_string(),
         };
@@ -345,4 +533,301 @@ mod tests {
             "ALTER TABLE \"invoices\" DROP CONSTRAINT \"fk_invoices_user\""
         );
     }
+
+    #[test]
+    fn test_create_expression_index_sql() {
+        let dialect = PostgresDialect::new();
+        let op = CreateIndexBuilder::new()
+            .name("idx_users_lower_email")
+            .on_table("users")
+            .column("tenant_id")
+            .expr_sql("lower(email)")
+            .unwrap()
+            .where_sql("deleted_at IS NULL")
+            .unwrap()
+            .unique()
+            .build();
+        assert_eq!(
+            dialect.create_index(&op),
+            "CREATE UNIQUE INDEX \"idx_users_lower_email\" ON \"users\" \
+             (\"tenant_id\", (lower(email))) WHERE deleted_at IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_create_trigger_sql() {
+        use crate::migrations::operation::{CreateTriggerOp, Operation, TriggerTiming};
+
+        let dialect = PostgresDialect::new();
+        let op = CreateTriggerOp::new(
+            "orders_log",
+            "orders",
+            TriggerTiming::Before,
+            TriggerEvent::Delete,
+        )
+        .then_sql("INSERT INTO log (note) VALUES ('cost $$')")
+        .unwrap();
+        assert_eq!(
+            dialect.generate_sql(&op.into()),
+            "CREATE OR REPLACE FUNCTION \"orders_log_fn\"() RETURNS trigger AS $body1$\n\
+             BEGIN\n    INSERT INTO log (note) VALUES ('cost $$');\n    RETURN OLD;\n\
+             END;\n$body1$ LANGUAGE plpgsql;\n\
+             CREATE TRIGGER \"orders_log\" BEFORE DELETE ON \"orders\" FOR EACH ROW \
+             EXECUTE FUNCTION \"orders_log_fn\"()"
+        );
+        assert_eq!(
+            dialect.generate_sql(&Operation::drop_trigger("orders_log", "orders")),
+            "DROP TRIGGER \"orders_log\" ON \"orders\";\n\
+             DROP FUNCTION IF EXISTS \"orders_log_fn\"()"
+        );
+    }
+
+    #[test]
+    fn test_schema_qualified_trigger_sql() {
+        use crate::migrations::operation::{CreateTriggerOp, Operation, TriggerTiming};
+
+        let dialect = PostgresDialect::new();
+        let op = CreateTriggerOp::new(
+            "orders_log",
+            "orders",
+            TriggerTiming::After,
+            TriggerEvent::Insert,
+        )
+        .schema("shop");
+        let sql = dialect.create_trigger(&op);
+        assert!(sql.starts_with(
+            "CREATE OR REPLACE FUNCTION \"shop\".\"orders_log_fn\"() RETURNS trigger"
+        ));
+        assert!(sql.ends_with(
+            "CREATE TRIGGER \"orders_log\" AFTER INSERT ON \"shop\".\"orders\" \
+             FOR EACH ROW EXECUTE FUNCTION \"shop\".\"orders_log_fn\"()"
+        ));
+        assert_eq!(
+            dialect
+                .generate_sql(&Operation::drop_trigger("orders_log", "orders").in_schema("shop")),
+            "DROP TRIGGER \"orders_log\" ON \"shop\".\"orders\";\n\
+             DROP FUNCTION IF EXISTS \"shop\".\"orders_log_fn\"()"
+        );
+    }
+
+    #[test]
+    fn test_create_statement_trigger_sql() {
+        use crate::ast::Expr;
+        use crate::migrations::operation::{CreateTriggerOp, TriggerTiming};
+
+        let dialect = PostgresDialect::new();
+        let op = CreateTriggerOp::new(
+            "users_touch",
+            "users",
+            TriggerTiming::After,
+            TriggerEvent::Insert,
+        )
+        .for_each_statement()
+        .when(Expr::boolean(true))
+        .if_not_exists();
+        let sql = dialect.create_trigger(&op);
+        assert!(sql.contains("AS $$\nBEGIN\n    RETURN NULL;\nEND;\n$$ LANGUAGE plpgsql;"));
+        assert!(sql.ends_with(
+            "CREATE OR REPLACE TRIGGER \"users_touch\" AFTER INSERT ON \"users\" \
+             FOR EACH STATEMENT WHEN (TRUE) EXECUTE FUNCTION \"users_touch_fn\"()"
+        ));
+    }
+
+    #[test]
+    fn test_sequence_sql() {
+        use crate::migrations::operation::{AlterSequenceOp, CreateSequenceOp, Operation};
+
+        let dialect = PostgresDialect::new();
+        let op = CreateSequenceOp::new("invoice_no")
+            .start(1000)
+            .increment(5)
+            .cycle()
+            .if_not_exists();
+        assert_eq!(
+            dialect.generate_sql(&op.into()),
+            "CREATE SEQUENCE IF NOT EXISTS \"invoice_no\" INCREMENT BY 5 START WITH 1000 CYCLE"
+        );
+        let op = AlterSequenceOp {
+            schema: None,
+            name: "invoice_no".to_string(),
+            restart: Some(1),
+            increment: None,
+            cycle: Some(false),
+        };
+        assert_eq!(
+            dialect.generate_sql(&op.into()),
+            "ALTER SEQUENCE \"invoice_no\" RESTART WITH 1 NO CYCLE"
+        );
+        assert_eq!(
+            dialect.generate_sql(&Operation::drop_sequence("invoice_no")),
+            "DROP SEQUENCE \"invoice_no\""
+        );
+
+        let op = CreateSequenceOp::new("invoice_no").schema("billing");
+        assert_eq!(
+            dialect.generate_sql(&op.into()),
+            "CREATE SEQUENCE \"billing\".\"invoice_no\""
+        );
+        let op = AlterSequenceOp {
+            schema: Some("billing".to_string()),
+            name: "invoice_no".to_string(),
+            restart: None,
+            increment: Some(2),
+            cycle: None,
+        };
+        assert_eq!(
+            dialect.generate_sql(&op.into()),
+            "ALTER SEQUENCE \"billing\".\"invoice_no\" INCREMENT BY 2"
+        );
+        assert_eq!(
+            dialect.generate_sql(&Operation::drop_sequence("invoice_no").in_schema("billing")),
+            "DROP SEQUENCE \"billing\".\"invoice_no\""
+        );
+    }
+
+    #[test]
+    fn test_partitioned_table_sql() {
+        use crate::migrations::column_builder::timestamp;
+        use crate::migrations::operation::{Operation, PartitionBound, PartitionStrategy};
+
+        let dialect = PostgresDialect::new();
+        let op = CreateTableBuilder::new()
+            .name("events")
+            .column(bigint("id").not_null().build())
+            .column(timestamp("created_at").not_null().build())
+            .partition_by(PartitionStrategy::Range, &["created_at"])
+            .build();
+        assert_eq!(
+            dialect.create_table(&op),
+            "CREATE TABLE \"events\" (\n    \"id\" BIGINT NOT NULL,\n    \
+             \"created_at\" TIMESTAMP NOT NULL\n) PARTITION BY RANGE (\"created_at\")"
+        );
+
+        let cases = [
+            (
+                PartitionBound::Range {
+                    from: vec!["MINVALUE".to_string()],
+                    to: vec!["'2024-01-01'".to_string()],
+                },
+                "FOR VALUES FROM (MINVALUE) TO ('2024-01-01')",
+            ),
+            (
+                PartitionBound::List(vec!["'eu'".to_string(), "'us'".to_string()]),
+                "FOR VALUES IN ('eu', 'us')",
+            ),
+            (
+                PartitionBound::Hash {
+                    modulus: 4,
+                    remainder: 1,
+                },
+                "FOR VALUES WITH (MODULUS 4, REMAINDER 1)",
+            ),
+            (PartitionBound::Default, "DEFAULT"),
+        ];
+        for (bound, expected) in cases {
+            let op = Operation::create_partition("events_p", "events", bound);
+            assert_eq!(
+                dialect.generate_sql(&op),
+                format!("CREATE TABLE \"events_p\" PARTITION OF \"events\" {expected}")
+            );
+        }
+
+        let op = Operation::create_partition("events_p", "events", PartitionBound::Default)
+            .in_schema("logs");
+        assert_eq!(
+            dialect.generate_sql(&op),
+            "CREATE TABLE \"logs\".\"events_p\" PARTITION OF \"logs\".\"events\" DEFAULT"
+        );
+    }
+
+    #[test]
+    fn test_table_options_sql() {
+        use crate::migrations::operation::TableOption;
+
+        let dialect = PostgresDialect::new();
+        let op = CreateTableBuilder::new()
+            .name("cache")
+            .column(varchar("key", 64).primary_key().build())
+            .option(TableOption::Unlogged)
+            .option(TableOption::Tablespace("fast".to_string()))
+            .option(TableOption::Strict)
+            .build();
+        assert_eq!(
+            dialect.create_table(&op),
+            "CREATE UNLOGGED TABLE \"cache\" (\n    \"key\" VARCHAR(64) PRIMARY KEY\n) \
+             TABLESPACE \"fast\""
+        );
+    }
+
+    #[test]
+    fn test_schema_qualified_sql() {
+        use crate::migrations::operation::Operation;
+
+        let dialect = PostgresDialect::new();
+        let op = CreateTableBuilder::new()
+            .schema("billing")
+            .name("invoices")
+            .column(bigint("id").primary_key().build())
+            .build();
+        assert_eq!(
+            dialect.create_table(&op),
+            "CREATE TABLE \"billing\".\"invoices\" (\n    \"id\" BIGINT PRIMARY KEY\n)"
+        );
+
+        let op = Operation::rename_table("invoices", "bills").in_schema("billing");
+        assert_eq!(
+            dialect.generate_sql(&op),
+            "ALTER TABLE \"billing\".\"invoices\" RENAME TO \"bills\""
+        );
+
+        let op = CreateIndexBuilder::new()
+            .schema("billing")
+            .name("idx_invoices_total")
+            .on_table("invoices")
+            .column("total")
+            .build();
+        assert_eq!(
+            dialect.create_index(&op),
+            "CREATE INDEX \"idx_invoices_total\" ON \"billing\".\"invoices\" (\"total\")"
+        );
+
+        let op = Operation::DropIndex(DropIndexOp {
+            schema: Some("billing".into()),
+            name: "idx_invoices_total".into(),
+            table: None,
+            if_exists: true,
+        });
+        assert_eq!(
+            dialect.generate_sql(&op),
+            "DROP INDEX IF EXISTS \"billing\".\"idx_invoices_total\""
+        );
+    }
+
+    #[test]
+    fn test_schema_sql() {
+        use crate::migrations::operation::{CreateSchemaOp, DropSchemaOp, Operation};
+
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            dialect.generate_sql(&Operation::create_schema("billing")),
+            "CREATE SCHEMA \"billing\""
+        );
+        let op = CreateSchemaOp {
+            name: "billing".into(),
+            if_not_exists: true,
+        };
+        assert_eq!(
+            dialect.create_schema(&op),
+            "CREATE SCHEMA IF NOT EXISTS \"billing\""
+        );
+        let op = DropSchemaOp {
+            name: "billing".into(),
+            if_exists: true,
+            cascade: true,
+        };
+        assert_eq!(
+            dialect.drop_schema(&op),
+            "DROP SCHEMA IF EXISTS \"billing\" CASCADE"
+        );
+    }
 }
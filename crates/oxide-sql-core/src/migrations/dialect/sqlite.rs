@@ -2,8 +2,11 @@
 
 use super::MigrationDialect;
 use crate::ast::DataType;
+use crate::migrations::column_builder::DefaultValue;
 use crate::migrations::operation::{
-    AlterColumnChange, AlterColumnOp, DropIndexOp, RenameColumnOp, RenameTableOp,
+    AlterColumnChange, AlterColumnOp, AlterSequenceOp, CreateSchemaOp, CreateSequenceOp,
+    CreateTriggerOp, DropIndexOp, DropSchemaOp, DropSequenceOp, DropTriggerOp, RenameColumnOp,
+    RenameTableOp, TableOption,
 };
 use crate::schema::RustTypeMapping;
 
@@ -24,6 +27,23 @@ impl MigrationDialect for SqliteDialect {
         "sqlite"
     }
 
+    fn supports_add_foreign_key(&self) -> bool {
+        false
+    }
+
+    fn supports_forward_foreign_keys(&self) -> bool {
+        // References are only checked when rows change
+        true
+    }
+
+    fn supports_triggers(&self) -> bool {
+        true
+    }
+
+    fn supports_statement_triggers(&self) -> bool {
+        false
+    }
+
     fn map_data_type(&self, dt: &DataType) -> String {
         // SQLite has dynamic typing with type affinity
         match dt {
@@ -36,6 +56,7 @@ impl MigrationDialect for SqliteDialect {
                 "TEXT".to_string()
             }
             DataType::Boolean => "INTEGER".to_string(), // SQLite has no bool, use 0/1
+            DataType::Array(_) => "TEXT".to_string(),   // no arrays; store JSON text
             DataType::Custom(name) => name.clone(),
         }
     }
@@ -44,10 +65,26 @@ impl MigrationDialect for SqliteDialect {
         " AUTOINCREMENT".to_string()
     }
 
+    fn table_options(&self, options: &[TableOption]) -> String {
+        let rendered: Vec<&str> = options
+            .iter()
+            .filter_map(|option| match option {
+                TableOption::WithoutRowid => Some("WITHOUT ROWID"),
+                TableOption::Strict => Some("STRICT"),
+                _ => None,
+            })
+            .collect();
+        if rendered.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", rendered.join(", "))
+        }
+    }
+
     fn rename_table(&self, op: &RenameTableOp) -> String {
         format!(
             "ALTER TABLE {} RENAME TO {}",
-            self.quote_identifier(&op.old_name),
+            self.quote_table(op.schema.as_deref(), &op.old_name),
             self.quote_identifier(&op.new_name)
         )
     }
@@ -56,7 +93,7 @@ impl MigrationDialect for SqliteDialect {
         // SQLite 3.25.0+ supports RENAME COLUMN
         format!(
             "ALTER TABLE {} RENAME COLUMN {} TO {}",
-            self.quote_identifier(&op.table),
+            self.quote_table(op.schema.as_deref(), &op.table),
             self.quote_identifier(&op.old_name),
             self.quote_identifier(&op.new_name)
         )
@@ -112,6 +149,13 @@ impl MigrationDialect for SqliteDialect {
                     op.table, op.column
                 )
             }
+            AlterColumnChange::SetCollation { .. } => {
+                format!(
+                    "-- SQLite does not support ALTER COLUMN COLLATE for {}.{}; \
+                     table recreation required",
+                    op.table, op.column
+                )
+            }
         }
     }
 
@@ -121,10 +165,28 @@ impl MigrationDialect for SqliteDialect {
             sql.push_str("IF EXISTS ");
         }
         // SQLite index names are global, not per-table
-        sql.push_str(&self.quote_identifier(&op.name));
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
         sql
     }
 
+    fn qualifies_index_name(&self) -> bool {
+        true
+    }
+
+    fn create_schema(&self, op: &CreateSchemaOp) -> String {
+        format!(
+            "-- SQLite does not support CREATE SCHEMA; attach a database as {} instead",
+            op.name
+        )
+    }
+
+    fn drop_schema(&self, op: &DropSchemaOp) -> String {
+        format!(
+            "-- SQLite does not support DROP SCHEMA; detach database {} instead",
+            op.name
+        )
+    }
+
     fn drop_foreign_key(&self, op: &super::super::operation::DropForeignKeyOp) -> String {
         // SQLite does not support DROP CONSTRAINT; requires table recreation
         format!(
@@ -133,6 +195,69 @@ impl MigrationDialect for SqliteDialect {
             op.name, op.table
         )
     }
+
+    fn create_trigger(&self, op: &CreateTriggerOp) -> String {
+        // SQLite triggers are always row-level
+        if !op.for_each_row {
+            return format!(
+                "-- SQLite does not support FOR EACH STATEMENT triggers; cannot create {} on {}",
+                op.name, op.table
+            );
+        }
+        let mut sql = String::from("CREATE TRIGGER ");
+        if op.if_not_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        // The schema qualifies the trigger; its table must live in the
+        // same schema and is named without one.
+        sql.push_str(&format!(
+            "{} {} {} ON {} FOR EACH ROW",
+            self.quote_table(op.schema.as_deref(), &op.name),
+            op.timing.as_sql(),
+            self.trigger_event(&op.event),
+            self.quote_identifier(&op.table)
+        ));
+        if let Some(ref condition) = op.condition {
+            sql.push_str(&format!(" WHEN {condition}"));
+        }
+        sql.push_str(" BEGIN\n");
+        for statement in &op.body {
+            sql.push_str(&format!("    {statement};\n"));
+        }
+        sql.push_str("END");
+        sql
+    }
+
+    fn drop_trigger(&self, op: &DropTriggerOp) -> String {
+        let mut sql = String::from("DROP TRIGGER ");
+        if op.if_exists {
+            sql.push_str("IF EXISTS ");
+        }
+        // SQLite trigger names are per-schema, not per-table
+        sql.push_str(&self.quote_table(op.schema.as_deref(), &op.name));
+        sql
+    }
+
+    fn create_sequence(&self, op: &CreateSequenceOp) -> String {
+        format!(
+            "-- SQLite does not support sequences; cannot create {}",
+            op.name
+        )
+    }
+
+    fn alter_sequence(&self, op: &AlterSequenceOp) -> String {
+        format!(
+            "-- SQLite does not support sequences; cannot alter {}",
+            op.name
+        )
+    }
+
+    fn drop_sequence(&self, op: &DropSequenceOp) -> String {
+        format!(
+            "-- SQLite does not support sequences; cannot drop {}",
+            op.name
+        )
+    }
 }
 
 impl RustTypeMapping for SqliteDialect {
@@ -150,12 +275,56 @@ impl RustTypeMapping for SqliteDialect {
             _ => DataType::Text, // safe fallback for SQLite
         }
     }
+
+    fn types_equivalent(&self, a: &DataType, b: &DataType) -> bool {
+        a == b || affinity(&self.map_data_type(a)) == affinity(&self.map_data_type(b))
+    }
+
+    fn normalize_default(&self, value: &DefaultValue) -> DefaultValue {
+        value.normalized_with(&[
+            ("datetime('now')", "CURRENT_TIMESTAMP"),
+            ("date('now')", "CURRENT_DATE"),
+            ("time('now')", "CURRENT_TIME"),
+        ])
+    }
+
+    fn stores_booleans(&self, data_type: &DataType) -> bool {
+        affinity(&self.map_data_type(data_type)) == Affinity::Integer
+    }
+}
+
+/// SQLite column type affinity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Affinity {
+    Integer,
+    Text,
+    Blob,
+    Real,
+    Numeric,
+}
+
+/// Determines the affinity of a declared column type, following the
+/// rules in order from <https://www.sqlite.org/datatype3.html>.
+fn affinity(type_name: &str) -> Affinity {
+    let upper = type_name.to_ascii_uppercase();
+    if upper.contains("INT") {
+        Affinity::Integer
+    } else if ["CHAR", "CLOB", "TEXT"].iter().any(|s| upper.contains(s)) {
+        Affinity::Text
+    } else if upper.contains("BLOB") || upper.trim().is_empty() {
+        Affinity::Blob
+    } else if ["REAL", "FLOA", "DOUB"].iter().any(|s| upper.contains(s)) {
+        Affinity::Real
+    } else {
+        Affinity::Numeric
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::migrations::column_builder::{bigint, boolean, timestamp, varchar};
+    use crate::migrations::PostgresDialect;
+    use crate::migrations::column_builder::{bigint, boolean, integer, timestamp, varchar};
     use crate::migrations::operation::{DropTableOp, Operation};
     use crate::migrations::table_builder::CreateTableBuilder;
 
@@ -169,6 +338,42 @@ mod tests {
         assert_eq!(dialect.map_data_type(&DataType::Blob), "BLOB");
         assert_eq!(dialect.map_data_type(&DataType::Boolean), "INTEGER");
         assert_eq!(dialect.map_data_type(&DataType::Timestamp), "TEXT");
+        assert_eq!(
+            dialect.map_data_type(&DataType::Array(Box::new(DataType::Integer))),
+            "TEXT"
+        );
+    }
+
+    #[test]
+    fn test_types_equivalent_by_affinity() {
+        let dialect = SqliteDialect::new();
+        assert!(dialect.types_equivalent(&DataType::Varchar(Some(255)), &DataType::Text));
+        assert!(dialect.types_equivalent(&DataType::Char(Some(2)), &DataType::Timestamp));
+        assert!(dialect.types_equivalent(&DataType::Boolean, &DataType::Bigint));
+        assert!(
+            dialect.types_equivalent(&DataType::Custom("MEDIUMINT".into()), &DataType::Integer)
+        );
+        assert!(dialect.types_equivalent(
+            &DataType::Double,
+            &DataType::Decimal {
+                precision: Some(10),
+                scale: Some(2)
+            }
+        ));
+        assert!(!dialect.types_equivalent(&DataType::Text, &DataType::Blob));
+        assert!(!dialect.types_equivalent(&DataType::Integer, &DataType::Real));
+        assert!(!dialect.types_equivalent(&DataType::Custom("NUMERIC".into()), &DataType::Text));
+    }
+
+    #[test]
+    fn test_affinity_rules() {
+        assert_eq!(affinity("BIGINT"), Affinity::Integer);
+        assert_eq!(affinity("NVARCHAR(100)"), Affinity::Text);
+        assert_eq!(affinity(""), Affinity::Blob);
+        assert_eq!(affinity("double precision"), Affinity::Real);
+        assert_eq!(affinity("DATETIME"), Affinity::Numeric);
+        // "INT" wins over "CHAR", per the rule order.
+        assert_eq!(affinity("CHARINT"), Affinity::Integer);
     }
 
     #[test]
@@ -194,11 +399,19 @@ mod tests {
         assert!(sql.contains("DEFAULT CURRENT_TIMESTAMP"));
     }
 
+    #[test]
+    fn test_quote_identifier_escapes_quotes() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(dialect.quote_identifier("users"), "\"users\"");
+        assert_eq!(dialect.quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+
     #[test]
     fn test_drop_table_sql() {
         let dialect = SqliteDialect::new();
 
         let op = DropTableOp {
+            schema: None,
             name: "users".to_string(),
             if_exists: false,
             cascade: false,
@@ -206,6 +419,7 @@ mod tests {
         assert_eq!(dialect.drop_table(&op), "DROP TABLE \"users\"");
 
         let op = DropTableOp {
+            schema: None,
             name: "users".to_string(),
             if_exists: true,
             cascade: false,
@@ -232,6 +446,7 @@ mod tests {
     fn test_rename_table_sql() {
         let dialect = SqliteDialect::new();
         let op = RenameTableOp {
+            schema: None,
             old_name: "old_users".to_string(),
             new_name: "users".to_string(),
         };
@@ -245,6 +460,7 @@ mod tests {
     fn test_rename_column_sql() {
         let dialect = SqliteDialect::new();
         let op = RenameColumnOp {
+            schema: None,
             table: "users".to_string(),
             old_name: "name".to_string(),
             new_name: "full_name".to_string(),
@@ -254,4 +470,213 @@ mod tests {
             "ALTER TABLE \"users\" RENAME COLUMN \"name\" TO \"full_name\""
         );
     }
+
+    #[test]
+    fn test_column_collation() {
+        let dialect = SqliteDialect::new();
+        let col = varchar("name", 255).collation("NOCASE").build();
+        assert_eq!(
+            dialect.column_definition(&col),
+            "\"name\" TEXT COLLATE NOCASE"
+        );
+        let col = varchar("name", 255).collation("no case").build();
+        assert_eq!(
+            dialect.column_definition(&col),
+            "\"name\" TEXT COLLATE \"no case\""
+        );
+    }
+
+    #[test]
+    fn test_alter_collation_needs_rebuild() {
+        let dialect = SqliteDialect::new();
+        let op = AlterColumnOp {
+            schema: None,
+            table: "users".to_string(),
+            column: "name".to_string(),
+            change: AlterColumnChange::SetCollation {
+                data_type: DataType::Text,
+                collation: Some("NOCASE".to_string()),
+            },
+        };
+        assert_eq!(
+            dialect.alter_column(&op),
+            "-- SQLite does not support ALTER COLUMN COLLATE for users.name; \
+             table recreation required"
+        );
+    }
+
+    fn audit_trigger() -> CreateTriggerOp {
+        use crate::ast::Expr;
+        use crate::migrations::operation::{TriggerEvent, TriggerTiming};
+
+        CreateTriggerOp::new(
+            "users_audit",
+            "users",
+            TriggerTiming::After,
+            TriggerEvent::Update(vec!["email".to_string()]),
+        )
+        .when(Expr::qualified_column("OLD", "email").not_eq(Expr::qualified_column("NEW", "email")))
+        .then_sql("INSERT INTO audit (user_id, old_email) VALUES (NEW.id, OLD.email)")
+        .unwrap()
+    }
+
+    #[test]
+    fn test_create_trigger_sql() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(
+            dialect.generate_sql(&audit_trigger().into()),
+            "CREATE TRIGGER \"users_audit\" AFTER UPDATE OF \"email\" ON \"users\" \
+             FOR EACH ROW WHEN OLD.email != NEW.email BEGIN\n    \
+             INSERT INTO audit (user_id, old_email) VALUES (NEW.id, OLD.email);\nEND"
+        );
+        assert_eq!(
+            dialect.generate_sql(&Operation::drop_trigger("users_audit", "users")),
+            "DROP TRIGGER \"users_audit\""
+        );
+
+        let sql = dialect.generate_sql(&audit_trigger().schema("main").into());
+        assert!(
+            sql.starts_with(
+                "CREATE TRIGGER \"main\".\"users_audit\" AFTER UPDATE OF \"email\" ON \"users\""
+            ),
+            "{sql}"
+        );
+        assert_eq!(
+            dialect
+                .generate_sql(&Operation::drop_trigger("users_audit", "users").in_schema("main")),
+            "DROP TRIGGER \"main\".\"users_audit\""
+        );
+    }
+
+    #[test]
+    fn test_create_statement_trigger_unsupported() {
+        let dialect = SqliteDialect::new();
+        let op = audit_trigger().for_each_statement();
+        assert!(
+            dialect
+                .create_trigger(&op)
+                .starts_with("-- SQLite does not support")
+        );
+        assert_eq!(
+            dialect.unsupported_reason(&op.into()).as_deref(),
+            Some(
+                "sqlite does not support FOR EACH STATEMENT triggers; cannot create users_audit on users"
+            )
+        );
+        assert_eq!(dialect.unsupported_reason(&audit_trigger().into()), None);
+    }
+
+    #[test]
+    fn test_sequences_unsupported() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(
+            dialect.generate_sql(&Operation::drop_sequence("seq_ids")),
+            "-- SQLite does not support sequences; cannot drop seq_ids"
+        );
+    }
+
+    #[test]
+    fn test_partitioning_unsupported() {
+        use crate::migrations::operation::{PartitionBound, PartitionStrategy};
+
+        let dialect = SqliteDialect::new();
+        let op = CreateTableBuilder::new()
+            .name("events")
+            .column(bigint("id").build())
+            .partition_by(PartitionStrategy::Hash, &["id"])
+            .build();
+        assert_eq!(
+            dialect.create_table(&op),
+            "-- sqlite does not support table partitioning; cannot create events"
+        );
+        let op = Operation::create_partition("events_p0", "events", PartitionBound::Default);
+        assert_eq!(
+            dialect.generate_sql(&op),
+            "-- sqlite does not support table partitioning; cannot create events_p0"
+        );
+    }
+
+    #[test]
+    fn test_table_options_sql() {
+        let dialect = SqliteDialect::new();
+        let op = CreateTableBuilder::new()
+            .name("kv")
+            .column(varchar("key", 64).primary_key().build())
+            .option(TableOption::WithoutRowid)
+            .option(TableOption::Strict)
+            .option(TableOption::Tablespace("fast".to_string()))
+            .build();
+        assert_eq!(
+            dialect.create_table(&op),
+            "CREATE TABLE \"kv\" (\n    \"key\" TEXT PRIMARY KEY\n) WITHOUT ROWID, STRICT"
+        );
+    }
+
+    #[test]
+    fn test_without_rowid_conflicts_are_unsupported() {
+        let table = |pk: bool, autoincrement: bool| {
+            let mut id = integer("id");
+            if pk {
+                id = id.primary_key();
+            }
+            if autoincrement {
+                id = id.autoincrement();
+            }
+            Operation::from(
+                CreateTableBuilder::new()
+                    .name("kv")
+                    .column(id.build())
+                    .option(TableOption::WithoutRowid)
+                    .build(),
+            )
+        };
+        let dialect = SqliteDialect::new();
+        assert_eq!(dialect.unsupported_reason(&table(true, false)), None);
+        assert_eq!(
+            dialect.unsupported_reason(&table(false, false)).as_deref(),
+            Some("cannot create kv: a WITHOUT ROWID table needs a PRIMARY KEY")
+        );
+        assert_eq!(
+            dialect.unsupported_reason(&table(true, true)).as_deref(),
+            Some("cannot create kv: a WITHOUT ROWID table cannot use AUTOINCREMENT")
+        );
+        // Dialects that ignore the option do not care.
+        assert_eq!(
+            PostgresDialect::new().unsupported_reason(&table(false, false)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_schema_qualified_sql() {
+        use crate::migrations::table_builder::CreateIndexBuilder;
+
+        let dialect = SqliteDialect::new();
+        let op = Operation::drop_table("events").in_schema("archive");
+        assert_eq!(
+            dialect.generate_sql(&op),
+            "DROP TABLE \"archive\".\"events\""
+        );
+
+        // SQLite puts the schema on the index, not the table.
+        let op = CreateIndexBuilder::new()
+            .schema("archive")
+            .name("idx_events_at")
+            .on_table("events")
+            .column("at")
+            .build();
+        assert_eq!(
+            dialect.create_index(&op),
+            "CREATE INDEX \"archive\".\"idx_events_at\" ON \"events\" (\"at\")"
+        );
+    }
+
+    #[test]
+    fn test_schemas_unsupported() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(
+            dialect.generate_sql(&Operation::create_schema("archive")),
+            "-- SQLite does not support CREATE SCHEMA; attach a database as archive instead"
+        );
+    }
 }
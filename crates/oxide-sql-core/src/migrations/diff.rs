@@ -6,13 +6,15 @@
 
 use std::collections::BTreeSet;
 
+use crate::ast::DataType;
 use crate::schema::{RustTypeMapping, TableSchema};
 
-use super::column_builder::ColumnDefinition;
+use super::column_builder::{ColumnDefinition, DefaultValue, ForeignKeyRef, GeneratedColumn};
 use super::dialect::MigrationDialect;
 use super::operation::{
-    AddColumnOp, AddForeignKeyOp, AlterColumnChange, AlterColumnOp, CreateIndexOp, CreateTableOp,
-    DropColumnOp, DropForeignKeyOp, DropIndexOp, DropTableOp, Operation,
+    AddColumnOp, AddForeignKeyOp, AlterColumnChange, AlterColumnOp, CreateIndexOp, CreateSchemaOp,
+    CreateTableOp, DropColumnOp, DropForeignKeyOp, DropIndexOp, DropTableOp, IndexColumn,
+    Operation, TableConstraint, TableOption,
 };
 use super::snapshot::{
     ColumnSnapshot, ForeignKeySnapshot, IndexSnapshot, SchemaSnapshot, TableSnapshot,
@@ -122,6 +124,36 @@ pub enum DiffWarning {
         /// Column names in the new order.
         new_order: Vec<String>,
     },
+    /// A column became generated, stopped being generated, or changed
+    /// its expression. Databases cannot alter this without recreating
+    /// the table.
+    GeneratedColumnChange {
+        /// Table name.
+        table: String,
+        /// Column name.
+        column: String,
+        /// New generated expression, or `None` for a regular column.
+        new_value: Option<GeneratedColumn>,
+    },
+    /// The table options (e.g. SQLite `STRICT` or `WITHOUT ROWID`)
+    /// changed. These are fixed when the table is created.
+    TableOptionsChanged {
+        /// Table name.
+        table: String,
+        /// Options of the current table.
+        old_options: Vec<TableOption>,
+        /// Desired options.
+        new_options: Vec<TableOption>,
+    },
+    /// A foreign key in a reference cycle that the dialect can neither
+    /// add after its table is created nor declare before its target
+    /// exists (DuckDB). It is left out of the migration.
+    ForeignKeyCycle {
+        /// Table the foreign key belongs to.
+        table: String,
+        /// The foreign key that was left out.
+        foreign_key: ForeignKeySnapshot,
+    },
 }
 
 /// Result of comparing two schema snapshots.
@@ -189,9 +221,31 @@ impl SchemaDiff {
 // Table-level diff
 // ================================================================
 
+/// Decides whether two column types, and two defaults of a column,
+/// are the same for diffing purposes (see
+/// [`RustTypeMapping::types_equivalent`] and
+/// [`RustTypeMapping::defaults_equivalent`]).
+#[derive(Clone, Copy)]
+struct Same<'a> {
+    types: &'a dyn Fn(&DataType, &DataType) -> bool,
+    defaults: &'a dyn Fn(&DefaultValue, &DefaultValue, &DataType) -> bool,
+}
+
+/// Compares types exactly and defaults with
+/// [`DefaultValue::is_equivalent`], for diffs without a dialect.
+const EXACT: Same<'static> = Same {
+    types: &DataType::eq,
+    defaults: &DefaultValue::is_equivalent,
+};
+
 /// Compares a single table's current and desired snapshots,
 /// producing the operations needed to migrate.
-fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> SchemaDiff {
+fn diff_table(
+    table_name: &str,
+    old: &TableSnapshot,
+    new: &TableSnapshot,
+    same: Same<'_>,
+) -> SchemaDiff {
     let old_names: BTreeSet<&str> = old.columns.iter().map(|c| c.name.as_str()).collect();
     let new_names: BTreeSet<&str> = new.columns.iter().map(|c| c.name.as_str()).collect();
 
@@ -213,7 +267,7 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
         let old_col = old.column(d).unwrap();
         for &a in &added {
             let new_col = new.column(a).unwrap();
-            if old_col.data_type == new_col.data_type {
+            if (same.types)(&old_col.data_type, &new_col.data_type) {
                 let sim = similarity(d, a);
                 if sim >= RENAME_SIMILARITY_THRESHOLD {
                     candidates.push((d, a, sim));
@@ -244,6 +298,7 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
         }
         let col = new.column(name).unwrap();
         operations.push(Operation::AddColumn(AddColumnOp {
+            schema: new.schema.clone(),
             table: table_name.to_string(),
             column: snapshot_to_column_def(col),
         }));
@@ -254,8 +309,9 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
         let old_col = old.column(name).unwrap();
         let new_col = new.column(name).unwrap();
 
-        if old_col.data_type != new_col.data_type {
+        if !(same.types)(&old_col.data_type, &new_col.data_type) {
             operations.push(Operation::AlterColumn(AlterColumnOp {
+                schema: new.schema.clone(),
                 table: table_name.to_string(),
                 column: name.to_string(),
                 change: AlterColumnChange::SetDataType(new_col.data_type.clone()),
@@ -264,6 +320,7 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
 
         if old_col.nullable != new_col.nullable {
             operations.push(Operation::AlterColumn(AlterColumnOp {
+                schema: new.schema.clone(),
                 table: table_name.to_string(),
                 column: name.to_string(),
                 change: AlterColumnChange::SetNullable(new_col.nullable),
@@ -272,12 +329,25 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
 
         if old_col.unique != new_col.unique {
             operations.push(Operation::AlterColumn(AlterColumnOp {
+                schema: new.schema.clone(),
                 table: table_name.to_string(),
                 column: name.to_string(),
                 change: AlterColumnChange::SetUnique(new_col.unique),
             }));
         }
 
+        if !same_collation(old_col.collation.as_deref(), new_col.collation.as_deref()) {
+            operations.push(Operation::AlterColumn(AlterColumnOp {
+                schema: new.schema.clone(),
+                table: table_name.to_string(),
+                column: name.to_string(),
+                change: AlterColumnChange::SetCollation {
+                    data_type: new_col.data_type.clone(),
+                    collation: new_col.collation.clone(),
+                },
+            }));
+        }
+
         if old_col.primary_key != new_col.primary_key {
             warnings.push(DiffWarning::PrimaryKeyChange {
                 table: table_name.to_string(),
@@ -294,9 +364,23 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
             });
         }
 
+        let same_generated = match (&old_col.generated, &new_col.generated) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.is_equivalent(b),
+            _ => false,
+        };
+        if !same_generated {
+            warnings.push(DiffWarning::GeneratedColumnChange {
+                table: table_name.to_string(),
+                column: name.to_string(),
+                new_value: new_col.generated.clone(),
+            });
+        }
+
         match (&old_col.default, &new_col.default) {
             (None, Some(new_default)) => {
                 operations.push(Operation::AlterColumn(AlterColumnOp {
+                    schema: new.schema.clone(),
                     table: table_name.to_string(),
                     column: name.to_string(),
                     change: AlterColumnChange::SetDefault(new_default.clone()),
@@ -304,13 +388,17 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
             }
             (Some(_), None) => {
                 operations.push(Operation::AlterColumn(AlterColumnOp {
+                    schema: new.schema.clone(),
                     table: table_name.to_string(),
                     column: name.to_string(),
                     change: AlterColumnChange::DropDefault,
                 }));
             }
-            (Some(old_def), Some(new_def)) if old_def != new_def => {
+            (Some(old_def), Some(new_def))
+                if !(same.defaults)(old_def, new_def, &new_col.data_type) =>
+            {
                 operations.push(Operation::AlterColumn(AlterColumnOp {
+                    schema: new.schema.clone(),
                     table: table_name.to_string(),
                     column: name.to_string(),
                     change: AlterColumnChange::SetDefault(new_def.clone()),
@@ -326,6 +414,7 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
             continue;
         }
         operations.push(Operation::DropColumn(DropColumnOp {
+            schema: new.schema.clone(),
             table: table_name.to_string(),
             column: name.to_string(),
         }));
@@ -340,6 +429,15 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
     // ---- Column ordering detection -----------------------------
     detect_column_order_change(table_name, old, new, &common, &mut warnings);
 
+    // ---- Table options -----------------------------------------
+    if !same_options(&old.options, &new.options) {
+        warnings.push(DiffWarning::TableOptionsChanged {
+            table: table_name.to_string(),
+            old_options: old.options.clone(),
+            new_options: new.options.clone(),
+        });
+    }
+
     SchemaDiff {
         operations,
         ambiguous,
@@ -354,11 +452,26 @@ fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> Sch
 /// Two indexes are considered equivalent if they cover the same
 /// columns, uniqueness, and type. Names are ignored because they
 /// may differ between environments.
+///
+/// Expressions and predicates are compared by their rendered SQL, so
+/// source spans and formatting don't count as changes.
 fn indexes_equivalent(a: &IndexSnapshot, b: &IndexSnapshot) -> bool {
-    a.columns == b.columns
+    index_elements_sql(&a.columns) == index_elements_sql(&b.columns)
         && a.unique == b.unique
         && a.index_type == b.index_type
-        && a.condition == b.condition
+        && a.condition.as_ref().map(ToString::to_string)
+            == b.condition.as_ref().map(ToString::to_string)
+}
+
+/// Renders index elements for comparison.
+fn index_elements_sql(columns: &[IndexColumn]) -> Vec<String> {
+    columns
+        .iter()
+        .map(|column| match column {
+            IndexColumn::Column(name) => name.clone(),
+            IndexColumn::Expr(expr) => expr.to_string(),
+        })
+        .collect()
 }
 
 /// Diffs indexes between old and new table snapshots.
@@ -373,6 +486,7 @@ fn diff_indexes(
         let still_exists = new.indexes.iter().any(|n| indexes_equivalent(old_idx, n));
         if !still_exists {
             operations.push(Operation::DropIndex(DropIndexOp {
+                schema: new.schema.clone(),
                 name: old_idx.name.clone(),
                 table: Some(table_name.to_string()),
                 if_exists: false,
@@ -383,19 +497,25 @@ fn diff_indexes(
     for new_idx in &new.indexes {
         let already_exists = old.indexes.iter().any(|o| indexes_equivalent(o, new_idx));
         if !already_exists {
-            operations.push(Operation::CreateIndex(CreateIndexOp {
-                name: new_idx.name.clone(),
-                table: table_name.to_string(),
-                columns: new_idx.columns.clone(),
-                unique: new_idx.unique,
-                index_type: new_idx.index_type,
-                if_not_exists: false,
-                condition: new_idx.condition.clone(),
-            }));
+            operations.push(create_index_op(new, new_idx));
         }
     }
 }
 
+/// Builds the operation that creates `index` on `table`.
+fn create_index_op(table: &TableSnapshot, index: &IndexSnapshot) -> Operation {
+    Operation::CreateIndex(CreateIndexOp {
+        schema: table.schema.clone(),
+        name: index.name.clone(),
+        table: table.name.clone(),
+        columns: index.columns.clone(),
+        unique: index.unique,
+        index_type: index.index_type,
+        if_not_exists: false,
+        condition: index.condition.clone(),
+    })
+}
+
 /// Two foreign keys are equivalent if they reference the same
 /// columns, target table, target columns, and actions.
 fn fks_equivalent(a: &ForeignKeySnapshot, b: &ForeignKeySnapshot) -> bool {
@@ -416,13 +536,12 @@ fn diff_foreign_keys(
     // FKs present in old but not in new → DropForeignKey.
     for old_fk in &old.foreign_keys {
         let still_exists = new.foreign_keys.iter().any(|n| fks_equivalent(old_fk, n));
-        if !still_exists {
-            if let Some(ref name) = old_fk.name {
-                operations.push(Operation::DropForeignKey(DropForeignKeyOp {
-                    table: table_name.to_string(),
-                    name: name.clone(),
-                }));
-            }
+        if !still_exists && let Some(ref name) = old_fk.name {
+            operations.push(Operation::DropForeignKey(DropForeignKeyOp {
+                schema: new.schema.clone(),
+                table: table_name.to_string(),
+                name: name.clone(),
+            }));
         }
     }
     // FKs present in new but not in old → AddForeignKey.
@@ -430,6 +549,7 @@ fn diff_foreign_keys(
         let already_exists = old.foreign_keys.iter().any(|o| fks_equivalent(o, new_fk));
         if !already_exists {
             operations.push(Operation::AddForeignKey(AddForeignKeyOp {
+                schema: new.schema.clone(),
                 table: table_name.to_string(),
                 name: new_fk.name.clone(),
                 columns: new_fk.columns.clone(),
@@ -481,6 +601,21 @@ fn detect_column_order_change(
 // Helpers
 // ================================================================
 
+/// Returns whether two option lists hold the same options, in any
+/// order.
+fn same_options(a: &[TableOption], b: &[TableOption]) -> bool {
+    a.iter().all(|o| b.contains(o)) && b.iter().all(|o| a.contains(o))
+}
+
+/// Returns whether two collations are the same; names compare
+/// case-insensitively, as introspection may report `nocase` for `NOCASE`.
+fn same_collation(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
 /// Converts a `ColumnSnapshot` into a `ColumnDefinition` for use
 /// in `AddColumnOp`.
 fn snapshot_to_column_def(col: &ColumnSnapshot) -> ColumnDefinition {
@@ -494,7 +629,8 @@ fn snapshot_to_column_def(col: &ColumnSnapshot) -> ColumnDefinition {
         autoincrement: col.autoincrement,
         references: None,
         check: None,
-        collation: None,
+        collation: col.collation.clone(),
+        generated: col.generated.clone(),
     }
 }
 
@@ -505,9 +641,19 @@ fn snapshot_to_column_def(col: &ColumnSnapshot) -> ColumnDefinition {
 /// Compares two full schema snapshots and produces the operations
 /// needed to migrate from `current` to `desired`.
 ///
-/// Operation ordering: CreateTable > AddColumn > AlterColumn >
-/// DropColumn > DropTable (avoids FK constraint violations).
+/// Operation ordering: CreateSchema > CreateTable > AddColumn >
+/// AlterColumn > DropColumn > DropTable (avoids FK constraint
+/// violations).
+///
+/// Column types are compared exactly and defaults with
+/// [`DefaultValue::is_equivalent`]; use
+/// [`auto_diff_schema_for_dialect`] to compare them the way the
+/// target database stores them.
 pub fn auto_diff_schema(current: &SchemaSnapshot, desired: &SchemaSnapshot) -> SchemaDiff {
+    diff_schema(current, desired, EXACT)
+}
+
+fn diff_schema(current: &SchemaSnapshot, desired: &SchemaSnapshot, same: Same<'_>) -> SchemaDiff {
     let current_tables: BTreeSet<&str> = current.tables.keys().map(String::as_str).collect();
     let desired_tables: BTreeSet<&str> = desired.tables.keys().map(String::as_str).collect();
 
@@ -542,7 +688,7 @@ pub fn auto_diff_schema(current: &SchemaSnapshot, desired: &SchemaSnapshot) -> S
         let old_table = &current.tables[d];
         for &a in &added_tables {
             let new_table = &desired.tables[a];
-            if tables_have_same_columns(old_table, new_table) {
+            if tables_have_same_columns(old_table, new_table, same) {
                 let sim = similarity(d, a);
                 candidates.push((d, a, sim));
             }
@@ -570,18 +716,25 @@ pub fn auto_diff_schema(current: &SchemaSnapshot, desired: &SchemaSnapshot) -> S
         let table = &desired.tables[name];
         let columns = table.columns.iter().map(snapshot_to_column_def).collect();
         create_ops.push(Operation::CreateTable(CreateTableOp {
-            name: name.to_string(),
+            schema: table.schema.clone(),
+            name: table.name.clone(),
             columns,
             constraints: vec![],
             if_not_exists: false,
+            partition_by: None,
+            options: table.options.clone(),
         }));
+        // Indexes of a new table are created right after it.
+        for index in &table.indexes {
+            create_ops.push(create_index_op(table, index));
+        }
     }
 
     // ---- Existing tables -> diff columns -----------------------
     for &name in &common_tables {
         let old_table = &current.tables[name];
         let new_table = &desired.tables[name];
-        let table_diff = diff_table(name, old_table, new_table);
+        let table_diff = diff_table(&new_table.name, old_table, new_table, same);
 
         for op in table_diff.operations {
             match &op {
@@ -604,15 +757,29 @@ pub fn auto_diff_schema(current: &SchemaSnapshot, desired: &SchemaSnapshot) -> S
         if rename_dropped.contains(name) {
             continue;
         }
+        let table = &current.tables[name];
         drop_table_ops.push(Operation::DropTable(DropTableOp {
-            name: name.to_string(),
+            schema: table.schema.clone(),
+            name: table.name.clone(),
             if_exists: false,
             cascade: false,
         }));
     }
 
+    // ---- New schemas -> CreateSchema ---------------------------
+    // Schemas are never dropped automatically; they may hold objects
+    // the snapshot does not track.
+    let current_schemas = current.schemas();
+    let desired_schemas = desired.schemas();
+    let schema_ops = desired_schemas.difference(&current_schemas).map(|schema| {
+        Operation::CreateSchema(CreateSchemaOp {
+            name: (*schema).to_string(),
+            if_not_exists: true,
+        })
+    });
+
     // Assemble in safe order.
-    let mut operations = Vec::new();
+    let mut operations: Vec<Operation> = schema_ops.collect();
     operations.extend(create_ops);
     operations.extend(add_ops);
     operations.extend(alter_ops);
@@ -626,44 +793,325 @@ pub fn auto_diff_schema(current: &SchemaSnapshot, desired: &SchemaSnapshot) -> S
     }
 }
 
+/// Like [`auto_diff_schema`], but compares column types with
+/// [`RustTypeMapping::types_equivalent`] (so `VARCHAR(255)` and `TEXT`
+/// are the same column in SQLite), defaults with
+/// [`RustTypeMapping::defaults_equivalent`] (so `datetime('now')` and
+/// `CURRENT_TIMESTAMP` are the same default in SQLite), and lets
+/// `dialect` turn autoincrement changes into operations where it can
+/// (e.g. DuckDB creates or drops the backing sequence). Changes the
+/// dialect cannot express stay in [`SchemaDiff::warnings`]. Table
+/// options the dialect does not render (e.g. `STRICT` outside SQLite)
+/// are not compared.
+pub fn auto_diff_schema_for_dialect<D: MigrationDialect + RustTypeMapping>(
+    current: &SchemaSnapshot,
+    desired: &SchemaSnapshot,
+    dialect: &D,
+) -> SchemaDiff {
+    let same = Same {
+        types: &|a, b| dialect.types_equivalent(a, b),
+        defaults: &|a, b, data_type| dialect.defaults_equivalent(a, b, data_type),
+    };
+    let mut diff = diff_schema(current, desired, same);
+    let mut sequence_ops = Vec::new();
+    let renders = |option: &TableOption| {
+        let option = std::slice::from_ref(option);
+        !dialect.table_modifiers(option).is_empty() || !dialect.table_options(option).is_empty()
+    };
+    diff.warnings.retain(|warning| {
+        if let DiffWarning::TableOptionsChanged {
+            old_options,
+            new_options,
+            ..
+        } = warning
+        {
+            let old: Vec<TableOption> =
+                old_options.iter().filter(|o| renders(o)).cloned().collect();
+            let new: Vec<TableOption> =
+                new_options.iter().filter(|o| renders(o)).cloned().collect();
+            return !same_options(&old, &new);
+        }
+        let DiffWarning::AutoincrementChange {
+            table,
+            column,
+            new_value,
+        } = warning
+        else {
+            return true;
+        };
+        match dialect.autoincrement_change(table, column, *new_value) {
+            Some(ops) => {
+                sequence_ops.extend(ops);
+                false
+            }
+            None => true,
+        }
+    });
+
+    // Keep the safe order: run before any column or table is dropped.
+    let at = diff
+        .operations
+        .iter()
+        .position(|op| matches!(op, Operation::DropColumn(_) | Operation::DropTable(_)))
+        .unwrap_or(diff.operations.len());
+    diff.operations.splice(at..at, sequence_ops);
+    diff
+}
+
 /// Compares a single table's current snapshot against the desired
 /// schema derived from a `#[derive(Table)]` struct.
+///
+/// Column types are compared with
+/// [`RustTypeMapping::types_equivalent`], and defaults with
+/// [`RustTypeMapping::defaults_equivalent`].
 pub fn auto_diff_table<T: TableSchema>(
     current: &TableSnapshot,
     dialect: &impl RustTypeMapping,
 ) -> SchemaDiff {
     let desired = TableSnapshot::from_table_schema::<T>(dialect);
-    diff_table(&desired.name, current, &desired)
+    let same = Same {
+        types: &|a, b| dialect.types_equivalent(a, b),
+        defaults: &|a, b, data_type| dialect.defaults_equivalent(a, b, data_type),
+    };
+    diff_table(&desired.name, current, &desired, same)
+}
+
+/// Builds an initial migration that creates every table from scratch:
+/// one `CreateTable` per table, each followed by its indexes.
+///
+/// Each entry of `tables` builds a table's snapshot for `dialect`;
+/// pass `TableSnapshot::from_table_schema::<T>` for every
+/// `#[derive(Table)]` struct. Tables are created in the given order,
+/// except that a table is created after the tables its foreign keys
+/// reference. Single-column foreign keys are declared on the column,
+/// others as table constraints. Foreign keys in a reference cycle are
+/// added with `AddForeignKey` once every table exists, if the dialect
+/// supports it (see [`MigrationDialect::supports_add_foreign_key`]).
+/// SQLite, which checks references only when rows change, keeps them
+/// inline. Dialects that can do neither (DuckDB) cannot create such a
+/// cycle: the foreign key that closes it is left out and reported as a
+/// [`DiffWarning::ForeignKeyCycle`].
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::migrations::{
+///     SqliteDialect, TableSnapshot, generate_migration_code, initial_from_tables,
+/// };
+/// use oxide_sql_derive::Table;
+///
+/// #[derive(Table)]
+/// #[table(name = "users")]
+/// pub struct User {
+///     #[column(primary_key, autoincrement)]
+///     pub id: i64,
+///     pub name: String,
+/// }
+///
+/// #[derive(Table)]
+/// #[table(name = "posts")]
+/// pub struct Post {
+///     #[column(primary_key, autoincrement)]
+///     pub id: i64,
+///     #[column(references = "users(id)")]
+///     pub author_id: i64,
+/// }
+///
+/// fn main() {
+///     let diff = initial_from_tables(
+///         &[
+///             TableSnapshot::from_table_schema::<PostTable>,
+///             TableSnapshot::from_table_schema::<UserTable>,
+///         ],
+///         &SqliteDialect::new(),
+///     );
+///     let code = generate_migration_code("0001_initial", &diff);
+///     assert!(code.find("\"users\"").unwrap() < code.find("\"posts\"").unwrap());
+///     assert!(code.contains(r#".references("users", "id")"#));
+/// }
+/// ```
+pub fn initial_from_tables<D: MigrationDialect + RustTypeMapping>(
+    tables: &[fn(&D) -> TableSnapshot],
+    dialect: &D,
+) -> SchemaDiff {
+    let can_defer = dialect.supports_add_foreign_key();
+    let can_inline = dialect.supports_forward_foreign_keys();
+    let mut pending: Vec<TableSnapshot> = tables.iter().map(|build| build(dialect)).collect();
+    let targets: Vec<(Option<String>, String)> = pending
+        .iter()
+        .map(|t| (t.schema.clone(), t.name.clone()))
+        .collect();
+    let is_target = |t: &TableSnapshot, fk: &ForeignKeySnapshot| {
+        fk.references_table == t.name || fk.references_table == t.qualified_name()
+    };
+    // Foreign keys to tables that are not part of the migration are
+    // assumed to exist already.
+    let known = |fk: &ForeignKeySnapshot| {
+        targets.iter().any(|(schema, name)| {
+            fk.references_table == *name
+                || schema
+                    .as_ref()
+                    .is_some_and(|schema| fk.references_table == format!("{schema}.{name}"))
+        })
+    };
+
+    let mut created: Vec<TableSnapshot> = Vec::new();
+    let mut create_ops = Vec::new();
+    let mut deferred_ops = Vec::new();
+    let mut warnings = Vec::new();
+    while !pending.is_empty() {
+        let ready = |t: &TableSnapshot| {
+            t.foreign_keys.iter().all(|fk| {
+                is_target(t, fk) || !known(fk) || created.iter().any(|c| is_target(c, fk))
+            })
+        };
+        // In a reference cycle no table is ready; break it at the
+        // first one and defer its foreign keys where the dialect can.
+        let at = pending.iter().position(ready).unwrap_or(0);
+        let table = pending.remove(at);
+
+        let mut columns: Vec<ColumnDefinition> =
+            table.columns.iter().map(snapshot_to_column_def).collect();
+        let mut constraints = Vec::new();
+        for fk in &table.foreign_keys {
+            let exists =
+                is_target(&table, fk) || !known(fk) || created.iter().any(|c| is_target(c, fk));
+            if !exists && can_defer {
+                deferred_ops.push(Operation::AddForeignKey(AddForeignKeyOp {
+                    schema: table.schema.clone(),
+                    table: table.name.clone(),
+                    name: fk.name.clone(),
+                    columns: fk.columns.clone(),
+                    references_table: fk.references_table.clone(),
+                    references_columns: fk.references_columns.clone(),
+                    on_delete: fk.on_delete,
+                    on_update: fk.on_update,
+                }));
+                continue;
+            }
+            if !exists && !can_inline {
+                warnings.push(DiffWarning::ForeignKeyCycle {
+                    table: table.qualified_name(),
+                    foreign_key: fk.clone(),
+                });
+                continue;
+            }
+            let column = match (
+                &fk.name,
+                fk.columns.as_slice(),
+                fk.references_columns.as_slice(),
+            ) {
+                (None, [column], [_]) => columns
+                    .iter_mut()
+                    .find(|c| c.name == *column && c.references.is_none()),
+                _ => None,
+            };
+            match column {
+                Some(column) => {
+                    column.references = Some(ForeignKeyRef {
+                        table: fk.references_table.clone(),
+                        column: fk.references_columns[0].clone(),
+                        on_delete: fk.on_delete,
+                        on_update: fk.on_update,
+                    });
+                }
+                None => constraints.push(TableConstraint::ForeignKey {
+                    name: fk.name.clone(),
+                    columns: fk.columns.clone(),
+                    references_table: fk.references_table.clone(),
+                    references_columns: fk.references_columns.clone(),
+                    on_delete: fk.on_delete,
+                    on_update: fk.on_update,
+                }),
+            }
+        }
+
+        create_ops.push(Operation::CreateTable(CreateTableOp {
+            schema: table.schema.clone(),
+            name: table.name.clone(),
+            columns,
+            constraints,
+            if_not_exists: false,
+            partition_by: None,
+            options: table.options.clone(),
+        }));
+        for index in &table.indexes {
+            create_ops.push(create_index_op(&table, index));
+        }
+        created.push(table);
+    }
+
+    let schemas: BTreeSet<&str> = created.iter().filter_map(|t| t.schema.as_deref()).collect();
+    let mut operations: Vec<Operation> = schemas
+        .into_iter()
+        .map(|schema| {
+            Operation::CreateSchema(CreateSchemaOp {
+                name: schema.to_string(),
+                if_not_exists: true,
+            })
+        })
+        .collect();
+    operations.extend(create_ops);
+    operations.extend(deferred_ops);
+
+    SchemaDiff {
+        operations,
+        ambiguous: vec![],
+        warnings,
+    }
 }
 
 /// Returns `true` if two table snapshots have identical column
 /// structure (names, types, nullable, etc.).
-fn tables_have_same_columns(a: &TableSnapshot, b: &TableSnapshot) -> bool {
+fn tables_have_same_columns(a: &TableSnapshot, b: &TableSnapshot, same: Same<'_>) -> bool {
     if a.columns.len() != b.columns.len() {
         return false;
     }
     a.columns.iter().zip(b.columns.iter()).all(|(ac, bc)| {
         ac.name == bc.name
-            && ac.data_type == bc.data_type
+            && (same.types)(&ac.data_type, &bc.data_type)
             && ac.nullable == bc.nullable
             && ac.primary_key == bc.primary_key
             && ac.unique == bc.unique
             && ac.autoincrement == bc.autoincrement
-            && ac.default == bc.default
+            && same_collation(ac.collation.as_deref(), bc.collation.as_deref())
+            && match (&ac.default, &bc.default) {
+                (Some(a), Some(b)) => (same.defaults)(a, b, &ac.data_type),
+                (a, b) => a.is_none() && b.is_none(),
+            }
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::DataType;
-    use crate::migrations::column_builder::{DefaultValue, ForeignKeyAction};
+    use crate::migrations::column_builder::ForeignKeyAction;
     use crate::migrations::operation::IndexType;
+    use crate::migrations::{PostgresDialect, SqliteDialect};
 
     // ============================================================
     // Helpers
     // ============================================================
 
+    /// Diffs a table comparing column types exactly.
+    fn diff_table(table_name: &str, old: &TableSnapshot, new: &TableSnapshot) -> SchemaDiff {
+        super::diff_table(table_name, old, new, EXACT)
+    }
+
+    /// Diffs a table comparing column types and defaults as `dialect`
+    /// does.
+    fn diff_table_for(
+        dialect: &impl RustTypeMapping,
+        old: &TableSnapshot,
+        new: &TableSnapshot,
+    ) -> SchemaDiff {
+        let same = Same {
+            types: &|a, b| dialect.types_equivalent(a, b),
+            defaults: &|a, b, data_type| dialect.defaults_equivalent(a, b, data_type),
+        };
+        super::diff_table(&new.name, old, new, same)
+    }
+
     fn col(name: &str, data_type: DataType, nullable: bool) -> ColumnSnapshot {
         ColumnSnapshot {
             name: name.to_string(),
@@ -673,6 +1121,8 @@ mod tests {
             unique: false,
             autoincrement: false,
             default: None,
+            collation: None,
+            generated: None,
         }
     }
 
@@ -685,15 +1135,19 @@ mod tests {
             unique: false,
             autoincrement: true,
             default: None,
+            collation: None,
+            generated: None,
         }
     }
 
     fn table(name: &str, columns: Vec<ColumnSnapshot>) -> TableSnapshot {
         TableSnapshot {
+            schema: None,
             name: name.to_string(),
             columns,
             indexes: vec![],
             foreign_keys: vec![],
+            options: vec![],
         }
     }
 
@@ -920,6 +1374,75 @@ mod tests {
         ));
     }
 
+    /// Builds `t (c)` with the given type and default.
+    fn with_default(data_type: DataType, default: DefaultValue) -> TableSnapshot {
+        let mut column = col("c", data_type, false);
+        column.default = Some(default);
+        table("t", vec![column])
+    }
+
+    #[test]
+    fn equivalent_default_spellings_are_not_changes() {
+        let cases = [
+            (DataType::Boolean, "FALSE", DefaultValue::Integer(0)),
+            (DataType::Boolean, "'false'", DefaultValue::Boolean(false)),
+            (
+                DataType::Timestamp,
+                "current_timestamp()",
+                DefaultValue::Expression("CURRENT_TIMESTAMP".into()),
+            ),
+            (DataType::Double, "1.0", DefaultValue::Integer(1)),
+        ];
+        for (data_type, old_sql, new_default) in cases {
+            let old = with_default(data_type.clone(), DefaultValue::Expression(old_sql.into()));
+            let new = with_default(data_type, new_default);
+            let diff = diff_table("t", &old, &new);
+            assert!(diff.is_empty(), "{old_sql}: {:?}", diff.operations);
+        }
+    }
+
+    #[test]
+    fn default_spellings_are_compared_per_dialect() {
+        let now = DefaultValue::Expression("CURRENT_TIMESTAMP".into());
+        let sqlite_now = DefaultValue::Expression("datetime('now')".into());
+        let pg_now = DefaultValue::Expression("now()".into());
+
+        // SQLite: booleans are integers, and datetime('now') is its
+        // spelling of the current time.
+        let sqlite = SqliteDialect::new();
+        let int_false = with_default(DataType::Integer, DefaultValue::Boolean(false));
+        let int_zero = with_default(DataType::Integer, DefaultValue::Integer(0));
+        assert!(diff_table_for(&sqlite, &int_false, &int_zero).is_empty());
+        let old = with_default(DataType::Text, sqlite_now.clone());
+        let new = with_default(DataType::Text, now.clone());
+        assert!(diff_table_for(&sqlite, &old, &new).is_empty());
+        let old = with_default(DataType::Text, pg_now.clone());
+        assert_eq!(diff_table_for(&sqlite, &old, &new).operations.len(), 1);
+
+        // PostgreSQL: now() is CURRENT_TIMESTAMP, but an integer column
+        // does not hold booleans.
+        let pg = PostgresDialect::new();
+        let old = with_default(DataType::Timestamp, pg_now);
+        let new = with_default(DataType::Timestamp, now);
+        assert!(diff_table_for(&pg, &old, &new).is_empty());
+        let old = with_default(DataType::Timestamp, sqlite_now);
+        assert_eq!(diff_table_for(&pg, &old, &new).operations.len(), 1);
+        assert_eq!(
+            diff_table_for(&pg, &int_false, &int_zero).operations.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn boolean_like_defaults_still_differ_on_text() {
+        let mut old_col = col("c", DataType::Text, false);
+        old_col.default = Some(DefaultValue::Expression("'false'".into()));
+        let mut new_col = col("c", DataType::Text, false);
+        new_col.default = Some(DefaultValue::Integer(0));
+        let diff = diff_table("t", &table("t", vec![old_col]), &table("t", vec![new_col]));
+        assert_eq!(diff.operations.len(), 1);
+    }
+
     // ============================================================
     // Rename detection (N:M with similarity)
     // ============================================================
@@ -1188,6 +1711,9 @@ mod tests {
                     unique: false,
                     autoincrement: true,
                     default_expr: None,
+                    collation: None,
+                    references: None,
+                    generated: None,
                 },
                 ColumnSchema {
                     name: "title",
@@ -1197,6 +1723,9 @@ mod tests {
                     unique: false,
                     autoincrement: false,
                     default_expr: None,
+                    collation: None,
+                    references: None,
+                    generated: None,
                 },
             ];
         }
@@ -1238,6 +1767,40 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn collation_change_detected() {
+        let old = table("users", vec![col("name", DataType::Text, false)]);
+        let mut new_col = col("name", DataType::Text, false);
+        new_col.collation = Some("NOCASE".into());
+        let new = table("users", vec![new_col]);
+
+        let diff = diff_table("users", &old, &new);
+        assert_eq!(
+            diff.operations,
+            vec![Operation::AlterColumn(AlterColumnOp {
+                schema: None,
+                table: "users".into(),
+                column: "name".into(),
+                change: AlterColumnChange::SetCollation {
+                    data_type: DataType::Text,
+                    collation: Some("NOCASE".into()),
+                },
+            })]
+        );
+        assert!(diff_table("users", &new, &new).is_empty());
+    }
+
+    #[test]
+    fn collation_case_ignored() {
+        let mut old_col = col("name", DataType::Text, false);
+        old_col.collation = Some("nocase".into());
+        let mut new_col = old_col.clone();
+        new_col.collation = Some("NOCASE".into());
+        let old = table("users", vec![old_col]);
+        let new = table("users", vec![new_col]);
+        assert!(diff_table("users", &old, &new).is_empty());
+    }
+
     // ============================================================
     // Warning detection
     // ============================================================
@@ -1284,6 +1847,112 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn autoincrement_change_becomes_sequence_ops_in_duckdb() {
+        use crate::migrations::dialect::{DuckDbDialect, SqliteDialect};
+        use crate::migrations::operation::{CreateSequenceOp, DropSequenceOp};
+
+        let mut old_col = col("id", DataType::Bigint, false);
+        old_col.autoincrement = false;
+        let mut new_col = col("id", DataType::Bigint, false);
+        new_col.autoincrement = true;
+        let old = schema(vec![table(
+            "t",
+            vec![old_col.clone(), col("gone", DataType::Text, true)],
+        )]);
+        let new = schema(vec![table("t", vec![new_col.clone()])]);
+
+        let diff = auto_diff_schema_for_dialect(&old, &new, &DuckDbDialect::new());
+        assert!(diff.warnings.is_empty());
+        assert_eq!(
+            diff.operations[0],
+            CreateSequenceOp::new("seq_t_id")
+                .start(1)
+                .if_not_exists()
+                .into()
+        );
+        assert!(matches!(
+            &diff.operations[1],
+            Operation::AlterColumn(AlterColumnOp {
+                change: AlterColumnChange::SetDefault(DefaultValue::Expression(e)),
+                ..
+            }) if e == "nextval('seq_t_id')"
+        ));
+        assert!(matches!(&diff.operations[2], Operation::DropColumn(_)));
+
+        // Turning autoincrement off drops the default, then the sequence.
+        let diff = auto_diff_schema_for_dialect(
+            &schema(vec![table("t", vec![new_col.clone()])]),
+            &schema(vec![table("t", vec![old_col])]),
+            &DuckDbDialect::new(),
+        );
+        assert_eq!(
+            diff.operations,
+            vec![
+                Operation::AlterColumn(AlterColumnOp {
+                    schema: None,
+                    table: "t".into(),
+                    column: "id".into(),
+                    change: AlterColumnChange::DropDefault,
+                }),
+                Operation::DropSequence(DropSequenceOp {
+                    schema: None,
+                    name: "seq_t_id".into(),
+                    if_exists: true,
+                }),
+            ]
+        );
+
+        // Other dialects keep the warning.
+        let diff = auto_diff_schema_for_dialect(
+            &schema(vec![table("t", vec![new_col])]),
+            &schema(vec![table("t", vec![col("id", DataType::Bigint, false)])]),
+            &SqliteDialect::new(),
+        );
+        assert!(diff.operations.is_empty());
+        assert_eq!(diff.warnings.len(), 1);
+    }
+
+    #[test]
+    fn sqlite_compares_types_by_affinity() {
+        use crate::migrations::dialect::{PostgresDialect, SqliteDialect};
+
+        // VARCHAR(255) introspects back from SQLite as TEXT.
+        let old = schema(vec![table(
+            "t",
+            vec![
+                col("name", DataType::Text, false),
+                col("n", DataType::Integer, false),
+            ],
+        )]);
+        let new = schema(vec![table(
+            "t",
+            vec![
+                col("name", DataType::Varchar(Some(255)), false),
+                col("n", DataType::Bigint, false),
+            ],
+        )]);
+
+        let diff = auto_diff_schema_for_dialect(&old, &new, &SqliteDialect::new());
+        assert!(diff.is_empty(), "{:?}", diff.operations);
+
+        // A different affinity is still a change.
+        let blob = schema(vec![table(
+            "t",
+            vec![
+                col("name", DataType::Blob, false),
+                col("n", DataType::Integer, false),
+            ],
+        )]);
+        let diff = auto_diff_schema_for_dialect(&old, &blob, &SqliteDialect::new());
+        assert_eq!(diff.operations.len(), 1);
+
+        // PostgreSQL stores these types differently.
+        let diff = auto_diff_schema_for_dialect(&old, &new, &PostgresDialect::new());
+        assert_eq!(diff.operations.len(), 2);
+        assert_eq!(auto_diff_schema(&old, &new).operations.len(), 2);
+    }
+
     #[test]
     fn column_order_change_emits_warning() {
         let old = table(
@@ -1350,6 +2019,109 @@ mod tests {
         );
     }
 
+    fn lower_email_index(condition: Option<&str>) -> IndexSnapshot {
+        IndexSnapshot {
+            name: "idx_email".into(),
+            columns: vec![IndexColumn::parse("lower(email)").unwrap()],
+            unique: true,
+            index_type: IndexType::BTree,
+            condition: condition.map(|sql| crate::Parser::new(sql).parse_expr().unwrap()),
+        }
+    }
+
+    #[test]
+    fn expression_index_unchanged_is_empty() {
+        let mut old = table("t", vec![col("email", DataType::Text, false)]);
+        old.indexes.push(lower_email_index(Some("active")));
+        let mut new = table("t", vec![col("email", DataType::Text, false)]);
+        // Same predicate at different source offsets.
+        new.indexes.push(lower_email_index(Some("  active")));
+        assert!(diff_table("t", &old, &new).is_empty());
+    }
+
+    #[test]
+    fn index_predicate_change_recreates_index() {
+        let mut old = table("t", vec![col("email", DataType::Text, false)]);
+        old.indexes.push(lower_email_index(None));
+        let mut new = table("t", vec![col("email", DataType::Text, false)]);
+        new.indexes.push(lower_email_index(Some("active")));
+        let diff = diff_table("t", &old, &new);
+        assert_eq!(diff.operations.len(), 2);
+        assert!(matches!(&diff.operations[0], Operation::DropIndex(di) if di.name == "idx_email"));
+        let Operation::CreateIndex(ci) = &diff.operations[1] else {
+            panic!("expected CreateIndex, got {:?}", diff.operations[1]);
+        };
+        assert_eq!(ci.condition.as_ref().unwrap().to_string(), "active");
+    }
+
+    #[test]
+    fn new_table_indexes_created_after_table() {
+        let mut users = table("users", vec![col("email", DataType::Text, false)]);
+        users.indexes.push(lower_email_index(None));
+        let diff = auto_diff_schema(&schema(vec![]), &schema(vec![users]));
+        assert!(matches!(&diff.operations[0], Operation::CreateTable(ct) if ct.name == "users"));
+        assert!(matches!(
+            &diff.operations[1],
+            Operation::CreateIndex(ci) if ci.table == "users" && ci.columns[0] != "email"
+        ));
+    }
+
+    // ============================================================
+    // Schemas (namespaces)
+    // ============================================================
+
+    #[test]
+    fn new_schema_created_before_its_tables() {
+        let invoices = table("invoices", vec![pk_col("id", DataType::Bigint)]).in_schema("billing");
+        let diff = auto_diff_schema(&schema(vec![]), &schema(vec![invoices]));
+        assert!(matches!(
+            &diff.operations[0],
+            Operation::CreateSchema(cs) if cs.name == "billing" && cs.if_not_exists
+        ));
+        assert!(matches!(
+            &diff.operations[1],
+            Operation::CreateTable(ct)
+                if ct.name == "invoices" && ct.schema.as_deref() == Some("billing")
+        ));
+    }
+
+    #[test]
+    fn same_table_name_in_two_schemas() {
+        let current = schema(vec![
+            table("events", vec![col("a", DataType::Text, false)]),
+            table("events", vec![col("a", DataType::Text, false)]).in_schema("archive"),
+        ]);
+        let desired = schema(vec![
+            table("events", vec![col("a", DataType::Text, false)]),
+            table(
+                "events",
+                vec![
+                    col("a", DataType::Text, false),
+                    col("b", DataType::Text, true),
+                ],
+            )
+            .in_schema("archive"),
+        ]);
+        let diff = auto_diff_schema(&current, &desired);
+        assert_eq!(
+            diff.operations,
+            vec![
+                Operation::add_column("events", ColumnDefinition::new("b", DataType::Text))
+                    .in_schema("archive")
+            ]
+        );
+    }
+
+    #[test]
+    fn schemas_are_not_dropped() {
+        let current = schema(vec![table("events", vec![]).in_schema("archive")]);
+        let diff = auto_diff_schema(&current, &schema(vec![]));
+        assert_eq!(
+            diff.operations,
+            vec![Operation::drop_table("events").in_schema("archive")]
+        );
+    }
+
     // ============================================================
     // Foreign key diff
     // ============================================================
@@ -1457,4 +2229,148 @@ mod tests {
         assert_eq!(sqls.len(), 1);
         assert!(sqls[0].contains("ADD COLUMN"));
     }
+
+    // ============================================================
+    // Initial migration
+    // ============================================================
+
+    fn fk(columns: &[&str], references_table: &str) -> ForeignKeySnapshot {
+        ForeignKeySnapshot {
+            name: None,
+            columns: columns.iter().map(|c| (*c).to_string()).collect(),
+            references_table: references_table.to_string(),
+            references_columns: columns.iter().map(|_| "id".to_string()).collect(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn users(_: &SqliteDialect) -> TableSnapshot {
+        let mut users = table(
+            "users",
+            vec![
+                pk_col("id", DataType::Bigint),
+                col("email", DataType::Text, false),
+            ],
+        );
+        users.indexes.push(IndexSnapshot {
+            name: "idx_users_email".to_string(),
+            columns: vec!["email".into()],
+            unique: true,
+            index_type: IndexType::BTree,
+            condition: None,
+        });
+        users
+    }
+
+    fn posts(_: &SqliteDialect) -> TableSnapshot {
+        let mut posts = table(
+            "posts",
+            vec![
+                pk_col("id", DataType::Bigint),
+                col("author_id", DataType::Bigint, false),
+                col("parent_id", DataType::Bigint, true),
+            ],
+        );
+        posts.foreign_keys = vec![fk(&["author_id"], "users"), fk(&["parent_id"], "posts")];
+        posts
+    }
+
+    #[test]
+    fn initial_from_tables_orders_by_foreign_keys() {
+        let diff = initial_from_tables(&[posts, users], &SqliteDialect::new());
+        let summary: Vec<String> = diff
+            .operations
+            .iter()
+            .map(|op| match op {
+                Operation::CreateTable(ct) => format!("table {}", ct.name),
+                Operation::CreateIndex(ci) => format!("index {}", ci.name),
+                other => format!("{other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            ["table users", "index idx_users_email", "table posts"]
+        );
+
+        let Operation::CreateTable(posts) = &diff.operations[2] else {
+            unreachable!()
+        };
+        let author = posts.columns[1].references.as_ref().unwrap();
+        assert_eq!(
+            (author.table.as_str(), author.column.as_str()),
+            ("users", "id")
+        );
+        assert_eq!(posts.columns[2].references.as_ref().unwrap().table, "posts");
+        assert!(posts.constraints.is_empty());
+
+        let sql = diff.to_sql(&SqliteDialect::new());
+        assert!(sql[2].contains("REFERENCES \"users\""));
+    }
+
+    #[test]
+    fn initial_from_tables_defers_cyclic_foreign_keys() {
+        use crate::migrations::{DuckDbDialect, PostgresDialect};
+
+        fn a<D>(_: &D) -> TableSnapshot {
+            let mut a = table("a", vec![pk_col("id", DataType::Bigint)]);
+            a.columns.push(col("b_id", DataType::Bigint, true));
+            a.foreign_keys.push(fk(&["b_id"], "b"));
+            a
+        }
+        fn b<D>(_: &D) -> TableSnapshot {
+            let mut b = table("b", vec![pk_col("id", DataType::Bigint)]);
+            b.columns.push(col("a_id", DataType::Bigint, true));
+            b.columns.push(col("tenant_id", DataType::Bigint, true));
+            b.foreign_keys.push(fk(&["a_id"], "a"));
+            b.foreign_keys.push(ForeignKeySnapshot {
+                name: Some("fk_b_tenant".to_string()),
+                ..fk(&["tenant_id"], "tenants")
+            });
+            b
+        }
+
+        let diff = initial_from_tables(&[a, b], &PostgresDialect::new());
+        assert_eq!(diff.operations.len(), 3);
+        let Operation::CreateTable(table_b) = &diff.operations[1] else {
+            unreachable!()
+        };
+        assert_eq!(table_b.columns[1].references.as_ref().unwrap().table, "a");
+        assert!(matches!(
+            &table_b.constraints[..],
+            [TableConstraint::ForeignKey { name: Some(n), .. }] if n == "fk_b_tenant"
+        ));
+        assert!(matches!(
+            &diff.operations[2],
+            Operation::AddForeignKey(op) if op.table == "a" && op.references_table == "b"
+        ));
+
+        // SQLite cannot add foreign keys later, so they stay inline.
+        let diff = initial_from_tables(&[a, b], &SqliteDialect::new());
+        assert_eq!(diff.operations.len(), 2);
+        let Operation::CreateTable(table_a) = &diff.operations[0] else {
+            unreachable!()
+        };
+        assert_eq!(table_a.columns[1].references.as_ref().unwrap().table, "b");
+        assert!(diff.warnings.is_empty());
+
+        // DuckDB can do neither, so the cycle cannot be created.
+        let diff = initial_from_tables(&[a, b], &DuckDbDialect::new());
+        assert_eq!(diff.operations.len(), 2);
+        let Operation::CreateTable(table_a) = &diff.operations[0] else {
+            unreachable!()
+        };
+        assert!(table_a.columns[1].references.is_none());
+        assert_eq!(
+            diff.warnings,
+            [DiffWarning::ForeignKeyCycle {
+                table: "a".to_string(),
+                foreign_key: fk(&["b_id"], "b"),
+            }]
+        );
+        let Operation::CreateTable(table_b) = &diff.operations[1] else {
+            unreachable!()
+        };
+        assert_eq!(table_b.columns[1].references.as_ref().unwrap().table, "a");
+    }
 }
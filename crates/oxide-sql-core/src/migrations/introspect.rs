@@ -1,6 +1,6 @@
 //! Schema introspection trait.
 //!
-//! Driver crates (oxide-sql-sqlite, etc.) implement [`Introspect`]
+//! Driver crates (oxide-sql-rusqlite, etc.) implement [`Introspect`]
 //! to read the current database schema at runtime. The core crate
 //! defines only the trait so it stays driver-agnostic.
 
@@ -9,13 +9,26 @@ use super::snapshot::SchemaSnapshot;
 /// Introspects a live database connection to produce a
 /// [`SchemaSnapshot`] of the current schema.
 ///
-/// Implementations live in driver crates (e.g. oxide-sql-sqlite).
+/// Implementations live in driver crates (e.g. oxide-sql-rusqlite).
 pub trait Introspect {
     /// Error type for introspection failures.
     type Error: std::error::Error;
 
     /// Reads the current database schema and returns a snapshot.
     fn introspect_schema(&self) -> Result<SchemaSnapshot, Self::Error>;
+
+    /// Reads only the tables in the given schemas (namespaces).
+    ///
+    /// The default implementation filters the result of
+    /// [`introspect_schema`](Self::introspect_schema); tables in the
+    /// default schema are dropped unless `schemas` is empty.
+    fn introspect_schemas(&self, schemas: &[&str]) -> Result<SchemaSnapshot, Self::Error> {
+        let mut snapshot = self.introspect_schema()?;
+        if !schemas.is_empty() {
+            snapshot.retain_schemas(schemas, false);
+        }
+        Ok(snapshot)
+    }
 }
 
 /// Helper constants and functions for implementing [`Introspect`]
@@ -23,7 +36,9 @@ pub trait Introspect {
 /// and type-mapping logic that any SQLite driver crate can use.
 pub mod sqlite_helpers {
     use crate::ast::DataType;
-    use crate::migrations::column_builder::DefaultValue;
+    use crate::lexer::{Keyword, Lexer, TokenKind};
+    use crate::migrations::column_builder::{DefaultValue, ForeignKeyAction, GeneratedColumn};
+    use crate::migrations::operation::TableOption;
     use crate::migrations::snapshot::ColumnSnapshot;
 
     /// SQL to list all user tables (excludes internal SQLite
@@ -35,6 +50,22 @@ pub mod sqlite_helpers {
     /// Replace `{table}` with the actual table name.
     pub const TABLE_INFO: &str = "PRAGMA table_info({table})";
 
+    /// PRAGMA to get column info for a table, including generated
+    /// columns, which `PRAGMA table_info` omits. The extra `hidden`
+    /// column is 2 for virtual and 3 for stored generated columns.
+    /// Replace `{table}` with the actual table name.
+    pub const TABLE_XINFO: &str = "PRAGMA table_xinfo({table})";
+
+    /// PRAGMA to get a table's kind, with its `wr` (`WITHOUT ROWID`)
+    /// and `strict` flags (SQLite 3.37+).
+    /// Replace `{table}` with the actual table name.
+    pub const TABLE_LIST: &str = "PRAGMA table_list({table})";
+
+    /// SQL to get the `CREATE TABLE` statement of a table, which holds
+    /// the expressions of its generated columns.
+    /// Bind the table name as the `?1` parameter.
+    pub const TABLE_SQL: &str = "SELECT sql FROM sqlite_master WHERE type='table' AND name = ?1";
+
     /// PRAGMA to get the index list for a table.
     /// Replace `{table}` with the actual table name.
     pub const INDEX_LIST: &str = "PRAGMA index_list({table})";
@@ -43,6 +74,11 @@ pub mod sqlite_helpers {
     /// Replace `{index}` with the actual index name.
     pub const INDEX_INFO: &str = "PRAGMA index_info({index})";
 
+    /// SQL to get the `CREATE INDEX` statement of an index, which holds
+    /// its expressions and partial index condition.
+    /// Bind the index name as the `?1` parameter.
+    pub const INDEX_SQL: &str = "SELECT sql FROM sqlite_master WHERE type='index' AND name = ?1";
+
     /// PRAGMA to get foreign key list for a table.
     /// Replace `{table}` with the actual table name.
     pub const FOREIGN_KEY_LIST: &str = "PRAGMA foreign_key_list({table})";
@@ -55,7 +91,7 @@ pub mod sqlite_helpers {
     pub fn parse_sqlite_type(type_str: &str) -> DataType {
         let upper = type_str.to_uppercase();
         let upper = upper.trim();
-        match upper.as_ref() {
+        match upper {
             "INTEGER" | "INT" => DataType::Integer,
             "BIGINT" => DataType::Bigint,
             "SMALLINT" | "TINYINT" => DataType::Smallint,
@@ -129,7 +165,260 @@ pub mod sqlite_helpers {
             unique: false,
             autoincrement: false,
             default,
+            collation: None,
+            generated: None,
+        }
+    }
+
+    /// Maps an `on_update` or `on_delete` action of
+    /// `PRAGMA foreign_key_list`.
+    ///
+    /// `NO ACTION`, which SQLite reports when no action was declared,
+    /// maps to `None`.
+    #[must_use]
+    pub fn foreign_key_action_from_pragma(action: &str) -> Option<ForeignKeyAction> {
+        match action.to_ascii_uppercase().as_str() {
+            "RESTRICT" => Some(ForeignKeyAction::Restrict),
+            "CASCADE" => Some(ForeignKeyAction::Cascade),
+            "SET NULL" => Some(ForeignKeyAction::SetNull),
+            "SET DEFAULT" => Some(ForeignKeyAction::SetDefault),
+            _ => None,
+        }
+    }
+
+    /// Splits a `CREATE INDEX` statement into the SQL of its elements
+    /// and of its `WHERE` condition, if any.
+    ///
+    /// Returns `None` if `create_sql` has no element list.
+    #[must_use]
+    pub fn index_parts_from_sql(create_sql: &str) -> Option<(Vec<String>, Option<String>)> {
+        let mut quote = None;
+        let open = create_sql.char_indices().find_map(|(i, c)| {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '[') => quote = Some(']'),
+                (None, '(') => return Some(i),
+                _ => {}
+            }
+            None
+        })?;
+        let body = balanced(&create_sql[open..])?;
+        let elements = split_top_level(body)
+            .into_iter()
+            .map(|element| element.trim().to_string())
+            .collect();
+        let rest = create_sql[open + body.len() + 2..].trim_start();
+        let condition = rest
+            .get(..5)
+            .filter(|keyword| keyword.eq_ignore_ascii_case("WHERE"))
+            .map(|_| rest[5..].trim().to_string());
+        Some((elements, condition))
+    }
+
+    /// Builds the table options from the `wr` and `strict` flags of
+    /// `PRAGMA table_list`.
+    #[must_use]
+    pub fn table_options_from_pragma(without_rowid: bool, strict: bool) -> Vec<TableOption> {
+        let mut options = Vec::new();
+        if without_rowid {
+            options.push(TableOption::WithoutRowid);
+        }
+        if strict {
+            options.push(TableOption::Strict);
+        }
+        options
+    }
+
+    /// Returns the generated column of `PRAGMA table_xinfo` row data,
+    /// reading its expression from the table's `CREATE TABLE` SQL.
+    ///
+    /// Returns `None` for regular columns (`hidden` other than 2 or 3)
+    /// and when `create_sql` has no expression for the column.
+    #[must_use]
+    pub fn generated_from_xinfo(
+        create_sql: &str,
+        column: &str,
+        hidden: i64,
+    ) -> Option<GeneratedColumn> {
+        let stored = match hidden {
+            2 => false,
+            3 => true,
+            _ => return None,
+        };
+        let body = create_sql.find('(').map(|start| &create_sql[start + 1..])?;
+        split_top_level(body)
+            .into_iter()
+            .find(|def| unquote(leading_word(def)).eq_ignore_ascii_case(column))
+            .and_then(generated_expression)
+            .map(|expression| GeneratedColumn { expression, stored })
+    }
+
+    /// Returns the column declared `AUTOINCREMENT` in a table's
+    /// `CREATE TABLE` SQL, if any.
+    ///
+    /// SQLite only accepts `AUTOINCREMENT` on the `INTEGER PRIMARY KEY`
+    /// column that aliases the rowid, written in its definition or in a
+    /// `PRIMARY KEY (id AUTOINCREMENT)` constraint. The SQL is tokenised,
+    /// so the word in a string, a comment, or a quoted name is ignored.
+    #[must_use]
+    pub fn autoincrement_column(create_sql: &str) -> Option<String> {
+        let mut depth = 0usize;
+        // The first word of the current definition, and the last name
+        // seen, which precedes `AUTOINCREMENT` inside a constraint.
+        let mut definition = None;
+        let mut last = None;
+        let mut starts_definition = false;
+        for token in Lexer::new(create_sql).tokens() {
+            let name = match token.ok()?.kind {
+                TokenKind::LeftParen => {
+                    depth += 1;
+                    starts_definition = depth == 1;
+                    continue;
+                }
+                TokenKind::RightParen => {
+                    depth = depth.saturating_sub(1);
+                    continue;
+                }
+                TokenKind::Comma if depth == 1 => {
+                    starts_definition = true;
+                    continue;
+                }
+                TokenKind::Keyword(Keyword::Autoincrement) => {
+                    return if depth == 1 { definition } else { last };
+                }
+                TokenKind::Identifier(name) => Some(name),
+                TokenKind::Keyword(keyword) => Some(keyword.as_str().to_string()),
+                _ => None,
+            };
+            if starts_definition {
+                definition.clone_from(&name);
+                starts_definition = false;
+            }
+            last = name;
+        }
+        None
+    }
+
+    /// Splits a column list at commas outside parentheses and quotes,
+    /// stopping at the parenthesis that closes the list.
+    fn split_top_level(body: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut start = 0;
+        for (i, c) in body.char_indices() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '[') => quote = Some(']'),
+                (None, '(') => depth += 1,
+                (None, ')') if depth == 0 => {
+                    parts.push(&body[start..i]);
+                    return parts;
+                }
+                (None, ')') => depth -= 1,
+                (None, ',') if depth == 0 => {
+                    parts.push(&body[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&body[start..]);
+        parts
+    }
+
+    /// Returns the column name at the start of a definition, still
+    /// quoted if it was.
+    fn leading_word(def: &str) -> &str {
+        let def = def.trim_start();
+        let close = match def.chars().next() {
+            Some(q @ ('"' | '`')) => q,
+            Some('[') => ']',
+            _ => {
+                let end = def.find(char::is_whitespace).unwrap_or(def.len());
+                return &def[..end];
+            }
+        };
+        def[1..].find(close).map_or(def, |end| &def[..end + 2])
+    }
+
+    /// Strips identifier quotes.
+    fn unquote(name: &str) -> String {
+        let inner = name
+            .strip_prefix('"')
+            .and_then(|n| n.strip_suffix('"'))
+            .map(|n| n.replace("\"\"", "\""))
+            .or_else(|| {
+                name.strip_prefix('`')
+                    .and_then(|n| n.strip_suffix('`'))
+                    .map(str::to_string)
+            })
+            .or_else(|| {
+                name.strip_prefix('[')
+                    .and_then(|n| n.strip_suffix(']'))
+                    .map(str::to_string)
+            });
+        inner.unwrap_or_else(|| name.to_string())
+    }
+
+    /// Returns the expression of an `AS (...)` clause of a column
+    /// definition, if any.
+    fn generated_expression(def: &str) -> Option<String> {
+        let upper = def.to_ascii_uppercase();
+        let bytes = upper.as_bytes();
+        let mut depth = 0usize;
+        let mut quote = None;
+        for (i, c) in upper.char_indices() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '[') => quote = Some(']'),
+                (None, '(') => depth += 1,
+                (None, ')') => depth = depth.saturating_sub(1),
+                (None, 'A')
+                    if depth == 0
+                        && upper[i..].starts_with("AS")
+                        && (i == 0 || !is_word_byte(bytes[i - 1]))
+                        && upper[i + 2..].trim_start().starts_with('(') =>
+                {
+                    let open = i + 2 + upper[i + 2..].find('(')?;
+                    return balanced(&def[open..]).map(|expr| expr.trim().to_string());
+                }
+                _ => {}
+            }
         }
+        None
+    }
+
+    /// Returns the text between a leading `(` and its matching `)`.
+    fn balanced(s: &str) -> Option<&str> {
+        let mut depth = 0usize;
+        let mut quote = None;
+        for (i, c) in s.char_indices() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&s[1..i]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    const fn is_word_byte(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b == b'_'
     }
 
     #[cfg(test)]
@@ -168,5 +457,111 @@ pub mod sqlite_helpers {
             let col = column_from_pragma("count", "INTEGER", false, Some("42"), false);
             assert_eq!(col.default, Some(DefaultValue::Integer(42)));
         }
+
+        #[test]
+        fn table_options_from_table_list() {
+            assert!(table_options_from_pragma(false, false).is_empty());
+            assert_eq!(
+                table_options_from_pragma(true, true),
+                [TableOption::WithoutRowid, TableOption::Strict]
+            );
+        }
+
+        #[test]
+        fn foreign_key_actions_from_pragma() {
+            assert_eq!(foreign_key_action_from_pragma("NO ACTION"), None);
+            assert_eq!(
+                foreign_key_action_from_pragma("CASCADE"),
+                Some(ForeignKeyAction::Cascade)
+            );
+            assert_eq!(
+                foreign_key_action_from_pragma("SET NULL"),
+                Some(ForeignKeyAction::SetNull)
+            );
+        }
+
+        #[test]
+        fn index_parts_from_create_sql() {
+            assert_eq!(
+                index_parts_from_sql(
+                    "CREATE UNIQUE INDEX \"idx (a)\" ON \"line items\" \
+                     (lower(email), \"name\") WHERE deleted_at IS NULL"
+                ),
+                Some((
+                    vec!["lower(email)".into(), "\"name\"".into()],
+                    Some("deleted_at IS NULL".into())
+                ))
+            );
+            assert_eq!(
+                index_parts_from_sql("CREATE INDEX idx ON t (a)"),
+                Some((vec!["a".into()], None))
+            );
+            assert_eq!(index_parts_from_sql("CREATE INDEX idx"), None);
+        }
+
+        // As stored in `sqlite_master` by SQLite 3.40.
+        const CREATE_SQL: &str = "CREATE TABLE \"line items\" (id INTEGER PRIMARY KEY, \
+             price REAL NOT NULL CHECK (CAST(price AS INTEGER) >= 0), qty INTEGER, \
+             total REAL GENERATED ALWAYS AS (price * qty) STORED, \
+             label TEXT AS (upper(\"name\")), \"name\" TEXT, \
+             \"as of\" TEXT AS ('a, b') VIRTUAL) STRICT, WITHOUT ROWID";
+
+        #[test]
+        fn generated_columns_from_create_sql() {
+            assert_eq!(
+                generated_from_xinfo(CREATE_SQL, "total", 3),
+                Some(GeneratedColumn {
+                    expression: "price * qty".into(),
+                    stored: true,
+                })
+            );
+            assert_eq!(
+                generated_from_xinfo(CREATE_SQL, "label", 2),
+                Some(GeneratedColumn {
+                    expression: "upper(\"name\")".into(),
+                    stored: false,
+                })
+            );
+            assert_eq!(
+                generated_from_xinfo(CREATE_SQL, "as of", 2),
+                Some(GeneratedColumn {
+                    expression: "'a, b'".into(),
+                    stored: false,
+                })
+            );
+        }
+
+        #[test]
+        fn regular_columns_are_not_generated() {
+            assert_eq!(generated_from_xinfo(CREATE_SQL, "price", 0), None);
+            assert_eq!(generated_from_xinfo(CREATE_SQL, "name", 0), None);
+            // A CAST inside a CHECK is not a generated column.
+            assert_eq!(generated_from_xinfo(CREATE_SQL, "price", 3), None);
+        }
+
+        #[test]
+        fn autoincrement_column_from_create_sql() {
+            assert_eq!(
+                autoincrement_column(
+                    "CREATE TABLE t (note TEXT DEFAULT 'AUTOINCREMENT', \
+                     \"id\" INTEGER PRIMARY KEY AUTOINCREMENT, n INTEGER)"
+                ),
+                Some("id".into())
+            );
+            assert_eq!(
+                autoincrement_column(
+                    "CREATE TABLE t (id INTEGER, n INTEGER, PRIMARY KEY (id AUTOINCREMENT))"
+                ),
+                Some("id".into())
+            );
+            assert_eq!(
+                autoincrement_column(
+                    "CREATE TABLE t (id INTEGER PRIMARY KEY, \
+                     \"autoincrement\" TEXT -- AUTOINCREMENT\n)"
+                ),
+                None
+            );
+            assert_eq!(autoincrement_column(CREATE_SQL), None);
+        }
     }
 }
@@ -4,9 +4,12 @@
 //! `MigrationRunner` that executes migrations in dependency order.
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
 
+use super::apply::ApplyError;
 use super::dialect::MigrationDialect;
 use super::operation::Operation;
+use super::snapshot::SchemaSnapshot;
 use super::state::MigrationState;
 
 /// A database migration with typed up/down operations.
@@ -104,6 +107,99 @@ pub struct MigrationStatus {
     pub applied_at: Option<String>,
 }
 
+/// Direction in which a migration is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationDirection {
+    /// Applying the migration (`up()`).
+    Up,
+    /// Rolling the migration back (`down()`).
+    Down,
+}
+
+/// Timing of a single executed statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementReport {
+    /// The SQL that was executed.
+    pub sql: String,
+    /// How long the statement took; zero on `wasm32-unknown-unknown`,
+    /// which has no clock.
+    pub duration: Duration,
+}
+
+/// Result of executing one migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigrationReport {
+    /// The migration ID.
+    pub id: &'static str,
+    /// Whether the migration was applied or rolled back.
+    pub direction: MigrationDirection,
+    /// The executed statements, in order.
+    pub statements: Vec<StatementReport>,
+    /// How long the whole migration took; zero on
+    /// `wasm32-unknown-unknown`, which has no clock.
+    pub duration: Duration,
+}
+
+/// Measures how long statements take.
+///
+/// `Instant::now()` panics on `wasm32-unknown-unknown`, so there every
+/// duration is zero.
+#[derive(Clone, Copy)]
+struct Stopwatch {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    started: std::time::Instant,
+}
+
+impl Stopwatch {
+    fn start() -> Self {
+        Self {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn elapsed(self) -> Duration {
+        self.started.elapsed()
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    const fn elapsed(self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// Callbacks invoked while a [`MigrationRunner`] executes migrations,
+/// e.g. to drive a progress bar or emit metrics.
+///
+/// Every method does nothing by default.
+pub trait MigrationHooks {
+    /// Called before the first statement of a migration runs.
+    fn before_migration(
+        &mut self,
+        _id: &'static str,
+        _direction: MigrationDirection,
+        _statements: usize,
+    ) {
+    }
+
+    /// Called after every statement of a migration succeeded.
+    fn after_migration(&mut self, _report: &AppliedMigrationReport) {}
+
+    /// Called before a statement runs; `index` counts from 0 within the
+    /// migration.
+    fn before_statement(&mut self, _id: &'static str, _index: usize, _sql: &str) {}
+
+    /// Called after a statement succeeded.
+    fn after_statement(&mut self, _id: &'static str, _index: usize, _report: &StatementReport) {}
+
+    /// Called when a statement fails, before the runner returns `error`.
+    ///
+    /// The migrations completed before it were reported through
+    /// [`after_migration`](Self::after_migration) and stay applied.
+    fn on_error(&mut self, _id: &'static str, _index: usize, _error: &MigrationError) {}
+}
+
 /// Runs migrations in dependency order.
 ///
 /// The runner tracks which migrations are registered and uses the provided
@@ -145,6 +241,7 @@ pub struct MigrationStatus {
 pub struct MigrationRunner<D: MigrationDialect> {
     migrations: Vec<RegisteredMigration>,
     dialect: D,
+    hooks: Option<Box<dyn MigrationHooks + Send>>,
 }
 
 impl<D: MigrationDialect> MigrationRunner<D> {
@@ -154,9 +251,20 @@ impl<D: MigrationDialect> MigrationRunner<D> {
         Self {
             migrations: Vec::new(),
             dialect,
+            hooks: None,
         }
     }
 
+    /// Sets the callbacks invoked by [`apply_pending`](Self::apply_pending)
+    /// and [`apply_rollback`](Self::apply_rollback), replacing any set
+    /// before.
+    ///
+    /// Hooks must be `Send` so the runner can move to another thread.
+    pub fn set_hooks(&mut self, hooks: impl MigrationHooks + Send + 'static) -> &mut Self {
+        self.hooks = Some(Box::new(hooks));
+        self
+    }
+
     /// Registers a migration.
     pub fn register<M: Migration>(&mut self) -> &mut Self {
         self.migrations.push(RegisteredMigration::new::<M>());
@@ -215,11 +323,13 @@ impl<D: MigrationDialect> MigrationRunner<D> {
             }
         }
 
-        // Kahn's algorithm for topological sort
-        let mut queue: VecDeque<&str> = in_degree
+        // Kahn's algorithm for topological sort, seeded in registration
+        // order so independent migrations keep a stable order
+        let mut queue: VecDeque<&str> = self
+            .migrations
             .iter()
-            .filter(|(_, deg)| **deg == 0)
-            .map(|(id, _)| *id)
+            .filter(|m| in_degree[m.id] == 0)
+            .map(|m| m.id)
             .collect();
         let mut result = Vec::new();
 
@@ -250,6 +360,11 @@ impl<D: MigrationDialect> MigrationRunner<D> {
     /// Generates SQL for all pending migrations.
     ///
     /// Returns a list of (migration_id, sql_statements) pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::UnsupportedOperation`] if a pending
+    /// migration uses a feature the dialect lacks.
     pub fn sql_for_pending(
         &self,
         state: &MigrationState,
@@ -263,17 +378,14 @@ impl<D: MigrationDialect> MigrationRunner<D> {
         let mut result = Vec::new();
         for migration in pending {
             let operations = (migration.up)();
-            let sqls: Vec<String> = operations
-                .iter()
-                .map(|op| self.dialect.generate_sql(op))
-                .collect();
-            result.push((migration.id, sqls));
+            result.push((migration.id, self.generate(migration.id, &operations)?));
         }
 
         Ok(result)
     }
 
-    /// Generates SQL for rolling back migrations.
+    /// Generates SQL for rolling back the last `count` applied
+    /// migrations, in reverse dependency order.
     ///
     /// Returns a list of (migration_id, sql_statements) pairs in reverse order.
     pub fn sql_for_rollback(
@@ -284,31 +396,235 @@ impl<D: MigrationDialect> MigrationRunner<D> {
         let sorted = self.sorted_migrations()?;
 
         // Get applied migrations in reverse order
-        let applied: Vec<_> = sorted
+        let ids: Vec<&str> = sorted
             .into_iter()
             .rev()
             .filter(|m| state.is_applied(m.id))
             .take(count)
+            .map(|m| m.id)
             .collect();
 
+        self.sql_for_rollback_of(state, &ids)
+    }
+
+    /// Generates SQL for rolling back every applied migration that
+    /// depends, directly or transitively, on `id`, so that `id` is the
+    /// newest applied migration of its branch. `id` itself stays
+    /// applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::UnknownMigration`] if `id` is not
+    /// registered or not applied.
+    pub fn sql_for_rollback_to(
+        &self,
+        state: &MigrationState,
+        id: &str,
+    ) -> Result<Vec<(&'static str, Vec<String>)>, MigrationError> {
+        if !state.is_applied(id) || !self.migrations.iter().any(|m| m.id == id) {
+            return Err(MigrationError::UnknownMigration(id.to_string()));
+        }
+        let mut targets: HashSet<&str> = HashSet::from([id]);
+        for m in self.sorted_migrations()? {
+            if m.dependencies.iter().any(|dep| targets.contains(dep)) {
+                targets.insert(m.id);
+            }
+        }
+        targets.remove(id);
+        let ids: Vec<&str> = targets
+            .into_iter()
+            .filter(|t| state.is_applied(t))
+            .collect();
+        self.sql_for_rollback_of(state, &ids)
+    }
+
+    /// Generates SQL for rolling back exactly the migrations in `ids`,
+    /// dependents first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::HasDependents`] if an applied
+    /// migration outside `ids` depends on one of them,
+    /// [`MigrationError::NotReversible`] if one has no down operations,
+    /// and [`MigrationError::UnsupportedOperation`] if one uses a
+    /// feature the dialect lacks.
+    pub fn sql_for_rollback_of(
+        &self,
+        state: &MigrationState,
+        ids: &[&str],
+    ) -> Result<Vec<(&'static str, Vec<String>)>, MigrationError> {
+        let sorted = self.sorted_migrations()?;
+
+        // Refuse to leave an applied migration without its dependency.
+        for &id in ids {
+            let dependents: Vec<String> = sorted
+                .iter()
+                .filter(|m| {
+                    state.is_applied(m.id) && !ids.contains(&m.id) && m.dependencies.contains(&id)
+                })
+                .map(|m| m.id.to_string())
+                .collect();
+            if !dependents.is_empty() {
+                return Err(MigrationError::HasDependents {
+                    migration: id.to_string(),
+                    dependents,
+                });
+            }
+        }
+
         let mut result = Vec::new();
-        for migration in applied {
+        for migration in sorted.into_iter().rev().filter(|m| ids.contains(&m.id)) {
             let operations = (migration.down)();
             if operations.is_empty() {
                 return Err(MigrationError::NotReversible(migration.id.to_string()));
             }
-            let sqls: Vec<String> = operations
-                .iter()
-                .map(|op| self.dialect.generate_sql(op))
-                .collect();
-            result.push((migration.id, sqls));
+            result.push((migration.id, self.generate(migration.id, &operations)?));
         }
 
         Ok(result)
     }
 
-    /// Validates that all dependencies exist and are registered.
+    /// Applies all pending migrations by passing each statement to
+    /// `execute`, and marks them applied in `state`.
+    ///
+    /// Execution stops at the first failing statement; the migrations
+    /// before it stay applied and the failing one is not marked. The
+    /// runner only updates the in-memory `state`; record applied
+    /// migrations in the tracking table (e.g. from
+    /// [`MigrationHooks::after_migration`]) to persist them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::DatabaseError`] if `execute` fails, or
+    /// the error of [`sql_for_pending`](Self::sql_for_pending).
+    pub fn apply_pending<E: std::fmt::Display>(
+        &mut self,
+        state: &mut MigrationState,
+        execute: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<Vec<AppliedMigrationReport>, MigrationError> {
+        let plan = self.sql_for_pending(state)?;
+        self.execute_plan(plan, MigrationDirection::Up, state, execute)
+    }
+
+    /// Rolls back the last `count` applied migrations by passing each
+    /// statement to `execute`, and marks them unapplied in `state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::DatabaseError`] if `execute` fails, or
+    /// the error of [`sql_for_rollback`](Self::sql_for_rollback).
+    pub fn apply_rollback<E: std::fmt::Display>(
+        &mut self,
+        state: &mut MigrationState,
+        count: usize,
+        execute: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<Vec<AppliedMigrationReport>, MigrationError> {
+        let plan = self.sql_for_rollback(state, count)?;
+        self.execute_plan(plan, MigrationDirection::Down, state, execute)
+    }
+
+    /// Rolls back every applied migration that depends on `id` (see
+    /// [`sql_for_rollback_to`](Self::sql_for_rollback_to)) by passing
+    /// each statement to `execute`, and marks them unapplied in `state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::DatabaseError`] if `execute` fails, or
+    /// the error of [`sql_for_rollback_to`](Self::sql_for_rollback_to).
+    pub fn apply_rollback_to<E: std::fmt::Display>(
+        &mut self,
+        state: &mut MigrationState,
+        id: &str,
+        execute: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<Vec<AppliedMigrationReport>, MigrationError> {
+        let plan = self.sql_for_rollback_to(state, id)?;
+        self.execute_plan(plan, MigrationDirection::Down, state, execute)
+    }
+
+    /// Executes planned statements, timing them and calling the hooks.
+    fn execute_plan<E: std::fmt::Display>(
+        &mut self,
+        plan: Vec<(&'static str, Vec<String>)>,
+        direction: MigrationDirection,
+        state: &mut MigrationState,
+        mut execute: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<Vec<AppliedMigrationReport>, MigrationError> {
+        let mut reports = Vec::with_capacity(plan.len());
+        for (id, sqls) in plan {
+            if let Some(hooks) = self.hooks.as_mut() {
+                hooks.before_migration(id, direction, sqls.len());
+            }
+            let started = Stopwatch::start();
+            let mut statements = Vec::with_capacity(sqls.len());
+            for (index, sql) in sqls.into_iter().enumerate() {
+                if let Some(hooks) = self.hooks.as_mut() {
+                    hooks.before_statement(id, index, &sql);
+                }
+                let statement_started = Stopwatch::start();
+                if let Err(e) = execute(&sql) {
+                    let error =
+                        MigrationError::DatabaseError(format!("migration '{id}' failed: {e}"));
+                    if let Some(hooks) = self.hooks.as_mut() {
+                        hooks.on_error(id, index, &error);
+                    }
+                    return Err(error);
+                }
+                let report = StatementReport {
+                    sql,
+                    duration: statement_started.elapsed(),
+                };
+                if let Some(hooks) = self.hooks.as_mut() {
+                    hooks.after_statement(id, index, &report);
+                }
+                statements.push(report);
+            }
+            match direction {
+                MigrationDirection::Up => state.mark_applied(id),
+                MigrationDirection::Down => state.mark_unapplied(id),
+            }
+            let report = AppliedMigrationReport {
+                id,
+                direction,
+                statements,
+                duration: started.elapsed(),
+            };
+            if let Some(hooks) = self.hooks.as_mut() {
+                hooks.after_migration(&report);
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// Validates that all dependencies exist and are registered, and
+    /// that the migrations apply cleanly to an empty database.
+    ///
+    /// A first migration that alters tables created outside the runner
+    /// fails here; use [`validate_against`](Self::validate_against) with
+    /// a snapshot of the existing schema instead.
+    ///
+    /// # Errors
+    ///
+    /// See [`validate_against`](Self::validate_against).
     pub fn validate(&self) -> Result<(), MigrationError> {
+        self.validate_against(&SchemaSnapshot::new())
+    }
+
+    /// Validates that all dependencies exist and are registered, then
+    /// simulates every migration's `up()` in dependency order on a copy
+    /// of `initial`, catching logically invalid operations (dropping a
+    /// missing column, creating a table twice) before any SQL runs.
+    ///
+    /// The schema is unknown after raw SQL, so simulation stops at the
+    /// first [`Operation::RunSql`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::UnsupportedOperation`] if an `up()` or
+    /// `down()` operation uses a feature the dialect lacks, and
+    /// [`MigrationError::InvalidOperation`] for the first operation that
+    /// cannot be applied.
+    pub fn validate_against(&self, initial: &SchemaSnapshot) -> Result<(), MigrationError> {
         let ids: HashSet<&str> = self.migrations.iter().map(|m| m.id).collect();
 
         for m in &self.migrations {
@@ -323,10 +639,62 @@ impl<D: MigrationDialect> MigrationRunner<D> {
         }
 
         // Check for circular dependencies
-        let _ = self.sorted_migrations()?;
+        let sorted = self.sorted_migrations()?;
+
+        for migration in &sorted {
+            for operation in (migration.up)().iter().chain(&(migration.down)()) {
+                self.check_supported(migration.id, operation)?;
+            }
+        }
+
+        let mut schema = initial.clone();
+        for migration in sorted {
+            for operation in (migration.up)() {
+                if matches!(operation, Operation::RunSql(_)) {
+                    return Ok(());
+                }
+                operation.apply_to(&mut schema).map_err(|error| {
+                    MigrationError::InvalidOperation {
+                        migration: migration.id.to_string(),
+                        error,
+                    }
+                })?;
+            }
+        }
 
         Ok(())
     }
+
+    /// Generates SQL for one migration's operations, refusing any the
+    /// dialect cannot run.
+    fn generate(
+        &self,
+        migration: &str,
+        operations: &[Operation],
+    ) -> Result<Vec<String>, MigrationError> {
+        operations
+            .iter()
+            .map(|op| {
+                self.check_supported(migration, op)?;
+                Ok(self.dialect.generate_sql(op))
+            })
+            .collect()
+    }
+
+    /// Fails if the dialect cannot run `operation`.
+    fn check_supported(
+        &self,
+        migration: &str,
+        operation: &Operation,
+    ) -> Result<(), MigrationError> {
+        match self.dialect.unsupported_reason(operation) {
+            Some(reason) => Err(MigrationError::UnsupportedOperation {
+                migration: migration.to_string(),
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Errors that can occur during migration.
@@ -343,6 +711,32 @@ pub enum MigrationError {
     },
     /// A migration is not reversible.
     NotReversible(String),
+    /// A migration is not registered, or not applied.
+    UnknownMigration(String),
+    /// A migration cannot be rolled back while applied migrations
+    /// still depend on it.
+    HasDependents {
+        /// The migration to roll back.
+        migration: String,
+        /// The applied migrations that depend on it.
+        dependents: Vec<String>,
+    },
+    /// An operation cannot be applied to the schema left by the
+    /// migrations before it.
+    InvalidOperation {
+        /// The migration containing the operation.
+        migration: String,
+        /// Why the operation cannot be applied.
+        error: ApplyError,
+    },
+    /// An operation cannot be expressed in the runner's dialect (e.g. a
+    /// partitioned table on SQLite).
+    UnsupportedOperation {
+        /// The migration containing the operation.
+        migration: String,
+        /// Why the dialect cannot run the operation.
+        reason: String,
+    },
     /// Database error.
     DatabaseError(String),
 }
@@ -360,6 +754,24 @@ impl std::fmt::Display for MigrationError {
                 migration, dependency
             ),
             Self::NotReversible(id) => write!(f, "Migration '{}' is not reversible", id),
+            Self::UnknownMigration(id) => {
+                write!(f, "Migration '{}' is not registered or not applied", id)
+            }
+            Self::HasDependents {
+                migration,
+                dependents,
+            } => write!(
+                f,
+                "Migration '{}' is required by applied migrations: {}",
+                migration,
+                dependents.join(", ")
+            ),
+            Self::InvalidOperation { migration, error } => {
+                write!(f, "Migration '{}' is invalid: {}", migration, error)
+            }
+            Self::UnsupportedOperation { migration, reason } => {
+                write!(f, "Migration '{}' is unsupported: {}", migration, reason)
+            }
             Self::DatabaseError(msg) => write!(f, "Database error: {}", msg),
         }
     }
@@ -369,6 +781,8 @@ impl std::error::Error for MigrationError {}
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
     use super::*;
     use crate::migrations::column_builder::{bigint, boolean, varchar};
     use crate::migrations::dialect::SqliteDialect;
@@ -541,4 +955,280 @@ mod tests {
         let s2 = status.iter().find(|s| s.id == "0002_add_email").unwrap();
         assert!(!s2.applied);
     }
+
+    /// Records hook calls into a shared log.
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl MigrationHooks for Recorder {
+        fn before_migration(&mut self, id: &'static str, direction: MigrationDirection, n: usize) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("before {id} {direction:?} {n}"));
+        }
+
+        fn after_migration(&mut self, report: &AppliedMigrationReport) {
+            self.0.lock().unwrap().push(format!("after {}", report.id));
+        }
+
+        fn before_statement(&mut self, id: &'static str, index: usize, _sql: &str) {
+            self.0.lock().unwrap().push(format!("run {id} #{index}"));
+        }
+
+        fn after_statement(&mut self, id: &'static str, index: usize, _report: &StatementReport) {
+            self.0.lock().unwrap().push(format!("done {id} #{index}"));
+        }
+
+        fn on_error(&mut self, id: &'static str, index: usize, error: &MigrationError) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("error {id} #{index}: {error}"));
+        }
+    }
+
+    #[test]
+    fn test_apply_pending_with_hooks() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut runner = MigrationRunner::new(SqliteDialect::new());
+        runner.register::<Migration0001>();
+        runner.register::<Migration0002>();
+        runner.set_hooks(Recorder(log.clone()));
+
+        let mut state = MigrationState::new();
+        let mut executed = Vec::new();
+        let reports = runner
+            .apply_pending(&mut state, |sql| {
+                executed.push(sql.to_string());
+                Ok::<_, String>(())
+            })
+            .unwrap();
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].id, "0001_initial");
+        assert_eq!(reports[1].direction, MigrationDirection::Up);
+        assert_eq!(reports[1].statements[0].sql, executed[1]);
+        assert!(state.is_applied("0001_initial"));
+        assert!(state.is_applied("0002_add_email"));
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "before 0001_initial Up 1",
+                "run 0001_initial #0",
+                "done 0001_initial #0",
+                "after 0001_initial",
+                "before 0002_add_email Up 1",
+                "run 0002_add_email #0",
+                "done 0002_add_email #0",
+                "after 0002_add_email",
+            ]
+        );
+
+        let reports = runner
+            .apply_rollback(&mut state, 1, |_| Ok::<_, String>(()))
+            .unwrap();
+        assert_eq!(reports[0].id, "0002_add_email");
+        assert_eq!(reports[0].direction, MigrationDirection::Down);
+        assert!(!state.is_applied("0002_add_email"));
+    }
+
+    #[test]
+    fn test_apply_pending_stops_on_error() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut runner = MigrationRunner::new(SqliteDialect::new());
+        runner.register::<Migration0001>();
+        runner.register::<Migration0002>();
+        runner.set_hooks(Recorder(log.clone()));
+
+        let mut state = MigrationState::new();
+        let result = runner.apply_pending(&mut state, |sql| {
+            if sql.contains("ADD COLUMN") {
+                Err("duplicate column")
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(
+            result,
+            Err(MigrationError::DatabaseError(
+                "migration '0002_add_email' failed: duplicate column".to_string()
+            ))
+        );
+        assert!(state.is_applied("0001_initial"));
+        assert!(!state.is_applied("0002_add_email"));
+        assert_eq!(
+            log.lock().unwrap().last().unwrap(),
+            "error 0002_add_email #0: Database error: migration '0002_add_email' failed: duplicate column"
+        );
+    }
+
+    #[test]
+    fn test_runner_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<MigrationRunner<SqliteDialect>>();
+    }
+
+    /// Depends on 0001 only, in parallel with 0002.
+    struct Migration0004;
+    impl Migration for Migration0004 {
+        const ID: &'static str = "0004_posts";
+        const DEPENDENCIES: &'static [&'static str] = &["0001_initial"];
+        fn up() -> Vec<Operation> {
+            vec![
+                CreateTableBuilder::new()
+                    .name("posts")
+                    .column(bigint("id").primary_key().build())
+                    .build()
+                    .into(),
+            ]
+        }
+        fn down() -> Vec<Operation> {
+            vec![Operation::drop_table("posts")]
+        }
+    }
+
+    fn branching_runner() -> (MigrationRunner<SqliteDialect>, MigrationState) {
+        let mut runner = MigrationRunner::new(SqliteDialect::new());
+        runner.register::<Migration0001>();
+        runner.register::<Migration0002>();
+        runner.register::<Migration0003>();
+        runner.register::<Migration0004>();
+        let state = MigrationState::from_applied(
+            [
+                "0001_initial",
+                "0002_add_email",
+                "0003_add_active",
+                "0004_posts",
+            ]
+            .map(String::from),
+        );
+        (runner, state)
+    }
+
+    #[test]
+    fn test_rollback_refuses_required_migration() {
+        let (runner, state) = branching_runner();
+
+        let result = runner.sql_for_rollback_of(&state, &["0002_add_email"]);
+        assert_eq!(
+            result,
+            Err(MigrationError::HasDependents {
+                migration: "0002_add_email".to_string(),
+                dependents: vec!["0003_add_active".to_string()],
+            })
+        );
+
+        let sql = runner
+            .sql_for_rollback_of(&state, &["0002_add_email", "0003_add_active"])
+            .unwrap();
+        let ids: Vec<_> = sql.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, ["0003_add_active", "0002_add_email"]);
+    }
+
+    #[test]
+    fn test_rollback_to() {
+        let (mut runner, mut state) = branching_runner();
+
+        let sql = runner
+            .sql_for_rollback_to(&state, "0002_add_email")
+            .unwrap();
+        let ids: Vec<_> = sql.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, ["0003_add_active"]);
+
+        let reports = runner
+            .apply_rollback_to(&mut state, "0001_initial", |_| Ok::<_, String>(()))
+            .unwrap();
+        let ids: Vec<_> = reports.iter().map(|r| r.id).collect();
+        assert_eq!(ids, ["0003_add_active", "0004_posts", "0002_add_email"]);
+        assert!(state.is_applied("0001_initial"));
+        assert_eq!(state.applied_count(), 1);
+
+        assert_eq!(
+            runner.sql_for_rollback_to(&state, "0004_posts"),
+            Err(MigrationError::UnknownMigration("0004_posts".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_simulates_operations() {
+        let mut runner = MigrationRunner::new(SqliteDialect::new());
+        runner.register::<Migration0001>();
+        runner.register::<Migration0002>();
+        runner.register::<Migration0003>();
+        assert_eq!(runner.validate(), Ok(()));
+
+        struct DropMissing;
+        impl Migration for DropMissing {
+            const ID: &'static str = "0004_drop_missing";
+            const DEPENDENCIES: &'static [&'static str] = &["0003_add_active"];
+            fn up() -> Vec<Operation> {
+                vec![Operation::drop_column("users", "nickname")]
+            }
+            fn down() -> Vec<Operation> {
+                vec![]
+            }
+        }
+        runner.register::<DropMissing>();
+
+        assert_eq!(
+            runner.validate(),
+            Err(MigrationError::InvalidOperation {
+                migration: "0004_drop_missing".to_string(),
+                error: ApplyError::UnknownColumn {
+                    table: "users".to_string(),
+                    column: "nickname".to_string(),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_against_existing_schema() {
+        let mut runner = MigrationRunner::new(SqliteDialect::new());
+        runner.register::<Migration0001>();
+
+        let mut existing = SchemaSnapshot::new();
+        Migration0001::up()[0].apply_to(&mut existing).unwrap();
+
+        assert_eq!(
+            runner.validate_against(&existing),
+            Err(MigrationError::InvalidOperation {
+                migration: "0001_initial".to_string(),
+                error: ApplyError::TableExists("users".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_unsupported_operation_is_rejected() {
+        use crate::migrations::operation::PartitionStrategy;
+
+        struct Partitioned;
+        impl Migration for Partitioned {
+            const ID: &'static str = "0001_events";
+            fn up() -> Vec<Operation> {
+                vec![
+                    CreateTableBuilder::new()
+                        .name("events")
+                        .column(bigint("id").build())
+                        .partition_by(PartitionStrategy::Hash, &["id"])
+                        .build()
+                        .into(),
+                ]
+            }
+            fn down() -> Vec<Operation> {
+                vec![Operation::drop_table("events")]
+            }
+        }
+
+        let mut runner = MigrationRunner::new(SqliteDialect::new());
+        runner.register::<Partitioned>();
+        let error = MigrationError::UnsupportedOperation {
+            migration: "0001_events".to_string(),
+            reason: "sqlite does not support table partitioning; cannot create events".to_string(),
+        };
+        assert_eq!(runner.validate(), Err(error.clone()));
+        assert_eq!(runner.sql_for_pending(&MigrationState::new()), Err(error));
+    }
 }
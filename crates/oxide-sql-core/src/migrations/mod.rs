@@ -40,6 +40,7 @@
 //! }
 //! ```
 
+mod apply;
 pub mod codegen;
 mod column_builder;
 pub mod dialect;
@@ -51,20 +52,32 @@ pub mod snapshot;
 mod state;
 mod table_builder;
 
-pub use codegen::generate_migration_code;
+pub(crate) use column_builder::same_default;
+
+pub use apply::ApplyError;
+pub use codegen::{MigrationMetadata, generate_migration_code};
 pub use column_builder::{
-    ColumnBuilder, ColumnDefinition, DefaultValue, ForeignKeyAction, ForeignKeyRef, bigint, binary,
-    blob, boolean, char, date, datetime, decimal, double, integer, numeric, real, smallint, text,
-    time, timestamp, varbinary, varchar,
+    ColumnBuilder, ColumnDefinition, DefaultValue, ForeignKeyAction, ForeignKeyRef,
+    GeneratedColumn, array, bigint, binary, blob, boolean, char, date, datetime, decimal, double,
+    integer, numeric, real, smallint, text, time, timestamp, varbinary, varchar,
 };
 pub use dialect::{DuckDbDialect, MigrationDialect, PostgresDialect, SqliteDialect};
-pub use diff::{AmbiguousChange, DiffWarning, SchemaDiff, auto_diff_schema, auto_diff_table};
+pub use diff::{
+    AmbiguousChange, DiffWarning, SchemaDiff, auto_diff_schema, auto_diff_schema_for_dialect,
+    auto_diff_table, initial_from_tables,
+};
 pub use introspect::Introspect;
-pub use migration::{Migration, MigrationRunner, MigrationStatus};
+pub use migration::{
+    AppliedMigrationReport, Migration, MigrationDirection, MigrationError, MigrationHooks,
+    MigrationRunner, MigrationStatus, StatementReport,
+};
 pub use operation::{
-    AddColumnOp, AddForeignKeyOp, AlterColumnChange, AlterColumnOp, CreateIndexOp, CreateTableOp,
-    DropColumnOp, DropForeignKeyOp, DropIndexOp, DropTableOp, IndexType, Operation, RawSqlOp,
-    RenameColumnOp, RenameTableOp,
+    AddColumnOp, AddForeignKeyOp, AlterColumnChange, AlterColumnOp, AlterSequenceOp, CreateIndexOp,
+    CreatePartitionOp, CreateSchemaOp, CreateSequenceOp, CreateTableOp, CreateTriggerOp,
+    DropColumnOp, DropForeignKeyOp, DropIndexOp, DropSchemaOp, DropSequenceOp, DropTableOp,
+    DropTriggerOp, IndexColumn, IndexType, Operation, PartitionBound, PartitionBy,
+    PartitionStrategy, RawSqlOp, RenameColumnOp, RenameTableOp, TableOption, TriggerEvent,
+    TriggerTiming,
 };
 pub use snapshot::{
     ColumnSnapshot, ForeignKeySnapshot, IndexSnapshot, SchemaSnapshot, TableSnapshot,
@@ -2,7 +2,9 @@
 //!
 //! Defines all possible migration operations like CREATE TABLE, ADD COLUMN, etc.
 
-use super::column_builder::{ColumnDefinition, DefaultValue};
+use super::column_builder::{ColumnDefinition, DefaultValue, ForeignKeyRef, GeneratedColumn};
+use crate::ast::{Expr, Statement};
+use crate::parser::{ParseError, Parser};
 use crate::schema::{RustTypeMapping, TableSchema};
 
 /// All possible migration operations.
@@ -30,6 +32,22 @@ pub enum Operation {
     AddForeignKey(AddForeignKeyOp),
     /// Drop a foreign key constraint.
     DropForeignKey(DropForeignKeyOp),
+    /// Create a trigger.
+    CreateTrigger(CreateTriggerOp),
+    /// Drop a trigger.
+    DropTrigger(DropTriggerOp),
+    /// Create a sequence.
+    CreateSequence(CreateSequenceOp),
+    /// Alter a sequence.
+    AlterSequence(AlterSequenceOp),
+    /// Drop a sequence.
+    DropSequence(DropSequenceOp),
+    /// Create a partition of a partitioned table.
+    CreatePartition(CreatePartitionOp),
+    /// Create a schema (namespace).
+    CreateSchema(CreateSchemaOp),
+    /// Drop a schema.
+    DropSchema(DropSchemaOp),
     /// Run raw SQL.
     RunSql(RawSqlOp),
 }
@@ -39,6 +57,7 @@ impl Operation {
     #[must_use]
     pub fn drop_table(name: impl Into<String>) -> Self {
         Self::DropTable(DropTableOp {
+            schema: None,
             name: name.into(),
             if_exists: false,
             cascade: false,
@@ -49,6 +68,7 @@ impl Operation {
     #[must_use]
     pub fn drop_table_if_exists(name: impl Into<String>) -> Self {
         Self::DropTable(DropTableOp {
+            schema: None,
             name: name.into(),
             if_exists: true,
             cascade: false,
@@ -59,6 +79,7 @@ impl Operation {
     #[must_use]
     pub fn rename_table(old_name: impl Into<String>, new_name: impl Into<String>) -> Self {
         Self::RenameTable(RenameTableOp {
+            schema: None,
             old_name: old_name.into(),
             new_name: new_name.into(),
         })
@@ -68,6 +89,7 @@ impl Operation {
     #[must_use]
     pub fn add_column(table: impl Into<String>, column: ColumnDefinition) -> Self {
         Self::AddColumn(AddColumnOp {
+            schema: None,
             table: table.into(),
             column,
         })
@@ -77,6 +99,7 @@ impl Operation {
     #[must_use]
     pub fn drop_column(table: impl Into<String>, column: impl Into<String>) -> Self {
         Self::DropColumn(DropColumnOp {
+            schema: None,
             table: table.into(),
             column: column.into(),
         })
@@ -90,12 +113,103 @@ impl Operation {
         new_name: impl Into<String>,
     ) -> Self {
         Self::RenameColumn(RenameColumnOp {
+            schema: None,
             table: table.into(),
             old_name: old_name.into(),
             new_name: new_name.into(),
         })
     }
 
+    /// Creates a drop trigger operation.
+    #[must_use]
+    pub fn drop_trigger(name: impl Into<String>, table: impl Into<String>) -> Self {
+        Self::DropTrigger(DropTriggerOp {
+            schema: None,
+            name: name.into(),
+            table: table.into(),
+            if_exists: false,
+        })
+    }
+
+    /// Creates a drop sequence operation.
+    #[must_use]
+    pub fn drop_sequence(name: impl Into<String>) -> Self {
+        Self::DropSequence(DropSequenceOp {
+            schema: None,
+            name: name.into(),
+            if_exists: false,
+        })
+    }
+
+    /// Creates a partition of a partitioned table.
+    #[must_use]
+    pub fn create_partition(
+        name: impl Into<String>,
+        parent: impl Into<String>,
+        bound: PartitionBound,
+    ) -> Self {
+        Self::CreatePartition(CreatePartitionOp {
+            schema: None,
+            name: name.into(),
+            parent: parent.into(),
+            bound,
+            if_not_exists: false,
+        })
+    }
+
+    /// Creates a schema (namespace) operation.
+    #[must_use]
+    pub fn create_schema(name: impl Into<String>) -> Self {
+        Self::CreateSchema(CreateSchemaOp {
+            name: name.into(),
+            if_not_exists: false,
+        })
+    }
+
+    /// Creates a drop schema operation.
+    #[must_use]
+    pub fn drop_schema(name: impl Into<String>) -> Self {
+        Self::DropSchema(DropSchemaOp {
+            name: name.into(),
+            if_exists: false,
+            cascade: false,
+        })
+    }
+
+    /// Places a schema-scoped operation in `schema`, e.g.
+    /// `Operation::drop_table("events").in_schema("analytics")`.
+    ///
+    /// Schema operations and raw SQL are returned unchanged.
+    #[must_use]
+    pub fn in_schema(self, schema: impl Into<String>) -> Self {
+        self.with_schema(Some(schema.into()))
+    }
+
+    /// Sets the schema of a schema-scoped operation.
+    fn with_schema(mut self, schema: Option<String>) -> Self {
+        match &mut self {
+            Self::CreateTable(CreateTableOp { schema: s, .. })
+            | Self::DropTable(DropTableOp { schema: s, .. })
+            | Self::RenameTable(RenameTableOp { schema: s, .. })
+            | Self::AddColumn(AddColumnOp { schema: s, .. })
+            | Self::DropColumn(DropColumnOp { schema: s, .. })
+            | Self::AlterColumn(AlterColumnOp { schema: s, .. })
+            | Self::RenameColumn(RenameColumnOp { schema: s, .. })
+            | Self::CreateIndex(CreateIndexOp { schema: s, .. })
+            | Self::DropIndex(DropIndexOp { schema: s, .. })
+            | Self::AddForeignKey(AddForeignKeyOp { schema: s, .. })
+            | Self::DropForeignKey(DropForeignKeyOp { schema: s, .. })
+            | Self::CreateTrigger(CreateTriggerOp { schema: s, .. })
+            | Self::DropTrigger(DropTriggerOp { schema: s, .. })
+            | Self::CreateSequence(CreateSequenceOp { schema: s, .. })
+            | Self::AlterSequence(AlterSequenceOp { schema: s, .. })
+            | Self::DropSequence(DropSequenceOp { schema: s, .. })
+            | Self::CreatePartition(CreatePartitionOp { schema: s, .. }) => *s = schema,
+            Self::CreateSchema(_) | Self::DropSchema(_) | Self::RunSql(_) => {}
+        }
+        self
+    }
+
     /// Creates a raw SQL operation.
     #[must_use]
     pub fn run_sql(sql: impl Into<String>) -> Self {
@@ -120,20 +234,25 @@ impl Operation {
     #[must_use]
     pub fn reverse(&self) -> Option<Self> {
         match self {
-            Self::CreateTable(op) => Some(Self::drop_table(&op.name)),
+            Self::CreateTable(op) => {
+                Some(Self::drop_table(&op.name).with_schema(op.schema.clone()))
+            }
             Self::DropTable(_) => None, // Cannot reverse without knowing the schema
-            Self::RenameTable(op) => {
-                Some(Self::rename_table(op.new_name.clone(), op.old_name.clone()))
+            Self::RenameTable(op) => Some(
+                Self::rename_table(op.new_name.clone(), op.old_name.clone())
+                    .with_schema(op.schema.clone()),
+            ),
+            Self::AddColumn(op) => {
+                Some(Self::drop_column(&op.table, &op.column.name).with_schema(op.schema.clone()))
             }
-            Self::AddColumn(op) => Some(Self::drop_column(&op.table, &op.column.name)),
             Self::DropColumn(_) => None, // Cannot reverse without knowing the column definition
             Self::AlterColumn(_) => None, // Cannot reverse without knowing the old definition
-            Self::RenameColumn(op) => Some(Self::rename_column(
-                &op.table,
-                op.new_name.clone(),
-                op.old_name.clone(),
-            )),
+            Self::RenameColumn(op) => Some(
+                Self::rename_column(&op.table, op.new_name.clone(), op.old_name.clone())
+                    .with_schema(op.schema.clone()),
+            ),
             Self::CreateIndex(op) => Some(Self::DropIndex(DropIndexOp {
+                schema: op.schema.clone(),
                 name: op.name.clone(),
                 table: Some(op.table.clone()),
                 if_exists: false,
@@ -141,11 +260,26 @@ impl Operation {
             Self::DropIndex(_) => None, // Cannot reverse without knowing the index definition
             Self::AddForeignKey(op) => op.name.as_ref().map(|name| {
                 Self::DropForeignKey(DropForeignKeyOp {
+                    schema: op.schema.clone(),
                     table: op.table.clone(),
                     name: name.clone(),
                 })
             }),
             Self::DropForeignKey(_) => None, // Cannot reverse without knowing the FK definition
+            Self::CreateTrigger(op) => {
+                Some(Self::drop_trigger(&op.name, &op.table).with_schema(op.schema.clone()))
+            }
+            Self::DropTrigger(_) => None, // Cannot reverse without knowing the trigger body
+            Self::CreateSequence(op) => {
+                Some(Self::drop_sequence(&op.name).with_schema(op.schema.clone()))
+            }
+            Self::AlterSequence(_) => None, // Cannot reverse without knowing the old options
+            Self::DropSequence(_) => None,  // Cannot reverse without knowing the sequence options
+            Self::CreatePartition(op) => {
+                Some(Self::drop_table(&op.name).with_schema(op.schema.clone()))
+            }
+            Self::CreateSchema(op) => Some(Self::drop_schema(&op.name)),
+            Self::DropSchema(_) => None, // Cannot reverse without knowing the schema contents
             Self::RunSql(op) => op.down_sql.as_ref().map(|down| Self::run_sql(down.clone())),
         }
     }
@@ -160,6 +294,8 @@ impl Operation {
 /// Create table operation.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CreateTableOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub name: String,
     /// Column definitions.
@@ -168,6 +304,10 @@ pub struct CreateTableOp {
     pub constraints: Vec<TableConstraint>,
     /// Whether to use IF NOT EXISTS.
     pub if_not_exists: bool,
+    /// Partitioning of the table (`PARTITION BY`), if any.
+    pub partition_by: Option<PartitionBy>,
+    /// Dialect-specific table options.
+    pub options: Vec<TableOption>,
 }
 
 impl CreateTableOp {
@@ -187,14 +327,28 @@ impl CreateTableOp {
                 if let Some(expr) = col.default_expr {
                     def.default = Some(DefaultValue::Expression(expr.to_string()));
                 }
+                def.collation = col.collation.map(str::to_string);
+                def.references = col.references.map(|target| ForeignKeyRef {
+                    table: target.table.to_string(),
+                    column: target.column.to_string(),
+                    on_delete: None,
+                    on_update: None,
+                });
+                def.generated = col.generated.map(|generated| GeneratedColumn {
+                    expression: generated.expr.to_string(),
+                    stored: generated.stored,
+                });
                 def
             })
             .collect();
         Self {
+            schema: None,
             name: T::NAME.to_string(),
             columns,
             constraints: vec![],
             if_not_exists: false,
+            partition_by: None,
+            options: table_options::<T>(),
         }
     }
 
@@ -204,6 +358,28 @@ impl CreateTableOp {
         op.if_not_exists = true;
         op
     }
+
+    /// Returns why SQLite would reject this table's `WITHOUT ROWID`
+    /// option, or `None` if the table does not use it or uses it
+    /// validly: such a table needs a PRIMARY KEY and cannot use
+    /// AUTOINCREMENT.
+    pub(crate) fn without_rowid_conflict(&self) -> Option<&'static str> {
+        if !self.options.contains(&TableOption::WithoutRowid) {
+            return None;
+        }
+        let has_primary_key = self.columns.iter().any(|c| c.primary_key)
+            || self
+                .constraints
+                .iter()
+                .any(|c| matches!(c, TableConstraint::PrimaryKey { .. }));
+        if !has_primary_key {
+            Some("a WITHOUT ROWID table needs a PRIMARY KEY")
+        } else if self.columns.iter().any(|c| c.autoincrement) {
+            Some("a WITHOUT ROWID table cannot use AUTOINCREMENT")
+        } else {
+            None
+        }
+    }
 }
 
 /// Strips `Option<T>` wrapper from a Rust type string, returning
@@ -216,6 +392,18 @@ pub(super) fn strip_option(rust_type: &str) -> &str {
         .unwrap_or(rust_type)
 }
 
+/// Returns the table options declared on a `#[derive(Table)]` struct.
+pub(super) fn table_options<T: TableSchema>() -> Vec<TableOption> {
+    let mut options = Vec::new();
+    if T::WITHOUT_ROWID {
+        options.push(TableOption::WithoutRowid);
+    }
+    if T::STRICT {
+        options.push(TableOption::Strict);
+    }
+    options
+}
+
 impl From<CreateTableOp> for Operation {
     fn from(op: CreateTableOp) -> Self {
         Self::CreateTable(op)
@@ -263,9 +451,105 @@ pub enum TableConstraint {
     },
 }
 
+/// Dialect-specific table option.
+///
+/// Each dialect renders the options it understands and ignores the
+/// rest, so one `CreateTableOp` can target several databases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableOption {
+    /// SQLite `WITHOUT ROWID`.
+    WithoutRowid,
+    /// SQLite `STRICT` (type-checked columns, SQLite 3.37+).
+    Strict,
+    /// PostgreSQL `TABLESPACE name`.
+    Tablespace(String),
+    /// PostgreSQL `UNLOGGED` (not written to the WAL).
+    Unlogged,
+}
+
+/// How rows of a partitioned table are split between partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionStrategy {
+    /// Contiguous ranges of the key.
+    Range,
+    /// Explicit lists of key values.
+    List,
+    /// Hash of the key.
+    Hash,
+}
+
+impl PartitionStrategy {
+    /// Returns the SQL keyword for this strategy.
+    #[must_use]
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            Self::Range => "RANGE",
+            Self::List => "LIST",
+            Self::Hash => "HASH",
+        }
+    }
+}
+
+/// `PARTITION BY` clause of a partitioned table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionBy {
+    /// Partitioning strategy.
+    pub strategy: PartitionStrategy,
+    /// Partition key columns.
+    pub columns: Vec<String>,
+}
+
+/// Values a partition accepts. Values are SQL expressions, e.g.
+/// `'2024-01-01'` or `MINVALUE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionBound {
+    /// `FOR VALUES FROM (..) TO (..)`; `to` is exclusive.
+    Range {
+        /// Lower bound, one value per key column.
+        from: Vec<String>,
+        /// Upper bound, one value per key column.
+        to: Vec<String>,
+    },
+    /// `FOR VALUES IN (..)`.
+    List(Vec<String>),
+    /// `FOR VALUES WITH (MODULUS .., REMAINDER ..)`.
+    Hash {
+        /// Number of hash partitions.
+        modulus: u32,
+        /// Remainder selecting this partition.
+        remainder: u32,
+    },
+    /// `DEFAULT`: rows no other partition accepts.
+    Default,
+}
+
+/// Create partition operation: a child table of a partitioned table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatePartitionOp {
+    /// Schema of the partition and its parent; `None` uses the default
+    /// schema.
+    pub schema: Option<String>,
+    /// Partition (child table) name.
+    pub name: String,
+    /// Partitioned parent table.
+    pub parent: String,
+    /// Values the partition accepts.
+    pub bound: PartitionBound,
+    /// Whether to use IF NOT EXISTS.
+    pub if_not_exists: bool,
+}
+
+impl From<CreatePartitionOp> for Operation {
+    fn from(op: CreatePartitionOp) -> Self {
+        Self::CreatePartition(op)
+    }
+}
+
 /// Drop table operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropTableOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub name: String,
     /// Whether to use IF EXISTS.
@@ -283,6 +567,8 @@ impl From<DropTableOp> for Operation {
 /// Rename table operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RenameTableOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Current table name.
     pub old_name: String,
     /// New table name.
@@ -298,6 +584,8 @@ impl From<RenameTableOp> for Operation {
 /// Add column operation.
 #[derive(Debug, Clone, PartialEq)]
 pub struct AddColumnOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub table: String,
     /// Column definition.
@@ -313,6 +601,8 @@ impl From<AddColumnOp> for Operation {
 /// Drop column operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropColumnOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub table: String,
     /// Column name.
@@ -339,13 +629,24 @@ pub enum AlterColumnChange {
     /// Add or drop a UNIQUE constraint.
     SetUnique(bool),
     /// Mark autoincrement change (informational — most DBs cannot
-    /// alter this; prefer using [`DiffWarning`] instead).
+    /// alter this; prefer using [`DiffWarning`](super::DiffWarning) instead).
     SetAutoincrement(bool),
+    /// Change the collation, or reset it to the default with `None`.
+    /// Carries the column's data type, which PostgreSQL and DuckDB
+    /// restate when changing collation.
+    SetCollation {
+        /// The column's data type.
+        data_type: crate::ast::DataType,
+        /// The new collation name.
+        collation: Option<String>,
+    },
 }
 
 /// Alter column operation.
 #[derive(Debug, Clone, PartialEq)]
 pub struct AlterColumnOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub table: String,
     /// Column name.
@@ -363,6 +664,8 @@ impl From<AlterColumnOp> for Operation {
 /// Rename column operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RenameColumnOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub table: String,
     /// Current column name.
@@ -391,15 +694,74 @@ pub enum IndexType {
     Gin,
 }
 
+/// An element of an index: a plain column or an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexColumn {
+    /// A column, by name.
+    Column(String),
+    /// An expression, e.g. `lower(email)`.
+    Expr(Expr),
+}
+
+impl IndexColumn {
+    /// Parses an index element from SQL. A bare column name becomes
+    /// [`IndexColumn::Column`]; anything else becomes
+    /// [`IndexColumn::Expr`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if `sql` is not a valid expression.
+    pub fn parse(sql: &str) -> Result<Self, ParseError> {
+        Ok(match Parser::new(sql).parse_expr()? {
+            Expr::Column {
+                table: None, name, ..
+            } => Self::Column(name.value),
+            expr => Self::Expr(expr),
+        })
+    }
+}
+
+impl From<&str> for IndexColumn {
+    fn from(column: &str) -> Self {
+        Self::Column(column.to_string())
+    }
+}
+
+impl From<String> for IndexColumn {
+    fn from(column: String) -> Self {
+        Self::Column(column)
+    }
+}
+
+impl From<Expr> for IndexColumn {
+    fn from(expr: Expr) -> Self {
+        Self::Expr(expr)
+    }
+}
+
+impl PartialEq<str> for IndexColumn {
+    fn eq(&self, other: &str) -> bool {
+        matches!(self, Self::Column(name) if name == other)
+    }
+}
+
+impl PartialEq<&str> for IndexColumn {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
 /// Create index operation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CreateIndexOp {
+    /// Schema of the table and index; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Index name.
     pub name: String,
     /// Table name.
     pub table: String,
-    /// Columns to index.
-    pub columns: Vec<String>,
+    /// Columns and expressions to index.
+    pub columns: Vec<IndexColumn>,
     /// Whether this is a unique index.
     pub unique: bool,
     /// Index type.
@@ -407,7 +769,7 @@ pub struct CreateIndexOp {
     /// Whether to use IF NOT EXISTS.
     pub if_not_exists: bool,
     /// Partial index condition (WHERE clause).
-    pub condition: Option<String>,
+    pub condition: Option<Expr>,
 }
 
 impl From<CreateIndexOp> for Operation {
@@ -419,6 +781,8 @@ impl From<CreateIndexOp> for Operation {
 /// Drop index operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropIndexOp {
+    /// Schema of the table and index; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Index name.
     pub name: String,
     /// Table name (required for some dialects).
@@ -436,6 +800,8 @@ impl From<DropIndexOp> for Operation {
 /// Add foreign key operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AddForeignKeyOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub table: String,
     /// Optional constraint name.
@@ -461,6 +827,8 @@ impl From<AddForeignKeyOp> for Operation {
 /// Drop foreign key operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropForeignKeyOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub table: String,
     /// Constraint name.
@@ -473,6 +841,303 @@ impl From<DropForeignKeyOp> for Operation {
     }
 }
 
+/// When a trigger fires relative to its event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerTiming {
+    /// Fire before the row is changed.
+    Before,
+    /// Fire after the row is changed.
+    After,
+    /// Fire in place of the change (views only).
+    InsteadOf,
+}
+
+impl TriggerTiming {
+    /// Returns the SQL keyword(s) for this timing.
+    #[must_use]
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            Self::Before => "BEFORE",
+            Self::After => "AFTER",
+            Self::InsteadOf => "INSTEAD OF",
+        }
+    }
+}
+
+/// The data change that fires a trigger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerEvent {
+    /// `INSERT`.
+    Insert,
+    /// `UPDATE`, or `UPDATE OF col, ...` when columns are given.
+    Update(Vec<String>),
+    /// `DELETE`.
+    Delete,
+}
+
+/// Create trigger operation.
+///
+/// The WHEN condition and the body are kept as parsed AST, so they are
+/// rendered (and can be inspected) like any other statement rather than
+/// being opaque SQL strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateTriggerOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
+    /// Trigger name.
+    pub name: String,
+    /// Table the trigger is attached to.
+    pub table: String,
+    /// When the trigger fires.
+    pub timing: TriggerTiming,
+    /// The event that fires the trigger.
+    pub event: TriggerEvent,
+    /// `FOR EACH ROW` (`true`) or `FOR EACH STATEMENT` (`false`).
+    pub for_each_row: bool,
+    /// Optional WHEN condition; may reference `NEW` and `OLD`.
+    pub condition: Option<Expr>,
+    /// Statements run when the trigger fires.
+    pub body: Vec<Statement>,
+    /// Whether to use IF NOT EXISTS.
+    pub if_not_exists: bool,
+}
+
+impl CreateTriggerOp {
+    /// Creates a row-level trigger with an empty body.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        table: impl Into<String>,
+        timing: TriggerTiming,
+        event: TriggerEvent,
+    ) -> Self {
+        Self {
+            schema: None,
+            name: name.into(),
+            table: table.into(),
+            timing,
+            event,
+            for_each_row: true,
+            condition: None,
+            body: vec![],
+            if_not_exists: false,
+        }
+    }
+
+    /// Sets the schema of the trigger's table.
+    #[must_use]
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Makes this a statement-level trigger.
+    #[must_use]
+    pub const fn for_each_statement(mut self) -> Self {
+        self.for_each_row = false;
+        self
+    }
+
+    /// Sets the WHEN condition.
+    #[must_use]
+    pub fn when(mut self, condition: Expr) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Appends a statement to the trigger body.
+    #[must_use]
+    pub fn then(mut self, statement: Statement) -> Self {
+        self.body.push(statement);
+        self
+    }
+
+    /// Parses a single statement and appends it to the trigger body.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if `sql` is not a valid statement.
+    pub fn then_sql(self, sql: &str) -> Result<Self, ParseError> {
+        let statement = Parser::new(sql).parse_statement()?;
+        Ok(self.then(statement))
+    }
+
+    /// Uses IF NOT EXISTS.
+    #[must_use]
+    pub const fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+}
+
+impl From<CreateTriggerOp> for Operation {
+    fn from(op: CreateTriggerOp) -> Self {
+        Self::CreateTrigger(op)
+    }
+}
+
+/// Drop trigger operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropTriggerOp {
+    /// Schema (namespace) of the table; `None` uses the default schema.
+    pub schema: Option<String>,
+    /// Trigger name.
+    pub name: String,
+    /// Table the trigger is attached to (required by PostgreSQL).
+    pub table: String,
+    /// Whether to use IF EXISTS.
+    pub if_exists: bool,
+}
+
+impl From<DropTriggerOp> for Operation {
+    fn from(op: DropTriggerOp) -> Self {
+        Self::DropTrigger(op)
+    }
+}
+
+/// Create sequence operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateSequenceOp {
+    /// Schema (namespace) of the sequence; `None` uses the default schema.
+    pub schema: Option<String>,
+    /// Sequence name.
+    pub name: String,
+    /// First value (`START WITH`).
+    pub start: Option<i64>,
+    /// Step between values (`INCREMENT BY`).
+    pub increment: Option<i64>,
+    /// Whether to wrap around after reaching the limit.
+    pub cycle: bool,
+    /// Whether to use IF NOT EXISTS.
+    pub if_not_exists: bool,
+}
+
+impl CreateSequenceOp {
+    /// Creates a sequence with the database's default options.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            schema: None,
+            name: name.into(),
+            start: None,
+            increment: None,
+            cycle: false,
+            if_not_exists: false,
+        }
+    }
+
+    /// Creates the sequence in `schema` instead of the default schema.
+    #[must_use]
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Sets the first value.
+    #[must_use]
+    pub const fn start(mut self, start: i64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Sets the step between values.
+    #[must_use]
+    pub const fn increment(mut self, increment: i64) -> Self {
+        self.increment = Some(increment);
+        self
+    }
+
+    /// Wraps around after reaching the limit.
+    #[must_use]
+    pub const fn cycle(mut self) -> Self {
+        self.cycle = true;
+        self
+    }
+
+    /// Uses IF NOT EXISTS.
+    #[must_use]
+    pub const fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+}
+
+impl From<CreateSequenceOp> for Operation {
+    fn from(op: CreateSequenceOp) -> Self {
+        Self::CreateSequence(op)
+    }
+}
+
+/// Alter sequence operation. `None` fields are left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlterSequenceOp {
+    /// Schema (namespace) of the sequence; `None` uses the default schema.
+    pub schema: Option<String>,
+    /// Sequence name.
+    pub name: String,
+    /// Value to restart from (`RESTART WITH`).
+    pub restart: Option<i64>,
+    /// New step between values.
+    pub increment: Option<i64>,
+    /// New cycle setting.
+    pub cycle: Option<bool>,
+}
+
+impl From<AlterSequenceOp> for Operation {
+    fn from(op: AlterSequenceOp) -> Self {
+        Self::AlterSequence(op)
+    }
+}
+
+/// Drop sequence operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropSequenceOp {
+    /// Schema (namespace) of the sequence; `None` uses the default schema.
+    pub schema: Option<String>,
+    /// Sequence name.
+    pub name: String,
+    /// Whether to use IF EXISTS.
+    pub if_exists: bool,
+}
+
+impl From<DropSequenceOp> for Operation {
+    fn from(op: DropSequenceOp) -> Self {
+        Self::DropSequence(op)
+    }
+}
+
+/// Create schema operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateSchemaOp {
+    /// Schema name.
+    pub name: String,
+    /// Whether to use IF NOT EXISTS.
+    pub if_not_exists: bool,
+}
+
+impl From<CreateSchemaOp> for Operation {
+    fn from(op: CreateSchemaOp) -> Self {
+        Self::CreateSchema(op)
+    }
+}
+
+/// Drop schema operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropSchemaOp {
+    /// Schema name.
+    pub name: String,
+    /// Whether to use IF EXISTS.
+    pub if_exists: bool,
+    /// Whether to drop the objects the schema contains.
+    pub cascade: bool,
+}
+
+impl From<DropSchemaOp> for Operation {
+    fn from(op: DropSchemaOp) -> Self {
+        Self::DropSchema(op)
+    }
+}
+
 /// Raw SQL operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawSqlOp {
@@ -535,10 +1200,13 @@ mod tests {
     fn test_reverse_operations() {
         // Create table can be reversed to drop table
         let create = CreateTableOp {
+            schema: None,
             name: "users".to_string(),
             columns: vec![bigint("id").primary_key().build()],
             constraints: vec![],
             if_not_exists: false,
+            partition_by: None,
+            options: vec![],
         };
         let op = Operation::CreateTable(create);
         let reversed = op.reverse().expect("Should be reversible");
@@ -588,6 +1256,87 @@ mod tests {
         assert!(op.is_reversible());
     }
 
+    #[test]
+    fn test_trigger_operation() {
+        let op: Operation = CreateTriggerOp::new(
+            "users_audit",
+            "users",
+            TriggerTiming::After,
+            TriggerEvent::Update(vec!["email".to_string()]),
+        )
+        .when(Expr::qualified_column("OLD", "email").not_eq(Expr::qualified_column("NEW", "email")))
+        .then_sql("INSERT INTO audit (user_id) VALUES (NEW.id)")
+        .expect("valid body")
+        .into();
+        match &op {
+            Operation::CreateTrigger(trigger) => {
+                assert!(trigger.for_each_row);
+                assert!(trigger.condition.is_some());
+                assert!(matches!(trigger.body.as_slice(), [Statement::Insert(_)]));
+            }
+            _ => panic!("Expected CreateTrigger"),
+        }
+
+        let reversed = op.reverse().expect("Should be reversible");
+        assert_eq!(reversed, Operation::drop_trigger("users_audit", "users"));
+        assert!(!reversed.is_reversible());
+    }
+
+    #[test]
+    fn test_trigger_body_parse_error() {
+        let result = CreateTriggerOp::new(
+            "t_bad",
+            "users",
+            TriggerTiming::Before,
+            TriggerEvent::Insert,
+        )
+        .then_sql("INSERT INTO");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sequence_operation() {
+        let op: Operation = CreateSequenceOp::new("order_numbers")
+            .start(1000)
+            .increment(10)
+            .cycle()
+            .into();
+        match &op {
+            Operation::CreateSequence(seq) => {
+                assert_eq!(seq.start, Some(1000));
+                assert_eq!(seq.increment, Some(10));
+                assert!(seq.cycle);
+                assert!(!seq.if_not_exists);
+            }
+            _ => panic!("Expected CreateSequence"),
+        }
+        assert_eq!(
+            op.reverse(),
+            Some(Operation::drop_sequence("order_numbers"))
+        );
+        assert!(!Operation::drop_sequence("order_numbers").is_reversible());
+    }
+
+    #[test]
+    fn test_partition_operation() {
+        let op = Operation::create_partition(
+            "events_2024",
+            "events",
+            PartitionBound::Range {
+                from: vec!["'2024-01-01'".to_string()],
+                to: vec!["'2025-01-01'".to_string()],
+            },
+        );
+        match &op {
+            Operation::CreatePartition(part) => {
+                assert_eq!(part.parent, "events");
+                assert!(!part.if_not_exists);
+            }
+            _ => panic!("Expected CreatePartition"),
+        }
+        assert_eq!(op.reverse(), Some(Operation::drop_table("events_2024")));
+    }
+
     #[test]
     fn test_table_constraint() {
         let pk = TableConstraint::PrimaryKey {
@@ -622,4 +1371,69 @@ mod tests {
             _ => panic!("Expected ForeignKey"),
         }
     }
+
+    #[test]
+    fn test_in_schema() {
+        let op = Operation::drop_column("invoices", "note").in_schema("billing");
+        match &op {
+            Operation::DropColumn(drop) => {
+                assert_eq!(drop.schema.as_deref(), Some("billing"));
+                assert_eq!(drop.table, "invoices");
+            }
+            _ => panic!("Expected DropColumn"),
+        }
+
+        let seq = Operation::drop_sequence("order_numbers").in_schema("billing");
+        match &seq {
+            Operation::DropSequence(drop) => {
+                assert_eq!(drop.schema.as_deref(), Some("billing"));
+            }
+            _ => panic!("Expected DropSequence"),
+        }
+
+        // Schema operations are left alone.
+        let op = Operation::drop_schema("billing");
+        assert_eq!(op.clone().in_schema("other"), op);
+    }
+
+    #[test]
+    fn test_reverse_keeps_schema() {
+        let op = Operation::rename_table("invoices", "bills").in_schema("billing");
+        assert_eq!(
+            op.reverse(),
+            Some(Operation::rename_table("bills", "invoices").in_schema("billing"))
+        );
+
+        let op = Operation::add_column("invoices", bigint("total").build()).in_schema("billing");
+        assert_eq!(
+            op.reverse(),
+            Some(Operation::drop_column("invoices", "total").in_schema("billing"))
+        );
+
+        let op: Operation = CreateSequenceOp::new("invoice_numbers")
+            .schema("billing")
+            .into();
+        assert_eq!(
+            op.reverse(),
+            Some(Operation::drop_sequence("invoice_numbers").in_schema("billing"))
+        );
+
+        let op = Operation::create_partition(
+            "invoices_2024",
+            "invoices",
+            PartitionBound::List(vec!["2024".into()]),
+        )
+        .in_schema("billing");
+        assert_eq!(
+            op.reverse(),
+            Some(Operation::drop_table("invoices_2024").in_schema("billing"))
+        );
+    }
+
+    #[test]
+    fn test_schema_operations() {
+        let op = Operation::create_schema("billing");
+        assert_eq!(op.reverse(), Some(Operation::drop_schema("billing")));
+        assert!(!Operation::drop_schema("billing").is_reversible());
+    }
 }
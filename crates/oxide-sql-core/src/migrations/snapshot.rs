@@ -3,29 +3,30 @@
 //! Dialect-resolved representations of database schemas used for
 //! comparison. Unlike [`ColumnSchema`](crate::schema::ColumnSchema)
 //! (which stores Rust type strings), snapshots store resolved
-//! [`DataType`](crate::ast::DataType) values.
+//! [`DataType`] values.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::ast::DataType;
+use crate::ast::{DataType, Expr};
+use crate::parser::Parser;
 use crate::schema::{RustTypeMapping, TableSchema};
 
-use super::column_builder::{DefaultValue, ForeignKeyAction};
-use super::operation::{IndexType, strip_option};
+use super::column_builder::{DefaultValue, ForeignKeyAction, GeneratedColumn};
+use super::operation::{IndexColumn, IndexType, TableOption, strip_option, table_options};
 
 /// A snapshot of a database index.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IndexSnapshot {
     /// Index name.
     pub name: String,
-    /// Columns covered by the index.
-    pub columns: Vec<String>,
+    /// Columns and expressions covered by the index.
+    pub columns: Vec<IndexColumn>,
     /// Whether this is a UNIQUE index.
     pub unique: bool,
     /// Index type (BTree, Hash, etc.).
     pub index_type: IndexType,
     /// Partial index condition (WHERE clause), if any.
-    pub condition: Option<String>,
+    pub condition: Option<Expr>,
 }
 
 /// A snapshot of a foreign key constraint.
@@ -62,11 +63,17 @@ pub struct ColumnSnapshot {
     pub autoincrement: bool,
     /// Default value, if any.
     pub default: Option<DefaultValue>,
+    /// Collation name, if any.
+    pub collation: Option<String>,
+    /// Generated column expression, if the column is computed.
+    pub generated: Option<GeneratedColumn>,
 }
 
 /// A snapshot of a single table's resolved schema.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableSnapshot {
+    /// Schema (namespace) of the table; `None` is the default schema.
+    pub schema: Option<String>,
     /// Table name.
     pub name: String,
     /// Columns in declaration order.
@@ -75,12 +82,19 @@ pub struct TableSnapshot {
     pub indexes: Vec<IndexSnapshot>,
     /// Foreign key constraints on this table.
     pub foreign_keys: Vec<ForeignKeySnapshot>,
+    /// Table options such as SQLite `STRICT` or `WITHOUT ROWID`.
+    pub options: Vec<TableOption>,
 }
 
 impl TableSnapshot {
     /// Builds a snapshot from a `#[derive(Table)]` struct, resolving
     /// Rust types to SQL `DataType` via the dialect's
     /// `RustTypeMapping`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index in `T::INDEXES` holds invalid SQL. Indexes
+    /// declared with `#[table(index(...))]` are checked at compile time.
     pub fn from_table_schema<T: TableSchema>(dialect: &impl RustTypeMapping) -> Self {
         let columns = T::SCHEMA
             .iter()
@@ -98,14 +112,69 @@ impl TableSnapshot {
                     unique: col.unique,
                     autoincrement: col.autoincrement,
                     default,
+                    collation: col.collation.map(str::to_string),
+                    generated: col.generated.map(|generated| GeneratedColumn {
+                        expression: generated.expr.to_string(),
+                        stored: generated.stored,
+                    }),
                 }
             })
             .collect();
+        let indexes = T::INDEXES
+            .iter()
+            .map(|index| IndexSnapshot {
+                name: index.name.to_string(),
+                columns: index
+                    .columns
+                    .iter()
+                    .map(|sql| IndexColumn::parse(sql).expect("valid index expression"))
+                    .collect(),
+                unique: index.unique,
+                index_type: IndexType::BTree,
+                condition: index.condition.map(|sql| {
+                    Parser::new(sql)
+                        .parse_expr()
+                        .expect("valid index condition")
+                }),
+            })
+            .collect();
+        let foreign_keys = T::SCHEMA
+            .iter()
+            .filter_map(|col| {
+                col.references.map(|target| ForeignKeySnapshot {
+                    name: None,
+                    columns: vec![col.name.to_string()],
+                    references_table: target.table.to_string(),
+                    references_columns: vec![target.column.to_string()],
+                    on_delete: None,
+                    on_update: None,
+                })
+            })
+            .collect();
         Self {
+            schema: None,
             name: T::NAME.to_string(),
             columns,
-            indexes: vec![],
-            foreign_keys: vec![],
+            indexes,
+            foreign_keys,
+            options: table_options::<T>(),
+        }
+    }
+
+    /// Moves the table into `schema`.
+    #[must_use]
+    pub fn in_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Returns the name qualified with the schema, e.g.
+    /// `billing.invoices`, or the bare name in the default schema.
+    #[must_use]
+    pub fn qualified_name(&self) -> String {
+        match &self.schema {
+            Some(schema) => format!("{schema}.{}", self.name),
+            None => self.name.clone(),
         }
     }
 
@@ -119,7 +188,8 @@ impl TableSnapshot {
 /// A snapshot of an entire database schema (multiple tables).
 #[derive(Debug, Clone, PartialEq)]
 pub struct SchemaSnapshot {
-    /// Tables keyed by name, sorted for deterministic iteration.
+    /// Tables keyed by [qualified name](TableSnapshot::qualified_name),
+    /// sorted for deterministic iteration.
     pub tables: BTreeMap<String, TableSnapshot>,
 }
 
@@ -134,7 +204,25 @@ impl SchemaSnapshot {
 
     /// Adds a table snapshot.
     pub fn add_table(&mut self, table: TableSnapshot) {
-        self.tables.insert(table.name.clone(), table);
+        self.tables.insert(table.qualified_name(), table);
+    }
+
+    /// Returns the non-default schemas that hold at least one table.
+    #[must_use]
+    pub fn schemas(&self) -> BTreeSet<&str> {
+        self.tables
+            .values()
+            .filter_map(|t| t.schema.as_deref())
+            .collect()
+    }
+
+    /// Keeps only the tables in the given schemas. The default schema
+    /// is kept when `include_default` is set.
+    pub fn retain_schemas(&mut self, schemas: &[&str], include_default: bool) {
+        self.tables.retain(|_, t| match t.schema.as_deref() {
+            Some(schema) => schemas.contains(&schema),
+            None => include_default,
+        });
     }
 
     /// Adds a table snapshot built from a `#[derive(Table)]` struct.
@@ -177,6 +265,9 @@ mod tests {
                 unique: false,
                 autoincrement: true,
                 default_expr: None,
+                collation: None,
+                references: None,
+                generated: None,
             },
             ColumnSchema {
                 name: "name",
@@ -186,6 +277,9 @@ mod tests {
                 unique: true,
                 autoincrement: false,
                 default_expr: None,
+                collation: None,
+                references: None,
+                generated: None,
             },
             ColumnSchema {
                 name: "score",
@@ -195,6 +289,9 @@ mod tests {
                 unique: false,
                 autoincrement: false,
                 default_expr: None,
+                collation: None,
+                references: None,
+                generated: None,
             },
             ColumnSchema {
                 name: "active",
@@ -204,6 +301,9 @@ mod tests {
                 unique: false,
                 autoincrement: false,
                 default_expr: Some("TRUE"),
+                collation: None,
+                references: None,
+                generated: None,
             },
         ];
     }
@@ -282,4 +382,26 @@ mod tests {
         assert_eq!(schema.tables.len(), 1);
         assert!(schema.tables.contains_key("test_items"));
     }
+
+    #[test]
+    fn schema_snapshot_keys_by_qualified_name() {
+        let dialect = SqliteDialect::new();
+        let table = TableSnapshot::from_table_schema::<TestTable>(&dialect);
+        let mut schema = SchemaSnapshot::new();
+        schema.add_table(table.clone());
+        schema.add_table(table.in_schema("archive"));
+
+        assert_eq!(schema.tables.len(), 2);
+        assert!(schema.tables.contains_key("archive.test_items"));
+        assert_eq!(
+            schema.schemas().into_iter().collect::<Vec<_>>(),
+            ["archive"]
+        );
+
+        schema.retain_schemas(&["archive"], false);
+        assert_eq!(
+            schema.tables.keys().collect::<Vec<_>>(),
+            ["archive.test_items"]
+        );
+    }
 }
@@ -7,7 +7,9 @@
 use std::marker::PhantomData;
 
 use super::column_builder::ColumnDefinition;
-use super::operation::{CreateTableOp, DropTableOp, TableConstraint};
+use super::operation::{
+    CreateTableOp, DropTableOp, PartitionBy, PartitionStrategy, TableConstraint, TableOption,
+};
 
 // =============================================================================
 // Typestate Markers
@@ -56,10 +58,13 @@ pub struct HasColumns;
 /// ```
 #[derive(Debug, Clone)]
 pub struct CreateTableBuilder<Name, Cols> {
+    schema: Option<String>,
     name: Option<String>,
     columns: Vec<ColumnDefinition>,
     constraints: Vec<TableConstraint>,
     if_not_exists: bool,
+    partition_by: Option<PartitionBy>,
+    options: Vec<TableOption>,
     _state: PhantomData<(Name, Cols)>,
 }
 
@@ -74,10 +79,13 @@ impl CreateTableBuilder<NoName, NoColumns> {
     #[must_use]
     pub fn new() -> Self {
         Self {
+            schema: None,
             name: None,
             columns: Vec::new(),
             constraints: Vec::new(),
             if_not_exists: false,
+            partition_by: None,
+            options: Vec::new(),
             _state: PhantomData,
         }
     }
@@ -88,10 +96,13 @@ impl<Cols> CreateTableBuilder<NoName, Cols> {
     #[must_use]
     pub fn name(self, name: impl Into<String>) -> CreateTableBuilder<HasName, Cols> {
         CreateTableBuilder {
+            schema: self.schema,
             name: Some(name.into()),
             columns: self.columns,
             constraints: self.constraints,
             if_not_exists: self.if_not_exists,
+            partition_by: self.partition_by,
+            options: self.options,
             _state: PhantomData,
         }
     }
@@ -102,10 +113,13 @@ impl<Name> CreateTableBuilder<Name, NoColumns> {
     #[must_use]
     pub fn column(self, column: ColumnDefinition) -> CreateTableBuilder<Name, HasColumns> {
         CreateTableBuilder {
+            schema: self.schema,
             name: self.name,
             columns: vec![column],
             constraints: self.constraints,
             if_not_exists: self.if_not_exists,
+            partition_by: self.partition_by,
+            options: self.options,
             _state: PhantomData,
         }
     }
@@ -121,12 +135,43 @@ impl<Name> CreateTableBuilder<Name, HasColumns> {
 }
 
 impl<Name, Cols> CreateTableBuilder<Name, Cols> {
+    /// Creates the table in `schema` instead of the default schema.
+    #[must_use]
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
     /// Uses IF NOT EXISTS clause.
     #[must_use]
     pub fn if_not_exists(mut self) -> Self {
         self.if_not_exists = true;
         self
     }
+
+    /// Makes this a partitioned table (`PARTITION BY ...`).
+    ///
+    /// Only rendered by dialects that support partitioning
+    /// (PostgreSQL); add partitions with [`Operation::create_partition`].
+    ///
+    /// [`Operation::create_partition`]: super::Operation::create_partition
+    #[must_use]
+    pub fn partition_by(mut self, strategy: PartitionStrategy, columns: &[&str]) -> Self {
+        self.partition_by = Some(PartitionBy {
+            strategy,
+            columns: columns.iter().map(|&s| s.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Adds a dialect-specific table option, such as SQLite
+    /// `WITHOUT ROWID` or PostgreSQL `TABLESPACE`. Dialects ignore
+    /// options they do not understand.
+    #[must_use]
+    pub fn option(mut self, option: TableOption) -> Self {
+        self.options.push(option);
+        self
+    }
 }
 
 impl<Cols> CreateTableBuilder<HasName, Cols> {
@@ -207,10 +252,13 @@ impl CreateTableBuilder<HasName, HasColumns> {
     #[must_use]
     pub fn build(self) -> CreateTableOp {
         CreateTableOp {
+            schema: self.schema,
             name: self.name.expect("Name was set"),
             columns: self.columns,
             constraints: self.constraints,
             if_not_exists: self.if_not_exists,
+            partition_by: self.partition_by,
+            options: self.options,
         }
     }
 }
@@ -222,6 +270,7 @@ impl CreateTableBuilder<HasName, HasColumns> {
 /// Builder for DROP TABLE operations.
 #[derive(Debug, Clone, Default)]
 pub struct DropTableBuilder {
+    schema: Option<String>,
     name: Option<String>,
     if_exists: bool,
     cascade: bool,
@@ -248,6 +297,13 @@ impl DropTableBuilder {
         self
     }
 
+    /// Sets the schema of the table.
+    #[must_use]
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
     /// Uses CASCADE (PostgreSQL).
     #[must_use]
     pub fn cascade(mut self) -> Self {
@@ -263,6 +319,7 @@ impl DropTableBuilder {
     #[must_use]
     pub fn build(self) -> DropTableOp {
         DropTableOp {
+            schema: self.schema,
             name: self.name.expect("Table name must be set"),
             if_exists: self.if_exists,
             cascade: self.cascade,
@@ -274,19 +331,22 @@ impl DropTableBuilder {
 // IndexBuilder
 // =============================================================================
 
-use super::operation::{CreateIndexOp, IndexType};
+use super::operation::{CreateIndexOp, IndexColumn, IndexType};
+use crate::ast::Expr;
+use crate::parser::{ParseError, Parser};
 
 /// Builder for CREATE INDEX operations.
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
 pub struct CreateIndexBuilder {
+    schema: Option<String>,
     name: Option<String>,
     table: Option<String>,
-    columns: Vec<String>,
+    columns: Vec<IndexColumn>,
     unique: bool,
     index_type: IndexType,
     if_not_exists: bool,
-    condition: Option<String>,
+    condition: Option<Expr>,
 }
 
 #[allow(dead_code)]
@@ -311,20 +371,45 @@ impl CreateIndexBuilder {
         self
     }
 
+    /// Sets the schema of the table and index.
+    #[must_use]
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
     /// Adds a column to the index.
     #[must_use]
     pub fn column(mut self, column: impl Into<String>) -> Self {
-        self.columns.push(column.into());
+        self.columns.push(IndexColumn::Column(column.into()));
         self
     }
 
     /// Adds multiple columns to the index.
     #[must_use]
     pub fn columns(mut self, columns: &[&str]) -> Self {
-        self.columns.extend(columns.iter().map(|&s| s.to_string()));
+        self.columns
+            .extend(columns.iter().map(|&s| IndexColumn::from(s)));
+        self
+    }
+
+    /// Adds an expression to the index, e.g. `lower(email)`.
+    #[must_use]
+    pub fn expr(mut self, expr: Expr) -> Self {
+        self.columns.push(IndexColumn::Expr(expr));
         self
     }
 
+    /// Parses an index expression from SQL and adds it to the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if `sql` is not a valid expression.
+    pub fn expr_sql(mut self, sql: &str) -> Result<Self, ParseError> {
+        self.columns.push(IndexColumn::parse(sql)?);
+        Ok(self)
+    }
+
     /// Makes this a unique index.
     #[must_use]
     pub fn unique(mut self) -> Self {
@@ -348,11 +433,21 @@ impl CreateIndexBuilder {
 
     /// Adds a partial index condition (WHERE clause).
     #[must_use]
-    pub fn where_clause(mut self, condition: impl Into<String>) -> Self {
-        self.condition = Some(condition.into());
+    pub fn where_clause(mut self, condition: Expr) -> Self {
+        self.condition = Some(condition);
         self
     }
 
+    /// Parses a partial index condition (WHERE clause) from SQL.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if `sql` is not a valid expression.
+    pub fn where_sql(mut self, sql: &str) -> Result<Self, ParseError> {
+        self.condition = Some(Parser::new(sql).parse_expr()?);
+        Ok(self)
+    }
+
     /// Builds the `CreateIndexOp`.
     ///
     /// # Panics
@@ -361,6 +456,7 @@ impl CreateIndexBuilder {
     #[must_use]
     pub fn build(self) -> CreateIndexOp {
         CreateIndexOp {
+            schema: self.schema,
             name: self.name.expect("Index name must be set"),
             table: self.table.expect("Table name must be set"),
             columns: self.columns,
@@ -414,6 +510,24 @@ mod tests {
         assert!(op.if_not_exists);
     }
 
+    #[test]
+    fn test_create_table_partition_by() {
+        let op = CreateTableBuilder::new()
+            .partition_by(PartitionStrategy::Range, &["created_at"])
+            .name("events")
+            .column(bigint("id").not_null().build())
+            .column(timestamp("created_at").not_null().build())
+            .build();
+
+        assert_eq!(
+            op.partition_by,
+            Some(PartitionBy {
+                strategy: PartitionStrategy::Range,
+                columns: vec!["created_at".to_string()],
+            })
+        );
+    }
+
     #[test]
     fn test_create_table_with_constraints() {
         let op = CreateTableBuilder::new()
@@ -500,10 +614,40 @@ mod tests {
             .name("idx_active_users")
             .on_table("users")
             .column("email")
-            .where_clause("active = true")
+            .where_clause(Expr::column("active").eq(Expr::boolean(true)))
+            .build();
+
+        assert_eq!(
+            op.condition,
+            Some(Expr::column("active").eq(Expr::boolean(true)))
+        );
+    }
+
+    #[test]
+    fn test_expression_index() {
+        let op = CreateIndexBuilder::new()
+            .name("idx_users_lower_email")
+            .on_table("users")
+            .expr_sql("lower(email)")
+            .unwrap()
+            .where_sql("deleted_at IS NULL")
+            .unwrap()
+            .unique()
             .build();
 
-        assert_eq!(op.condition, Some("active = true".to_string()));
+        assert!(matches!(
+            op.columns.as_slice(),
+            [IndexColumn::Expr(Expr::Function(f))] if f.name == "lower"
+        ));
+        assert_eq!(
+            op.condition.map(|c| c.to_string()),
+            Some("deleted_at IS NULL".to_string())
+        );
+        assert!(
+            CreateIndexBuilder::new()
+                .where_sql("deleted_at IS")
+                .is_err()
+        );
     }
 
     #[test]
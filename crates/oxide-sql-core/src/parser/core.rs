@@ -1,15 +1,18 @@
 //! SQL Parser implementation.
 
 use super::error::ParseError;
+use super::pool::NodePool;
 use super::pratt::{
     infix_binding_power, prefix_binding_power, token_to_binary_op, token_to_unary_op,
 };
 use crate::ast::{
-    DataType, DeleteStatement, Expr, FunctionCall, InsertSource, InsertStatement, JoinClause,
-    JoinType, Literal, OrderBy, OrderDirection, SelectColumn, SelectStatement, Statement, TableRef,
-    UpdateAssignment, UpdateStatement,
+    BinaryOp, ConflictAction, DataType, DeleteStatement, Expr, FunctionCall, GroupByItem, Ident,
+    InsertSource, InsertStatement, JoinClause, JoinType, Literal, MergeAction, MergeClause,
+    MergeStatement, NullOrdering, OnConflict, OrderBy, OrderDirection, Quantifier, QuoteStyle,
+    SelectColumn, SelectStatement, Statement, TableRef, UnaryOp, UpdateAssignment, UpdateStatement,
 };
-use crate::lexer::{Keyword, Lexer, Span, Token, TokenKind};
+use crate::dialect::Dialect;
+use crate::lexer::{Keyword, Lexer, LexerOptions, RawToken, RawTokenKind, Span, Token, TokenKind};
 
 /// SQL Parser.
 pub struct Parser<'a> {
@@ -18,29 +21,95 @@ pub struct Parser<'a> {
     previous: Token,
     /// Parameter counter for ? placeholders.
     param_counter: usize,
+    /// Whether `ARRAY[...]` and `type[]` are parsed.
+    arrays: bool,
+    /// Expression nodes to reuse before allocating new ones.
+    pool: NodePool,
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new parser for the given input.
     #[must_use]
     pub fn new(input: &'a str) -> Self {
-        let mut lexer = Lexer::new(input);
+        Self::with_options(input, LexerOptions::new())
+    }
+
+    /// Creates a new parser for SQL in `dialect`, using
+    /// [`Dialect::lexer_options`] and [`Dialect::supports_arrays`].
+    #[must_use]
+    pub fn for_dialect<D: Dialect + ?Sized>(input: &'a str, dialect: &D) -> Self {
+        Self::with_options(input, dialect.lexer_options()).arrays(dialect.supports_arrays())
+    }
+
+    /// Sets whether `ARRAY[1, 2]` constructors and `INTEGER[]` types are
+    /// parsed, as in PostgreSQL. Off by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::Parser;
+    ///
+    /// let sql = "SELECT * FROM t WHERE id = ANY(ARRAY[1, 2])";
+    /// assert!(Parser::new(sql).parse_statement().is_err());
+    /// let stmt = Parser::new(sql).arrays(true).parse_statement().unwrap();
+    /// assert_eq!(stmt.to_string(), sql);
+    /// ```
+    #[must_use]
+    pub const fn arrays(mut self, enabled: bool) -> Self {
+        self.arrays = enabled;
+        self
+    }
+
+    /// Creates a new parser whose lexer uses the given options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::lexer::{IdentifierCase, LexerOptions};
+    /// use oxide_sql_core::Parser;
+    ///
+    /// let options = LexerOptions::new().identifier_case(IdentifierCase::Lower);
+    /// let stmt = Parser::with_options("SELECT UserName FROM Users", options)
+    ///     .parse_statement()
+    ///     .unwrap();
+    /// assert_eq!(stmt.to_string(), "SELECT username FROM users");
+    /// ```
+    #[must_use]
+    pub fn with_options(input: &'a str, options: LexerOptions) -> Self {
+        let mut lexer = Lexer::with_options(input, options);
         let current = lexer.next_token();
         Self {
             lexer,
             current,
             previous: Token::new(TokenKind::Eof, Span::new(0, 0)),
             param_counter: 0,
+            arrays: false,
+            pool: NodePool::new(),
         }
     }
 
+    /// Takes expression nodes from `pool` before allocating new ones.
+    /// See [`NodePool`].
+    #[must_use]
+    pub fn with_pool(mut self, pool: NodePool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Returns the pool given to [`Parser::with_pool`], minus the nodes
+    /// the parser has used.
+    #[must_use]
+    pub fn into_pool(self) -> NodePool {
+        self.pool
+    }
+
     /// Parses a single SQL statement.
     ///
     /// # Errors
     ///
     /// Returns a `ParseError` if the input is not a valid SQL statement.
     pub fn parse_statement(&mut self) -> Result<Statement, ParseError> {
-        match &self.current.kind {
+        let statement = match &self.current.kind {
             TokenKind::Keyword(Keyword::Select) => {
                 Ok(Statement::Select(self.parse_select_statement()?))
             }
@@ -53,11 +122,59 @@ impl<'a> Parser<'a> {
             TokenKind::Keyword(Keyword::Delete) => {
                 Ok(Statement::Delete(self.parse_delete_statement()?))
             }
+            // MERGE is not a keyword, so tables and columns may use the name.
+            _ if self.check_word("MERGE") && self.next_is_keyword(Keyword::Into) => {
+                Ok(Statement::Merge(self.parse_merge_statement()?))
+            }
             _ => Err(ParseError::unexpected(
-                "SELECT, INSERT, UPDATE, or DELETE",
+                "SELECT, INSERT, UPDATE, DELETE, or MERGE",
                 self.current.kind.clone(),
                 self.current.span,
             )),
+        }?;
+
+        // A single trailing semicolon is allowed; anything else after the
+        // statement is an error rather than silently ignored.
+        if self.check(&TokenKind::Semicolon) {
+            self.advance();
+        }
+        self.expect_end()?;
+
+        Ok(statement)
+    }
+
+    /// Parses a single standalone expression, such as an index
+    /// expression or a `WHERE` predicate.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if the input is not a valid expression or
+    /// anything follows it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxide_sql_core::Parser;
+    ///
+    /// let expr = Parser::new("lower(email)").parse_expr().unwrap();
+    /// assert_eq!(expr.to_string(), "lower(email)");
+    /// ```
+    pub fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.parse_expression(0)?;
+        self.expect_end()?;
+        Ok(expr)
+    }
+
+    /// Fails unless all input has been consumed.
+    fn expect_end(&self) -> Result<(), ParseError> {
+        if self.check(&TokenKind::Eof) {
+            Ok(())
+        } else {
+            Err(ParseError::unexpected(
+                "end of input",
+                self.current.kind.clone(),
+                self.current.span,
+            ))
         }
     }
 
@@ -99,7 +216,7 @@ impl<'a> Parser<'a> {
         let group_by = if self.check_keyword(Keyword::Group) {
             self.advance();
             self.expect_keyword(Keyword::By)?;
-            self.parse_expression_list()?
+            self.parse_group_by_list()?
         } else {
             vec![]
         };
@@ -189,7 +306,7 @@ impl<'a> Parser<'a> {
                 let alias = self.parse_optional_alias()?;
                 TableRef::Subquery {
                     query: Box::new(query),
-                    alias: alias.unwrap_or_else(|| String::from("subquery")),
+                    alias: alias.unwrap_or_else(|| Ident::new("subquery")),
                 }
             } else {
                 let inner = self.parse_table_ref()?;
@@ -341,7 +458,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Parses an optional table alias.
-    fn parse_optional_alias(&mut self) -> Result<Option<String>, ParseError> {
+    fn parse_optional_alias(&mut self) -> Result<Option<Ident>, ParseError> {
         if self.check_keyword(Keyword::As) {
             self.advance();
             Ok(Some(self.expect_identifier()?))
@@ -432,15 +549,44 @@ impl<'a> Parser<'a> {
             ));
         };
 
+        // ON CONFLICT (optional)
+        let on_conflict = if self.check_keyword(Keyword::On) {
+            self.advance();
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
+
         Ok(InsertStatement {
             schema,
             table,
             columns,
             values,
-            on_conflict: None,
+            on_conflict,
         })
     }
 
+    /// Parses `CONFLICT (cols) DO NOTHING | DO UPDATE SET ...` after `ON`.
+    fn parse_on_conflict(&mut self) -> Result<OnConflict, ParseError> {
+        self.expect_keyword(Keyword::Conflict)?;
+        self.expect(&TokenKind::LeftParen)?;
+        let columns = self.parse_identifier_list()?;
+        self.expect(&TokenKind::RightParen)?;
+
+        // DO and NOTHING are not reserved words, so they lex as identifiers.
+        self.expect_word("DO")?;
+        let action = if self.check_keyword(Keyword::Update) {
+            self.advance();
+            self.expect_keyword(Keyword::Set)?;
+            ConflictAction::DoUpdate(self.parse_assignments()?)
+        } else {
+            self.expect_word("NOTHING")?;
+            ConflictAction::DoNothing
+        };
+
+        Ok(OnConflict { columns, action })
+    }
+
     /// Parses an UPDATE statement.
     fn parse_update_statement(&mut self) -> Result<UpdateStatement, ParseError> {
         self.expect_keyword(Keyword::Update)?;
@@ -459,18 +605,7 @@ impl<'a> Parser<'a> {
         self.expect_keyword(Keyword::Set)?;
 
         // Parse SET assignments
-        let mut assignments = vec![];
-        loop {
-            let column = self.expect_identifier()?;
-            self.expect(&TokenKind::Eq)?;
-            let value = self.parse_expression(0)?;
-            assignments.push(UpdateAssignment { column, value });
-
-            if !self.check(&TokenKind::Comma) {
-                break;
-            }
-            self.advance();
-        }
+        let assignments = self.parse_assignments()?;
 
         // FROM clause (optional, for joins)
         let from = if self.check_keyword(Keyword::From) {
@@ -498,6 +633,23 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses a comma-separated list of `column = expr` assignments.
+    fn parse_assignments(&mut self) -> Result<Vec<UpdateAssignment>, ParseError> {
+        let mut assignments = vec![];
+        loop {
+            let column = self.expect_identifier()?;
+            self.expect(&TokenKind::Eq)?;
+            let value = self.parse_expression(0)?;
+            assignments.push(UpdateAssignment { column, value });
+
+            if !self.check(&TokenKind::Comma) {
+                break;
+            }
+            self.advance();
+        }
+        Ok(assignments)
+    }
+
     /// Parses a DELETE statement.
     fn parse_delete_statement(&mut self) -> Result<DeleteStatement, ParseError> {
         self.expect_keyword(Keyword::Delete)?;
@@ -530,6 +682,165 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses a MERGE statement.
+    fn parse_merge_statement(&mut self) -> Result<MergeStatement, ParseError> {
+        self.expect_word("MERGE")?;
+        self.expect_keyword(Keyword::Into)?;
+
+        let first = self.expect_identifier()?;
+        let (schema, table) = if self.check(&TokenKind::Dot) {
+            self.advance();
+            let table_name = self.expect_identifier()?;
+            (Some(first), table_name)
+        } else {
+            (None, first)
+        };
+
+        let alias = self.parse_optional_alias()?;
+
+        self.expect_keyword(Keyword::Using)?;
+        let source = self.parse_table_ref()?;
+        self.expect_keyword(Keyword::On)?;
+        let on = self.parse_expression(0)?;
+
+        let mut clauses = vec![self.parse_merge_clause()?];
+        while self.check_keyword(Keyword::When) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+
+        Ok(MergeStatement {
+            schema,
+            table,
+            alias,
+            source,
+            on,
+            clauses,
+        })
+    }
+
+    /// Parses a `WHEN [NOT] MATCHED [AND condition] THEN action` clause.
+    fn parse_merge_clause(&mut self) -> Result<MergeClause, ParseError> {
+        self.expect_keyword(Keyword::When)?;
+        let matched = if self.check_keyword(Keyword::Not) {
+            self.advance();
+            false
+        } else {
+            true
+        };
+        // MATCHED, DO, and NOTHING are not reserved words.
+        self.expect_word("MATCHED")?;
+
+        let condition = if self.check_keyword(Keyword::And) {
+            self.advance();
+            Some(self.parse_expression(0)?)
+        } else {
+            None
+        };
+        self.expect_keyword(Keyword::Then)?;
+
+        let action = if self.check_word("DO") {
+            self.advance();
+            self.expect_word("NOTHING")?;
+            MergeAction::DoNothing
+        } else if matched && self.check_keyword(Keyword::Update) {
+            self.advance();
+            self.expect_keyword(Keyword::Set)?;
+            MergeAction::Update(self.parse_assignments()?)
+        } else if matched && self.check_keyword(Keyword::Delete) {
+            self.advance();
+            MergeAction::Delete
+        } else if !matched && self.check_keyword(Keyword::Insert) {
+            self.advance();
+            let columns = if self.check(&TokenKind::LeftParen) {
+                self.advance();
+                let cols = self.parse_identifier_list()?;
+                self.expect(&TokenKind::RightParen)?;
+                cols
+            } else {
+                vec![]
+            };
+            self.expect_keyword(Keyword::Values)?;
+            self.expect(&TokenKind::LeftParen)?;
+            let values = self.parse_expression_list()?;
+            self.expect(&TokenKind::RightParen)?;
+            MergeAction::Insert { columns, values }
+        } else {
+            return Err(ParseError::unexpected(
+                if matched {
+                    "UPDATE, DELETE, or DO NOTHING"
+                } else {
+                    "INSERT or DO NOTHING"
+                },
+                self.current.kind.clone(),
+                self.current.span,
+            ));
+        };
+
+        Ok(MergeClause {
+            matched,
+            condition,
+            action,
+        })
+    }
+
+    /// Parses a GROUP BY list.
+    fn parse_group_by_list(&mut self) -> Result<Vec<GroupByItem>, ParseError> {
+        let mut items = vec![];
+        loop {
+            // ROLLUP, CUBE, and GROUPING are not keywords, so they only
+            // start a grouping construct when followed by its syntax.
+            let item = if self.check_word("ROLLUP") && self.next_is(&TokenKind::LeftParen) {
+                self.advance();
+                GroupByItem::Rollup(self.parse_grouping_list()?)
+            } else if self.check_word("CUBE") && self.next_is(&TokenKind::LeftParen) {
+                self.advance();
+                GroupByItem::Cube(self.parse_grouping_list()?)
+            } else if self.check_word("GROUPING") && self.next_is_word("SETS") {
+                self.advance();
+                self.advance();
+                GroupByItem::GroupingSets(self.parse_grouping_list()?)
+            } else {
+                GroupByItem::Expr(self.parse_expression(0)?)
+            };
+            items.push(item);
+
+            if !self.check(&TokenKind::Comma) {
+                break;
+            }
+            self.advance();
+        }
+        Ok(items)
+    }
+
+    /// Parses the parenthesized groups of ROLLUP, CUBE, or GROUPING SETS,
+    /// where each group is an expression or a parenthesized list of them.
+    fn parse_grouping_list(&mut self) -> Result<Vec<Vec<Expr>>, ParseError> {
+        self.expect(&TokenKind::LeftParen)?;
+        let mut groups = vec![];
+        loop {
+            let group = if self.check(&TokenKind::LeftParen) {
+                self.advance();
+                let group = if self.check(&TokenKind::RightParen) {
+                    vec![]
+                } else {
+                    self.parse_expression_list()?
+                };
+                self.expect(&TokenKind::RightParen)?;
+                group
+            } else {
+                vec![self.parse_expression(0)?]
+            };
+            groups.push(group);
+
+            if !self.check(&TokenKind::Comma) {
+                break;
+            }
+            self.advance();
+        }
+        self.expect(&TokenKind::RightParen)?;
+        Ok(groups)
+    }
+
     /// Parses an ORDER BY list.
     fn parse_order_by_list(&mut self) -> Result<Vec<OrderBy>, ParseError> {
         let mut items = vec![];
@@ -545,10 +856,23 @@ impl<'a> Parser<'a> {
                 OrderDirection::Asc
             };
 
+            let nulls = if self.check_keyword(Keyword::Nulls) {
+                self.advance();
+                if self.check_keyword(Keyword::First) {
+                    self.advance();
+                    Some(NullOrdering::First)
+                } else {
+                    self.expect_keyword(Keyword::Last)?;
+                    Some(NullOrdering::Last)
+                }
+            } else {
+                None
+            };
+
             items.push(OrderBy {
                 expr,
                 direction,
-                nulls: None,
+                nulls,
             });
 
             if !self.check(&TokenKind::Comma) {
@@ -589,42 +913,69 @@ impl<'a> Parser<'a> {
                     };
                     self.expect_keyword(Keyword::Null)?;
                     lhs = Expr::IsNull {
-                        expr: Box::new(lhs),
+                        expr: self.pool.boxed(lhs),
                         negated,
                     };
                 }
                 TokenKind::Keyword(Keyword::In) => {
+                    lhs = self.parse_in(lhs, false)?;
+                }
+                TokenKind::Keyword(Keyword::Collate) => {
                     self.advance();
-                    self.expect(&TokenKind::LeftParen)?;
-                    let list = self.parse_expression_list()?;
-                    self.expect(&TokenKind::RightParen)?;
-                    lhs = Expr::In {
-                        expr: Box::new(lhs),
-                        list,
-                        negated: false,
+                    let collation = self.expect_identifier()?;
+                    lhs = Expr::Collate {
+                        expr: self.pool.boxed(lhs),
+                        collation,
                     };
                 }
                 TokenKind::Keyword(Keyword::Between) => {
+                    lhs = self.parse_between(lhs, r_bp, false)?;
+                }
+                // NOT IN, NOT BETWEEN, NOT LIKE
+                TokenKind::Keyword(Keyword::Not) => {
                     self.advance();
-                    let low = self.parse_expression(r_bp)?;
-                    self.expect_keyword(Keyword::And)?;
-                    let high = self.parse_expression(r_bp)?;
-                    lhs = Expr::Between {
-                        expr: Box::new(lhs),
-                        low: Box::new(low),
-                        high: Box::new(high),
-                        negated: false,
+                    lhs = match &self.current.kind {
+                        TokenKind::Keyword(Keyword::In) => self.parse_in(lhs, true)?,
+                        TokenKind::Keyword(Keyword::Between) => {
+                            self.parse_between(lhs, r_bp, true)?
+                        }
+                        TokenKind::Keyword(Keyword::Like) => {
+                            self.advance();
+                            let rhs = self.parse_expression(r_bp)?;
+                            let like = Expr::Binary {
+                                left: self.pool.boxed(lhs),
+                                op: BinaryOp::Like,
+                                right: self.pool.boxed(rhs),
+                            };
+                            Expr::Unary {
+                                op: UnaryOp::Not,
+                                operand: self.pool.boxed(like),
+                            }
+                        }
+                        _ => {
+                            return Err(ParseError::unexpected(
+                                "IN, BETWEEN, or LIKE",
+                                self.current.kind.clone(),
+                                self.current.span,
+                            ));
+                        }
                     };
                 }
                 _ => {
                     // Standard binary operator
                     if let Some(op) = token_to_binary_op(&self.current.kind) {
                         self.advance();
+                        if op.is_comparison()
+                            && let Some(quantifier) = self.quantifier()
+                        {
+                            lhs = self.parse_quantified(lhs, op, quantifier)?;
+                            continue;
+                        }
                         let rhs = self.parse_expression(r_bp)?;
                         lhs = Expr::Binary {
-                            left: Box::new(lhs),
+                            left: self.pool.boxed(lhs),
                             op,
-                            right: Box::new(rhs),
+                            right: self.pool.boxed(rhs),
                         };
                     } else {
                         break;
@@ -636,6 +987,75 @@ impl<'a> Parser<'a> {
         Ok(lhs)
     }
 
+    /// Parses the `IN (...)` list following `expr`.
+    fn parse_in(&mut self, expr: Expr, negated: bool) -> Result<Expr, ParseError> {
+        self.expect_keyword(Keyword::In)?;
+        self.expect(&TokenKind::LeftParen)?;
+        let list = self.parse_expression_list()?;
+        self.expect(&TokenKind::RightParen)?;
+        Ok(Expr::In {
+            expr: self.pool.boxed(expr),
+            list,
+            negated,
+        })
+    }
+
+    /// Returns the quantifier starting the current token, if it is `ALL`,
+    /// `ANY`, or `SOME` followed by `(`.
+    ///
+    /// `ANY` and `SOME` are not keywords, so `x = any` still compares
+    /// against a column.
+    fn quantifier(&self) -> Option<Quantifier> {
+        let quantifier = match &self.current.kind {
+            TokenKind::Keyword(Keyword::All) => Quantifier::All,
+            TokenKind::Identifier(name)
+                if self.quote_style().is_none()
+                    && (name.eq_ignore_ascii_case("ANY") || name.eq_ignore_ascii_case("SOME")) =>
+            {
+                Quantifier::Any
+            }
+            _ => return None,
+        };
+        self.next_is(&TokenKind::LeftParen).then_some(quantifier)
+    }
+
+    /// Parses the `ANY (...)` or `ALL (...)` following `left op`.
+    fn parse_quantified(
+        &mut self,
+        left: Expr,
+        op: BinaryOp,
+        quantifier: Quantifier,
+    ) -> Result<Expr, ParseError> {
+        self.advance(); // ANY, SOME, or ALL
+        self.expect(&TokenKind::LeftParen)?;
+        let right = if self.check_keyword(Keyword::Select) {
+            Expr::Subquery(Box::new(self.parse_select_statement()?))
+        } else {
+            self.parse_expression(0)?
+        };
+        self.expect(&TokenKind::RightParen)?;
+        Ok(Expr::Quantified {
+            left: self.pool.boxed(left),
+            op,
+            quantifier,
+            right: self.pool.boxed(right),
+        })
+    }
+
+    /// Parses the `BETWEEN low AND high` following `expr`.
+    fn parse_between(&mut self, expr: Expr, bp: u8, negated: bool) -> Result<Expr, ParseError> {
+        self.expect_keyword(Keyword::Between)?;
+        let low = self.parse_expression(bp)?;
+        self.expect_keyword(Keyword::And)?;
+        let high = self.parse_expression(bp)?;
+        Ok(Expr::Between {
+            expr: self.pool.boxed(expr),
+            low: self.pool.boxed(low),
+            high: self.pool.boxed(high),
+            negated,
+        })
+    }
+
     /// Parses a prefix expression.
     fn parse_prefix(&mut self) -> Result<Expr, ParseError> {
         // Check for unary operators
@@ -645,7 +1065,7 @@ impl<'a> Parser<'a> {
             let operand = self.parse_expression(bp)?;
             return Ok(Expr::Unary {
                 op,
-                operand: Box::new(operand),
+                operand: self.pool.boxed(operand),
             });
         }
 
@@ -654,25 +1074,34 @@ impl<'a> Parser<'a> {
 
     /// Parses a primary expression.
     fn parse_primary(&mut self) -> Result<Expr, ParseError> {
-        let token = self.current.clone();
+        let span = self.current.span;
 
-        match &token.kind {
+        // Owned token data (strings, blobs, names) is moved out of the
+        // current token rather than cloned; `advance()` discards it anyway.
+        match &mut self.current.kind {
             // Literals
             TokenKind::Integer(n) => {
+                let value = *n;
                 self.advance();
-                Ok(Expr::Literal(Literal::Integer(*n)))
+                Ok(Expr::Literal(Literal::Integer(value)))
             }
             TokenKind::Float(f) => {
+                let value = *f;
+                self.advance();
+                Ok(Expr::Literal(Literal::Float(value)))
+            }
+            TokenKind::BigNumber(digits) => {
+                let value = core::mem::take(digits);
                 self.advance();
-                Ok(Expr::Literal(Literal::Float(*f)))
+                Ok(Expr::Literal(Literal::BigNumber(value)))
             }
             TokenKind::String(s) => {
-                let value = s.clone();
+                let value = core::mem::take(s);
                 self.advance();
                 Ok(Expr::Literal(Literal::String(value)))
             }
             TokenKind::Blob(b) => {
-                let value = b.clone();
+                let value = core::mem::take(b);
                 self.advance();
                 Ok(Expr::Literal(Literal::Blob(value)))
             }
@@ -688,6 +1117,16 @@ impl<'a> Parser<'a> {
                 self.advance();
                 Ok(Expr::Literal(Literal::Null))
             }
+            TokenKind::Keyword(kw @ (Keyword::Date | Keyword::Time | Keyword::Timestamp)) => {
+                let kw = *kw;
+                self.advance();
+                let value = self.expect_string()?;
+                Ok(Expr::Literal(match kw {
+                    Keyword::Date => Literal::Date(value),
+                    Keyword::Time => Literal::Time(value),
+                    _ => Literal::Timestamp(value),
+                }))
+            }
 
             // Parameter placeholders
             TokenKind::Question => {
@@ -703,7 +1142,7 @@ impl<'a> Parser<'a> {
                 self.advance();
                 let name = self.expect_identifier()?;
                 Ok(Expr::Parameter {
-                    name: Some(name),
+                    name: Some(name.value),
                     position: 0,
                 })
             }
@@ -724,7 +1163,7 @@ impl<'a> Parser<'a> {
                 } else {
                     let expr = self.parse_expression(0)?;
                     self.expect(&TokenKind::RightParen)?;
-                    Ok(Expr::Paren(Box::new(expr)))
+                    Ok(Expr::Paren(self.pool.boxed(expr)))
                 }
             }
 
@@ -740,8 +1179,9 @@ impl<'a> Parser<'a> {
             // Other functions
             TokenKind::Keyword(kw @ (Keyword::Coalesce | Keyword::Nullif | Keyword::Cast)) => {
                 let name = kw.as_str().to_string();
+                let is_cast = matches!(kw, Keyword::Cast);
                 self.advance();
-                if matches!(kw, Keyword::Cast) {
+                if is_cast {
                     self.parse_cast_expression()
                 } else {
                     self.parse_function_call(name)
@@ -757,23 +1197,48 @@ impl<'a> Parser<'a> {
                 self.expect(&TokenKind::LeftParen)?;
                 let subquery = self.parse_select_statement()?;
                 self.expect(&TokenKind::RightParen)?;
-                Ok(Expr::Function(FunctionCall {
-                    name: String::from("EXISTS"),
-                    args: vec![Expr::Subquery(Box::new(subquery))],
-                    distinct: false,
-                }))
+                Ok(Expr::Exists(Box::new(subquery)))
             }
 
             // Identifier (column reference or function call)
             TokenKind::Identifier(name) => {
-                let name = name.clone();
-                let span = token.span;
+                let name = core::mem::take(name);
+                let quote_style = self.quote_style();
                 self.advance();
 
+                // INTERVAL is not a keyword, so columns may still use the
+                // name; only a following string makes it a literal.
+                if quote_style.is_none()
+                    && name.eq_ignore_ascii_case("INTERVAL")
+                    && matches!(self.current.kind, TokenKind::String(_))
+                {
+                    return Ok(Expr::Literal(Literal::Interval(self.expect_string()?)));
+                }
+
+                // Likewise ARRAY, which starts a constructor only before `[`.
+                if self.arrays
+                    && quote_style.is_none()
+                    && name.eq_ignore_ascii_case("ARRAY")
+                    && self.check(&TokenKind::LeftBracket)
+                {
+                    self.advance();
+                    let elements = if self.check(&TokenKind::RightBracket) {
+                        vec![]
+                    } else {
+                        self.parse_expression_list()?
+                    };
+                    self.expect(&TokenKind::RightBracket)?;
+                    return Ok(Expr::Array(elements));
+                }
+
                 // Check for function call
                 if self.check(&TokenKind::LeftParen) {
                     return self.parse_function_call(name);
                 }
+                let name = Ident {
+                    value: name,
+                    quote_style,
+                };
 
                 // Check for qualified name (table.column or table.*)
                 if self.check(&TokenKind::Dot) {
@@ -827,10 +1292,38 @@ impl<'a> Parser<'a> {
 
         self.expect(&TokenKind::RightParen)?;
 
+        // WITHIN and FILTER are not keywords, so they are only recognized
+        // in front of the clause they introduce.
+        let within_group = if self.check_word("WITHIN") && self.next_is_keyword(Keyword::Group) {
+            self.advance();
+            self.advance();
+            self.expect(&TokenKind::LeftParen)?;
+            self.expect_keyword(Keyword::Order)?;
+            self.expect_keyword(Keyword::By)?;
+            let order_by = self.parse_order_by_list()?;
+            self.expect(&TokenKind::RightParen)?;
+            order_by
+        } else {
+            vec![]
+        };
+
+        let filter = if self.check_word("FILTER") && self.next_is(&TokenKind::LeftParen) {
+            self.advance();
+            self.advance();
+            self.expect_keyword(Keyword::Where)?;
+            let condition = self.parse_expression(0)?;
+            self.expect(&TokenKind::RightParen)?;
+            Some(self.pool.boxed(condition))
+        } else {
+            None
+        };
+
         Ok(Expr::Function(FunctionCall {
             name,
             args,
             distinct,
+            within_group,
+            filter,
         }))
     }
 
@@ -843,7 +1336,7 @@ impl<'a> Parser<'a> {
         self.expect(&TokenKind::RightParen)?;
 
         Ok(Expr::Cast {
-            expr: Box::new(expr),
+            expr: self.pool.boxed(expr),
             data_type,
         })
     }
@@ -854,7 +1347,8 @@ impl<'a> Parser<'a> {
 
         // Check for simple CASE (CASE expr WHEN ...)
         let operand = if !self.check_keyword(Keyword::When) {
-            Some(Box::new(self.parse_expression(0)?))
+            let operand = self.parse_expression(0)?;
+            Some(self.pool.boxed(operand))
         } else {
             None
         };
@@ -872,7 +1366,8 @@ impl<'a> Parser<'a> {
         // Parse ELSE clause
         let else_clause = if self.check_keyword(Keyword::Else) {
             self.advance();
-            Some(Box::new(self.parse_expression(0)?))
+            let else_expr = self.parse_expression(0)?;
+            Some(self.pool.boxed(else_expr))
         } else {
             None
         };
@@ -888,7 +1383,7 @@ impl<'a> Parser<'a> {
 
     /// Parses a data type.
     fn parse_data_type(&mut self) -> Result<DataType, ParseError> {
-        let data_type = match &self.current.kind {
+        let mut data_type = match &mut self.current.kind {
             TokenKind::Keyword(Keyword::Int | Keyword::Integer) => {
                 self.advance();
                 DataType::Integer
@@ -962,7 +1457,7 @@ impl<'a> Parser<'a> {
                 DataType::Datetime
             }
             TokenKind::Identifier(name) => {
-                let name = name.clone();
+                let name = core::mem::take(name);
                 self.advance();
                 DataType::Custom(name)
             }
@@ -975,6 +1470,12 @@ impl<'a> Parser<'a> {
             }
         };
 
+        while self.arrays && self.check(&TokenKind::LeftBracket) {
+            self.advance();
+            self.expect(&TokenKind::RightBracket)?;
+            data_type = DataType::Array(Box::new(data_type));
+        }
+
         Ok(data_type)
     }
 
@@ -1067,7 +1568,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Parses a comma-separated list of identifiers.
-    fn parse_identifier_list(&mut self) -> Result<Vec<String>, ParseError> {
+    fn parse_identifier_list(&mut self) -> Result<Vec<Ident>, ParseError> {
         let mut idents = vec![];
         loop {
             idents.push(self.expect_identifier()?);
@@ -1124,13 +1625,88 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Expects and returns an identifier.
-    fn expect_identifier(&mut self) -> Result<String, ParseError> {
+    /// Returns whether the current token is the unquoted non-reserved word
+    /// `word`.
+    fn check_word(&self, word: &str) -> bool {
+        matches!(&self.current.kind, TokenKind::Identifier(name) if name.eq_ignore_ascii_case(word))
+            && self.quote_style().is_none()
+    }
+
+    /// Expects a non-reserved word such as `DO`, which lexes as an
+    /// identifier.
+    fn expect_word(&mut self, word: &str) -> Result<(), ParseError> {
         match &self.current.kind {
+            TokenKind::Identifier(name) if name.eq_ignore_ascii_case(word) => {
+                self.advance();
+                Ok(())
+            }
+            _ => Err(ParseError::unexpected(
+                word,
+                self.current.kind.clone(),
+                self.current.span,
+            )),
+        }
+    }
+
+    /// Returns whether the token after the current one is the symbol `kind`.
+    fn next_is(&self, kind: &TokenKind) -> bool {
+        matches!(
+            self.lexer.clone().next_raw(),
+            Ok(RawToken { kind: RawTokenKind::Symbol(next), .. }) if next == *kind
+        )
+    }
+
+    /// Returns whether the token after the current one is the unquoted
+    /// word `word`.
+    fn next_is_word(&self, word: &str) -> bool {
+        matches!(
+            self.lexer.clone().next_raw(),
+            Ok(RawToken { kind: RawTokenKind::Identifier, text, .. }) if text.eq_ignore_ascii_case(word)
+        )
+    }
+
+    /// Returns whether the token after the current one is `keyword`.
+    fn next_is_keyword(&self, keyword: Keyword) -> bool {
+        matches!(
+            self.lexer.clone().next_raw(),
+            Ok(RawToken { kind: RawTokenKind::Keyword(next), .. }) if next == keyword
+        )
+    }
+
+    /// Returns how the current token was quoted in the source, judging by
+    /// its first character.
+    fn quote_style(&self) -> Option<QuoteStyle> {
+        let input = self.lexer.input();
+        input[self.current.span.start..]
+            .chars()
+            .next()
+            .and_then(QuoteStyle::from_open)
+    }
+
+    /// Expects and returns a string literal.
+    fn expect_string(&mut self) -> Result<String, ParseError> {
+        match &mut self.current.kind {
+            TokenKind::String(value) => {
+                let value = core::mem::take(value);
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(ParseError::unexpected(
+                "string literal",
+                self.current.kind.clone(),
+                self.current.span,
+            )),
+        }
+    }
+
+    /// Expects and returns an identifier.
+    fn expect_identifier(&mut self) -> Result<Ident, ParseError> {
+        let quote_style = self.quote_style();
+        match &mut self.current.kind {
             TokenKind::Identifier(name) => {
-                let name = name.clone();
+                let value = core::mem::take(name);
                 self.advance();
-                Ok(name)
+                Ok(Ident { value, quote_style })
             }
             _ => Err(ParseError::unexpected(
                 "identifier",
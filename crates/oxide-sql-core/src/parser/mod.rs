@@ -73,7 +73,9 @@
 
 mod core;
 mod error;
+mod pool;
 mod pratt;
 
 pub use core::Parser;
 pub use error::ParseError;
+pub use pool::NodePool;
@@ -0,0 +1,169 @@
+//! Reusable expression nodes for the parser.
+
+use crate::ast::visit::VisitExprs;
+use crate::ast::{Expr, Literal, Statement};
+
+/// A pool of expression nodes the parser reuses instead of allocating.
+///
+/// Every operator, `BETWEEN`, `CASE`, or parenthesized expression the
+/// parser reads puts a node in its own `Box<Expr>`, so parsing a large SQL
+/// file makes one small allocation per node. A parser given a pool with
+/// [`Parser::with_pool`] takes its nodes from the pool first, and
+/// [`NodePool::reclaim`] returns the nodes of a statement that is no
+/// longer needed. Parsing many statements one after another then
+/// allocates nodes only until the pool has grown to the size of the
+/// largest statement.
+///
+/// The pool only changes where nodes come from: a pooled parser returns
+/// the same statements as any other.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::Parser;
+/// use oxide_sql_core::parser::NodePool;
+///
+/// let mut pool = NodePool::new();
+/// for sql in ["SELECT a + 1 FROM t", "SELECT b * 2 FROM t"] {
+///     let mut parser = Parser::new(sql).with_pool(pool);
+///     let stmt = parser.parse_statement().unwrap();
+///     pool = parser.into_pool();
+///     assert_eq!(stmt.to_string(), sql);
+///     pool.reclaim(stmt);
+/// }
+/// // The two operands of `+`, reused for `*`
+/// assert_eq!(pool.len(), 2);
+/// ```
+///
+/// [`Parser::with_pool`]: super::Parser::with_pool
+#[derive(Debug, Default)]
+pub struct NodePool {
+    // The boxes are the point: each is an allocation the parser reuses.
+    #[allow(clippy::vec_box)]
+    nodes: Vec<Box<Expr>>,
+}
+
+impl NodePool {
+    /// Creates an empty pool.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Creates a pool holding `capacity` nodes, allocated up front.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut nodes = Vec::with_capacity(capacity);
+        nodes.resize_with(capacity, empty_node);
+        Self { nodes }
+    }
+
+    /// Returns the number of nodes available for reuse.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if no nodes are available for reuse.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the nodes of `statement` to the pool.
+    pub fn reclaim(&mut self, mut statement: Statement) {
+        statement.visit_exprs(&mut |expr| self.reclaim_expr(take(expr)));
+    }
+
+    /// Returns the nodes of `expr` to the pool.
+    pub fn reclaim_expr(&mut self, expr: Expr) {
+        match expr {
+            Expr::Literal(_)
+            | Expr::Column { .. }
+            | Expr::Parameter { .. }
+            | Expr::Wildcard { .. } => {}
+            Expr::Binary { left, right, .. } | Expr::Quantified { left, right, .. } => {
+                self.reclaim_node(left);
+                self.reclaim_node(right);
+            }
+            Expr::Unary { operand: expr, .. }
+            | Expr::IsNull { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::Paren(expr) => self.reclaim_node(expr),
+            Expr::Function(func) => {
+                self.reclaim_list(func.args);
+                for order in func.within_group {
+                    self.reclaim_expr(order.expr);
+                }
+                if let Some(filter) = func.filter {
+                    self.reclaim_node(filter);
+                }
+            }
+            Expr::Subquery(mut query) | Expr::Exists(mut query) => {
+                query.visit_exprs(&mut |expr| self.reclaim_expr(take(expr)));
+            }
+            Expr::Array(list) => self.reclaim_list(list),
+            Expr::In { expr, list, .. } => {
+                self.reclaim_node(expr);
+                self.reclaim_list(list);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.reclaim_node(expr);
+                self.reclaim_node(low);
+                self.reclaim_node(high);
+            }
+            Expr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                if let Some(operand) = operand {
+                    self.reclaim_node(operand);
+                }
+                for (when, then) in when_clauses {
+                    self.reclaim_expr(when);
+                    self.reclaim_expr(then);
+                }
+                if let Some(else_clause) = else_clause {
+                    self.reclaim_node(else_clause);
+                }
+            }
+        }
+    }
+
+    /// Boxes `expr`, reusing a pooled node if there is one.
+    pub(crate) fn boxed(&mut self, expr: Expr) -> Box<Expr> {
+        match self.nodes.pop() {
+            Some(mut node) => {
+                *node = expr;
+                node
+            }
+            None => Box::new(expr),
+        }
+    }
+
+    /// Returns a node, and the nodes below it, to the pool.
+    fn reclaim_node(&mut self, mut node: Box<Expr>) {
+        self.reclaim_expr(take(&mut node));
+        self.nodes.push(node);
+    }
+
+    fn reclaim_list(&mut self, list: Vec<Expr>) {
+        for expr in list {
+            self.reclaim_expr(expr);
+        }
+    }
+}
+
+/// A node holding an expression that owns no memory.
+fn empty_node() -> Box<Expr> {
+    Box::new(Expr::Literal(Literal::Null))
+}
+
+/// Moves an expression out, leaving one that owns no memory.
+fn take(expr: &mut Expr) -> Expr {
+    core::mem::replace(expr, Expr::Literal(Literal::Null))
+}
@@ -18,6 +18,7 @@ pub const fn prefix_binding_power(kind: &TokenKind) -> Option<u8> {
         // Primary expressions (literals, identifiers, etc.)
         TokenKind::Integer(_)
         | TokenKind::Float(_)
+        | TokenKind::BigNumber(_)
         | TokenKind::LeftParen
         | TokenKind::Question
         | TokenKind::Colon
@@ -30,6 +31,9 @@ pub const fn prefix_binding_power(kind: &TokenKind) -> Option<u8> {
             Keyword::Null
             | Keyword::True
             | Keyword::False
+            | Keyword::Date
+            | Keyword::Time
+            | Keyword::Timestamp
             | Keyword::Case
             | Keyword::Cast
             | Keyword::Exists
@@ -70,10 +74,10 @@ pub const fn infix_binding_power(kind: &TokenKind) -> Option<(u8, u8)> {
         | TokenKind::Gt
         | TokenKind::GtEq => Some((5, 6)),
 
-        // IS, IN, BETWEEN, LIKE
-        TokenKind::Keyword(Keyword::Is | Keyword::In | Keyword::Between | Keyword::Like) => {
-            Some((5, 6))
-        }
+        // IS, IN, BETWEEN, LIKE, and their NOT forms
+        TokenKind::Keyword(
+            Keyword::Is | Keyword::In | Keyword::Between | Keyword::Like | Keyword::Not,
+        ) => Some((5, 6)),
 
         // Bitwise OR
         TokenKind::BitOr => Some((7, 8)),
@@ -90,6 +94,16 @@ pub const fn infix_binding_power(kind: &TokenKind) -> Option<(u8, u8)> {
         // Multiplicative
         TokenKind::Star | TokenKind::Slash | TokenKind::Percent => Some((15, 16)),
 
+        // JSON access binds tightest, as in SQLite, so `data->>'age' > 18`
+        // and `data->'a'->>'b'` need no parentheses
+        TokenKind::Arrow
+        | TokenKind::LongArrow
+        | TokenKind::HashArrow
+        | TokenKind::HashLongArrow => Some((17, 18)),
+
+        // COLLATE applies to the operand immediately before it
+        TokenKind::Keyword(Keyword::Collate) => Some((19, 20)),
+
         _ => None,
     }
 }
@@ -104,7 +118,7 @@ pub const fn postfix_binding_power(kind: &TokenKind) -> Option<u8> {
         // IS NULL, IS NOT NULL
         TokenKind::Keyword(Keyword::Is) => Some(17),
         // COLLATE
-        TokenKind::Keyword(Keyword::As) => Some(17),
+        TokenKind::Keyword(Keyword::Collate) => Some(19),
         _ => None,
     }
 }
@@ -132,6 +146,10 @@ pub const fn token_to_binary_op(kind: &TokenKind) -> Option<BinaryOp> {
         TokenKind::BitOr => Some(BinaryOp::BitOr),
         TokenKind::LeftShift => Some(BinaryOp::LeftShift),
         TokenKind::RightShift => Some(BinaryOp::RightShift),
+        TokenKind::Arrow => Some(BinaryOp::JsonGet),
+        TokenKind::LongArrow => Some(BinaryOp::JsonGetText),
+        TokenKind::HashArrow => Some(BinaryOp::JsonPath),
+        TokenKind::HashLongArrow => Some(BinaryOp::JsonPathText),
         _ => None,
     }
 }
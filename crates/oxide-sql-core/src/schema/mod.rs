@@ -3,6 +3,9 @@
 //! This module provides traits that are implemented by the `#[derive(Table)]`
 //! macro to enable compile-time checked SQL queries.
 
+use crate::builder::value::{DecodeError, FromSqlValue, SqlValue, decode_value};
+use crate::migrations::{DefaultValue, same_default};
+
 /// Trait for table metadata.
 ///
 /// Implemented by types generated from `#[derive(Table)]` to provide
@@ -51,16 +54,64 @@ pub trait TypedColumn<T>: Column<Type = T> {}
 ///
 /// Implemented for tuples of column types to enable type-safe SELECT queries.
 pub trait Selectable<T: Table> {
+    /// The Rust type of a result row: the column's type for a single
+    /// column, or a tuple of column types in selection order.
+    type Output;
+
+    /// The column names to select.
+    ///
+    /// A constant, so builders can check a selection at compile time.
+    const COLUMN_NAMES: &'static [&'static str];
+
     /// Returns the column names to select.
-    fn column_names() -> &'static [&'static str];
+    #[must_use]
+    fn column_names() -> &'static [&'static str] {
+        Self::COLUMN_NAMES
+    }
+}
+
+/// Decodes result rows of a [`Selectable`] into its [`Output`](Selectable::Output).
+///
+/// Values are read by position, so drivers can hydrate typed tuples without
+/// looking up columns by name. Implemented for every `Selectable` whose
+/// column types implement [`FromSqlValue`].
+pub trait DecodeRow<T: Table>: Selectable<T> {
+    /// Decodes a row whose values are in selection order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the row has the wrong number of values
+    /// or a value cannot be converted to its column's type.
+    fn decode_row(row: Vec<SqlValue>) -> Result<Self::Output, DecodeError>;
+}
+
+/// Checks that a row has exactly `expected` values.
+fn check_column_count(row: &[SqlValue], expected: usize) -> Result<(), DecodeError> {
+    if row.len() == expected {
+        Ok(())
+    } else {
+        Err(DecodeError::ColumnCount {
+            expected,
+            found: row.len(),
+        })
+    }
 }
 
 // Implement Selectable for single columns
 impl<T: Table, C: Column<Table = T>> Selectable<T> for C {
-    fn column_names() -> &'static [&'static str] {
-        // Return as a slice containing just this column
-        // This is a compile-time constant
-        &[C::NAME]
+    type Output = C::Type;
+
+    const COLUMN_NAMES: &'static [&'static str] = &[C::NAME];
+}
+
+impl<T: Table, C: Column<Table = T>> DecodeRow<T> for C
+where
+    C::Type: FromSqlValue,
+{
+    fn decode_row(row: Vec<SqlValue>) -> Result<Self::Output, DecodeError> {
+        check_column_count(&row, 1)?;
+        let value = row.into_iter().next().unwrap_or(SqlValue::Null);
+        decode_value(0, value)
     }
 }
 
@@ -81,6 +132,47 @@ pub struct ColumnSchema {
     pub autoincrement: bool,
     /// Raw SQL default expression, if any.
     pub default_expr: Option<&'static str>,
+    /// Collation name (e.g. `NOCASE`), if any.
+    pub collation: Option<&'static str>,
+    /// The column this column references as a foreign key, if any.
+    pub references: Option<ReferenceSchema>,
+    /// The expression computing this column, if it is generated.
+    pub generated: Option<GeneratedSchema>,
+}
+
+/// The target of a column's foreign key, as declared with
+/// `#[column(references = "table(column)")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceSchema {
+    /// The referenced table name.
+    pub table: &'static str,
+    /// The referenced column name.
+    pub column: &'static str,
+}
+
+/// A generated column's expression, as declared with
+/// `#[column(generated = "expr")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratedSchema {
+    /// The SQL expression computing the column.
+    pub expr: &'static str,
+    /// Whether the value is stored rather than computed on read.
+    pub stored: bool,
+}
+
+/// Metadata for a table index, as declared with
+/// `#[table(index(...))]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexSchema {
+    /// The index name.
+    pub name: &'static str,
+    /// Indexed elements as SQL: column names or expressions such as
+    /// `lower(email)`.
+    pub columns: &'static [&'static str],
+    /// Whether this is a UNIQUE index.
+    pub unique: bool,
+    /// Partial index predicate (WHERE clause) as SQL, if any.
+    pub condition: Option<&'static str>,
 }
 
 /// Tables that provide full column schema for DDL generation.
@@ -92,6 +184,15 @@ pub struct ColumnSchema {
 pub trait TableSchema: Table {
     /// Complete schema for every column in declaration order.
     const SCHEMA: &'static [ColumnSchema];
+
+    /// Indexes declared on the table.
+    const INDEXES: &'static [IndexSchema] = &[];
+
+    /// Whether the table is a SQLite `STRICT` table.
+    const STRICT: bool = false;
+
+    /// Whether the table is a SQLite `WITHOUT ROWID` table.
+    const WITHOUT_ROWID: bool = false;
 }
 
 /// Maps Rust type names to SQL data types.
@@ -102,14 +203,110 @@ pub trait TableSchema: Table {
 pub trait RustTypeMapping {
     /// Maps a Rust type name to the dialect-specific SQL data type.
     fn map_type(&self, rust_type: &str) -> crate::ast::DataType;
+
+    /// Returns `true` if columns of types `a` and `b` store values the
+    /// same way, so changing one into the other is not a schema change.
+    ///
+    /// Used by the schema diff. Defaults to exact equality; SQLite
+    /// compares type affinities instead.
+    fn types_equivalent(&self, a: &crate::ast::DataType, b: &crate::ast::DataType) -> bool {
+        a == b
+    }
+
+    /// Returns the canonical form of a column default in this dialect.
+    ///
+    /// Defaults to [`DefaultValue::normalized`]; dialects also map
+    /// their own current-time functions (such as `now()`) onto
+    /// `CURRENT_TIMESTAMP`, `CURRENT_DATE`, and `CURRENT_TIME`.
+    fn normalize_default(&self, value: &DefaultValue) -> DefaultValue {
+        value.normalized()
+    }
+
+    /// Returns `true` if columns of `data_type` hold booleans, so that
+    /// `FALSE`, `0`, and `'false'` defaults are the same value.
+    ///
+    /// Defaults to `BOOLEAN` columns only; SQLite stores booleans as
+    /// integers.
+    fn stores_booleans(&self, data_type: &crate::ast::DataType) -> bool {
+        matches!(data_type, crate::ast::DataType::Boolean)
+    }
+
+    /// Returns `true` if defaults `a` and `b` give a column of
+    /// `data_type` the same value, so changing one into the other is
+    /// not a schema change.
+    ///
+    /// Used by the schema diff. Compares the defaults after
+    /// [`normalize_default`](Self::normalize_default), treating the
+    /// spellings of a boolean alike where
+    /// [`stores_booleans`](Self::stores_booleans) holds.
+    fn defaults_equivalent(
+        &self,
+        a: &DefaultValue,
+        b: &DefaultValue,
+        data_type: &crate::ast::DataType,
+    ) -> bool {
+        same_default(
+            &self.normalize_default(a),
+            &self.normalize_default(b),
+            || self.stores_booleans(data_type),
+        )
+    }
+}
+
+/// Returns `true` if `names` contains `name`.
+///
+/// This is a `const fn` so it can be evaluated in `const` assertions, which
+/// is how the `sql!` macro checks column names against `Table::COLUMNS` at
+/// compile time.
+#[must_use]
+pub const fn contains_name(names: &[&str], name: &str) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        if str_eq(names[i], name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
 }
 
 // Implement Selectable for tuples of columns (up to 12)
 macro_rules! impl_selectable_tuple {
     ($($idx:tt: $col:ident),+) => {
         impl<T: Table, $($col: Column<Table = T>),+> Selectable<T> for ($($col,)+) {
-            fn column_names() -> &'static [&'static str] {
-                &[$($col::NAME),+]
+            type Output = ($($col::Type,)+);
+
+            const COLUMN_NAMES: &'static [&'static str] = &[$($col::NAME),+];
+        }
+
+        impl<T: Table, $($col: Column<Table = T>),+> DecodeRow<T> for ($($col,)+)
+        where
+            $($col::Type: FromSqlValue),+
+        {
+            fn decode_row(row: Vec<SqlValue>) -> Result<Self::Output, DecodeError> {
+                check_column_count(&row, [$($idx),+].len())?;
+                let mut values = row.into_iter();
+                Ok(($(
+                    decode_value::<$col::Type>(
+                        $idx,
+                        values.next().unwrap_or(SqlValue::Null),
+                    )?,
+                )+))
             }
         }
     };
@@ -0,0 +1,226 @@
+//! Allocation counts for rendering and parsing large statements.
+//!
+//! These tests verify that:
+//! - `build_into()` renders a large INSERT batch into a pre-sized buffer
+//!   without per-row allocations
+//! - `build()` pre-sizes its buffer instead of growing it row by row
+//! - A `QueryCache` hit only allocates the parameters, not the shape
+//! - The lexer only allocates for tokens that own data
+//! - The parser moves identifiers and literals out of tokens instead of
+//!   cloning them
+//! - A parser with a `NodePool` reuses expression nodes instead of
+//!   allocating them
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use oxide_sql_core::builder::{InsertDyn, QueryCache, SqlValue};
+use oxide_sql_core::parser::NodePool;
+use oxide_sql_core::{Lexer, Parser, TokenKind};
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: delegates to the system allocator and only counts calls.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        // SAFETY: same contract as `GlobalAlloc::alloc`.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: same contract as `GlobalAlloc::dealloc`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        // SAFETY: same contract as `GlobalAlloc::realloc`.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the number of allocations made on this thread while running `f`.
+fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    (result, after - before)
+}
+
+// =============================================================================
+// Builders
+// =============================================================================
+
+const ROWS: usize = 1000;
+
+/// Builds an INSERT with `ROWS` rows of three values each.
+macro_rules! large_insert {
+    () => {
+        InsertDyn::new()
+            .into_table("events")
+            .columns(&["id", "kind", "payload"])
+            .values_many(
+                (0..ROWS)
+                    .map(|i| {
+                        vec![
+                            SqlValue::Int(i64::try_from(i).unwrap()),
+                            SqlValue::from("click"),
+                            SqlValue::Null,
+                        ]
+                    })
+                    .collect(),
+            )
+    };
+}
+
+#[test]
+fn build_into_large_insert_allocates_only_params() {
+    let insert = large_insert!();
+    let mut sql = String::with_capacity(32 * ROWS);
+
+    let (params, allocations) = count_allocations(|| insert.build_into(&mut sql));
+
+    assert_eq!(params.len(), 3 * ROWS);
+    assert!(sql.ends_with("(?, ?, ?)"));
+    // One allocation for the parameter vector; nothing per row.
+    assert!(allocations <= 2, "{allocations} allocations");
+}
+
+#[test]
+fn build_large_insert_presizes_buffer() {
+    let insert = large_insert!();
+
+    let ((sql, params), allocations) = count_allocations(|| insert.build());
+
+    assert_eq!(params.len(), 3 * ROWS);
+    assert_eq!(sql.matches('?').count(), 3 * ROWS);
+    // The SQL buffer and the parameter vector, without regrowing either.
+    assert!(allocations <= 3, "{allocations} allocations");
+}
+
+#[test]
+fn build_into_reused_buffer_does_not_grow() {
+    let mut sql = String::new();
+    let _ = large_insert!().build_into(&mut sql);
+    let capacity = sql.capacity();
+
+    sql.clear();
+    let insert = large_insert!();
+    let (_, allocations) = count_allocations(|| insert.build_into(&mut sql));
+
+    assert_eq!(sql.capacity(), capacity);
+    assert!(allocations <= 2, "{allocations} allocations");
+}
+
+#[test]
+fn query_cache_hit_allocates_only_params() {
+    let cache = QueryCache::new();
+    let _ = cache.build(large_insert!());
+    let insert = large_insert!();
+
+    let ((sql, params), allocations) = count_allocations(|| cache.build(insert));
+
+    assert_eq!(params.len(), 3 * ROWS);
+    assert!(sql.ends_with("(?, ?, ?)"));
+    assert!(allocations <= 2, "{allocations} allocations");
+}
+
+// =============================================================================
+// Lexer and parser
+// =============================================================================
+
+/// Lexes `sql` to the end and returns the number of allocations made.
+fn lex_allocations(sql: &str) -> usize {
+    let mut lexer = Lexer::new(sql);
+    let (_, allocations) = count_allocations(|| while lexer.next_token().kind != TokenKind::Eof {});
+    allocations
+}
+
+/// Parses `sql` and returns the number of allocations made.
+fn parse_allocations(sql: &str) -> usize {
+    let (stmt, allocations) = count_allocations(|| Parser::new(sql).parse_statement());
+    assert!(stmt.is_ok(), "{sql}: {stmt:?}");
+    allocations
+}
+
+#[test]
+fn lexing_keywords_does_not_allocate() {
+    let allocations =
+        lex_allocations("SELECT DISTINCT * FROM WHERE AND OR NOT NULL ORDER BY LIMIT ( ) , ?");
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn lexing_allocates_once_per_owned_token() {
+    assert_eq!(lex_allocations("alpha beta gamma"), 3);
+    assert_eq!(lex_allocations("'one' 'it''s' \"quoted \"\"name\"\"\""), 3);
+}
+
+#[test]
+fn raw_token_stream_does_not_allocate() {
+    let sql = "SELECT \"quoted\", name, 'it''s', X'CAFE', 1.5 FROM t -- comment";
+    let (count, allocations) = count_allocations(|| Lexer::new(sql).raw_tokens().count());
+    assert_eq!(count, 12);
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn parsing_columns_allocates_once_per_name() {
+    let columns = |n: usize| {
+        let names: Vec<String> = (0..n).map(|i| format!("column_{i}")).collect();
+        format!("SELECT {} FROM t", names.join(", "))
+    };
+    let small = columns(100);
+    let large = columns(200);
+
+    // Each extra column costs its name plus amortized `Vec` growth; cloning
+    // tokens or uppercasing keywords would add more per column.
+    let extra = parse_allocations(&large) - parse_allocations(&small);
+    assert!(extra <= 110, "{extra} allocations for 100 extra columns");
+}
+
+#[test]
+fn parsing_string_literals_does_not_copy_them() {
+    let values = |n: usize| {
+        let values: Vec<String> = (0..n).map(|i| format!("'value {i}'")).collect();
+        format!("SELECT * FROM t WHERE name IN ({})", values.join(", "))
+    };
+    let small = values(100);
+    let large = values(200);
+
+    let extra = parse_allocations(&large) - parse_allocations(&small);
+    assert!(extra <= 110, "{extra} allocations for 100 extra literals");
+}
+
+#[test]
+fn pooled_parsing_reuses_expression_nodes() {
+    let predicates: Vec<String> = (0..100).map(|i| format!("x{i} + 1 > {i}")).collect();
+    let sql = format!("SELECT * FROM t WHERE {}", predicates.join(" AND "));
+    let unpooled = parse_allocations(&sql);
+
+    // Warm the pool with the statement's own nodes.
+    let mut parser = Parser::new(&sql).with_pool(NodePool::new());
+    let stmt = parser.parse_statement().unwrap();
+    let mut pool = parser.into_pool();
+    pool.reclaim(stmt);
+    let nodes = pool.len();
+
+    let (pool, pooled) = count_allocations(|| {
+        let mut parser = Parser::new(&sql).with_pool(pool);
+        assert!(parser.parse_statement().is_ok());
+        parser.into_pool()
+    });
+
+    // `+` and `>` box two operands per predicate, `AND` two per join.
+    assert_eq!(nodes, 4 * 100 + 2 * 99);
+    assert!(pool.is_empty());
+    assert_eq!(unpooled - pooled, nodes);
+}
@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use oxide_sql_core::ast::{
-    DeleteStatement, InsertStatement, SelectStatement, Statement, UpdateStatement,
+    DeleteStatement, InsertStatement, MergeStatement, SelectStatement, Statement, UpdateStatement,
 };
 use oxide_sql_core::{ParseError, Parser};
 
@@ -45,6 +45,13 @@ pub fn parse_delete(sql: &str) -> DeleteStatement {
     }
 }
 
+pub fn parse_merge(sql: &str) -> MergeStatement {
+    match parse(sql) {
+        Statement::Merge(m) => m,
+        other => panic!("Expected MERGE, got {other:?}"),
+    }
+}
+
 /// Verifies that `to_string()` produces a fixed point:
 /// parse(sql).to_string() can be re-parsed and yields the same
 /// string again.
@@ -8,9 +8,13 @@
 
 use oxide_sql_core::ast::DataType;
 use oxide_sql_core::migrations::{
-    CreateTableOp, DefaultValue, DuckDbDialect, MigrationDialect, PostgresDialect, SqliteDialect,
+    CreateTableOp, DefaultValue, DiffWarning, DuckDbDialect, IndexColumn, MigrationDialect,
+    PostgresDialect, SchemaSnapshot, SqliteDialect, TableSnapshot, auto_diff_schema,
+    initial_from_tables,
+};
+use oxide_sql_core::schema::{
+    Column, GeneratedSchema, IndexSchema, ReferenceSchema, Table, TableSchema,
 };
-use oxide_sql_core::schema::{Column, Table, TableSchema};
 use oxide_sql_derive::Table;
 
 // =============================================================================
@@ -562,3 +566,304 @@ fn test_option_stripped_for_type_mapping() {
     assert_eq!(email.data_type, DataType::Varchar(Some(255)));
     assert!(email.nullable);
 }
+
+// =============================================================================
+// Test: Indexes declared with #[table(index(...))]
+// =============================================================================
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(
+    name = "accounts",
+    index(columns = "tenant_id, created_at"),
+    index(expr = "lower(email)", unique, where = "deleted_at IS NULL"),
+    index(
+        name = "idx_accounts_tenant_email",
+        columns = "tenant_id",
+        expr = "trim(email)"
+    )
+)]
+pub struct Account {
+    #[column(primary_key)]
+    pub id: i64,
+    pub tenant_id: i64,
+    pub email: String,
+    pub created_at: String,
+    #[column(nullable)]
+    pub deleted_at: Option<String>,
+}
+
+#[test]
+fn test_table_indexes() {
+    assert_eq!(
+        AccountTable::INDEXES,
+        &[
+            IndexSchema {
+                name: "idx_accounts_tenant_id_created_at",
+                columns: &["tenant_id", "created_at"],
+                unique: false,
+                condition: None,
+            },
+            IndexSchema {
+                name: "idx_accounts_lower_email",
+                columns: &["lower(email)"],
+                unique: true,
+                condition: Some("deleted_at IS NULL"),
+            },
+            IndexSchema {
+                name: "idx_accounts_tenant_email",
+                columns: &["tenant_id", "trim(email)"],
+                unique: false,
+                condition: None,
+            },
+        ]
+    );
+    assert!(UserTable::INDEXES.is_empty());
+}
+
+#[test]
+fn test_table_indexes_in_snapshot_and_sql() {
+    let dialect = SqliteDialect::new();
+    let snapshot = TableSnapshot::from_table_schema::<AccountTable>(&dialect);
+    assert_eq!(snapshot.indexes.len(), 3);
+    assert!(matches!(
+        snapshot.indexes[1].columns.as_slice(),
+        [IndexColumn::Expr(_)]
+    ));
+
+    let mut desired = SchemaSnapshot::new();
+    desired.add_from_table_schema::<AccountTable>(&dialect);
+    let diff = auto_diff_schema(&SchemaSnapshot::new(), &desired);
+    let sql: Vec<String> = diff
+        .operations
+        .iter()
+        .map(|op| dialect.generate_sql(op))
+        .collect();
+    assert_eq!(sql.len(), 4);
+    assert_eq!(
+        sql[2],
+        "CREATE UNIQUE INDEX \"idx_accounts_lower_email\" ON \"accounts\" \
+         ((lower(email))) WHERE deleted_at IS NULL"
+    );
+}
+
+// =============================================================================
+// Test: Column collation
+// =============================================================================
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "tags")]
+pub struct Tag {
+    #[column(primary_key)]
+    pub id: i64,
+    #[column(collation = "NOCASE")]
+    pub label: String,
+    #[column(collation = "C")]
+    pub code: String,
+}
+
+#[test]
+fn test_collation_in_schema_and_sql() {
+    assert_eq!(TagTable::SCHEMA[0].collation, None);
+    assert_eq!(TagTable::SCHEMA[1].collation, Some("NOCASE"));
+
+    let dialect = SqliteDialect::new();
+    let op = CreateTableOp::from_table::<TagTable>(&dialect);
+    assert_eq!(op.columns[1].collation.as_deref(), Some("NOCASE"));
+    assert!(
+        dialect
+            .create_table(&op)
+            .contains("\"label\" TEXT NOT NULL COLLATE NOCASE")
+    );
+
+    let snapshot = TableSnapshot::from_table_schema::<TagTable>(&dialect);
+    assert_eq!(snapshot.columns[1].collation.as_deref(), Some("NOCASE"));
+}
+
+#[test]
+fn test_collation_change_diffed() {
+    let dialect = PostgresDialect::new();
+    let mut current = TableSnapshot::from_table_schema::<TagTable>(&dialect);
+    current.columns[2].collation = None;
+
+    let diff = oxide_sql_core::migrations::auto_diff_table::<TagTable>(&current, &dialect);
+    let sql: Vec<String> = diff
+        .operations
+        .iter()
+        .map(|op| dialect.generate_sql(op))
+        .collect();
+    assert_eq!(
+        sql,
+        ["ALTER TABLE \"tags\" ALTER COLUMN \"code\" TYPE VARCHAR(255) COLLATE \"C\""]
+    );
+}
+
+// =============================================================================
+// Test: Foreign keys with #[column(references = "...")]
+// =============================================================================
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "comments")]
+pub struct Comment {
+    #[column(primary_key, autoincrement)]
+    pub id: i64,
+    #[column(references = "users_full(id)")]
+    pub author_id: i64,
+    pub body: String,
+}
+
+#[test]
+fn test_references_in_schema() {
+    let author = &CommentTable::SCHEMA[1];
+    assert_eq!(
+        author.references,
+        Some(ReferenceSchema {
+            table: "users_full",
+            column: "id",
+        })
+    );
+    assert!(CommentTable::SCHEMA[2].references.is_none());
+
+    let dialect = SqliteDialect::new();
+    let snapshot = TableSnapshot::from_table_schema::<CommentTable>(&dialect);
+    assert_eq!(snapshot.foreign_keys.len(), 1);
+    assert_eq!(snapshot.foreign_keys[0].columns, ["author_id"]);
+    assert_eq!(snapshot.foreign_keys[0].references_table, "users_full");
+
+    let op = CreateTableOp::from_table::<CommentTable>(&dialect);
+    assert!(
+        dialect
+            .create_table(&op)
+            .contains("\"author_id\" INTEGER NOT NULL REFERENCES \"users_full\" (\"id\")")
+    );
+}
+
+#[test]
+fn test_initial_from_tables() {
+    let dialect = PostgresDialect::new();
+    let diff = initial_from_tables(
+        &[
+            TableSnapshot::from_table_schema::<CommentTable>,
+            TableSnapshot::from_table_schema::<UserFullTable>,
+        ],
+        &dialect,
+    );
+    let sql = diff.to_sql(&dialect);
+    assert_eq!(sql.len(), 2);
+    assert!(sql[0].starts_with("CREATE TABLE \"users_full\""));
+    assert!(sql[1].starts_with("CREATE TABLE \"comments\""));
+    assert!(sql[1].contains("REFERENCES \"users_full\" (\"id\")"));
+}
+
+// =============================================================================
+// Test: SQLite table options and generated columns
+// =============================================================================
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "line_items", strict, without_rowid)]
+pub struct LineItem {
+    #[column(primary_key)]
+    pub id: i64,
+    pub price: f64,
+    pub quantity: i64,
+    #[column(generated = "price * quantity", stored)]
+    pub total: f64,
+    #[column(generated = "quantity > 1")]
+    pub bulk: i64,
+}
+
+#[test]
+fn test_table_options_and_generated_columns() {
+    const { assert!(LineItemTable::STRICT) };
+    const { assert!(LineItemTable::WITHOUT_ROWID) };
+    const { assert!(!UserTable::STRICT) };
+    assert_eq!(
+        LineItemTable::SCHEMA[3].generated,
+        Some(GeneratedSchema {
+            expr: "price * quantity",
+            stored: true,
+        })
+    );
+    assert!(!LineItemTable::SCHEMA[4].generated.unwrap().stored);
+    assert!(LineItemTable::SCHEMA[2].generated.is_none());
+
+    let dialect = SqliteDialect::new();
+    let sql = dialect.create_table(&CreateTableOp::from_table::<LineItemTable>(&dialect));
+    assert!(sql.contains("\"total\" REAL NOT NULL GENERATED ALWAYS AS (price * quantity) STORED"));
+    assert!(sql.contains("\"bulk\" INTEGER NOT NULL GENERATED ALWAYS AS (quantity > 1) VIRTUAL"));
+    assert!(sql.ends_with(") WITHOUT ROWID, STRICT"));
+}
+
+#[test]
+fn test_introspected_sqlite_options_are_not_changes() {
+    use oxide_sql_core::migrations::auto_diff_schema_for_dialect;
+    use oxide_sql_core::migrations::introspect::sqlite_helpers::{
+        column_from_pragma, generated_from_xinfo, table_options_from_pragma,
+    };
+
+    let dialect = SqliteDialect::new();
+    let create_sql = dialect.create_table(&CreateTableOp::from_table::<LineItemTable>(&dialect));
+
+    // What an introspector reads back: `PRAGMA table_xinfo` rows as
+    // (name, type, notnull, pk, hidden), and `PRAGMA table_list` flags.
+    let xinfo = [
+        ("id", "INTEGER", true, true, 0),
+        ("price", "REAL", true, false, 0),
+        ("quantity", "INTEGER", true, false, 0),
+        ("total", "REAL", true, false, 3),
+        ("bulk", "INTEGER", true, false, 2),
+    ];
+    let columns = xinfo
+        .iter()
+        .map(|&(name, type_str, notnull, pk, hidden)| {
+            let mut column = column_from_pragma(name, type_str, notnull, None, pk);
+            column.generated = generated_from_xinfo(&create_sql, name, hidden);
+            column
+        })
+        .collect();
+    let introspected = TableSnapshot {
+        schema: None,
+        name: "line_items".into(),
+        columns,
+        indexes: vec![],
+        foreign_keys: vec![],
+        options: table_options_from_pragma(true, true),
+    };
+
+    let mut current = SchemaSnapshot::new();
+    current.add_table(introspected.clone());
+    let mut desired = SchemaSnapshot::new();
+    desired.add_table(TableSnapshot::from_table_schema::<LineItemTable>(&dialect));
+    let diff = auto_diff_schema_for_dialect(&current, &desired, &dialect);
+    assert!(diff.is_empty(), "{diff:?}");
+
+    // A plain table in the database is a real change.
+    let mut plain = introspected;
+    plain.options.clear();
+    plain.columns[3].generated = None;
+    let mut current = SchemaSnapshot::new();
+    current.add_table(plain);
+    let diff = auto_diff_schema_for_dialect(&current, &desired, &dialect);
+    assert!(diff.operations.is_empty());
+    assert!(matches!(
+        diff.warnings.as_slice(),
+        [
+            DiffWarning::GeneratedColumnChange { column, .. },
+            DiffWarning::TableOptionsChanged { .. },
+        ] if column == "total"
+    ));
+
+    // Other databases ignore SQLite's options.
+    let postgres = PostgresDialect::new();
+    let mut current = SchemaSnapshot::new();
+    current.add_table(TableSnapshot {
+        options: vec![],
+        ..TableSnapshot::from_table_schema::<LineItemTable>(&postgres)
+    });
+    let mut desired = SchemaSnapshot::new();
+    desired.add_table(TableSnapshot::from_table_schema::<LineItemTable>(&postgres));
+    assert!(auto_diff_schema_for_dialect(&current, &desired, &postgres).is_empty());
+}
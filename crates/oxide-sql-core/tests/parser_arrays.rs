@@ -0,0 +1,158 @@
+//! Tests for PostgreSQL arrays: `ARRAY[...]` constructors, `type[]` types,
+//! and `ANY` / `ALL` comparisons.
+
+mod common;
+use common::*;
+
+use oxide_sql_core::Parser;
+use oxide_sql_core::ast::{BinaryOp, DataType, Expr, Literal, Quantifier, Statement};
+use oxide_sql_core::dialect::{Dialect, GenericDialect};
+
+/// A PostgreSQL-like dialect with arrays.
+struct Arrays;
+
+impl Dialect for Arrays {
+    fn name(&self) -> &'static str {
+        "arrays"
+    }
+
+    fn supports_arrays(&self) -> bool {
+        true
+    }
+}
+
+fn parse_arrays(sql: &str) -> Statement {
+    Parser::for_dialect(sql, &Arrays)
+        .parse_statement()
+        .unwrap_or_else(|e| panic!("Failed to parse: {sql}\nError: {e:?}"))
+}
+
+fn round_trip_arrays(sql: &str) {
+    let rendered = parse_arrays(sql).to_string();
+    assert_eq!(rendered, sql);
+    assert_eq!(parse_arrays(&rendered).to_string(), rendered);
+}
+
+// ===================================================================
+// ARRAY constructors
+// ===================================================================
+
+#[test]
+fn array_literal() {
+    let stmt = parse_arrays("SELECT ARRAY[1, 2, 3]");
+    let Statement::Select(s) = &stmt else {
+        panic!("Expected SELECT, got {stmt:?}");
+    };
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Array(elements) if elements.len() == 3
+            && matches!(elements[0], Expr::Literal(Literal::Integer(1)))
+    ));
+    round_trip_arrays("SELECT ARRAY[1, 2, 3]");
+}
+
+#[test]
+fn array_literal_empty_and_nested() {
+    round_trip_arrays("SELECT ARRAY[]");
+    round_trip_arrays("SELECT ARRAY[ARRAY[1, 2], ARRAY[3, 4]]");
+    round_trip_arrays("INSERT INTO posts (tags) VALUES (ARRAY['a', 'b'])");
+}
+
+#[test]
+fn array_is_still_a_column_name() {
+    let s = parse_select("SELECT array FROM t");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Column { name, .. } if name == "array"
+    ));
+    round_trip_arrays("SELECT array FROM t");
+}
+
+#[test]
+fn arrays_need_dialect_support() {
+    parse_err("SELECT ARRAY[1, 2]");
+    parse_err("SELECT CAST(x AS INTEGER[]) FROM t");
+    assert!(
+        Parser::for_dialect("SELECT ARRAY[1]", &GenericDialect::new())
+            .parse_statement()
+            .is_err()
+    );
+}
+
+// ===================================================================
+// Array types
+// ===================================================================
+
+#[test]
+fn array_data_type() {
+    let stmt = parse_arrays("SELECT CAST(x AS TEXT[]), CAST(y AS INTEGER[][]) FROM t");
+    let Statement::Select(s) = &stmt else {
+        panic!("Expected SELECT, got {stmt:?}");
+    };
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Cast { data_type: DataType::Array(inner), .. } if **inner == DataType::Text
+    ));
+    assert!(matches!(
+        &s.columns[1].expr,
+        Expr::Cast { data_type: DataType::Array(inner), .. }
+            if **inner == DataType::Array(Box::new(DataType::Integer))
+    ));
+    round_trip_arrays("SELECT CAST(x AS TEXT[]), CAST(y AS INTEGER[][]) FROM t");
+}
+
+// ===================================================================
+// ANY / ALL
+// ===================================================================
+
+#[test]
+fn any_with_array() {
+    let stmt = parse_arrays("SELECT * FROM t WHERE id = ANY(ARRAY[1, 2])");
+    let Statement::Select(s) = &stmt else {
+        panic!("Expected SELECT, got {stmt:?}");
+    };
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Quantified { op: BinaryOp::Eq, quantifier: Quantifier::Any, right, .. })
+            if matches!(right.as_ref(), Expr::Array(_))
+    ));
+    round_trip_arrays("SELECT * FROM t WHERE id = ANY(ARRAY[1, 2])");
+}
+
+#[test]
+fn any_and_all_with_columns_and_parameters() {
+    let s = parse_select("SELECT * FROM t WHERE tag <> ALL(tags) AND id = ANY(?)");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Binary { left, op: BinaryOp::And, right })
+            if matches!(left.as_ref(), Expr::Quantified { quantifier: Quantifier::All, .. })
+                && matches!(right.as_ref(), Expr::Quantified { quantifier: Quantifier::Any, .. })
+    ));
+    round_trip("SELECT * FROM t WHERE tag <> ALL(tags) AND id = ANY(?)");
+}
+
+#[test]
+fn some_is_any() {
+    let stmt = parse("SELECT * FROM t WHERE x > SOME(y)");
+    assert_eq!(stmt.to_string(), "SELECT * FROM t WHERE x > ANY(y)");
+}
+
+#[test]
+fn any_with_subquery() {
+    let s = parse_select("SELECT * FROM t WHERE price > ALL(SELECT price FROM u WHERE u.k = t.k)");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Quantified { right, .. }) if matches!(right.as_ref(), Expr::Subquery(_))
+    ));
+    round_trip("SELECT * FROM t WHERE price > ALL(SELECT price FROM u WHERE u.k = t.k)");
+}
+
+#[test]
+fn any_is_still_a_column_name() {
+    let s = parse_select("SELECT * FROM t WHERE x = any");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Binary { right, .. })
+            if matches!(right.as_ref(), Expr::Column { name, .. } if name == "any")
+    ));
+}
@@ -32,3 +32,20 @@ fn error_unclosed_paren() {
 fn error_join_without_on_or_using() {
     let _ = parse_err("SELECT * FROM a INNER JOIN b WHERE a.id = 1");
 }
+
+#[test]
+fn error_trailing_tokens() {
+    let err = parse_err("SELECT a FROM t garbage here");
+    assert_eq!(err.expected.as_deref(), Some("end of input"));
+    let _ = parse_err("SELECT 1; SELECT 2");
+}
+
+#[test]
+fn trailing_semicolon_is_allowed() {
+    let _ = parse_select("SELECT 1;");
+}
+
+#[test]
+fn error_not_without_operator() {
+    let _ = parse_err("SELECT * FROM t WHERE a NOT b");
+}
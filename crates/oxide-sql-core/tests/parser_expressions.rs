@@ -1,10 +1,10 @@
 //! Tests for special expression forms: IS NULL, BETWEEN, IN, CASE,
-//! and CAST.
+//! CAST, and COLLATE.
 
 mod common;
 use common::*;
 
-use oxide_sql_core::ast::{BinaryOp, DataType, Expr, Literal};
+use oxide_sql_core::ast::{BinaryOp, DataType, Expr, Ident, Literal, QuoteStyle};
 
 // ===================================================================
 // IS NULL / IS NOT NULL
@@ -74,6 +74,16 @@ fn between_with_expressions() {
     round_trip("SELECT * FROM t WHERE x BETWEEN 1 + 1 AND 5 * 2");
 }
 
+#[test]
+fn not_between() {
+    let s = parse_select("SELECT * FROM t WHERE x NOT BETWEEN 1 AND 10");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Between { negated: true, .. })
+    ));
+    round_trip("SELECT * FROM t WHERE x NOT BETWEEN 1 AND 10");
+}
+
 // ===================================================================
 // IN
 // ===================================================================
@@ -105,6 +115,18 @@ fn in_strings() {
     round_trip("SELECT * FROM t WHERE name IN ('a', 'b')");
 }
 
+#[test]
+fn not_in() {
+    let s = parse_select("SELECT * FROM t WHERE id NOT IN (1, 2)");
+    if let Some(Expr::In { list, negated, .. }) = &s.where_clause {
+        assert!(negated);
+        assert_eq!(list.len(), 2);
+    } else {
+        panic!("Expected NOT IN");
+    }
+    round_trip("SELECT * FROM t WHERE id NOT IN (1, 2)");
+}
+
 // ===================================================================
 // CASE expressions
 // ===================================================================
@@ -235,3 +257,73 @@ fn cast_to_text() {
     }
     round_trip("SELECT CAST(42 AS TEXT) FROM t");
 }
+
+// ===================================================================
+// COLLATE
+// ===================================================================
+
+#[test]
+fn collate_in_where() {
+    let s = parse_select("SELECT * FROM users WHERE name COLLATE NOCASE = 'ada'");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Binary { op: BinaryOp::Eq, left, .. })
+            if matches!(left.as_ref(), Expr::Collate { expr, collation }
+                if matches!(expr.as_ref(), Expr::Column { name, .. } if name == "name")
+                    && collation == "NOCASE")
+    ));
+    round_trip("SELECT * FROM users WHERE name COLLATE NOCASE = 'ada'");
+}
+
+#[test]
+fn collate_in_order_by_keeps_quotes() {
+    let s = parse_select(r#"SELECT * FROM users ORDER BY name COLLATE "de_DE" DESC"#);
+    assert!(matches!(
+        &s.order_by[0].expr,
+        Expr::Collate { collation, .. }
+            if *collation == Ident::quoted("de_DE", QuoteStyle::Double)
+    ));
+    round_trip(r#"SELECT * FROM users ORDER BY name COLLATE "de_DE" DESC"#);
+}
+
+#[test]
+fn collate_binds_to_operand() {
+    let s = parse_select("SELECT a || b COLLATE NOCASE FROM t");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Binary { op: BinaryOp::Concat, right, .. }
+            if matches!(right.as_ref(), Expr::Collate { .. })
+    ));
+    round_trip("SELECT a || b COLLATE NOCASE FROM t");
+}
+
+#[test]
+fn collate_requires_name() {
+    parse_err("SELECT name COLLATE FROM t");
+    parse_err("SELECT name COLLATE 'x' FROM t");
+}
+
+// ===================================================================
+// Standalone expressions
+// ===================================================================
+
+#[test]
+fn parse_standalone_expr() {
+    let expr = oxide_sql_core::Parser::new("lower(email) = 'a' AND active")
+        .parse_expr()
+        .unwrap();
+    assert!(matches!(
+        expr,
+        Expr::Binary {
+            op: BinaryOp::And,
+            ..
+        }
+    ));
+    assert_eq!(expr.to_string(), "lower(email) = 'a' AND active");
+}
+
+#[test]
+fn parse_standalone_expr_rejects_trailing_input() {
+    assert!(oxide_sql_core::Parser::new("a = 1 b").parse_expr().is_err());
+    assert!(oxide_sql_core::Parser::new("").parse_expr().is_err());
+}
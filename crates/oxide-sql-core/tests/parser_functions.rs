@@ -1,10 +1,10 @@
-//! Tests for function calls (aggregates and custom), subqueries,
-//! EXISTS, and parameters.
+//! Tests for function calls (aggregates, FILTER and WITHIN GROUP, and
+//! custom functions), subqueries, EXISTS, and parameters.
 
 mod common;
 use common::*;
 
-use oxide_sql_core::ast::{Expr, FunctionCall};
+use oxide_sql_core::ast::{BinaryOp, Expr, FunctionCall, OrderDirection};
 
 // ===================================================================
 // Aggregate functions
@@ -17,10 +17,14 @@ fn function_count_star() {
         name,
         args,
         distinct,
+        within_group,
+        filter,
     }) = &s.columns[0].expr
     {
         assert_eq!(name, "COUNT");
         assert!(!distinct);
+        assert!(within_group.is_empty());
+        assert!(filter.is_none());
         assert_eq!(args.len(), 1);
         assert!(matches!(args[0], Expr::Wildcard { table: None }));
     } else {
@@ -121,6 +125,78 @@ fn function_nullif() {
     round_trip("SELECT NULLIF(x, 0) FROM t");
 }
 
+// ===================================================================
+// FILTER and WITHIN GROUP
+// ===================================================================
+
+#[test]
+fn function_filter() {
+    let s = parse_select("SELECT COUNT(*) FILTER (WHERE x > 0) FROM t");
+    if let Expr::Function(FunctionCall {
+        name,
+        filter: Some(filter),
+        within_group,
+        ..
+    }) = &s.columns[0].expr
+    {
+        assert_eq!(name, "COUNT");
+        assert!(matches!(
+            filter.as_ref(),
+            Expr::Binary {
+                op: BinaryOp::Gt,
+                ..
+            }
+        ));
+        assert!(within_group.is_empty());
+    } else {
+        panic!("Expected COUNT with FILTER");
+    }
+    round_trip("SELECT COUNT(*) FILTER (WHERE x > 0) FROM t");
+}
+
+#[test]
+fn function_within_group() {
+    let s = parse_select("SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY x DESC) FROM t");
+    if let Expr::Function(FunctionCall {
+        name,
+        args,
+        within_group,
+        filter: None,
+        ..
+    }) = &s.columns[0].expr
+    {
+        assert_eq!(name, "percentile_cont");
+        assert_eq!(args.len(), 1);
+        assert_eq!(within_group.len(), 1);
+        assert_eq!(within_group[0].direction, OrderDirection::Desc);
+    } else {
+        panic!("Expected percentile_cont with WITHIN GROUP");
+    }
+    round_trip("SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY x DESC) FROM t");
+}
+
+#[test]
+fn function_within_group_and_filter() {
+    round_trip(
+        "SELECT dept, mode() WITHIN GROUP (ORDER BY title, level) \
+         FILTER (WHERE active = TRUE) FROM staff GROUP BY dept",
+    );
+    round_trip("SELECT SUM(total) FILTER (WHERE status = 'paid') AS paid FROM orders");
+}
+
+#[test]
+fn filter_and_within_are_still_names() {
+    let s = parse_select("SELECT filter, within FROM t WHERE filter = 1");
+    assert_eq!(s.columns.len(), 2);
+    round_trip("SELECT COUNT(*) filter FROM t");
+}
+
+#[test]
+fn function_filter_requires_where() {
+    parse_err("SELECT COUNT(*) FILTER (x > 0) FROM t");
+    parse_err("SELECT percentile_cont(0.5) WITHIN GROUP (x) FROM t");
+}
+
 // ===================================================================
 // Custom functions
 // ===================================================================
@@ -159,10 +235,7 @@ fn exists_in_where() {
         "SELECT * FROM users u \
          WHERE EXISTS (SELECT 1 FROM orders o WHERE o.user_id = u.id)",
     );
-    assert!(matches!(
-        &s.where_clause,
-        Some(Expr::Function(FunctionCall { name, .. })) if name == "EXISTS"
-    ));
+    assert!(matches!(&s.where_clause, Some(Expr::Exists(_))));
     round_trip(
         "SELECT * FROM users AS u \
          WHERE EXISTS(SELECT 1 FROM orders AS o WHERE o.user_id = u.id)",
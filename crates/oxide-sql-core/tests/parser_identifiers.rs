@@ -0,0 +1,137 @@
+//! Tests for quoted, escaped, and non-ASCII identifiers, and for
+//! re-rendering them per dialect.
+
+mod common;
+use common::*;
+
+use oxide_sql_core::Parser;
+use oxide_sql_core::ast::{Expr, Ident, QuoteStyle, Statement, TableRef};
+use oxide_sql_core::dialect::{Dialect, GenericDialect};
+use oxide_sql_core::lexer::LexerOptions;
+
+/// A MySQL-like dialect that quotes identifiers with backticks.
+struct Backticks;
+
+impl Dialect for Backticks {
+    fn name(&self) -> &'static str {
+        "backticks"
+    }
+
+    fn identifier_quote(&self) -> char {
+        '`'
+    }
+}
+
+fn parse_brackets(sql: &str) -> Statement {
+    let options = LexerOptions::new().bracket_identifiers(true);
+    Parser::with_options(sql, options)
+        .parse_statement()
+        .unwrap_or_else(|e| panic!("Failed to parse: {sql}\nError: {e:?}"))
+}
+
+// ===================================================================
+// Quoting styles
+// ===================================================================
+
+#[test]
+fn double_quoted_identifier_with_escaped_quotes() {
+    let s = parse_select(r#"SELECT "weird ""name""" FROM t"#);
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Column { name, .. }
+            if *name == Ident::quoted("weird \"name\"", QuoteStyle::Double)
+    ));
+    assert_eq!(
+        parse(r#"SELECT "weird ""name""" FROM t"#).to_string(),
+        r#"SELECT "weird ""name""" FROM t"#
+    );
+}
+
+#[test]
+fn backtick_identifiers_keep_their_style() {
+    let s = parse_select("SELECT `a``b` FROM `my table`");
+    assert!(matches!(
+        &s.from,
+        Some(TableRef::Table { name, .. })
+            if name.quote_style == Some(QuoteStyle::Backtick) && name == "my table"
+    ));
+    assert_eq!(s.to_string(), "SELECT `a``b` FROM `my table`");
+}
+
+#[test]
+fn bracket_identifiers() {
+    let stmt = parse_brackets("SELECT [first name], [a]]b] FROM [order] AS [o]");
+    let Statement::Select(s) = &stmt else {
+        panic!("Expected SELECT, got {stmt:?}");
+    };
+    assert!(matches!(
+        &s.columns[1].expr,
+        Expr::Column { name, .. } if *name == Ident::quoted("a]b", QuoteStyle::Bracket)
+    ));
+    let rendered = stmt.to_string();
+    assert_eq!(rendered, "SELECT [first name], [a]]b] FROM [order] AS [o]");
+    assert_eq!(parse_brackets(&rendered), stmt);
+}
+
+#[test]
+fn bracket_identifiers_are_off_by_default() {
+    parse_err("SELECT [first name] FROM t");
+}
+
+#[test]
+fn quoted_reserved_words_round_trip() {
+    round_trip(r#"SELECT "order"."select" FROM "order" WHERE "group" = 1"#);
+    round_trip(r#"INSERT INTO "table" ("from", "to") VALUES (1, 2)"#);
+    round_trip(r#"UPDATE "user" SET "value" = 1 WHERE "key" = 2"#);
+}
+
+#[test]
+fn non_ascii_identifiers() {
+    let s = parse_select("SELECT café, \"日付 列\" FROM 名前");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Column { name, .. } if *name == Ident::new("café")
+    ));
+    round_trip("SELECT café, \"日付 列\" FROM 名前");
+}
+
+// ===================================================================
+// Per-dialect rendering
+// ===================================================================
+
+#[test]
+fn to_sql_switches_quote_style() {
+    let stmt = parse(r#"SELECT `first name`, "a""b", id FROM users"#);
+    assert_eq!(
+        stmt.to_sql(&GenericDialect::new()),
+        r#"SELECT "first name", "a""b", id FROM users"#
+    );
+    assert_eq!(
+        stmt.to_sql(&Backticks),
+        "SELECT `first name`, `a\"b`, id FROM users"
+    );
+}
+
+#[test]
+fn to_sql_quotes_reserved_words() {
+    let stmt = parse_brackets("SELECT [order].id FROM [order] JOIN items USING ([group])");
+    assert_eq!(
+        stmt.to_sql(&GenericDialect::new()),
+        r#"SELECT "order".id FROM "order" INNER JOIN items USING ("group")"#
+    );
+}
+
+#[test]
+fn requote_reaches_nested_queries() {
+    let mut stmt = parse(
+        "INSERT INTO `t` (`a`) SELECT `b` FROM (SELECT `c` FROM `u`) AS `s` \
+         WHERE EXISTS (SELECT `e` FROM `v` WHERE `d` = 1)",
+    );
+    stmt.requote(&GenericDialect::new());
+    let rendered = stmt.to_string();
+    assert!(!rendered.contains('`'), "{rendered}");
+    assert_eq!(
+        rendered,
+        r#"INSERT INTO "t" ("a") SELECT "b" FROM (SELECT "c" FROM "u") AS "s" WHERE EXISTS(SELECT "e" FROM "v" WHERE "d" = 1)"#
+    );
+}
@@ -5,7 +5,7 @@
 mod common;
 use common::*;
 
-use oxide_sql_core::ast::{BinaryOp, Expr, InsertSource};
+use oxide_sql_core::ast::{BinaryOp, ConflictAction, Expr, InsertSource};
 
 #[test]
 fn insert_with_columns() {
@@ -112,3 +112,31 @@ fn insert_with_parameters() {
     }
     round_trip("INSERT INTO users (name, email) VALUES (?, ?)");
 }
+
+#[test]
+fn insert_on_conflict_do_nothing() {
+    let i = parse_insert("INSERT INTO users (email) VALUES ('a') ON CONFLICT (email) DO NOTHING");
+    let conflict = i.on_conflict.expect("Expected ON CONFLICT");
+    assert_eq!(conflict.columns, vec![String::from("email")]);
+    assert_eq!(conflict.action, ConflictAction::DoNothing);
+    round_trip("INSERT INTO users (email) VALUES ('a') ON CONFLICT (email) DO NOTHING");
+}
+
+#[test]
+fn insert_on_conflict_do_update() {
+    let i = parse_insert(
+        "INSERT INTO users (email, name) VALUES (?, ?) \
+         ON CONFLICT (email) do update SET name = ?, visits = visits + 1",
+    );
+    let conflict = i.on_conflict.expect("Expected ON CONFLICT");
+    if let ConflictAction::DoUpdate(assignments) = &conflict.action {
+        assert_eq!(assignments.len(), 2);
+        assert_eq!(assignments[1].column, "visits");
+    } else {
+        panic!("Expected DO UPDATE");
+    }
+    round_trip(
+        "INSERT INTO users (email, name) VALUES (?, ?) \
+         ON CONFLICT (email) DO UPDATE SET name = ?, visits = visits + 1",
+    );
+}
@@ -1,10 +1,29 @@
-//! Tests for literal parsing: integers, floats, strings, blobs,
-//! booleans, and NULL.
+//! Tests for literal parsing: integers, floats, big and hexadecimal
+//! numbers, strings, blobs, booleans, NULL, and typed temporal literals.
 
 mod common;
 use common::*;
 
-use oxide_sql_core::ast::{Expr, Literal, UnaryOp};
+use oxide_sql_core::Parser;
+use oxide_sql_core::ast::{Expr, Literal, Statement, UnaryOp};
+use oxide_sql_core::lexer::LexerOptions;
+
+fn parse_postgres(sql: &str) -> Statement {
+    Parser::with_options(sql, LexerOptions::postgres())
+        .parse_statement()
+        .unwrap_or_else(|e| panic!("Failed to parse: {sql}\nError: {e:?}"))
+}
+
+/// Returns the string value of the first selected column.
+fn first_string(stmt: &Statement) -> &str {
+    match stmt {
+        Statement::Select(s) => match &s.columns[0].expr {
+            Expr::Literal(Literal::String(value)) => value,
+            other => panic!("Expected a string literal, got {other:?}"),
+        },
+        other => panic!("Expected SELECT, got {other:?}"),
+    }
+}
 
 #[test]
 fn literal_integer() {
@@ -84,3 +103,192 @@ fn literal_null() {
     assert!(matches!(&s.columns[0].expr, Expr::Literal(Literal::Null)));
     round_trip("SELECT NULL");
 }
+
+// ===================================================================
+// Numeric edge cases
+// ===================================================================
+
+#[test]
+fn literal_hex_integer() {
+    let s = parse_select("SELECT 0x1F, 0XFFFFFFFFFFFFFFFF");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Literal(Literal::Integer(31))
+    ));
+    assert!(matches!(
+        &s.columns[1].expr,
+        Expr::Literal(Literal::Integer(-1))
+    ));
+    assert_eq!(
+        parse("SELECT 0x1F, 0XFFFFFFFFFFFFFFFF").to_string(),
+        "SELECT 31, -1"
+    );
+}
+
+#[test]
+fn literal_hex_integer_too_large() {
+    parse_err("SELECT 0x10000000000000000");
+}
+
+#[test]
+fn literal_exponent_floats() {
+    let s = parse_select("SELECT 1e10, 1.5E-3, .5, 2E+2");
+    let values: Vec<f64> = s
+        .columns
+        .iter()
+        .map(|c| match c.expr {
+            Expr::Literal(Literal::Float(f)) => f,
+            ref other => panic!("Expected float literal, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(values, [1e10, 1.5e-3, 0.5, 200.0]);
+    round_trip("SELECT 1e10, 1.5E-3, .5, 2E+2");
+}
+
+#[test]
+fn literal_float_stays_float_when_rendered() {
+    let stmt = parse("SELECT 1e10, 1e300, 2.0");
+    let rendered = stmt.to_string();
+    assert_eq!(rendered, "SELECT 10000000000.0, 1e300, 2.0");
+    assert_eq!(parse(&rendered), stmt);
+}
+
+#[test]
+fn literal_missing_exponent_digits() {
+    parse_err("SELECT 1e");
+    parse_err("SELECT 1.5E- FROM t");
+}
+
+#[test]
+fn literal_big_number() {
+    let s = parse_select("SELECT 18446744073709551616, -99999999999999999999");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Literal(Literal::BigNumber(v)) if v == "18446744073709551616"
+    ));
+    assert!(matches!(
+        &s.columns[1].expr,
+        Expr::Unary { op: UnaryOp::Neg, operand }
+            if matches!(operand.as_ref(), Expr::Literal(Literal::BigNumber(v)) if v == "99999999999999999999")
+    ));
+    round_trip("SELECT 18446744073709551616, -99999999999999999999");
+}
+
+#[test]
+fn literal_big_number_in_insert() {
+    let i = parse_insert("INSERT INTO t (id, x) VALUES (123456789012345678901234567890, 1e999)");
+    round_trip("INSERT INTO t (id, x) VALUES (123456789012345678901234567890, 1e999)");
+    assert_eq!(
+        i.to_string(),
+        "INSERT INTO t (id, x) VALUES (123456789012345678901234567890, 1e999)"
+    );
+}
+
+// ===================================================================
+// Typed temporal literals
+// ===================================================================
+
+#[test]
+fn literal_date_time_timestamp() {
+    let s = parse_select("SELECT DATE '2024-01-01', TIME '10:00', TIMESTAMP '2024-01-01 10:00:00'");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Literal(Literal::Date(v)) if v == "2024-01-01"
+    ));
+    assert!(matches!(
+        &s.columns[1].expr,
+        Expr::Literal(Literal::Time(v)) if v == "10:00"
+    ));
+    assert!(matches!(
+        &s.columns[2].expr,
+        Expr::Literal(Literal::Timestamp(v)) if v == "2024-01-01 10:00:00"
+    ));
+    round_trip("SELECT DATE '2024-01-01', TIME '10:00', TIMESTAMP '2024-01-01 10:00:00'");
+}
+
+#[test]
+fn literal_interval() {
+    let s = parse_select("SELECT * FROM t WHERE created_at > NOW() - INTERVAL '1 day'");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Binary { right, .. })
+            if matches!(right.as_ref(), Expr::Binary { right, .. }
+                if matches!(right.as_ref(), Expr::Literal(Literal::Interval(v)) if v == "1 day"))
+    ));
+    round_trip("SELECT * FROM t WHERE created_at > NOW() - INTERVAL '1 day'");
+}
+
+#[test]
+fn literal_temporal_in_filters() {
+    let s = parse_select(
+        "SELECT id FROM events WHERE day BETWEEN DATE '2024-01-01' AND DATE '2024-01-31'",
+    );
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Between { low, .. })
+            if matches!(low.as_ref(), Expr::Literal(Literal::Date(_)))
+    ));
+    round_trip("SELECT id FROM events WHERE day BETWEEN DATE '2024-01-01' AND DATE '2024-01-31'");
+    round_trip("UPDATE tasks SET due = DATE '2024-12-31' WHERE id = 1");
+}
+
+#[test]
+fn literal_temporal_escapes_quotes() {
+    let stmt = parse("SELECT DATE 'it''s'");
+    assert_eq!(stmt.to_string(), "SELECT DATE 'it''s'");
+}
+
+#[test]
+fn interval_is_still_a_column_name() {
+    let s = parse_select("SELECT interval FROM schedules WHERE interval > 5");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Column { name, .. } if name == "interval"
+    ));
+    round_trip("SELECT \"interval\" FROM t");
+}
+
+#[test]
+fn literal_temporal_requires_string() {
+    parse_err("SELECT DATE 5");
+    parse_err("SELECT TIMESTAMP");
+}
+
+// ===================================================================
+// Escaped and PostgreSQL string forms
+// ===================================================================
+
+#[test]
+fn literal_string_doubled_quotes() {
+    let stmt = parse("SELECT 'it''s ''quoted'''");
+    assert_eq!(first_string(&stmt), "it's 'quoted'");
+    assert_eq!(stmt.to_string(), "SELECT 'it''s ''quoted'''");
+    round_trip("SELECT 'it''s ''quoted'''");
+}
+
+#[test]
+fn literal_escape_string_round_trips_as_standard_string() {
+    let stmt = parse_postgres(r"SELECT E'line\none\tit\'s \\'");
+    assert_eq!(first_string(&stmt), "line\none\tit's \\");
+
+    // Rendered as a standard string, which needs no escapes besides
+    // doubled quotes and reads back to the same value.
+    let rendered = stmt.to_string();
+    assert_eq!(rendered, "SELECT 'line\none\tit''s \\'");
+    assert_eq!(parse(&rendered), stmt);
+}
+
+#[test]
+fn literal_dollar_quoted_string() {
+    let stmt = parse_postgres("SELECT $body$it's a $$ string$body$ FROM t");
+    assert_eq!(first_string(&stmt), "it's a $$ string");
+    let rendered = stmt.to_string();
+    assert_eq!(rendered, "SELECT 'it''s a $$ string' FROM t");
+    assert_eq!(parse_postgres(&rendered), stmt);
+}
+
+#[test]
+fn literal_postgres_strings_need_options() {
+    parse_err("SELECT $$x$$");
+    parse_err("SELECT E'x'");
+}
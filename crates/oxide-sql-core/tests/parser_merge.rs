@@ -0,0 +1,136 @@
+//! Tests for MERGE statements: targets and sources, WHEN clauses, and
+//! their actions.
+
+mod common;
+use common::*;
+
+use oxide_sql_core::ast::{BinaryOp, Expr, MergeAction, TableRef};
+
+// ===================================================================
+// Targets and sources
+// ===================================================================
+
+#[test]
+fn merge_update_and_insert() {
+    let m = parse_merge(
+        "MERGE INTO users u USING staging s ON u.id = s.id \
+         WHEN MATCHED THEN UPDATE SET name = s.name \
+         WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)",
+    );
+    assert_eq!(m.table, "users");
+    assert_eq!(m.alias.as_deref(), Some("u"));
+    assert!(matches!(
+        &m.source,
+        TableRef::Table { name, alias: Some(alias), .. } if name == "staging" && alias == "s"
+    ));
+    assert!(matches!(
+        m.on,
+        Expr::Binary {
+            op: BinaryOp::Eq,
+            ..
+        }
+    ));
+    assert_eq!(m.clauses.len(), 2);
+    assert!(m.clauses[0].matched);
+    assert!(matches!(&m.clauses[0].action, MergeAction::Update(a) if a.len() == 1));
+    assert!(!m.clauses[1].matched);
+    assert!(matches!(
+        &m.clauses[1].action,
+        MergeAction::Insert { columns, values } if columns.len() == 2 && values.len() == 2
+    ));
+    round_trip(
+        "MERGE INTO users u USING staging s ON u.id = s.id \
+         WHEN MATCHED THEN UPDATE SET name = s.name \
+         WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)",
+    );
+}
+
+#[test]
+fn merge_schema_and_subquery_source() {
+    let m = parse_merge(
+        "MERGE INTO main.stock AS t \
+         USING (SELECT sku, SUM(qty) AS qty FROM moves GROUP BY sku) AS m ON t.sku = m.sku \
+         WHEN MATCHED THEN UPDATE SET qty = t.qty + m.qty",
+    );
+    assert_eq!(m.schema.as_deref(), Some("main"));
+    assert!(matches!(&m.source, TableRef::Subquery { alias, .. } if alias == "m"));
+    round_trip(
+        "MERGE INTO main.stock AS t \
+         USING (SELECT sku, SUM(qty) AS qty FROM moves GROUP BY sku) AS m ON t.sku = m.sku \
+         WHEN MATCHED THEN UPDATE SET qty = t.qty + m.qty",
+    );
+}
+
+// ===================================================================
+// WHEN clauses
+// ===================================================================
+
+#[test]
+fn merge_conditions_delete_and_do_nothing() {
+    let m = parse_merge(
+        "MERGE INTO t USING s ON t.id = s.id \
+         WHEN MATCHED AND s.deleted = TRUE THEN DELETE \
+         WHEN MATCHED THEN DO NOTHING \
+         WHEN NOT MATCHED AND s.active = TRUE THEN INSERT VALUES (s.id) \
+         WHEN NOT MATCHED THEN DO NOTHING",
+    );
+    assert_eq!(m.clauses.len(), 4);
+    assert!(m.clauses[0].condition.is_some());
+    assert_eq!(m.clauses[0].action, MergeAction::Delete);
+    assert_eq!(m.clauses[1].action, MergeAction::DoNothing);
+    assert!(matches!(
+        &m.clauses[2].action,
+        MergeAction::Insert { columns, .. } if columns.is_empty()
+    ));
+    assert_eq!(m.clauses[3].action, MergeAction::DoNothing);
+    round_trip(
+        "MERGE INTO t USING s ON t.id = s.id \
+         WHEN MATCHED AND s.deleted = TRUE THEN DELETE \
+         WHEN MATCHED THEN DO NOTHING \
+         WHEN NOT MATCHED AND s.active = TRUE THEN INSERT VALUES (s.id) \
+         WHEN NOT MATCHED THEN DO NOTHING",
+    );
+}
+
+#[test]
+fn merge_with_parameters() {
+    let m = parse_merge(
+        "MERGE INTO t USING s ON t.id = s.id \
+         WHEN MATCHED THEN UPDATE SET a = ?, b = ? \
+         WHEN NOT MATCHED THEN INSERT (a) VALUES (?)",
+    );
+    assert!(matches!(
+        &m.clauses[1].action,
+        MergeAction::Insert { values, .. }
+            if matches!(values[0], Expr::Parameter { position: 3, .. })
+    ));
+}
+
+#[test]
+fn merge_is_still_a_name() {
+    let s = parse_select("SELECT merge FROM merge WHERE matched = 1");
+    assert_eq!(s.columns.len(), 1);
+    round_trip("INSERT INTO merge (matched) VALUES (1)");
+}
+
+// ===================================================================
+// Errors
+// ===================================================================
+
+#[test]
+fn merge_requires_a_when_clause() {
+    parse_err("MERGE INTO t USING s ON t.id = s.id");
+}
+
+#[test]
+fn merge_rejects_mismatched_actions() {
+    parse_err("MERGE INTO t USING s ON t.id = s.id WHEN MATCHED THEN INSERT VALUES (1)");
+    parse_err("MERGE INTO t USING s ON t.id = s.id WHEN NOT MATCHED THEN DELETE");
+    parse_err("MERGE INTO t USING s ON t.id = s.id WHEN NOT MATCHED THEN UPDATE SET a = 1");
+}
+
+#[test]
+fn merge_requires_using_and_on() {
+    parse_err("MERGE INTO t WHEN MATCHED THEN DELETE");
+    parse_err("MERGE INTO t USING s WHEN MATCHED THEN DELETE");
+}
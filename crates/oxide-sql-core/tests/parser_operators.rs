@@ -1,5 +1,5 @@
 //! Tests for binary operators (arithmetic, comparison, logical,
-//! bitwise), unary operators, and operator precedence.
+//! bitwise, JSON), unary operators, and operator precedence.
 
 mod common;
 use common::*;
@@ -213,6 +213,27 @@ fn binop_like() {
     round_trip("SELECT * FROM t WHERE name LIKE '%test%'");
 }
 
+#[test]
+fn binop_not_like() {
+    let s = parse_select("SELECT * FROM t WHERE name NOT LIKE 'a%'");
+    if let Some(Expr::Unary {
+        op: UnaryOp::Not,
+        operand,
+    }) = &s.where_clause
+    {
+        assert!(matches!(
+            operand.as_ref(),
+            Expr::Binary {
+                op: BinaryOp::Like,
+                ..
+            }
+        ));
+    } else {
+        panic!("Expected NOT LIKE");
+    }
+    round_trip("SELECT * FROM t WHERE name NOT LIKE 'a%'");
+}
+
 // ===================================================================
 // Bitwise operators
 // ===================================================================
@@ -269,6 +290,72 @@ fn binop_right_shift() {
     round_trip("SELECT 16 >> 2");
 }
 
+// ===================================================================
+// JSON operators
+// ===================================================================
+
+#[test]
+fn binop_json_operators() {
+    let s =
+        parse_select("SELECT data -> 'a', data ->> 'b', data #> '{a,b}', data #>> '{a,b}' FROM t");
+    let ops: Vec<BinaryOp> = s
+        .columns
+        .iter()
+        .map(|c| match &c.expr {
+            Expr::Binary { op, .. } => *op,
+            other => panic!("Expected binary expression, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        ops,
+        [
+            BinaryOp::JsonGet,
+            BinaryOp::JsonGetText,
+            BinaryOp::JsonPath,
+            BinaryOp::JsonPathText,
+        ]
+    );
+    round_trip("SELECT data -> 'a', data ->> 'b', data #> '{a,b}', data #>> '{a,b}' FROM t");
+}
+
+#[test]
+fn binop_json_binds_tighter_than_comparison() {
+    // data->>'age' > 18 parses as (data->>'age') > 18
+    let s = parse_select("SELECT * FROM t WHERE data->>'age' > 18");
+    assert!(matches!(
+        &s.where_clause,
+        Some(Expr::Binary { op: BinaryOp::Gt, left, .. })
+            if matches!(left.as_ref(), Expr::Binary { op: BinaryOp::JsonGetText, .. })
+    ));
+    round_trip("SELECT * FROM t WHERE data->>'age' > 18");
+}
+
+#[test]
+fn binop_json_chains_left_to_right() {
+    // data->'a'->>'b' parses as (data->'a')->>'b'
+    let s = parse_select("SELECT data->'a'->>'b' || '!' FROM t");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Binary { op: BinaryOp::Concat, left, .. }
+            if matches!(left.as_ref(), Expr::Binary { op: BinaryOp::JsonGetText, left, .. }
+                if matches!(left.as_ref(), Expr::Binary { op: BinaryOp::JsonGet, .. }))
+    ));
+    assert_eq!(
+        parse("SELECT data->'a'->>'b' FROM t").to_string(),
+        "SELECT data -> 'a' ->> 'b' FROM t"
+    );
+}
+
+#[test]
+fn binop_json_with_array_index() {
+    let s = parse_select("SELECT tags->0 FROM t");
+    assert!(matches!(
+        &s.columns[0].expr,
+        Expr::Binary { op: BinaryOp::JsonGet, right, .. }
+            if matches!(right.as_ref(), Expr::Literal(Literal::Integer(0)))
+    ));
+}
+
 // ===================================================================
 // Unary operators
 // ===================================================================
@@ -286,6 +373,14 @@ fn unary_neg() {
     round_trip("SELECT -x FROM t");
 }
 
+#[test]
+fn unary_double_neg_is_not_a_comment() {
+    let s = parse_select("SELECT - -x FROM t");
+    assert_eq!(s.to_string(), "SELECT - -x FROM t");
+    round_trip("SELECT - -x FROM t");
+    round_trip("SELECT - -1 + 2");
+}
+
 #[test]
 fn unary_not() {
     let s = parse_select("SELECT * FROM t WHERE NOT active");
@@ -0,0 +1,117 @@
+//! Tests that parsing with a `NodePool` gives the same statements as
+//! parsing without one.
+
+use oxide_sql_core::Parser;
+use oxide_sql_core::ast::Statement;
+use oxide_sql_core::parser::NodePool;
+
+/// Statements covering every expression node the parser boxes.
+const CORPUS: &[&str] = &[
+    "SELECT a + b * c - -d, ~e FROM t WHERE NOT (x = 1 OR y <> 2) AND z IS NOT NULL",
+    "SELECT c.name, COUNT(o.id) AS order_count, SUM(o.total) AS revenue \
+     FROM customers c LEFT JOIN orders o ON c.id = o.customer_id \
+     WHERE c.active = 1 GROUP BY c.name HAVING COUNT(o.id) > 0 \
+     ORDER BY revenue DESC LIMIT 100 OFFSET 10",
+    "SELECT * FROM t WHERE id IN (1, 2, 3) AND name NOT IN ('a', lower(?))",
+    "SELECT * FROM t WHERE age BETWEEN 18 AND 65 AND score NOT BETWEEN 0 AND 10",
+    "SELECT CASE status WHEN 1 THEN 'on' WHEN 0 THEN 'off' ELSE 'unknown' END FROM t",
+    "SELECT CASE WHEN a > b THEN a ELSE b END, CAST(price * 100 AS INTEGER) FROM t",
+    "SELECT name COLLATE NOCASE FROM t WHERE name LIKE 'a%' AND code NOT LIKE 'x_'",
+    "SELECT COUNT(*) FILTER (WHERE x > 0) FROM t",
+    "SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY x DESC) FROM t",
+    "SELECT * FROM t WHERE id = ANY(ARRAY[1, 2 + 3]) AND tag <> ALL(tags)",
+    "SELECT * FROM t WHERE EXISTS (SELECT 1 FROM u WHERE u.id = t.id + 1)",
+    "SELECT (SELECT MAX(v) FROM w WHERE w.k = t.k) * 2 FROM (SELECT k FROM s WHERE k > 0) AS t",
+    "SELECT data->'a'->>'b', flags & 4 | 1, bits << 2 >> 1, a || b FROM t",
+    "SELECT a, b, SUM(c) FROM t GROUP BY ROLLUP (a, b) HAVING SUM(c) > ?",
+    "INSERT INTO users (email, visits) VALUES (:email, 1 + 1), ('b', -2) \
+     ON CONFLICT (email) DO UPDATE SET name = ?, visits = visits + 1",
+    "INSERT INTO archive SELECT * FROM events WHERE created < now() - 30",
+    "UPDATE t SET a = a + 1, b = CASE WHEN b IS NULL THEN 0 ELSE b END \
+     FROM u WHERE t.id = u.id AND u.x BETWEEN 1 AND 2",
+    "DELETE FROM t WHERE (a = 1 AND b = 2) OR c = (SELECT c FROM d WHERE e < 3)",
+    "MERGE INTO users u USING staging s ON u.id = s.id AND s.ok = TRUE \
+     WHEN MATCHED AND s.deleted = 1 THEN DELETE \
+     WHEN MATCHED THEN UPDATE SET name = s.name, n = u.n + 1 \
+     WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)",
+];
+
+fn parse(sql: &str) -> Statement {
+    Parser::new(sql)
+        .arrays(true)
+        .parse_statement()
+        .unwrap_or_else(|e| panic!("Failed to parse: {sql}\nError: {e:?}"))
+}
+
+/// Parses `sql` with nodes from `pool`, returning the statement and the
+/// pool.
+fn parse_pooled(sql: &str, pool: NodePool) -> (Statement, NodePool) {
+    let mut parser = Parser::new(sql).arrays(true).with_pool(pool);
+    let stmt = parser
+        .parse_statement()
+        .unwrap_or_else(|e| panic!("Failed to parse: {sql}\nError: {e:?}"));
+    (stmt, parser.into_pool())
+}
+
+#[test]
+fn pooled_parsing_matches_unpooled_parsing() {
+    let mut pool = NodePool::new();
+    // The second pass parses entirely from reclaimed nodes.
+    for _ in 0..2 {
+        for sql in CORPUS {
+            let (stmt, rest) = parse_pooled(sql, pool);
+            assert_eq!(stmt, parse(sql), "{sql}");
+            assert_eq!(stmt.to_string(), parse(sql).to_string());
+            pool = rest;
+            pool.reclaim(stmt);
+        }
+    }
+}
+
+#[test]
+fn reclaim_returns_every_node() {
+    for sql in CORPUS {
+        let (stmt, pool) = parse_pooled(sql, NodePool::new());
+        assert!(pool.is_empty());
+        let mut pool = pool;
+        pool.reclaim(stmt);
+        let nodes = pool.len();
+
+        // Parsing the statement again uses exactly the reclaimed nodes.
+        let (stmt, pool) = parse_pooled(sql, pool);
+        assert!(pool.is_empty(), "{sql}");
+        let mut pool = pool;
+        pool.reclaim(stmt);
+        assert_eq!(pool.len(), nodes, "{sql}");
+    }
+}
+
+#[test]
+fn pooled_nodes_do_not_leak_between_statements() {
+    let (first, mut pool) = parse_pooled("SELECT a + b FROM t", NodePool::new());
+    pool.reclaim(first);
+    let (second, pool) = parse_pooled("SELECT x * y FROM t", pool);
+    assert_eq!(second.to_string(), "SELECT x * y FROM t");
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn reclaim_expr_returns_nested_nodes() {
+    let expr = Parser::new("-(a + b) BETWEEN 1 AND 2")
+        .parse_expr()
+        .unwrap();
+    let mut pool = NodePool::new();
+    pool.reclaim_expr(expr);
+    // BETWEEN's three operands, the negation's operand, the parentheses'
+    // inner expression, and the two operands of `+`.
+    assert_eq!(pool.len(), 7);
+}
+
+#[test]
+fn preallocated_pool_is_used_first() {
+    let pool = NodePool::with_capacity(8);
+    assert_eq!(pool.len(), 8);
+    let (stmt, pool) = parse_pooled("SELECT a + b FROM t", pool);
+    assert_eq!(stmt, parse("SELECT a + b FROM t"));
+    assert_eq!(pool.len(), 6);
+}
@@ -0,0 +1,504 @@
+//! Property-based tests for the lexer and parser.
+//!
+//! Statements are generated at random from the AST with proptest
+//! strategies and rendered with `Display`. These tests verify that:
+//! - Every generated statement parses
+//! - parse → print → parse reaches a fixed point
+//! - The lexer and parser never panic on arbitrary input, including
+//!   truncated and mutated statements
+//!
+//! On failure proptest shrinks the statement to a minimal one and prints
+//! it along with the seed to replay it.
+
+use std::ops::Range;
+
+use oxide_sql_core::ast::{
+    BinaryOp, ConflictAction, DataType, DeleteStatement, Expr, FunctionCall, GroupByItem, Ident,
+    InsertSource, InsertStatement, JoinClause, JoinType, Literal, NullOrdering, OnConflict,
+    OrderBy, OrderDirection, QuoteStyle, SelectColumn, SelectStatement, Statement, TableRef,
+    UnaryOp, UpdateAssignment, UpdateStatement,
+};
+use oxide_sql_core::{Lexer, Parser, TokenKind};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::{Index, select};
+
+const CASES: u32 = 500;
+
+/// Runs `cases` cases. Statements are deep trees, so shrinking one takes
+/// more steps than proptest's default budget of four per case.
+fn config(cases: u32) -> ProptestConfig {
+    ProptestConfig {
+        cases,
+        max_shrink_iters: 100_000,
+        ..ProptestConfig::default()
+    }
+}
+
+// =============================================================================
+// Strategies
+// =============================================================================
+
+const NAMES: &[&str] = &["id", "name", "email", "total", "a", "b", "c", "user_id"];
+const QUOTED_NAMES: &[&str] = &["order", "my col", "wéird \"name\"", "a`b", "1st", "名前"];
+const TABLES: &[&str] = &["users", "orders", "t", "items"];
+const FUNCTIONS: &[&str] = &["lower", "upper", "abs", "length", "COALESCE", "NULLIF"];
+const AGGREGATES: &[&str] = &["COUNT", "SUM", "AVG", "MIN", "MAX"];
+const STRINGS: &[&str] = &["", "x", "it's", "''", "héllo wörld", "a;b", "--", "\"q\""];
+
+const BINARY_OPS: &[BinaryOp] = &[
+    BinaryOp::Add,
+    BinaryOp::Sub,
+    BinaryOp::Mul,
+    BinaryOp::Div,
+    BinaryOp::Mod,
+    BinaryOp::Eq,
+    BinaryOp::NotEq,
+    BinaryOp::Lt,
+    BinaryOp::LtEq,
+    BinaryOp::Gt,
+    BinaryOp::GtEq,
+    BinaryOp::And,
+    BinaryOp::Or,
+    BinaryOp::Concat,
+    BinaryOp::Like,
+    BinaryOp::BitAnd,
+    BinaryOp::BitOr,
+    BinaryOp::LeftShift,
+    BinaryOp::RightShift,
+];
+
+fn name() -> impl Strategy<Value = Ident> {
+    prop_oneof![
+        85 => select(NAMES).prop_map(Ident::new),
+        15 => (
+            select(QUOTED_NAMES),
+            select(&[QuoteStyle::Double, QuoteStyle::Backtick][..]),
+        )
+            .prop_map(|(name, style)| Ident::quoted(name, style)),
+    ]
+}
+
+fn literal() -> impl Strategy<Value = Literal> {
+    prop_oneof![
+        (0..1_000_000_i64).prop_map(Literal::Integer),
+        select(&[0.5, 2.25, 1e-3, 12.75][..]).prop_map(Literal::Float),
+        select(STRINGS).prop_map(|s| Literal::String(String::from(s))),
+        vec(any::<u8>(), 0..4).prop_map(Literal::Blob),
+        any::<bool>().prop_map(Literal::Boolean),
+        Just(Literal::Null),
+    ]
+}
+
+fn data_type() -> impl Strategy<Value = DataType> {
+    prop_oneof![
+        Just(DataType::Integer),
+        Just(DataType::Bigint),
+        Just(DataType::Text),
+        (1..=255_u32).prop_map(|n| DataType::Varchar(Some(n))),
+        Just(DataType::Decimal {
+            precision: Some(10),
+            scale: Some(2),
+        }),
+        Just(DataType::Boolean),
+    ]
+}
+
+fn leaf() -> impl Strategy<Value = Expr> {
+    prop_oneof![
+        2 => literal().prop_map(Expr::Literal),
+        1 => name().prop_map(Expr::column),
+        1 => (select(TABLES), name()).prop_map(|(table, name)| Expr::qualified_column(table, name)),
+        1 => option::of(select(NAMES)).prop_map(|name| Expr::Parameter {
+            name: name.map(String::from),
+            position: 0,
+        }),
+    ]
+}
+
+/// Parenthesizes an operand of an operator if it is itself an operator
+/// expression. `Display` does not add parentheses, so the AST must carry
+/// them explicitly, just as the parser records them.
+fn operand(expr: Expr) -> Expr {
+    match expr {
+        expr @ (Expr::Binary { .. }
+        | Expr::Unary { .. }
+        | Expr::IsNull { .. }
+        | Expr::In { .. }
+        | Expr::Between { .. }) => Expr::Paren(Box::new(expr)),
+        expr => expr,
+    }
+}
+
+fn function(inner: &BoxedStrategy<Expr>) -> impl Strategy<Value = FunctionCall> + use<> {
+    let scalar =
+        (select(FUNCTIONS), vec(inner.clone(), 1..=2)).prop_map(|(name, args)| FunctionCall {
+            name: String::from(name),
+            args,
+            distinct: false,
+            within_group: vec![],
+            filter: None,
+        });
+    let aggregate = (
+        select(AGGREGATES),
+        vec(inner.clone(), 1..=2),
+        prop::bool::weighted(0.3),
+        option::weighted(0.1, order_by(inner, 1..3)),
+        option::weighted(0.2, inner.clone()),
+    )
+        .prop_map(
+            |(name, args, distinct, within_group, filter)| FunctionCall {
+                name: String::from(name),
+                args,
+                distinct,
+                within_group: within_group.unwrap_or_default(),
+                filter: filter.map(Box::new),
+            },
+        );
+    prop_oneof![scalar, aggregate]
+}
+
+fn expr(depth: u32) -> BoxedStrategy<Expr> {
+    if depth == 0 {
+        return leaf().boxed();
+    }
+    let inner = expr(depth - 1);
+    let boxed = inner.clone().prop_map(Box::new);
+    let operand = inner.clone().prop_map(|e| Box::new(operand(e)));
+    let nested = if depth > 1 {
+        prop_oneof![
+            4 => boxed.clone().prop_map(Expr::Paren),
+            1 => select_statement(depth - 2).prop_map(|s| Expr::Subquery(Box::new(s))),
+            1 => select_statement(depth - 2).prop_map(|s| Expr::Exists(Box::new(s))),
+        ]
+        .boxed()
+    } else {
+        boxed.clone().prop_map(Expr::Paren).boxed()
+    };
+    prop_oneof![
+        9 => leaf(),
+        6 => (operand.clone(), select(BINARY_OPS), operand.clone())
+            .prop_map(|(left, op, right)| Expr::Binary { left, op, right }),
+        2 => (
+            select(&[UnaryOp::Neg, UnaryOp::Not, UnaryOp::BitNot][..]),
+            operand.clone(),
+        )
+            .prop_map(|(op, operand)| Expr::Unary { op, operand }),
+        2 => function(&inner).prop_map(Expr::Function),
+        2 => (operand.clone(), any::<bool>())
+            .prop_map(|(expr, negated)| Expr::IsNull { expr, negated }),
+        2 => (operand.clone(), vec(inner.clone(), 1..=3), any::<bool>())
+            .prop_map(|(expr, list, negated)| Expr::In { expr, list, negated }),
+        2 => (operand.clone(), operand.clone(), operand, any::<bool>()).prop_map(
+            |(expr, low, high, negated)| Expr::Between {
+                expr,
+                low,
+                high,
+                negated,
+            }
+        ),
+        2 => (
+            option::of(boxed.clone()),
+            vec((inner.clone(), inner.clone()), 1..=2),
+            option::of(boxed.clone()),
+        )
+            .prop_map(|(operand, when_clauses, else_clause)| Expr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            }),
+        2 => (boxed, data_type()).prop_map(|(expr, data_type)| Expr::Cast { expr, data_type }),
+        2 => nested,
+    ]
+    .boxed()
+}
+
+fn table_ref(expr: &BoxedStrategy<Expr>) -> impl Strategy<Value = TableRef> + use<> {
+    let table = (
+        select(TABLES),
+        option::weighted(0.3, select(&["x", "y", "z"][..])),
+    )
+        .prop_map(|(name, alias)| {
+            let table = TableRef::table(name);
+            match alias {
+                Some(alias) => table.alias(alias),
+                None => table,
+            }
+        });
+    let condition = prop_oneof![
+        expr.clone().prop_map(|on| (Some(on), vec![])),
+        name().prop_map(|using| (None, vec![using])),
+    ];
+    let join = prop_oneof![
+        1 => select(TABLES).prop_map(|table| JoinClause {
+            join_type: JoinType::Cross,
+            table: TableRef::table(table),
+            on: None,
+            using: vec![],
+        }),
+        4 => (
+            select(&[JoinType::Inner, JoinType::Left, JoinType::Right, JoinType::Full][..]),
+            select(TABLES),
+            condition,
+        )
+            .prop_map(|(join_type, table, (on, using))| JoinClause {
+                join_type,
+                table: TableRef::table(table),
+                on,
+                using,
+            }),
+    ];
+    (table, option::weighted(0.3, join)).prop_map(|(table, join)| match join {
+        Some(join) => TableRef::Join {
+            left: Box::new(table),
+            join: Box::new(join),
+        },
+        None => table,
+    })
+}
+
+fn select_statement(depth: u32) -> BoxedStrategy<SelectStatement> {
+    let expr = expr(depth);
+    let columns = prop_oneof![
+        15 => Just(vec![SelectColumn::new(Expr::Wildcard { table: None })]),
+        85 => vec(
+            (expr.clone(), option::weighted(0.3, select(&["v", "w"][..]))).prop_map(
+                |(expr, alias)| SelectColumn {
+                    expr,
+                    alias: alias.map(Ident::new),
+                }
+            ),
+            1..=3,
+        ),
+    ];
+    (
+        prop::bool::weighted(0.2),
+        columns,
+        option::weighted(0.8, table_ref(&expr)),
+        option::of(expr.clone()),
+        option::weighted(0.2, group_by_item()),
+        order_by(&expr, 0..3),
+        option::weighted(0.3, 0..100_i64),
+    )
+        .prop_map(
+            |(distinct, columns, from, where_clause, group_by, order_by, limit)| SelectStatement {
+                distinct,
+                columns,
+                from,
+                where_clause,
+                group_by: group_by.into_iter().collect(),
+                having: None,
+                order_by,
+                limit: limit.map(Expr::integer),
+                offset: None,
+            },
+        )
+        .boxed()
+}
+
+fn group_by_item() -> impl Strategy<Value = GroupByItem> {
+    let groups = |min: usize| vec(vec(name().prop_map(Expr::column), min..=min + 1), 1..=3);
+    prop_oneof![
+        groups(1).prop_map(GroupByItem::Rollup),
+        groups(1).prop_map(GroupByItem::Cube),
+        groups(0).prop_map(GroupByItem::GroupingSets),
+        name().prop_map(|name| GroupByItem::Expr(Expr::column(name))),
+    ]
+}
+
+fn order_by(
+    expr: &BoxedStrategy<Expr>,
+    len: Range<usize>,
+) -> impl Strategy<Value = Vec<OrderBy>> + use<> {
+    vec(
+        (
+            expr.clone(),
+            select(&[OrderDirection::Asc, OrderDirection::Desc][..]),
+            option::weighted(0.3, select(&[NullOrdering::First, NullOrdering::Last][..])),
+        )
+            .prop_map(|(expr, direction, nulls)| OrderBy {
+                expr,
+                direction,
+                nulls,
+            }),
+        len,
+    )
+}
+
+fn assignments(expr: &BoxedStrategy<Expr>) -> impl Strategy<Value = Vec<UpdateAssignment>> + use<> {
+    vec(
+        (name(), expr.clone()).prop_map(|(column, value)| UpdateAssignment { column, value }),
+        1..=2,
+    )
+}
+
+fn insert(depth: u32) -> impl Strategy<Value = InsertStatement> {
+    let expr = expr(depth);
+    // Rows are generated two values wide and cut to the number of columns.
+    let values = prop_oneof![
+        1 => select_statement(depth).prop_map(|s| InsertSource::Query(Box::new(s))),
+        1 => Just(InsertSource::DefaultValues),
+        2 => vec(vec(expr.clone(), 2), 1..=2).prop_map(InsertSource::Values),
+    ];
+    let on_conflict = (
+        name(),
+        prop_oneof![
+            Just(ConflictAction::DoNothing),
+            assignments(&expr).prop_map(ConflictAction::DoUpdate),
+        ],
+    )
+        .prop_map(|(column, action)| OnConflict {
+            columns: vec![column],
+            action,
+        });
+    (
+        select(TABLES),
+        vec(name(), 1..=2),
+        values,
+        option::weighted(0.3, on_conflict),
+    )
+        .prop_map(|(table, columns, mut values, on_conflict)| {
+            if let InsertSource::Values(rows) = &mut values {
+                for row in rows {
+                    row.truncate(columns.len());
+                }
+            }
+            let on_conflict =
+                on_conflict.filter(|_| !matches!(values, InsertSource::DefaultValues));
+            InsertStatement {
+                schema: None,
+                table: Ident::new(table),
+                columns,
+                values,
+                on_conflict,
+            }
+        })
+}
+
+fn statement() -> impl Strategy<Value = Statement> {
+    let depth = 3;
+    let expr = expr(depth);
+    prop_oneof![
+        insert(depth).prop_map(Statement::Insert),
+        (
+            select(TABLES),
+            assignments(&expr),
+            option::weighted(0.7, expr.clone()),
+        )
+            .prop_map(|(table, assignments, where_clause)| {
+                Statement::Update(UpdateStatement {
+                    schema: None,
+                    table: Ident::new(table),
+                    alias: None,
+                    assignments,
+                    from: None,
+                    where_clause,
+                })
+            }),
+        (select(TABLES), option::weighted(0.7, expr)).prop_map(|(table, where_clause)| {
+            Statement::Delete(DeleteStatement {
+                schema: None,
+                table: Ident::new(table),
+                alias: None,
+                where_clause,
+            })
+        }),
+        select_statement(depth).prop_map(Statement::Select),
+    ]
+}
+
+// =============================================================================
+// Properties
+// =============================================================================
+
+fn parse(sql: &str) -> Result<Statement, String> {
+    Parser::new(sql)
+        .parse_statement()
+        .map_err(|e| format!("{e:?}"))
+}
+
+proptest! {
+    #![proptest_config(config(CASES))]
+
+    #[test]
+    fn generated_statements_parse(statement in statement()) {
+        let sql = statement.to_string();
+        if let Err(e) = parse(&sql) {
+            prop_assert!(false, "failed to parse {}\n{}", sql, e);
+        }
+    }
+
+    #[test]
+    fn parse_print_parse_is_a_fixed_point(statement in statement()) {
+        let sql = statement.to_string();
+        let Ok(first) = parse(&sql) else { return Ok(()) };
+        let printed = first.to_string();
+        let second = parse(&printed);
+        prop_assert!(second.is_ok(), "reprint of {} failed: {}\n{:?}", sql, printed, second);
+        prop_assert_eq!(
+            &printed,
+            &second.unwrap().to_string(),
+            "not a fixed point for {}",
+            sql
+        );
+    }
+}
+
+// =============================================================================
+// Crash resistance
+// =============================================================================
+
+/// Lexes and parses `input`, only checking that neither panics or loops.
+fn lex_and_parse(input: &str) {
+    let mut lexer = Lexer::new(input);
+    for _ in 0..=input.len() {
+        if lexer.next_token().kind == TokenKind::Eof {
+            break;
+        }
+    }
+    let _ = Parser::new(input).parse_statement();
+}
+
+const FRAGMENTS: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "(", ")", ",", "*", "'", "\"", "`", "x'", "X'0", "?", ":", "::",
+    "1", "1.", "1e", "--", "/*", "*/", "CASE", "WHEN", "END", "IN", "NOT", "BETWEEN", "AND",
+    "CAST", "AS", "JOIN", "ON", "USING", ";", "||", "<<", "!", "é", "\n", " ",
+];
+
+proptest! {
+    #![proptest_config(config(CASES * 4))]
+
+    #[test]
+    fn arbitrary_bytes_do_not_panic(bytes in vec(any::<u8>(), 0..64)) {
+        lex_and_parse(&String::from_utf8_lossy(&bytes));
+    }
+
+    #[test]
+    fn arbitrary_sql_tokens_do_not_panic(fragments in vec(select(FRAGMENTS), 0..24)) {
+        lex_and_parse(&fragments.concat());
+    }
+}
+
+proptest! {
+    #![proptest_config(config(CASES / 5))]
+
+    #[test]
+    fn truncated_and_mutated_statements_do_not_panic(
+        statement in statement(),
+        mutations in vec((any::<Index>(), select(&b"()'\"`,;*?: -xX0"[..])), 8),
+    ) {
+        let sql = statement.to_string();
+
+        for (i, _) in sql.char_indices() {
+            lex_and_parse(&sql[..i]);
+        }
+
+        let mut bytes = sql.into_bytes();
+        for (index, byte) in mutations {
+            let i = index.index(bytes.len());
+            bytes[i] = byte;
+            lex_and_parse(&String::from_utf8_lossy(&bytes));
+        }
+    }
+}
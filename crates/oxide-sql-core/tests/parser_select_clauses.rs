@@ -1,10 +1,10 @@
-//! Tests for SELECT clauses: WHERE, GROUP BY, HAVING, ORDER BY,
-//! LIMIT, and OFFSET.
+//! Tests for SELECT clauses: WHERE, GROUP BY (with ROLLUP, CUBE, and
+//! GROUPING SETS), HAVING, ORDER BY, LIMIT, and OFFSET.
 
 mod common;
 use common::*;
 
-use oxide_sql_core::ast::{BinaryOp, Expr, Literal, OrderDirection};
+use oxide_sql_core::ast::{BinaryOp, Expr, GroupByItem, Literal, NullOrdering, OrderDirection};
 
 #[test]
 fn where_simple() {
@@ -38,7 +38,7 @@ fn group_by_single() {
     assert_eq!(s.group_by.len(), 1);
     assert!(matches!(
         &s.group_by[0],
-        Expr::Column { name, .. } if name == "status"
+        GroupByItem::Expr(Expr::Column { name, .. }) if name == "status"
     ));
     round_trip("SELECT status, COUNT(*) FROM orders GROUP BY status");
 }
@@ -53,6 +53,62 @@ fn group_by_multiple() {
     round_trip("SELECT status, region, COUNT(*) FROM orders GROUP BY status, region");
 }
 
+#[test]
+fn group_by_rollup() {
+    let s = parse_select(
+        "SELECT region, city, SUM(total) FROM orders GROUP BY ROLLUP(region, (city, store))",
+    );
+    assert!(matches!(
+        &s.group_by[0],
+        GroupByItem::Rollup(groups) if groups.len() == 2 && groups[1].len() == 2
+    ));
+    round_trip(
+        "SELECT region, city, SUM(total) FROM orders GROUP BY ROLLUP(region, (city, store))",
+    );
+}
+
+#[test]
+fn group_by_cube_mixed_with_columns() {
+    let s = parse_select("SELECT a, b, c, COUNT(*) FROM t GROUP BY a, CUBE(b, c)");
+    assert_eq!(s.group_by.len(), 2);
+    assert!(matches!(&s.group_by[0], GroupByItem::Expr(_)));
+    assert!(matches!(&s.group_by[1], GroupByItem::Cube(groups) if groups.len() == 2));
+    round_trip("SELECT a, b, c, COUNT(*) FROM t GROUP BY a, CUBE(b, c)");
+}
+
+#[test]
+fn group_by_grouping_sets() {
+    let s = parse_select(
+        "SELECT brand, size, SUM(sales) FROM items GROUP BY GROUPING SETS ((brand), (size), ())",
+    );
+    assert!(matches!(
+        &s.group_by[0],
+        GroupByItem::GroupingSets(sets) if sets.len() == 3 && sets[2].is_empty()
+    ));
+    round_trip(
+        "SELECT brand, size, SUM(sales) FROM items GROUP BY GROUPING SETS ((brand), (size), ())",
+    );
+    assert_eq!(
+        parse("SELECT a FROM t GROUP BY GROUPING SETS (a, (a, b))").to_string(),
+        "SELECT a FROM t GROUP BY GROUPING SETS ((a), (a, b))"
+    );
+}
+
+#[test]
+fn grouping_words_are_still_names() {
+    let s = parse_select("SELECT rollup, cube FROM t GROUP BY rollup, cube, grouping");
+    assert_eq!(s.group_by.len(), 3);
+    assert!(s.group_by.iter().all(|g| matches!(g, GroupByItem::Expr(_))));
+    round_trip("SELECT GROUPING(a) FROM t GROUP BY ROLLUP(a)");
+}
+
+#[test]
+fn grouping_sets_errors() {
+    parse_err("SELECT a FROM t GROUP BY ROLLUP()");
+    parse_err("SELECT a FROM t GROUP BY GROUPING SETS (a");
+    parse_err("SELECT a FROM t GROUP BY GROUPING SETS a");
+}
+
 #[test]
 fn having_with_aggregate() {
     let s = parse_select(
@@ -118,6 +174,15 @@ fn order_by_multiple_columns() {
     round_trip("SELECT * FROM users ORDER BY last_name ASC, first_name DESC");
 }
 
+#[test]
+fn order_by_nulls_first_and_last() {
+    let s = parse_select("SELECT * FROM users ORDER BY a NULLS FIRST, b DESC NULLS LAST");
+    assert_eq!(s.order_by[0].nulls, Some(NullOrdering::First));
+    assert_eq!(s.order_by[1].direction, OrderDirection::Desc);
+    assert_eq!(s.order_by[1].nulls, Some(NullOrdering::Last));
+    round_trip("SELECT * FROM users ORDER BY a NULLS FIRST, b DESC NULLS LAST");
+}
+
 #[test]
 fn limit_only() {
     let s = parse_select("SELECT * FROM users LIMIT 10");
@@ -0,0 +1,185 @@
+//! Tests for the `sql!` macro.
+//!
+//! These tests verify that the macro:
+//! - Parses SQL at compile time and expands to a `Query`
+//! - Turns `{expr}` interpolations into bound parameters
+//! - Checks tables and columns against `derive(Table)` types when given,
+//!   resolving qualified columns through table aliases
+//!
+//! Inputs that must fail to compile live in `oxide-sql-derive/tests/ui`.
+
+use oxide_sql_core::builder::SqlValue;
+use oxide_sql_core::schema::{Table, contains_name};
+use oxide_sql_derive::{Table, sql};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "users")]
+pub struct User {
+    #[column(primary_key)]
+    pub id: i64,
+    pub name: String,
+    pub active: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "posts")]
+pub struct Post {
+    #[column(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub title: String,
+}
+
+// =============================================================================
+// Parameters
+// =============================================================================
+
+#[test]
+fn sql_without_params() {
+    let query = sql!("SELECT id, name FROM users");
+    assert_eq!(query.sql(), "SELECT id, name FROM users");
+    assert!(query.params().is_empty());
+}
+
+#[test]
+fn sql_interpolates_params_in_order() {
+    let id = 42_i64;
+    let name = "alice";
+    let query = sql!("SELECT id FROM users WHERE id = {id} AND name = {name}");
+    assert_eq!(
+        query.sql(),
+        "SELECT id FROM users WHERE id = ? AND name = ?"
+    );
+    assert_eq!(
+        query.params(),
+        &[SqlValue::Int(42), SqlValue::Text(String::from("alice"))]
+    );
+}
+
+#[test]
+fn sql_interpolates_expressions() {
+    let user = User {
+        id: 7,
+        name: String::from("bob"),
+        active: true,
+    };
+    let query = sql!("UPDATE users SET name = {user.name.clone()} WHERE id = {user.id + 1}");
+    assert_eq!(query.sql(), "UPDATE users SET name = ? WHERE id = ?");
+    assert_eq!(
+        query.params(),
+        &[SqlValue::Text(String::from("bob")), SqlValue::Int(8)]
+    );
+}
+
+#[test]
+fn sql_interpolates_expressions_with_braces() {
+    let user = User {
+        id: 7,
+        name: String::from("bob"),
+        active: true,
+    };
+    let query = sql!(
+        "SELECT id FROM users WHERE id = {if user.active { user.id } else { 0 }} \
+         AND name = {User { name: String::from(\"}\"), ..user.clone() }.name} \
+         AND active = {(|c: char| c == '}')('}')}"
+    );
+    assert_eq!(
+        query.sql(),
+        "SELECT id FROM users WHERE id = ? AND name = ? AND active = ?"
+    );
+    assert_eq!(
+        query.params(),
+        &[
+            SqlValue::Int(7),
+            SqlValue::Text(String::from("}")),
+            SqlValue::Bool(true),
+        ]
+    );
+}
+
+#[test]
+fn sql_escaped_braces() {
+    let query = sql!("SELECT '{{}}' FROM users");
+    assert_eq!(query.sql(), "SELECT '{}' FROM users");
+}
+
+#[test]
+fn sql_keeps_braces_in_quotes() {
+    let id = 1_i64;
+    let query = sql!(r#"SELECT '{x}' AS "{y}" FROM users WHERE name = 'it''s {z}' AND id = {id}"#);
+    assert_eq!(
+        query.sql(),
+        r#"SELECT '{x}' AS "{y}" FROM users WHERE name = 'it''s {z}' AND id = ?"#
+    );
+    assert_eq!(query.params(), &[SqlValue::Int(1)]);
+}
+
+// =============================================================================
+// Schema checks
+// =============================================================================
+
+#[test]
+fn sql_checked_against_table() {
+    let active = true;
+    let query = sql!(
+        "SELECT id, name AS label FROM users WHERE active = {active} ORDER BY label",
+        UserTable
+    );
+    assert_eq!(query.params(), &[SqlValue::Bool(true)]);
+}
+
+#[test]
+fn sql_checked_against_multiple_tables() {
+    let query = sql!(
+        "SELECT u.name, p.title FROM users u INNER JOIN posts p ON p.user_id = u.id",
+        UserTable,
+        PostTable
+    );
+    assert!(query.params().is_empty());
+}
+
+#[test]
+fn sql_checked_qualified_columns() {
+    let query = sql!(
+        "SELECT posts.title, a.name FROM posts INNER JOIN users a ON a.id = posts.user_id \
+         WHERE a.id = (SELECT MAX(s.id) FROM (SELECT id FROM users) s)",
+        UserTable,
+        PostTable
+    );
+    assert!(query.params().is_empty());
+}
+
+#[test]
+fn sql_checked_insert_and_delete() {
+    let name = "carol";
+    let insert = sql!(
+        "INSERT INTO users (name, active) VALUES ({name}, TRUE)",
+        UserTable
+    );
+    assert_eq!(
+        insert.sql(),
+        "INSERT INTO users (name, active) VALUES (?, TRUE)"
+    );
+
+    let delete = sql!("DELETE FROM posts WHERE user_id = {1_i64}", PostTable);
+    assert_eq!(delete.params(), &[SqlValue::Int(1)]);
+}
+
+#[test]
+fn sql_checked_merge() {
+    let query = sql!(
+        "MERGE INTO users u USING users s ON u.id = s.id \
+         WHEN MATCHED THEN UPDATE SET active = {false} \
+         WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)",
+        UserTable
+    );
+    assert_eq!(query.params(), &[SqlValue::Bool(false)]);
+}
+
+#[test]
+fn contains_name_is_const() {
+    const { assert!(contains_name(UserTable::COLUMNS, "active")) };
+    const { assert!(!contains_name(UserTable::COLUMNS, "missing")) };
+}
@@ -0,0 +1,324 @@
+//! Tests for the typed `Select` builder with `#[derive(Table)]` types.
+//!
+//! These tests verify that:
+//! - `select::<(A, B)>()` emits the selected columns in order
+//! - The selection exposes its Rust output type and decodes rows by position
+//! - `select_all_except()` drops the listed columns from `Table::COLUMNS`
+//! - `order_by_collate()` renders `COLLATE` before the sort direction
+//! - Typed builders work with `QueryCache`
+//! - `exists()`/`not_exists()` take typed subqueries correlated through
+//!   `qualified_col()`
+//! - `guard()` caps the LIMIT at the guard's maximum row count
+//! - `group_by()` and the ROLLUP/CUBE/GROUPING SETS variants append
+//!   grouping elements
+
+use oxide_sql_core::builder::{
+    DecodeError, GroupingSet, QueryCache, QueryGuard, Select, SqlValue, col, exists, not_exists,
+    qualified_col,
+};
+use oxide_sql_core::schema::{DecodeRow, Selectable};
+use oxide_sql_derive::Table;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "users")]
+pub struct User {
+    #[column(primary_key)]
+    pub id: i64,
+    pub name: String,
+    #[column(nullable)]
+    pub email: Option<String>,
+    pub password_hash: String,
+}
+
+// =============================================================================
+// Row decoding
+// =============================================================================
+
+#[test]
+fn select_tuple_output_type() {
+    fn output_of<S: Selectable<UserTable>>(_: fn() -> S::Output) {}
+    output_of::<(UserColumns::Id, UserColumns::Name)>(|| (1_i64, String::new()));
+    output_of::<UserColumns::Email>(|| None::<String>);
+}
+
+#[test]
+fn select_tuple_decodes_rows() {
+    let select = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Id, UserColumns::Name, UserColumns::Email)>()
+        .from_table();
+    let decode = select.row_decoder();
+
+    let (sql, _) = select.build();
+    assert_eq!(sql, "SELECT id, name, email FROM users");
+
+    let row = vec![
+        SqlValue::Int(1),
+        SqlValue::Text(String::from("alice")),
+        SqlValue::Null,
+    ];
+    let (id, name, email): (i64, String, Option<String>) = decode(row).unwrap();
+    assert_eq!(id, 1);
+    assert_eq!(name, "alice");
+    assert_eq!(email, None);
+}
+
+#[test]
+fn select_single_column_decodes_value() {
+    let row = vec![SqlValue::Text(String::from("bob"))];
+    assert_eq!(
+        <UserColumns::Name as DecodeRow<UserTable>>::decode_row(row),
+        Ok(String::from("bob"))
+    );
+}
+
+#[test]
+fn decode_rejects_wrong_column_count() {
+    let row = vec![SqlValue::Int(1)];
+    assert_eq!(
+        <(UserColumns::Id, UserColumns::Name) as DecodeRow<UserTable>>::decode_row(row),
+        Err(DecodeError::ColumnCount {
+            expected: 2,
+            found: 1
+        })
+    );
+}
+
+#[test]
+fn decode_reports_type_mismatch_position() {
+    let row = vec![SqlValue::Int(1), SqlValue::Int(2)];
+    let err = <(UserColumns::Id, UserColumns::Name) as DecodeRow<UserTable>>::decode_row(row)
+        .unwrap_err();
+    assert!(matches!(err, DecodeError::TypeMismatch { index: 1, .. }));
+}
+
+// =============================================================================
+// select_all_except
+// =============================================================================
+
+#[test]
+fn select_all_except_single_column() {
+    let (sql, _) = Select::<UserTable, _, _>::new()
+        .select_all_except::<(UserColumns::PasswordHash,)>()
+        .from_table()
+        .build();
+    assert_eq!(sql, "SELECT id, name, email FROM users");
+}
+
+#[test]
+fn select_all_except_multiple_columns() {
+    let (sql, _) = Select::<UserTable, _, _>::new()
+        .select_all_except::<(UserColumns::Email, UserColumns::PasswordHash)>()
+        .from_table()
+        .build();
+    assert_eq!(sql, "SELECT id, name FROM users");
+}
+
+// =============================================================================
+// ORDER BY ... COLLATE
+// =============================================================================
+
+#[test]
+fn order_by_collate() {
+    let (sql, _) = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Id, UserColumns::Name)>()
+        .from_table()
+        .order_by_collate(UserColumns::Name, "NOCASE", true)
+        .order_by_collate(UserColumns::Email, "\"de_DE\"", false)
+        .order_by(UserColumns::Id, true)
+        .build();
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users ORDER BY name COLLATE NOCASE, \
+         email COLLATE \"de_DE\" DESC, id"
+    );
+}
+
+#[test]
+fn order_by_collate_quotes_invalid_names() {
+    let (sql, _) = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Id,)>()
+        .from_table()
+        .order_by_collate(UserColumns::Name, "x; DROP TABLE users", true)
+        .build();
+    assert_eq!(
+        sql,
+        "SELECT id FROM users ORDER BY name COLLATE \"x; DROP TABLE users\""
+    );
+}
+
+// =============================================================================
+// QueryCache
+// =============================================================================
+
+#[test]
+fn typed_select_reuses_cached_sql() {
+    let cache = QueryCache::new();
+    let find = |id: i64| {
+        Select::<UserTable, _, _>::new()
+            .select::<(UserColumns::Id, UserColumns::Name)>()
+            .from_table()
+            .where_col(User::id(), col(User::id()).eq(id))
+    };
+
+    let (first, _) = cache.build(find(1));
+    let (second, params) = cache.build(find(2));
+
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+    assert_eq!(&*second, "SELECT id, name FROM users WHERE id = ?");
+    assert_eq!(params, vec![SqlValue::Int(2)]);
+}
+
+// =============================================================================
+// Correlated subqueries
+// =============================================================================
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "orders")]
+pub struct Order {
+    #[column(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub status: String,
+}
+
+#[test]
+fn where_not_exists_correlated_typed_columns() {
+    let open_orders = Select::<OrderTable, _, _>::new()
+        .select::<OrderColumns::Id>()
+        .from_table()
+        .where_clause(
+            qualified_col(Order::user_id())
+                .eq_expr(qualified_col(User::id()))
+                .and(col(Order::status()).eq("open")),
+        );
+
+    let (sql, params) = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Id, UserColumns::Name)>()
+        .from_table()
+        .where_clause(not_exists(open_orders))
+        .build();
+
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users WHERE NOT EXISTS \
+         (SELECT id FROM orders WHERE orders.user_id = users.id AND status = ?)"
+    );
+    assert_eq!(params, vec![SqlValue::Text(String::from("open"))]);
+}
+
+#[test]
+fn where_exists_combines_with_outer_params() {
+    let any_order = Select::<OrderTable, _, _>::new()
+        .select::<OrderColumns::Id>()
+        .from_table()
+        .where_clause(
+            qualified_col(Order::user_id())
+                .eq_expr(qualified_col(User::id()))
+                .and(col(Order::status()).eq("paid")),
+        );
+
+    let (sql, params) = Select::<UserTable, _, _>::new()
+        .select::<UserColumns::Id>()
+        .from_table()
+        .where_clause(
+            col(User::name())
+                .like("a%")
+                .and(exists(any_order))
+                .and(col(User::id()).gt(10)),
+        )
+        .build();
+
+    assert_eq!(
+        sql,
+        "SELECT id FROM users WHERE name LIKE ? AND EXISTS \
+         (SELECT id FROM orders WHERE orders.user_id = users.id AND status = ?) AND id > ?"
+    );
+    assert_eq!(
+        params,
+        vec![
+            SqlValue::Text(String::from("a%")),
+            SqlValue::Text(String::from("paid")),
+            SqlValue::Int(10),
+        ]
+    );
+}
+
+// =============================================================================
+// Query guards
+// =============================================================================
+
+#[test]
+fn guard_caps_user_requested_limit() {
+    let guard = QueryGuard::new().max_rows(100);
+    let page = |limit: i64| {
+        Select::<UserTable, _, _>::new()
+            .select::<UserColumns::Id>()
+            .from_table()
+            .limit(limit)
+            .guard(&guard)
+            .build_sql()
+    };
+
+    assert_eq!(page(20), "SELECT id FROM users LIMIT 20");
+    assert_eq!(page(5000), "SELECT id FROM users LIMIT 100");
+    assert_eq!(page(-1), "SELECT id FROM users LIMIT 100");
+
+    let (sql, _) = Select::<UserTable, _, _>::new()
+        .select_all()
+        .from_table()
+        .guard(&guard)
+        .build();
+    assert!(sql.ends_with(" LIMIT 100"));
+    // The guard applies at build time, so a later limit() can't exceed it.
+    let sql = Select::<UserTable, _, _>::new()
+        .select::<UserColumns::Id>()
+        .from_table()
+        .guard(&guard)
+        .limit(5000)
+        .build_sql();
+    assert_eq!(sql, "SELECT id FROM users LIMIT 100");
+}
+
+// =============================================================================
+// Grouping
+// =============================================================================
+
+#[test]
+fn group_by_appends_grouping_elements() {
+    let sql = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Name, UserColumns::Email, UserColumns::Id)>()
+        .from_table()
+        .group_by::<UserColumns::Name>()
+        .group_by_cube::<(UserColumns::Email, UserColumns::Id)>()
+        .build_sql();
+    assert_eq!(
+        sql,
+        "SELECT name, email, id FROM users GROUP BY name, CUBE(email, id)"
+    );
+
+    let sql = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Name, UserColumns::Email)>()
+        .from_table()
+        .group_by_rollup::<(UserColumns::Name, UserColumns::Email)>()
+        .build_sql();
+    assert_eq!(
+        sql,
+        "SELECT name, email FROM users GROUP BY ROLLUP(name, email)"
+    );
+
+    let sql = Select::<UserTable, _, _>::new()
+        .select::<(UserColumns::Name, UserColumns::Email)>()
+        .from_table()
+        .group_by_grouping_sets(&[
+            GroupingSet::of::<UserColumns::Name>(),
+            GroupingSet::of::<(UserColumns::Name, UserColumns::Email)>(),
+            GroupingSet::total(),
+        ])
+        .build_sql();
+    assert_eq!(
+        sql,
+        "SELECT name, email FROM users GROUP BY GROUPING SETS ((name), (name, email), ())"
+    );
+}
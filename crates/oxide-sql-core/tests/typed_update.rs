@@ -0,0 +1,135 @@
+//! Tests for the typed `Update` builder with `#[derive(Table)]` types.
+//!
+//! These tests verify that:
+//! - SET clauses accept both values and expressions, with parameters bound
+//!   in clause order
+//! - `where_in_chunked()` splits large ID sets across statements
+
+use oxide_sql_core::builder::func::{coalesce, lower, now};
+use oxide_sql_core::builder::{ChunkError, Delete, ExprBuilder, SqlValue, Update, col};
+use oxide_sql_core::dialect::{Dialect, GenericDialect};
+use oxide_sql_derive::Table;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "posts")]
+pub struct Post {
+    #[column(primary_key)]
+    pub id: i64,
+    pub slug: String,
+    pub views: i64,
+    pub updated_at: String,
+}
+
+#[test]
+fn update_set_expr_counter() {
+    let (sql, params) = Update::<PostTable, _>::new()
+        .set_expr(Post::views(), col(Post::views()).add(1))
+        .where_col(Post::id(), col(Post::id()).eq(5))
+        .build();
+
+    assert_eq!(sql, "UPDATE posts SET views = views + ? WHERE id = ?");
+    assert_eq!(params, vec![SqlValue::Int(1), SqlValue::Int(5)]);
+}
+
+#[test]
+fn update_mixes_values_and_functions() {
+    let (sql, params) = Update::<PostTable, _>::new()
+        .set(Post::slug(), "Hello")
+        .set_expr(Post::slug(), lower(col(Post::slug())))
+        .set_expr(
+            Post::views(),
+            coalesce(vec![col(Post::views()), ExprBuilder::value(0)]),
+        )
+        .set_expr(Post::updated_at(), now())
+        .build();
+
+    assert_eq!(
+        sql,
+        "UPDATE posts SET slug = ?, slug = LOWER(slug), views = COALESCE(views, ?), \
+         updated_at = CURRENT_TIMESTAMP"
+    );
+    assert_eq!(
+        params,
+        vec![SqlValue::Text(String::from("Hello")), SqlValue::Int(0)]
+    );
+}
+
+// =============================================================================
+// Chunked WHERE ... IN
+// =============================================================================
+
+/// A dialect accepting at most the given number of bound parameters.
+struct MaxParams(usize);
+
+impl Dialect for MaxParams {
+    fn name(&self) -> &'static str {
+        "max-params"
+    }
+
+    fn max_parameters(&self) -> usize {
+        self.0
+    }
+}
+
+#[test]
+fn update_where_in_chunked() {
+    let statements: Vec<_> = Update::<PostTable, _>::new()
+        .set(Post::views(), 0_i64)
+        .where_in_chunked(Post::id(), vec![1_i64, 2, 3, 4], 100, &MaxParams(4))
+        .unwrap()
+        .collect();
+
+    assert_eq!(statements.len(), 2);
+    assert_eq!(
+        statements[0].0,
+        "UPDATE posts SET views = ? WHERE id IN (?, ?, ?)"
+    );
+    assert_eq!(statements[1].1, vec![SqlValue::Int(0), SqlValue::Int(4)]);
+}
+
+#[test]
+fn delete_where_in_chunked_respects_dialect_limit() {
+    let dialect = GenericDialect::new();
+    let ids: Vec<i64> = (0..2500).collect();
+    let statements: Vec<_> = Delete::<PostTable>::new()
+        .where_clause(col(Post::views()).eq(0))
+        .where_in_chunked(Post::id(), ids, usize::MAX, &dialect)
+        .unwrap()
+        .collect();
+
+    assert_eq!(statements.len(), 3);
+    assert_eq!(statements[0].1.len(), dialect.max_parameters());
+    assert!(
+        statements
+            .iter()
+            .all(|(_, params)| params.len() <= dialect.max_parameters())
+    );
+    assert!(
+        statements[0]
+            .0
+            .starts_with("DELETE FROM posts WHERE (views = ?) AND id IN (?, ")
+    );
+}
+
+#[test]
+fn where_in_chunked_honours_chunk_size_and_limit() {
+    let statements: Vec<_> = Delete::<PostTable>::new()
+        .where_in_chunked(Post::id(), vec![1_i64, 2, 3], 2, &MaxParams(100))
+        .unwrap()
+        .collect();
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[0].0, "DELETE FROM posts WHERE id IN (?, ?)");
+
+    let result = Update::<PostTable, _>::new()
+        .set(Post::views(), 0_i64)
+        .where_clause(col(Post::views()).gt(10))
+        .where_in_chunked(Post::id(), vec![1_i64], 100, &MaxParams(2));
+    assert_eq!(
+        result.err(),
+        Some(ChunkError::ParameterLimit {
+            reserved: 2,
+            max_parameters: 2,
+        })
+    );
+}
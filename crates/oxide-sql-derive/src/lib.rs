@@ -1,12 +1,58 @@
 //! Derive macros for type-safe SQL table definitions.
 //!
 //! This crate provides the `#[derive(Table)]` macro for defining database tables
-//! with compile-time checked column names.
+//! with compile-time checked column names, and the `sql!` macro for writing
+//! raw SQL that is parsed and checked at compile time.
 
+mod sql;
+
+use oxide_sql_core::Parser;
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
-use syn::{Attribute, Data, DeriveInput, Expr, Fields, Ident, Lit, Meta, Type, parse_macro_input};
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Fields, Ident, Lit, LitStr, Meta, Type, parse_macro_input,
+};
+
+/// Parses a SQL statement at compile time and expands to a
+/// `oxide_sql_core::builder::Query` with bound parameters.
+///
+/// Rust expressions inside `{...}` become `?` placeholders, and their values
+/// are converted with `ToSqlValue` in order. Use `{{` and `}}` for literal
+/// braces; single braces inside quoted strings (`'{x}'`) and identifiers are
+/// kept as written. Invalid SQL is a compile error.
+///
+/// Passing one or more `Table` types after the SQL also checks every table
+/// and column the statement references against those tables' `NAME` and
+/// `COLUMNS`, failing compilation on unknown names. A qualified column
+/// (`u.title`) must belong to the table its qualifier names or aliases.
+///
+/// # Example
+///
+/// ```ignore
+/// use oxide_sql_derive::{Table, sql};
+///
+/// #[derive(Table)]
+/// #[table(name = "users")]
+/// pub struct User {
+///     #[column(primary_key)]
+///     pub id: i64,
+///     pub name: String,
+/// }
+///
+/// let id = 42;
+/// let query = sql!("SELECT id, name FROM users WHERE id = {id}", UserTable);
+///
+/// assert_eq!(query.sql(), "SELECT id, name FROM users WHERE id = ?");
+/// ```
+#[proc_macro]
+pub fn sql(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as sql::SqlInput);
+    sql::sql_impl(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
 /// Derives the `Table` trait for a struct, generating type-safe column accessors.
 ///
@@ -14,6 +60,18 @@ use syn::{Attribute, Data, DeriveInput, Expr, Fields, Ident, Lit, Meta, Type, pa
 ///
 /// - `#[table(name = "table_name")]` - Specifies the SQL table name (optional,
 ///   defaults to snake_case of struct name)
+/// - `#[table(index(columns = "a, b"))]` - Declares an index on columns
+/// - `#[table(index(expr = "lower(email)", unique))]` - Declares an
+///   expression index; `columns` and `expr` may be combined and repeated
+/// - `#[table(index(..., where = "active"))]` - Makes the index partial
+/// - `#[table(index(..., name = "idx_name"))]` - Names the index (optional,
+///   defaults to `idx_<table>_<elements>`)
+/// - `#[table(strict)]` - Makes the table a SQLite `STRICT` table
+/// - `#[table(without_rowid)]` - Makes the table a SQLite `WITHOUT ROWID`
+///   table
+///
+/// Index expressions and predicates are parsed at compile time, and every
+/// column they reference must be a field of the struct.
 ///
 /// # Field Attributes
 ///
@@ -24,6 +82,11 @@ use syn::{Attribute, Data, DeriveInput, Expr, Fields, Ident, Lit, Meta, Type, pa
 /// - `#[column(unique)]` - Marks the column as UNIQUE
 /// - `#[column(autoincrement)]` - Marks the column as AUTOINCREMENT
 /// - `#[column(default = "expr")]` - Sets a raw SQL default expression
+/// - `#[column(collation = "NOCASE")]` - Sets the column collation
+/// - `#[column(references = "users(id)")]` - Declares a foreign key to
+///   the `id` column of `users`
+/// - `#[column(generated = "price * quantity")]` - Makes the column a
+///   virtual generated column; add `stored` to store its value on write
 ///
 /// # Generated Items
 ///
@@ -42,7 +105,10 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
 
 fn derive_table_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
     let struct_name = &input.ident;
-    let table_name = get_table_name(&input.attrs, struct_name)?;
+    let table_attrs = parse_table_attrs(&input.attrs)?;
+    let table_name = table_attrs
+        .name
+        .unwrap_or_else(|| to_snake_case(&struct_name.to_string()));
 
     let fields = match &input.data {
         Data::Struct(data) => match &data.fields {
@@ -78,9 +144,28 @@ fn derive_table_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
             is_unique: column_attrs.unique,
             is_autoincrement: column_attrs.autoincrement,
             default_expr: column_attrs.default_expr,
+            collation: column_attrs.collation,
+            references: column_attrs.references,
+            generated: column_attrs.generated,
         });
     }
 
+    // SQLite rejects these combinations when creating the table.
+    if let Some(span) = table_attrs.without_rowid {
+        if !column_infos.iter().any(|c| c.is_primary_key) {
+            return Err(syn::Error::new(
+                span,
+                "`without_rowid` tables need a `#[column(primary_key)]` column",
+            ));
+        }
+        if let Some(info) = column_infos.iter().find(|c| c.is_autoincrement) {
+            return Err(syn::Error::new_spanned(
+                &info.field_name,
+                "`autoincrement` cannot be used in a `without_rowid` table",
+            ));
+        }
+    }
+
     // Generate column type names (PascalCase)
     let column_type_names: Vec<Ident> = column_infos
         .iter()
@@ -173,6 +258,28 @@ fn derive_table_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
                 Some(expr) => quote! { Some(#expr) },
                 None => quote! { None },
             };
+            let collation_token = match &info.collation {
+                Some(collation) => quote! { Some(#collation) },
+                None => quote! { None },
+            };
+            let generated_token = match &info.generated {
+                Some((expr, stored)) => quote! {
+                    Some(::oxide_sql_core::schema::GeneratedSchema {
+                        expr: #expr,
+                        stored: #stored,
+                    })
+                },
+                None => quote! { None },
+            };
+            let references_token = match &info.references {
+                Some((table, column)) => quote! {
+                    Some(::oxide_sql_core::schema::ReferenceSchema {
+                        table: #table,
+                        column: #column,
+                    })
+                },
+                None => quote! { None },
+            };
 
             quote! {
                 ::oxide_sql_core::schema::ColumnSchema {
@@ -183,11 +290,23 @@ fn derive_table_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
                     unique: #is_unique,
                     autoincrement: #is_autoincrement,
                     default_expr: #default_expr_token,
+                    collation: #collation_token,
+                    references: #references_token,
+                    generated: #generated_token,
                 }
             }
         })
         .collect();
 
+    // Generate TableSchema index entries
+    let index_entries = table_attrs
+        .indexes
+        .iter()
+        .map(|index| index_entry(index, &table_name, &all_column_names))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let strict = table_attrs.strict;
+    let without_rowid = table_attrs.without_rowid.is_some();
+
     let expanded = quote! {
         /// Column types for `#struct_name` table.
         #[allow(non_snake_case)]
@@ -215,6 +334,13 @@ fn derive_table_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
             ] = &[
                 #(#schema_entries),*
             ];
+            const INDEXES: &'static [
+                ::oxide_sql_core::schema::IndexSchema
+            ] = &[
+                #(#index_entries),*
+            ];
+            const STRICT: bool = #strict;
+            const WITHOUT_ROWID: bool = #without_rowid;
         }
 
         impl #table_struct_name {
@@ -249,6 +375,9 @@ struct ColumnInfo {
     is_unique: bool,
     is_autoincrement: bool,
     default_expr: Option<String>,
+    collation: Option<String>,
+    references: Option<(String, String)>,
+    generated: Option<(String, bool)>,
 }
 
 struct ColumnAttrs {
@@ -258,30 +387,160 @@ struct ColumnAttrs {
     unique: bool,
     autoincrement: bool,
     default_expr: Option<String>,
+    collation: Option<String>,
+    /// Referenced `(table, column)`.
+    references: Option<(String, String)>,
+    /// Generated column `(expression, stored)`.
+    generated: Option<(String, bool)>,
+}
+
+struct TableAttrs {
+    name: Option<String>,
+    indexes: Vec<IndexAttrs>,
+    strict: bool,
+    /// Where `without_rowid` was given, for error spans.
+    without_rowid: Option<Span>,
+}
+
+struct IndexAttrs {
+    span: Span,
+    name: Option<String>,
+    /// Indexed elements with the literal they came from, for error spans.
+    elements: Vec<(String, LitStr)>,
+    unique: bool,
+    condition: Option<LitStr>,
 }
 
-fn get_table_name(attrs: &[Attribute], struct_name: &Ident) -> syn::Result<String> {
+fn parse_table_attrs(attrs: &[Attribute]) -> syn::Result<TableAttrs> {
+    let mut result = TableAttrs {
+        name: None,
+        indexes: Vec::new(),
+        strict: false,
+        without_rowid: None,
+    };
+
     for attr in attrs {
         if attr.path().is_ident("table") {
-            let mut table_name = None;
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("name") {
                     let value: Expr = meta.value()?.parse()?;
-                    if let Expr::Lit(lit) = value {
-                        if let Lit::Str(s) = lit.lit {
-                            table_name = Some(s.value());
-                        }
+                    if let Expr::Lit(lit) = value
+                        && let Lit::Str(s) = lit.lit
+                    {
+                        result.name = Some(s.value());
                     }
+                } else if meta.path.is_ident("index") {
+                    let mut index = IndexAttrs {
+                        span: meta.path.span(),
+                        name: None,
+                        elements: Vec::new(),
+                        unique: false,
+                        condition: None,
+                    };
+                    meta.parse_nested_meta(|inner| {
+                        if inner.path.is_ident("unique") {
+                            index.unique = true;
+                        } else if inner.path.is_ident("name") {
+                            let lit: LitStr = inner.value()?.parse()?;
+                            index.name = Some(lit.value());
+                        } else if inner.path.is_ident("columns") {
+                            let lit: LitStr = inner.value()?.parse()?;
+                            for column in lit.value().split(',') {
+                                index
+                                    .elements
+                                    .push((column.trim().to_string(), lit.clone()));
+                            }
+                        } else if inner.path.is_ident("expr") {
+                            let lit: LitStr = inner.value()?.parse()?;
+                            index.elements.push((lit.value(), lit));
+                        } else if inner.path.is_ident("where") {
+                            index.condition = Some(inner.value()?.parse()?);
+                        } else {
+                            return Err(inner.error(
+                                "expected `columns`, `expr`, `unique`, `name`, or `where`",
+                            ));
+                        }
+                        Ok(())
+                    })?;
+                    result.indexes.push(index);
+                } else if meta.path.is_ident("strict") {
+                    result.strict = true;
+                } else if meta.path.is_ident("without_rowid") {
+                    result.without_rowid = Some(meta.path.span());
                 }
                 Ok(())
             })?;
-            if let Some(name) = table_name {
-                return Ok(name);
-            }
         }
     }
-    // Default to snake_case of struct name
-    Ok(to_snake_case(&struct_name.to_string()))
+
+    Ok(result)
+}
+
+/// Validates an index declaration and generates its `IndexSchema` entry.
+fn index_entry(index: &IndexAttrs, table: &str, columns: &[&str]) -> syn::Result<TokenStream2> {
+    if index.elements.is_empty() {
+        return Err(syn::Error::new(
+            index.span,
+            "index needs at least one of `columns` or `expr`",
+        ));
+    }
+    for (sql, lit) in &index.elements {
+        check_index_sql(sql, lit, columns)?;
+    }
+    if let Some(condition) = &index.condition {
+        check_index_sql(&condition.value(), condition, columns)?;
+    }
+
+    let name = index.name.clone().unwrap_or_else(|| {
+        let elements: Vec<&str> = index.elements.iter().map(|(sql, _)| sql.as_str()).collect();
+        format!("idx_{table}_{}", sanitize_ident(&elements.join("_")))
+    });
+    let elements = index.elements.iter().map(|(sql, _)| sql);
+    let unique = index.unique;
+    let condition = match &index.condition {
+        Some(condition) => quote! { Some(#condition) },
+        None => quote! { None },
+    };
+
+    Ok(quote! {
+        ::oxide_sql_core::schema::IndexSchema {
+            name: #name,
+            columns: &[#(#elements),*],
+            unique: #unique,
+            condition: #condition,
+        }
+    })
+}
+
+/// Parses an index element or predicate and checks that every column it
+/// references belongs to the table.
+fn check_index_sql(sql: &str, lit: &LitStr, columns: &[&str]) -> syn::Result<()> {
+    let expr = Parser::new(sql)
+        .parse_expr()
+        .map_err(|e| syn::Error::new(lit.span(), format!("invalid SQL `{sql}`: {}", e.message)))?;
+    let mut names = sql::Names::default();
+    names.expr(&expr);
+    match names
+        .columns
+        .iter()
+        .chain(names.qualified.iter().map(|(_, column)| column))
+        .find(|c| !columns.contains(&c.as_str()))
+    {
+        Some(column) => Err(syn::Error::new(
+            lit.span(),
+            format!("index references unknown column `{column}`"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Replaces runs of non-alphanumeric characters with a single `_`.
+fn sanitize_ident(s: &str) -> String {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase()
 }
 
 fn parse_column_attrs(attrs: &[Attribute]) -> syn::Result<ColumnAttrs> {
@@ -292,7 +551,11 @@ fn parse_column_attrs(attrs: &[Attribute]) -> syn::Result<ColumnAttrs> {
         unique: false,
         autoincrement: false,
         default_expr: None,
+        collation: None,
+        references: None,
+        generated: None,
     };
+    let mut stored = None;
 
     for attr in attrs {
         if attr.path().is_ident("column") {
@@ -312,27 +575,65 @@ fn parse_column_attrs(attrs: &[Attribute]) -> syn::Result<ColumnAttrs> {
                     result.autoincrement = true;
                 } else if meta.path.is_ident("name") {
                     let value: Expr = meta.value()?.parse()?;
-                    if let Expr::Lit(lit) = value {
-                        if let Lit::Str(s) = lit.lit {
-                            result.name = Some(s.value());
-                        }
+                    if let Expr::Lit(lit) = value
+                        && let Lit::Str(s) = lit.lit
+                    {
+                        result.name = Some(s.value());
                     }
                 } else if meta.path.is_ident("default") {
                     let value: Expr = meta.value()?.parse()?;
-                    if let Expr::Lit(lit) = value {
-                        if let Lit::Str(s) = lit.lit {
-                            result.default_expr = Some(s.value());
-                        }
+                    if let Expr::Lit(lit) = value
+                        && let Lit::Str(s) = lit.lit
+                    {
+                        result.default_expr = Some(s.value());
                     }
+                } else if meta.path.is_ident("collation") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    result.collation = Some(lit.value());
+                } else if meta.path.is_ident("references") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    result.references = Some(parse_reference(&lit)?);
+                } else if meta.path.is_ident("generated") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    Parser::new(&lit.value()).parse_expr().map_err(|e| {
+                        syn::Error::new(
+                            lit.span(),
+                            format!("invalid SQL `{}`: {}", lit.value(), e.message),
+                        )
+                    })?;
+                    result.generated = Some((lit.value(), false));
+                } else if meta.path.is_ident("stored") {
+                    stored = Some(meta.path.span());
                 }
                 Ok(())
             })?;
         }
     }
 
+    if let Some(span) = stored {
+        match &mut result.generated {
+            Some((_, is_stored)) => *is_stored = true,
+            None => return Err(syn::Error::new(span, "`stored` requires `generated`")),
+        }
+    }
+
     Ok(result)
 }
 
+/// Parses a `table(column)` foreign key target.
+fn parse_reference(lit: &LitStr) -> syn::Result<(String, String)> {
+    let value = lit.value();
+    value
+        .trim()
+        .strip_suffix(')')
+        .and_then(|rest| rest.split_once('('))
+        .map(|(table, column)| (table.trim().to_string(), column.trim().to_string()))
+        .filter(|(table, column)| {
+            !table.is_empty() && !column.is_empty() && !column.contains([',', '('])
+        })
+        .ok_or_else(|| syn::Error::new(lit.span(), "expected `table(column)`"))
+}
+
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     for (i, c) in s.chars().enumerate() {
@@ -0,0 +1,499 @@
+//! Implementation of the `sql!` macro.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use oxide_sql_core::Parser;
+use oxide_sql_core::ast::{
+    ConflictAction, Expr as SqlExpr, GroupByItem, InsertSource, MergeAction, SelectStatement,
+    Statement, TableRef,
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, LitStr, Path, Token};
+
+/// Parsed macro input: a SQL literal followed by optional table types.
+pub struct SqlInput {
+    sql: LitStr,
+    tables: Vec<Path>,
+}
+
+impl Parse for SqlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let sql: LitStr = input.parse()?;
+        let mut tables = Vec::new();
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let rest = Punctuated::<Path, Token![,]>::parse_terminated(input)?;
+            tables.extend(rest);
+        }
+        Ok(Self { sql, tables })
+    }
+}
+
+pub fn sql_impl(input: SqlInput) -> syn::Result<TokenStream2> {
+    let (sql, args) = extract_params(&input.sql)?;
+
+    let statement = Parser::new(&sql)
+        .parse_statement()
+        .map_err(|e| syn::Error::new(input.sql.span(), format!("invalid SQL: {}", e.message)))?;
+
+    let checks = if input.tables.is_empty() {
+        quote! {}
+    } else {
+        schema_checks(&statement, &input.tables, &input.sql)?
+    };
+
+    Ok(quote! {
+        {
+            #checks
+            ::oxide_sql_core::builder::Query::new(
+                ::std::string::String::from(#sql),
+                ::std::vec![
+                    #(::oxide_sql_core::builder::ToSqlValue::to_sql_value(#args)),*
+                ],
+            )
+        }
+    })
+}
+
+/// Replaces every `{expr}` in the SQL with a `?` placeholder and returns the
+/// rewritten SQL together with the parsed Rust expressions, in order.
+///
+/// `{{` and `}}` produce literal braces. Single braces inside quoted strings
+/// and identifiers are kept as written.
+fn extract_params(lit: &LitStr) -> syn::Result<(String, Vec<Expr>)> {
+    let source = lit.value();
+    let mut sql = String::with_capacity(source.len());
+    let mut args = Vec::new();
+    let mut chars = source.chars().peekable();
+    // The closing quote of the string or identifier being copied, if any.
+    let mut quote = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                sql.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                sql.push('}');
+            }
+            _ if quote.is_some() => {
+                if quote == Some(c) {
+                    quote = None;
+                }
+                sql.push(c);
+            }
+            '\'' | '"' => {
+                quote = Some(c);
+                sql.push(c);
+            }
+            '{' => {
+                let expr = interpolation(&mut chars)
+                    .ok_or_else(|| syn::Error::new(lit.span(), "unclosed `{` in SQL"))?;
+                let expr: Expr = syn::parse_str(&expr).map_err(|e| {
+                    syn::Error::new(lit.span(), format!("invalid parameter `{expr}`: {e}"))
+                })?;
+                args.push(expr);
+                sql.push('?');
+            }
+            '}' => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "unmatched `}` in SQL, use `}}` for a literal brace",
+                ));
+            }
+            _ => sql.push(c),
+        }
+    }
+
+    Ok((sql, args))
+}
+
+/// Reads the Rust expression of a `{expr}` interpolation, consuming its
+/// closing `}`.
+///
+/// Braces of blocks, closures, and struct literals nest, and braces inside
+/// string and character literals are skipped. Returns `None` if the
+/// interpolation is never closed.
+fn interpolation(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    let mut expr = String::new();
+    let mut depth = 0_usize;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '}' if depth == 0 => return Some(expr),
+            '}' => depth -= 1,
+            '{' => depth += 1,
+            '"' => {
+                expr.push(c);
+                while let Some(c) = chars.next() {
+                    expr.push(c);
+                    match c {
+                        '\\' => expr.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            // A character literal, as opposed to a lifetime or label.
+            '\'' if chars.peek() == Some(&'\\') || chars.clone().nth(1) == Some('\'') => {
+                expr.push(c);
+                while let Some(c) = chars.next() {
+                    expr.push(c);
+                    match c {
+                        '\\' => expr.extend(chars.next()),
+                        '\'' => break,
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        expr.push(c);
+    }
+
+    None
+}
+
+/// Generates `const` assertions checking that every table and column the
+/// statement references exists in one of the given `Table` types.
+///
+/// Qualified columns (`u.title`) must exist in the table their qualifier
+/// names or aliases; columns of subqueries aliased in `FROM` are not checked.
+fn schema_checks(
+    statement: &Statement,
+    tables: &[Path],
+    lit: &LitStr,
+) -> syn::Result<TokenStream2> {
+    let mut names = Names::default();
+    names.statement(statement);
+
+    let table_checks = names.tables.iter().map(|table| {
+        let message = format!("table `{table}` is not one of the tables passed to sql!");
+        quote! {
+            assert!(
+                #(::oxide_sql_core::schema::contains_name(
+                    &[<#tables as ::oxide_sql_core::schema::Table>::NAME],
+                    #table,
+                ))||*,
+                #message
+            );
+        }
+    });
+
+    let column_checks = names
+        .columns
+        .iter()
+        .filter(|column| !names.aliases.contains(column))
+        .map(|column| {
+            let message = format!("column `{column}` does not exist in the tables passed to sql!");
+            quote! {
+                assert!(
+                    #(::oxide_sql_core::schema::contains_name(
+                        <#tables as ::oxide_sql_core::schema::Table>::COLUMNS,
+                        #column,
+                    ))||*,
+                    #message
+                );
+            }
+        });
+
+    let mut qualified_checks = Vec::new();
+    for (qualifier, column) in &names.qualified {
+        let Some((_, table)) = names.sources.iter().find(|(name, _)| name == qualifier) else {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("unknown table or alias `{qualifier}` in `{qualifier}.{column}`"),
+            ));
+        };
+        let Some(table) = table else {
+            continue;
+        };
+        let message = format!("column `{qualifier}.{column}` does not exist in table `{table}`");
+        qualified_checks.push(quote! {
+            assert!(
+                #((::oxide_sql_core::schema::contains_name(
+                    &[<#tables as ::oxide_sql_core::schema::Table>::NAME],
+                    #table,
+                ) && ::oxide_sql_core::schema::contains_name(
+                    <#tables as ::oxide_sql_core::schema::Table>::COLUMNS,
+                    #column,
+                )))||*,
+                #message
+            );
+        });
+    }
+
+    Ok(quote! {
+        const _: () = {
+            #(#table_checks)*
+            #(#column_checks)*
+            #(#qualified_checks)*
+        };
+    })
+}
+
+/// Table, column, and alias names collected from a statement.
+#[derive(Default)]
+pub(crate) struct Names {
+    tables: Vec<String>,
+    /// Unqualified column names.
+    pub(crate) columns: Vec<String>,
+    /// Qualified columns, as (qualifier, column).
+    pub(crate) qualified: Vec<(String, String)>,
+    /// Names a column can be qualified with, and the table they stand
+    /// for (`None` for a subquery).
+    sources: Vec<(String, Option<String>)>,
+    aliases: Vec<String>,
+}
+
+impl Names {
+    fn table(&mut self, name: &str) {
+        if !self.tables.iter().any(|t| t == name) {
+            self.tables.push(name.to_string());
+        }
+        self.source(name, Some(name));
+    }
+
+    /// Records that columns qualified with `name` belong to `table`.
+    fn source(&mut self, name: &str, table: Option<&str>) {
+        self.sources
+            .push((name.to_string(), table.map(ToString::to_string)));
+    }
+
+    fn qualified_column(&mut self, qualifier: &str, name: &str) {
+        let column = (qualifier.to_string(), name.to_string());
+        if !self.qualified.contains(&column) {
+            self.qualified.push(column);
+        }
+    }
+
+    fn column(&mut self, name: &str) {
+        if !self.columns.iter().any(|c| c == name) {
+            self.columns.push(name.to_string());
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Select(select) => self.select(select),
+            Statement::Insert(insert) => {
+                self.table(&insert.table);
+                for column in &insert.columns {
+                    self.column(column);
+                }
+                match &insert.values {
+                    InsertSource::Values(rows) => {
+                        for expr in rows.iter().flatten() {
+                            self.expr(expr);
+                        }
+                    }
+                    InsertSource::Query(select) => self.select(select),
+                    InsertSource::DefaultValues => {}
+                }
+                if let Some(on_conflict) = &insert.on_conflict {
+                    for column in &on_conflict.columns {
+                        self.column(column);
+                    }
+                    if let ConflictAction::DoUpdate(assignments) = &on_conflict.action {
+                        for assignment in assignments {
+                            self.column(&assignment.column);
+                            self.expr(&assignment.value);
+                        }
+                    }
+                }
+            }
+            Statement::Update(update) => {
+                self.table(&update.table);
+                if let Some(alias) = &update.alias {
+                    self.aliases.push(alias.value.clone());
+                    self.source(alias, Some(&update.table));
+                }
+                for assignment in &update.assignments {
+                    self.column(&assignment.column);
+                    self.expr(&assignment.value);
+                }
+                if let Some(from) = &update.from {
+                    self.table_ref(from);
+                }
+                if let Some(expr) = &update.where_clause {
+                    self.expr(expr);
+                }
+            }
+            Statement::Delete(delete) => {
+                self.table(&delete.table);
+                if let Some(expr) = &delete.where_clause {
+                    self.expr(expr);
+                }
+            }
+            Statement::Merge(merge) => {
+                self.table(&merge.table);
+                if let Some(alias) = &merge.alias {
+                    self.aliases.push(alias.value.clone());
+                    self.source(alias, Some(&merge.table));
+                }
+                self.table_ref(&merge.source);
+                self.expr(&merge.on);
+                for clause in &merge.clauses {
+                    if let Some(condition) = &clause.condition {
+                        self.expr(condition);
+                    }
+                    match &clause.action {
+                        MergeAction::Update(assignments) => {
+                            for assignment in assignments {
+                                self.column(&assignment.column);
+                                self.expr(&assignment.value);
+                            }
+                        }
+                        MergeAction::Insert { columns, values } => {
+                            for column in columns {
+                                self.column(column);
+                            }
+                            for value in values {
+                                self.expr(value);
+                            }
+                        }
+                        MergeAction::Delete | MergeAction::DoNothing => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn select(&mut self, select: &SelectStatement) {
+        for column in &select.columns {
+            if let Some(alias) = &column.alias {
+                self.aliases.push(alias.value.clone());
+            }
+            self.expr(&column.expr);
+        }
+        if let Some(from) = &select.from {
+            self.table_ref(from);
+        }
+        let clauses = select
+            .where_clause
+            .iter()
+            .chain(select.group_by.iter().flat_map(group_by_exprs))
+            .chain(&select.having)
+            .chain(select.order_by.iter().map(|o| &o.expr))
+            .chain(&select.limit)
+            .chain(&select.offset);
+        for expr in clauses {
+            self.expr(expr);
+        }
+    }
+
+    fn table_ref(&mut self, table_ref: &TableRef) {
+        match table_ref {
+            TableRef::Table { name, alias, .. } => {
+                self.table(name);
+                if let Some(alias) = alias {
+                    self.source(alias, Some(name));
+                }
+            }
+            TableRef::Subquery { query, alias } => {
+                self.aliases.push(alias.value.clone());
+                self.source(alias, None);
+                self.select(query);
+            }
+            TableRef::Join { left, join } => {
+                self.table_ref(left);
+                self.table_ref(&join.table);
+                if let Some(on) = &join.on {
+                    self.expr(on);
+                }
+                for column in &join.using {
+                    self.column(column);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn expr(&mut self, expr: &SqlExpr) {
+        match expr {
+            SqlExpr::Column {
+                table: Some(table),
+                name,
+                ..
+            } => self.qualified_column(table, name),
+            SqlExpr::Column { name, .. } => self.column(name),
+            SqlExpr::Binary { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            SqlExpr::Unary { operand, .. } => self.expr(operand),
+            SqlExpr::Function(func) => {
+                for arg in &func.args {
+                    self.expr(arg);
+                }
+                for order in &func.within_group {
+                    self.expr(&order.expr);
+                }
+                if let Some(filter) = &func.filter {
+                    self.expr(filter);
+                }
+            }
+            SqlExpr::Subquery(select) | SqlExpr::Exists(select) => self.select(select),
+            SqlExpr::IsNull { expr, .. }
+            | SqlExpr::Cast { expr, .. }
+            | SqlExpr::Collate { expr, .. } => self.expr(expr),
+            SqlExpr::Paren(expr) => self.expr(expr),
+            SqlExpr::Array(elements) => {
+                for element in elements {
+                    self.expr(element);
+                }
+            }
+            SqlExpr::Quantified { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            SqlExpr::In { expr, list, .. } => {
+                self.expr(expr);
+                for item in list {
+                    self.expr(item);
+                }
+            }
+            SqlExpr::Between {
+                expr, low, high, ..
+            } => {
+                self.expr(expr);
+                self.expr(low);
+                self.expr(high);
+            }
+            SqlExpr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                if let Some(operand) = operand {
+                    self.expr(operand);
+                }
+                for (when, then) in when_clauses {
+                    self.expr(when);
+                    self.expr(then);
+                }
+                if let Some(else_clause) = else_clause {
+                    self.expr(else_clause);
+                }
+            }
+            SqlExpr::Literal(_) | SqlExpr::Parameter { .. } | SqlExpr::Wildcard { .. } => {}
+        }
+    }
+}
+
+/// The expressions inside a GROUP BY entry, including those in ROLLUP,
+/// CUBE, and GROUPING SETS groups.
+fn group_by_exprs(item: &GroupByItem) -> Vec<&SqlExpr> {
+    match item {
+        GroupByItem::Expr(expr) => vec![expr],
+        GroupByItem::Rollup(groups)
+        | GroupByItem::Cube(groups)
+        | GroupByItem::GroupingSets(groups) => groups.iter().flatten().collect(),
+    }
+}
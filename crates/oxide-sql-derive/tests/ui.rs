@@ -0,0 +1,15 @@
+//! Compile tests for the `sql!` macro, `derive(Table)`, and the typed
+//! builders over derived tables.
+//!
+//! Each file in `tests/ui` must fail to compile with the error recorded in
+//! its `.stderr` file. Regenerate those with `TRYBUILD=overwrite`. Files in
+//! `tests/ui/pass` must build and run; having them also makes trybuild
+//! build rather than only check the failing cases, so errors raised when
+//! generic code is instantiated are caught too.
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass/*.rs");
+    cases.compile_fail("tests/ui/*.rs");
+}
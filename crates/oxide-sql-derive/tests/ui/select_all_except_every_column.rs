@@ -0,0 +1,17 @@
+use oxide_sql_core::builder::Select;
+use oxide_sql_derive::Table;
+
+#[derive(Table)]
+#[table(name = "users")]
+pub struct User {
+    #[column(primary_key)]
+    pub id: i64,
+    pub name: String,
+}
+
+fn main() {
+    let _ = Select::<UserTable, _, _>::new()
+        .select_all_except::<(UserColumns::Id, UserColumns::Name)>()
+        .from_table()
+        .build();
+}
@@ -0,0 +1,5 @@
+use oxide_sql_derive::sql;
+
+fn main() {
+    let _ = sql!("SELECT FROM WHERE");
+}
@@ -0,0 +1,7 @@
+use oxide_sql_derive::sql;
+
+fn main() {
+    let id = 1_i64;
+    // The whole block is the parameter, so the error quotes all of it.
+    let _ = sql!("SELECT id FROM users WHERE id = {match id { 1 => }}");
+}
@@ -0,0 +1,27 @@
+use oxide_sql_derive::{Table, sql};
+
+#[derive(Table)]
+#[table(name = "users")]
+pub struct User {
+    #[column(primary_key)]
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Table)]
+#[table(name = "posts")]
+pub struct Post {
+    #[column(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    pub title: String,
+}
+
+fn main() {
+    // `title` exists, but in `posts`, not in `users`.
+    let _ = sql!(
+        "SELECT u.title FROM users u INNER JOIN posts p ON p.user_id = u.id",
+        UserTable,
+        PostTable
+    );
+}
@@ -0,0 +1,6 @@
+use oxide_sql_derive::sql;
+
+fn main() {
+    let id = 1_i64;
+    let _ = sql!("SELECT id FROM users WHERE id = {id");
+}
@@ -0,0 +1,13 @@
+use oxide_sql_derive::{Table, sql};
+
+#[derive(Table)]
+#[table(name = "users")]
+pub struct User {
+    #[column(primary_key)]
+    pub id: i64,
+    pub name: String,
+}
+
+fn main() {
+    let _ = sql!("SELECT id, email FROM users", UserTable);
+}
@@ -0,0 +1,12 @@
+use oxide_sql_derive::{Table, sql};
+
+#[derive(Table)]
+#[table(name = "users")]
+pub struct User {
+    #[column(primary_key)]
+    pub id: i64,
+}
+
+fn main() {
+    let _ = sql!("SELECT id FROM accounts", UserTable);
+}
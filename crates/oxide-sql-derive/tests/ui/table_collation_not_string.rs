@@ -0,0 +1,12 @@
+use oxide_sql_derive::Table;
+
+#[derive(Table)]
+#[table(name = "tags")]
+pub struct Tag {
+    #[column(primary_key)]
+    pub id: i64,
+    #[column(collation = NOCASE)]
+    pub label: String,
+}
+
+fn main() {}
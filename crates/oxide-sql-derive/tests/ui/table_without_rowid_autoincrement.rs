@@ -0,0 +1,11 @@
+use oxide_sql_derive::Table;
+
+#[derive(Table)]
+#[table(name = "tags", without_rowid)]
+pub struct Tag {
+    #[column(primary_key, autoincrement)]
+    pub id: i64,
+    pub label: String,
+}
+
+fn main() {}
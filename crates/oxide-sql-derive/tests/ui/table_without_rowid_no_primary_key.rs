@@ -0,0 +1,9 @@
+use oxide_sql_derive::Table;
+
+#[derive(Table)]
+#[table(name = "tags", without_rowid)]
+pub struct Tag {
+    pub label: String,
+}
+
+fn main() {}
@@ -0,0 +1,98 @@
+//! Sequence-backed autoincrement helpers.
+//!
+//! DuckDB has no `AUTOINCREMENT` keyword. The migrations [`DuckDbDialect`]
+//! gives an autoincrement column a sequence named `seq_<table>_<column>`
+//! and a `DEFAULT nextval('seq_<table>_<column>')`. These helpers produce
+//! the same names and SQL, for tables created outside migrations.
+
+use oxide_sql_core::builder::ExprBuilder;
+use oxide_sql_core::builder::func::call;
+use oxide_sql_core::dialect::Dialect;
+use oxide_sql_core::migrations::{DefaultValue, DuckDbDialect};
+
+/// Returns the name of the sequence backing an autoincrement column.
+#[must_use]
+pub fn sequence_name(table: &str, column: &str) -> String {
+    DuckDbDialect::sequence_name(table, column)
+}
+
+/// Returns the `CREATE SEQUENCE` statement for an autoincrement column,
+/// as emitted before `CREATE TABLE` by migrations.
+#[must_use]
+pub fn create_sequence(table: &str, column: &str) -> String {
+    format!(
+        "CREATE SEQUENCE IF NOT EXISTS {} START 1",
+        crate::DuckDbDialect::new().quote_identifier(&sequence_name(table, column))
+    )
+}
+
+/// Returns the column default that draws from the sequence.
+#[must_use]
+pub fn default_value(table: &str, column: &str) -> DefaultValue {
+    DefaultValue::Expression(format!(
+        "nextval('{}')",
+        sequence_name(table, column).replace('\'', "''")
+    ))
+}
+
+/// Returns `nextval(?)` for the sequence, to draw an ID explicitly, e.g.
+/// when inserting into a table whose column has no default.
+#[must_use]
+pub fn nextval(table: &str, column: &str) -> ExprBuilder {
+    call(
+        "nextval",
+        vec![ExprBuilder::value(sequence_name(table, column))],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxide_sql_core::builder::value::SqlValue;
+    use oxide_sql_core::migrations::{CreateTableBuilder, MigrationDialect, integer, varchar};
+
+    #[test]
+    fn test_matches_migration_ddl() {
+        let op = CreateTableBuilder::new()
+            .name("users")
+            .column(integer("id").primary_key().autoincrement().build())
+            .column(varchar("name", 255).build())
+            .build();
+        let ddl = DuckDbDialect::new().create_table(&op);
+
+        assert_eq!(sequence_name("users", "id"), "seq_users_id");
+        assert!(ddl.starts_with(&format!("{};\n", create_sequence("users", "id"))));
+        let DefaultValue::Expression(default) = default_value("users", "id") else {
+            unreachable!()
+        };
+        assert!(ddl.contains(&format!("DEFAULT {default}")));
+    }
+
+    #[test]
+    fn test_quotes_in_table_names() {
+        let op = CreateTableBuilder::new()
+            .name("it's \"here\"")
+            .column(integer("id").primary_key().autoincrement().build())
+            .build();
+        let ddl = DuckDbDialect::new().create_table(&op);
+
+        let create = create_sequence("it's \"here\"", "id");
+        assert_eq!(
+            create,
+            "CREATE SEQUENCE IF NOT EXISTS \"seq_it's \"\"here\"\"_id\" START 1"
+        );
+        assert!(ddl.starts_with(&format!("{create};\n")));
+        let DefaultValue::Expression(default) = default_value("it's \"here\"", "id") else {
+            unreachable!()
+        };
+        assert_eq!(default, "nextval('seq_it''s \"here\"_id')");
+        assert!(ddl.contains(&format!("DEFAULT {default}")));
+    }
+
+    #[test]
+    fn test_nextval() {
+        let (sql, params) = nextval("users", "id").build();
+        assert_eq!(sql, "nextval(?)");
+        assert_eq!(params, [SqlValue::Text(String::from("seq_users_id"))]);
+    }
+}
@@ -0,0 +1,428 @@
+//! DuckDB `COPY TO` / `COPY FROM` builders.
+
+use oxide_sql_core::builder::Query;
+use oxide_sql_core::builder::ident::{sanitize_column_for, sanitize_table_for};
+use oxide_sql_core::builder::value::SqlValue;
+
+use crate::DuckDbDialect;
+
+/// The dialect whose reserved keywords decide which names are quoted.
+const DIALECT: DuckDbDialect = DuckDbDialect::new();
+
+/// File format of a `COPY` statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Comma-separated values.
+    Csv,
+    /// Apache Parquet.
+    Parquet,
+    /// Newline-delimited JSON.
+    Json,
+}
+
+impl CopyFormat {
+    /// Returns the format name as written in the `FORMAT` option.
+    #[must_use]
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Compression codec of the files written by `COPY ... TO`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// No compression.
+    Uncompressed,
+    /// Snappy (the Parquet default).
+    Snappy,
+    /// Gzip.
+    Gzip,
+    /// Zstandard.
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the codec name as written in the `COMPRESSION` option.
+    #[must_use]
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            Self::Uncompressed => "uncompressed",
+            Self::Snappy => "snappy",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Options shared by `COPY TO` and `COPY FROM`.
+#[derive(Debug, Clone, Default)]
+struct CopyOptions {
+    format: Option<CopyFormat>,
+    header: Option<bool>,
+    delimiter: Option<char>,
+    compression: Option<Compression>,
+    partition_by: Vec<String>,
+    overwrite: bool,
+}
+
+impl CopyOptions {
+    /// Appends ` (OPTION value, ...)`, or nothing without options.
+    fn write(&self, sql: &mut String) {
+        let mut options = Vec::new();
+        if let Some(format) = self.format {
+            options.push(format!("FORMAT {}", format.as_sql()));
+        }
+        if let Some(header) = self.header {
+            options.push(format!("HEADER {header}"));
+        }
+        if let Some(delimiter) = self.delimiter {
+            options.push(format!(
+                "DELIMITER {}",
+                string_literal(&delimiter.to_string())
+            ));
+        }
+        if let Some(compression) = self.compression {
+            options.push(format!("COMPRESSION {}", compression.as_sql()));
+        }
+        if !self.partition_by.is_empty() {
+            options.push(format!("PARTITION_BY ({})", self.partition_by.join(", ")));
+        }
+        if self.overwrite {
+            options.push(String::from("OVERWRITE_OR_IGNORE true"));
+        }
+        if !options.is_empty() {
+            sql.push_str(" (");
+            sql.push_str(&options.join(", "));
+            sql.push(')');
+        }
+    }
+}
+
+/// Renders a file path or option value as a string literal. DuckDB does
+/// not accept parameters in these positions.
+fn string_literal(value: &str) -> String {
+    SqlValue::Text(String::from(value)).to_sql_inline()
+}
+
+/// A `COPY ... TO` builder that exports a table or query to a file.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::{SelectDyn, dyn_col};
+/// use oxide_sql_duckdb::{Compression, CopyFormat, CopyToBuilder};
+///
+/// let sql = CopyToBuilder::table("events", "events.parquet")
+///     .format(CopyFormat::Parquet)
+///     .compression(Compression::Zstd)
+///     .build_sql();
+/// assert_eq!(
+///     sql,
+///     "COPY events TO 'events.parquet' (FORMAT parquet, COMPRESSION zstd)"
+/// );
+///
+/// let recent = SelectDyn::new()
+///     .columns(&["id", "kind"])
+///     .from("events")
+///     .where_clause(dyn_col("id").gt(100))
+///     .build();
+/// let (sql, params) = CopyToBuilder::query(recent, "recent.csv")
+///     .format(CopyFormat::Csv)
+///     .header(true)
+///     .build();
+/// assert_eq!(
+///     sql,
+///     "COPY (SELECT id, kind FROM events WHERE id > ?) TO 'recent.csv' \
+///      (FORMAT csv, HEADER true)"
+/// );
+/// assert_eq!(params.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CopyToBuilder {
+    source: String,
+    params: Vec<SqlValue>,
+    path: String,
+    options: CopyOptions,
+}
+
+impl CopyToBuilder {
+    /// Exports every row of `table` to `path`.
+    #[must_use]
+    pub fn table(table: &str, path: &str) -> Self {
+        Self {
+            source: sanitize_table_for(table, &DIALECT),
+            params: vec![],
+            path: String::from(path),
+            options: CopyOptions::default(),
+        }
+    }
+
+    /// Exports the result of `query` to `path`, keeping its parameters.
+    #[must_use]
+    pub fn query(query: impl Into<Query>, path: &str) -> Self {
+        let (sql, params) = query.into().into_parts();
+        Self {
+            source: format!("({sql})"),
+            params,
+            path: String::from(path),
+            options: CopyOptions::default(),
+        }
+    }
+
+    /// Sets the file format. DuckDB otherwise infers it from the file
+    /// extension.
+    #[must_use]
+    pub const fn format(mut self, format: CopyFormat) -> Self {
+        self.options.format = Some(format);
+        self
+    }
+
+    /// Sets whether a CSV file starts with a header row.
+    #[must_use]
+    pub const fn header(mut self, header: bool) -> Self {
+        self.options.header = Some(header);
+        self
+    }
+
+    /// Sets the CSV field delimiter.
+    #[must_use]
+    pub const fn delimiter(mut self, delimiter: char) -> Self {
+        self.options.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Sets the compression codec.
+    #[must_use]
+    pub const fn compression(mut self, compression: Compression) -> Self {
+        self.options.compression = Some(compression);
+        self
+    }
+
+    /// Writes a Hive-partitioned directory at the path, one subdirectory
+    /// per value of the given columns.
+    #[must_use]
+    pub fn partition_by(mut self, cols: &[&str]) -> Self {
+        self.options.partition_by = cols
+            .iter()
+            .map(|s| sanitize_column_for(s, &DIALECT))
+            .collect();
+        self
+    }
+
+    /// Writes into an existing partitioned directory instead of failing.
+    #[must_use]
+    pub const fn overwrite(mut self) -> Self {
+        self.options.overwrite = true;
+        self
+    }
+
+    /// Builds the `COPY` statement and returns SQL with parameters.
+    #[must_use]
+    pub fn build(self) -> (String, Vec<SqlValue>) {
+        let mut sql = format!("COPY {} TO {}", self.source, string_literal(&self.path));
+        self.options.write(&mut sql);
+        (sql, self.params)
+    }
+
+    /// Builds the `COPY` statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
+    }
+
+    /// Builds the `COPY` statement and returns only the SQL string.
+    #[must_use]
+    pub fn build_sql(self) -> String {
+        let (sql, _) = self.build();
+        sql
+    }
+}
+
+/// A `COPY ... FROM` builder that loads a file into an existing table.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_duckdb::{CopyFormat, CopyFromBuilder};
+///
+/// let sql = CopyFromBuilder::new("events", "events.csv")
+///     .columns(&["id", "kind"])
+///     .format(CopyFormat::Csv)
+///     .header(true)
+///     .delimiter(';')
+///     .build_sql();
+/// assert_eq!(
+///     sql,
+///     "COPY events (id, kind) FROM 'events.csv' (FORMAT csv, HEADER true, DELIMITER ';')"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CopyFromBuilder {
+    table: String,
+    columns: Vec<String>,
+    path: String,
+    options: CopyOptions,
+}
+
+impl CopyFromBuilder {
+    /// Loads `path` into `table`.
+    #[must_use]
+    pub fn new(table: &str, path: &str) -> Self {
+        Self {
+            table: sanitize_table_for(table, &DIALECT),
+            columns: vec![],
+            path: String::from(path),
+            options: CopyOptions::default(),
+        }
+    }
+
+    /// Loads the file's columns into these table columns, in order.
+    #[must_use]
+    pub fn columns(mut self, cols: &[&str]) -> Self {
+        self.columns = cols
+            .iter()
+            .map(|s| sanitize_column_for(s, &DIALECT))
+            .collect();
+        self
+    }
+
+    /// Sets the file format. DuckDB otherwise infers it from the file
+    /// extension.
+    #[must_use]
+    pub const fn format(mut self, format: CopyFormat) -> Self {
+        self.options.format = Some(format);
+        self
+    }
+
+    /// Sets whether a CSV file starts with a header row.
+    #[must_use]
+    pub const fn header(mut self, header: bool) -> Self {
+        self.options.header = Some(header);
+        self
+    }
+
+    /// Sets the CSV field delimiter.
+    #[must_use]
+    pub const fn delimiter(mut self, delimiter: char) -> Self {
+        self.options.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Builds the `COPY` statement and returns SQL with parameters.
+    #[must_use]
+    pub fn build(self) -> (String, Vec<SqlValue>) {
+        let mut sql = format!("COPY {}", self.table);
+        if !self.columns.is_empty() {
+            sql.push_str(" (");
+            sql.push_str(&self.columns.join(", "));
+            sql.push(')');
+        }
+        sql.push_str(" FROM ");
+        sql.push_str(&string_literal(&self.path));
+        self.options.write(&mut sql);
+        (sql, vec![])
+    }
+
+    /// Builds the `COPY` statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
+    }
+
+    /// Builds the `COPY` statement and returns only the SQL string.
+    #[must_use]
+    pub fn build_sql(self) -> String {
+        let (sql, _) = self.build();
+        sql
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_table_to_parquet() {
+        let sql = CopyToBuilder::table("events", "out/events.parquet")
+            .format(CopyFormat::Parquet)
+            .compression(Compression::Zstd)
+            .build_sql();
+        assert_eq!(
+            sql,
+            "COPY events TO 'out/events.parquet' (FORMAT parquet, COMPRESSION zstd)"
+        );
+    }
+
+    #[test]
+    fn test_copy_to_partitioned() {
+        let sql = CopyToBuilder::table("events", "out")
+            .format(CopyFormat::Parquet)
+            .partition_by(&["year", "month"])
+            .overwrite()
+            .build_sql();
+        assert_eq!(
+            sql,
+            "COPY events TO 'out' \
+             (FORMAT parquet, PARTITION_BY (year, month), OVERWRITE_OR_IGNORE true)"
+        );
+    }
+
+    #[test]
+    fn test_copy_without_options() {
+        assert_eq!(
+            CopyToBuilder::table("events", "events.csv").build_sql(),
+            "COPY events TO 'events.csv'"
+        );
+        assert_eq!(
+            CopyFromBuilder::new("events", "events.csv").build_sql(),
+            "COPY events FROM 'events.csv'"
+        );
+    }
+
+    #[test]
+    fn test_copy_query_keeps_params() {
+        let query = Query::new(
+            String::from("SELECT * FROM events WHERE kind = ?"),
+            vec![SqlValue::Text(String::from("click"))],
+        );
+        let (sql, params) = CopyToBuilder::query(query, "clicks.json")
+            .format(CopyFormat::Json)
+            .build();
+        assert_eq!(
+            sql,
+            "COPY (SELECT * FROM events WHERE kind = ?) TO 'clicks.json' (FORMAT json)"
+        );
+        assert_eq!(params, [SqlValue::Text(String::from("click"))]);
+    }
+
+    #[test]
+    fn test_copy_quotes_duckdb_keywords() {
+        let sql = CopyToBuilder::table("pivot", "out")
+            .partition_by(&["qualify", "summarize"])
+            .build_sql();
+        assert_eq!(
+            sql,
+            "COPY \"pivot\" TO 'out' (PARTITION_BY (\"qualify\", \"summarize\"))"
+        );
+        let sql = CopyFromBuilder::new("pivot", "in.csv")
+            .columns(&["qualify"])
+            .build_sql();
+        assert_eq!(sql, "COPY \"pivot\" (\"qualify\") FROM 'in.csv'");
+    }
+
+    #[test]
+    fn test_copy_sql_injection_prevention() {
+        let sql = CopyFromBuilder::new("events; DROP TABLE users", "it's.csv")
+            .columns(&["id; --"])
+            .delimiter('\'')
+            .build_sql();
+        assert_eq!(
+            sql,
+            "COPY \"events; DROP TABLE users\" (\"id; --\") FROM 'it''s.csv' (DELIMITER '''')"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! DuckDB `CREATE TABLE ... AS SELECT` builder.
+
+use std::marker::PhantomData;
+
+use oxide_sql_core::builder::Query;
+use oxide_sql_core::builder::ident::sanitize_table_for;
+use oxide_sql_core::builder::value::SqlValue;
+
+use crate::DuckDbDialect;
+
+/// The dialect whose reserved keywords decide which names are quoted.
+const DIALECT: DuckDbDialect = DuckDbDialect::new();
+
+// Typestate markers
+
+/// Marker: the statement fails if the table exists.
+#[derive(Debug, Clone, Copy)]
+pub struct Create;
+/// Marker: `OR REPLACE` replaces an existing table.
+#[derive(Debug, Clone, Copy)]
+pub struct OrReplace;
+/// Marker: `IF NOT EXISTS` keeps an existing table.
+#[derive(Debug, Clone, Copy)]
+pub struct IfNotExists;
+
+/// A `CREATE TABLE ... AS` builder that creates a table from the result
+/// of a query, taking its columns and types from the query.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::{SelectDyn, dyn_col};
+/// use oxide_sql_duckdb::CreateTableAsBuilder;
+///
+/// let active = SelectDyn::new()
+///     .columns(&["id", "name"])
+///     .from("users")
+///     .where_clause(dyn_col("active").eq(true))
+///     .build();
+/// let (sql, params) = CreateTableAsBuilder::new("active_users", active)
+///     .or_replace()
+///     .build();
+/// assert_eq!(
+///     sql,
+///     "CREATE OR REPLACE TABLE active_users AS SELECT id, name FROM users WHERE active = ?"
+/// );
+/// assert_eq!(params.len(), 1);
+/// ```
+///
+/// DuckDB rejects `OR REPLACE` together with `IF NOT EXISTS`, so only one
+/// of them can be chosen:
+///
+/// ```compile_fail
+/// use oxide_sql_core::builder::Query;
+/// use oxide_sql_duckdb::CreateTableAsBuilder;
+///
+/// let query = Query::new(String::from("SELECT 1"), vec![]);
+/// let _ = CreateTableAsBuilder::new("t", query).or_replace().if_not_exists();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CreateTableAsBuilder<Mode = Create> {
+    table: String,
+    query: Query,
+    temporary: bool,
+    _state: PhantomData<Mode>,
+}
+
+impl CreateTableAsBuilder<Create> {
+    /// Creates `table` from the result of `query`.
+    #[must_use]
+    pub fn new(table: &str, query: impl Into<Query>) -> Self {
+        Self {
+            table: sanitize_table_for(table, &DIALECT),
+            query: query.into(),
+            temporary: false,
+            _state: PhantomData,
+        }
+    }
+
+    /// Replaces the table if it already exists.
+    #[must_use]
+    pub fn or_replace(self) -> CreateTableAsBuilder<OrReplace> {
+        self.into_mode()
+    }
+
+    /// Does nothing if the table already exists.
+    #[must_use]
+    pub fn if_not_exists(self) -> CreateTableAsBuilder<IfNotExists> {
+        self.into_mode()
+    }
+
+    /// Moves to another existing-table mode.
+    fn into_mode<Mode>(self) -> CreateTableAsBuilder<Mode> {
+        CreateTableAsBuilder {
+            table: self.table,
+            query: self.query,
+            temporary: self.temporary,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<Mode: ExistingTable> CreateTableAsBuilder<Mode> {
+    /// Creates a temporary table, dropped at the end of the session.
+    #[must_use]
+    pub const fn temporary(mut self) -> Self {
+        self.temporary = true;
+        self
+    }
+
+    /// Builds the statement and returns SQL with parameters.
+    #[must_use]
+    pub fn build(self) -> (String, Vec<SqlValue>) {
+        let mut sql = String::from("CREATE ");
+        sql.push_str(Mode::PREFIX);
+        if self.temporary {
+            sql.push_str("TEMPORARY ");
+        }
+        sql.push_str("TABLE ");
+        sql.push_str(Mode::SUFFIX);
+        sql.push_str(&self.table);
+        let (query, params) = self.query.into_parts();
+        sql.push_str(" AS ");
+        sql.push_str(&query);
+        (sql, params)
+    }
+
+    /// Builds the statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
+    }
+
+    /// Builds the statement and returns only the SQL string.
+    #[must_use]
+    pub fn build_sql(self) -> String {
+        let (sql, _) = self.build();
+        sql
+    }
+}
+
+/// How a mode renders around `TABLE`.
+pub trait ExistingTable {
+    /// Rendered after `CREATE`.
+    const PREFIX: &'static str;
+    /// Rendered after `TABLE`.
+    const SUFFIX: &'static str;
+}
+
+impl ExistingTable for Create {
+    const PREFIX: &'static str = "";
+    const SUFFIX: &'static str = "";
+}
+
+impl ExistingTable for OrReplace {
+    const PREFIX: &'static str = "OR REPLACE ";
+    const SUFFIX: &'static str = "";
+}
+
+impl ExistingTable for IfNotExists {
+    const PREFIX: &'static str = "";
+    const SUFFIX: &'static str = "IF NOT EXISTS ";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query() -> Query {
+        Query::new(
+            String::from("SELECT * FROM events WHERE kind = ?"),
+            vec![SqlValue::Text(String::from("click"))],
+        )
+    }
+
+    #[test]
+    fn test_create_table_as() {
+        let (sql, params) = CreateTableAsBuilder::new("clicks", query()).build();
+        assert_eq!(
+            sql,
+            "CREATE TABLE clicks AS SELECT * FROM events WHERE kind = ?"
+        );
+        assert_eq!(params, [SqlValue::Text(String::from("click"))]);
+    }
+
+    #[test]
+    fn test_create_temporary_table_as_if_not_exists() {
+        let sql = CreateTableAsBuilder::new("clicks", query())
+            .temporary()
+            .if_not_exists()
+            .build_sql();
+        assert_eq!(
+            sql,
+            "CREATE TEMPORARY TABLE IF NOT EXISTS clicks AS SELECT * FROM events WHERE kind = ?"
+        );
+    }
+
+    #[test]
+    fn test_create_table_as_quotes_table() {
+        let sql = CreateTableAsBuilder::new("my clicks", query()).build_sql();
+        assert!(sql.starts_with("CREATE TABLE \"my clicks\" AS "));
+    }
+
+    #[test]
+    fn test_create_table_as_quotes_duckdb_keywords() {
+        let sql = CreateTableAsBuilder::new("pivot", query())
+            .or_replace()
+            .temporary()
+            .build_sql();
+        assert!(sql.starts_with("CREATE OR REPLACE TEMPORARY TABLE \"pivot\" AS "));
+    }
+}
@@ -0,0 +1,7 @@
+//! DuckDB-specific SQL builders.
+
+mod copy;
+mod create_table_as;
+
+pub use copy::{Compression, CopyFormat, CopyFromBuilder, CopyToBuilder};
+pub use create_table_as::CreateTableAsBuilder;
@@ -0,0 +1,193 @@
+//! DuckDB dialect implementation.
+
+use oxide_sql_core::dialect::Dialect;
+
+/// Keywords DuckDB does not accept as unquoted identifiers, in upper case and
+/// sorted, as listed by `duckdb_keywords()` with category `reserved`.
+const DUCKDB_RESERVED_KEYWORDS: &[&str] = &[
+    "ALL",
+    "ANALYSE",
+    "ANALYZE",
+    "AND",
+    "ANY",
+    "ARRAY",
+    "AS",
+    "ASC",
+    "ASYMMETRIC",
+    "BOTH",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DESC",
+    "DESCRIBE",
+    "DISTINCT",
+    "DO",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "FALSE",
+    "FETCH",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INITIALLY",
+    "INTERSECT",
+    "INTO",
+    "LAMBDA",
+    "LATERAL",
+    "LEADING",
+    "LIMIT",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "PIVOT",
+    "PIVOT_LONGER",
+    "PIVOT_WIDER",
+    "PLACING",
+    "PRIMARY",
+    "QUALIFY",
+    "REFERENCES",
+    "RETURNING",
+    "SELECT",
+    "SHOW",
+    "SOME",
+    "SUMMARIZE",
+    "SYMMETRIC",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRAILING",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UNPIVOT",
+    "USING",
+    "VARIADIC",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// DuckDB dialect.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DuckDbDialect;
+
+impl DuckDbDialect {
+    /// Creates a new DuckDB dialect.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Dialect for DuckDbDialect {
+    fn name(&self) -> &'static str {
+        "duckdb"
+    }
+
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    fn supports_upsert(&self) -> bool {
+        true // DuckDB 0.8.0+
+    }
+
+    fn supports_arrays(&self) -> bool {
+        true // LIST values, written as `ARRAY[...]` and `type[]`
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        DUCKDB_RESERVED_KEYWORDS
+    }
+
+    fn max_parameters(&self) -> usize {
+        // DuckDB sets no limit of its own; cap batches at PostgreSQL's
+        // 65535 so split statements stay a reasonable size.
+        65535
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxide_sql_core::builder::dyn_col;
+    use oxide_sql_core::builder::value::SqlValue;
+
+    #[test]
+    fn test_duckdb_dialect() {
+        let dialect = DuckDbDialect::new();
+        assert_eq!(dialect.name(), "duckdb");
+        assert_eq!(dialect.identifier_quote(), '"');
+        assert_eq!(dialect.parameter_placeholder(), "?");
+        assert!(dialect.supports_returning());
+        assert!(dialect.supports_upsert());
+        assert!(dialect.supports_arrays());
+        assert_eq!(dialect.max_parameters(), 65535);
+    }
+
+    #[test]
+    fn test_duckdb_build_split_uses_duckdb_limit() {
+        use oxide_sql_core::builder::InsertDyn;
+
+        let rows: Vec<Vec<i64>> = (0..40_000).map(|i| vec![i, i]).collect();
+        let queries = InsertDyn::new()
+            .into_table("points")
+            .columns(&["x", "y"])
+            .values_many(rows)
+            .build_split(&DuckDbDialect::new());
+
+        // 32767 two-value rows fit in 65535 parameters; SQLite's default
+        // of 999 would have needed 81 statements.
+        assert_eq!(queries.len(), 2);
+        let sizes: Vec<usize> = queries.iter().map(|q| q.params().len()).collect();
+        assert_eq!(sizes, [65534, 14466]);
+    }
+
+    #[test]
+    fn test_duckdb_reserved_keywords() {
+        let dialect = DuckDbDialect::new();
+        assert!(DUCKDB_RESERVED_KEYWORDS.is_sorted());
+        assert!(dialect.is_reserved_keyword("qualify"));
+        assert!(dialect.is_reserved_keyword("Pivot"));
+        // Reserved in standard SQL, but a valid name in DuckDB.
+        assert!(!dialect.is_reserved_keyword("user"));
+        assert_eq!(dialect.quote_identifier_if_needed("user"), "user");
+        assert_eq!(
+            dialect.quote_identifier_if_needed("summarize"),
+            "\"summarize\""
+        );
+    }
+
+    #[test]
+    fn test_duckdb_render_value() {
+        let dialect = DuckDbDialect::new();
+        assert_eq!(dialect.render_value(&SqlValue::Bool(true)), "TRUE");
+        assert_eq!(
+            dialect.render_value(&SqlValue::Text(String::from("a'b"))),
+            "'a''b'"
+        );
+    }
+
+    #[test]
+    fn test_duckdb_json_get_uses_arrow() {
+        let dialect = DuckDbDialect::new();
+        let (sql, params) = dyn_col("data").json_get_for("name", &dialect).build();
+        assert_eq!(sql, "data ->> ?");
+        assert_eq!(params[0], SqlValue::Text(String::from("name")));
+    }
+}
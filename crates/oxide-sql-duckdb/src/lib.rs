@@ -0,0 +1,48 @@
+//! # oxide-sql-duckdb
+//!
+//! DuckDB-specific extensions for `oxide-sql-core`.
+//!
+//! # How DuckDB differs from other dialects
+//!
+//! - **[COPY]**: DuckDB reads and writes files directly with
+//!   `COPY ... TO` and `COPY ... FROM`, in CSV, Parquet, or JSON.
+//!   This crate provides [`CopyToBuilder`] and [`CopyFromBuilder`].
+//! - **[CREATE TABLE AS]**: DuckDB creates a table from a query with
+//!   `CREATE [OR REPLACE] TABLE ... AS SELECT`. This crate provides
+//!   [`CreateTableAsBuilder`].
+//! - **[Sequences]**: DuckDB has no `AUTOINCREMENT` or `SERIAL`;
+//!   auto-increment columns use `CREATE SEQUENCE` and a
+//!   `DEFAULT nextval(...)`. The [`autoincrement`] helpers produce
+//!   the same sequence names as the migrations `DuckDbDialect`.
+//! - **[UPSERT]** and **[RETURNING]**: DuckDB supports
+//!   `INSERT ... ON CONFLICT` and `RETURNING`, like PostgreSQL.
+//! - **[Keywords]**: DuckDB reserves fewer words than standard SQL
+//!   (`user` is a valid name) but adds its own, such as `QUALIFY`,
+//!   `PIVOT`, and `SUMMARIZE`.
+//!
+//! [COPY]: https://duckdb.org/docs/sql/statements/copy
+//! [CREATE TABLE AS]: https://duckdb.org/docs/sql/statements/create_table#create-table--as-select-ctas
+//! [Sequences]: https://duckdb.org/docs/sql/statements/create_sequence
+//! [UPSERT]: https://duckdb.org/docs/sql/statements/insert#on-conflict-clause
+//! [RETURNING]: https://duckdb.org/docs/sql/statements/insert#returning-clause
+//! [Keywords]: https://duckdb.org/docs/sql/dialect/keywords_and_identifiers
+//!
+//! ## Example
+//!
+//! ```rust
+//! use oxide_sql_duckdb::{CopyFormat, CopyToBuilder};
+//!
+//! // Export a table to Parquet
+//! let sql = CopyToBuilder::table("events", "events.parquet")
+//!     .format(CopyFormat::Parquet)
+//!     .build_sql();
+//!
+//! assert_eq!(sql, "COPY events TO 'events.parquet' (FORMAT parquet)");
+//! ```
+
+pub mod autoincrement;
+pub mod builder;
+mod dialect;
+
+pub use builder::{Compression, CopyFormat, CopyFromBuilder, CopyToBuilder, CreateTableAsBuilder};
+pub use dialect::DuckDbDialect;
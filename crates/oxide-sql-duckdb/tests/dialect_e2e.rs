@@ -6,7 +6,7 @@
 
 use duckdb::{Connection, types::ToSql};
 
-use oxide_sql_core::builder::value::SqlValue;
+use oxide_sql_core::builder::value::{DriverValue, PrimitiveValue, SqlValue};
 use oxide_sql_core::builder::{Delete, Insert, Select, Update, col};
 use oxide_sql_core::migrations::dialect::DuckDbDialect;
 use oxide_sql_core::migrations::dialect::MigrationDialect;
@@ -76,20 +76,27 @@ pub struct User {
 // Helpers
 // ------------------------------------------------------------------
 
+/// A DuckDB parameter converted from a `SqlValue`.
+struct DuckDbParam(Box<dyn ToSql>);
+
+impl DriverValue for DuckDbParam {
+    fn from_primitive(value: PrimitiveValue) -> Self {
+        Self(match value {
+            PrimitiveValue::Null => Box::new(duckdb::types::Null),
+            PrimitiveValue::Bool(b) => Box::new(b),
+            PrimitiveValue::Int(i) => Box::new(i),
+            PrimitiveValue::Float(f) => Box::new(f),
+            PrimitiveValue::Text(s) => Box::new(s),
+            PrimitiveValue::Blob(b) => Box::new(b),
+        })
+    }
+}
+
 /// Converts `&[SqlValue]` into boxed `dyn ToSql` for DuckDB.
 fn to_duckdb_params(values: &[SqlValue]) -> Vec<Box<dyn ToSql>> {
-    values
-        .iter()
-        .map(|v| -> Box<dyn ToSql> {
-            match v {
-                SqlValue::Null => Box::new(duckdb::types::Null),
-                SqlValue::Bool(b) => Box::new(*b),
-                SqlValue::Int(i) => Box::new(*i),
-                SqlValue::Float(f) => Box::new(*f),
-                SqlValue::Text(s) => Box::new(s.clone()),
-                SqlValue::Blob(b) => Box::new(b.clone()),
-            }
-        })
+    DuckDbParam::from_sql_values(values.to_vec())
+        .into_iter()
+        .map(|param| param.0)
         .collect()
 }
 
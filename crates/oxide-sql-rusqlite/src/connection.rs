@@ -0,0 +1,69 @@
+//! Running builder output on a [`Connection`].
+
+use oxide_sql_core::builder::typed::{HasColumns, HasFrom};
+use oxide_sql_core::builder::{DriverValue, Query, Select, SqlValue};
+use oxide_sql_core::schema::{DecodeRow, Table};
+use rusqlite::{Connection, params_from_iter};
+
+use crate::error::Error;
+use crate::value::{Param, row_values};
+
+/// Runs queries built by `oxide-sql-core` on a rusqlite [`Connection`].
+///
+/// Every builder's `build()` output converts into a [`Query`], so
+/// `(String, Vec<SqlValue>)` tuples can be passed as they are.
+pub trait ConnectionExt {
+    /// Executes a statement and returns the number of rows it changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Sqlite`] if the statement fails or returns rows;
+    /// use [`query_values`](Self::query_values) for `RETURNING`.
+    fn execute_query(&self, query: impl Into<Query>) -> Result<usize, Error>;
+
+    /// Runs a query and returns the values of each row, in column order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Sqlite`] if the query fails.
+    fn query_values(&self, query: impl Into<Query>) -> Result<Vec<Vec<SqlValue>>, Error>;
+
+    /// Runs a typed SELECT and decodes each row into the selection's
+    /// output, e.g. `(i64, String)` for `select::<(Id, Name)>()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Sqlite`] if the query fails, or
+    /// [`Error::Decode`] if a row does not match the selected columns.
+    fn query_select<T: Table, S: DecodeRow<T>>(
+        &self,
+        select: Select<T, HasColumns<S>, HasFrom>,
+    ) -> Result<Vec<S::Output>, Error>;
+}
+
+impl ConnectionExt for Connection {
+    fn execute_query(&self, query: impl Into<Query>) -> Result<usize, Error> {
+        let (sql, params) = query.into().into_parts();
+        let params = Param::from_sql_values(params);
+        Ok(self.execute(&sql, params_from_iter(params))?)
+    }
+
+    fn query_values(&self, query: impl Into<Query>) -> Result<Vec<Vec<SqlValue>>, Error> {
+        let (sql, params) = query.into().into_parts();
+        let params = Param::from_sql_values(params);
+        let mut stmt = self.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(params), row_values)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn query_select<T: Table, S: DecodeRow<T>>(
+        &self,
+        select: Select<T, HasColumns<S>, HasFrom>,
+    ) -> Result<Vec<S::Output>, Error> {
+        let decode = select.row_decoder();
+        self.query_values(select)?
+            .into_iter()
+            .map(|row| decode(row).map_err(Error::Decode))
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+//! Error type of the adapter.
+
+use std::fmt;
+
+use oxide_sql_core::ParseError;
+use oxide_sql_core::builder::DecodeError;
+
+/// An error from running a query or introspecting a database.
+#[derive(Debug)]
+pub enum Error {
+    /// SQLite returned an error.
+    Sqlite(rusqlite::Error),
+    /// A result row does not match the selected columns' types.
+    Decode(DecodeError),
+    /// An index expression or condition read from the database could not
+    /// be parsed.
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sqlite(e) => write!(f, "SQLite error: {e}"),
+            Self::Decode(e) => write!(f, "decode error: {e}"),
+            Self::Parse(e) => write!(f, "parse error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Sqlite(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Sqlite(e)
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
@@ -0,0 +1,272 @@
+//! [`Introspect`] for rusqlite connections.
+
+use std::collections::BTreeMap;
+
+use oxide_sql_core::migrations::introspect::sqlite_helpers::{
+    FOREIGN_KEY_LIST, INDEX_INFO, INDEX_LIST, INDEX_SQL, LIST_TABLES, TABLE_INFO, TABLE_LIST,
+    TABLE_SQL, TABLE_XINFO, autoincrement_column, column_from_pragma,
+    foreign_key_action_from_pragma, generated_from_xinfo, index_parts_from_sql,
+    table_options_from_pragma,
+};
+use oxide_sql_core::migrations::{
+    ForeignKeySnapshot, IndexColumn, IndexSnapshot, IndexType, Introspect, MigrationDialect,
+    SchemaSnapshot, SqliteDialect, TableOption, TableSnapshot,
+};
+use oxide_sql_core::{Expr, ParseError, Parser};
+use rusqlite::Connection;
+
+use crate::error::Error;
+
+/// Reads the schema of the `main` database of a rusqlite [`Connection`].
+///
+/// Tables, columns (including generated ones), `STRICT` and
+/// `WITHOUT ROWID`, indexes, and foreign keys are read from SQLite's
+/// `PRAGMA`s and `sqlite_master`. SQLite does not report column
+/// collations or foreign key constraint names, so those are `None`.
+/// Requires SQLite 3.37 or later, for `PRAGMA table_list`.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::migrations::Introspect;
+/// use oxide_sql_rusqlite::SqliteIntrospector;
+/// use rusqlite::Connection;
+///
+/// let conn = Connection::open_in_memory().unwrap();
+/// conn.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+///     .unwrap();
+///
+/// let schema = SqliteIntrospector::new(&conn).introspect_schema().unwrap();
+/// let users = schema.tables.get("users").unwrap();
+/// assert!(users.column("id").unwrap().primary_key);
+/// assert!(!users.column("name").unwrap().nullable);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SqliteIntrospector<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteIntrospector<'a> {
+    /// Creates an introspector reading through `conn`.
+    #[must_use]
+    pub const fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Reads one table.
+    fn table(&self, name: &str) -> Result<TableSnapshot, Error> {
+        let quoted = SqliteDialect::new().quote_identifier(name);
+        let create_sql: String = self.conn.query_row(TABLE_SQL, [name], |row| row.get(0))?;
+        let autoincrement = autoincrement_column(&create_sql);
+
+        let mut stmt = self
+            .conn
+            .prepare(&TABLE_XINFO.replace("{table}", &quoted))?;
+        let mut rows = stmt.query([])?;
+        let mut columns = Vec::new();
+        while let Some(row) = rows.next()? {
+            let hidden: i64 = row.get("hidden")?;
+            // Hidden columns of virtual tables are not part of the schema.
+            if hidden == 1 {
+                continue;
+            }
+            let name: String = row.get("name")?;
+            let type_str: String = row.get("type")?;
+            let default: Option<String> = row.get("dflt_value")?;
+            let pk = row.get::<_, i64>("pk")? > 0;
+            // An INTEGER PRIMARY KEY aliases the rowid, which is never
+            // NULL, but SQLite reports it without NOT NULL.
+            let rowid_alias = pk && type_str.eq_ignore_ascii_case("INTEGER");
+            let notnull = row.get("notnull")? || rowid_alias;
+            let mut column = column_from_pragma(&name, &type_str, notnull, default.as_deref(), pk);
+            column.autoincrement = rowid_alias
+                && autoincrement
+                    .as_deref()
+                    .is_some_and(|column| column.eq_ignore_ascii_case(&name));
+            column.generated = generated_from_xinfo(&create_sql, &name, hidden);
+            columns.push(column);
+        }
+
+        let mut table = TableSnapshot {
+            schema: None,
+            name: name.to_string(),
+            columns,
+            indexes: Vec::new(),
+            foreign_keys: self.foreign_keys(&quoted)?,
+            options: self.options(&quoted)?,
+        };
+        self.read_indexes(&quoted, &mut table)?;
+        Ok(table)
+    }
+
+    /// Reads the `STRICT` and `WITHOUT ROWID` flags of a table.
+    fn options(&self, quoted: &str) -> Result<Vec<TableOption>, Error> {
+        let mut stmt = self.conn.prepare(&TABLE_LIST.replace("{table}", quoted))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            if row.get::<_, String>("schema")? == "main" {
+                return Ok(table_options_from_pragma(
+                    row.get("wr")?,
+                    row.get("strict")?,
+                ));
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Reads the indexes of a table. Single-column `UNIQUE` constraints
+    /// mark their column unique; primary key indexes are skipped.
+    fn read_indexes(&self, quoted: &str, table: &mut TableSnapshot) -> Result<(), Error> {
+        let mut stmt = self.conn.prepare(&INDEX_LIST.replace("{table}", quoted))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let name: String = row.get("name")?;
+            let unique: bool = row.get("unique")?;
+            let origin: String = row.get("origin")?;
+            let partial: bool = row.get("partial")?;
+            let columns = self.index_columns(&name)?;
+            match origin.as_str() {
+                "u" => {
+                    if let [Some(column)] = columns.as_slice() {
+                        table
+                            .columns
+                            .iter_mut()
+                            .filter(|c| &c.name == column)
+                            .for_each(|c| c.unique = true);
+                    }
+                }
+                "c" => {
+                    let (columns, condition) = if partial || columns.contains(&None) {
+                        self.index_parts(&name)?
+                    } else {
+                        (
+                            columns
+                                .into_iter()
+                                .flatten()
+                                .map(IndexColumn::Column)
+                                .collect(),
+                            None,
+                        )
+                    };
+                    table.indexes.push(IndexSnapshot {
+                        name,
+                        columns,
+                        unique,
+                        index_type: IndexType::BTree,
+                        condition,
+                    });
+                }
+                _ => {}
+            }
+        }
+        table.indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(())
+    }
+
+    /// Returns the columns of an index, `None` for expressions.
+    fn index_columns(&self, index: &str) -> Result<Vec<Option<String>>, Error> {
+        let quoted = SqliteDialect::new().quote_identifier(index);
+        let mut stmt = self.conn.prepare(&INDEX_INFO.replace("{index}", &quoted))?;
+        let columns = stmt
+            .query_map([], |row| row.get("name"))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(columns)
+    }
+
+    /// Reads the elements and condition of an index from its SQL.
+    fn index_parts(&self, index: &str) -> Result<(Vec<IndexColumn>, Option<Expr>), Error> {
+        let sql: String = self.conn.query_row(INDEX_SQL, [index], |row| row.get(0))?;
+        let (elements, condition) = index_parts_from_sql(&sql).unwrap_or_default();
+        let columns = elements
+            .iter()
+            .map(|element| index_column(element))
+            .collect::<Result<_, _>>()?;
+        let condition = condition
+            .map(|sql| Parser::new(&sql).parse_expr())
+            .transpose()?;
+        Ok((columns, condition))
+    }
+
+    /// Reads the foreign keys of a table. A reference without columns
+    /// targets the referenced table's primary key.
+    fn foreign_keys(&self, quoted: &str) -> Result<Vec<ForeignKeySnapshot>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(&FOREIGN_KEY_LIST.replace("{table}", quoted))?;
+        let mut rows = stmt.query([])?;
+        let mut keys: BTreeMap<i64, (ForeignKeySnapshot, Vec<Option<String>>)> = BTreeMap::new();
+        while let Some(row) = rows.next()? {
+            let (key, to) = keys.entry(row.get("id")?).or_insert_with(|| {
+                (
+                    ForeignKeySnapshot {
+                        name: None,
+                        columns: Vec::new(),
+                        references_table: String::new(),
+                        references_columns: Vec::new(),
+                        on_delete: None,
+                        on_update: None,
+                    },
+                    Vec::new(),
+                )
+            });
+            key.references_table = row.get("table")?;
+            key.on_update = foreign_key_action_from_pragma(&row.get::<_, String>("on_update")?);
+            key.on_delete = foreign_key_action_from_pragma(&row.get::<_, String>("on_delete")?);
+            key.columns.push(row.get("from")?);
+            to.push(row.get("to")?);
+        }
+        keys.into_values()
+            .map(|(mut key, to)| {
+                key.references_columns = if to.iter().all(Option::is_some) {
+                    to.into_iter().flatten().collect()
+                } else {
+                    self.primary_key(&key.references_table)?
+                };
+                Ok(key)
+            })
+            .collect()
+    }
+
+    /// Returns the primary key columns of a table, in key order.
+    fn primary_key(&self, table: &str) -> Result<Vec<String>, Error> {
+        let quoted = SqliteDialect::new().quote_identifier(table);
+        let mut stmt = self.conn.prepare(&TABLE_INFO.replace("{table}", &quoted))?;
+        let mut columns: Vec<(i64, String)> = stmt
+            .query_map([], |row| Ok((row.get("pk")?, row.get("name")?)))?
+            .filter(|column| !matches!(column, Ok((0, _))))
+            .collect::<rusqlite::Result<_>>()?;
+        columns.sort();
+        Ok(columns.into_iter().map(|(_, name)| name).collect())
+    }
+}
+
+/// Parses an index element, dropping the parentheses migrations put
+/// around expressions.
+fn index_column(sql: &str) -> Result<IndexColumn, ParseError> {
+    let mut expr = Parser::new(sql).parse_expr()?;
+    while let Expr::Paren(inner) = expr {
+        expr = *inner;
+    }
+    Ok(match expr {
+        Expr::Column {
+            table: None, name, ..
+        } => IndexColumn::Column(name.value),
+        expr => IndexColumn::Expr(expr),
+    })
+}
+
+impl Introspect for SqliteIntrospector<'_> {
+    type Error = Error;
+
+    fn introspect_schema(&self) -> Result<SchemaSnapshot, Error> {
+        let mut stmt = self.conn.prepare(LIST_TABLES)?;
+        let names: Vec<String> = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut snapshot = SchemaSnapshot::new();
+        for name in names {
+            snapshot.add_table(self.table(&name)?);
+        }
+        Ok(snapshot)
+    }
+}
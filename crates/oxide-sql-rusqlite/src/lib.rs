@@ -0,0 +1,61 @@
+//! # oxide-sql-rusqlite
+//!
+//! Runs `oxide-sql-core` queries and migrations on a synchronous
+//! [rusqlite] connection, for embedded and single-threaded applications.
+//!
+//! - [`Param`] binds [`SqlValue`](oxide_sql_core::builder::SqlValue)
+//!   parameters as rusqlite values, and [`row_values`] reads result rows
+//!   back into `SqlValue`s.
+//! - [`ConnectionExt`] executes builder output on a `Connection`, and
+//!   decodes typed SELECTs into their selection's output tuple.
+//! - [`SqliteIntrospector`] implements
+//!   [`Introspect`](oxide_sql_core::migrations::Introspect), so a live
+//!   database can be diffed against `#[derive(Table)]` structs.
+//!
+//! Migrations run through
+//! [`MigrationRunner::apply_pending`](oxide_sql_core::migrations::MigrationRunner::apply_pending)
+//! with `|sql| conn.execute_batch(sql)`.
+//!
+//! Enable the `bundled` feature to compile SQLite instead of linking the
+//! system library.
+//!
+//! [rusqlite]: https://docs.rs/rusqlite
+//!
+//! ## Example
+//!
+//! ```rust
+//! use oxide_sql_core::builder::{InsertDyn, SelectDyn, SqlValue, dyn_col};
+//! use oxide_sql_rusqlite::ConnectionExt;
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! conn.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+//!     .unwrap();
+//!
+//! let insert = InsertDyn::new()
+//!     .into_table("users")
+//!     .columns(&["name"])
+//!     .values(vec!["Alice"])
+//!     .build();
+//! assert_eq!(conn.execute_query(insert).unwrap(), 1);
+//!
+//! let select = SelectDyn::new()
+//!     .columns(&["id", "name"])
+//!     .from("users")
+//!     .where_clause(dyn_col("name").eq("Alice"))
+//!     .build();
+//! assert_eq!(
+//!     conn.query_values(select).unwrap(),
+//!     [[SqlValue::Int(1), SqlValue::from("Alice")]]
+//! );
+//! ```
+
+mod connection;
+mod error;
+mod introspect;
+mod value;
+
+pub use connection::ConnectionExt;
+pub use error::Error;
+pub use introspect::SqliteIntrospector;
+pub use value::{Param, row_values, sql_value};
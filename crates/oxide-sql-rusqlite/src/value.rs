@@ -0,0 +1,103 @@
+//! Conversion between [`SqlValue`] and rusqlite values.
+
+use oxide_sql_core::builder::{DriverValue, PrimitiveValue, SqlValue};
+use rusqlite::Row;
+use rusqlite::types::{FromSqlError, ToSql, ToSqlOutput, Type, Value, ValueRef};
+
+/// A bound parameter, converted from a [`SqlValue`].
+///
+/// SQLite has no boolean type: booleans are bound as `0` and `1`.
+/// Feature-gated values (timestamps, UUIDs, decimals, JSON) are bound as
+/// text, in the formats of [`SqlValue::into_primitive`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param(pub Value);
+
+impl DriverValue for Param {
+    fn from_primitive(value: PrimitiveValue) -> Self {
+        Self(match value {
+            PrimitiveValue::Null => Value::Null,
+            PrimitiveValue::Bool(b) => Value::Integer(i64::from(b)),
+            PrimitiveValue::Int(n) => Value::Integer(n),
+            PrimitiveValue::Float(f) => Value::Real(f),
+            PrimitiveValue::Text(s) => Value::Text(s),
+            PrimitiveValue::Blob(b) => Value::Blob(b),
+        })
+    }
+}
+
+impl ToSql for Param {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+/// Converts a value read from SQLite into a [`SqlValue`].
+///
+/// # Errors
+///
+/// Returns a `FromSqlError` if a text value is not valid UTF-8.
+pub fn sql_value(value: ValueRef<'_>) -> Result<SqlValue, FromSqlError> {
+    Ok(match value {
+        ValueRef::Null => SqlValue::Null,
+        ValueRef::Integer(n) => SqlValue::Int(n),
+        ValueRef::Real(f) => SqlValue::Float(f),
+        ValueRef::Text(_) => SqlValue::Text(value.as_str()?.to_string()),
+        ValueRef::Blob(b) => SqlValue::Blob(b.to_vec()),
+    })
+}
+
+/// Reads every value of a result row, in column order.
+///
+/// # Errors
+///
+/// Returns an error if a value cannot be read or is text that is not
+/// valid UTF-8.
+pub fn row_values(row: &Row<'_>) -> rusqlite::Result<Vec<SqlValue>> {
+    (0..row.as_ref().column_count())
+        .map(|index| {
+            let value = row.get_ref(index)?;
+            sql_value(value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_from_sql_values() {
+        let params = Param::from_sql_values(vec![
+            SqlValue::Null,
+            SqlValue::Bool(true),
+            SqlValue::Int(7),
+            SqlValue::Float(1.5),
+            SqlValue::Text(String::from("a")),
+            SqlValue::Blob(vec![1, 2]),
+        ]);
+        assert_eq!(
+            params,
+            [
+                Param(Value::Null),
+                Param(Value::Integer(1)),
+                Param(Value::Integer(7)),
+                Param(Value::Real(1.5)),
+                Param(Value::Text(String::from("a"))),
+                Param(Value::Blob(vec![1, 2])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sql_value_from_value_ref() {
+        assert_eq!(sql_value(ValueRef::Null), Ok(SqlValue::Null));
+        assert_eq!(sql_value(ValueRef::Integer(3)), Ok(SqlValue::Int(3)));
+        assert_eq!(
+            sql_value(ValueRef::Text(b"abc")),
+            Ok(SqlValue::Text(String::from("abc")))
+        );
+        assert!(sql_value(ValueRef::Text(&[0xff])).is_err());
+    }
+}
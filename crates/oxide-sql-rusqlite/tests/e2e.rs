@@ -0,0 +1,258 @@
+//! End-to-end tests running builders, migrations, and introspection
+//! against an in-memory SQLite database.
+
+use oxide_sql_core::builder::{DecodeError, Insert, Select, SqlValue, col};
+use oxide_sql_core::migrations::{
+    CreateTableBuilder, IndexColumn, Introspect, Migration, MigrationRunner, MigrationState,
+    Operation, SchemaSnapshot, SqliteDialect, TableSnapshot, auto_diff_schema_for_dialect,
+    initial_from_tables, integer, text,
+};
+use oxide_sql_derive::Table;
+use oxide_sql_rusqlite::{ConnectionExt, Error, SqliteIntrospector};
+use rusqlite::Connection;
+
+// ------------------------------------------------------------------
+// Table definitions
+// ------------------------------------------------------------------
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "authors")]
+pub struct Author {
+    #[column(primary_key, autoincrement)]
+    pub id: i64,
+    pub name: String,
+    #[column(unique)]
+    pub email: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(
+    name = "posts",
+    index(columns = "author_id"),
+    index(expr = "lower(title)", unique, where = "draft = 0")
+)]
+pub struct Post {
+    #[column(primary_key)]
+    pub id: i64,
+    #[column(references = "authors(id)")]
+    pub author_id: i64,
+    pub title: String,
+    pub draft: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Table)]
+#[table(name = "tags", strict, without_rowid)]
+pub struct Tag {
+    #[column(primary_key)]
+    pub name: String,
+}
+
+// ------------------------------------------------------------------
+// Helpers
+// ------------------------------------------------------------------
+
+/// Creates the tables with the migrations the derives generate.
+fn create_tables(conn: &Connection) {
+    let dialect = SqliteDialect::new();
+    let diff = initial_from_tables(
+        &[
+            TableSnapshot::from_table_schema::<AuthorTable>,
+            TableSnapshot::from_table_schema::<PostTable>,
+            TableSnapshot::from_table_schema::<TagTable>,
+        ],
+        &dialect,
+    );
+    for sql in diff.to_sql(&dialect) {
+        conn.execute_batch(&sql).unwrap();
+    }
+}
+
+/// Returns the schema the derived tables describe.
+fn desired(dialect: &SqliteDialect) -> SchemaSnapshot {
+    let mut schema = SchemaSnapshot::new();
+    schema.add_from_table_schema::<AuthorTable>(dialect);
+    schema.add_from_table_schema::<PostTable>(dialect);
+    schema.add_from_table_schema::<TagTable>(dialect);
+    schema
+}
+
+/// Inserts an author and returns the number of changed rows.
+fn insert_author(conn: &Connection, name: &str, email: &str) -> usize {
+    let insert = Insert::<AuthorTable, _>::new()
+        .set(Author::name(), name)
+        .set(Author::email(), email)
+        .build();
+    conn.execute_query(insert).unwrap()
+}
+
+// ------------------------------------------------------------------
+// Tests
+// ------------------------------------------------------------------
+
+#[test]
+fn test_execute_and_query_typed() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_tables(&conn);
+    assert_eq!(insert_author(&conn, "alice", "alice@example.com"), 1);
+    assert_eq!(insert_author(&conn, "bob", "bob@example.com"), 1);
+
+    let select = Select::<AuthorTable, _, _>::new()
+        .select::<(AuthorColumns::Id, AuthorColumns::Name)>()
+        .from_table()
+        .where_col(Author::name(), col(Author::name()).eq("bob"));
+    assert_eq!(
+        conn.query_select(select).unwrap(),
+        [(2, String::from("bob"))]
+    );
+}
+
+#[test]
+fn test_bound_values_round_trip() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch("CREATE TABLE t (b, i, f, s, x, n)")
+        .unwrap();
+    let insert = (
+        String::from("INSERT INTO t VALUES (?, ?, ?, ?, ?, ?)"),
+        vec![
+            SqlValue::Bool(true),
+            SqlValue::Int(-7),
+            SqlValue::Float(1.5),
+            SqlValue::Text(String::from("it's")),
+            SqlValue::Blob(vec![0, 255]),
+            SqlValue::Null,
+        ],
+    );
+    conn.execute_query(insert).unwrap();
+
+    let rows = conn
+        .query_values((String::from("SELECT * FROM t"), vec![]))
+        .unwrap();
+    assert_eq!(
+        rows,
+        [[
+            SqlValue::Int(1),
+            SqlValue::Int(-7),
+            SqlValue::Float(1.5),
+            SqlValue::Text(String::from("it's")),
+            SqlValue::Blob(vec![0, 255]),
+            SqlValue::Null,
+        ]]
+    );
+}
+
+#[test]
+fn test_query_select_reports_decode_errors() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_tables(&conn);
+    conn.execute_batch("INSERT INTO authors (id, name, email) VALUES (1, X'00', 'a@example.com')")
+        .unwrap();
+
+    let select = Select::<AuthorTable, _, _>::new()
+        .select::<(AuthorColumns::Id, AuthorColumns::Name)>()
+        .from_table();
+    let err = conn.query_select(select).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Decode(DecodeError::TypeMismatch { index: 1, .. })
+    ));
+}
+
+#[test]
+fn test_introspected_schema_matches_derived_tables() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_tables(&conn);
+
+    let dialect = SqliteDialect::new();
+    let current = SqliteIntrospector::new(&conn).introspect_schema().unwrap();
+    let diff = auto_diff_schema_for_dialect(&current, &desired(&dialect), &dialect);
+    assert!(diff.is_empty(), "{diff:?}");
+
+    let posts = &current.tables["posts"];
+    assert_eq!(posts.foreign_keys[0].references_table, "authors");
+    assert_eq!(posts.foreign_keys[0].references_columns, ["id"]);
+    assert!(posts.indexes[1].condition.is_some());
+    assert!(current.tables["authors"].column("email").unwrap().unique);
+    assert!(
+        current.tables["authors"]
+            .column("id")
+            .unwrap()
+            .autoincrement
+    );
+}
+
+#[test]
+fn test_introspection_flags_only_the_autoincrement_column() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE \"it's\" (
+             id INTEGER, note TEXT DEFAULT 'AUTOINCREMENT',
+             PRIMARY KEY (id AUTOINCREMENT)
+         );
+         CREATE INDEX \"it's_note\" ON \"it's\" (lower(note));",
+    )
+    .unwrap();
+
+    let current = SqliteIntrospector::new(&conn).introspect_schema().unwrap();
+    let table = &current.tables["it's"];
+    assert!(table.column("id").unwrap().autoincrement);
+    assert!(!table.column("note").unwrap().autoincrement);
+    assert_eq!(table.indexes[0].name, "it's_note");
+    assert!(matches!(table.indexes[0].columns[0], IndexColumn::Expr(_)));
+}
+
+#[test]
+fn test_introspection_sees_changes() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_tables(&conn);
+    conn.execute_batch("ALTER TABLE posts ADD COLUMN subtitle TEXT")
+        .unwrap();
+
+    let dialect = SqliteDialect::new();
+    let current = SqliteIntrospector::new(&conn).introspect_schema().unwrap();
+    let diff = auto_diff_schema_for_dialect(&current, &desired(&dialect), &dialect);
+    assert!(matches!(
+        diff.operations.as_slice(),
+        [Operation::DropColumn(op)] if op.column == "subtitle"
+    ));
+}
+
+struct CreateNotes;
+
+impl Migration for CreateNotes {
+    const ID: &'static str = "0001_create_notes";
+
+    fn up() -> Vec<Operation> {
+        vec![
+            CreateTableBuilder::new()
+                .name("notes")
+                .column(integer("id").primary_key().build())
+                .column(text("body").build())
+                .build()
+                .into(),
+        ]
+    }
+
+    fn down() -> Vec<Operation> {
+        vec![Operation::drop_table("notes")]
+    }
+}
+
+#[test]
+fn test_apply_migrations() {
+    let conn = Connection::open_in_memory().unwrap();
+    let mut runner = MigrationRunner::new(SqliteDialect::new());
+    runner.register::<CreateNotes>();
+
+    let mut state = MigrationState::new();
+    let reports = runner
+        .apply_pending(&mut state, |sql| conn.execute_batch(sql))
+        .unwrap();
+    assert_eq!(reports.len(), 1);
+    assert!(state.is_applied("0001_create_notes"));
+
+    let schema = SqliteIntrospector::new(&conn).introspect_schema().unwrap();
+    assert!(schema.tables.contains_key("notes"));
+}
@@ -0,0 +1,160 @@
+//! SQLite [JSON1] function builders.
+//!
+//! These produce [`ExprBuilder`]s for JSON stored as text in SQLite
+//! columns. Paths are bound as parameters. Combine them with the core
+//! builders: [`json_each`] and [`json_each_path`] are table-valued and
+//! go in [`SelectDyn::from_expr`] or [`SelectDyn::cross_join_expr`].
+//!
+//! ```rust
+//! use oxide_sql_core::builder::{SelectDyn, dyn_col};
+//! use oxide_sql_sqlite::builder::json::{json_each_path, json_extract, json_group_array};
+//!
+//! let (sql, params) = SelectDyn::new()
+//!     .columns(&["users.id"])
+//!     .expr(json_group_array(dyn_col("tag.value").into()), "tags")
+//!     .from("users")
+//!     .cross_join_expr(json_each_path(dyn_col("users.data").into(), "$.tags"), "tag")
+//!     .where_clause(json_extract(dyn_col("users.data").into(), "$.active").eq(1))
+//!     .group_by(&["users.id"])
+//!     .build();
+//!
+//! assert_eq!(
+//!     sql,
+//!     "SELECT users.id, json_group_array(tag.value) AS tags FROM users \
+//!      CROSS JOIN json_each(users.data, ?) AS tag \
+//!      WHERE json_extract(users.data, ?) = ? GROUP BY users.id"
+//! );
+//! assert_eq!(params.len(), 3);
+//! ```
+//!
+//! [JSON1]: https://www.sqlite.org/json1.html
+//! [`SelectDyn::from_expr`]: oxide_sql_core::builder::SelectDyn::from_expr
+//! [`SelectDyn::cross_join_expr`]: oxide_sql_core::builder::SelectDyn::cross_join_expr
+
+use oxide_sql_core::builder::ExprBuilder;
+use oxide_sql_core::builder::func::call;
+
+/// `json_extract(json, path)`: the value at `path`, as an SQL value.
+#[must_use]
+pub fn json_extract(json: ExprBuilder, path: &str) -> ExprBuilder {
+    call("json_extract", vec![json, ExprBuilder::value(path)])
+}
+
+/// `json_set(json, path, value, ...)`: `json` with each path set to its
+/// value, inserting or replacing.
+///
+/// Text values are stored as JSON strings; wrap a value in
+/// [`json`] to store it as JSON instead.
+#[must_use]
+pub fn json_set(json: ExprBuilder, assignments: Vec<(&str, ExprBuilder)>) -> ExprBuilder {
+    let mut args = vec![json];
+    for (path, value) in assignments {
+        args.push(ExprBuilder::value(path));
+        args.push(value);
+    }
+    call("json_set", args)
+}
+
+/// `json(value)`: `value` parsed and minified as JSON.
+#[must_use]
+pub fn json(value: ExprBuilder) -> ExprBuilder {
+    call("json", vec![value])
+}
+
+/// `json_each(json)`: one row per element of the top-level array or
+/// object, with columns `key`, `value`, `type`, `atom`, `id`, `parent`,
+/// `fullkey`, and `path`.
+#[must_use]
+pub fn json_each(json: ExprBuilder) -> ExprBuilder {
+    call("json_each", vec![json])
+}
+
+/// `json_each(json, path)`: like [`json_each`], over the element at
+/// `path`.
+#[must_use]
+pub fn json_each_path(json: ExprBuilder, path: &str) -> ExprBuilder {
+    call("json_each", vec![json, ExprBuilder::value(path)])
+}
+
+/// `json_group_array(value)`: aggregates the values of a group into a
+/// JSON array.
+#[must_use]
+pub fn json_group_array(value: ExprBuilder) -> ExprBuilder {
+    call("json_group_array", vec![value])
+}
+
+#[cfg(test)]
+mod tests {
+    use oxide_sql_core::builder::value::SqlValue;
+    use oxide_sql_core::builder::{SelectDyn, UpdateDyn, dyn_col};
+
+    use super::*;
+
+    #[test]
+    fn test_json_extract() {
+        let (sql, params) = json_extract(dyn_col("data").into(), "$.name")
+            .eq("Ada")
+            .build();
+        assert_eq!(sql, "json_extract(data, ?) = ?");
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Text(String::from("$.name")),
+                SqlValue::Text(String::from("Ada")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_set_in_update() {
+        let (sql, params) = UpdateDyn::new()
+            .table("users")
+            .set_expr(
+                "data",
+                json_set(
+                    dyn_col("data").into(),
+                    vec![
+                        ("$.name", ExprBuilder::value("Ada")),
+                        ("$.tags", json(ExprBuilder::value("[\"a\"]"))),
+                    ],
+                ),
+            )
+            .where_clause(dyn_col("id").eq(1))
+            .build();
+
+        assert_eq!(
+            sql,
+            "UPDATE users SET data = json_set(data, ?, ?, ?, json(?)) WHERE id = ?"
+        );
+        assert_eq!(params.len(), 5);
+        assert_eq!(params[0], SqlValue::Text(String::from("$.name")));
+        assert_eq!(params[4], SqlValue::Int(1));
+    }
+
+    #[test]
+    fn test_json_each_as_from_source() {
+        let (sql, params) = SelectDyn::new()
+            .columns(&["e.key", "e.value"])
+            .from_expr(json_each(ExprBuilder::value("{\"a\": 1}")), "e")
+            .build();
+
+        assert_eq!(sql, "SELECT e.key, e.value FROM json_each(?) AS e");
+        assert_eq!(params, vec![SqlValue::Text(String::from("{\"a\": 1}"))]);
+    }
+
+    #[test]
+    fn test_json_group_array() {
+        let (sql, params) = SelectDyn::new()
+            .columns(&["kind"])
+            .expr(json_group_array(dyn_col("id").into()), "ids")
+            .from("events")
+            .group_by(&["kind"])
+            .build();
+
+        assert_eq!(
+            sql,
+            "SELECT kind, json_group_array(id) AS ids FROM events GROUP BY kind"
+        );
+        assert!(params.is_empty());
+    }
+}
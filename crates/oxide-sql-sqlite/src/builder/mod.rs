@@ -1,5 +1,6 @@
 //! SQLite-specific SQL builders.
 
+pub mod json;
 mod upsert;
 
 pub use upsert::UpsertBuilder;
@@ -2,7 +2,16 @@
 
 use std::marker::PhantomData;
 
+use oxide_sql_core::builder::Query;
+use oxide_sql_core::builder::ident::{sanitize_column_for, sanitize_table_for};
 use oxide_sql_core::builder::value::{SqlValue, ToSqlValue};
+use oxide_sql_core::dialect::Dialect;
+use oxide_sql_core::schema::Column;
+
+use crate::SqliteDialect;
+
+/// The dialect whose reserved keywords decide which names are quoted.
+const DIALECT: SqliteDialect = SqliteDialect::new();
 
 // Typestate markers
 
@@ -16,17 +25,114 @@ pub struct NoValues;
 pub struct HasValues;
 /// Marker: No conflict target specified yet.
 pub struct NoConflict;
-/// Marker: Conflict target has been specified.
+/// Marker: An `ON CONFLICT (target)` clause awaits its action.
 pub struct HasConflict;
+/// Marker: A target-less `ON CONFLICT` clause awaits its action.
+pub struct HasAnyConflict;
+/// Marker: Every clause has an action; more clauses may follow.
+pub struct Resolved;
+/// Marker: The target-less clause has its action; it must be the last.
+pub struct ResolvedAny;
+
+/// Conflict states in which every clause has an action, so the statement
+/// can be built.
+pub trait ConflictResolved {}
+
+impl ConflictResolved for Resolved {}
+impl ConflictResolved for ResolvedAny {}
+
+/// States in which a new `ON CONFLICT` clause may start.
+pub trait AcceptsConflict {}
+
+impl AcceptsConflict for NoConflict {}
+impl AcceptsConflict for Resolved {}
+
+/// One `ON CONFLICT` clause: its target columns and action.
+#[derive(Default, Clone)]
+struct ConflictClause {
+    /// Conflict target columns; empty for a clause without a target.
+    columns: Vec<String>,
+    update_columns: Vec<String>,
+    do_nothing: bool,
+}
 
 /// A type-safe UPSERT (INSERT ... ON CONFLICT) builder for SQLite.
+///
+/// Each [`on_conflict`](UpsertBuilder::on_conflict) starts an
+/// `ON CONFLICT` clause, and [`do_nothing`](UpsertBuilder::do_nothing)
+/// or [`do_update`](UpsertBuilder::do_update) sets its action. SQLite
+/// (3.35+) tries the clauses in order; only the last may omit its
+/// target. The typestate enforces both: a clause needs an action before
+/// the next one starts or the statement is built, and
+/// [`on_any_conflict`](UpsertBuilder::on_any_conflict) starts the last
+/// clause.
+///
+/// # Example
+///
+/// ```rust
+/// use oxide_sql_core::builder::value::ToSqlValue;
+/// use oxide_sql_sqlite::UpsertBuilder;
+///
+/// let sql = UpsertBuilder::new()
+///     .into_table("users")
+///     .columns(&["id", "email", "name"])
+///     .values(vec![
+///         1_i64.to_sql_value(),
+///         "alice@example.com".to_sql_value(),
+///         "Alice".to_sql_value(),
+///     ])
+///     .on_conflict("id", &[])
+///     .do_update(&["name"])
+///     .on_any_conflict()
+///     .do_nothing()
+///     .build_sql();
+///
+/// assert_eq!(
+///     sql,
+///     "INSERT INTO users (id, email, name) VALUES (?, ?, ?) \
+///      ON CONFLICT (id) DO UPDATE SET name = excluded.name \
+///      ON CONFLICT DO NOTHING"
+/// );
+/// ```
+///
+/// A clause without an action does not compile:
+///
+/// ```compile_fail
+/// use oxide_sql_core::builder::value::ToSqlValue;
+/// use oxide_sql_sqlite::UpsertBuilder;
+///
+/// let _ = UpsertBuilder::new()
+///     .into_table("users")
+///     .columns(&["id"])
+///     .values(vec![1_i64.to_sql_value()])
+///     .on_conflict("id", &[])
+///     .on_any_conflict()
+///     .do_nothing()
+///     .build();
+/// ```
+///
+/// Nor does a clause after a target-less one:
+///
+/// ```compile_fail
+/// use oxide_sql_core::builder::value::ToSqlValue;
+/// use oxide_sql_sqlite::UpsertBuilder;
+///
+/// let _ = UpsertBuilder::new()
+///     .into_table("users")
+///     .columns(&["id"])
+///     .values(vec![1_i64.to_sql_value()])
+///     .on_any_conflict()
+///     .do_nothing()
+///     .on_conflict("id", &[])
+///     .do_nothing()
+///     .build();
+/// ```
 pub struct UpsertBuilder<Table, Values, Conflict> {
     table: Option<String>,
     columns: Vec<String>,
-    values: Vec<SqlValue>,
-    conflict_columns: Vec<String>,
-    update_columns: Vec<String>,
-    do_nothing: bool,
+    rows: Vec<Vec<SqlValue>>,
+    clauses: Vec<ConflictClause>,
+    returning: Vec<String>,
     _state: PhantomData<(Table, Values, Conflict)>,
 }
 
@@ -37,10 +143,9 @@ impl UpsertBuilder<NoTable, NoValues, NoConflict> {
         Self {
             table: None,
             columns: vec![],
-            values: vec![],
-            conflict_columns: vec![],
-            update_columns: vec![],
-            do_nothing: false,
+            rows: vec![],
+            clauses: vec![],
+            returning: vec![],
             _state: PhantomData,
         }
     }
@@ -58,12 +163,11 @@ impl<Values, Conflict> UpsertBuilder<NoTable, Values, Conflict> {
     #[must_use]
     pub fn into_table(self, table: &str) -> UpsertBuilder<HasTable, Values, Conflict> {
         UpsertBuilder {
-            table: Some(String::from(table)),
+            table: Some(sanitize_table_for(table, &DIALECT)),
             columns: self.columns,
-            values: self.values,
-            conflict_columns: self.conflict_columns,
-            update_columns: self.update_columns,
-            do_nothing: self.do_nothing,
+            rows: self.rows,
+            clauses: self.clauses,
+            returning: self.returning,
             _state: PhantomData,
         }
     }
@@ -74,7 +178,10 @@ impl<Values, Conflict> UpsertBuilder<HasTable, Values, Conflict> {
     /// Specifies the columns to insert into.
     #[must_use]
     pub fn columns(mut self, cols: &[&str]) -> Self {
-        self.columns = cols.iter().map(|s| String::from(*s)).collect();
+        self.columns = cols
+            .iter()
+            .map(|s| sanitize_column_for(s, &DIALECT))
+            .collect();
         self
     }
 }
@@ -87,50 +194,165 @@ impl<Conflict> UpsertBuilder<HasTable, NoValues, Conflict> {
         self,
         vals: Vec<T>,
     ) -> UpsertBuilder<HasTable, HasValues, Conflict> {
-        let sql_values: Vec<SqlValue> = vals.into_iter().map(ToSqlValue::to_sql_value).collect();
+        self.values_many(vec![vals])
+    }
+
+    /// Adds multiple rows of values to insert.
+    #[must_use]
+    pub fn values_many<T: ToSqlValue>(
+        self,
+        rows: Vec<Vec<T>>,
+    ) -> UpsertBuilder<HasTable, HasValues, Conflict> {
         UpsertBuilder {
             table: self.table,
             columns: self.columns,
-            values: sql_values,
-            conflict_columns: self.conflict_columns,
-            update_columns: self.update_columns,
-            do_nothing: self.do_nothing,
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(ToSqlValue::to_sql_value).collect())
+                .collect(),
+            clauses: self.clauses,
+            returning: self.returning,
             _state: PhantomData,
         }
     }
 }
 
-// Transition: NoConflict -> HasConflict
-impl UpsertBuilder<HasTable, HasValues, NoConflict> {
-    /// Specifies the conflict target columns.
+// Methods available after adding values
+impl<Conflict> UpsertBuilder<HasTable, HasValues, Conflict> {
+    /// Adds another row of values.
+    #[must_use]
+    pub fn and_values<T: ToSqlValue>(mut self, vals: Vec<T>) -> Self {
+        self.rows
+            .push(vals.into_iter().map(ToSqlValue::to_sql_value).collect());
+        self
+    }
+}
+
+// Transition: NoConflict or Resolved -> HasConflict or HasAnyConflict
+impl<Conflict: AcceptsConflict> UpsertBuilder<HasTable, HasValues, Conflict> {
+    /// Starts an `ON CONFLICT` clause on the target columns `first` and
+    /// `rest`.
+    ///
+    /// The target always has a column; use
+    /// [`on_any_conflict`](Self::on_any_conflict) for a clause without
+    /// one.
+    #[must_use]
+    pub fn on_conflict(
+        self,
+        first: &str,
+        rest: &[&str],
+    ) -> UpsertBuilder<HasTable, HasValues, HasConflict> {
+        let columns = std::iter::once(first)
+            .chain(rest.iter().copied())
+            .map(|s| sanitize_column_for(s, &DIALECT))
+            .collect();
+        self.push_clause(columns)
+    }
+
+    /// Starts the last `ON CONFLICT` clause, without a target, which
+    /// handles a conflict on any uniqueness constraint.
+    #[must_use]
+    pub fn on_any_conflict(self) -> UpsertBuilder<HasTable, HasValues, HasAnyConflict> {
+        self.push_clause(vec![])
+    }
+
+    /// Appends a clause on `columns` and moves to the `Next` state.
+    fn push_clause<Next>(
+        mut self,
+        columns: Vec<String>,
+    ) -> UpsertBuilder<HasTable, HasValues, Next> {
+        self.clauses.push(ConflictClause {
+            columns,
+            ..ConflictClause::default()
+        });
+        self.into_state()
+    }
+}
+
+// Transition: HasConflict -> Resolved
+impl UpsertBuilder<HasTable, HasValues, HasConflict> {
+    /// Sets DO NOTHING as the action of the current clause.
+    #[must_use]
+    pub fn do_nothing(self) -> UpsertBuilder<HasTable, HasValues, Resolved> {
+        self.set_do_nothing()
+    }
+
+    /// Sets DO UPDATE with specified columns as the action of the
+    /// current clause.
+    #[must_use]
+    pub fn do_update(self, cols: &[&str]) -> UpsertBuilder<HasTable, HasValues, Resolved> {
+        self.set_do_update(cols)
+    }
+}
+
+// Transition: HasAnyConflict -> ResolvedAny
+impl UpsertBuilder<HasTable, HasValues, HasAnyConflict> {
+    /// Sets DO NOTHING as the action of the target-less clause.
+    #[must_use]
+    pub fn do_nothing(self) -> UpsertBuilder<HasTable, HasValues, ResolvedAny> {
+        self.set_do_nothing()
+    }
+
+    /// Sets DO UPDATE with specified columns as the action of the
+    /// target-less clause.
     #[must_use]
-    pub fn on_conflict(self, cols: &[&str]) -> UpsertBuilder<HasTable, HasValues, HasConflict> {
+    pub fn do_update(self, cols: &[&str]) -> UpsertBuilder<HasTable, HasValues, ResolvedAny> {
+        self.set_do_update(cols)
+    }
+}
+
+impl<Table, Values, Conflict> UpsertBuilder<Table, Values, Conflict> {
+    /// Sets DO NOTHING on the current clause and moves to `Next`.
+    fn set_do_nothing<Next>(mut self) -> UpsertBuilder<Table, Values, Next> {
+        if let Some(clause) = self.clauses.last_mut() {
+            clause.do_nothing = true;
+        }
+        self.into_state()
+    }
+
+    /// Sets DO UPDATE on the current clause and moves to `Next`.
+    fn set_do_update<Next>(mut self, cols: &[&str]) -> UpsertBuilder<Table, Values, Next> {
+        if let Some(clause) = self.clauses.last_mut() {
+            clause.update_columns = cols
+                .iter()
+                .map(|s| sanitize_column_for(s, &DIALECT))
+                .collect();
+        }
+        self.into_state()
+    }
+
+    /// Moves to another typestate, keeping every field.
+    fn into_state<Next>(self) -> UpsertBuilder<Table, Values, Next> {
         UpsertBuilder {
             table: self.table,
             columns: self.columns,
-            values: self.values,
-            conflict_columns: cols.iter().map(|s| String::from(*s)).collect(),
-            update_columns: self.update_columns,
-            do_nothing: self.do_nothing,
+            rows: self.rows,
+            clauses: self.clauses,
+            returning: self.returning,
             _state: PhantomData,
         }
     }
 }
 
-// Methods available after ON CONFLICT
-impl UpsertBuilder<HasTable, HasValues, HasConflict> {
-    /// Sets DO NOTHING action.
+// Methods available once every clause has an action
+impl<Conflict: ConflictResolved> UpsertBuilder<HasTable, HasValues, Conflict> {
+    /// Adds a `RETURNING` clause with the given columns, so the
+    /// inserted or updated row comes back from the same statement.
+    ///
+    /// Needs SQLite 3.35.0+ (see
+    /// [`SqliteDialect::supports_returning`](crate::SqliteDialect)).
+    /// Rows skipped by `DO NOTHING` are not returned.
     #[must_use]
-    pub fn do_nothing(mut self) -> Self {
-        self.do_nothing = true;
+    pub fn returning(mut self, cols: &[&str]) -> Self {
+        self.returning
+            .extend(cols.iter().map(|s| sanitize_column_for(s, &DIALECT)));
         self
     }
 
-    /// Sets DO UPDATE with specified columns.
+    /// Adds a typed column to the `RETURNING` clause.
     #[must_use]
-    pub fn do_update(mut self, cols: &[&str]) -> Self {
-        self.update_columns = cols.iter().map(|s| String::from(*s)).collect();
-        self.do_nothing = false;
+    pub fn returning_col<C: Column>(mut self, _col: C) -> Self {
+        self.returning.push(sanitize_column_for(C::NAME, &DIALECT));
         self
     }
 
@@ -150,38 +372,87 @@ impl UpsertBuilder<HasTable, HasValues, HasConflict> {
             sql.push(')');
         }
 
-        sql.push_str(" VALUES (");
-        let placeholders: Vec<&str> = self.values.iter().map(|_| "?").collect();
-        sql.push_str(&placeholders.join(", "));
-        sql.push(')');
+        sql.push_str(" VALUES ");
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| format!("({})", vec!["?"; row.len()].join(", ")))
+            .collect();
+        sql.push_str(&rows.join(", "));
 
-        params.extend(self.values.clone());
+        params.extend(self.rows.into_iter().flatten());
 
-        sql.push_str(" ON CONFLICT (");
-        sql.push_str(&self.conflict_columns.join(", "));
-        sql.push(')');
+        for clause in &self.clauses {
+            sql.push_str(" ON CONFLICT");
+            if !clause.columns.is_empty() {
+                sql.push_str(" (");
+                sql.push_str(&clause.columns.join(", "));
+                sql.push(')');
+            }
 
-        if self.do_nothing {
-            sql.push_str(" DO NOTHING");
-        } else if !self.update_columns.is_empty() {
-            sql.push_str(" DO UPDATE SET ");
-            let updates: Vec<String> = self
-                .update_columns
-                .iter()
-                .map(|col| format!("{col} = excluded.{col}"))
-                .collect();
-            sql.push_str(&updates.join(", "));
+            if clause.do_nothing {
+                sql.push_str(" DO NOTHING");
+            } else if !clause.update_columns.is_empty() {
+                sql.push_str(" DO UPDATE SET ");
+                let updates: Vec<String> = clause
+                    .update_columns
+                    .iter()
+                    .map(|col| format!("{col} = excluded.{col}"))
+                    .collect();
+                sql.push_str(&updates.join(", "));
+            }
+        }
+
+        if !self.returning.is_empty() {
+            sql.push_str(" RETURNING ");
+            sql.push_str(&self.returning.join(", "));
         }
 
         (sql, params)
     }
 
+    /// Builds the UPSERT statement and returns it as a [`Query`].
+    #[must_use]
+    pub fn build_query(self) -> Query {
+        Query::from(self.build())
+    }
+
     /// Builds the UPSERT statement and returns only the SQL string.
     #[must_use]
     pub fn build_sql(self) -> String {
         let (sql, _) = self.build();
         sql
     }
+
+    /// Builds as many UPSERT statements as needed to keep each within
+    /// [`Dialect::max_parameters`], splitting the rows in order. Each
+    /// statement carries the same `ON CONFLICT` and `RETURNING` clauses.
+    ///
+    /// A row with more values than the limit still gets a statement of
+    /// its own.
+    #[must_use]
+    pub fn build_split<D: Dialect + ?Sized>(self, dialect: &D) -> Vec<Query> {
+        let width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let rows_per_statement = dialect
+            .max_parameters()
+            .checked_div(width)
+            .unwrap_or(self.rows.len())
+            .max(1);
+        let mut rows = self.rows.into_iter().peekable();
+        let mut queries = vec![];
+        while rows.peek().is_some() {
+            let chunk = Self {
+                table: self.table.clone(),
+                columns: self.columns.clone(),
+                rows: rows.by_ref().take(rows_per_statement).collect(),
+                clauses: self.clauses.clone(),
+                returning: self.returning.clone(),
+                _state: PhantomData,
+            };
+            queries.push(chunk.build_query());
+        }
+        queries
+    }
 }
 
 #[cfg(test)]
@@ -194,7 +465,7 @@ mod tests {
             .into_table("users")
             .columns(&["id", "name"])
             .values(vec![1_i64.to_sql_value(), "Alice".to_sql_value()])
-            .on_conflict(&["id"])
+            .on_conflict("id", &[])
             .do_nothing()
             .build();
 
@@ -205,6 +476,24 @@ mod tests {
         assert_eq!(params.len(), 2);
     }
 
+    #[test]
+    fn test_upsert_quotes_sqlite_keywords() {
+        // `user` is reserved in standard SQL but a valid name in SQLite.
+        let (sql, _) = UpsertBuilder::new()
+            .into_table("user")
+            .columns(&["id", "order"])
+            .values(vec![1_i64.to_sql_value(), 2_i64.to_sql_value()])
+            .on_conflict("id", &[])
+            .do_update(&["order"])
+            .build();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO user (id, \"order\") VALUES (?, ?) \
+             ON CONFLICT (id) DO UPDATE SET \"order\" = excluded.\"order\""
+        );
+    }
+
     #[test]
     fn test_upsert_do_update() {
         let (sql, params) = UpsertBuilder::new()
@@ -215,7 +504,7 @@ mod tests {
                 "Alice".to_sql_value(),
                 "alice@example.com".to_sql_value(),
             ])
-            .on_conflict(&["id"])
+            .on_conflict("id", &[])
             .do_update(&["name", "email"])
             .build();
 
@@ -237,7 +526,7 @@ mod tests {
                 2_i64.to_sql_value(),
                 "2024-01-01".to_sql_value(),
             ])
-            .on_conflict(&["user_id", "role_id"])
+            .on_conflict("user_id", &["role_id"])
             .do_update(&["granted_at"])
             .build();
 
@@ -245,6 +534,152 @@ mod tests {
         assert!(sql.contains("DO UPDATE SET granted_at = excluded.granted_at"));
     }
 
+    #[test]
+    fn test_upsert_do_nothing_without_target() {
+        let (sql, params) = UpsertBuilder::new()
+            .into_table("users")
+            .columns(&["id", "email"])
+            .values(vec![1_i64.to_sql_value(), "a@example.com".to_sql_value()])
+            .on_any_conflict()
+            .do_nothing()
+            .build();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, email) VALUES (?, ?) ON CONFLICT DO NOTHING"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_upsert_multiple_conflict_targets() {
+        let sql = UpsertBuilder::new()
+            .into_table("users")
+            .columns(&["id", "email", "name"])
+            .values(vec![
+                1_i64.to_sql_value(),
+                "a@example.com".to_sql_value(),
+                "Alice".to_sql_value(),
+            ])
+            .on_conflict("id", &[])
+            .do_update(&["email", "name"])
+            .on_conflict("email", &[])
+            .do_update(&["name"])
+            .on_any_conflict()
+            .do_nothing()
+            .build_sql();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, email, name) VALUES (?, ?, ?) \
+             ON CONFLICT (id) DO UPDATE SET email = excluded.email, name = excluded.name \
+             ON CONFLICT (email) DO UPDATE SET name = excluded.name \
+             ON CONFLICT DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn test_upsert_returning() {
+        let (sql, params) = UpsertBuilder::new()
+            .into_table("users")
+            .columns(&["email", "name"])
+            .values(vec!["a@example.com", "Alice"])
+            .on_conflict("email", &[])
+            .do_update(&["name"])
+            .returning(&["id", "name"])
+            .build();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (email, name) VALUES (?, ?) \
+             ON CONFLICT (email) DO UPDATE SET name = excluded.name \
+             RETURNING id, name"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_upsert_returning_typed_column() {
+        struct Users;
+        impl oxide_sql_core::schema::Table for Users {
+            type Row = ();
+            const NAME: &'static str = "users";
+            const COLUMNS: &'static [&'static str] = &["id"];
+            const PRIMARY_KEY: Option<&'static str> = Some("id");
+        }
+        struct Id;
+        impl Column for Id {
+            type Table = Users;
+            type Type = i64;
+            const NAME: &'static str = "id";
+            const NULLABLE: bool = false;
+            const PRIMARY_KEY: bool = true;
+        }
+
+        let sql = UpsertBuilder::new()
+            .into_table("users")
+            .values(vec![1_i64])
+            .on_any_conflict()
+            .do_nothing()
+            .returning_col(Id)
+            .build_sql();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users VALUES (?) ON CONFLICT DO NOTHING RETURNING id"
+        );
+    }
+
+    #[test]
+    fn test_upsert_multiple_rows() {
+        let (sql, params) = UpsertBuilder::new()
+            .into_table("users")
+            .columns(&["id", "name"])
+            .values(vec![1_i64.to_sql_value(), "Alice".to_sql_value()])
+            .and_values(vec![2_i64.to_sql_value(), "Bob".to_sql_value()])
+            .on_conflict("id", &[])
+            .do_update(&["name"])
+            .build();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES (?, ?), (?, ?) \
+             ON CONFLICT (id) DO UPDATE SET name = excluded.name"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn test_upsert_build_split() {
+        let rows: Vec<Vec<i64>> = (0..1000).map(|i| vec![i, i]).collect();
+        let queries = UpsertBuilder::new()
+            .into_table("counters")
+            .columns(&["id", "hits"])
+            .values_many(rows)
+            .on_conflict("id", &[])
+            .do_update(&["hits"])
+            .returning(&["id"])
+            .build_split(&SqliteDialect::with_version(3, 31, 0));
+
+        // 999 parameters fit 499 two-value rows.
+        assert_eq!(queries.len(), 3);
+        assert_eq!(queries[0].params().len(), 998);
+        assert_eq!(queries[2].params().len(), 4);
+        assert_eq!(
+            queries[2].sql(),
+            "INSERT INTO counters (id, hits) VALUES (?, ?), (?, ?) \
+             ON CONFLICT (id) DO UPDATE SET hits = excluded.hits RETURNING id"
+        );
+
+        let queries = UpsertBuilder::new()
+            .into_table("counters")
+            .values(vec![1_i64, 1])
+            .on_any_conflict()
+            .do_nothing()
+            .build_split(&DIALECT);
+        assert_eq!(queries.len(), 1);
+    }
+
     #[test]
     fn test_upsert_sql_injection_prevention() {
         let malicious = "'; DROP TABLE users; --";
@@ -252,7 +687,7 @@ mod tests {
             .into_table("users")
             .columns(&["id", "name"])
             .values(vec![1_i64.to_sql_value(), malicious.to_sql_value()])
-            .on_conflict(&["id"])
+            .on_conflict("id", &[])
             .do_update(&["name"])
             .build();
 
@@ -1,16 +1,125 @@
 //! SQLite dialect implementation.
 
+use oxide_sql_core::builder::func::call;
+use oxide_sql_core::builder::{ExprBuilder, SqlValue, is_valid_identifier};
 use oxide_sql_core::dialect::Dialect;
+use oxide_sql_core::lexer::LexerOptions;
+
+/// Keywords SQLite does not accept as unquoted identifiers, in upper case and
+/// sorted. SQLite lets most other keywords double as names.
+const SQLITE_RESERVED_KEYWORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "AS",
+    "AUTOINCREMENT",
+    "BETWEEN",
+    "CASE",
+    "CHECK",
+    "COLLATE",
+    "COMMIT",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DELETE",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "ESCAPE",
+    "EXCEPT",
+    "EXISTS",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GLOB",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "ISNULL",
+    "JOIN",
+    "LEFT",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NOTNULL",
+    "NULL",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RETURNING",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRANSACTION",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+];
 
 /// SQLite dialect.
-#[derive(Debug, Default, Clone, Copy)]
-pub struct SqliteDialect;
+///
+/// Feature flags such as [`supports_returning`](Dialect::supports_returning)
+/// depend on the targeted SQLite version, which defaults to 3.35.0, the
+/// first release with `RETURNING`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteDialect {
+    version: (u32, u32, u32),
+}
 
 impl SqliteDialect {
-    /// Creates a new SQLite dialect.
+    /// Creates a new SQLite dialect targeting SQLite 3.35.0 or later.
     #[must_use]
     pub const fn new() -> Self {
-        Self
+        Self::with_version(3, 35, 0)
+    }
+
+    /// Creates a SQLite dialect targeting the given SQLite version, as
+    /// reported by `sqlite_version()`.
+    #[must_use]
+    pub const fn with_version(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            version: (major, minor, patch),
+        }
+    }
+
+    /// Returns the targeted SQLite version as `(major, minor, patch)`.
+    #[must_use]
+    pub const fn version(&self) -> (u32, u32, u32) {
+        self.version
+    }
+
+    /// Returns whether the targeted version is at least `major.minor.patch`.
+    #[must_use]
+    pub fn is_at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        self.version >= (major, minor, patch)
+    }
+}
+
+impl Default for SqliteDialect {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -24,17 +133,68 @@ impl Dialect for SqliteDialect {
     }
 
     fn supports_returning(&self) -> bool {
-        true // SQLite 3.35.0+
+        self.is_at_least(3, 35, 0)
     }
 
     fn supports_upsert(&self) -> bool {
-        true // SQLite 3.24.0+
+        self.is_at_least(3, 24, 0)
+    }
+
+    fn max_parameters(&self) -> usize {
+        if self.is_at_least(3, 32, 0) {
+            32766
+        } else {
+            999
+        }
+    }
+
+    fn lexer_options(&self) -> LexerOptions {
+        LexerOptions::new().bracket_identifiers(true)
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        SQLITE_RESERVED_KEYWORDS
+    }
+
+    fn render_value(&self, value: &SqlValue) -> String {
+        match value {
+            // SQLite has no boolean storage class; TRUE/FALSE are aliases
+            // for 1/0 since 3.23.0, so emit the integers directly.
+            SqlValue::Bool(b) => String::from(if *b { "1" } else { "0" }),
+            // Everything else is stored as text, including decimals, which
+            // keeps their exact precision.
+            _ => value.clone().into_primitive().to_sql_inline(),
+        }
+    }
+
+    fn json_get(&self, target: ExprBuilder, key: &str) -> ExprBuilder {
+        // `->>` needs SQLite 3.38.0+; json_extract() works everywhere, with
+        // keys that are not plain names quoted in the path, escaping `"` and
+        // `\` as in a JSON string.
+        let path = if is_valid_identifier(key) {
+            format!("$.{key}")
+        } else {
+            let key = key.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("$.\"{key}\"")
+        };
+        call("json_extract", vec![target, ExprBuilder::value(path)])
+    }
+
+    fn temporal_value(&self, _type_name: &str, value: &str) -> ExprBuilder {
+        // SQLite stores dates and times as ISO-8601 text, which compares in
+        // order. A CAST would apply NUMERIC affinity and turn
+        // '2024-01-01' into 2024.
+        ExprBuilder::value(value)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oxide_sql_core::Parser;
+    use oxide_sql_core::ast::Expr;
+    use oxide_sql_core::builder::func::date_for;
+    use oxide_sql_core::builder::{DeleteDyn, InsertDyn, SelectDyn, UpdateDyn, dyn_col};
 
     #[test]
     fn test_sqlite_dialect() {
@@ -43,5 +203,122 @@ mod tests {
         assert_eq!(dialect.identifier_quote(), '"');
         assert!(dialect.supports_returning());
         assert!(dialect.supports_upsert());
+        assert_eq!(dialect.max_parameters(), 32766);
+    }
+
+    #[test]
+    fn test_sqlite_dialect_version_capabilities() {
+        assert_eq!(SqliteDialect::default(), SqliteDialect::new());
+        assert_eq!(SqliteDialect::new().version(), (3, 35, 0));
+
+        let old = SqliteDialect::with_version(3, 31, 1);
+        assert!(!old.supports_returning());
+        assert!(old.supports_upsert());
+        assert_eq!(old.max_parameters(), 999);
+
+        let ancient = SqliteDialect::with_version(3, 22, 0);
+        assert!(!ancient.supports_upsert());
+
+        let recent = SqliteDialect::with_version(3, 45, 1);
+        assert!(recent.supports_returning());
+        assert_eq!(recent.max_parameters(), 32766);
+    }
+
+    #[test]
+    fn test_sqlite_reserved_keywords() {
+        let dialect = SqliteDialect::new();
+        assert!(SQLITE_RESERVED_KEYWORDS.is_sorted());
+        assert!(dialect.is_reserved_keyword("glob"));
+        assert!(dialect.is_reserved_keyword("Returning"));
+        // Reserved in standard SQL, but a valid name in SQLite.
+        assert!(!dialect.is_reserved_keyword("user"));
+        assert_eq!(dialect.quote_identifier_if_needed("user"), "user");
+        assert_eq!(dialect.quote_identifier_if_needed("order"), "\"order\"");
+    }
+
+    #[test]
+    fn test_sqlite_dyn_builders_quote_sqlite_keywords() {
+        let dialect = SqliteDialect::new();
+        let select = || SelectDyn::new().columns(&["user", "glob"]).from("t");
+        assert_eq!(select().build().0, "SELECT \"user\", glob FROM t");
+        assert_eq!(
+            select().build_for(&dialect).0,
+            "SELECT user, \"glob\" FROM t"
+        );
+
+        let (sql, _) = InsertDyn::new()
+            .into_table("user")
+            .columns(&["glob"])
+            .values(vec![1])
+            .build_for(&dialect);
+        assert_eq!(sql, "INSERT INTO user (\"glob\") VALUES (?)");
+
+        let (sql, _) = UpdateDyn::new()
+            .table("user")
+            .set("glob", 1)
+            .build_for(&dialect);
+        assert_eq!(sql, "UPDATE user SET \"glob\" = ?");
+
+        let (sql, _) = DeleteDyn::new().from("user").build_for(&dialect);
+        assert_eq!(sql, "DELETE FROM user");
+    }
+
+    #[test]
+    fn test_sqlite_parses_bracket_identifiers() {
+        let stmt = Parser::for_dialect("SELECT [a b] FROM [t]", &SqliteDialect::new())
+            .parse_statement()
+            .unwrap();
+        assert_eq!(stmt.to_string(), "SELECT [a b] FROM [t]");
+    }
+
+    #[test]
+    fn test_sqlite_render_value() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(dialect.render_value(&SqlValue::Bool(true)), "1");
+        assert_eq!(dialect.render_value(&SqlValue::Bool(false)), "0");
+        assert_eq!(dialect.render_value(&SqlValue::Int(7)), "7");
+        assert_eq!(
+            dialect.render_value(&SqlValue::Text(String::from("a'b"))),
+            "'a''b'"
+        );
+    }
+
+    #[test]
+    fn test_sqlite_temporal_values_are_plain_text() {
+        let dialect = SqliteDialect::new();
+        let (sql, params) = UpdateDyn::new()
+            .table("tasks")
+            .set_expr("due", date_for("2024-01-01", &dialect))
+            .build();
+        assert_eq!(sql, "UPDATE tasks SET due = ?");
+        assert_eq!(params, vec![SqlValue::Text(String::from("2024-01-01"))]);
+
+        let ast = Expr::column("created_at").lt(Expr::timestamp("2024-02-01 00:00:00"));
+        assert_eq!(
+            ExprBuilder::from_ast_for(&ast, &dialect).sql(),
+            "created_at < ?"
+        );
+    }
+
+    #[test]
+    fn test_sqlite_json_get_uses_json_extract() {
+        let dialect = SqliteDialect::new();
+        let (sql, params) = dyn_col("data")
+            .json_get_for("name", &dialect)
+            .eq("Ada")
+            .build();
+        assert_eq!(sql, "json_extract(data, ?) = ?");
+        assert_eq!(params[0], SqlValue::Text(String::from("$.name")));
+
+        let (_, params) = dyn_col("data").json_get_for("first name", &dialect).build();
+        assert_eq!(params[0], SqlValue::Text(String::from("$.\"first name\"")));
+
+        let (_, params) = dyn_col("data")
+            .json_get_for(r#"say "hi"\now"#, &dialect)
+            .build();
+        assert_eq!(
+            params[0],
+            SqlValue::Text(String::from(r#"$."say \"hi\"\\now""#))
+        );
     }
 }
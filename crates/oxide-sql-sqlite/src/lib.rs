@@ -10,7 +10,12 @@
 //!   crate provides [`UpsertBuilder`] for type-safe upsert
 //!   construction.
 //! - **[RETURNING]**: SQLite supports `RETURNING` clauses on
-//!   INSERT, UPDATE, and DELETE (since SQLite 3.35.0).
+//!   INSERT, UPDATE, and DELETE (since SQLite 3.35.0), including
+//!   upserts via [`UpsertBuilder::returning`]. Use
+//!   [`SqliteDialect::with_version`] to target an older SQLite.
+//! - **[JSON1]**: JSON is stored as text and queried with functions
+//!   such as `json_extract()` and the table-valued `json_each()`; see
+//!   [`builder::json`].
 //! - **Identifier quoting**: SQLite uses double quotes (`"`) as
 //!   the standard quoting style, though it also accepts backticks
 //!   and square brackets. See [SQLite keywords].
@@ -27,6 +32,7 @@
 //!
 //! [UPSERT]: https://www.sqlite.org/lang_upsert.html
 //! [RETURNING]: https://www.sqlite.org/lang_returning.html
+//! [JSON1]: https://www.sqlite.org/json1.html
 //! [SQLite keywords]: https://www.sqlite.org/lang_keywords.html
 //! [Type affinity]: https://www.sqlite.org/datatype3.html
 //! [`STRICT` tables]: https://www.sqlite.org/stricttables.html
@@ -48,7 +54,7 @@
 //!         "Alice".to_sql_value(),
 //!         "alice@example.com".to_sql_value(),
 //!     ])
-//!     .on_conflict(&["id"])
+//!     .on_conflict("id", &[])
 //!     .do_update(&["name", "email"])
 //!     .build();
 //! ```
@@ -0,0 +1,131 @@
+//! End-to-end tests running the SQLite builders against an in-memory
+//! SQLite database.
+
+use rusqlite::Connection;
+use rusqlite::types::Value;
+
+use oxide_sql_core::ast::Expr;
+use oxide_sql_core::builder::func::date_for;
+use oxide_sql_core::builder::value::{DriverValue, PrimitiveValue, SqlValue};
+use oxide_sql_core::builder::{ExprBuilder, SelectDyn, dyn_col};
+use oxide_sql_sqlite::SqliteDialect;
+
+// ------------------------------------------------------------------
+// Helpers
+// ------------------------------------------------------------------
+
+/// A SQLite parameter converted from a `SqlValue`.
+struct SqliteParam(Value);
+
+impl DriverValue for SqliteParam {
+    fn from_primitive(value: PrimitiveValue) -> Self {
+        Self(match value {
+            PrimitiveValue::Null => Value::Null,
+            PrimitiveValue::Bool(b) => Value::Integer(i64::from(b)),
+            PrimitiveValue::Int(i) => Value::Integer(i),
+            PrimitiveValue::Float(f) => Value::Real(f),
+            PrimitiveValue::Text(s) => Value::Text(s),
+            PrimitiveValue::Blob(b) => Value::Blob(b),
+        })
+    }
+}
+
+/// Runs a parameterised query and returns the first column as `i64`.
+fn query_ids(conn: &Connection, sql: &str, params: Vec<SqlValue>) -> Vec<i64> {
+    let params: Vec<Value> = SqliteParam::from_sql_values(params)
+        .into_iter()
+        .map(|param| param.0)
+        .collect();
+    let mut stmt = conn.prepare(sql).unwrap();
+    stmt.query_map(rusqlite::params_from_iter(params), |row| row.get(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+/// Creates and fills `events (id, created_at)`, with dates stored as
+/// ISO-8601 text.
+fn events(conn: &Connection) {
+    conn.execute_batch(
+        "CREATE TABLE events (id INTEGER PRIMARY KEY, created_at DATE);
+         INSERT INTO events VALUES
+             (1, '2023-12-31'), (2, '2024-01-01'), (3, '2024-01-15'), (4, '2024-02-01');",
+    )
+    .unwrap();
+}
+
+// ------------------------------------------------------------------
+// Tests
+// ------------------------------------------------------------------
+
+#[test]
+fn test_date_filter() {
+    let conn = Connection::open_in_memory().unwrap();
+    events(&conn);
+    let dialect = SqliteDialect::new();
+
+    let (sql, params) = SelectDyn::new()
+        .columns(&["id"])
+        .from("events")
+        .where_clause(dyn_col("created_at").eq_expr(date_for("2024-01-15", &dialect)))
+        .build();
+
+    assert_eq!(sql, "SELECT id FROM events WHERE created_at = ?");
+    assert_eq!(query_ids(&conn, &sql, params), [3]);
+}
+
+#[test]
+fn test_date_filter_from_ast() {
+    let conn = Connection::open_in_memory().unwrap();
+    events(&conn);
+
+    let ast = Expr::column("created_at").gt(Expr::date("2024-01-01"));
+    let (sql, params) = SelectDyn::new()
+        .columns(&["id"])
+        .from("events")
+        .where_clause(ExprBuilder::from_ast_for(&ast, &SqliteDialect::new()))
+        .order_by(&["id"])
+        .build();
+
+    assert_eq!(query_ids(&conn, &sql, params), [3, 4]);
+}
+
+#[test]
+fn test_cast_to_date_breaks_sqlite_filters() {
+    // The generic rendering applies NUMERIC affinity on SQLite, turning
+    // '2024-01-01' into 2024, which sorts before every stored date text.
+    let conn = Connection::open_in_memory().unwrap();
+    events(&conn);
+
+    let ast = Expr::column("created_at").gt(Expr::date("2024-01-01"));
+    let (sql, params) = SelectDyn::new()
+        .columns(&["id"])
+        .from("events")
+        .where_clause(ExprBuilder::from(&ast))
+        .order_by(&["id"])
+        .build();
+
+    assert_eq!(query_ids(&conn, &sql, params), [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_json_get_escapes_keys() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        r#"CREATE TABLE docs (id INTEGER PRIMARY KEY, data TEXT);
+           INSERT INTO docs VALUES
+               (1, '{"say \"hi\"": 1, "a\\b": 2}'),
+               (2, '{"say \"hi\"": 2, "a\\b": 1}');"#,
+    )
+    .unwrap();
+    let dialect = SqliteDialect::new();
+
+    for (key, ids) in [(r#"say "hi""#, [1]), (r"a\b", [2])] {
+        let (sql, params) = SelectDyn::new()
+            .columns(&["id"])
+            .from("docs")
+            .where_clause(dyn_col("data").json_get_for(key, &dialect).eq(1))
+            .build();
+        assert_eq!(query_ids(&conn, &sql, params), ids);
+    }
+}
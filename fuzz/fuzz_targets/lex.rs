@@ -0,0 +1,18 @@
+//! Lexes arbitrary input to the end, checking the lexer always terminates.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use oxide_sql_core::{Lexer, TokenKind};
+
+fuzz_target!(|data: &[u8]| {
+    let sql = String::from_utf8_lossy(data);
+    let mut lexer = Lexer::new(&sql);
+    // Every token consumes at least one character, plus the final EOF.
+    for _ in 0..=sql.len() {
+        if lexer.next_token().kind == TokenKind::Eof {
+            return;
+        }
+    }
+    panic!("lexer did not reach EOF for {sql:?}");
+});
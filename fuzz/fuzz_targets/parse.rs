@@ -0,0 +1,22 @@
+//! Parses arbitrary input and checks that anything that parses reaches a
+//! parse → print → parse fixed point.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use oxide_sql_core::Parser;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(sql) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(first) = Parser::new(sql).parse_statement() else {
+        return;
+    };
+
+    let printed = first.to_string();
+    let second = Parser::new(&printed)
+        .parse_statement()
+        .unwrap_or_else(|e| panic!("reprint of {sql:?} failed: {printed}\n{e:?}"));
+    assert_eq!(printed, second.to_string(), "not a fixed point: {sql:?}");
+});